@@ -0,0 +1,57 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+#[macro_use]
+extern crate arbitrary;
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    beta: f64,
+    teams: Vec<Vec<(f64, f64)>>,
+    ranks: Vec<usize>,
+    outcome: u8,
+}
+
+fuzz_target!(|input: Input| {
+    if !input.beta.is_finite() {
+        return;
+    }
+
+    let rater = Rater::new(input.beta);
+    let teams: Vec<Vec<Rating>> = input
+        .teams
+        .iter()
+        .map(|team| {
+            team.iter()
+                .filter(|&&(mu, sigma)| mu.is_finite() && sigma.is_finite())
+                .map(|&(mu, sigma)| Rating::new(mu, sigma))
+                .collect()
+        })
+        .collect();
+
+    if let Ok(result) = rater.update_ratings(teams.clone(), input.ranks) {
+        for team in result.iter() {
+            for player in team.iter() {
+                assert!(player.mu().is_finite(), "{:?}", player);
+                assert!(player.sigma().is_finite(), "{:?}", player);
+            }
+        }
+    }
+
+    let mut players = teams.into_iter().flat_map(|team| team.into_iter());
+    if let (Some(p1), Some(p2)) = (players.next(), players.next()) {
+        let outcome = match input.outcome % 3 {
+            0 => Outcome::Win,
+            1 => Outcome::Loss,
+            _ => Outcome::Draw,
+        };
+
+        let (p1, p2) = rater.duel(p1, p2, outcome);
+
+        assert!(p1.mu().is_finite() && p1.sigma().is_finite(), "{:?}", p1);
+        assert!(p2.mu().is_finite() && p2.sigma().is_finite(), "{:?}", p2);
+    }
+});
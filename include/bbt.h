@@ -0,0 +1,46 @@
+/*
+ * C interface to bbt, built with the `ffi` feature. See the documentation of
+ * the `bbt::ffi` module for details.
+ */
+
+#ifndef BBT_H
+#define BBT_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define BBT_OK 0
+#define BBT_MISMATCHED_LENGTHS 1
+#define BBT_EMPTY_TEAM 2
+#define BBT_NOT_ENOUGH_TEAMS 3
+#define BBT_INVALID_RATING 4
+#define BBT_TEAM_OVERFLOW 5
+#define BBT_INVALID_RANKS 6
+#define BBT_NUMERICAL_ERROR 7
+#define BBT_INVALID_PARAMETER 8
+#define BBT_NULL_POINTER (-1)
+#define BBT_PANIC (-2)
+#define BBT_INVALID_ARGUMENT (-3)
+
+typedef struct bbt_rater bbt_rater;
+
+/* Returns NULL if beta is negative or not finite. */
+bbt_rater *bbt_rater_new(double beta);
+void bbt_rater_free(bbt_rater *rater);
+
+/* outcome: 1 if the first player won, -1 if they lost, 0 for a draw. */
+int bbt_duel(const bbt_rater *rater, double *mu1, double *sigma1, double *mu2,
+             double *sigma2, int outcome);
+
+int bbt_update_ratings(const bbt_rater *rater, double *mus, double *sigmas,
+                       size_t n_players, const size_t *team_offsets,
+                       size_t n_teams, const size_t *ranks);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
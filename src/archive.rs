@@ -0,0 +1,25 @@
+use {ArchivedRater, ArchivedRating};
+
+impl ArchivedRating<f64> {
+    /// Returns the estimated skill of the player.
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Returns the variance on the estimate of the player's skill.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Returns the conservative skill estimate `mu - 3 * sigma`.
+    pub fn conservative_estimate(&self) -> f64 {
+        self.mu - 3.0 * self.sigma
+    }
+}
+
+impl ArchivedRater<f64> {
+    /// Returns the β-parameter of the rater.
+    pub fn beta(&self) -> f64 {
+        self.beta_sq.sqrt()
+    }
+}
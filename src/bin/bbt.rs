@@ -0,0 +1,413 @@
+//! A command-line interface for quick experiments, enabled by the `cli`
+//! feature. Run `bbt help` for the commands.
+//!
+//! The exit code is 0 on success, 1 if an input file cannot be read or
+//! contains an invalid or rejected game, and 2 if the command line is
+//! malformed.
+
+extern crate bbt;
+extern crate csv;
+extern crate serde_json;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use bbt::eval;
+use bbt::replay::{self, GameRecord, ReplayError};
+use bbt::{Rater, Rating};
+
+const USAGE: &str = "\
+Usage:
+  bbt replay <games> [--beta <beta>] [--out <ratings.csv>]
+  bbt predict <mu1> <sigma1> <mu2> <sigma2> [--beta <beta>]
+  bbt fit-beta <games> [--min <beta>] [--max <beta>] [--steps <n>]
+  bbt help
+
+Commands:
+  replay    Rates a history of games and writes the final rating table, with
+            the columns id, mu, sigma and conservative.
+  predict   Prints the probability that the first player beats the second.
+  fit-beta  Evaluates the betas of a grid on a history of games and prints
+            the one whose predictions have the lowest log loss.
+
+Games are read from CSV files with one row per player and the columns game,
+team, player and rank, where the rows of a game are consecutive, or from JSON
+Lines files (.jsonl) with one game per line, such as
+  {\"teams\": [[\"alice\", \"bob\"], [\"carol\"]], \"ranks\": [1, 2]}
+Use --format csv or --format jsonl to override the file extension.
+
+The default beta is 25/6. fit-beta searches from 0.5 to 10 in 20 steps by
+default.
+
+Exit codes: 0 on success, 1 for unreadable or invalid input files and 2 for
+malformed command lines.";
+
+/// Error is the reason a command failed, which decides the exit code.
+enum Error {
+    /// The command line is malformed.
+    Usage(String),
+
+    /// An input file could not be read or contains an invalid game.
+    Data(String),
+}
+
+impl Error {
+    fn exit_code(&self) -> i32 {
+        match *self {
+            Error::Data(_) => 1,
+            Error::Usage(_) => 2,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Usage(ref message) => write!(f, "{}\n\n{}", message, USAGE),
+            Error::Data(ref message) => f.write_str(message),
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let Err(error) = run(&args) {
+        eprintln!("bbt: {}", error);
+        process::exit(error.exit_code());
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Error> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err(Error::Usage("missing command".to_string())),
+    };
+
+    match command {
+        "replay" => replay_command(&Options::parse(args, &["beta", "out", "format"])?),
+        "predict" => predict_command(&Options::parse(args, &["beta"])?),
+        "fit-beta" => fit_beta_command(&Options::parse(args, &["min", "max", "steps", "format"])?),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(Error::Usage(format!("unknown command `{}`", command))),
+    }
+}
+
+/// Options are the positional arguments and `--name value` flags of a
+/// command.
+struct Options {
+    positional: Vec<String>,
+    flags: HashMap<String, String>,
+}
+
+impl Options {
+    /// Splits the arguments of a command, which may only use the given
+    /// flags.
+    fn parse(args: &[String], allowed: &[&str]) -> Result<Options, Error> {
+        let mut positional = Vec::new();
+        let mut flags = HashMap::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                positional.push(arg.clone());
+                continue;
+            }
+
+            let name = &arg[2..];
+            if !allowed.contains(&name) {
+                return Err(Error::Usage(format!("unknown option `{}`", arg)));
+            }
+
+            let value = args
+                .next()
+                .ok_or_else(|| Error::Usage(format!("missing value for `{}`", arg)))?;
+            if flags.insert(name.to_string(), value.clone()).is_some() {
+                return Err(Error::Usage(format!("`{}` is given more than once", arg)));
+            }
+        }
+
+        Ok(Options { positional, flags })
+    }
+
+    /// Returns the positional arguments, which must be `count` many.
+    fn positional(&self, count: usize, names: &str) -> Result<&[String], Error> {
+        if self.positional.len() == count {
+            Ok(&self.positional)
+        } else {
+            Err(Error::Usage(format!(
+                "expected {}, found {} arguments",
+                names,
+                self.positional.len()
+            )))
+        }
+    }
+
+    /// Parses the value of a flag, or returns `default` if it is not given.
+    fn number<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, Error> {
+        match self.flags.get(name) {
+            Some(value) => parse_number(value, &format!("--{}", name)),
+            None => Ok(default),
+        }
+    }
+
+    /// Returns the rater with the β-parameter given by `--beta`.
+    fn rater(&self) -> Result<Rater, Error> {
+        let beta: f64 = self.number("beta", 25.0 / 6.0)?;
+        if !(beta.is_finite() && beta > 0.0) {
+            return Err(Error::Usage("`--beta` must be positive".to_string()));
+        }
+
+        Ok(Rater::new(beta))
+    }
+
+    /// Reads the games of the file given as the only positional argument.
+    fn games(&self) -> Result<Games, Error> {
+        let path = &self.positional(1, "a games file")?[0];
+
+        let format = match self.flags.get("format") {
+            Some(format) => format.as_str(),
+            None if path.ends_with(".jsonl") => "jsonl",
+            None => "csv",
+        };
+
+        match format {
+            "csv" => read_csv_games(path),
+            "jsonl" => read_jsonl_games(path),
+            _ => Err(Error::Usage(format!("unknown format `{}`", format))),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::Usage(format!("`{}` is not a valid number for {}", value, name)))
+}
+
+/// Games are the records of a games file, with the file name and the line of
+/// every record, for error messages.
+struct Games {
+    path: String,
+    records: Vec<GameRecord>,
+    lines: Vec<u64>,
+}
+
+impl Games {
+    /// Describes an error of replaying the records.
+    fn error(&self, error: ReplayError) -> Error {
+        match error.index() {
+            Some(index) => Error::Data(format!("{}:{}: {}", self.path, self.lines[index], error)),
+            None => Error::Data(format!("{}: {}", self.path, error)),
+        }
+    }
+}
+
+fn read_file(path: &str) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|error| Error::Data(format!("{}: {}", path, error)))
+}
+
+/// Reads a CSV file with one row per player.
+fn read_csv_games(path: &str) -> Result<Games, Error> {
+    // The csv crate miscounts the lines of files with CRLF line endings.
+    let text = read_file(path)?.replace("\r\n", "\n");
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+
+    let csv_error = |error: csv::Error| {
+        let line = error.position().map_or(1, |position| position.line());
+        Error::Data(format!("{}:{}: {}", path, line, error))
+    };
+
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or_else(|| Error::Data(format!("{}:1: missing column `{}`", path, name)))
+    };
+    let (game_column, team_column) = (column("game")?, column("team")?);
+    let (player_column, rank_column) = (column("player")?, column("rank")?);
+
+    let mut games = Games {
+        path: path.to_string(),
+        records: Vec::new(),
+        lines: Vec::new(),
+    };
+    let mut current: Option<String> = None;
+    let mut team_names: Vec<String> = Vec::new();
+
+    for row in reader.records() {
+        let row = row.map_err(csv_error)?;
+        let line = row.position().map_or(1, |position| position.line());
+        let data_error = |message: String| Error::Data(format!("{}:{}: {}", path, line, message));
+
+        let game = row[game_column].trim();
+        let team = row[team_column].trim();
+        let rank: usize = row[rank_column]
+            .trim()
+            .parse()
+            .map_err(|_| data_error(format!("`{}` is not a rank", &row[rank_column])))?;
+
+        if current.as_deref() != Some(game) {
+            current = Some(game.to_string());
+            team_names.clear();
+            games.records.push(GameRecord::new(Vec::new(), Vec::new()));
+            games.lines.push(line);
+        }
+
+        let record = games.records.last_mut().unwrap();
+        let index = match team_names.iter().position(|name| name == team) {
+            Some(index) => index,
+            None => {
+                team_names.push(team.to_string());
+                record.teams.push(Vec::new());
+                record.ranks.push(rank);
+                team_names.len() - 1
+            }
+        };
+
+        if record.ranks[index] != rank {
+            return Err(data_error(format!(
+                "team `{}` of game `{}` has more than one rank",
+                team, game
+            )));
+        }
+
+        record.teams[index].push(row[player_column].trim().to_string());
+    }
+
+    Ok(games)
+}
+
+/// Reads a JSON Lines file with one game per line.
+fn read_jsonl_games(path: &str) -> Result<Games, Error> {
+    let text = read_file(path)?;
+
+    let mut games = Games {
+        path: path.to_string(),
+        records: Vec::new(),
+        lines: Vec::new(),
+    };
+
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: GameRecord = serde_json::from_str(line).map_err(|error| {
+            Error::Data(format!(
+                "{}:{}: invalid game at column {}",
+                path,
+                index + 1,
+                error.column()
+            ))
+        })?;
+
+        games.records.push(record);
+        games.lines.push(index as u64 + 1);
+    }
+
+    Ok(games)
+}
+
+fn replay_command(options: &Options) -> Result<(), Error> {
+    let rater = options.rater()?;
+    let games = options.games()?;
+
+    let ratings = replay::replay(&rater, &games.records, Rating::default())
+        .map_err(|error| games.error(error))?;
+
+    let mut table: Vec<(&String, &Rating)> = ratings.iter().collect();
+    table.sort_by(|a, b| a.0.cmp(b.0));
+
+    match options.flags.get("out") {
+        Some(path) => File::create(Path::new(path))
+            .map_err(|error| Error::Data(format!("{}: {}", path, error)))
+            .and_then(|file| write_table(file, table)),
+        None => write_table(io::stdout().lock(), table),
+    }
+}
+
+fn write_table<W: Write>(w: W, table: Vec<(&String, &Rating)>) -> Result<(), Error> {
+    bbt::io::write_csv(w, table).map_err(|error| Error::Data(error.to_string()))
+}
+
+fn predict_command(options: &Options) -> Result<(), Error> {
+    let rater = options.rater()?;
+    let args = options.positional(4, "<mu1> <sigma1> <mu2> <sigma2>")?;
+
+    let mut numbers = [0.0; 4];
+    for (number, (arg, name)) in numbers
+        .iter_mut()
+        .zip(args.iter().zip(&["mu1", "sigma1", "mu2", "sigma2"]))
+    {
+        *number = parse_number(arg, name)?;
+    }
+
+    let rating = |mu: f64, sigma: f64, name: &str| {
+        Rating::try_new(mu, sigma)
+            .map_err(|_| Error::Usage(format!("{} is not a valid rating", name)))
+    };
+    let p1 = rating(numbers[0], numbers[1], "the first player")?;
+    let p2 = rating(numbers[2], numbers[3], "the second player")?;
+
+    println!("{}", rater.win_probability(&p1, &p2));
+
+    Ok(())
+}
+
+fn fit_beta_command(options: &Options) -> Result<(), Error> {
+    let min: f64 = options.number("min", 0.5)?;
+    let max: f64 = options.number("max", 10.0)?;
+    let steps: usize = options.number("steps", 20)?;
+
+    if !(min.is_finite() && min > 0.0 && max.is_finite() && max > min) {
+        return Err(Error::Usage(
+            "`--min` and `--max` must be positive, with `--min` below `--max`".to_string(),
+        ));
+    }
+    if steps < 2 {
+        return Err(Error::Usage("`--steps` must be at least 2".to_string()));
+    }
+
+    let games = options.games()?;
+
+    // The number of predictions does not depend on beta, so the log loss is
+    // NaN for every beta if it is for one.
+    let mut best = (f64::NAN, f64::INFINITY);
+    let mut table = Vec::with_capacity(steps);
+
+    for step in 0..steps {
+        let beta = min + (max - min) * step as f64 / (steps - 1) as f64;
+
+        let report = eval::prequential(&Rater::new(beta), &games.records, Rating::default())
+            .map_err(|error| games.error(error))?;
+        let log_loss = report.metrics.log_loss;
+
+        if log_loss.is_nan() {
+            return Err(Error::Data(format!(
+                "{}: the games have no results to predict",
+                games.path
+            )));
+        }
+        if log_loss < best.1 {
+            best = (beta, log_loss);
+        }
+        table.push((beta, log_loss));
+    }
+
+    println!("{:>10} {:>10}", "beta", "log loss");
+    for (beta, log_loss) in table {
+        println!("{:>10.4} {:>10.6}", beta, log_loss);
+    }
+    println!("best beta: {:.4}", best.0);
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use Float;
+use Rating;
+
+/// RatingsBuffer stores many ratings column by column instead of as a slice
+/// of `Rating`s. Operations over a whole population, such as decaying every
+/// rating or computing conservative skill estimates, then only touch the
+/// columns they need and can be vectorized by the compiler.
+///
+/// Besides mu and sigma, the buffer keeps the variance of every rating, so
+/// that converting ratings into a buffer and back is exact.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RatingsBuffer<F: Float = f64> {
+    mu: Vec<F>,
+    sigma: Vec<F>,
+    sigma_sq: Vec<F>,
+}
+
+impl<F: Float> RatingsBuffer<F> {
+    /// Creates an empty buffer.
+    pub fn new() -> RatingsBuffer<F> {
+        RatingsBuffer {
+            mu: Vec::new(),
+            sigma: Vec::new(),
+            sigma_sq: Vec::new(),
+        }
+    }
+
+    /// Creates an empty buffer with room for `capacity` ratings.
+    pub fn with_capacity(capacity: usize) -> RatingsBuffer<F> {
+        RatingsBuffer {
+            mu: Vec::with_capacity(capacity),
+            sigma: Vec::with_capacity(capacity),
+            sigma_sq: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of ratings in the buffer.
+    pub fn len(&self) -> usize {
+        self.mu.len()
+    }
+
+    /// Returns whether the buffer holds no ratings.
+    pub fn is_empty(&self) -> bool {
+        self.mu.is_empty()
+    }
+
+    /// Appends a rating to the end of the buffer.
+    pub fn push(&mut self, rating: Rating<F>) {
+        self.mu.push(rating.mu);
+        self.sigma.push(rating.sigma);
+        self.sigma_sq.push(rating.sigma_sq);
+    }
+
+    /// Returns the rating at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Rating<F>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(Rating {
+            mu: self.mu[index],
+            sigma: self.sigma[index],
+            sigma_sq: self.sigma_sq[index],
+        })
+    }
+
+    /// Replaces the rating at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, rating: Rating<F>) {
+        assert!(index < self.len(), "index out of bounds");
+
+        self.mu[index] = rating.mu;
+        self.sigma[index] = rating.sigma;
+        self.sigma_sq[index] = rating.sigma_sq;
+    }
+
+    /// Returns the skill estimates (mu) of all ratings.
+    pub fn mus(&self) -> &[F] {
+        &self.mu
+    }
+
+    /// Returns the uncertainties (sigma) of all ratings.
+    pub fn sigmas(&self) -> &[F] {
+        &self.sigma
+    }
+
+    /// Returns an iterator over the ratings in the buffer.
+    pub fn iter(&self) -> RatingsIter<'_, F> {
+        RatingsIter {
+            buffer: self,
+            index: 0,
+        }
+    }
+
+    /// Copies the ratings into a `Vec`.
+    pub fn to_vec(&self) -> Vec<Rating<F>> {
+        self.iter().collect()
+    }
+
+    /// Increases the variance of every rating by `tau²`, which models the
+    /// uncertainty that accumulates while a player is not playing.
+    pub fn decay_all(&mut self, tau: F) {
+        let tau_sq = tau * tau;
+
+        for (sigma, sigma_sq) in self.sigma.iter_mut().zip(self.sigma_sq.iter_mut()) {
+            *sigma_sq += tau_sq;
+            *sigma = sigma_sq.sqrt();
+        }
+    }
+
+    /// Writes the conservative skill estimate `mu - 3 * sigma` of every
+    /// rating into `out`. This is the value that ratings are ordered by.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` does not have the same length as the buffer.
+    pub fn conservative_estimates(&self, out: &mut [F]) {
+        assert_eq!(out.len(), self.len(), "output length mismatch");
+
+        let three = F::from_f64(3.0);
+
+        for ((out, &mu), &sigma) in out.iter_mut().zip(&self.mu).zip(&self.sigma) {
+            *out = mu - three * sigma;
+        }
+    }
+}
+
+impl<'a, F: Float> From<&'a [Rating<F>]> for RatingsBuffer<F> {
+    fn from(ratings: &'a [Rating<F>]) -> RatingsBuffer<F> {
+        let mut buffer = RatingsBuffer::with_capacity(ratings.len());
+
+        for rating in ratings {
+            buffer.push(*rating);
+        }
+
+        buffer
+    }
+}
+
+impl<'a, F: Float> IntoIterator for &'a RatingsBuffer<F> {
+    type Item = Rating<F>;
+    type IntoIter = RatingsIter<'a, F>;
+
+    fn into_iter(self) -> RatingsIter<'a, F> {
+        self.iter()
+    }
+}
+
+/// An iterator over the ratings in a `RatingsBuffer`, created by
+/// `RatingsBuffer::iter`.
+#[derive(Clone, Debug)]
+pub struct RatingsIter<'a, F: Float + 'a = f64> {
+    buffer: &'a RatingsBuffer<F>,
+    index: usize,
+}
+
+impl<'a, F: Float> Iterator for RatingsIter<'a, F> {
+    type Item = Rating<F>;
+
+    fn next(&mut self) -> Option<Rating<F>> {
+        let rating = self.buffer.get(self.index)?;
+        self.index += 1;
+        Some(rating)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.buffer.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, F: Float> ExactSizeIterator for RatingsIter<'a, F> {}
@@ -0,0 +1,155 @@
+//! Column-wise ratings for data-science workflows.
+//!
+//! The functions of this module take the mus and sigmas of many ratings as
+//! separate columns, as they are stored in data frames, and compute the same
+//! values as the scalar methods of `Rating` and `Rater`, element for element.
+//! Mismatched columns are reported as errors.
+//!
+//! ```rust
+//! extern crate bbt;
+//! extern crate ndarray;
+//!
+//! use ndarray::array;
+//!
+//! let mus = array![30.0, 25.0];
+//! let sigmas = array![2.0, 5.0];
+//!
+//! let conservative = bbt::bulk::conservative(mus.view(), sigmas.view()).unwrap();
+//! assert_eq!(conservative, array![24.0, 10.0]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use ndarray::{Array1, Array2, ArrayView1, Zip};
+
+use {Outcome, Rater, Rating};
+
+/// BulkError describes why the columns given to a bulk function could not be
+/// processed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BulkError {
+    /// The column `argument` has `found` elements, but the first column has
+    /// `expected`.
+    MismatchedLength {
+        argument: &'static str,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for BulkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BulkError::MismatchedLength {
+                argument,
+                expected,
+                found,
+            } => write!(
+                f,
+                "The column `{}` has {} elements instead of {}",
+                argument, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for BulkError {}
+
+/// Checks that the column `argument` has `expected` elements.
+fn check_len<T>(
+    argument: &'static str,
+    column: &ArrayView1<T>,
+    expected: usize,
+) -> Result<(), BulkError> {
+    if column.len() == expected {
+        Ok(())
+    } else {
+        Err(BulkError::MismatchedLength {
+            argument,
+            expected,
+            found: column.len(),
+        })
+    }
+}
+
+/// Returns the matrix of `Rater::win_probability` for every pair of the given
+/// ratings, where element `[i, j]` is the probability that rating `i` beats
+/// rating `j`.
+///
+/// Returns `BulkError::MismatchedLength` if the columns differ in length.
+pub fn win_probability_matrix(
+    rater: &Rater,
+    mus: ArrayView1<f64>,
+    sigmas: ArrayView1<f64>,
+) -> Result<Array2<f64>, BulkError> {
+    check_len("sigmas", &sigmas, mus.len())?;
+
+    let ratings: Vec<Rating> = mus
+        .iter()
+        .zip(&sigmas)
+        .map(|(&mu, &sigma)| Rating::new(mu, sigma))
+        .collect();
+
+    Ok(Array2::from_shape_fn(
+        (ratings.len(), ratings.len()),
+        |(i, j)| rater.win_probability(&ratings[i], &ratings[j]),
+    ))
+}
+
+/// Returns the conservative skill estimates `mu - 3 * sigma` of the given
+/// ratings.
+///
+/// Returns `BulkError::MismatchedLength` if the columns differ in length.
+pub fn conservative(
+    mus: ArrayView1<f64>,
+    sigmas: ArrayView1<f64>,
+) -> Result<Array1<f64>, BulkError> {
+    check_len("sigmas", &sigmas, mus.len())?;
+
+    Ok(Zip::from(&mus)
+        .and(&sigmas)
+        .map_collect(|&mu, &sigma| mu - 3.0 * sigma))
+}
+
+/// Rates one duel per row with `Rater::duel`, between the player given by
+/// `mus` and `sigmas` and the opponent given by `opponents_mu` and
+/// `opponents_sigma`, with the outcome from the player's perspective.
+/// Returns the new mus and sigmas of the players.
+///
+/// The duels are independent of each other, as if every row was a different
+/// pair of players. The new ratings of the opponents are those of the duels
+/// with the columns swapped and the outcomes reversed.
+///
+/// Returns `BulkError::MismatchedLength` if the columns differ in length.
+pub fn duel_updates(
+    rater: &Rater,
+    mus: ArrayView1<f64>,
+    sigmas: ArrayView1<f64>,
+    opponents_mu: ArrayView1<f64>,
+    opponents_sigma: ArrayView1<f64>,
+    outcomes: ArrayView1<Outcome>,
+) -> Result<(Array1<f64>, Array1<f64>), BulkError> {
+    let n = mus.len();
+    check_len("sigmas", &sigmas, n)?;
+    check_len("opponents_mu", &opponents_mu, n)?;
+    check_len("opponents_sigma", &opponents_sigma, n)?;
+    check_len("outcomes", &outcomes, n)?;
+
+    let mut new_mus = Array1::zeros(n);
+    let mut new_sigmas = Array1::zeros(n);
+
+    for i in 0..n {
+        let (player, _) = rater.duel(
+            Rating::new(mus[i], sigmas[i]),
+            Rating::new(opponents_mu[i], opponents_sigma[i]),
+            outcomes[i],
+        );
+
+        new_mus[i] = player.mu();
+        new_sigmas[i] = player.sigma();
+    }
+
+    Ok((new_mus, new_sigmas))
+}
@@ -0,0 +1,209 @@
+use std::ops::RangeInclusive;
+
+use {BBTError, Rater, Rating};
+
+impl Rating {
+    /// Encodes the rating as 16 bytes: mu followed by sigma, each as an IEEE
+    /// 754 double in little-endian byte order. This is a stable wire format
+    /// that is the same on every platform and will not change between
+    /// versions.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.mu.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.sigma.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a rating encoded by `Rating::to_bytes`.
+    ///
+    /// Returns `BBTError::InvalidParameter` if the rating is rejected by
+    /// `Rating::try_new`.
+    pub fn from_bytes(bytes: &[u8; 16]) -> Result<Rating, BBTError> {
+        let mut mu = [0; 8];
+        let mut sigma = [0; 8];
+        mu.copy_from_slice(&bytes[..8]);
+        sigma.copy_from_slice(&bytes[8..]);
+
+        let mu = f64::from_le_bytes(mu);
+        let sigma = f64::from_le_bytes(sigma);
+
+        Rating::try_new(mu, sigma)
+    }
+}
+
+impl Rater {
+    /// Encodes β as 8 bytes, an IEEE 754 double in little-endian byte order.
+    /// Like `Rating::to_bytes`, this is a stable wire format. The options set
+    /// by `allow_fewer_than_two_teams` and `strict_ranks` are not encoded.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.beta().to_le_bytes()
+    }
+
+    /// Decodes a rater encoded by `Rater::to_bytes`.
+    ///
+    /// Returns `BBTError::InvalidParameter` if β is negative or not finite.
+    pub fn from_bytes(bytes: &[u8; 8]) -> Result<Rater, BBTError> {
+        let beta = f64::from_le_bytes(*bytes);
+
+        if !beta.is_finite() || beta < 0.0 {
+            return Err(BBTError::InvalidParameter { name: "beta" });
+        }
+
+        Ok(Rater::new(beta))
+    }
+}
+
+/// QuantizationSpec describes how `Rating::to_quantized` packs a rating into
+/// 32 bits: the range of mu and sigma that can be represented, and the
+/// number of bits for each. Mu is stored in the high `mu_bits` bits of the
+/// word and sigma in the `sigma_bits` bits below them; any bits below those
+/// are zero.
+///
+/// Each range is divided into `2^bits - 1` equal steps, and values are
+/// rounded to the nearest step. Within the range, the quantization error is
+/// therefore at most half a step, `(max - min) / (2 * (2^bits - 1))`, as
+/// returned by `max_mu_error` and `max_sigma_error`. Values outside of the
+/// range saturate at its edges.
+///
+/// ```rust
+/// use bbt::{QuantizationSpec, Rating};
+///
+/// // Mu between 0 and 50 to within 0.0004, sigma between 0.01 and 25/3.
+/// let spec = QuantizationSpec::new(0.0..=50.0, 16, 0.01..=25.0 / 3.0, 16).unwrap();
+///
+/// let rating = Rating::new(27.5, 2.25);
+/// let decoded = Rating::from_quantized(rating.to_quantized(&spec), &spec);
+///
+/// assert!((decoded.mu() - rating.mu()).abs() <= spec.max_mu_error());
+/// assert!((decoded.sigma() - rating.sigma()).abs() <= spec.max_sigma_error());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizationSpec {
+    mu_min: f64,
+    mu_max: f64,
+    mu_bits: u32,
+    sigma_min: f64,
+    sigma_max: f64,
+    sigma_bits: u32,
+}
+
+impl QuantizationSpec {
+    /// Creates a spec with the given ranges and numbers of bits.
+    ///
+    /// Returns `BBTError::InvalidParameter` if a range is empty, a single
+    /// value or not finite, if the smallest sigma is not positive, so that
+    /// every decoded rating is valid, or if either number of bits is zero or
+    /// both together exceed 32.
+    pub fn new(
+        mu: RangeInclusive<f64>,
+        mu_bits: u32,
+        sigma: RangeInclusive<f64>,
+        sigma_bits: u32,
+    ) -> Result<QuantizationSpec, BBTError> {
+        let (mu_min, mu_max) = mu.into_inner();
+        let (sigma_min, sigma_max) = sigma.into_inner();
+
+        if !(mu_min.is_finite() && mu_max.is_finite() && mu_min < mu_max) {
+            return Err(BBTError::InvalidParameter { name: "mu" });
+        }
+
+        if !(sigma_min > 0.0 && sigma_max.is_finite() && sigma_min < sigma_max) {
+            return Err(BBTError::InvalidParameter { name: "sigma" });
+        }
+
+        if mu_bits == 0 || mu_bits >= 32 {
+            return Err(BBTError::InvalidParameter { name: "mu_bits" });
+        }
+
+        if sigma_bits == 0 || mu_bits + sigma_bits > 32 {
+            return Err(BBTError::InvalidParameter { name: "sigma_bits" });
+        }
+
+        Ok(QuantizationSpec {
+            mu_min,
+            mu_max,
+            mu_bits,
+            sigma_min,
+            sigma_max,
+            sigma_bits,
+        })
+    }
+
+    /// Returns the range of mu that can be represented.
+    pub fn mu_range(&self) -> RangeInclusive<f64> {
+        self.mu_min..=self.mu_max
+    }
+
+    /// Returns the range of sigma that can be represented.
+    pub fn sigma_range(&self) -> RangeInclusive<f64> {
+        self.sigma_min..=self.sigma_max
+    }
+
+    /// Returns the largest difference between the mu of a rating within the
+    /// range and the mu it is decoded to.
+    pub fn max_mu_error(&self) -> f64 {
+        (self.mu_max - self.mu_min) / (2.0 * steps(self.mu_bits))
+    }
+
+    /// Returns the largest difference between the sigma of a rating within
+    /// the range and the sigma it is decoded to.
+    pub fn max_sigma_error(&self) -> f64 {
+        (self.sigma_max - self.sigma_min) / (2.0 * steps(self.sigma_bits))
+    }
+
+    /// Returns the number of unused low bits.
+    fn padding(&self) -> u32 {
+        32 - self.mu_bits - self.sigma_bits
+    }
+}
+
+/// Returns the number of steps of a field with the given number of bits.
+fn steps(bits: u32) -> f64 {
+    ((1u64 << bits) - 1) as f64
+}
+
+/// Returns the step of `value` in a field, saturating at the edges of the
+/// range. NaN saturates at the bottom.
+fn quantize(value: f64, min: f64, max: f64, bits: u32) -> u32 {
+    let steps = steps(bits);
+    let scaled = (value - min) / (max - min) * steps;
+
+    if scaled >= steps {
+        steps as u32
+    } else if scaled > 0.0 {
+        // Rounds to the nearest step, without `f64::round`, which needs std.
+        (scaled + 0.5) as u32
+    } else {
+        0
+    }
+}
+
+/// Returns the value of a step of a field.
+fn dequantize(step: u32, min: f64, max: f64, bits: u32) -> f64 {
+    min + (max - min) * (f64::from(step) / steps(bits))
+}
+
+impl Rating {
+    /// Packs the rating into 32 bits as described by the spec, for sending
+    /// many ratings over the network. Mu and sigma outside of the ranges of
+    /// the spec saturate at the edges instead of wrapping around.
+    pub fn to_quantized(&self, spec: &QuantizationSpec) -> u32 {
+        let mu = quantize(self.mu, spec.mu_min, spec.mu_max, spec.mu_bits);
+        let sigma = quantize(self.sigma, spec.sigma_min, spec.sigma_max, spec.sigma_bits);
+
+        ((mu << spec.sigma_bits) | sigma) << spec.padding()
+    }
+
+    /// Unpacks a rating packed by `Rating::to_quantized` with the same spec.
+    /// Bits below the fields of the spec are ignored.
+    pub fn from_quantized(bits: u32, spec: &QuantizationSpec) -> Rating {
+        let bits = bits >> spec.padding();
+        let sigma = bits & ((1u64 << spec.sigma_bits) - 1) as u32;
+        let mu = (bits >> spec.sigma_bits) & ((1u64 << spec.mu_bits) - 1) as u32;
+
+        Rating::new(
+            dequantize(mu, spec.mu_min, spec.mu_max, spec.mu_bits),
+            dequantize(sigma, spec.sigma_min, spec.sigma_max, spec.sigma_bits),
+        )
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "strict-math")]
+use check_team_updates;
+use {apply_team_updates, with_workspace, BBTError, Float, Rater, Rating};
+
+/// RankObservation is what is known about the placement of a team in a match
+/// whose ranking was only partially observed, such as a qualifier that only
+/// publishes its top placements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RankObservation {
+    /// The team finished with the given rank, where lower ranks are better,
+    /// as in `Rater::update_ratings`.
+    Exact(usize),
+
+    /// The team finished somewhere below the given rank, i.e. it lost to
+    /// every team with an exact rank up to and including it, and its order
+    /// among the other censored teams is unknown.
+    Below(usize),
+}
+
+impl RankObservation {
+    /// Returns the rank the team is compared with, which is the first rank
+    /// below the cut for censored teams.
+    fn rank(self) -> usize {
+        match self {
+            RankObservation::Exact(rank) => rank,
+            RankObservation::Below(cut) => cut.saturating_add(1),
+        }
+    }
+
+    fn is_censored(self) -> bool {
+        match self {
+            RankObservation::Exact(_) => false,
+            RankObservation::Below(_) => true,
+        }
+    }
+}
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings of a match like `update_ratings`, but
+    /// for a ranking in which some teams are only known to have finished
+    /// below a cut. A team observed `Below(k)` is compared as a loss with
+    /// every team with an exact rank up to `k`, and not at all with the other
+    /// censored teams, since nothing is known about their order.
+    ///
+    /// The teams with exact ranks are updated exactly as by `update_ratings`
+    /// with the censored teams ranked below all of them.
+    ///
+    /// Returns the errors of `update_ratings`, and `BBTError::InvalidRanks`
+    /// if a team has an exact rank below the cut of a censored team. With
+    /// `Rater::strict_ranks`, the exact ranks followed by the ranks just
+    /// below the cuts have to form a standard competition ranking.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating, RankObservation};
+    ///
+    /// let rater = Rater::default();
+    /// let teams = vec![vec![Rating::default()]; 4];
+    /// let observations = vec![
+    ///     RankObservation::Exact(1),
+    ///     RankObservation::Exact(2),
+    ///     RankObservation::Below(2),
+    ///     RankObservation::Below(2),
+    /// ];
+    ///
+    /// let teams = rater.update_ratings_censored(teams, &observations).unwrap();
+    /// assert!(teams[1][0].mu() > teams[2][0].mu());
+    /// assert_eq!(teams[2], teams[3]);
+    /// ```
+    pub fn update_ratings_censored(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        observations: &[RankObservation],
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let ranks: Vec<usize> = observations.iter().map(|o| o.rank()).collect();
+
+        let lowest_cut = observations
+            .iter()
+            .filter_map(|&observation| match observation {
+                RankObservation::Below(cut) => Some(cut),
+                RankObservation::Exact(_) => None,
+            })
+            .min();
+
+        if let Some(cut) = lowest_cut {
+            let below_cut = |&observation: &RankObservation| match observation {
+                RankObservation::Exact(rank) => rank > cut,
+                RankObservation::Below(_) => false,
+            };
+
+            if observations.iter().any(below_cut) {
+                return Err(BBTError::InvalidRanks);
+            }
+        }
+
+        with_workspace(teams.len(), |work| {
+            self.validate_into(&teams, &ranks, work)?;
+
+            // Censored teams tie with each other, and ties are never
+            // surprising, so only their losses to exact teams can inflate
+            // them.
+            self.inflate_surprised(&mut teams, &ranks, work);
+
+            for team_idx in 0..ranks.len() {
+                let censored = observations[team_idx].is_censored();
+                let opponents = (0..ranks.len())
+                    .filter(|&other| !(censored && observations[other].is_censored()));
+
+                let (omega, delta) = self.team_omega_delta(
+                    team_idx,
+                    opponents,
+                    work.team_mu,
+                    work.team_sigma_sq,
+                    &ranks,
+                    (work.omega_terms, work.delta_terms),
+                );
+
+                work.team_omega[team_idx] = omega;
+                work.team_delta[team_idx] = delta;
+            }
+
+            #[cfg(feature = "strict-math")]
+            check_team_updates(&teams, work)?;
+
+            apply_team_updates(&mut teams, work, self.write_back(), |_, _| ());
+
+            Ok(teams)
+        })
+    }
+}
@@ -0,0 +1,179 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "tracing")]
+use trace_update;
+#[cfg(feature = "validate")]
+use validate;
+use {canonical_sum, sum_of_one, updated_rating};
+use {BBTError, Float, Outcome, Rater, Rating};
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings of players from a list of pairwise
+    /// comparisons, such as judge decisions, which need not add up to a
+    /// ranking: they may be incomplete or even contradict each other.
+    ///
+    /// Every comparison `(i, j, outcome)` is a duel between `ratings[i]` and
+    /// `ratings[j]` with the outcome from the perspective of `i`, and
+    /// contributes to the omega and delta of both players. Each player is
+    /// then updated once with the sum of their contributions, so the result
+    /// does not depend on the order of the comparisons. A single comparison
+    /// is the same as `duel`, and the comparisons of a full round robin are
+    /// the same as `update_ratings` with the corresponding ranks. Players
+    /// that take part in no comparison are left untouched.
+    ///
+    /// Returns `BBTError::InvalidParameter` if a comparison refers to a
+    /// player outside `ratings` or compares a player with themselves, and
+    /// `BBTError::InvalidRating` if a compared player's rating is invalid,
+    /// with the index of the player as the team and zero as the player. The
+    /// ratings are only changed if no error is returned.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating};
+    ///
+    /// let mut ratings = [Rating::default(); 3];
+    /// let comparisons = [(0, 1, Outcome::Win), (2, 0, Outcome::Draw), (1, 2, Outcome::Win)];
+    ///
+    /// Rater::default().update_from_comparisons(&mut ratings, &comparisons).unwrap();
+    ///
+    /// assert!(ratings[0].mu() > ratings[1].mu() && ratings[1].mu() > ratings[2].mu());
+    /// ```
+    pub fn update_from_comparisons(
+        &self,
+        ratings: &mut [Rating<F>],
+        comparisons: &[(usize, usize, Outcome)],
+    ) -> Result<(), BBTError> {
+        for &(i, j, _) in comparisons {
+            if i >= ratings.len() || j >= ratings.len() || i == j {
+                return Err(BBTError::InvalidParameter {
+                    name: "comparisons",
+                });
+            }
+
+            for &player in &[i, j] {
+                if !ratings[player].is_valid() {
+                    return Err(BBTError::InvalidRating {
+                        team: player,
+                        player: 0,
+                    });
+                }
+            }
+        }
+
+        let mu: Vec<F> = ratings.iter().map(|rating| sum_of_one(rating.mu)).collect();
+        let sigma_sq: Vec<F> = ratings
+            .iter()
+            .map(|rating| sum_of_one(rating.sigma_sq))
+            .collect();
+        let two_beta_sq = F::from_f64(2.0) * self.beta_sq;
+
+        // The terms of every comparison, by player, computed with the same
+        // operations as in `team_omega_delta`.
+        let mut terms: Vec<(usize, F, F)> = Vec::with_capacity(2 * comparisons.len());
+
+        for &(i, j, outcome) in comparisons {
+            let c = self.sqrt(sigma_sq[i] + sigma_sq[j] + two_beta_sq);
+
+            // Two fully certain players with β = 0 carry no information
+            // about each other.
+            if c == F::ZERO {
+                continue;
+            }
+
+            let pij = self.logistic(mu[i] - mu[j], c);
+            let pji = self.logistic(mu[j] - mu[i], c);
+
+            let (si, sj) = match outcome {
+                Outcome::Win => (F::ONE, F::ZERO),
+                Outcome::Loss => (F::ZERO, F::ONE),
+                Outcome::Draw => (F::from_f64(0.5), F::from_f64(0.5)),
+            };
+
+            for &(player, s, p, q) in &[(i, si, pij, pji), (j, sj, pji, pij)] {
+                let omega = (sigma_sq[player] / c) * (s - p);
+                let gamma = self.sqrt(sigma_sq[player]) / c;
+                let delta = gamma * (sigma_sq[player] / (c * c)) * p * q;
+
+                terms.push((player, omega, delta));
+            }
+        }
+
+        terms.sort_by_key(|&(player, _, _)| player);
+
+        let mut omega = vec![F::ZERO; ratings.len()];
+        let mut delta = vec![F::ZERO; ratings.len()];
+        let mut compared = vec![false; ratings.len()];
+        let mut omega_terms = Vec::new();
+        let mut delta_terms = Vec::new();
+
+        for chunk in terms.chunk_by(|a, b| a.0 == b.0) {
+            let player = chunk[0].0;
+
+            omega_terms.clear();
+            omega_terms.extend(chunk.iter().map(|&(_, omega, _)| omega));
+            delta_terms.clear();
+            delta_terms.extend(chunk.iter().map(|&(_, _, delta)| delta));
+
+            omega[player] = canonical_sum(&mut omega_terms);
+            delta[player] = canonical_sum(&mut delta_terms);
+            compared[player] = true;
+        }
+
+        #[cfg(feature = "strict-math")]
+        for player in 0..ratings.len() {
+            if !compared[player] || sigma_sq[player] == F::ZERO {
+                continue;
+            }
+
+            let (rating, _) = updated_rating(
+                &ratings[player],
+                sigma_sq[player],
+                omega[player],
+                delta[player],
+            );
+            if !rating.mu.is_finite() || !rating.sigma.is_finite() || rating.sigma < F::ZERO {
+                return Err(BBTError::NumericalError {
+                    team: player,
+                    player: 0,
+                });
+            }
+        }
+
+        #[cfg(feature = "validate")]
+        let values = validate::TeamValues {
+            mu: &mu,
+            sigma_sq: &sigma_sq,
+            omega: &omega,
+            delta: &delta,
+        };
+        #[cfg(feature = "validate")]
+        validate::assert_teams(&values);
+
+        let write_back = self.write_back();
+
+        for player in 0..ratings.len() {
+            // Like a team without variance, a fully certain player is not
+            // updated.
+            if !compared[player] || sigma_sq[player] == F::ZERO {
+                continue;
+            }
+
+            let (rating, _clamped) = updated_rating(
+                &ratings[player],
+                sigma_sq[player],
+                omega[player],
+                delta[player],
+            );
+
+            #[cfg(feature = "tracing")]
+            trace_update(player, 0, &rating, _clamped);
+
+            #[cfg(feature = "validate")]
+            validate::assert_update(player, 0, &ratings[player], &rating, &values);
+
+            ratings[player] = write_back.apply(&ratings[player], rating);
+        }
+
+        Ok(())
+    }
+}
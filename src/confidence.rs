@@ -0,0 +1,142 @@
+use {BBTError, Rating};
+
+impl Rating {
+    /// Creates a rating whose central credible interval of the given
+    /// `confidence` is `mu ± half_width`. For example, a player who is 90%
+    /// likely to have a skill between 20 and 30 has a mu of 25, a half-width
+    /// of 5 and a confidence of 0.9.
+    ///
+    /// Since the skill is normally distributed, the sigma is the half-width
+    /// divided by the quantile of the standard normal distribution at
+    /// `(1 + confidence) / 2`. The more confident, the smaller the sigma for
+    /// the same interval. A confidence of about 99.7% gives the interval of
+    /// three sigmas on either side of mu that the default rating uses.
+    ///
+    /// Returns `BBTError::InvalidParameter` if the confidence is not strictly
+    /// between zero and one, if the half-width is not positive and finite, or
+    /// if mu is not finite.
+    ///
+    /// ```rust
+    /// use bbt::Rating;
+    ///
+    /// let rating = Rating::new_with_confidence(25.0, 5.0, 0.9).unwrap();
+    /// assert!((rating.sigma() - 3.04).abs() < 0.01);
+    /// ```
+    pub fn new_with_confidence(
+        mu: f64,
+        half_width: f64,
+        confidence: f64,
+    ) -> Result<Rating, BBTError> {
+        // Also rejects NaN.
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(BBTError::InvalidParameter { name: "confidence" });
+        }
+
+        if !half_width.is_finite() || half_width <= 0.0 {
+            return Err(BBTError::InvalidParameter { name: "half_width" });
+        }
+
+        // The lower tail keeps its precision for confidences close to one.
+        let z = -normal_quantile((1.0 - confidence) / 2.0);
+
+        Rating::try_new(mu, half_width / z)
+    }
+}
+
+/// Returns the quantile of the standard normal distribution at `p`, which
+/// must be strictly between zero and one, with Wichura's algorithm AS 241
+/// (PPND16). It is accurate to about 16 significant digits. The coefficients
+/// are given as published.
+#[allow(clippy::excessive_precision)]
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 8] = [
+        3.387_132_872_796_366_608,
+        1.331_416_678_917_843_774_5e2,
+        1.971_590_950_306_551_442_7e3,
+        1.373_169_376_550_946_112_5e4,
+        4.592_195_393_154_987_145_7e4,
+        6.726_577_092_700_870_085_3e4,
+        3.343_057_558_358_812_810_5e4,
+        2.509_080_928_730_122_672_7e3,
+    ];
+    const B: [f64; 8] = [
+        1.0,
+        4.231_333_070_160_091_125_2e1,
+        6.871_870_074_920_579_083e2,
+        5.394_196_021_424_751_107_7e3,
+        2.121_379_430_158_659_586_7e4,
+        3.930_789_580_009_271_061e4,
+        2.872_908_573_572_194_267_4e4,
+        5.226_495_278_852_854_561e3,
+    ];
+    const C: [f64; 8] = [
+        1.423_437_110_749_683_577_34,
+        4.630_337_846_156_545_295_9,
+        5.769_497_221_460_691_405_5,
+        3.647_848_324_763_204_605_04,
+        1.270_458_252_452_368_382_58,
+        2.417_807_251_774_506_117_7e-1,
+        2.272_384_498_926_918_458_33e-2,
+        7.745_450_142_783_414_076_4e-4,
+    ];
+    const D: [f64; 8] = [
+        1.0,
+        2.053_191_626_637_758_821_87,
+        1.676_384_830_183_803_849_4,
+        6.897_673_349_851_000_045_5e-1,
+        1.481_039_764_274_800_745_9e-1,
+        1.519_866_656_361_645_719_66e-2,
+        5.475_938_084_995_344_946e-4,
+        1.050_750_071_644_416_843_24e-9,
+    ];
+    const E: [f64; 8] = [
+        6.657_904_643_501_103_777_2,
+        5.463_784_911_164_114_369_9,
+        1.784_826_539_917_291_335_8,
+        2.965_605_718_285_048_912_3e-1,
+        2.653_218_952_657_612_309_3e-2,
+        1.242_660_947_388_078_438_6e-3,
+        2.711_555_568_743_487_578_15e-5,
+        2.010_334_399_292_288_132_65e-7,
+    ];
+    const F: [f64; 8] = [
+        1.0,
+        5.998_322_065_558_879_376_9e-1,
+        1.369_298_809_227_358_053_1e-1,
+        1.487_536_129_085_061_485_25e-2,
+        7.868_691_311_456_132_591e-4,
+        1.846_318_317_510_054_681_8e-5,
+        1.421_511_758_316_445_888_7e-7,
+        2.044_263_103_389_939_785_64e-15,
+    ];
+
+    fn ratio(numerator: &[f64; 8], denominator: &[f64; 8], x: f64) -> f64 {
+        let horner = |coefficients: &[f64; 8]| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(0.0, |sum, &coefficient| sum * x + coefficient)
+        };
+
+        horner(numerator) / horner(denominator)
+    }
+
+    let q = p - 0.5;
+
+    if q.abs() <= 0.425 {
+        return q * ratio(&A, &B, 0.180_625 - q * q);
+    }
+
+    let r = (-(if q < 0.0 { p } else { 1.0 - p }).ln()).sqrt();
+    let z = if r <= 5.0 {
+        ratio(&C, &D, r - 1.6)
+    } else {
+        ratio(&E, &F, r - 5.0)
+    };
+
+    if q < 0.0 {
+        -z
+    } else {
+        z
+    }
+}
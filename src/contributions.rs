@@ -0,0 +1,159 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "strict-math")]
+use check_team_updates;
+#[cfg(feature = "tracing")]
+use trace_update;
+#[cfg(feature = "validate")]
+use validate;
+use {updated_rating, with_workspace, CompensatedSum};
+use {BBTError, Float, Rater, Rating};
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings like `update_ratings`, but divides the
+    /// change of each team's skill among its players by their contribution
+    /// to the result, such as their score, so that players who were carried
+    /// gain less than the players who carried them.
+    ///
+    /// `contributions` has one score per player, in the shape of `teams`.
+    /// In the flat update, every player's mu moves by their share of the
+    /// team's variance. Here, each player's variance is first weighted with
+    /// their contribution, so between two equally certain players, one who
+    /// contributed twice as much moves twice as far. The shares still add up
+    /// to one, so the total change of the team's mu is the same as in the
+    /// flat update, and equal contributions give the flat update exactly.
+    /// Players who contributed nothing keep their mu. If only fully certain
+    /// players contributed, the flat shares are used.
+    ///
+    /// Sigma is updated as in the flat update, whatever the contributions,
+    /// so that a player cannot become more or less certain by padding their
+    /// score.
+    ///
+    /// Returns the errors of `update_ratings`, and
+    /// `BBTError::InvalidParameter` if `contributions` is not of the shape of
+    /// `teams`, if a contribution is negative or not finite, or if all
+    /// contributions of a team are zero.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let teams = vec![vec![Rating::default(); 2], vec![Rating::default(); 2]];
+    /// let contributions: [&[f64]; 2] = [&[30.0, 10.0], &[5.0, 5.0]];
+    ///
+    /// let new = Rater::default()
+    ///     .update_ratings_with_contributions(teams, vec![1, 2], &contributions)
+    ///     .unwrap();
+    ///
+    /// let (carry, passenger) = (new[0][0].mu() - 25.0, new[0][1].mu() - 25.0);
+    /// assert!((carry - 3.0 * passenger).abs() < 1e-12);
+    /// assert_eq!(new[0][0].sigma(), new[0][1].sigma());
+    /// ```
+    pub fn update_ratings_with_contributions(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+        contributions: &[&[F]],
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        with_workspace(teams.len(), |work| {
+            self.validate_into(&teams, &ranks, work)?;
+            check_contributions(&teams, contributions)?;
+            self.compute_all_omega_delta(&ranks, work);
+
+            #[cfg(feature = "strict-math")]
+            check_team_updates(&teams, work)?;
+
+            #[cfg(feature = "validate")]
+            let values = validate::TeamValues {
+                mu: &work.team_mu[..],
+                sigma_sq: &work.team_sigma_sq[..],
+                omega: &work.team_omega[..],
+                delta: &work.team_delta[..],
+            };
+            #[cfg(feature = "validate")]
+            validate::assert_teams(&values);
+
+            let write_back = self.write_back();
+
+            for (team_idx, team) in teams.iter_mut().enumerate() {
+                if work.team_sigma_sq[team_idx] == F::ZERO {
+                    continue;
+                }
+
+                let weights = contributions[team_idx];
+                let mut weighted_sigma_sq = CompensatedSum::default();
+                for (player, &weight) in team.iter().zip(weights) {
+                    weighted_sigma_sq.add(weight * player.sigma_sq);
+                }
+                let weighted_sigma_sq = weighted_sigma_sq.total();
+
+                // Equal contributions cancel out, so the flat shares are
+                // used as they are.
+                let flat = weights.iter().all(|&weight| weight == weights[0])
+                    || weighted_sigma_sq == F::ZERO;
+
+                for (player_idx, player) in team.iter_mut().enumerate() {
+                    let (mut rating, _clamped) = updated_rating(
+                        player,
+                        work.team_sigma_sq[team_idx],
+                        work.team_omega[team_idx],
+                        work.team_delta[team_idx],
+                    );
+
+                    if !flat {
+                        let share = weights[player_idx] * player.sigma_sq / weighted_sigma_sq;
+                        rating.mu = player.mu + share * work.team_omega[team_idx];
+
+                        // A large share can overflow where the flat one did
+                        // not.
+                        #[cfg(feature = "strict-math")]
+                        if !rating.mu.is_finite() {
+                            return Err(BBTError::NumericalError {
+                                team: team_idx,
+                                player: player_idx,
+                            });
+                        }
+                    }
+
+                    #[cfg(feature = "tracing")]
+                    trace_update(team_idx, player_idx, &rating, _clamped);
+
+                    #[cfg(feature = "validate")]
+                    validate::assert_update(team_idx, player_idx, player, &rating, &values);
+
+                    *player = write_back.apply(player, rating);
+                }
+            }
+
+            Ok(teams)
+        })
+    }
+}
+
+/// Checks that there is a non-negative, finite contribution for every
+/// player, and that every team has a contribution that is not zero.
+fn check_contributions<F: Float>(
+    teams: &[Vec<Rating<F>>],
+    contributions: &[&[F]],
+) -> Result<(), BBTError> {
+    let invalid = Err(BBTError::InvalidParameter {
+        name: "contributions",
+    });
+
+    if contributions.len() != teams.len() {
+        return invalid;
+    }
+
+    for (team, weights) in teams.iter().zip(contributions) {
+        if weights.len() != team.len()
+            || weights
+                .iter()
+                .any(|&weight| !weight.is_finite() || weight < F::ZERO)
+            || weights.iter().all(|&weight| weight == F::ZERO)
+        {
+            return invalid;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Inactivity decay over many ratings at once.
+//!
+//! While a player does not play, their skill may drift, so their rating
+//! should become less certain. Each rating period without a game adds `tau²`
+//! to the variance, up to a cap of `max_sigma`, after which the rating is as
+//! uncertain as that of a new player.
+//!
+//! ```rust
+//! let mut ratings = [bbt::Rating::new(30.0, 2.0), bbt::Rating::new(20.0, 8.0)];
+//!
+//! // The first player missed three periods, the second one period.
+//! let modified = bbt::decay::apply(&mut ratings, &[3, 1], 0.5, 8.0).unwrap();
+//!
+//! assert_eq!(modified, 1);
+//! assert_eq!(ratings[0].sigma(), (4.0f64 + 3.0 * 0.25).sqrt());
+//! // The second player was already at the cap.
+//! assert_eq!(ratings[1].sigma(), 8.0);
+//! ```
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use std::cmp::Ordering;
+
+use {BBTError, Float, Rating};
+
+/// Inflates the variance of every rating by `periods[i] * tau²`, without
+/// letting sigma grow beyond `max_sigma`, and returns how many ratings were
+/// modified.
+///
+/// Ratings with zero periods, and ratings whose sigma is already at or above
+/// `max_sigma`, are left untouched.
+///
+/// Returns `BBTError::InvalidParameter` if `periods` is not as long as
+/// `ratings`, if `tau` is negative or not finite, or if `max_sigma` is not
+/// positive. An infinite `max_sigma` disables the cap.
+pub fn apply<F: Float>(
+    ratings: &mut [Rating<F>],
+    periods: &[u32],
+    tau: F,
+    max_sigma: F,
+) -> Result<usize, BBTError> {
+    if periods.len() != ratings.len() {
+        return Err(BBTError::InvalidParameter { name: "periods" });
+    }
+
+    check_parameters(tau, max_sigma)?;
+
+    let mut modified = 0;
+
+    for (rating, &periods) in ratings.iter_mut().zip(periods) {
+        if let Some(inflated) = inflate(rating, periods, tau, max_sigma) {
+            *rating = inflated;
+            modified += 1;
+        }
+    }
+
+    Ok(modified)
+}
+
+/// Applies decay to a table of ratings, counting the periods from the time
+/// each player last played. A player who last played at `t` has missed
+/// `(now - t) / period` whole periods, so repeated calls with the same `now`
+/// and `last_played` apply the same decay again; update `last_played`, or
+/// only decay at period boundaries, to avoid that.
+///
+/// Players missing from `last_played`, or whose last game is in the future,
+/// are left untouched. Times can be in any unit, as long as `now`,
+/// `last_played` and `period` agree.
+///
+/// Returns `BBTError::InvalidParameter` if `period` is not positive, or
+/// under the same conditions as `apply`.
+#[cfg(feature = "std")]
+pub fn apply_elapsed<K: Eq + Hash, F: Float>(
+    ratings: &mut HashMap<K, Rating<F>>,
+    last_played: &HashMap<K, i64>,
+    now: i64,
+    period: i64,
+    tau: F,
+    max_sigma: F,
+) -> Result<usize, BBTError> {
+    if period <= 0 {
+        return Err(BBTError::InvalidParameter { name: "period" });
+    }
+
+    check_parameters(tau, max_sigma)?;
+
+    let mut modified = 0;
+
+    for (id, rating) in ratings.iter_mut() {
+        let periods = match last_played.get(id) {
+            Some(&last_played) => elapsed_periods(last_played, now, period),
+            None => continue,
+        };
+
+        if let Some(inflated) = inflate(rating, periods, tau, max_sigma) {
+            *rating = inflated;
+            modified += 1;
+        }
+    }
+
+    Ok(modified)
+}
+
+fn check_parameters<F: Float>(tau: F, max_sigma: F) -> Result<(), BBTError> {
+    if !tau.is_finite() || tau < F::ZERO {
+        return Err(BBTError::InvalidParameter { name: "tau" });
+    }
+
+    if max_sigma.partial_cmp(&F::ZERO) != Some(Ordering::Greater) {
+        return Err(BBTError::InvalidParameter { name: "max_sigma" });
+    }
+
+    Ok(())
+}
+
+/// Returns the number of whole periods between `last_played` and `now`,
+/// saturating at `u32::MAX`.
+#[cfg(feature = "std")]
+fn elapsed_periods(last_played: i64, now: i64, period: i64) -> u32 {
+    let elapsed = i128::from(now) - i128::from(last_played);
+    if elapsed <= 0 {
+        return 0;
+    }
+
+    let periods = elapsed / i128::from(period);
+    if periods > i128::from(u32::MAX) {
+        u32::MAX
+    } else {
+        periods as u32
+    }
+}
+
+/// Returns the decayed rating, or `None` if decay does not change it.
+fn inflate<F: Float>(rating: &Rating<F>, periods: u32, tau: F, max_sigma: F) -> Option<Rating<F>> {
+    // Invalid ratings are left alone, like `update_ratings` rejects them.
+    if periods == 0 || tau == F::ZERO || !rating.sigma_sq.is_finite() || rating.sigma >= max_sigma {
+        return None;
+    }
+
+    let max_sigma_sq = max_sigma * max_sigma;
+    let mut sigma_sq = rating.sigma_sq + F::from_f64(f64::from(periods)) * tau * tau;
+    let mut sigma = sigma_sq.sqrt();
+
+    if sigma >= max_sigma {
+        sigma_sq = max_sigma_sq;
+        sigma = max_sigma;
+    }
+
+    if sigma_sq == rating.sigma_sq {
+        return None;
+    }
+
+    Some(Rating {
+        mu: rating.mu,
+        sigma,
+        sigma_sq,
+    })
+}
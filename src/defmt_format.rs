@@ -0,0 +1,66 @@
+use defmt::{write, Format, Formatter};
+
+use {BBTError, Fixed, Float, Rater, Rating};
+
+impl Format for Fixed {
+    fn format(&self, f: Formatter) {
+        write!(f, "{=f64}", self.to_f64())
+    }
+}
+
+impl<F: Float + Format> Format for Rating<F> {
+    fn format(&self, f: Formatter) {
+        write!(f, "mu={} sigma={}", self.mu, self.sigma)
+    }
+}
+
+impl<F: Float + Format> Format for Rater<F> {
+    fn format(&self, f: Formatter) {
+        write!(f, "Rater(beta={})", self.beta())
+    }
+}
+
+impl Format for BBTError {
+    fn format(&self, f: Formatter) {
+        match *self {
+            BBTError::MismatchedLengths { teams, ranks } => write!(
+                f,
+                "`teams` and `ranks` vectors must be of the same length (got {=usize} teams and {=usize} ranks)",
+                teams,
+                ranks
+            ),
+            BBTError::EmptyTeam { index } => {
+                write!(f, "The team at index {=usize} contains no players", index)
+            }
+            BBTError::NotEnoughTeams { found } => write!(
+                f,
+                "At least two teams are required, but {=usize} were given",
+                found
+            ),
+            BBTError::InvalidRating { team, player } => write!(
+                f,
+                "The rating of player {=usize} of team {=usize} is not finite or has a negative sigma",
+                player,
+                team
+            ),
+            BBTError::TeamOverflow { index } => write!(
+                f,
+                "The skill or variance of the team at index {=usize} is too large",
+                index
+            ),
+            BBTError::InvalidRanks => write!(
+                f,
+                "The ranks are not a standard competition ranking (e.g. 1, 2, 2, 4)"
+            ),
+            BBTError::NumericalError { team, player } => write!(
+                f,
+                "The update produced a non-finite rating for player {=usize} of team {=usize}",
+                player,
+                team
+            ),
+            BBTError::InvalidParameter { name } => {
+                write!(f, "The parameter `{=str}` is out of range", name)
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Order-independent digests of rating tables.
+//!
+//! Two copies of a rating table, such as the table of a server and the one
+//! recomputed by a worker that replays its games, can be compared by their
+//! digests instead of entry by entry. The digest of a table does not depend
+//! on the order of its entries, so tables kept in hash maps can be compared
+//! without sorting them first.
+//!
+//! ```rust
+//! use bbt::digest::state_digest;
+//! use bbt::Rating;
+//!
+//! let alice = Rating::new(27.5, 4.0);
+//! let bob = Rating::new(22.0, 6.0);
+//!
+//! assert_eq!(
+//!     state_digest(vec![("alice", &alice), ("bob", &bob)]),
+//!     state_digest(vec![("bob", &bob), ("alice", &alice)])
+//! );
+//! ```
+//!
+//! # Algorithm
+//!
+//! The algorithm is stable: it will not change without a new major version
+//! of the crate, so digests may be stored and compared across versions.
+//!
+//! 1. Every entry is encoded as the length of its id in bytes, as a
+//!    little-endian `u64`, followed by the UTF-8 bytes of the id and the 16
+//!    bytes of `Rating::to_bytes`: the IEEE 754 bits of mu and sigma in
+//!    little-endian byte order.
+//! 2. The encoding is hashed with 64-bit FNV-1a, with the offset basis
+//!    `0xcbf29ce484222325` and the prime `0x100000001b3`.
+//! 3. The hash is mixed with the finalizer of SplitMix64: `x ^= x >> 30;
+//!    x *= 0xbf58476d1ce4e5b9; x ^= x >> 27; x *= 0x94d049bb133111eb;
+//!    x ^= x >> 31`, with wrapping multiplication.
+//! 4. The digest is the wrapping sum of the mixed hashes of all entries.
+//!    The empty table has the digest zero.
+//!
+//! Since the ratings are compared bit for bit, mus or sigmas that are equal
+//! as numbers but differ in their bits, such as `0.0` and `-0.0`, give
+//! different digests. A table with an entry twice has a different digest
+//! than the table with the entry once.
+
+use Rating;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Returns the digest of a table of ratings, as described in the module
+/// documentation. The order of the entries does not matter.
+pub fn state_digest<'a, I, S>(entries: I) -> u64
+where
+    I: IntoIterator<Item = (S, &'a Rating)>,
+    S: AsRef<str>,
+{
+    let mut builder = DigestBuilder::new();
+    for (id, rating) in entries {
+        builder.add(id.as_ref(), rating);
+    }
+
+    builder.finish()
+}
+
+/// DigestBuilder computes the digest of `state_digest` one entry at a time,
+/// for tables that are streamed rather than held in memory.
+///
+/// ```rust
+/// use bbt::digest::{state_digest, DigestBuilder};
+/// use bbt::Rating;
+///
+/// let mut builder = DigestBuilder::new();
+/// builder.add("alice", &Rating::new(27.5, 4.0));
+///
+/// assert_eq!(builder.finish(), state_digest(vec![("alice", &Rating::new(27.5, 4.0))]));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DigestBuilder {
+    sum: u64,
+}
+
+impl DigestBuilder {
+    /// Creates a builder for an empty table.
+    pub fn new() -> DigestBuilder {
+        DigestBuilder::default()
+    }
+
+    /// Adds an entry to the table.
+    pub fn add(&mut self, id: &str, rating: &Rating) {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut write = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        write(&(id.len() as u64).to_le_bytes());
+        write(id.as_bytes());
+        write(&rating.to_bytes());
+
+        self.sum = self.sum.wrapping_add(mix(hash));
+    }
+
+    /// Returns the digest of the entries added so far.
+    pub fn finish(&self) -> u64 {
+        self.sum
+    }
+}
+
+/// The finalizer of SplitMix64.
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
@@ -0,0 +1,422 @@
+//! Prequential evaluation of a rater on a history of games, enabled by the
+//! `serde` and `std` features.
+//!
+//! The history is replayed in order like `replay::replay`, but before every
+//! game, the outcome is predicted with the ratings from before the game.
+//! Since every prediction is made before the rater has seen the game, the
+//! predictions measure how well the rater generalizes, without holding back
+//! any games for testing.
+//!
+//! A game is predicted as pairwise comparisons between its teams: for every
+//! two teams with different ranks, the probability that the team listed
+//! first finishes ahead of the other, as given by `Rater::win_probability`
+//! for the teams' aggregate ratings. Teams with the same rank are not
+//! predicted.
+//!
+//! ```rust
+//! use bbt::eval;
+//! use bbt::replay::GameRecord;
+//! use bbt::{Rater, Rating};
+//!
+//! let games = vec![
+//!     GameRecord::new(vec![vec!["alice".into()], vec!["bob".into()]], vec![1, 2]),
+//!     GameRecord::new(vec![vec!["alice".into()], vec!["bob".into()]], vec![1, 2]),
+//! ];
+//!
+//! let report = eval::prequential(&Rater::default(), &games, Rating::default()).unwrap();
+//!
+//! assert_eq!(report.predictions[0].probability, 0.5);
+//! assert!(report.predictions[1].probability > 0.5);
+//! assert_eq!(report.metrics.accuracy, 0.75);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use replay::{apply_record, check_players, GameRecord, PlayerId, ReplayError};
+use {CompensatedSum, Rater, Rating, TeamRating};
+
+/// Probabilities are kept this far from zero and one when computing the log
+/// loss, so that a certain prediction that turns out wrong has a large, but
+/// finite, loss.
+const MIN_PROBABILITY: f64 = 1e-15;
+
+/// PrequentialOptions changes which predictions `prequential_with_options`
+/// scores.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrequentialOptions {
+    /// Whether predictions that involve a player in their first game are
+    /// left out of the metrics. They are still part of the predictions of
+    /// the report. By default, they are scored like any other prediction.
+    pub exclude_new_players: bool,
+
+    /// The number of calibration bins, ten by default. Zero leaves out the
+    /// calibration.
+    pub calibration_bins: usize,
+}
+
+impl Default for PrequentialOptions {
+    fn default() -> PrequentialOptions {
+        PrequentialOptions {
+            exclude_new_players: false,
+            calibration_bins: 10,
+        }
+    }
+}
+
+/// Prediction is the predicted result of a pair of teams in one game of the
+/// history.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Prediction {
+    /// The index of the game in the history.
+    pub game: usize,
+
+    /// The indices of the two teams within the game.
+    pub teams: (usize, usize),
+
+    /// The predicted probability that the first team finishes ahead of the
+    /// second one.
+    pub probability: f64,
+
+    /// Whether the first team finished ahead of the second one.
+    pub won: bool,
+
+    /// Whether a player of either team played their first game.
+    pub new_players: bool,
+}
+
+/// CalibrationBin compares the predictions within a range of probabilities
+/// to how often the predicted team actually won.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalibrationBin {
+    /// The lowest probability in the bin.
+    pub lower: f64,
+
+    /// The highest probability in the bin. Only the last bin includes it.
+    pub upper: f64,
+
+    /// The number of predictions in the bin.
+    pub count: usize,
+
+    /// The mean predicted probability, or zero if the bin is empty.
+    pub mean_probability: f64,
+
+    /// The fraction of the predictions in the bin that were won, or zero if
+    /// the bin is empty.
+    pub win_rate: f64,
+}
+
+/// Metrics are the scores of a set of predictions. A well calibrated rater
+/// has a win rate close to the mean probability in every calibration bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metrics {
+    /// The number of predictions.
+    pub count: usize,
+
+    /// The mean negative natural logarithm of the probability given to the
+    /// actual result. Lower is better, and always predicting 0.5 gives
+    /// `ln(2)`.
+    pub log_loss: f64,
+
+    /// The mean squared difference between the probability and the actual
+    /// result. Lower is better, and always predicting 0.5 gives 0.25.
+    pub brier_score: f64,
+
+    /// The fraction of predictions that favoured the team that finished
+    /// ahead. A probability of exactly 0.5 counts as half right.
+    pub accuracy: f64,
+
+    /// The calibration bins, which divide the probabilities from zero to one
+    /// into ranges of equal width.
+    pub calibration: Vec<CalibrationBin>,
+}
+
+impl Metrics {
+    /// Scores the given predictions, with `calibration_bins` calibration
+    /// bins. The log loss, Brier score and accuracy are NaN if there are no
+    /// predictions.
+    pub fn from_predictions(predictions: &[Prediction], calibration_bins: usize) -> Metrics {
+        let mut log_loss = CompensatedSum::default();
+        let mut brier_score = CompensatedSum::default();
+        let mut correct = CompensatedSum::default();
+        let mut bins = vec![(CompensatedSum::default(), 0, 0); calibration_bins];
+
+        for prediction in predictions {
+            let p = prediction.probability;
+            let (actual, p_actual) = if prediction.won {
+                (1.0, p)
+            } else {
+                (0.0, 1.0 - p)
+            };
+
+            log_loss.add(-p_actual.clamp(MIN_PROBABILITY, 1.0).ln());
+            brier_score.add((p - actual) * (p - actual));
+            correct.add(if p == 0.5 {
+                0.5
+            } else if p_actual > 0.5 {
+                1.0
+            } else {
+                0.0
+            });
+
+            if calibration_bins > 0 {
+                let bin = ((p * calibration_bins as f64) as usize).min(calibration_bins - 1);
+                bins[bin].0.add(p);
+                bins[bin].1 += 1;
+                bins[bin].2 += prediction.won as usize;
+            }
+        }
+
+        let count = predictions.len();
+        let calibration = bins
+            .into_iter()
+            .enumerate()
+            .map(|(bin, (probability, count, won))| CalibrationBin {
+                lower: bin as f64 / calibration_bins as f64,
+                upper: (bin + 1) as f64 / calibration_bins as f64,
+                count,
+                mean_probability: if count == 0 {
+                    0.0
+                } else {
+                    probability.total() / count as f64
+                },
+                win_rate: if count == 0 {
+                    0.0
+                } else {
+                    won as f64 / count as f64
+                },
+            })
+            .collect();
+
+        Metrics {
+            count,
+            log_loss: log_loss.total() / count as f64,
+            brier_score: brier_score.total() / count as f64,
+            accuracy: correct.total() / count as f64,
+            calibration,
+        }
+    }
+}
+
+/// PrequentialReport is the result of a prequential evaluation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrequentialReport {
+    /// Every prediction, in the order of the history, including the ones
+    /// that were left out of the metrics.
+    pub predictions: Vec<Prediction>,
+
+    /// The scores of the predictions.
+    pub metrics: Metrics,
+
+    /// The ratings after the last game.
+    pub ratings: HashMap<PlayerId, Rating>,
+}
+
+/// Evaluates the rater on a history of games with the default options. See
+/// `prequential_with_options`.
+pub fn prequential(
+    rater: &Rater,
+    games: &[GameRecord],
+    initial: Rating,
+) -> Result<PrequentialReport, ReplayError> {
+    prequential_with_options(rater, games, initial, &PrequentialOptions::default())
+}
+
+/// Evaluates the rater on a history of games: every game is predicted with
+/// the ratings from before it, and then rated like in `replay::replay`.
+/// Players start with the `initial` rating when they first appear.
+///
+/// Returns the errors of `replay::replay`, which stop the evaluation.
+pub fn prequential_with_options(
+    rater: &Rater,
+    games: &[GameRecord],
+    initial: Rating,
+    options: &PrequentialOptions,
+) -> Result<PrequentialReport, ReplayError> {
+    let mut ratings = HashMap::new();
+    let mut predictions = Vec::new();
+
+    for (index, game) in games.iter().enumerate() {
+        check_players(game).map_err(|message| ReplayError::InvalidGame { index, message })?;
+
+        let teams: Vec<(TeamRating, bool)> = game
+            .teams
+            .iter()
+            .map(|team| {
+                let members: Vec<Rating> = team
+                    .iter()
+                    .map(|player| *ratings.get(player).unwrap_or(&initial))
+                    .collect();
+                let new_players = team.iter().any(|player| !ratings.contains_key(player));
+
+                (TeamRating::from_members(&members), new_players)
+            })
+            .collect();
+
+        for (i, (a, a_new)) in teams.iter().enumerate() {
+            for (j, (b, b_new)) in teams.iter().enumerate().skip(i + 1) {
+                match (game.ranks.get(i), game.ranks.get(j)) {
+                    (Some(rank_a), Some(rank_b)) if rank_a != rank_b => {
+                        predictions.push(Prediction {
+                            game: index,
+                            teams: (i, j),
+                            probability: rater.win_probability(a.as_rating(), b.as_rating()),
+                            won: rank_a < rank_b,
+                            new_players: *a_new || *b_new,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        apply_record(rater, &mut ratings, game, &initial, 1.0)
+            .map_err(|error| ReplayError::RejectedGame { index, error })?;
+    }
+
+    let metrics = if options.exclude_new_players {
+        let scored: Vec<Prediction> = predictions
+            .iter()
+            .filter(|prediction| !prediction.new_players)
+            .copied()
+            .collect();
+        Metrics::from_predictions(&scored, options.calibration_bins)
+    } else {
+        Metrics::from_predictions(&predictions, options.calibration_bins)
+    };
+
+    Ok(PrequentialReport {
+        predictions,
+        metrics,
+        ratings,
+    })
+}
+
+/// ModelResult is the evaluation of one rater configuration in a
+/// `ComparisonReport`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelResult {
+    /// The name of the configuration.
+    pub name: String,
+
+    /// The scores of the predictions of the configuration.
+    pub metrics: Metrics,
+
+    /// How long the evaluation of the configuration took.
+    pub runtime: Duration,
+}
+
+/// ComparisonReport is the result of `compare_models`, with one result per
+/// configuration, in the order they were given.
+///
+/// Its `Display` implementation renders the results as a table with a
+/// header and one row per configuration: the name, log loss, Brier score
+/// and accuracy, and the runtime in milliseconds, separated by whitespace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonReport {
+    /// The results of the configurations.
+    pub models: Vec<ModelResult>,
+}
+
+impl ComparisonReport {
+    /// Returns the configuration with the lowest log loss, or `None` if no
+    /// configuration was compared. Configurations without predictions, whose
+    /// log loss is NaN, are only returned if there is no other.
+    pub fn best_by_log_loss(&self) -> Option<&ModelResult> {
+        self.models
+            .iter()
+            .min_by(|a, b| a.metrics.log_loss.total_cmp(&b.metrics.log_loss))
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .models
+            .iter()
+            .map(|model| model.name.chars().count())
+            .chain(Some("model".len()))
+            .max()
+            .unwrap_or(0);
+
+        writeln!(
+            f,
+            "{:<width$}  {:>10}  {:>10}  {:>10}  {:>12}",
+            "model",
+            "log_loss",
+            "brier",
+            "accuracy",
+            "runtime_ms",
+            width = width
+        )?;
+
+        for model in &self.models {
+            writeln!(
+                f,
+                "{:<width$}  {:>10.6}  {:>10.6}  {:>10.6}  {:>12.3}",
+                model.name,
+                model.metrics.log_loss,
+                model.metrics.brier_score,
+                model.metrics.accuracy,
+                model.runtime.as_secs_f64() * 1000.0,
+                width = width
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Evaluates several rater configurations on the same history of games, to
+/// choose between them. Each configuration is evaluated like `prequential`,
+/// and its runtime measured. The configurations are named for the report,
+/// and the names need not be unique.
+///
+/// The runtime is wall-clock time and includes the bookkeeping of the
+/// evaluation, so it is only comparable between configurations evaluated on
+/// the same machine.
+///
+/// Returns the errors of `replay::replay`. Since every configuration
+/// replays the same history, they are found with the first one.
+///
+/// ```rust
+/// use bbt::eval;
+/// use bbt::replay::GameRecord;
+/// use bbt::{Rater, Rating};
+///
+/// let games = vec![
+///     GameRecord::new(vec![vec!["alice".into()], vec!["bob".into()]], vec![1, 2]),
+///     GameRecord::new(vec![vec!["alice".into()], vec!["bob".into()]], vec![1, 2]),
+/// ];
+///
+/// let configs = [
+///     ("default".to_string(), Rater::default()),
+///     ("volatile".to_string(), Rater::default().with_volatility(1.0, 2.0)),
+/// ];
+///
+/// let report = eval::compare_models(&configs, &games, Rating::default()).unwrap();
+///
+/// assert_eq!(report.models.len(), 2);
+/// println!("{}", report);
+/// ```
+pub fn compare_models(
+    configs: &[(String, Rater)],
+    games: &[GameRecord],
+    initial: Rating,
+) -> Result<ComparisonReport, ReplayError> {
+    let mut models = Vec::with_capacity(configs.len());
+
+    for (name, rater) in configs {
+        let start = Instant::now();
+        let report = prequential(rater, games, initial)?;
+
+        models.push(ModelResult {
+            name: name.clone(),
+            metrics: report.metrics,
+            runtime: start.elapsed(),
+        });
+    }
+
+    Ok(ComparisonReport { models })
+}
@@ -0,0 +1,210 @@
+//! A C interface to `Rater`, enabled by the `ffi` feature.
+//!
+//! The declarations for C and C++ are in `include/bbt.h`. To build a shared
+//! library, run
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! Functions that can fail return `BBT_OK` on success and an error code
+//! otherwise. The positive codes correspond to the variants of `BBTError`,
+//! the negative codes to errors specific to this interface. No function
+//! unwinds into the caller: a panic is caught and reported as `BBT_PANIC`.
+//! Ratings are only written back if the call succeeds.
+
+use std::os::raw::c_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use {BBTError, Outcome, Rater, Rating};
+
+/// The call succeeded.
+pub const BBT_OK: c_int = 0;
+/// See `BBTError::MismatchedLengths`.
+pub const BBT_MISMATCHED_LENGTHS: c_int = 1;
+/// See `BBTError::EmptyTeam`.
+pub const BBT_EMPTY_TEAM: c_int = 2;
+/// See `BBTError::NotEnoughTeams`.
+pub const BBT_NOT_ENOUGH_TEAMS: c_int = 3;
+/// See `BBTError::InvalidRating`.
+pub const BBT_INVALID_RATING: c_int = 4;
+/// See `BBTError::TeamOverflow`.
+pub const BBT_TEAM_OVERFLOW: c_int = 5;
+/// See `BBTError::InvalidRanks`.
+pub const BBT_INVALID_RANKS: c_int = 6;
+/// See `BBTError::NumericalError`.
+pub const BBT_NUMERICAL_ERROR: c_int = 7;
+/// See `BBTError::InvalidParameter`.
+pub const BBT_INVALID_PARAMETER: c_int = 8;
+
+/// A required pointer was null.
+pub const BBT_NULL_POINTER: c_int = -1;
+/// The call panicked. This indicates a bug in bbt.
+pub const BBT_PANIC: c_int = -2;
+/// An argument of the call is out of range, such as an unknown outcome or
+/// team offsets that are not increasing.
+pub const BBT_INVALID_ARGUMENT: c_int = -3;
+
+/// Returns the error code of `error`.
+pub fn error_code(error: &BBTError) -> c_int {
+    match *error {
+        BBTError::MismatchedLengths { .. } => BBT_MISMATCHED_LENGTHS,
+        BBTError::EmptyTeam { .. } => BBT_EMPTY_TEAM,
+        BBTError::NotEnoughTeams { .. } => BBT_NOT_ENOUGH_TEAMS,
+        BBTError::InvalidRating { .. } => BBT_INVALID_RATING,
+        BBTError::TeamOverflow { .. } => BBT_TEAM_OVERFLOW,
+        BBTError::InvalidRanks => BBT_INVALID_RANKS,
+        BBTError::NumericalError { .. } => BBT_NUMERICAL_ERROR,
+        BBTError::InvalidParameter { .. } => BBT_INVALID_PARAMETER,
+    }
+}
+
+/// Runs `f`, turning a panic into `BBT_PANIC`.
+fn guard<T: FnOnce() -> c_int>(f: T) -> c_int {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(BBT_PANIC)
+}
+
+/// Creates a rater with the given β. Returns null if β is negative or not
+/// finite. The rater must be freed with `bbt_rater_free`.
+#[no_mangle]
+pub extern "C" fn bbt_rater_new(beta: f64) -> *mut Rater {
+    if !beta.is_finite() || beta < 0.0 {
+        return std::ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(Rater::new(beta)))
+}
+
+/// Frees a rater created by `bbt_rater_new`. Does nothing if `rater` is
+/// null.
+///
+/// # Safety
+///
+/// `rater` must be null or a pointer returned by `bbt_rater_new` that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bbt_rater_free(rater: *mut Rater) {
+    if !rater.is_null() {
+        drop(Box::from_raw(rater));
+    }
+}
+
+/// Updates the ratings of two players after a duel, like `Rater::duel`. The
+/// outcome is from the first player's perspective: 1 for a win, -1 for a
+/// loss and 0 for a draw.
+///
+/// # Safety
+///
+/// `rater` must be null or a live pointer returned by `bbt_rater_new`, and
+/// the other pointers must be null or valid for reads and writes.
+#[no_mangle]
+pub unsafe extern "C" fn bbt_duel(
+    rater: *const Rater,
+    mu1: *mut f64,
+    sigma1: *mut f64,
+    mu2: *mut f64,
+    sigma2: *mut f64,
+    outcome: c_int,
+) -> c_int {
+    if rater.is_null() || mu1.is_null() || sigma1.is_null() || mu2.is_null() || sigma2.is_null() {
+        return BBT_NULL_POINTER;
+    }
+
+    let outcome = match outcome {
+        1 => Outcome::Win,
+        -1 => Outcome::Loss,
+        0 => Outcome::Draw,
+        _ => return BBT_INVALID_ARGUMENT,
+    };
+
+    guard(|| {
+        let p1 = Rating::new(*mu1, *sigma1);
+        let p2 = Rating::new(*mu2, *sigma2);
+
+        if !p1.is_valid() || !p2.is_valid() {
+            return BBT_INVALID_RATING;
+        }
+
+        let (p1, p2) = (*rater).duel(p1, p2, outcome);
+
+        *mu1 = p1.mu;
+        *sigma1 = p1.sigma;
+        *mu2 = p2.mu;
+        *sigma2 = p2.sigma;
+
+        BBT_OK
+    })
+}
+
+/// Updates the ratings of a match, like `Rater::update_ratings`.
+///
+/// The ratings of all `n_players` players are passed as the arrays `mus` and
+/// `sigmas`, team by team. Team `i` consists of the players from
+/// `team_offsets[i]` up to the offset of the next team, or up to the last
+/// player for the last team, so `team_offsets` holds `n_teams` increasing
+/// offsets that start at zero. `ranks` holds the rank of each team.
+///
+/// # Safety
+///
+/// `rater` must be null or a live pointer returned by `bbt_rater_new`.
+/// `mus` and `sigmas` must be null or valid for reads and writes of
+/// `n_players` values, and `team_offsets` and `ranks` must be null or valid
+/// for reads of `n_teams` values.
+#[no_mangle]
+pub unsafe extern "C" fn bbt_update_ratings(
+    rater: *const Rater,
+    mus: *mut f64,
+    sigmas: *mut f64,
+    n_players: usize,
+    team_offsets: *const usize,
+    n_teams: usize,
+    ranks: *const usize,
+) -> c_int {
+    if rater.is_null()
+        || mus.is_null()
+        || sigmas.is_null()
+        || team_offsets.is_null()
+        || ranks.is_null()
+    {
+        return BBT_NULL_POINTER;
+    }
+
+    guard(|| {
+        let mus = slice::from_raw_parts_mut(mus, n_players);
+        let sigmas = slice::from_raw_parts_mut(sigmas, n_players);
+        let team_offsets = slice::from_raw_parts(team_offsets, n_teams);
+        let ranks = slice::from_raw_parts(ranks, n_teams);
+
+        if team_offsets.first().is_some_and(|&offset| offset != 0)
+            || team_offsets.windows(2).any(|pair| pair[0] > pair[1])
+            || team_offsets
+                .last()
+                .is_some_and(|&offset| offset > n_players)
+        {
+            return BBT_INVALID_ARGUMENT;
+        }
+
+        let teams = (0..n_teams)
+            .map(|i| {
+                let end = team_offsets.get(i + 1).map_or(n_players, |&end| end);
+
+                (team_offsets[i]..end)
+                    .map(|j| Rating::new(mus[j], sigmas[j]))
+                    .collect()
+            })
+            .collect();
+
+        match (*rater).update_ratings(teams, ranks.to_vec()) {
+            Ok(teams) => {
+                for (j, rating) in teams.iter().flatten().enumerate() {
+                    mus[j] = rating.mu;
+                    sigmas[j] = rating.sigma;
+                }
+
+                BBT_OK
+            }
+            Err(error) => error_code(&error),
+        }
+    })
+}
@@ -0,0 +1,123 @@
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::vec::Vec;
+
+use {BBTError, CompensatedSum, Float, Rater, Rating};
+
+impl<F: Float> Rater<F> {
+    /// Returns the win rate that a player can expect against a population of
+    /// players, such as the current player pool: the mean of
+    /// `win_probability` against every rating of `field`, as if the player
+    /// were matched against a random member of it.
+    ///
+    /// Returns `BBTError::InvalidParameter` if `field` is empty.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let field = [Rating::new(25.0, 2.0), Rating::new(35.0, 2.0)];
+    ///
+    /// let win_rate = rater.expected_winrate_vs_field(&Rating::new(30.0, 2.0), &field).unwrap();
+    /// assert!((win_rate - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn expected_winrate_vs_field(
+        &self,
+        player: &Rating<F>,
+        field: &[Rating<F>],
+    ) -> Result<F, BBTError> {
+        if field.is_empty() {
+            return Err(BBTError::InvalidParameter { name: "field" });
+        }
+
+        let mut total = CompensatedSum::default();
+        for opponent in field {
+            total.add(self.win_probability(player, opponent));
+        }
+
+        Ok(total.total() / F::from_f64(field.len() as f64))
+    }
+
+    /// Like `expected_winrate_vs_field`, with the win probabilities weighted
+    /// by how often the player would meet each member of the field, such as
+    /// the number of games they played recently. Weights are relative, so
+    /// they need not sum to one.
+    ///
+    /// Returns `BBTError::InvalidParameter` if `field` is empty, or if
+    /// `weights` is not as long as `field`, contains a negative or infinite
+    /// weight, or only zeros.
+    pub fn expected_winrate_vs_field_weighted(
+        &self,
+        player: &Rating<F>,
+        field: &[Rating<F>],
+        weights: &[F],
+    ) -> Result<F, BBTError> {
+        if field.is_empty() {
+            return Err(BBTError::InvalidParameter { name: "field" });
+        }
+
+        if weights.len() != field.len()
+            || weights
+                .iter()
+                .any(|&weight| !weight.is_finite() || weight < F::ZERO)
+        {
+            return Err(BBTError::InvalidParameter { name: "weights" });
+        }
+
+        let mut total = CompensatedSum::default();
+        let mut total_weight = CompensatedSum::default();
+        for (opponent, &weight) in field.iter().zip(weights) {
+            total.add(weight * self.win_probability(player, opponent));
+            total_weight.add(weight);
+        }
+
+        let total_weight = total_weight.total();
+        if total_weight == F::ZERO || !total_weight.is_finite() {
+            return Err(BBTError::InvalidParameter { name: "weights" });
+        }
+
+        Ok(total.total() / total_weight)
+    }
+
+    /// Returns the expected win rate against `field` of a hypothetical
+    /// player with each mu of `mu_points` and the given sigma, as by
+    /// `expected_winrate_vs_field`, for plotting win rate against rating.
+    /// The win rate grows with mu.
+    ///
+    /// Returns `BBTError::InvalidParameter` if `field` is empty, if sigma is
+    /// negative or not finite, or if a mu is not finite.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let field = vec![Rating::new(25.0, 3.0); 10];
+    ///
+    /// let curve = rater.winrate_curve(&field, &[15.0, 25.0, 35.0], 3.0).unwrap();
+    /// assert!(curve[0] < 0.5 && curve[2] > 0.5);
+    /// assert!((curve[1] - 0.5).abs() < 1e-12);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn winrate_curve(
+        &self,
+        field: &[Rating<F>],
+        mu_points: &[F],
+        sigma: F,
+    ) -> Result<Vec<F>, BBTError> {
+        if field.is_empty() {
+            return Err(BBTError::InvalidParameter { name: "field" });
+        }
+
+        if !sigma.is_finite() || sigma < F::ZERO {
+            return Err(BBTError::InvalidParameter { name: "sigma" });
+        }
+
+        if mu_points.iter().any(|mu| !mu.is_finite()) {
+            return Err(BBTError::InvalidParameter { name: "mu_points" });
+        }
+
+        mu_points
+            .iter()
+            .map(|&mu| self.expected_winrate_vs_field(&Rating::new(mu, sigma), field))
+            .collect()
+    }
+}
@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+use Float;
+
+/// The number of fractional bits of a `Fixed`.
+const FRAC_BITS: u32 = 32;
+
+/// The number of fractional bits used inside `exp` and `ln`.
+const WIDE_BITS: u32 = 62;
+
+/// One in the internal format of `exp` and `ln`.
+const WIDE_ONE: i128 = 1 << WIDE_BITS;
+
+/// ln(2) in the internal format of `exp` and `ln`, rounded to nearest.
+const WIDE_LN2: i128 = 0x2C5C_85FD_F473_DE6B;
+
+/// Fixed is a signed Q32.32 fixed-point number: a 64-bit integer counting
+/// units of 2^-32. All of its operations, including `exp` and `sqrt`, are
+/// computed with integer arithmetic, so they give the same results on every
+/// platform. `RatingI` and `RaterI` use it to make rating updates
+/// reproducible bit for bit.
+///
+/// Fixed covers the range of about ±2.1e9 with a resolution of about 2.3e-10.
+/// Results outside of that range saturate to `Fixed::MAX` or `Fixed::MIN`,
+/// which count as non-finite, like the infinities of a float. Operations
+/// that would produce NaN, such as the square root of a negative number,
+/// return `Fixed::MIN`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// The largest value, which counts as positive infinity.
+    pub const MAX: Fixed = Fixed(i64::MAX);
+
+    /// The smallest value, which counts as negative infinity or NaN.
+    pub const MIN: Fixed = Fixed(i64::MIN);
+
+    /// Creates a number from its raw representation in units of 2^-32.
+    pub const fn from_bits(bits: i64) -> Fixed {
+        Fixed(bits)
+    }
+
+    /// Returns the raw representation in units of 2^-32.
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1u64 << FRAC_BITS) as f64
+    }
+
+    /// Saturates a wide intermediate result to the range of a `Fixed`.
+    fn saturate(value: i128) -> Fixed {
+        if value >= i128::from(i64::MAX) {
+            Fixed::MAX
+        } else if value <= i128::from(i64::MIN) {
+            Fixed::MIN
+        } else {
+            Fixed(value as i64)
+        }
+    }
+
+    /// Computes `x * 2^-shift`, rounding to nearest.
+    fn shift_round(x: i128, shift: u32) -> i128 {
+        if shift == 0 {
+            x
+        } else if shift >= 127 {
+            0
+        } else {
+            (x + (1 << (shift - 1))) >> shift
+        }
+    }
+
+    /// Computes `ln(self)` for positive values.
+    fn ln(self) -> Fixed {
+        if self.0 <= 0 {
+            return Fixed::MIN;
+        }
+
+        // self = m * 2^e with m in [1, 2).
+        let msb = 63 - self.0.leading_zeros();
+        let e = i128::from(msb) - i128::from(FRAC_BITS);
+        let m = i128::from(self.0) << (WIDE_BITS - msb);
+
+        // ln(m) = 2 * atanh(s) = 2 * (s + s³/3 + s⁵/5 + ...) with s <= 1/3.
+        let s = ((m - WIDE_ONE) << WIDE_BITS) / (m + WIDE_ONE);
+        let s_sq = (s * s) >> WIDE_BITS;
+
+        let mut power = s;
+        let mut series = 0;
+        let mut n = 1;
+        while power != 0 {
+            series += power / n;
+            power = (power * s_sq) >> WIDE_BITS;
+            n += 2;
+        }
+
+        let ln = e * WIDE_LN2 + 2 * series;
+        Fixed::saturate(Fixed::shift_round(ln, WIDE_BITS - FRAC_BITS))
+    }
+
+    /// Computes `self^n` for integer `n` by repeated squaring.
+    fn powi(self, mut n: i64) -> Fixed {
+        if n < 0 {
+            return Fixed::ONE / self.powi(-n);
+        }
+
+        let mut base = self;
+        let mut result = Fixed::ONE;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            n >>= 1;
+        }
+
+        result
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, other: Fixed) -> Fixed {
+        let product = i128::from(self.0) * i128::from(other.0);
+        Fixed::saturate(Fixed::shift_round(product, FRAC_BITS))
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    fn div(self, other: Fixed) -> Fixed {
+        if other.0 == 0 {
+            return if self.0 > 0 { Fixed::MAX } else { Fixed::MIN };
+        }
+
+        let numerator = i128::from(self.0) << FRAC_BITS;
+        let denominator = i128::from(other.0);
+        let mut quotient = numerator / denominator;
+
+        // Rounds to nearest, with ties away from zero.
+        let remainder = numerator % denominator;
+        if 2 * remainder.abs() >= denominator.abs() {
+            quotient += if (numerator < 0) == (denominator < 0) {
+                1
+            } else {
+                -1
+            };
+        }
+
+        Fixed::saturate(quotient)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Float for Fixed {
+    const ZERO: Fixed = Fixed(0);
+    const ONE: Fixed = Fixed(1 << FRAC_BITS);
+
+    fn from_f64(value: f64) -> Fixed {
+        if value.is_nan() {
+            return Fixed::MIN;
+        }
+
+        // Scaling by a power of two is exact, and the conversion saturates,
+        // so this only rounds once, identically on every platform.
+        let scaled = value * (1u64 << FRAC_BITS) as f64;
+        let rounded = if scaled >= 0.0 {
+            scaled + 0.5
+        } else {
+            scaled - 0.5
+        };
+
+        Fixed(rounded as i64)
+    }
+
+    fn exp(self) -> Fixed {
+        // exp(x) rounds to zero below -23 and saturates above 22.
+        let x = self.0.clamp(-64 << FRAC_BITS, 64 << FRAC_BITS);
+        let x = i128::from(x) << (WIDE_BITS - FRAC_BITS);
+
+        // exp(x) = 2^k * exp(r) with |r| <= ln(2) / 2.
+        let k = (x + WIDE_LN2 / 2).div_euclid(WIDE_LN2);
+        let r = x - k * WIDE_LN2;
+
+        let mut term = WIDE_ONE;
+        let mut series = 0;
+        let mut n = 1;
+        while term != 0 {
+            series += term;
+            term = ((term * r) >> WIDE_BITS) / n;
+            n += 1;
+        }
+
+        let shift = i128::from(WIDE_BITS - FRAC_BITS) - k;
+        if shift >= 0 {
+            Fixed::saturate(Fixed::shift_round(series, shift as u32))
+        } else {
+            Fixed::saturate(series << -shift)
+        }
+    }
+
+    fn sqrt(self) -> Fixed {
+        if self.0 < 0 {
+            return Fixed::MIN;
+        }
+
+        // The square root of the raw value scaled by 2^32, by Newton's
+        // method on integers, rounded to nearest.
+        let n = (self.0 as u128) << FRAC_BITS;
+        if n == 0 {
+            return Fixed::ZERO;
+        }
+
+        let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+        loop {
+            let next = (x + n / x) / 2;
+            if next >= x {
+                break;
+            }
+            x = next;
+        }
+
+        if n - x * x > x {
+            x += 1;
+        }
+
+        Fixed(x as i64)
+    }
+
+    fn abs(self) -> Fixed {
+        Fixed(self.0.saturating_abs())
+    }
+
+    fn powf(self, n: Fixed) -> Fixed {
+        if n.0 & ((1 << FRAC_BITS) - 1) == 0 {
+            return self.powi(n.0 >> FRAC_BITS);
+        }
+
+        match self.0.cmp(&0) {
+            Ordering::Less => return Fixed::MIN,
+            Ordering::Equal if n.0 > 0 => return Fixed::ZERO,
+            Ordering::Equal => return Fixed::MAX,
+            Ordering::Greater => (),
+        }
+
+        (n * self.ln()).exp()
+    }
+
+    fn is_finite(self) -> bool {
+        self != Fixed::MAX && self != Fixed::MIN
+    }
+
+    fn total_cmp(&self, other: &Fixed) -> Ordering {
+        self.cmp(other)
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+/// Float is the scalar type that ratings are computed in. It is implemented
+/// for `f64`, which is the default everywhere, and for `f32`, which is
+/// faster and smaller on some targets at the cost of precision.
+pub trait Float:
+    Copy
+    + Default
+    + PartialOrd
+    + fmt::Debug
+    + fmt::Display
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + 'static
+{
+    /// Zero.
+    const ZERO: Self;
+
+    /// One.
+    const ONE: Self;
+
+    /// Converts an `f64` constant to this type, rounding if necessary.
+    fn from_f64(value: f64) -> Self;
+
+    /// Returns `e^self`.
+    fn exp(self) -> Self;
+
+    /// Returns the square root of `self`.
+    fn sqrt(self) -> Self;
+
+    /// Returns the absolute value of `self`.
+    fn abs(self) -> Self;
+
+    /// Returns `self` raised to the power `n`.
+    fn powf(self, n: Self) -> Self;
+
+    /// Returns whether `self` is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+
+    /// Returns the ordering of `self` and `other` according to the IEEE 754
+    /// totalOrder predicate.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    /// Computes the pairwise omega and delta terms of team `team_idx` against
+    /// all other teams with a vectorized kernel, returning the number of
+    /// terms, or `None` if this type has no vectorized kernel. This is an
+    /// implementation detail of the `simd` feature.
+    #[doc(hidden)]
+    #[cfg(feature = "simd")]
+    fn pair_terms_simd(
+        _team_idx: usize,
+        _team_mu: &[Self],
+        _team_sigma_sq: &[Self],
+        _ranks: &[usize],
+        _beta_sq: Self,
+        _terms: (&mut [Self], &mut [Self]),
+    ) -> Option<usize> {
+        None
+    }
+}
+
+macro_rules! impl_float {
+    ($t:ident, $exp:ident, $sqrt:ident, $abs:ident, $powf:ident $(, $simd:item)*) => {
+        impl Float for $t {
+            const ZERO: $t = 0.0;
+            const ONE: $t = 1.0;
+
+            fn from_f64(value: f64) -> $t {
+                value as $t
+            }
+
+            #[cfg(feature = "std")]
+            fn exp(self) -> $t {
+                $t::exp(self)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn exp(self) -> $t {
+                ::libm::$exp(self)
+            }
+
+            #[cfg(feature = "std")]
+            fn sqrt(self) -> $t {
+                $t::sqrt(self)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn sqrt(self) -> $t {
+                ::libm::$sqrt(self)
+            }
+
+            #[cfg(feature = "std")]
+            fn abs(self) -> $t {
+                $t::abs(self)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn abs(self) -> $t {
+                ::libm::$abs(self)
+            }
+
+            #[cfg(feature = "std")]
+            fn powf(self, n: $t) -> $t {
+                $t::powf(self, n)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn powf(self, n: $t) -> $t {
+                ::libm::$powf(self, n)
+            }
+
+            fn is_finite(self) -> bool {
+                $t::is_finite(self)
+            }
+
+            fn total_cmp(&self, other: &$t) -> Ordering {
+                $t::total_cmp(self, other)
+            }
+
+            $($simd)*
+        }
+    };
+}
+
+impl_float!(f32, expf, sqrtf, fabsf, powf);
+impl_float!(
+    f64,
+    exp,
+    sqrt,
+    fabs,
+    pow,
+    #[cfg(feature = "simd")]
+    fn pair_terms_simd(
+        team_idx: usize,
+        team_mu: &[f64],
+        team_sigma_sq: &[f64],
+        ranks: &[usize],
+        beta_sq: f64,
+        terms: (&mut [f64], &mut [f64]),
+    ) -> Option<usize> {
+        ::simd::pair_terms(team_idx, team_mu, team_sigma_sq, ranks, beta_sq, terms)
+    }
+);
@@ -0,0 +1,152 @@
+//! Formatting of rating changes for match-end screens.
+//!
+//! `format_delta` compares a rating before and after a match and displays
+//! the changes with explicit signs, like "+1.3 (now 27.6)". Changes that
+//! round to zero are shown as "0.0" rather than "+0.0" or "-0.0".
+//!
+//! ```rust
+//! use bbt::{Outcome, Rater, Rating};
+//!
+//! let before = Rating::default();
+//! let (after, _) = Rater::default().duel(before, Rating::default(), Outcome::Win);
+//!
+//! let delta = bbt::fmt::format_delta(&before, &after);
+//! assert_eq!(format!("{:#}", delta), "mu +2.6 (now 27.6), sigma -0.3");
+//! assert_eq!(
+//!     delta.to_string(),
+//!     "mu +2.6 (now 27.6)\nsigma -0.3 (now 8.1)\nestimate +3.4 (now 3.4)"
+//! );
+//! ```
+
+use std::fmt;
+
+use {Float, Rating};
+
+/// Returns the change from `before` to `after`, for display.
+pub fn format_delta<F: Float>(before: &Rating<F>, after: &Rating<F>) -> RatingDeltaDisplay<F> {
+    RatingDeltaDisplay {
+        before: *before,
+        after: *after,
+        precision: 1,
+    }
+}
+
+/// RatingDeltaDisplay is the change of a rating over a match, as returned by
+/// `format_delta`.
+///
+/// It displays one line each for the changes of mu, sigma and the
+/// conservative estimate `mu - 3 * sigma`, followed by the new value, e.g.
+/// "mu +1.3 (now 27.6)". The alternate flag (`{:#}`) displays a compact
+/// one-line summary of the changes of mu and sigma instead. Values are
+/// shown with one decimal by default.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RatingDeltaDisplay<F: Float = f64> {
+    before: Rating<F>,
+    after: Rating<F>,
+    precision: usize,
+}
+
+impl<F: Float> RatingDeltaDisplay<F> {
+    /// Displays the values with the given number of decimals.
+    pub fn with_precision(mut self, precision: usize) -> RatingDeltaDisplay<F> {
+        self.precision = precision;
+        self
+    }
+
+    /// Returns the rating before the match.
+    pub fn before(&self) -> &Rating<F> {
+        &self.before
+    }
+
+    /// Returns the rating after the match.
+    pub fn after(&self) -> &Rating<F> {
+        &self.after
+    }
+
+    /// Returns the change of mu.
+    pub fn mu_delta(&self) -> F {
+        self.after.mu - self.before.mu
+    }
+
+    /// Returns the change of sigma, which is negative when the rating became
+    /// more certain.
+    pub fn sigma_delta(&self) -> F {
+        self.after.sigma - self.before.sigma
+    }
+
+    /// Returns the change of the conservative estimate `mu - 3 * sigma`.
+    pub fn estimate_delta(&self) -> F {
+        self.after.conservative_estimate() - self.before.conservative_estimate()
+    }
+
+    /// Writes `value` with an explicit sign, or as an unsigned zero if it
+    /// rounds to zero.
+    fn write_delta(&self, f: &mut fmt::Formatter, value: F) -> fmt::Result {
+        if rounds_to_zero(value, self.precision) {
+            write!(f, "{:.*}", self.precision, F::ZERO)
+        } else if value < F::ZERO {
+            write!(f, "-{:.*}", self.precision, value.abs())
+        } else {
+            write!(f, "+{:.*}", self.precision, value)
+        }
+    }
+
+    /// Writes `value`, without a sign for values that round to zero.
+    fn write_value(&self, f: &mut fmt::Formatter, value: F) -> fmt::Result {
+        if rounds_to_zero(value, self.precision) {
+            write!(f, "{:.*}", self.precision, F::ZERO)
+        } else {
+            write!(f, "{:.*}", self.precision, value)
+        }
+    }
+
+    fn write_line(&self, f: &mut fmt::Formatter, name: &str, delta: F, now: F) -> fmt::Result {
+        write!(f, "{} ", name)?;
+        self.write_delta(f, delta)?;
+        f.write_str(" (now ")?;
+        self.write_value(f, now)?;
+        f.write_str(")")
+    }
+}
+
+impl<F: Float> fmt::Display for RatingDeltaDisplay<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            self.write_line(f, "mu", self.mu_delta(), self.after.mu)?;
+            f.write_str(", sigma ")?;
+            return self.write_delta(f, self.sigma_delta());
+        }
+
+        self.write_line(f, "mu", self.mu_delta(), self.after.mu)?;
+        f.write_str("\n")?;
+        self.write_line(f, "sigma", self.sigma_delta(), self.after.sigma)?;
+        f.write_str("\n")?;
+        self.write_line(
+            f,
+            "estimate",
+            self.estimate_delta(),
+            self.after.conservative_estimate(),
+        )
+    }
+}
+
+/// Returns whether the finite `value` is displayed as zero with `precision`
+/// decimals, by formatting it and looking for a non-zero digit, so that the
+/// result agrees with the rounding of the formatter.
+fn rounds_to_zero<F: Float>(value: F, precision: usize) -> bool {
+    struct Digits {
+        non_zero: bool,
+    }
+
+    impl fmt::Write for Digits {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.non_zero |= s.bytes().any(|byte| (b'1'..=b'9').contains(&byte));
+            Ok(())
+        }
+    }
+
+    let mut digits = Digits { non_zero: false };
+    let _ = fmt::write(&mut digits, format_args!("{:.*}", precision, value.abs()));
+
+    value.is_finite() && !digits.non_zero
+}
@@ -0,0 +1,151 @@
+//! Short-term form, tracked alongside the main rating.
+//!
+//! The main rating converges on a player's long-term skill and then barely
+//! moves, which is what a ranking needs but hides a hot or cold streak. A
+//! `FormRating` is a second rating of the same player that reacts quickly to
+//! recent results and is pulled back toward the main rating after every
+//! game, so that it only remembers the last few games. It is meant for
+//! display, such as an arrow next to a name, and never feeds back into the
+//! main rating.
+//!
+//! ```rust
+//! use bbt::form::{self, FormRating};
+//! use bbt::{Outcome, Rater, Rating};
+//!
+//! let rater = Rater::default();
+//! let mut main = Rating::new(25.0, 1.0);
+//! let mut form = FormRating::new(&main);
+//! let opponent = Rating::new(25.0, 1.0);
+//!
+//! for _ in 0..5 {
+//!     form::update_pair(&rater, &mut main, &mut form, &opponent, Outcome::Win);
+//! }
+//!
+//! assert!(form.delta_vs_main(&main) > 1.0);
+//! ```
+
+use {Outcome, Rater, Rating};
+
+/// The factor by which the β-parameter of the rater is multiplied to rate
+/// the form. A larger β makes single results less conclusive, which keeps
+/// the form from overreacting to its own wide sigma.
+pub const BETA_FACTOR: f64 = 2.0;
+
+/// The default share of the distance to the main rating that the form
+/// recovers after every game.
+pub const DEFAULT_DECAY: f64 = 0.2;
+
+/// FormRating is the short-term form of a player, kept next to their main
+/// rating. It is updated by `update_pair`, which rates the game with a rater
+/// of `BETA_FACTOR` times the β-parameter, keeps the sigma of the form at
+/// least β so that it never settles, and then moves the mu of the form
+/// toward that of the main rating by the decay of the form.
+///
+/// With the `serde` feature, forms are serialized like ratings with an
+/// additional `decay` field, which may be missing and is then read as
+/// `DEFAULT_DECAY`. Binary formats use the tuple `(mu, sigma, decay)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FormRating {
+    rating: Rating,
+    decay: f64,
+}
+
+impl FormRating {
+    /// Starts tracking the form of a player at their main rating, with the
+    /// default decay.
+    pub fn new(main: &Rating) -> FormRating {
+        FormRating::with_decay(*main, DEFAULT_DECAY)
+    }
+
+    /// Creates a form from a rating and the share of the distance to the
+    /// main rating it recovers after every game. The decay is clamped to
+    /// `[0, 1]`; zero disables it, and one resets the form to the main
+    /// rating after every game.
+    pub fn with_decay(rating: Rating, decay: f64) -> FormRating {
+        let decay = if decay.is_nan() {
+            DEFAULT_DECAY
+        } else {
+            decay.clamp(0.0, 1.0)
+        };
+
+        FormRating { rating, decay }
+    }
+
+    /// Returns the form as a rating.
+    pub fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    /// Returns the mu of the form.
+    pub fn mu(&self) -> f64 {
+        self.rating.mu
+    }
+
+    /// Returns the sigma of the form.
+    pub fn sigma(&self) -> f64 {
+        self.rating.sigma
+    }
+
+    /// Returns the decay of the form.
+    pub fn decay_rate(&self) -> f64 {
+        self.decay
+    }
+
+    /// Returns how far the form is above the main rating, in points of mu,
+    /// negative if the player is out of form.
+    pub fn delta_vs_main(&self, main: &Rating) -> f64 {
+        self.rating.mu - main.mu
+    }
+
+    /// Moves the mu of the form toward that of the main rating by the decay,
+    /// as after a game. Calling this for periods without games lets the form
+    /// of an inactive player return to their main rating.
+    pub fn decay(&mut self, main: &Rating) {
+        let mu = self.rating.mu + self.decay * (main.mu - self.rating.mu);
+        self.rating = Rating::new(mu, self.rating.sigma);
+    }
+}
+
+/// Rates a game of a player against an opponent, updating both the main
+/// rating and the form of the player.
+///
+/// The main rating is updated exactly as by `rater.duel`, so tracking the
+/// form does not change it. The form is rated against the main rating of the
+/// opponent, with a rater of `BETA_FACTOR` times the β-parameter and no
+/// other settings, and then decays toward the updated main rating. While the
+/// main rating is still less certain than β, it moves further than the form
+/// does.
+///
+/// ```rust
+/// use bbt::form::{self, FormRating};
+/// use bbt::{Outcome, Rater, Rating};
+///
+/// let rater = Rater::default();
+/// let settled = Rating::new(25.0, 1.0);
+/// let mut main = settled;
+/// let mut form = FormRating::new(&main);
+///
+/// form::update_pair(&rater, &mut main, &mut form, &settled, Outcome::Loss);
+///
+/// assert_eq!(main, rater.duel(settled, settled, Outcome::Loss).0);
+/// assert!(form.delta_vs_main(&main) < 0.0);
+/// ```
+pub fn update_pair(
+    rater: &Rater,
+    main: &mut Rating,
+    form: &mut FormRating,
+    opponent_main: &Rating,
+    outcome: Outcome,
+) {
+    *main = rater.duel(*main, *opponent_main, outcome).0;
+
+    let beta = rater.beta();
+    let sigma = form.rating.sigma.max(beta);
+    let form_rater = Rater::new(beta * BETA_FACTOR);
+    let rated = form_rater
+        .duel(Rating::new(form.rating.mu, sigma), *opponent_main, outcome)
+        .0;
+
+    form.rating = Rating::new(rated.mu, rated.sigma.max(beta));
+    form.decay(main);
+}
@@ -0,0 +1,104 @@
+use Rating;
+
+/// GlickoScale maps Glicko-2 ratings and rating deviations (RD) to the mu and
+/// sigma of a rating and back. Both are mapped linearly with the same slope,
+/// so that rating differences and deviations stay comparable: by default, a
+/// rating of 1500 corresponds to a mu of 25, and an RD of 350 to a sigma of
+/// 25/3, i.e. 42 Glicko points are one point of mu.
+///
+/// The volatility of Glicko-2 has no counterpart in this model and is
+/// ignored; bbt's uncertainty only shrinks as players play.
+///
+/// An RD of 0 would give a sigma of 0, which would freeze the rating for
+/// good, so sigma is at least the scale's minimum sigma, which is 0.01 by
+/// default.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlickoScale {
+    anchor_rating: f64,
+    anchor_rd: f64,
+    anchor_mu: f64,
+    anchor_sigma: f64,
+    min_sigma: f64,
+}
+
+impl GlickoScale {
+    /// Creates a scale on which a Glicko rating of `rating` corresponds to a
+    /// mu of `mu`, and an RD of `rd` to a sigma of `sigma`.
+    pub fn new(rating: f64, rd: f64, mu: f64, sigma: f64) -> GlickoScale {
+        GlickoScale {
+            anchor_rating: rating,
+            anchor_rd: rd,
+            anchor_mu: mu,
+            anchor_sigma: sigma,
+            min_sigma: 0.01,
+        }
+    }
+
+    /// Returns the scale with the given minimum sigma.
+    pub fn with_min_sigma(mut self, min_sigma: f64) -> GlickoScale {
+        self.min_sigma = min_sigma;
+        self
+    }
+
+    /// Returns the minimum sigma of ratings converted from Glicko-2.
+    pub fn min_sigma(&self) -> f64 {
+        self.min_sigma
+    }
+}
+
+impl Default for GlickoScale {
+    /// Returns the scale anchoring 1500 and 350 to 25 and 25/3.
+    fn default() -> GlickoScale {
+        GlickoScale::new(1500.0, 350.0, 25.0, 25.0 / 3.0)
+    }
+}
+
+/// Rounds `x` to nine decimal places. This undoes the rounding errors of
+/// converting to the model's scale and back for values with at most nine
+/// decimal places, which covers what Glicko-2 systems export.
+fn round_to_nine_places(x: f64) -> f64 {
+    let scaled = x * 1e9;
+
+    // Beyond 2^53, doubles are integers anyway.
+    if scaled.is_nan() || scaled.abs() >= 9_007_199_254_740_992.0 {
+        return x;
+    }
+
+    let rounded = if scaled >= 0.0 {
+        scaled + 0.5
+    } else {
+        scaled - 0.5
+    };
+
+    rounded as i64 as f64 / 1e9
+}
+
+impl Rating {
+    /// Returns the rating corresponding to the given Glicko-2 rating and RD.
+    /// The volatility is not needed; see `GlickoScale`.
+    pub fn from_glicko2(rating: f64, rd: f64, scale: &GlickoScale) -> Rating {
+        let mu =
+            scale.anchor_mu + (rating - scale.anchor_rating) * scale.anchor_sigma / scale.anchor_rd;
+        let sigma = rd * scale.anchor_sigma / scale.anchor_rd;
+
+        Rating::new(
+            mu,
+            if sigma < scale.min_sigma {
+                scale.min_sigma
+            } else {
+                sigma
+            },
+        )
+    }
+
+    /// Returns the Glicko-2 rating and RD corresponding to this rating,
+    /// rounded to nine decimal places. Converting a Glicko-2 rating and back
+    /// returns it exactly, unless its sigma was raised to the minimum.
+    pub fn to_glicko2(&self, scale: &GlickoScale) -> (f64, f64) {
+        let rating = scale.anchor_rating
+            + (self.mu - scale.anchor_mu) * scale.anchor_rd / scale.anchor_sigma;
+        let rd = self.sigma * scale.anchor_rd / scale.anchor_sigma;
+
+        (round_to_nine_places(rating), round_to_nine_places(rd))
+    }
+}
@@ -0,0 +1,347 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::collections::vec_deque::{self, VecDeque};
+#[cfg(any(feature = "std", test))]
+use std::collections::vec_deque::{self, VecDeque};
+
+use Rating;
+
+/// RatingHistory records the ratings of a player over time, for plotting
+/// and for showing peak ratings. Every snapshot is a `(time, Rating)` pair,
+/// where the time is a sequence number or a timestamp, whichever the caller
+/// prefers; it is only stored, never interpreted.
+///
+/// A history created with `RatingHistory::bounded` keeps only the most
+/// recent snapshots, so its memory stays fixed. The peaks are tracked over
+/// every snapshot ever pushed, including evicted ones.
+///
+/// ```rust
+/// let mut history = bbt::RatingHistory::bounded(2);
+/// history.push(1, bbt::Rating::new(25.0, 8.0));
+/// history.push(2, bbt::Rating::new(30.0, 4.0));
+/// history.push(3, bbt::Rating::new(28.0, 3.0));
+///
+/// assert_eq!(history.len(), 2);
+/// assert_eq!(history.peak_mu(), Some(&(2, bbt::Rating::new(30.0, 4.0))));
+/// assert_eq!(history.delta_over_last(1), Some(-2.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RatingHistory {
+    snapshots: VecDeque<(i64, Rating)>,
+    capacity: Option<usize>,
+    peak_mu: Option<(i64, Rating)>,
+    peak_conservative: Option<(i64, Rating)>,
+}
+
+impl RatingHistory {
+    /// Creates an empty history that keeps every snapshot.
+    pub fn new() -> RatingHistory {
+        RatingHistory {
+            snapshots: VecDeque::new(),
+            capacity: None,
+            peak_mu: None,
+            peak_conservative: None,
+        }
+    }
+
+    /// Creates an empty history that keeps the `capacity` most recent
+    /// snapshots and evicts older ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn bounded(capacity: usize) -> RatingHistory {
+        assert!(capacity > 0, "a history must keep at least one snapshot");
+
+        RatingHistory {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+            peak_mu: None,
+            peak_conservative: None,
+        }
+    }
+
+    /// Returns the maximum number of snapshots kept, or `None` if the
+    /// history is unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns the number of snapshots kept.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns whether no snapshots are kept.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Records the rating at the given time, evicting the oldest snapshot
+    /// if the history is full.
+    pub fn push(&mut self, time: i64, rating: Rating) {
+        if Some(self.snapshots.len()) == self.capacity {
+            self.snapshots.pop_front();
+        }
+
+        // Earlier snapshots win ties, so a peak is dated to when it was
+        // first reached.
+        let is_higher = |peak: &Option<(i64, Rating)>, key: fn(&Rating) -> f64| match *peak {
+            Some((_, ref peak)) => key(&rating) > key(peak),
+            None => true,
+        };
+
+        if is_higher(&self.peak_mu, |rating| rating.mu) {
+            self.peak_mu = Some((time, rating));
+        }
+
+        if is_higher(&self.peak_conservative, Rating::conservative_estimate) {
+            self.peak_conservative = Some((time, rating));
+        }
+
+        self.snapshots.push_back((time, rating));
+    }
+
+    /// Returns the most recent snapshot.
+    pub fn current(&self) -> Option<&(i64, Rating)> {
+        self.snapshots.back()
+    }
+
+    /// Returns the snapshot with the highest mu ever pushed.
+    pub fn peak_mu(&self) -> Option<&(i64, Rating)> {
+        self.peak_mu.as_ref()
+    }
+
+    /// Returns the snapshot with the highest conservative skill estimate
+    /// `mu - 3 * sigma` ever pushed.
+    pub fn peak_conservative(&self) -> Option<&(i64, Rating)> {
+        self.peak_conservative.as_ref()
+    }
+
+    /// Returns the snapshot at the given index, counting from the oldest
+    /// snapshot kept.
+    pub fn at(&self, index: usize) -> Option<&(i64, Rating)> {
+        self.snapshots.get(index)
+    }
+
+    /// Returns an iterator over the snapshots kept, from oldest to newest.
+    pub fn iter(&self) -> vec_deque::Iter<'_, (i64, Rating)> {
+        self.snapshots.iter()
+    }
+
+    /// Returns how much mu changed over the last `n` snapshots, i.e. the
+    /// difference between the current mu and the mu `n` snapshots earlier,
+    /// or `None` if fewer than `n + 1` snapshots are kept.
+    pub fn delta_over_last(&self, n: usize) -> Option<f64> {
+        let current = self.current()?;
+        let earlier = self.snapshots.len().checked_sub(n + 1)?;
+
+        Some(current.1.mu - self.snapshots[earlier].1.mu)
+    }
+}
+
+impl Default for RatingHistory {
+    fn default() -> RatingHistory {
+        RatingHistory::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a RatingHistory {
+    type Item = &'a (i64, Rating);
+    type IntoIter = vec_deque::Iter<'a, (i64, Rating)>;
+
+    fn into_iter(self) -> vec_deque::Iter<'a, (i64, Rating)> {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    #[cfg(not(any(feature = "std", test)))]
+    use alloc::collections::VecDeque;
+    #[cfg(any(feature = "std", test))]
+    use std::collections::VecDeque;
+    use std::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+    use super::RatingHistory;
+    use Rating;
+
+    /// Histories are serialized as a struct with the fields `capacity`
+    /// (`None` if unbounded), `snapshots`, a sequence of `(time, rating)`
+    /// pairs from oldest to newest, and the all-time peaks `peak_mu` and
+    /// `peak_conservative`, which may predate the snapshots kept.
+    impl Serialize for RatingHistory {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("RatingHistory", 4)?;
+            state.serialize_field("capacity", &self.capacity)?;
+            state.serialize_field("snapshots", &self.snapshots)?;
+            state.serialize_field("peak_mu", &self.peak_mu)?;
+            state.serialize_field("peak_conservative", &self.peak_conservative)?;
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RatingHistory {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            enum Field {
+                Capacity,
+                Snapshots,
+                PeakMu,
+                PeakConservative,
+            }
+
+            impl<'de> Deserialize<'de> for Field {
+                fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    struct FieldVisitor;
+
+                    impl<'de> Visitor<'de> for FieldVisitor {
+                        type Value = Field;
+
+                        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                            formatter.write_str(
+                                "`capacity`, `snapshots`, `peak_mu` or `peak_conservative`",
+                            )
+                        }
+
+                        fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                        where
+                            E: de::Error,
+                        {
+                            match value {
+                                "capacity" => Ok(Field::Capacity),
+                                "snapshots" => Ok(Field::Snapshots),
+                                "peak_mu" => Ok(Field::PeakMu),
+                                "peak_conservative" => Ok(Field::PeakConservative),
+                                _ => Err(de::Error::unknown_field(value, FIELDS)),
+                            }
+                        }
+                    }
+
+                    deserializer.deserialize_identifier(FieldVisitor)
+                }
+            }
+
+            struct HistoryVisitor;
+
+            impl<'de> Visitor<'de> for HistoryVisitor {
+                type Value = RatingHistory;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("struct RatingHistory")
+                }
+
+                fn visit_seq<V>(self, mut seq: V) -> Result<RatingHistory, V::Error>
+                where
+                    V: SeqAccess<'de>,
+                {
+                    let capacity = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    let snapshots = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    let peak_mu = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                    let peak_conservative = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+
+                    build(capacity, snapshots, peak_mu, peak_conservative)
+                }
+
+                fn visit_map<V>(self, mut map: V) -> Result<RatingHistory, V::Error>
+                where
+                    V: MapAccess<'de>,
+                {
+                    let mut capacity = None;
+                    let mut snapshots = None;
+                    let mut peak_mu = None;
+                    let mut peak_conservative = None;
+
+                    while let Some(key) = map.next_key()? {
+                        match key {
+                            Field::Capacity => {
+                                if capacity.is_some() {
+                                    return Err(de::Error::duplicate_field("capacity"));
+                                }
+                                capacity = Some(map.next_value()?);
+                            }
+                            Field::Snapshots => {
+                                if snapshots.is_some() {
+                                    return Err(de::Error::duplicate_field("snapshots"));
+                                }
+                                snapshots = Some(map.next_value()?);
+                            }
+                            Field::PeakMu => {
+                                if peak_mu.is_some() {
+                                    return Err(de::Error::duplicate_field("peak_mu"));
+                                }
+                                peak_mu = Some(map.next_value()?);
+                            }
+                            Field::PeakConservative => {
+                                if peak_conservative.is_some() {
+                                    return Err(de::Error::duplicate_field("peak_conservative"));
+                                }
+                                peak_conservative = Some(map.next_value()?);
+                            }
+                        }
+                    }
+
+                    build(
+                        capacity.ok_or_else(|| de::Error::missing_field("capacity"))?,
+                        snapshots.ok_or_else(|| de::Error::missing_field("snapshots"))?,
+                        peak_mu.unwrap_or(None),
+                        peak_conservative.unwrap_or(None),
+                    )
+                }
+            }
+
+            const FIELDS: &[&str] = &["capacity", "snapshots", "peak_mu", "peak_conservative"];
+            deserializer.deserialize_struct("RatingHistory", FIELDS, HistoryVisitor)
+        }
+    }
+
+    /// Rebuilds a history, checking that the snapshots fit the capacity and
+    /// that there are peaks if there are snapshots.
+    fn build<E: de::Error>(
+        capacity: Option<usize>,
+        snapshots: VecDeque<(i64, Rating)>,
+        peak_mu: Option<(i64, Rating)>,
+        peak_conservative: Option<(i64, Rating)>,
+    ) -> Result<RatingHistory, E> {
+        if capacity == Some(0) {
+            return Err(E::custom("the capacity of a history must not be zero"));
+        }
+
+        if let Some(capacity) = capacity.filter(|&capacity| snapshots.len() > capacity) {
+            return Err(E::custom(format_args!(
+                "the history has {} snapshots, more than its capacity of {}",
+                snapshots.len(),
+                capacity
+            )));
+        }
+
+        if !snapshots.is_empty() && (peak_mu.is_none() || peak_conservative.is_none()) {
+            return Err(E::custom("a history with snapshots must have peaks"));
+        }
+
+        Ok(RatingHistory {
+            snapshots,
+            capacity,
+            peak_mu,
+            peak_conservative,
+        })
+    }
+}
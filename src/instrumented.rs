@@ -0,0 +1,144 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use {BBTError, Float, Outcome, Rater, Rating};
+
+/// RaterStats are the counters of an `InstrumentedRater`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RaterStats {
+    /// The number of matches and duels that were rated.
+    pub updates: u64,
+
+    /// The number of player ratings in those matches and duels.
+    pub players: u64,
+
+    /// The number of variance reductions that had to be clamped to the sigma
+    /// floor (see `UpdateReport::clamps`).
+    pub clamps: u64,
+
+    /// The number of matches that were rejected with an error, and of duels
+    /// that were not rated because one of the ratings was invalid.
+    pub errors: u64,
+}
+
+/// InstrumentedRater wraps a `Rater` and counts what it does, for
+/// dashboards that want to know how many updates a service performed.
+///
+/// The counters are atomic, so they can be read with `stats` from other
+/// threads while the rater is in use. They are updated independently of
+/// each other, so a `RaterStats` read during an update may count some parts
+/// of that update but not others.
+///
+/// ```rust
+/// use bbt::{InstrumentedRater, Outcome, Rater, Rating};
+///
+/// let rater = InstrumentedRater::new(Rater::default());
+///
+/// rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+/// rater.update_ratings(vec![vec![Rating::default()]], vec![1]).unwrap_err();
+///
+/// let stats = rater.stats();
+/// assert_eq!((stats.updates, stats.players, stats.errors), (1, 2, 1));
+/// ```
+#[derive(Debug)]
+pub struct InstrumentedRater<F: Float = f64> {
+    rater: Rater<F>,
+    updates: AtomicU64,
+    players: AtomicU64,
+    clamps: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl<F: Float> InstrumentedRater<F> {
+    /// Wraps the rater, with all counters at zero.
+    pub fn new(rater: Rater<F>) -> InstrumentedRater<F> {
+        InstrumentedRater {
+            rater,
+            updates: AtomicU64::new(0),
+            players: AtomicU64::new(0),
+            clamps: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the wrapped rater. Updates performed with it directly are not
+    /// counted.
+    pub fn rater(&self) -> &Rater<F> {
+        &self.rater
+    }
+
+    /// Returns the wrapped rater, discarding the counters.
+    pub fn into_inner(self) -> Rater<F> {
+        self.rater
+    }
+
+    /// Updates the ratings like `Rater::update_ratings`, and counts the
+    /// update or the error.
+    pub fn update_ratings(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let players = teams.iter().map(Vec::len).sum::<usize>();
+
+        match self.rater.update_ratings_with_report(teams, ranks) {
+            Ok((teams, report)) => {
+                self.count_update(players, report.clamps);
+                Ok(teams)
+            }
+            Err(error) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+                Err(error)
+            }
+        }
+    }
+
+    /// Rates a duel like `Rater::duel`, and counts it. A duel with an invalid
+    /// rating, which `duel` returns unchanged, counts as an error.
+    pub fn duel(&self, p1: Rating<F>, p2: Rating<F>, outcome: Outcome) -> (Rating<F>, Rating<F>) {
+        if !p1.is_valid() || !p2.is_valid() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+            return (p1, p2);
+        }
+
+        let (ratings, clamps) = self.rater.duel_counting_clamps(p1, p2, outcome);
+        self.count_update(2, clamps);
+
+        ratings
+    }
+
+    /// Returns the current values of the counters.
+    pub fn stats(&self) -> RaterStats {
+        RaterStats {
+            updates: self.updates.load(Ordering::Relaxed),
+            players: self.players.load(Ordering::Relaxed),
+            clamps: self.clamps.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets all counters back to zero.
+    pub fn reset_stats(&self) {
+        self.updates.store(0, Ordering::Relaxed);
+        self.players.store(0, Ordering::Relaxed);
+        self.clamps.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+    }
+
+    fn count_update(&self, players: usize, clamps: u32) {
+        self.updates.fetch_add(1, Ordering::Relaxed);
+        self.players.fetch_add(players as u64, Ordering::Relaxed);
+        self.clamps.fetch_add(u64::from(clamps), Ordering::Relaxed);
+    }
+}
+
+impl<F: Float> From<Rater<F>> for InstrumentedRater<F> {
+    fn from(rater: Rater<F>) -> InstrumentedRater<F> {
+        InstrumentedRater::new(rater)
+    }
+}
+
+impl Default for InstrumentedRater {
+    fn default() -> InstrumentedRater {
+        InstrumentedRater::new(Rater::default())
+    }
+}
@@ -0,0 +1,256 @@
+//! Reading and writing tables of ratings as CSV, and reading the results of
+//! chess games from PGN files.
+//!
+//! With the `csv` feature, a table has a header row and one row per player
+//! with the columns `id`, `mu`, `sigma` and `conservative`, the conservative
+//! skill estimate `mu - 3 * sigma`. Tables are written with `write_csv` and
+//! read back with `read_csv`:
+//!
+//! ```rust
+//! # #[cfg(feature = "csv")] {
+//! let ratings = vec![("alice", bbt::Rating::new(27.5, 4.0))];
+//!
+//! let mut table = Vec::new();
+//! bbt::io::write_csv(&mut table, ratings.iter().map(|(id, r)| (id, r))).unwrap();
+//! assert_eq!(table, b"id,mu,sigma,conservative\nalice,27.5,4,15.5\n");
+//!
+//! let read = bbt::io::read_csv(&table[..]).unwrap();
+//! assert_eq!(read, vec![("alice".to_string(), bbt::Rating::new(27.5, 4.0))]);
+//! # }
+//! ```
+//!
+//! With the `pgn` feature, `pgn_results` reads the results of the games of a
+//! PGN file, and `replay_pgn` rates them.
+
+#[cfg(feature = "csv")]
+use std::error::Error;
+#[cfg(feature = "csv")]
+use std::fmt;
+#[cfg(feature = "csv")]
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "csv")]
+use csv;
+
+#[cfg(feature = "pgn")]
+pub use pgn::{pgn_results, replay_pgn, PgnError, PgnGame, PgnResults};
+#[cfg(feature = "csv")]
+use Rating;
+
+#[cfg(feature = "csv")]
+/// CsvError describes the ways in which reading or writing a table can fail.
+/// Line numbers start at one and count the header row.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvError {
+    /// Reading or writing the underlying stream failed.
+    Io(io::Error),
+
+    /// The table is not valid CSV, for example because a row has a different
+    /// number of fields than the header.
+    Malformed { line: u64, message: String },
+
+    /// The header row has no column with the given name.
+    MissingColumn { name: &'static str },
+
+    /// The field of the given column is not a number.
+    InvalidNumber { line: u64, column: &'static str },
+
+    /// The rating has a non-finite mu, or a sigma that is not positive and
+    /// finite.
+    InvalidRating { line: u64 },
+}
+
+#[cfg(feature = "csv")]
+impl CsvError {
+    /// Returns the line on which the error occurred, if it belongs to one.
+    pub fn line(&self) -> Option<u64> {
+        match *self {
+            CsvError::Malformed { line, .. }
+            | CsvError::InvalidNumber { line, .. }
+            | CsvError::InvalidRating { line } => Some(line),
+            CsvError::Io(_) | CsvError::MissingColumn { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvError::Io(ref error) => write!(f, "I/O error: {}", error),
+            CsvError::Malformed { line, ref message } => {
+                write!(f, "Malformed CSV on line {}: {}", line, message)
+            }
+            CsvError::MissingColumn { name } => {
+                write!(f, "The header has no `{}` column", name)
+            }
+            CsvError::InvalidNumber { line, column } => {
+                write!(f, "The `{}` field on line {} is not a number", column, line)
+            }
+            CsvError::InvalidRating { line } => write!(
+                f,
+                "The rating on line {} is not finite or has a sigma that is not positive",
+                line
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            CsvError::Io(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<io::Error> for CsvError {
+    fn from(error: io::Error) -> CsvError {
+        CsvError::Io(error)
+    }
+}
+
+#[cfg(feature = "csv")]
+/// Writes a table of ratings with a header row. Ids are quoted as needed.
+pub fn write_csv<'a, W, I, D>(w: W, records: I) -> Result<(), CsvError>
+where
+    W: Write,
+    I: IntoIterator<Item = (D, &'a Rating)>,
+    D: fmt::Display,
+{
+    let mut writer = csv::Writer::from_writer(w);
+
+    writer
+        .write_record(["id", "mu", "sigma", "conservative"])
+        .map_err(write_error)?;
+
+    for (id, rating) in records {
+        writer
+            .write_record([
+                id.to_string(),
+                rating.mu.to_string(),
+                rating.sigma.to_string(),
+                rating.conservative_estimate().to_string(),
+            ])
+            .map_err(write_error)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(feature = "csv")]
+/// Converts an error of the csv writer, which can only fail to write.
+fn write_error(error: csv::Error) -> CsvError {
+    let message = error.to_string();
+
+    match error.into_kind() {
+        csv::ErrorKind::Io(error) => CsvError::Io(error),
+        _ => CsvError::Io(io::Error::other(message)),
+    }
+}
+
+#[cfg(feature = "csv")]
+/// Reads a table of ratings. The columns are found by their names in the
+/// header row, so they may appear in any order, and other columns, such as
+/// `conservative`, are ignored. Both LF and CRLF line endings are accepted.
+pub fn read_csv<R: Read>(mut r: R) -> Result<Vec<(String, Rating)>, CsvError> {
+    // The csv crate miscounts lines in files with CRLF line endings, so the
+    // line numbers are computed from the input instead.
+    let mut data = Vec::new();
+    r.read_to_end(&mut data)?;
+    let mut lines = LineCounter::new(&data);
+
+    let mut reader = csv::Reader::from_reader(&data[..]);
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(error) => return Err(lines.error(error)),
+    };
+    let column = |name: &'static str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or(CsvError::MissingColumn { name })
+    };
+    let id_column = column("id")?;
+    let mu_column = column("mu")?;
+    let sigma_column = column("sigma")?;
+
+    let mut ratings = Vec::new();
+
+    for record in reader.records() {
+        let record = record.map_err(|error| lines.error(error))?;
+        let line = lines.line_at(record.position().map_or(0, |position| position.byte()));
+
+        let number = |index: usize, column: &'static str| {
+            record[index]
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| CsvError::InvalidNumber { line, column })
+        };
+        let mu = number(mu_column, "mu")?;
+        let sigma = number(sigma_column, "sigma")?;
+
+        if !mu.is_finite() || !sigma.is_finite() || sigma <= 0.0 {
+            return Err(CsvError::InvalidRating { line });
+        }
+
+        ratings.push((record[id_column].to_string(), Rating::new(mu, sigma)));
+    }
+
+    Ok(ratings)
+}
+
+#[cfg(feature = "csv")]
+/// Maps byte offsets to line numbers. Offsets must be given in increasing
+/// order, so that the whole input is only scanned once.
+struct LineCounter<'a> {
+    data: &'a [u8],
+    offset: usize,
+    line: u64,
+}
+
+#[cfg(feature = "csv")]
+impl<'a> LineCounter<'a> {
+    fn new(data: &'a [u8]) -> LineCounter<'a> {
+        LineCounter {
+            data,
+            offset: 0,
+            line: 1,
+        }
+    }
+
+    fn line_at(&mut self, byte: u64) -> u64 {
+        let mut end = (byte as usize).min(self.data.len()).max(self.offset);
+
+        // After a CRLF, the reported offset points at the LF, which still
+        // belongs to the previous line.
+        if self.data.get(end) == Some(&b'\n') {
+            end += 1;
+        }
+
+        self.line += self.data[self.offset..end]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count() as u64;
+        self.offset = end;
+
+        self.line
+    }
+
+    fn error(&mut self, error: csv::Error) -> CsvError {
+        let line = self.line_at(error.position().map_or(0, |position| position.byte()));
+        let message = error.to_string();
+
+        match error.into_kind() {
+            csv::ErrorKind::Io(error) => CsvError::Io(error),
+            _ => CsvError::Malformed { line, message },
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "strict-math")]
+use check_team_updates;
+use {apply_team_updates, canonical_sum, BBTError, Float, Rater, Rating, UpdateScratch, Workspace};
+
+/// UpdateJob rates a single match a few pairwise comparisons at a time, for
+/// matches so large that rating them in one go would block, e.g., the tick of
+/// a game server.
+///
+/// `UpdateJob::new` validates the match up front. Each call to `step` then
+/// processes up to the given number of pairwise contributions (Step 2 of
+/// Algorithm 1), and `finish` applies the updates to the teams (Step 3) once
+/// all of them have been processed. The teams are only read by `new` and
+/// only changed by `finish`, so a job that is cancelled or dropped leaves
+/// them untouched.
+///
+/// The updated ratings are identical to those of `Rater::update_ratings`,
+/// except with the `simd` feature, which `update_ratings` uses to compute the
+/// pairwise contributions and a job does not.
+///
+/// ```rust
+/// use bbt::{JobStatus, Rater, Rating, UpdateJob};
+///
+/// let rater = Rater::default();
+/// let mut teams = vec![vec![Rating::default()]; 100];
+/// let ranks: Vec<usize> = (1..=100).collect();
+///
+/// let mut job = UpdateJob::new(&rater, &teams, &ranks).unwrap();
+/// while let JobStatus::Running { .. } = job.step(500) {
+///     // Do other work in between.
+/// }
+/// job.finish(&mut teams).unwrap();
+///
+/// assert_eq!(teams, rater.update_ratings(vec![vec![Rating::default()]; 100], ranks).unwrap());
+/// ```
+pub struct UpdateJob<'a, F: Float = f64> {
+    rater: &'a Rater<F>,
+    ranks: Vec<usize>,
+    team_sizes: Vec<usize>,
+    inflated: Option<Vec<Vec<Rating<F>>>>,
+    scratch: UpdateScratch<F>,
+
+    /// The team whose contributions are being computed, and the next
+    /// opponent it is compared with.
+    team: usize,
+    opponent: usize,
+
+    /// The number of terms of the current team in the scratch space.
+    n_terms: usize,
+
+    processed: usize,
+}
+
+/// JobStatus tells whether an `UpdateJob` has processed all pairwise
+/// contributions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobStatus {
+    /// Some contributions are left. `processed` of the `total` pairs of teams
+    /// have been processed.
+    Running { processed: usize, total: usize },
+
+    /// All contributions have been processed, and the job can be finished.
+    Complete,
+}
+
+/// JobError describes why an `UpdateJob` could not be finished.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JobError {
+    /// `finish` was called before all pairwise contributions were processed.
+    Incomplete { processed: usize, total: usize },
+
+    /// The teams given to `finish` do not have the same number of teams and
+    /// players as those given to `UpdateJob::new`.
+    TeamsChanged,
+
+    /// The update was rejected by the rater, which can only happen with the
+    /// `strict-math` feature.
+    Rating(BBTError),
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JobError::Incomplete { processed, total } => write!(
+                f,
+                "The job is not complete ({} of {} pairs processed)",
+                processed, total
+            ),
+            JobError::TeamsChanged => {
+                write!(
+                    f,
+                    "The teams do not match the teams the job was created for"
+                )
+            }
+            JobError::Rating(ref error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for JobError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            JobError::Rating(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<BBTError> for JobError {
+    fn from(error: BBTError) -> JobError {
+        JobError::Rating(error)
+    }
+}
+
+impl<'a, F: Float> UpdateJob<'a, F> {
+    /// Validates the match and computes the team skills and variances (Step
+    /// 1). Returns the errors of `Rater::update_ratings` for malformed
+    /// matches.
+    pub fn new(
+        rater: &'a Rater<F>,
+        teams: &[Vec<Rating<F>>],
+        ranks: &[usize],
+    ) -> Result<UpdateJob<'a, F>, BBTError> {
+        let mut scratch = UpdateScratch::with_capacity(teams.len());
+        let mut inflated = None;
+
+        {
+            let mut work = scratch.workspace(teams.len());
+            rater.validate_into(teams, ranks, &mut work)?;
+
+            // The players of surprised teams are inflated on a copy, which
+            // replaces the players when the job is finished.
+            if rater.volatility.is_some() {
+                let mut copy = teams.to_vec();
+                rater.inflate_surprised(&mut copy, ranks, &mut work);
+                inflated = Some(copy);
+            }
+        }
+
+        Ok(UpdateJob {
+            rater,
+            ranks: ranks.to_vec(),
+            team_sizes: teams.iter().map(Vec::len).collect(),
+            inflated,
+            scratch,
+            team: 0,
+            opponent: 0,
+            n_terms: 0,
+            processed: 0,
+        })
+    }
+
+    /// Processes up to `max_pairs` pairwise contributions and returns whether
+    /// there are any left. There is one contribution for every ordered pair
+    /// of distinct teams.
+    pub fn step(&mut self, mut max_pairs: usize) -> JobStatus {
+        let n_teams = self.ranks.len();
+
+        while self.team < n_teams {
+            let remaining = n_teams - self.opponent - usize::from(self.team >= self.opponent);
+
+            if remaining == 0 {
+                let n_terms = self.n_terms;
+                let scratch = &mut self.scratch;
+                scratch.team_omega[self.team] = canonical_sum(&mut scratch.omega_terms[..n_terms]);
+                scratch.team_delta[self.team] = canonical_sum(&mut scratch.delta_terms[..n_terms]);
+
+                self.team += 1;
+                self.opponent = 0;
+                self.n_terms = 0;
+                continue;
+            }
+
+            if max_pairs == 0 {
+                break;
+            }
+
+            let start = self.opponent;
+            let mut end = n_teams.min(start.saturating_add(max_pairs));
+            if (start..end).contains(&self.team) && end < n_teams {
+                end += 1;
+            }
+            let pairs = end - start - usize::from((start..end).contains(&self.team));
+
+            let scratch = &mut self.scratch;
+            self.n_terms += self.rater.pair_terms_into(
+                self.team,
+                start..end,
+                &scratch.team_mu,
+                &scratch.team_sigma_sq,
+                &self.ranks,
+                (
+                    &mut scratch.omega_terms[self.n_terms..],
+                    &mut scratch.delta_terms[self.n_terms..],
+                ),
+                |_| (),
+            );
+
+            self.opponent = end;
+            self.processed += pairs;
+            max_pairs -= pairs;
+        }
+
+        self.status()
+    }
+
+    /// Returns whether there are pairwise contributions left.
+    pub fn status(&self) -> JobStatus {
+        if self.team == self.ranks.len() {
+            JobStatus::Complete
+        } else {
+            JobStatus::Running {
+                processed: self.processed,
+                total: self.total(),
+            }
+        }
+    }
+
+    /// Returns the number of pairwise contributions of the match.
+    fn total(&self) -> usize {
+        let n_teams = self.ranks.len();
+        n_teams * n_teams.saturating_sub(1)
+    }
+
+    /// Applies the updates to the teams (Step 3), which must be the teams
+    /// the job was created for, unchanged.
+    ///
+    /// Returns `JobError::Incomplete` if there are pairwise contributions
+    /// left, and `JobError::TeamsChanged` if the teams do not have the shape
+    /// of the original teams. The teams are unchanged in either case.
+    pub fn finish(self, teams: &mut [Vec<Rating<F>>]) -> Result<(), JobError> {
+        if self.team != self.ranks.len() {
+            return Err(JobError::Incomplete {
+                processed: self.processed,
+                total: self.total(),
+            });
+        }
+
+        if teams.len() != self.team_sizes.len()
+            || teams
+                .iter()
+                .zip(&self.team_sizes)
+                .any(|(team, &size)| team.len() != size)
+        {
+            return Err(JobError::TeamsChanged);
+        }
+
+        let UpdateJob {
+            rater,
+            inflated,
+            mut scratch,
+            ..
+        } = self;
+
+        let work = Workspace {
+            team_mu: &mut scratch.team_mu,
+            team_sigma_sq: &mut scratch.team_sigma_sq,
+            team_omega: &mut scratch.team_omega,
+            team_delta: &mut scratch.team_delta,
+            omega_terms: &mut scratch.omega_terms,
+            delta_terms: &mut scratch.delta_terms,
+        };
+
+        match inflated {
+            Some(mut inflated) => {
+                #[cfg(feature = "strict-math")]
+                check_team_updates(&inflated, &work)?;
+
+                apply_team_updates(&mut inflated, &work, rater.write_back(), |_, _| ());
+                for (team, updated) in teams.iter_mut().zip(inflated) {
+                    *team = updated;
+                }
+            }
+            None => {
+                #[cfg(feature = "strict-math")]
+                check_team_updates(teams, &work)?;
+
+                apply_team_updates(teams, &work, rater.write_back(), |_, _| ());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Abandons the job. The teams are left unchanged, as they are when the
+    /// job is simply dropped.
+    pub fn cancel(self) {}
+}
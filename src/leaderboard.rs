@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::slice;
+
+use {BBTError, Outcome, Rater, Rating};
+
+/// LeaderboardError describes why a game could not be recorded. Players are
+/// identified by their position in the game: the index of their team and
+/// their index within it. A duel has two teams of one player each.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LeaderboardError {
+    /// The player is not on a strict leaderboard.
+    UnknownPlayer { team: usize, player: usize },
+
+    /// The player already appeared earlier in the same game.
+    DuplicatePlayer { team: usize, player: usize },
+
+    /// The game was rejected by the rater.
+    Rating(BBTError),
+}
+
+impl fmt::Display for LeaderboardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LeaderboardError::UnknownPlayer { team, player } => write!(
+                f,
+                "Player {} of team {} is not on the leaderboard",
+                player, team
+            ),
+            LeaderboardError::DuplicatePlayer { team, player } => write!(
+                f,
+                "Player {} of team {} already appears earlier in the game",
+                player, team
+            ),
+            LeaderboardError::Rating(ref error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for LeaderboardError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            LeaderboardError::Rating(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<BBTError> for LeaderboardError {
+    fn from(error: BBTError) -> LeaderboardError {
+        LeaderboardError::Rating(error)
+    }
+}
+
+/// Leaderboard keeps the ratings of a group of players by their ids and
+/// updates them as games are recorded.
+///
+/// A leaderboard created with `Leaderboard::new` adds players it has not
+/// seen before with an initial rating, while one created with
+/// `Leaderboard::strict` only rates players that were added with `insert`
+/// and rejects games with anyone else. Games are recorded atomically: if a
+/// game is rejected, no rating changes and no player is added.
+///
+/// ```rust
+/// use bbt::{Leaderboard, Outcome, Rater, Rating};
+///
+/// let mut leaderboard = Leaderboard::new(Rater::default(), Rating::default());
+/// leaderboard.record_duel(&"alice", &"bob", Outcome::Win).unwrap();
+/// leaderboard.record_match(&[&["carol", "dave"], &["alice"]], &[1, 2]).unwrap();
+///
+/// let standings = leaderboard.standings();
+/// assert_eq!(standings.len(), 4);
+/// assert_eq!(*standings[3].0, "bob");
+/// ```
+pub struct Leaderboard<K: Eq + Hash> {
+    rater: Rater,
+    initial: Option<Rating>,
+    /// The rating of every player, together with the position at which the
+    /// player was added, which breaks ties in the standings.
+    players: HashMap<K, (usize, Rating)>,
+    next_position: usize,
+}
+
+impl<K: Eq + Hash> Leaderboard<K> {
+    /// Creates an empty leaderboard that adds unknown players with the
+    /// `initial` rating when they first play.
+    pub fn new(rater: Rater, initial: Rating) -> Leaderboard<K> {
+        Leaderboard {
+            rater,
+            initial: Some(initial),
+            players: HashMap::new(),
+            next_position: 0,
+        }
+    }
+
+    /// Creates an empty leaderboard that rejects games with players that
+    /// were not added with `insert` with `LeaderboardError::UnknownPlayer`.
+    pub fn strict(rater: Rater) -> Leaderboard<K> {
+        Leaderboard {
+            rater,
+            initial: None,
+            players: HashMap::new(),
+            next_position: 0,
+        }
+    }
+
+    /// Returns the rater that updates the ratings.
+    pub fn rater(&self) -> &Rater {
+        &self.rater
+    }
+
+    /// Returns the rating that unknown players start with, or `None` if the
+    /// leaderboard is strict.
+    pub fn initial_rating(&self) -> Option<&Rating> {
+        self.initial.as_ref()
+    }
+
+    /// Returns the number of players on the leaderboard.
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Returns whether the leaderboard has no players.
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
+    /// Sets the rating of a player, adding the player if necessary, and
+    /// returns the previous rating. A player that is already on the
+    /// leaderboard keeps their place in the tie-breaking order.
+    pub fn insert(&mut self, id: K, rating: Rating) -> Option<Rating> {
+        if let Some(entry) = self.players.get_mut(&id) {
+            return Some(mem::replace(&mut entry.1, rating));
+        }
+
+        self.players.insert(id, (self.next_position, rating));
+        self.next_position += 1;
+
+        None
+    }
+
+    /// Returns the rating of a player, or `None` if the player is not on the
+    /// leaderboard.
+    pub fn rating(&self, id: &K) -> Option<&Rating> {
+        self.players.get(id).map(|entry| &entry.1)
+    }
+
+    /// Records a duel between two players, with the outcome from the
+    /// perspective of the first.
+    pub fn record_duel(&mut self, p1: &K, p2: &K, outcome: Outcome) -> Result<(), LeaderboardError>
+    where
+        K: Clone,
+    {
+        let ranks = match outcome {
+            Outcome::Win => [1, 2],
+            Outcome::Loss => [2, 1],
+            Outcome::Draw => [1, 1],
+        };
+
+        self.record_match(&[slice::from_ref(p1), slice::from_ref(p2)], &ranks)
+    }
+
+    /// Records a match between teams, with the ranks as passed to
+    /// `Rater::update_ratings`.
+    pub fn record_match(&mut self, teams: &[&[K]], ranks: &[usize]) -> Result<(), LeaderboardError>
+    where
+        K: Clone,
+    {
+        let mut ratings = Vec::with_capacity(teams.len());
+
+        for (t, team) in teams.iter().enumerate() {
+            let mut team_ratings = Vec::with_capacity(team.len());
+
+            for (p, id) in team.iter().enumerate() {
+                let seen_before = teams[..t]
+                    .iter()
+                    .flat_map(|team| team.iter())
+                    .chain(&team[..p])
+                    .any(|other| other == id);
+
+                if seen_before {
+                    return Err(LeaderboardError::DuplicatePlayer { team: t, player: p });
+                }
+
+                let rating = match self.rating(id).or(self.initial.as_ref()) {
+                    Some(rating) => *rating,
+                    None => return Err(LeaderboardError::UnknownPlayer { team: t, player: p }),
+                };
+
+                team_ratings.push(rating);
+            }
+
+            ratings.push(team_ratings);
+        }
+
+        let updated = self.rater.update_ratings(ratings, ranks.to_vec())?;
+
+        for (team, new_ratings) in teams.iter().zip(updated) {
+            for (id, rating) in team.iter().zip(new_ratings) {
+                self.insert(id.clone(), rating);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns every player with their rating, ordered by the conservative
+    /// skill estimate `mu - 3 * sigma`, best first. Players with the same
+    /// estimate are ordered by when they were added to the leaderboard, so
+    /// the order does not depend on the order of the underlying hash map.
+    pub fn standings(&self) -> Vec<(&K, Rating)> {
+        let mut standings: Vec<_> = self.players.iter().collect();
+
+        standings.sort_by(|a, b| {
+            let (a_estimate, b_estimate) = (
+                (a.1).1.conservative_estimate(),
+                (b.1).1.conservative_estimate(),
+            );
+
+            b_estimate
+                .total_cmp(&a_estimate)
+                .then((a.1).0.cmp(&(b.1).0))
+        });
+
+        standings
+            .into_iter()
+            .map(|(id, entry)| (id, entry.1))
+            .collect()
+    }
+}
+
+impl<K: Eq + Hash + fmt::Debug> fmt::Debug for Leaderboard<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Leaderboard")
+            .field("rater", &self.rater)
+            .field("initial", &self.initial)
+            .field("standings", &self.standings())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use std::fmt;
+    use std::hash::Hash;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+    use super::Leaderboard;
+    use {Rater, Rating};
+
+    /// Leaderboards are serialized as a struct with the fields `rater`,
+    /// `initial` (`None` for strict leaderboards) and `players`, a sequence
+    /// of `(id, rating)` pairs in the order the players were added, which
+    /// preserves the tie-breaking order of the standings.
+    impl<K: Eq + Hash + Serialize> Serialize for Leaderboard<K> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut players: Vec<_> = self.players.iter().collect();
+            players.sort_by_key(|&(_, &(position, _))| position);
+            let players: Vec<_> = players
+                .into_iter()
+                .map(|(id, (_, rating))| (id, rating))
+                .collect();
+
+            let mut state = serializer.serialize_struct("Leaderboard", 3)?;
+            state.serialize_field("rater", &self.rater)?;
+            state.serialize_field("initial", &self.initial)?;
+            state.serialize_field("players", &players)?;
+            state.end()
+        }
+    }
+
+    impl<'de, K: Eq + Hash + Deserialize<'de>> Deserialize<'de> for Leaderboard<K> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            enum Field {
+                Rater,
+                Initial,
+                Players,
+            }
+
+            impl<'de> Deserialize<'de> for Field {
+                fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    struct FieldVisitor;
+
+                    impl<'de> Visitor<'de> for FieldVisitor {
+                        type Value = Field;
+
+                        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                            formatter.write_str("`rater`, `initial` or `players`")
+                        }
+
+                        fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                        where
+                            E: de::Error,
+                        {
+                            match value {
+                                "rater" => Ok(Field::Rater),
+                                "initial" => Ok(Field::Initial),
+                                "players" => Ok(Field::Players),
+                                _ => Err(de::Error::unknown_field(value, FIELDS)),
+                            }
+                        }
+                    }
+
+                    deserializer.deserialize_identifier(FieldVisitor)
+                }
+            }
+
+            struct LeaderboardVisitor<K>(PhantomData<K>);
+
+            impl<'de, K: Eq + Hash + Deserialize<'de>> Visitor<'de> for LeaderboardVisitor<K> {
+                type Value = Leaderboard<K>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("struct Leaderboard")
+                }
+
+                fn visit_seq<V>(self, mut seq: V) -> Result<Leaderboard<K>, V::Error>
+                where
+                    V: SeqAccess<'de>,
+                {
+                    let rater = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    let initial = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    let players = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+                    build(rater, initial, players)
+                }
+
+                fn visit_map<V>(self, mut map: V) -> Result<Leaderboard<K>, V::Error>
+                where
+                    V: MapAccess<'de>,
+                {
+                    let mut rater = None;
+                    let mut initial = None;
+                    let mut players = None;
+
+                    while let Some(key) = map.next_key()? {
+                        match key {
+                            Field::Rater => {
+                                if rater.is_some() {
+                                    return Err(de::Error::duplicate_field("rater"));
+                                }
+                                rater = Some(map.next_value()?);
+                            }
+                            Field::Initial => {
+                                if initial.is_some() {
+                                    return Err(de::Error::duplicate_field("initial"));
+                                }
+                                initial = Some(map.next_value()?);
+                            }
+                            Field::Players => {
+                                if players.is_some() {
+                                    return Err(de::Error::duplicate_field("players"));
+                                }
+                                players = Some(map.next_value()?);
+                            }
+                        }
+                    }
+
+                    build(
+                        rater.ok_or_else(|| de::Error::missing_field("rater"))?,
+                        initial.ok_or_else(|| de::Error::missing_field("initial"))?,
+                        players.ok_or_else(|| de::Error::missing_field("players"))?,
+                    )
+                }
+            }
+
+            const FIELDS: &[&str] = &["rater", "initial", "players"];
+            deserializer.deserialize_struct("Leaderboard", FIELDS, LeaderboardVisitor(PhantomData))
+        }
+    }
+
+    /// Rebuilds a leaderboard, rejecting ids that appear more than once.
+    fn build<K: Eq + Hash, E: de::Error>(
+        rater: Rater,
+        initial: Option<Rating>,
+        players: Vec<(K, Rating)>,
+    ) -> Result<Leaderboard<K>, E> {
+        let mut leaderboard = match initial {
+            Some(initial) => Leaderboard::new(rater, initial),
+            None => Leaderboard::strict(rater),
+        };
+
+        for (id, rating) in players {
+            if leaderboard.insert(id, rating).is_some() {
+                return Err(E::custom("a player appears more than once"));
+            }
+        }
+
+        Ok(leaderboard)
+    }
+}
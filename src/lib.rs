@@ -49,6 +49,7 @@
 //! contains no duplicates:
 //!
 //! ```rust
+//! # #[cfg(feature = "alloc")] {
 //! let rater = bbt::Rater::default();
 //!
 //! let p1 = bbt::Rating::default();
@@ -61,6 +62,7 @@
 //! let new_ratings = rater.update_ratings(vec![vec![p1], vec![p2], vec![p3],
 //!                                             vec![p4], vec![p5], vec![p6]],
 //!                                        vec![1, 2, 3, 4, 5, 6]).unwrap();
+//! # }
 //! ```
 //!
 //! In the example, the first player places first, the second player second, and
@@ -80,6 +82,7 @@
 //! can call the `update_ratings` function as follows:
 //!
 //! ```rust
+//! # #[cfg(feature = "alloc")] {
 //! let rater = bbt::Rater::default();
 //!
 //! let alice   = bbt::Rating::default();
@@ -96,6 +99,7 @@
 //!                                             vec![eve, fred],
 //!                                             vec![gabe, henry]],
 //!                                        vec![1, 2, 2, 4]).unwrap();
+//! # }
 //! ```
 //!
 //! The second vector assigns a rank to the teams given in the first vector.
@@ -109,300 +113,4852 @@
 //! scale in `Rating::new()`. For example, to use a more traditional scale of 0 to
 //! 3000, you can initialize ratings with `Rating::new(1500.0, 1500.0/3.0)`. You'll
 //! also need to adjust the β-value of the Rater instance accordingly:
-//! `Rater::new(1500.0/6.0)`.
+//! `Rater::new(1500.0/6.0)`. `bbt::presets` has these values for common scales:
+//!
+//! ```rust
+//! let (rater, rating) = bbt::presets::custom(1500.0).pair();
+//! assert_eq!((rater.beta(), rating.sigma()), (250.0, 500.0));
+//! ```
+//!
+//! ## Scalar type
+//!
+//! Ratings are computed in `f64` by default. `Rating`, `Rater` and the other
+//! types take an optional type parameter implementing `bbt::Float`, so on
+//! targets where `f64` is slow, you can use `f32` instead:
+//!
+//! ```rust
+//! let rater = bbt::Rater::new(25.0f32 / 6.0);
+//!
+//! let p1: bbt::Rating<f32> = bbt::Rating::default();
+//! let p2: bbt::Rating<f32> = bbt::Rating::default();
+//!
+//! let (new_p1, new_p2) = rater.duel(p1, p2, bbt::Outcome::Win);
+//! ```
+//!
+//! `f32` ratings are less precise and should not be mixed with `f64` ratings
+//! of the same players.
+//!
+//! ## `no_std`
+//!
+//! The crate uses the standard library through the default `std` feature.
+//! Without it, the crate is `#![no_std]` and needs the `libm` feature for its
+//! float math. The `alloc` feature brings back the `Vec`-based methods such as
+//! `update_ratings`; without it, `update_ratings_const` and `duel` update
+//! ratings without allocating:
+//!
+//! ```toml
+//! [dependencies]
+//! bbt = { version = "0.2", default-features = false, features = ["libm"] }
+//! ```
+//!
+//! With `std` enabled, the results are exactly the same as before; `libm` may
+//! differ from the platform's math functions in the last bits.
+//!
+//! ## Determinism
+//!
+//! Floating-point rating updates can differ in the last bits between
+//! platforms, because the math libraries compute `exp` differently. If ratings
+//! have to evolve identically everywhere, for example to verify a lockstep
+//! simulation between servers and a WASM client, use `RatingI` and `RaterI`.
+//! They compute in `bbt::Fixed`, a Q32.32 fixed-point number whose operations
+//! are implemented with integer arithmetic in this crate:
+//!
+//! ```rust
+//! use bbt::Float;
+//!
+//! let rater = bbt::RaterI::new(bbt::Fixed::from_f64(25.0 / 6.0));
+//!
+//! let p1 = bbt::RatingI::default();
+//! let p2 = bbt::RatingI::default();
+//!
+//! let (new_p1, new_p2) = rater.duel(p1, p2, bbt::Outcome::Win);
+//! assert!(new_p1.mu().to_f64() > 25.0);
+//! ```
+//!
+//! On the default scale, fixed-point ratings stay within about 1e-8 of their
+//! `f64` counterparts over a replay of a thousand games. Converting `f64`
+//! constants with `Fixed::from_f64` is exact up to rounding to 2^-32 and
+//! identical on every platform.
+//!
+//! ## OpenSkill compatibility
+//!
+//! The Bradley-Terry full-pair model of [OpenSkill](https://openskill.me) is
+//! the same algorithm, and `Rater::default()` is configured like OpenSkill's
+//! defaults: new ratings have a mu of 25 and a sigma of 25/3, β is 25/6, and
+//! a player's variance never shrinks below κ = 0.0001 times its old value in
+//! one update. Ranks are ordered the same way, with the lowest rank winning.
+//! `tests/openskill.rs` compares the updates with ones generated by
+//! openskill.js; it needs a fixture that is generated with the package
+//! installed, see `tests/fixtures/openskill/generate.js`.
+//!
+//! There is one difference in usage: OpenSkill's `rate` adds `tau²` to every
+//! player's variance before the update, while bbt never adds dynamics on its
+//! own. Pass `tau: 0` to OpenSkill, or add the variance to the ratings before
+//! updating them with bbt, to get the same results.
+//!
+//! With the `serde` feature, ratings serialize as `{"mu": .., "sigma": ..}`
+//! objects in human-readable formats, which is the shape of OpenSkill's
+//! ratings, so they can be exchanged with OpenSkill as JSON.
+//!
+//! ## SIMD
+//!
+//! The `simd` feature computes the pairwise comparisons of `f64` ratings four
+//! opponents at a time using the [`wide`](https://docs.rs/wide) crate, which
+//! is noticeably faster for matches with many teams. The results are not
+//! bit-identical to the scalar computation: each team's omega and delta
+//! differ by at most 1e-12 times the sum of the magnitudes of their pairwise
+//! terms.
+//!
+//! ## Math backends
+//!
+//! Most of the time of an update is spent computing exponentials. A rater
+//! can compute them, and the square roots of the update, with another
+//! `MathBackend` given to `Rater::with_math_backend`. The `fast-math` feature
+//! provides `FastMath`, whose approximate `exp` changes ratings by far less
+//! than 1e-4 rating points. Raters without a backend are unaffected.
+//!
+//! ## Tracing
+//!
+//! The `tracing` feature instruments the rating updates with the
+//! [`tracing`](https://docs.rs/tracing) crate. `update_ratings` and its
+//! variants, `duel`, `update_batch` and the replays of `bbt::replay` open
+//! DEBUG spans with the number of teams and players. Rejected matches are
+//! reported as DEBUG events, and players whose variance reduction was
+//! clamped, or whose updated rating is not finite, as WARN events. Without
+//! the feature, none of this is compiled.
+//!
+//! ## Invariant checks
+//!
+//! The `validate` feature checks the invariants of every update, such as
+//! finite ratings and sigmas that never grow, and panics with the offending
+//! team and player and the values needed to reproduce the update if one is
+//! broken. It is meant for debugging and fuzzing, not production; see
+//! `bbt::validate`.
+//!
+//! ## Bevy
+//!
+//! The `bevy_reflect` feature implements `Reflect`, `FromReflect` and
+//! `GetTypeRegistration` for `Rating`, `Rater` and `Outcome`, so that they
+//! can be inspected in the Bevy editor and saved in scenes. Ratings reflect
+//! as structs with the fields `mu` and `sigma`, which are read-only: writing
+//! sigma in place would leave its cached square stale. Ratings are changed by
+//! applying a struct with new values instead, which is rejected with an
+//! `ApplyError`, leaving the rating unchanged, if the result is not a valid
+//! rating (see `Rating::try_new`). `FromReflect` validates the same way.
+//!
+//! Raters reflect as opaque values that are serialized with serde, as their
+//! β-parameter. Applying or cloning a rater through reflection keeps its
+//! settings, but not its policy.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", test)))]
+extern crate core as std;
+
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("bbt needs either the `std` or the `libm` feature for its float math");
+
+#[cfg(feature = "libm")]
+extern crate libm;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+#[cfg(feature = "serde")]
+pub mod serde_compact;
+#[cfg(feature = "serde")]
+pub mod serde_default_sigma;
+#[cfg(feature = "serde")]
+pub mod serde_outcome;
+#[cfg(feature = "serde")]
+pub mod serde_scalar;
+#[cfg(feature = "serde")]
+pub mod serde_unchecked;
+#[cfg(feature = "serde")]
+mod serialization;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "simd")]
+extern crate wide;
+
+#[cfg(feature = "skillratings")]
+extern crate skillratings;
+
+#[cfg(feature = "csv")]
+extern crate csv;
+
+#[cfg(feature = "proptest")]
+extern crate proptest as proptest_crate;
+
+#[cfg(feature = "python")]
+extern crate pyo3;
+
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+
+#[cfg(feature = "defmt")]
+extern crate defmt;
+
+#[cfg(feature = "schemars")]
+extern crate schemars;
+
+#[cfg(feature = "sqlx-postgres")]
+extern crate sqlx;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "arrow")]
+extern crate arrow_schema;
+#[cfg(feature = "bevy_reflect")]
+extern crate bevy_reflect;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+
+// The code generated by the bevy_reflect, pyo3, rkyv and schemars macros
+// refers to `::core`, which has to be in the crate root in the 2015 edition.
+#[cfg(any(
+    feature = "bevy_reflect",
+    feature = "python",
+    feature = "rkyv",
+    feature = "schemars"
+))]
+extern crate core;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+extern crate js_sys;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+extern crate wasm_bindgen;
+
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "alloc")]
+mod buffer;
+#[cfg(feature = "ndarray")]
+pub mod bulk;
+mod bytes;
+#[cfg(feature = "alloc")]
+mod censored;
+#[cfg(feature = "alloc")]
+mod comparisons;
+#[cfg(feature = "std")]
+mod confidence;
+#[cfg(feature = "alloc")]
+mod contributions;
+pub mod decay;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+pub mod digest;
+mod elo;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod eval;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod field;
+mod fixed;
+mod float;
+pub mod fmt;
+pub mod form;
+mod glicko;
+#[cfg(feature = "alloc")]
+mod history;
+#[cfg(feature = "stats")]
+mod instrumented;
+#[cfg(any(feature = "csv", feature = "pgn"))]
+pub mod io;
+#[cfg(feature = "alloc")]
+mod job;
+#[cfg(feature = "std")]
+mod leaderboard;
+mod margin;
+#[cfg(feature = "alloc")]
+mod matchmaking;
+mod math;
+#[cfg(feature = "alloc")]
+mod period;
+#[cfg(feature = "pgn")]
+mod pgn;
+mod placement;
+mod policy;
+#[cfg(feature = "sqlx-postgres")]
+pub mod postgres;
+pub mod presets;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "alloc")]
+pub mod ranks;
+#[cfg(feature = "arrow")]
+mod record_batch;
+#[cfg(feature = "reference")]
+pub mod reference;
+#[cfg(feature = "bevy_reflect")]
+mod reflect;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod replay;
+#[cfg(feature = "alloc")]
+mod results;
+mod retroactive;
+#[cfg(feature = "alloc")]
+mod royale;
+mod scale;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "alloc")]
+mod scores;
+pub mod season;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "skillratings")]
+mod skillratings_compat;
+#[cfg(feature = "std")]
+pub mod snapshot;
+mod soft;
+#[cfg(feature = "alloc")]
+pub mod sort;
+pub mod stats;
+#[cfg(all(feature = "rand", feature = "serde"))]
+pub mod synth;
+#[cfg(feature = "alloc")]
+mod table;
+mod team;
+#[cfg(feature = "alloc")]
+mod tiers;
+#[cfg(feature = "alloc")]
+pub mod tournament;
+mod tracked;
+mod truncated;
+pub mod validate;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub mod wasm;
+
+#[cfg(feature = "alloc")]
+pub use buffer::{RatingsBuffer, RatingsIter};
+pub use bytes::QuantizationSpec;
+#[cfg(feature = "alloc")]
+pub use censored::RankObservation;
+pub use elo::EloScale;
+pub use fixed::Fixed;
+pub use float::Float;
+pub use glicko::GlickoScale;
+#[cfg(feature = "alloc")]
+pub use history::RatingHistory;
+#[cfg(feature = "stats")]
+pub use instrumented::{InstrumentedRater, RaterStats};
+#[cfg(feature = "alloc")]
+pub use job::{JobError, JobStatus, UpdateJob};
+#[cfg(feature = "std")]
+pub use leaderboard::{Leaderboard, LeaderboardError};
+pub use margin::MarginModel;
+#[cfg(feature = "alloc")]
+pub use matchmaking::MatchQueue;
+#[cfg(feature = "fast-math")]
+pub use math::FastMath;
+pub use math::{MathBackend, StdMath};
+#[cfg(feature = "alloc")]
+pub use period::PeriodGame;
+pub use placement::PlacementSeries;
+pub use policy::{MuBounds, Policy};
+#[cfg(feature = "alloc")]
+pub use results::TeamResult;
+#[cfg(feature = "alloc")]
+pub use royale::{PartialDelta, RoyaleError, RoyaleSession};
+pub use scale::{DefaultScale, Scale, ScaledRater, ScaledRating};
+#[cfg(feature = "alloc")]
+pub use scores::{ranks_from_scores, ScoreOrder};
+#[cfg(feature = "alloc")]
+pub use soft::SoftScores;
+#[cfg(feature = "alloc")]
+pub use table::WinProbabilityTable;
+pub use team::TeamRating;
+#[cfg(feature = "alloc")]
+pub use tiers::{Tier, TierLadder};
+pub use tracked::TrackedRating;
+
+/// A rating in fixed-point arithmetic, which evolves identically on every
+/// platform. See the crate documentation on determinism.
+pub type RatingI = Rating<Fixed>;
+
+/// A rater in fixed-point arithmetic, which updates `RatingI`s identically on
+/// every platform.
+pub type RaterI = Rater<Fixed>;
+
+use std::cmp::Ordering;
+use std::error::Error;
+#[cfg(feature = "alloc")]
+use std::mem;
+
+/// BBTError describes the ways in which a rating update can fail.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum BBTError {
+    /// The `teams` and `ranks` vectors are not of the same length.
+    MismatchedLengths { teams: usize, ranks: usize },
+
+    /// The team at position `index` contains no players.
+    EmptyTeam { index: usize },
+
+    /// A match needs at least two teams, but only `found` were given.
+    NotEnoughTeams { found: usize },
+
+    /// The given player's rating has a non-finite mu or sigma, or a negative
+    /// sigma.
+    InvalidRating { team: usize, player: usize },
+
+    /// The skill or variance of the team at position `index` is too large to
+    /// be represented.
+    TeamOverflow { index: usize },
+
+    /// The ranks are not a standard competition ranking. This is only
+    /// reported by raters created with `Rater::strict_ranks`.
+    InvalidRanks,
+
+    /// The update produced a non-finite rating for the given player, or a
+    /// sigma of zero for a player that was not fully certain. This is only
+    /// reported with the `strict-math` feature enabled.
+    NumericalError { team: usize, player: usize },
+
+    /// The parameter with the given name is out of range.
+    InvalidParameter { name: &'static str },
+}
+
+impl std::fmt::Display for BBTError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            BBTError::MismatchedLengths { teams, ranks } => write!(
+                f,
+                "`teams` and `ranks` vectors must be of the same length (got {} teams and {} ranks)",
+                teams, ranks
+            ),
+            BBTError::EmptyTeam { index } => {
+                write!(f, "The team at index {} contains no players", index)
+            }
+            BBTError::NotEnoughTeams { found } => {
+                write!(f, "At least two teams are required, but {} were given", found)
+            }
+            BBTError::InvalidRating { team, player } => write!(
+                f,
+                "The rating of player {} of team {} is not finite or has a negative sigma",
+                player, team
+            ),
+            BBTError::TeamOverflow { index } => write!(
+                f,
+                "The skill or variance of the team at index {} is too large",
+                index
+            ),
+            BBTError::InvalidRanks => write!(
+                f,
+                "The ranks are not a standard competition ranking (e.g. 1, 2, 2, 4)"
+            ),
+            BBTError::NumericalError { team, player } => write!(
+                f,
+                "The update produced a non-finite rating for player {} of team {}",
+                player, team
+            ),
+            BBTError::InvalidParameter { name } => {
+                write!(f, "The parameter `{}` is out of range", name)
+            }
+        }
+    }
+}
+
+impl Error for BBTError {}
+
+/// Rater is used to calculate rating updates given the β-parameter.
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes),
+    archive_attr(doc = "The archived form of a `Rater`, created by `rkyv`.")
+)]
+pub struct Rater<F: Float = f64> {
+    beta_sq: F,
+    allow_fewer_than_two_teams: bool,
+    strict_ranks: bool,
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "rkyv", with(rkyv::with::Skip))]
+    scratch: UpdateScratch<F>,
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "rkyv", with(rkyv::with::Skip))]
+    policy: Option<Box<dyn Policy<F>>>,
+    #[cfg_attr(feature = "rkyv", with(rkyv::with::Skip))]
+    math: Option<&'static dyn MathBackend<F>>,
+    #[cfg_attr(feature = "rkyv", with(rkyv::with::Skip))]
+    volatility: Option<Volatility<F>>,
+    #[cfg_attr(feature = "rkyv", with(rkyv::with::Skip))]
+    loss_protection: u32,
+    #[cfg_attr(feature = "rkyv", with(rkyv::with::Skip))]
+    truncation: Option<F>,
+}
+
+/// The settings of `Rater::with_volatility`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Volatility<F> {
+    /// Results that the model gave less than this probability are surprising.
+    max_probability: F,
+
+    /// The variance added to the players of a surprised team.
+    inflation_sq: F,
+}
+
+impl<F: Float> Rater<F> {
+    /// This method instantiates a new rater with the given β-parameter.
+    pub fn new(beta: F) -> Rater<F> {
+        Rater {
+            beta_sq: beta * beta,
+            allow_fewer_than_two_teams: false,
+            strict_ranks: false,
+            #[cfg(feature = "alloc")]
+            scratch: UpdateScratch::new(),
+            #[cfg(feature = "alloc")]
+            policy: None,
+            math: None,
+            volatility: None,
+            loss_protection: 0,
+            truncation: None,
+        }
+    }
+
+    /// Returns the β-parameter of the rater.
+    pub fn beta(&self) -> F {
+        self.beta_sq.sqrt()
+    }
+
+    /// By default, `update_ratings` returns `BBTError::NotEnoughTeams` for
+    /// matches with fewer than two teams. This method returns a rater that
+    /// instead accepts such matches and returns the ratings unchanged.
+    pub fn allow_fewer_than_two_teams(mut self) -> Rater<F> {
+        self.allow_fewer_than_two_teams = true;
+        self
+    }
+
+    /// By default, any numbers can be used as ranks, as long as lower numbers
+    /// mean better placements. This method returns a rater that only accepts
+    /// standard competition rankings ("1224" ranking), in which tied teams
+    /// share a rank and the following rank is skipped accordingly. Other rank
+    /// vectors are rejected with `BBTError::InvalidRanks`.
+    pub fn strict_ranks(mut self) -> Rater<F> {
+        self.strict_ranks = true;
+        self
+    }
+
+    /// This method returns a rater that passes every rating it writes back,
+    /// in `update_ratings`, `duel` and the other update methods, through the
+    /// given `Policy` first. Players whose ratings are not updated, such as
+    /// players with a sigma of zero, are not passed to the policy.
+    ///
+    /// The policy is neither serialized nor archived, so it has to be set
+    /// again on a deserialized rater.
+    #[cfg(feature = "alloc")]
+    pub fn with_policy<P: Policy<F> + 'static>(mut self, policy: P) -> Rater<F> {
+        self.policy = Some(Box::new(policy));
+        self
+    }
+
+    /// This method returns a rater that computes the exponentials and square
+    /// roots of the pairwise terms of its updates and predictions with the
+    /// given `MathBackend`, such as `FastMath`, instead of `Float::exp` and
+    /// `Float::sqrt`. The vectorized kernel of the `simd` feature is not used
+    /// with a backend.
+    ///
+    /// Like the policy, the backend is neither serialized nor archived.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating, StdMath};
+    ///
+    /// let rater = Rater::default().with_math_backend(&StdMath);
+    /// let (winner, _) = rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+    ///
+    /// assert_eq!(winner, Rater::default().duel(Rating::default(), Rating::default(), Outcome::Win).0);
+    /// ```
+    pub fn with_math_backend(mut self, backend: &'static dyn MathBackend<F>) -> Rater<F> {
+        self.math = Some(backend);
+        self
+    }
+
+    /// This method returns a rater that lets ratings move quickly again after
+    /// surprising results, like the volatility of Glicko-2, for players whose
+    /// skill suddenly changed. Otherwise, sigma only ever shrinks, and a
+    /// converged rating takes many games to catch up with a new skill.
+    ///
+    /// The surprise of a result is `-log2(p)`, where `p` is the probability
+    /// that the model gave it before the game: the probability that a team
+    /// finished ahead of a team it beat, as given by `win_probability` for
+    /// the teams' aggregate ratings. If the surprise of any pairwise result
+    /// of a team exceeds `threshold_bits`, both teams of that pair are
+    /// surprised, and the variance of each of their players grows by
+    /// `inflation²` before the update, so that a player's sigma grows by at
+    /// most `inflation` per game. Ties are never surprising, and fully
+    /// certain players with a sigma of zero are never inflated.
+    ///
+    /// Expected results are not surprising, so the rater only differs from
+    /// one without volatility after unexpected ones. Volatility applies to
+    /// `update_ratings` and the update methods that take the same input,
+    /// `update_batch`, `update_ratings_const` and `duel`, and to
+    /// `update_ratings_windowed`, which decides whether a team is surprised
+    /// from all of its pairwise results, not only those within the window.
+    /// It does not apply to the other approximations and specialized
+    /// updates, such as `update_period`. Like the policy, it is neither
+    /// serialized nor archived.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating};
+    ///
+    /// let rater = Rater::default().with_volatility(3.0, 2.0);
+    /// let (favourite, underdog) = (Rating::new(35.0, 1.0), Rating::new(20.0, 1.0));
+    ///
+    /// // The favourite had a chance of about 92% to win, so losing is a
+    /// // surprise of almost four bits.
+    /// let (new_favourite, _) = rater.duel(favourite, underdog, Outcome::Loss);
+    /// let (plain, _) = Rater::default().duel(favourite, underdog, Outcome::Loss);
+    /// assert!(new_favourite.mu() < plain.mu());
+    /// assert!(new_favourite.sigma() > favourite.sigma());
+    /// ```
+    pub fn with_volatility(mut self, threshold_bits: F, inflation: F) -> Rater<F> {
+        self.volatility = Some(Volatility {
+            max_probability: F::from_f64(2.0).powf(-threshold_bits),
+            inflation_sq: inflation * inflation,
+        });
+        self
+    }
+
+    /// This method returns a rater that protects new players from losing
+    /// mu: in the first `games` games counted by a `TrackedRating`, a
+    /// player's mu never decreases, while sigma is updated as usual. Wins
+    /// count fully, and losses only make the rating more certain.
+    ///
+    /// Only the protected player is affected. Opponents are updated exactly as
+    /// without protection, so they still gain from beating a protected
+    /// player, and mu is not conserved while players are protected.
+    ///
+    /// Protection applies to `duel_tracked` and `update_tracked_ratings`,
+    /// since the other update methods do not know how many games a player
+    /// has played. Like the policy, it is neither serialized nor archived.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, TrackedRating};
+    ///
+    /// let rater = Rater::default().with_loss_protection(10);
+    /// let mut newcomer = TrackedRating::default();
+    /// let mut veteran = TrackedRating::default();
+    ///
+    /// rater.duel_tracked(&mut newcomer, &mut veteran, Outcome::Loss);
+    ///
+    /// assert_eq!(newcomer.mu(), 25.0);
+    /// assert!(newcomer.sigma() < 25.0 / 3.0);
+    /// assert!(veteran.mu() > 25.0);
+    /// ```
+    pub fn with_loss_protection(mut self, games: u32) -> Rater<F> {
+        self.loss_protection = games;
+        self
+    }
+
+    /// Returns `e^x`, computed with the math backend if there is one.
+    fn exp(&self, x: F) -> F {
+        match self.math {
+            Some(math) => math.exp(x),
+            None => x.exp(),
+        }
+    }
+
+    /// Returns the square root of `x`, computed with the math backend if
+    /// there is one.
+    fn sqrt(&self, x: F) -> F {
+        match self.math {
+            Some(math) => math.sqrt(x),
+            None => x.sqrt(),
+        }
+    }
+
+    /// Computes `logistic` with the math backend.
+    fn logistic(&self, diff: F, c: F) -> F {
+        match self.math {
+            Some(math) => F::ONE / (F::ONE + math.exp(-diff / c)),
+            None => logistic(diff, c),
+        }
+    }
+
+    /// Returns the policy of the rater, if it has one.
+    fn policy(&self) -> Option<&dyn Policy<F>> {
+        #[cfg(feature = "alloc")]
+        {
+            self.policy.as_deref()
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            None
+        }
+    }
+
+    /// Returns what happens to updated ratings before they are written back.
+    fn write_back(&self) -> WriteBack<'_, F> {
+        WriteBack {
+            truncation: self.truncation,
+            policy: self.policy(),
+        }
+    }
+}
+
+/// WriteBack turns an updated rating into the rating that is written back:
+/// the rating is truncated as by `Rater::with_truncation`, and then passed to
+/// the policy of the rater. The default writes back updated ratings
+/// unchanged.
+#[derive(Clone, Copy, Default)]
+struct WriteBack<'a, F: Float> {
+    truncation: Option<F>,
+    policy: Option<&'a dyn Policy<F>>,
+}
+
+impl<'a, F: Float> WriteBack<'a, F> {
+    /// Returns the rating to write back for a player whose rating was `old`
+    /// before the update and `new` after it.
+    fn apply(&self, old: &Rating<F>, new: Rating<F>) -> Rating<F> {
+        let new = match self.truncation {
+            Some(lower) => new.truncated(lower),
+            None => new,
+        };
+
+        match self.policy {
+            Some(policy) => policy.apply(old, &new),
+            None => new,
+        }
+    }
+}
+
+impl<F: Float> std::fmt::Debug for Rater<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Rater")
+            .field("beta", &self.beta())
+            .field(
+                "allow_fewer_than_two_teams",
+                &self.allow_fewer_than_two_teams,
+            )
+            .field("strict_ranks", &self.strict_ranks)
+            .field("policy", &self.policy().map(|_| ".."))
+            .field("math", &self.math.map(|_| ".."))
+            .field("volatility", &self.volatility)
+            .field("loss_protection", &self.loss_protection)
+            .field("truncation", &self.truncation)
+            .finish()
+    }
+}
+
+impl Default for Rater {
+    /// This method instantiates a new rater the default β-parameter of 25.0/6.0
+    /// used in the paper.
+    ///
+    /// This is only implemented for `f64` raters, so that `Rater::default()`
+    /// keeps working without type annotations. Other scalar types can use
+    /// `Rater::new(25.0 / 6.0)`.
+    fn default() -> Rater {
+        Rater::new(25.0 / 6.0)
+    }
+}
+
+impl<F: Float> Rater<F> {
+    /// This method takes a vector of teams, with each team being a vector of
+    /// player ratings, and a vector ranks of the same size that specifies the
+    /// order in which the team finished a game. It returns either
+    /// `Err(BBTError)` if the input is incorrect or
+    /// `Ok(Vec<Vec<Rating<F>>>)`. The returned vector is an updated version of
+    /// the `teams` vector that was passed into the function.
+    ///
+    /// A team whose players all have a sigma of zero is treated as fully
+    /// certain: its players keep their ratings, and the other teams are
+    /// updated against it as if its skill were known exactly.
+    ///
+    /// For valid ratings (see `Rating::is_valid`) and a finite β, this method
+    /// never panics and never returns non-finite ratings. Invalid ratings are
+    /// reported as `BBTError::InvalidRating`.
+    ///
+    /// With the `strict-math` feature enabled, every updated rating is checked
+    /// to have a finite mu and a finite sigma, which must be positive unless
+    /// it was zero before the update. If any rating fails the check,
+    /// `BBTError::NumericalError` is returned instead of the updated ratings,
+    /// so no partial update is ever handed back.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        with_workspace(teams.len(), |work| {
+            self.update_in_workspace(teams, &ranks, work, None)
+        })
+    }
+
+    /// This method performs the same update as `update_ratings`, but uses the
+    /// given `UpdateScratch` for its working memory. The updated ratings are
+    /// written back into the `teams` vector, so once the scratch space has
+    /// grown to the size of the largest match, updates allocate no memory.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_in(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+        scratch: &mut UpdateScratch<F>,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let mut work = scratch.workspace(teams.len());
+
+        self.update_in_workspace(teams, &ranks, &mut work, None)
+    }
+
+    /// This method performs the same update as `update_ratings`, but uses
+    /// working memory owned by the rater, which is grown as needed and reused
+    /// across calls. A long-lived rater therefore stops allocating once it has
+    /// seen its largest match.
+    ///
+    /// The rater stays `Send` and `Sync`; since this method takes `&mut self`,
+    /// a rater shared between threads can still use the other methods.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_buffered(
+        &mut self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let mut scratch = mem::take(&mut self.scratch);
+        let result = self.update_ratings_in(teams, ranks, &mut scratch);
+        self.scratch = scratch;
+
+        result
+    }
+
+    /// This method performs the same update as `update_ratings`, but skips all
+    /// input validation. It is meant for hot paths whose inputs are known to
+    /// be valid.
+    ///
+    /// The caller must ensure that `teams` and `ranks` are of the same length
+    /// and that no team is empty. Violating these preconditions is not
+    /// undefined behavior, but it may panic or return meaningless ratings.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_unchecked(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+    ) -> Vec<Vec<Rating<F>>> {
+        debug_assert_eq!(teams.len(), ranks.len());
+        debug_assert!(teams.iter().all(|team| !team.is_empty()));
+
+        with_workspace(teams.len(), |work| {
+            self.compute_team_updates(&mut teams, &ranks, work);
+            apply_team_updates(&mut teams, work, self.write_back(), |_, _| ());
+        });
+
+        teams
+    }
+
+    /// This method performs the same update as `update_ratings`, and also
+    /// returns an `UpdateReport` describing how the update went.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::type_complexity)]
+    pub fn update_ratings_with_report(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+    ) -> Result<(Vec<Vec<Rating<F>>>, UpdateReport), BBTError> {
+        let mut report = UpdateReport::default();
+
+        let result = with_workspace(teams.len(), |work| {
+            self.update_in_workspace(teams, &ranks, work, Some(&mut report))
+        })?;
+
+        Ok((result, report))
+    }
+
+    /// This method performs the same update as `update_ratings`, and also
+    /// returns the intermediate values of Algorithm 1 that explain it: the
+    /// omega and delta of every team, and the old and new rating of every
+    /// player. The updated ratings are in the `new` field of the players,
+    /// or can be taken out with `UpdateDetails::into_ratings`.
+    ///
+    /// Both methods run the same update; this one only copies the team
+    /// values out of the working memory before it is released.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_detailed(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+    ) -> Result<UpdateDetails<F>, BBTError> {
+        let old = teams.clone();
+        let mut report = UpdateReport::default();
+
+        let (new, team_omega, team_delta) = with_workspace(teams.len(), |work| {
+            let new = self.update_in_workspace(teams, &ranks, work, Some(&mut report))?;
+
+            Ok((new, work.team_omega.to_vec(), work.team_delta.to_vec()))
+        })?;
+
+        let players = old
+            .into_iter()
+            .zip(new)
+            .enumerate()
+            .map(|(team_idx, (old_team, new_team))| {
+                old_team
+                    .into_iter()
+                    .zip(new_team)
+                    .enumerate()
+                    .map(|(player_idx, (old, new))| PlayerUpdate {
+                        mu_delta: new.mu - old.mu,
+                        sigma_delta: new.sigma - old.sigma,
+                        clamped: report.clamped_players.contains(&(team_idx, player_idx)),
+                        old,
+                        new,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(UpdateDetails {
+            team_omega,
+            team_delta,
+            players,
+        })
+    }
+
+    /// This method explains the update of a match without performing it:
+    /// for every team, it returns one `PairContribution` per opponent, in
+    /// the order of the teams. The omega and delta of a team are the sums of
+    /// the contributions of its opponents, so a team that finished well but
+    /// lost rating can see which opponents it did worse against than the
+    /// model expected.
+    ///
+    /// The match is validated like in `update_ratings`. Pairs of fully
+    /// certain teams with β = 0 carry no information about each other and
+    /// are left out.
+    #[cfg(feature = "alloc")]
+    pub fn explain(
+        &self,
+        teams: &[&[Rating<F>]],
+        ranks: &[usize],
+    ) -> Result<Vec<Vec<PairContribution<F>>>, BBTError> {
+        let mut team_mu = vec![F::ZERO; teams.len()];
+        let mut team_sigma_sq = vec![F::ZERO; teams.len()];
+
+        self.validate_with_totals(teams, ranks, |team_idx, mu, sigma_sq| {
+            team_mu[team_idx] = mu;
+            team_sigma_sq[team_idx] = sigma_sq;
+        })?;
+
+        let two_beta_sq = F::from_f64(2.0) * self.beta_sq;
+
+        // The terms are computed with the same operations as in
+        // `team_omega_delta`.
+        let explain_team = |team_idx: usize| {
+            let sigma = self.sqrt(team_sigma_sq[team_idx]);
+
+            (0..teams.len())
+                .filter(|&team2_idx| team2_idx != team_idx)
+                .filter_map(|team2_idx| {
+                    let c =
+                        self.sqrt(team_sigma_sq[team_idx] + team_sigma_sq[team2_idx] + two_beta_sq);
+
+                    if c == F::ZERO {
+                        return None;
+                    }
+
+                    let piq = self.logistic(team_mu[team_idx] - team_mu[team2_idx], c);
+                    let pqi = self.logistic(team_mu[team2_idx] - team_mu[team_idx], c);
+
+                    let s = match ranks[team2_idx].cmp(&ranks[team_idx]) {
+                        Ordering::Greater => F::ONE,
+                        Ordering::Equal => F::from_f64(0.5),
+                        Ordering::Less => F::ZERO,
+                    };
+
+                    let gamma = sigma / c;
+
+                    Some(PairContribution {
+                        opponent: team2_idx,
+                        expected: piq,
+                        actual: s,
+                        omega: (team_sigma_sq[team_idx] / c) * (s - piq),
+                        delta: gamma * (team_sigma_sq[team_idx] / (c * c)) * piq * pqi,
+                    })
+                })
+                .collect()
+        };
+
+        Ok((0..teams.len()).map(explain_team).collect())
+    }
+
+    /// This method performs the same update as `update_ratings`, and calls
+    /// `observer` with the model's expectation for every ordered pair of
+    /// teams as it is computed in Step 2, so that research code gets the
+    /// pairwise probabilities without a second prediction pass. Pairs of
+    /// fully certain teams with β = 0 carry no information about each other
+    /// and are not observed.
+    ///
+    /// The update always takes the general path of Step 2, which the
+    /// shortcuts for equal variances and large matches reproduce exactly, so
+    /// the ratings are the same as those of `update_ratings`. The only
+    /// exception is the vectorized kernel of the `simd` feature, which
+    /// `update_ratings` uses and which differs by up to 1e-12 (see the crate
+    /// documentation).
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_observed(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+        observer: &mut dyn FnMut(PairObservation<F>),
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        with_workspace(teams.len(), |work| {
+            self.validate_into(&teams, &ranks, work)?;
+
+            for team_idx in 0..ranks.len() {
+                let (omega, delta) = self.team_omega_delta_observed(
+                    team_idx,
+                    0..ranks.len(),
+                    work.team_mu,
+                    work.team_sigma_sq,
+                    &ranks,
+                    (work.omega_terms, work.delta_terms),
+                    &mut *observer,
+                );
+
+                work.team_omega[team_idx] = omega;
+                work.team_delta[team_idx] = delta;
+            }
+
+            #[cfg(feature = "strict-math")]
+            check_team_updates(&teams, work)?;
+
+            apply_team_updates(&mut teams, work, self.write_back(), |_, _| ());
+
+            Ok(teams)
+        })
+    }
+
+    /// This method performs the same update as `update_ratings`, but only
+    /// applies the given fraction of it: every mu and every variance (sigma²)
+    /// moves `weight` of the way from its old to its updated value. A weight
+    /// of one is a full update and a weight of zero leaves the ratings
+    /// unchanged, so weights can discount games that should count less.
+    ///
+    /// Returns `BBTError::InvalidParameter` if the weight is not between zero
+    /// and one.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_weighted(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+        weight: F,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        if !(weight >= F::ZERO && weight <= F::ONE) {
+            return Err(BBTError::InvalidParameter { name: "weight" });
+        }
+
+        if weight == F::ONE {
+            return self.update_ratings(teams, ranks);
+        }
+
+        let old: Vec<Vec<(F, F)>> = teams
+            .iter()
+            .map(|team| team.iter().map(|r| (r.mu, r.sigma_sq)).collect())
+            .collect();
+
+        let mut teams = self.update_ratings(teams, ranks)?;
+
+        for (team, old_team) in teams.iter_mut().zip(&old) {
+            for (rating, &(mu, sigma_sq)) in team.iter_mut().zip(old_team) {
+                let new_mu = mu + weight * (rating.mu - mu);
+                let new_sigma_sq = sigma_sq + weight * (rating.sigma_sq - sigma_sq);
+                *rating = Rating::new(new_mu, new_sigma_sq.sqrt());
+            }
+        }
+
+        Ok(teams)
+    }
+
+    /// This method performs an approximation of `update_ratings` for matches
+    /// with many teams. Instead of comparing every team with every other
+    /// team, each team is only compared with the teams that placed at most
+    /// `window` positions above or below it, which takes O(n·window) instead
+    /// of O(n²) comparisons. Tied teams share a position, so a tied group is
+    /// either compared as a whole or not at all.
+    ///
+    /// With `window` at least as large as the number of teams, the result is
+    /// identical to `update_ratings`. With smaller windows, teams lose the
+    /// contributions of far-away opponents. These are small when the field
+    /// finishes roughly in the expected order: for 50 teams with sigma 2,
+    /// spaced 2 mu apart and finishing in order of mu, a window of 10
+    /// changes no mu by more than 0.1. When the result is a surprise (e.g.
+    /// all teams start at the default rating), far-away opponents carry
+    /// real information and the approximation is much coarser.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_windowed(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<usize>,
+        window: usize,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        if window >= teams.len() {
+            return self.update_ratings(teams, ranks);
+        }
+
+        self.validate(&teams, &ranks)?;
+
+        // Sort the teams by rank; tied teams share the position of the first
+        // team in their group.
+        let mut order: Vec<usize> = (0..teams.len()).collect();
+        order.sort_by_key(|&team_idx| ranks[team_idx]);
+
+        let mut position = vec![0; teams.len()];
+        for (sorted_idx, &team_idx) in order.iter().enumerate() {
+            position[team_idx] = match sorted_idx {
+                0 => 0,
+                _ if ranks[order[sorted_idx - 1]] == ranks[team_idx] => {
+                    position[order[sorted_idx - 1]]
+                }
+                _ => sorted_idx,
+            };
+        }
+
+        with_workspace(teams.len(), |work| {
+            compute_team_totals(&teams, work);
+            self.inflate_surprised(&mut teams, &ranks, work);
+
+            for (sorted_idx, &team_idx) in order.iter().enumerate() {
+                let within_window = |&&other: &&usize| {
+                    let (a, b) = (position[team_idx], position[other]);
+                    a.max(b) - a.min(b) <= window
+                };
+
+                let above = order[..sorted_idx].iter().rev().take_while(within_window);
+                let below = order[sorted_idx + 1..].iter().take_while(within_window);
+
+                let (omega, delta) = self.team_omega_delta(
+                    team_idx,
+                    above.chain(below).cloned(),
+                    work.team_mu,
+                    work.team_sigma_sq,
+                    &ranks,
+                    (work.omega_terms, work.delta_terms),
+                );
+
+                work.team_omega[team_idx] = omega;
+                work.team_delta[team_idx] = delta;
+            }
+
+            #[cfg(feature = "strict-math")]
+            check_team_updates(&teams, work)?;
+
+            apply_team_updates(&mut teams, work, self.write_back(), |_, _| ());
+
+            Ok(teams)
+        })
+    }
+
+    /// This method updates the ratings of many independent games, one after
+    /// another, sharing the working memory between them. The results are the
+    /// same as calling `update_ratings` for each game in turn.
+    ///
+    /// All games are validated before any ratings are updated, so if any game
+    /// is malformed, the returned `BatchError` names it and no ratings have
+    /// been changed. The only exception is `BBTError::NumericalError` with the
+    /// `strict-math` feature, which can only be detected while processing the
+    /// games; the games before the failing one have been updated in that case.
+    #[cfg(feature = "alloc")]
+    pub fn update_batch(&self, games: &mut [GameMut<F>]) -> Result<(), BatchError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("update_batch", games = games.len()).entered();
+
+        for (index, game) in games.iter().enumerate() {
+            self.validate(game.teams, game.ranks)
+                .map_err(|error| BatchError { game: index, error })?;
+        }
+
+        let max_teams = games.iter().map(|game| game.teams.len()).max();
+        let mut scratch = UpdateScratch::with_capacity(max_teams.unwrap_or(0));
+
+        for (index, game) in games.iter_mut().enumerate() {
+            let mut work = scratch.workspace(game.teams.len());
+
+            self.update_validated(game.teams, game.ranks, &mut work)
+                .map_err(|error| BatchError { game: index, error })?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates the ratings of an already validated game in place.
+    #[cfg(feature = "alloc")]
+    fn update_validated(
+        &self,
+        teams: &mut [Vec<Rating<F>>],
+        ranks: &[usize],
+        work: &mut Workspace<F>,
+    ) -> Result<(), BBTError> {
+        self.compute_team_updates(teams, ranks, work);
+
+        #[cfg(feature = "strict-math")]
+        check_team_updates(teams, work)?;
+
+        apply_team_updates(teams, work, self.write_back(), |_, _| ());
+
+        Ok(())
+    }
+
+    /// This method performs the same update as `update_ratings` for a match
+    /// whose number of teams is known at compile time. The working memory is
+    /// kept on the stack and the ratings are updated in place, so this method
+    /// performs no heap allocation at all.
+    pub fn update_ratings_const<const N: usize>(
+        &self,
+        teams: &mut [&mut [Rating<F>]; N],
+        ranks: [usize; N],
+    ) -> Result<(), BBTError> {
+        let mut arrays = [[F::ZERO; N]; 6];
+        let mut work = Workspace::from_arrays(&mut arrays, N);
+
+        self.validate_into(&teams[..], &ranks, &mut work)?;
+        self.inflate_surprised(&mut teams[..], &ranks, &mut work);
+        self.compute_all_omega_delta(&ranks, &mut work);
+
+        #[cfg(feature = "strict-math")]
+        check_team_updates(&teams[..], &work)?;
+
+        apply_team_updates(&mut teams[..], &work, self.write_back(), |_, _| ());
+
+        Ok(())
+    }
+
+    /// Validates the input and performs the update using the given workspace.
+    #[cfg(feature = "alloc")]
+    fn update_in_workspace(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        ranks: &[usize],
+        work: &mut Workspace<F>,
+        mut report: Option<&mut UpdateReport>,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "update_ratings",
+            teams = teams.len(),
+            players = teams.iter().map(Vec::len).sum::<usize>()
+        )
+        .entered();
+
+        self.validate_into(&teams, ranks, work)?;
+        self.inflate_surprised(&mut teams, ranks, work);
+        self.compute_all_omega_delta(ranks, work);
+
+        #[cfg(feature = "strict-math")]
+        check_team_updates(&teams, work)?;
+
+        apply_team_updates(&mut teams, work, self.write_back(), |team, player| {
+            if let Some(ref mut report) = report {
+                report.clamps += 1;
+                report.clamped_players.push((team, player));
+            }
+        });
+
+        Ok(teams)
+    }
+
+    /// Computes the team skills and variances (Step 1), inflating the
+    /// variances of surprised teams, and the team omega and delta values
+    /// (Step 2) of Algorithm 1.
+    #[cfg(feature = "alloc")]
+    fn compute_team_updates<T: AsRef<[Rating<F>]> + AsMut<[Rating<F>]>>(
+        &self,
+        teams: &mut [T],
+        ranks: &[usize],
+        work: &mut Workspace<F>,
+    ) {
+        ////////////////////////////////////////////////////////////////////////
+        // Step 1 - Collect Team skill and variance ////////////////////////////
+        ////////////////////////////////////////////////////////////////////////
+
+        compute_team_totals(teams, work);
+        self.inflate_surprised(teams, ranks, work);
+
+        ////////////////////////////////////////////////////////////////////////
+        // Step 2 - Compute Team Omega and Delta ///////////////////////////////
+        ////////////////////////////////////////////////////////////////////////
+
+        self.compute_all_omega_delta(ranks, work);
+    }
+
+    /// Inflates the variances of the players of surprised teams, as set by
+    /// `with_volatility`. The team skills and variances of Step 1 have to be
+    /// in the workspace already, and are recomputed if a team was surprised.
+    fn inflate_surprised<T: AsRef<[Rating<F>]> + AsMut<[Rating<F>]>>(
+        &self,
+        teams: &mut [T],
+        ranks: &[usize],
+        work: &mut Workspace<F>,
+    ) {
+        let volatility = match self.volatility {
+            Some(volatility) => volatility,
+            None => return,
+        };
+
+        let mut inflated_any = false;
+
+        for team_idx in 0..ranks.len() {
+            let surprised = (0..ranks.len()).any(|other| {
+                let (ahead, behind) = match ranks[team_idx].cmp(&ranks[other]) {
+                    Ordering::Less => (team_idx, other),
+                    Ordering::Greater => (other, team_idx),
+                    Ordering::Equal => return false,
+                };
+
+                self.is_surprising(
+                    &volatility,
+                    work.team_mu[ahead] - work.team_mu[behind],
+                    work.team_sigma_sq[ahead] + work.team_sigma_sq[behind],
+                )
+            });
+
+            if surprised {
+                for player in teams[team_idx].as_mut() {
+                    *player = inflated(player, volatility.inflation_sq);
+                }
+                inflated_any = true;
+            }
+        }
+
+        if inflated_any {
+            compute_team_totals(teams, work);
+        }
+    }
+
+    /// Returns whether a team finishing ahead of another one is surprising,
+    /// given the difference and the sum of the variances of their skills.
+    /// Fully certain teams with β = 0 are never surprised.
+    fn is_surprising(&self, volatility: &Volatility<F>, mu_diff: F, sigma_sq_sum: F) -> bool {
+        let c = self.sqrt(sigma_sq_sum + F::from_f64(2.0) * self.beta_sq);
+
+        c != F::ZERO && self.logistic(mu_diff, c) < volatility.max_probability
+    }
+
+    /// Computes the team omega and delta values (Step 2) from the team skills
+    /// and variances in the workspace.
+    fn compute_all_omega_delta(&self, ranks: &[usize], work: &mut Workspace<F>) {
+        #[cfg(feature = "rayon")]
+        {
+            if ranks.len() >= PARALLEL_TEAMS {
+                self.compute_omega_delta_parallel(ranks, work);
+                return;
+            }
+        }
+
+        self.compute_omega_delta(ranks, work);
+    }
+
+    /// Computes the omega and delta values of every team, one after another.
+    fn compute_omega_delta(&self, ranks: &[usize], work: &mut Workspace<F>) {
+        for team_idx in 0..ranks.len() {
+            let (omega, delta) = self.team_omega_delta_against_all(
+                team_idx,
+                work.team_mu,
+                work.team_sigma_sq,
+                ranks,
+                (work.omega_terms, work.delta_terms),
+            );
+
+            work.team_omega[team_idx] = omega;
+            work.team_delta[team_idx] = delta;
+        }
+    }
+
+    /// Computes the omega and delta values of every team in parallel. Each
+    /// team is still summed on its own in the same order as in the serial
+    /// version, so the results are identical.
+    #[cfg(feature = "rayon")]
+    fn compute_omega_delta_parallel(&self, ranks: &[usize], work: &mut Workspace<F>) {
+        use rayon::prelude::*;
+
+        let n_teams = ranks.len();
+        let team_mu = &*work.team_mu;
+        let team_sigma_sq = &*work.team_sigma_sq;
+
+        work.team_omega
+            .par_iter_mut()
+            .zip(work.team_delta.par_iter_mut())
+            .enumerate()
+            .for_each_init(
+                || (vec![F::ZERO; n_teams], vec![F::ZERO; n_teams]),
+                |terms, (team_idx, (omega, delta))| {
+                    let (o, d) = self.team_omega_delta_against_all(
+                        team_idx,
+                        team_mu,
+                        team_sigma_sq,
+                        ranks,
+                        (&mut terms.0, &mut terms.1),
+                    );
+
+                    *omega = o;
+                    *delta = d;
+                },
+            );
+    }
+
+    /// Computes the omega and delta values of a single team from its pairwise
+    /// comparisons with all other teams, using the vectorized kernel of the
+    /// `simd` feature if it is available, or the general path otherwise.
+    fn team_omega_delta_against_all(
+        &self,
+        team_idx: usize,
+        team_mu: &[F],
+        team_sigma_sq: &[F],
+        ranks: &[usize],
+        terms: (&mut [F], &mut [F]),
+    ) -> (F, F) {
+        let (omega_terms, delta_terms) = terms;
+
+        #[cfg(feature = "simd")]
+        if self.math.is_none() {
+            let n_terms = F::pair_terms_simd(
+                team_idx,
+                team_mu,
+                team_sigma_sq,
+                ranks,
+                self.beta_sq,
+                (&mut *omega_terms, &mut *delta_terms),
+            );
+
+            if let Some(n_terms) = n_terms {
+                #[cfg(test)]
+                COMPARISONS.with(|count| count.set(count.get() + n_terms));
+
+                return (
+                    canonical_sum(&mut omega_terms[..n_terms]),
+                    canonical_sum(&mut delta_terms[..n_terms]),
+                );
+            }
+        }
+
+        self.team_omega_delta(
+            team_idx,
+            0..ranks.len(),
+            team_mu,
+            team_sigma_sq,
+            ranks,
+            (omega_terms, delta_terms),
+        )
+    }
+
+    /// Computes the omega and delta values of a single team from its pairwise
+    /// comparisons with all other teams.
+    fn team_omega_delta<I: Iterator<Item = usize>>(
+        &self,
+        team_idx: usize,
+        opponents: I,
+        team_mu: &[F],
+        team_sigma_sq: &[F],
+        ranks: &[usize],
+        terms: (&mut [F], &mut [F]),
+    ) -> (F, F) {
+        self.team_omega_delta_observed(
+            team_idx,
+            opponents,
+            team_mu,
+            team_sigma_sq,
+            ranks,
+            terms,
+            |_| (),
+        )
+    }
+
+    /// Computes the same values as `team_omega_delta`, and calls `observe`
+    /// with every pair of teams that is compared.
+    #[allow(clippy::too_many_arguments)]
+    fn team_omega_delta_observed<I: Iterator<Item = usize>, O: FnMut(PairObservation<F>)>(
+        &self,
+        team_idx: usize,
+        opponents: I,
+        team_mu: &[F],
+        team_sigma_sq: &[F],
+        ranks: &[usize],
+        terms: (&mut [F], &mut [F]),
+        observe: O,
+    ) -> (F, F) {
+        let (omega_terms, delta_terms) = terms;
+        let n_terms = self.pair_terms_into(
+            team_idx,
+            opponents,
+            team_mu,
+            team_sigma_sq,
+            ranks,
+            (&mut *omega_terms, &mut *delta_terms),
+            observe,
+        );
+
+        // The contributions of the opponents are summed in a canonical order,
+        // so that the result does not depend on the order of the teams.
+        (
+            canonical_sum(&mut omega_terms[..n_terms]),
+            canonical_sum(&mut delta_terms[..n_terms]),
+        )
+    }
+
+    /// Writes the omega and delta terms of team `team_idx` against the given
+    /// opponents to the start of `terms`, calling `observe` with every pair
+    /// of teams that is compared, and returns the number of terms written.
+    #[allow(clippy::too_many_arguments)]
+    fn pair_terms_into<I: Iterator<Item = usize>, O: FnMut(PairObservation<F>)>(
+        &self,
+        team_idx: usize,
+        opponents: I,
+        team_mu: &[F],
+        team_sigma_sq: &[F],
+        ranks: &[usize],
+        terms: (&mut [F], &mut [F]),
+        mut observe: O,
+    ) -> usize {
+        let (omega_terms, delta_terms) = terms;
+        let mut n_terms = 0;
+
+        let two_beta_sq = F::from_f64(2.0) * self.beta_sq;
+        let sigma = self.sqrt(team_sigma_sq[team_idx]);
+
+        for team2_idx in opponents {
+            if team_idx == team2_idx {
+                continue;
+            }
+
+            #[cfg(test)]
+            COMPARISONS.with(|count| count.set(count.get() + 1));
+
+            let c = self.sqrt(team_sigma_sq[team_idx] + team_sigma_sq[team2_idx] + two_beta_sq);
+
+            // Two fully certain teams with β = 0 carry no information about
+            // each other.
+            if c == F::ZERO {
+                continue;
+            }
+
+            let piq = self.logistic(team_mu[team_idx] - team_mu[team2_idx], c);
+            let pqi = self.logistic(team_mu[team2_idx] - team_mu[team_idx], c);
+            let ri = ranks[team_idx];
+            let rq = ranks[team2_idx];
+
+            let s = match rq.cmp(&ri) {
+                Ordering::Greater => F::ONE,
+                Ordering::Equal => F::from_f64(0.5),
+                Ordering::Less => F::ZERO,
+            };
+
+            observe(PairObservation {
+                team: team_idx,
+                opponent: team2_idx,
+                rank: ri,
+                opponent_rank: rq,
+                piq,
+                s,
+            });
+
+            let (delta, eta) = pair_terms(team_sigma_sq[team_idx], sigma, c, piq, pqi, s);
+
+            omega_terms[n_terms] = delta;
+            delta_terms[n_terms] = eta;
+            n_terms += 1;
+        }
+
+        n_terms
+    }
+
+    /// This method calculates the new ratings for two players after a
+    /// head-to-head duel. The outcome is from the first player `p1`'s
+    /// perspective, i.e. `Win` if the first player won, `Loss` if the second
+    /// player won and `Draw` if neither player won.
+    ///
+    /// If either rating is invalid (see `Rating::is_valid`), both ratings are
+    /// returned unchanged.
+    pub fn duel(&self, p1: Rating<F>, p2: Rating<F>, outcome: Outcome) -> (Rating<F>, Rating<F>) {
+        let (ratings, _clamps) = self.duel_counting_clamps(p1, p2, outcome);
+        ratings
+    }
+
+    /// Rates a duel like `duel`, and also returns how many of the two
+    /// variance reductions had to be clamped.
+    fn duel_counting_clamps(
+        &self,
+        p1: Rating<F>,
+        p2: Rating<F>,
+        outcome: Outcome,
+    ) -> ((Rating<F>, Rating<F>), u32) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("duel", teams = 2, players = 2).entered();
+
+        if !p1.is_valid() || !p2.is_valid() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("rejected duel with an invalid rating");
+
+            return ((p1, p2), 0);
+        }
+
+        let (p1, p2) = match self.volatility {
+            Some(ref volatility) => {
+                let (mu1, mu2) = (sum_of_one(p1.mu), sum_of_one(p2.mu));
+                let sigma_sq_sum = sum_of_one(p1.sigma_sq) + sum_of_one(p2.sigma_sq);
+
+                let surprised = match outcome {
+                    Outcome::Win => self.is_surprising(volatility, mu1 - mu2, sigma_sq_sum),
+                    Outcome::Loss => self.is_surprising(volatility, mu2 - mu1, sigma_sq_sum),
+                    Outcome::Draw => false,
+                };
+
+                if surprised {
+                    (
+                        inflated(&p1, volatility.inflation_sq),
+                        inflated(&p2, volatility.inflation_sq),
+                    )
+                } else {
+                    (p1, p2)
+                }
+            }
+            None => (p1, p2),
+        };
+
+        let s1 = match outcome {
+            Outcome::Win => F::ONE,
+            Outcome::Loss => F::ZERO,
+            Outcome::Draw => F::from_f64(0.5),
+        };
+
+        self.duel_scored(p1, p2, s1)
+    }
+
+    /// Rates a duel of two valid ratings in which `p1` scored `s1` and `p2`
+    /// scored `1 - s1`, and returns how many of the two variance reductions
+    /// had to be clamped.
+    fn duel_scored(&self, p1: Rating<F>, p2: Rating<F>, s1: F) -> ((Rating<F>, Rating<F>), u32) {
+        // This is Algorithm 1 written out for two teams of one player each.
+        // Every value is computed with the same operations, in the same order,
+        // as in `update_ratings`, so the results are identical.
+        let mu1 = sum_of_one(p1.mu);
+        let mu2 = sum_of_one(p2.mu);
+        let sigma_sq1 = sum_of_one(p1.sigma_sq);
+        let sigma_sq2 = sum_of_one(p2.sigma_sq);
+
+        let c = self.sqrt(sigma_sq1 + sigma_sq2 + F::from_f64(2.0) * self.beta_sq);
+
+        // Two fully certain players with β = 0 carry no information about
+        // each other, and their ratings are not updated either way.
+        if c == F::ZERO {
+            return ((p1, p2), 0);
+        }
+
+        let p12 = self.logistic(mu1 - mu2, c);
+        let p21 = self.logistic(mu2 - mu1, c);
+
+        let s2 = F::ONE - s1;
+
+        let (omega1, delta1) = pair_terms(sigma_sq1, self.sqrt(sigma_sq1), c, p12, p21, s1);
+        let (omega2, delta2) = pair_terms(sigma_sq2, self.sqrt(sigma_sq2), c, p21, p12, s2);
+        let (omega1, delta1) = (sum_of_one(omega1), sum_of_one(delta1));
+        let (omega2, delta2) = (sum_of_one(omega2), sum_of_one(delta2));
+
+        #[cfg(feature = "validate")]
+        let values = validate::TeamValues {
+            mu: &[mu1, mu2],
+            sigma_sq: &[sigma_sq1, sigma_sq2],
+            omega: &[omega1, omega2],
+            delta: &[delta1, delta2],
+        };
+        #[cfg(feature = "validate")]
+        validate::assert_teams(&values);
+
+        let write_back = self.write_back();
+        let apply = |player: Rating<F>, rating: Rating<F>| write_back.apply(&player, rating);
+
+        let (new_p1, clamped1) = if sigma_sq1 == F::ZERO {
+            (p1, false)
+        } else {
+            let (rating, clamped) = updated_rating(&p1, sigma_sq1, omega1, delta1);
+            #[cfg(feature = "tracing")]
+            trace_update(0, 0, &rating, clamped);
+            #[cfg(feature = "validate")]
+            validate::assert_update(0, 0, &p1, &rating, &values);
+
+            (apply(p1, rating), clamped)
+        };
+
+        let (new_p2, clamped2) = if sigma_sq2 == F::ZERO {
+            (p2, false)
+        } else {
+            let (rating, clamped) = updated_rating(&p2, sigma_sq2, omega2, delta2);
+            #[cfg(feature = "tracing")]
+            trace_update(1, 0, &rating, clamped);
+            #[cfg(feature = "validate")]
+            validate::assert_update(1, 0, &p2, &rating, &values);
+
+            (apply(p2, rating), clamped)
+        };
+
+        ((new_p1, new_p2), clamped1 as u32 + clamped2 as u32)
+    }
+
+    /// This method rates a duel like `duel`, but updates the ratings in place
+    /// and returns a `RatingUpdate` with everything a match-end screen shows:
+    /// the ratings before and after the duel, the changes of mu, and the
+    /// probability that `p1` would win that the model assigned before the
+    /// duel.
+    pub fn duel_detailed(
+        &self,
+        p1: &mut Rating<F>,
+        p2: &mut Rating<F>,
+        outcome: Outcome,
+    ) -> RatingUpdate<F> {
+        let before = [*p1, *p2];
+        let win_probability = self.win_probability(p1, p2);
+
+        let (new_p1, new_p2) = self.duel(*p1, *p2, outcome);
+        *p1 = new_p1;
+        *p2 = new_p2;
+
+        RatingUpdate {
+            before,
+            after: [new_p1, new_p2],
+            mu_delta: [new_p1.mu - before[0].mu, new_p2.mu - before[1].mu],
+            win_probability,
+        }
+    }
+
+    /// Returns the probability that player `p1` beats player `p2` in a duel,
+    /// as estimated by the model. Two fully certain players with β = 0 are
+    /// ranked by their mu alone.
+    pub fn win_probability(&self, p1: &Rating<F>, p2: &Rating<F>) -> F {
+        let c = self.sqrt(p1.sigma_sq + p2.sigma_sq + F::from_f64(2.0) * self.beta_sq);
+
+        if c == F::ZERO {
+            return match p1.mu.partial_cmp(&p2.mu) {
+                Some(Ordering::Greater) => F::ONE,
+                Some(Ordering::Less) => F::ZERO,
+                _ => F::from_f64(0.5),
+            };
+        }
+
+        self.logistic(p1.mu - p2.mu, c)
+    }
+
+    /// Returns the quality of a duel between `p1` and `p2`, between zero and
+    /// one, as defined by TrueSkill: the probability of a draw, relative to
+    /// the probability of a draw between two equally skilled players. Evenly
+    /// matched players with certain ratings give the highest quality.
+    pub fn match_quality(&self, p1: &Rating<F>, p2: &Rating<F>) -> F {
+        let two_beta_sq = F::from_f64(2.0) * self.beta_sq;
+        let c_sq = p1.sigma_sq + p2.sigma_sq + two_beta_sq;
+
+        if c_sq == F::ZERO {
+            return if p1.mu == p2.mu { F::ONE } else { F::ZERO };
+        }
+
+        let diff = p1.mu - p2.mu;
+
+        self.sqrt(two_beta_sq / c_sq) * self.exp(-(diff * diff) / (F::from_f64(2.0) * c_sq))
+    }
+
+    /// Returns how many wins in a row against `opponent` the `chaser` needs
+    /// until their conservative estimate `mu - 3 * sigma` is greater than
+    /// that of `target`, or `None` if `max_games` wins are not enough. This
+    /// is a best-case projection: it assumes that the chaser wins every game.
+    ///
+    /// With a `target_outcome` of `None`, the target's rating is held fixed.
+    /// Otherwise, the target plays the opponent once for every game of the
+    /// chaser, with the given outcome, so a target that keeps winning too is
+    /// harder to catch. The opponent's rating is held fixed either way.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let (chaser, target) = (Rating::default(), Rating::new(28.0, 6.0));
+    ///
+    /// let wins = rater.games_to_overtake(&chaser, &target, &Rating::default(), 20, None);
+    /// assert_eq!(wins, Some(4));
+    ///
+    /// // A target that keeps winning takes much longer to catch.
+    /// let outcome = Some(Outcome::Win);
+    /// let wins = rater.games_to_overtake(&chaser, &target, &Rating::default(), 20, outcome);
+    /// assert_eq!(wins, Some(15));
+    /// ```
+    pub fn games_to_overtake(
+        &self,
+        chaser: &Rating<F>,
+        target: &Rating<F>,
+        opponent: &Rating<F>,
+        max_games: usize,
+        target_outcome: Option<Outcome>,
+    ) -> Option<usize> {
+        let (mut chaser, mut target) = (*chaser, *target);
+
+        let mut games = 0;
+
+        loop {
+            if chaser.conservative_estimate() > target.conservative_estimate() {
+                return Some(games);
+            }
+
+            if games == max_games {
+                return None;
+            }
+
+            chaser = self.duel(chaser, *opponent, Outcome::Win).0;
+
+            if let Some(outcome) = target_outcome {
+                target = self.duel(target, *opponent, outcome).0;
+            }
+
+            games += 1;
+        }
+    }
+}
+
+/// Workspace holds the working arrays of a single rating update.
+struct Workspace<'a, F> {
+    team_mu: &'a mut [F],
+    team_sigma_sq: &'a mut [F],
+    team_omega: &'a mut [F],
+    team_delta: &'a mut [F],
+    omega_terms: &'a mut [F],
+    delta_terms: &'a mut [F],
+}
+
+impl<'a, F> Workspace<'a, F> {
+    /// Creates a workspace for `n_teams` teams on top of the given arrays.
+    fn from_arrays<const N: usize>(
+        arrays: &'a mut [[F; N]; 6],
+        n_teams: usize,
+    ) -> Workspace<'a, F> {
+        let [team_mu, team_sigma_sq, team_omega, team_delta, omega_terms, delta_terms] = arrays;
+
+        Workspace {
+            team_mu: &mut team_mu[..n_teams],
+            team_sigma_sq: &mut team_sigma_sq[..n_teams],
+            team_omega: &mut team_omega[..n_teams],
+            team_delta: &mut team_delta[..n_teams],
+            omega_terms: &mut omega_terms[..n_teams],
+            delta_terms: &mut delta_terms[..n_teams],
+        }
+    }
+}
+
+/// Matches with at least this many teams compute their pairwise comparisons in
+/// parallel when the `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+const PARALLEL_TEAMS: usize = 64;
+
+/// Matches with up to this many teams keep their working memory on the stack.
+#[cfg(feature = "alloc")]
+const INLINE_TEAMS: usize = 16;
+
+/// Calls `f` with a workspace for `n_teams` teams, which is kept on the stack
+/// for small matches and allocated on the heap for larger ones.
+#[cfg(feature = "alloc")]
+fn with_workspace<F: Float, R, G: FnOnce(&mut Workspace<F>) -> R>(n_teams: usize, f: G) -> R {
+    if n_teams <= INLINE_TEAMS {
+        let mut arrays = [[F::ZERO; INLINE_TEAMS]; 6];
+        f(&mut Workspace::from_arrays(&mut arrays, n_teams))
+    } else {
+        let mut scratch = UpdateScratch::with_capacity(n_teams);
+        f(&mut scratch.workspace(n_teams))
+    }
+}
+
+/// Computes the omega and delta terms of a team against one opponent (Step
+/// 2), given the variance of the team and its square root, the `c` of the
+/// pair, the probabilities `piq` and `pqi` that either team beats the other,
+/// and the score `s` of the team.
+fn pair_terms<F: Float>(sigma_sq: F, sigma: F, c: F, piq: F, pqi: F, s: F) -> (F, F) {
+    let delta = (sigma_sq / c) * (s - piq);
+    let gamma = sigma / c;
+    let eta = gamma * (sigma_sq / (c * c)) * piq * pqi;
+
+    (delta, eta)
+}
+
+/// Computes the skill and variance of every team.
+fn compute_team_totals<F: Float, T: AsRef<[Rating<F>]>>(teams: &[T], work: &mut Workspace<F>) {
+    for (team_idx, team) in teams.iter().enumerate() {
+        let mut mu = CompensatedSum::default();
+        let mut sigma_sq = CompensatedSum::default();
+
+        for player in team.as_ref().iter() {
+            mu.add(player.mu);
+            sigma_sq.add(player.sigma_sq);
+        }
+
+        work.team_mu[team_idx] = mu.total();
+        work.team_sigma_sq[team_idx] = sigma_sq.total();
+    }
+}
+
+/// Computes the updated rating of a player (Step 3 of Algorithm 1), and
+/// whether the variance reduction had to be clamped.
+fn updated_rating<F: Float>(
+    player: &Rating<F>,
+    team_sigma_sq: F,
+    omega: F,
+    delta: F,
+) -> (Rating<F>, bool) {
+    let share = player.sigma_sq / team_sigma_sq;
+    let new_mu = player.mu + share * omega;
+
+    let mut sigma_adj = F::ONE - share * delta;
+    let clamped = sigma_adj < F::from_f64(0.0001);
+
+    if clamped {
+        sigma_adj = F::from_f64(0.0001);
+    }
+
+    let new_sigma_sq = player.sigma_sq * sigma_adj;
+
+    let rating = Rating {
+        mu: new_mu,
+        sigma: new_sigma_sq.sqrt(),
+        sigma_sq: new_sigma_sq,
+    };
+
+    (rating, clamped)
+}
+
+/// Returns the rating with its variance grown by `inflation_sq`, for
+/// `Rater::with_volatility`. Fully certain ratings are returned unchanged.
+fn inflated<F: Float>(rating: &Rating<F>, inflation_sq: F) -> Rating<F> {
+    if rating.sigma_sq == F::ZERO {
+        return *rating;
+    }
+
+    let sigma_sq = rating.sigma_sq + inflation_sq;
+
+    Rating {
+        mu: rating.mu,
+        sigma: sigma_sq.sqrt(),
+        sigma_sq,
+    }
+}
+
+/// Writes the updated ratings back into `teams`, passing them through the
+/// policy if there is one, and calls `on_clamp` with the team and player
+/// index of every player whose variance had to be clamped.
+fn apply_team_updates<F: Float, T: AsMut<[Rating<F>]>, C: FnMut(usize, usize)>(
+    teams: &mut [T],
+    work: &Workspace<F>,
+    write_back: WriteBack<F>,
+    mut on_clamp: C,
+) {
+    ////////////////////////////////////////////////////////////////////////////
+    // Step 3 - Individual skill update ////////////////////////////////////////
+    ////////////////////////////////////////////////////////////////////////////
+
+    #[cfg(feature = "validate")]
+    let values = validate::TeamValues {
+        mu: &work.team_mu[..],
+        sigma_sq: &work.team_sigma_sq[..],
+        omega: &work.team_omega[..],
+        delta: &work.team_delta[..],
+    };
+    #[cfg(feature = "validate")]
+    validate::assert_teams(&values);
+
+    for (team_idx, team) in teams.iter_mut().enumerate() {
+        if work.team_sigma_sq[team_idx] == F::ZERO {
+            continue;
+        }
+
+        for (player_idx, player) in team.as_mut().iter_mut().enumerate() {
+            let (rating, clamped) = updated_rating(
+                player,
+                work.team_sigma_sq[team_idx],
+                work.team_omega[team_idx],
+                work.team_delta[team_idx],
+            );
+
+            if clamped {
+                on_clamp(team_idx, player_idx);
+            }
+
+            #[cfg(feature = "tracing")]
+            trace_update(team_idx, player_idx, &rating, clamped);
+
+            #[cfg(feature = "validate")]
+            validate::assert_update(team_idx, player_idx, player, &rating, &values);
+
+            *player = write_back.apply(player, rating);
+        }
+    }
+}
+
+/// Emits a warning if the update of a player had to be clamped to the sigma
+/// floor, or produced a rating that is not finite.
+#[cfg(feature = "tracing")]
+fn trace_update<F: Float>(team: usize, player: usize, rating: &Rating<F>, clamped: bool) {
+    if clamped {
+        tracing::warn!(
+            team,
+            player,
+            sigma = %rating.sigma,
+            "variance reduction clamped to the sigma floor"
+        );
+    }
+
+    if !rating.mu.is_finite() || !rating.sigma.is_finite() {
+        tracing::warn!(
+            team,
+            player,
+            mu = %rating.mu,
+            sigma = %rating.sigma,
+            "non-finite rating update"
+        );
+    }
+}
+
+/// Checks that every updated rating would have a finite mu and a finite
+/// sigma, before any of them are written back. The sigma has to be positive
+/// unless it already was zero, so that a variance that underflows does not
+/// silently make a player fully certain.
+#[cfg(feature = "strict-math")]
+fn check_team_updates<F: Float, T: AsRef<[Rating<F>]>>(
+    teams: &[T],
+    work: &Workspace<F>,
+) -> Result<(), BBTError> {
+    for (team_idx, team) in teams.iter().enumerate() {
+        if work.team_sigma_sq[team_idx] == F::ZERO {
+            continue;
+        }
+
+        for (player_idx, player) in team.as_ref().iter().enumerate() {
+            let (rating, _) = updated_rating(
+                player,
+                work.team_sigma_sq[team_idx],
+                work.team_omega[team_idx],
+                work.team_delta[team_idx],
+            );
+
+            let sigma_valid = if player.sigma == F::ZERO {
+                rating.sigma == F::ZERO
+            } else {
+                rating.sigma > F::ZERO
+            };
+
+            if !rating.mu.is_finite() || !rating.sigma.is_finite() || !sigma_valid {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    team = team_idx,
+                    player = player_idx,
+                    mu = %rating.mu,
+                    sigma = %rating.sigma,
+                    "invalid rating update rejected"
+                );
+
+                return Err(BBTError::NumericalError {
+                    team: team_idx,
+                    player: player_idx,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+thread_local! {
+    static VALIDATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    static COMPARISONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+impl<F: Float> Rater<F> {
+    /// Checks that `teams` and `ranks` describe a well-formed match.
+    #[cfg(feature = "alloc")]
+    fn validate<T: AsRef<[Rating<F>]>>(
+        &self,
+        teams: &[T],
+        ranks: &[usize],
+    ) -> Result<(), BBTError> {
+        self.validate_with_totals(teams, ranks, |_, _, _| ())
+    }
+
+    /// Checks that `teams` and `ranks` describe a well-formed match, and
+    /// stores the skill and variance of every team in the workspace (Step 1)
+    /// along the way, which saves a second pass over the players.
+    fn validate_into<T: AsRef<[Rating<F>]>>(
+        &self,
+        teams: &[T],
+        ranks: &[usize],
+        work: &mut Workspace<F>,
+    ) -> Result<(), BBTError> {
+        self.validate_with_totals(teams, ranks, |team_idx, mu, sigma_sq| {
+            work.team_mu[team_idx] = mu;
+            work.team_sigma_sq[team_idx] = sigma_sq;
+        })
+    }
+
+    /// Checks that `teams` and `ranks` describe a well-formed match, calling
+    /// `on_totals` with the index, skill and variance of every valid team.
+    fn validate_with_totals<T: AsRef<[Rating<F>]>, C: FnMut(usize, F, F)>(
+        &self,
+        teams: &[T],
+        ranks: &[usize],
+        on_totals: C,
+    ) -> Result<(), BBTError> {
+        let result = self.check_match(teams, ranks, on_totals);
+
+        #[cfg(feature = "tracing")]
+        {
+            if let Err(ref error) = result {
+                tracing::debug!(%error, teams = teams.len(), "rejected match");
+            }
+        }
+
+        result
+    }
+
+    /// Performs the checks of `validate_with_totals`.
+    fn check_match<T: AsRef<[Rating<F>]>, C: FnMut(usize, F, F)>(
+        &self,
+        teams: &[T],
+        ranks: &[usize],
+        mut on_totals: C,
+    ) -> Result<(), BBTError> {
+        #[cfg(test)]
+        VALIDATIONS.with(|count| count.set(count.get() + 1));
+
+        if teams.len() != ranks.len() {
+            return Err(BBTError::MismatchedLengths {
+                teams: teams.len(),
+                ranks: ranks.len(),
+            });
+        }
+
+        if teams.len() < 2 && !self.allow_fewer_than_two_teams {
+            return Err(BBTError::NotEnoughTeams { found: teams.len() });
+        }
+
+        if self.strict_ranks && !is_competition_ranking(ranks) {
+            return Err(BBTError::InvalidRanks);
+        }
+
+        for (team_idx, team) in teams.iter().enumerate() {
+            let team = team.as_ref();
+
+            if team.is_empty() {
+                return Err(BBTError::EmptyTeam { index: team_idx });
+            }
+
+            let mut team_mu = CompensatedSum::default();
+            let mut team_sigma_sq = CompensatedSum::default();
+
+            for (player_idx, player) in team.iter().enumerate() {
+                if !player.is_valid() {
+                    return Err(BBTError::InvalidRating {
+                        team: team_idx,
+                        player: player_idx,
+                    });
+                }
+
+                team_mu.add(player.mu);
+                team_sigma_sq.add(player.sigma_sq);
+            }
+
+            // The running sums are the plain sums of the team's values.
+            if !team_mu.sum.is_finite() || !team_sigma_sq.sum.is_finite() {
+                return Err(BBTError::TeamOverflow { index: team_idx });
+            }
+
+            on_totals(team_idx, team_mu.total(), team_sigma_sq.total());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether `ranks` is a standard competition ranking, i.e. whether
+/// the sorted ranks are of the form 1, 2, 2, 4, ...
+fn is_competition_ranking(ranks: &[usize]) -> bool {
+    // In a competition ranking, each rank is one more than the number of
+    // teams that placed strictly better.
+    ranks
+        .iter()
+        .all(|&rank| rank == ranks.iter().filter(|&&other| other < rank).count() + 1)
+}
+
+/// CompensatedSum accumulates floating-point values using Neumaier's variant
+/// of Kahan summation, which keeps the rounding error of large sums bounded
+/// independently of the number of terms.
+#[derive(Clone, Copy, Default)]
+struct CompensatedSum<F> {
+    sum: F,
+    compensation: F,
+}
+
+impl<F: Float> CompensatedSum<F> {
+    fn add(&mut self, value: F) {
+        let t = self.sum + value;
+
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - t) + value;
+        } else {
+            self.compensation += (value - t) + self.sum;
+        }
+
+        self.sum = t;
+    }
+
+    fn total(&self) -> F {
+        self.sum + self.compensation
+    }
+}
+
+/// Returns the compensated sum of the single value `x`. This is `x` itself,
+/// except that a negative zero becomes a positive zero.
+fn sum_of_one<F: Float>(x: F) -> F {
+    let mut sum = CompensatedSum::default();
+    sum.add(x);
+    sum.total()
+}
+
+/// Sums the given terms in ascending order, which makes the result independent
+/// of the order in which the terms were collected.
+fn canonical_sum<F: Float>(terms: &mut [F]) -> F {
+    terms.sort_unstable_by(|a, b| a.total_cmp(b));
+
+    let mut sum = CompensatedSum::default();
+    for term in terms.iter() {
+        sum.add(*term);
+    }
+
+    sum.total()
+}
+
+/// Computes `exp(mu_i / c) / (exp(mu_i / c) + exp(mu_q / c))` in terms of the
+/// difference `mu_i - mu_q`, which cannot overflow for large ratings.
+fn logistic<F: Float>(diff: F, c: F) -> F {
+    F::ONE / (F::ONE + (-diff / c).exp())
+}
+
+/// GameMut borrows the teams and ranks of a single game for
+/// `Rater::update_batch`. The teams are updated in place.
+#[cfg(feature = "alloc")]
+pub struct GameMut<'a, F: Float = f64> {
+    pub teams: &'a mut [Vec<Rating<F>>],
+    pub ranks: &'a [usize],
+}
+
+/// BatchError describes why `Rater::update_batch` failed.
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BatchError {
+    /// The index of the game that failed.
+    pub game: usize,
+
+    /// The reason the game failed.
+    pub error: BBTError,
+}
+
+#[cfg(feature = "alloc")]
+impl std::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Game {}: {}", self.game, self.error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Error for BatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// UpdateScratch holds the working memory of a rating update, so that it can
+/// be reused across calls to `Rater::update_ratings_in`. It grows as needed
+/// and never shrinks.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone)]
+pub struct UpdateScratch<F: Float = f64> {
+    team_mu: Vec<F>,
+    team_sigma_sq: Vec<F>,
+    team_omega: Vec<F>,
+    team_delta: Vec<F>,
+    omega_terms: Vec<F>,
+    delta_terms: Vec<F>,
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Float> UpdateScratch<F> {
+    /// Creates an empty scratch space.
+    pub fn new() -> UpdateScratch<F> {
+        UpdateScratch::default()
+    }
+
+    /// Creates a scratch space large enough for matches of `n_teams` teams.
+    pub fn with_capacity(n_teams: usize) -> UpdateScratch<F> {
+        UpdateScratch {
+            team_mu: Vec::with_capacity(n_teams),
+            team_sigma_sq: Vec::with_capacity(n_teams),
+            team_omega: Vec::with_capacity(n_teams),
+            team_delta: Vec::with_capacity(n_teams),
+            omega_terms: Vec::with_capacity(n_teams),
+            delta_terms: Vec::with_capacity(n_teams),
+        }
+    }
+
+    /// Returns a workspace for a match of `n_teams` teams, growing the scratch
+    /// space if necessary.
+    fn workspace(&mut self, n_teams: usize) -> Workspace<'_, F> {
+        for v in [
+            &mut self.team_mu,
+            &mut self.team_sigma_sq,
+            &mut self.team_omega,
+            &mut self.team_delta,
+            &mut self.omega_terms,
+            &mut self.delta_terms,
+        ] {
+            v.clear();
+            v.resize(n_teams, F::ZERO);
+        }
+
+        Workspace {
+            team_mu: &mut self.team_mu,
+            team_sigma_sq: &mut self.team_sigma_sq,
+            team_omega: &mut self.team_omega,
+            team_delta: &mut self.team_delta,
+            omega_terms: &mut self.omega_terms,
+            delta_terms: &mut self.delta_terms,
+        }
+    }
+}
+
+/// UpdateReport describes details of a rating update that are not visible in
+/// the updated ratings themselves.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UpdateReport {
+    /// The number of players whose variance would have shrunk by more than
+    /// the algorithm allows, and was clamped to the minimum factor instead.
+    /// The ratings of these players are not the exact output of Algorithm 1.
+    pub clamps: u32,
+
+    /// The `(team, player)` indices of the clamped players.
+    pub clamped_players: Vec<(usize, usize)>,
+}
+
+/// UpdateDetails holds the intermediate values of a rating update, as returned
+/// by `Rater::update_ratings_detailed`. The values are indexed like the teams
+/// and players of the match.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateDetails<F: Float = f64> {
+    /// The omega of every team (Step 2 of Algorithm 1): the sum over all
+    /// opponents of how much better or worse the team did than expected,
+    /// which is distributed to the players' mus by their share of the
+    /// team's variance.
+    pub team_omega: Vec<F>,
+
+    /// The delta of every team (Step 2 of Algorithm 1), which is
+    /// distributed to the players' variances like omega to their mus.
+    pub team_delta: Vec<F>,
+
+    /// The update of every player.
+    pub players: Vec<Vec<PlayerUpdate<F>>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Float> UpdateDetails<F> {
+    /// Returns the updated ratings, in the shape that `update_ratings`
+    /// returns them.
+    pub fn into_ratings(self) -> Vec<Vec<Rating<F>>> {
+        self.players
+            .into_iter()
+            .map(|team| team.into_iter().map(|player| player.new).collect())
+            .collect()
+    }
+}
+
+/// PairContribution is the part of a team's update that stems from a single
+/// opponent, as returned by `Rater::explain`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairContribution<F: Float = f64> {
+    /// The index of the opponent.
+    pub opponent: usize,
+
+    /// The probability that the team would beat the opponent, as estimated
+    /// by the model before the match.
+    pub expected: F,
+
+    /// The actual score against the opponent: one if the team placed better,
+    /// one half for a tie and zero if it placed worse.
+    pub actual: F,
+
+    /// The contribution to the team's omega, which is positive if the team
+    /// did better against the opponent than expected.
+    pub omega: F,
+
+    /// The contribution to the team's delta, which shrinks the variances.
+    pub delta: F,
+}
+
+/// PairObservation is the model's expectation for one ordered pair of teams
+/// in a match, as passed to the observer of `Rater::update_ratings_observed`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairObservation<F: Float = f64> {
+    /// The index of the team i whose update is being computed.
+    pub team: usize,
+
+    /// The index of the opponent q.
+    pub opponent: usize,
+
+    /// The rank of the team.
+    pub rank: usize,
+
+    /// The rank of the opponent.
+    pub opponent_rank: usize,
+
+    /// The probability p_iq that the team would beat the opponent, as
+    /// estimated by the model before the match.
+    pub piq: F,
+
+    /// The actual score s against the opponent: one if the team placed
+    /// better, one half for a tie and zero if it placed worse.
+    pub s: F,
+}
+
+/// PlayerUpdate describes how the rating of a single player changed in an
+/// update.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerUpdate<F: Float = f64> {
+    /// The rating before the update.
+    pub old: Rating<F>,
+
+    /// The rating after the update.
+    pub new: Rating<F>,
+
+    /// The change of mu, `new.mu() - old.mu()`.
+    pub mu_delta: F,
+
+    /// The change of sigma, `new.sigma() - old.sigma()`.
+    pub sigma_delta: F,
+
+    /// Whether the variance reduction had to be clamped, as counted by
+    /// `UpdateReport`.
+    pub clamped: bool,
+}
+
+/// RatingUpdate summarizes a duel rated by `Rater::duel_detailed`. Every
+/// array holds the values of the first player, then those of the second.
+///
+/// With the `serde` feature, updates are serialized as a struct with the
+/// fields `before`, `after`, `mu_delta` and `win_probability`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingUpdate<F: Float = f64> {
+    /// The ratings before the duel.
+    pub before: [Rating<F>; 2],
+
+    /// The ratings after the duel.
+    pub after: [Rating<F>; 2],
+
+    /// The changes of mu, `after[i].mu() - before[i].mu()`.
+    pub mu_delta: [F; 2],
+
+    /// The probability that the first player would win, as estimated by the
+    /// model before the duel.
+    pub win_probability: F,
+}
+
+/// Outcome represents the outcome of a head-to-head duel between two players.
+///
+/// With the `serde` feature, outcomes are serialized as `"Win"`, `"Loss"` and
+/// `"Draw"`. The `bbt::serde_outcome` module has other representations.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Debug, PartialEq)
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes),
+    archive_attr(
+        doc = "The archived form of an `Outcome`, created by `rkyv`.",
+        derive(Debug, PartialEq, Eq)
+    )
+)]
+pub enum Outcome {
+    /// The first player won the game
+    Win,
+
+    /// The first player lost the game
+    Loss,
+
+    /// Neither player won
+    Draw,
+}
+
+/// Rating represents the skill of a player.
+#[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes),
+    archive_attr(doc = "The archived form of a `Rating`, created by `rkyv`.")
+)]
+pub struct Rating<F: Float = f64> {
+    mu: F,
+    sigma: F,
+    sigma_sq: F,
+}
+
+impl<F: Float> Default for Rating<F> {
+    /// Instantiates a Rating with the default values of mu=25.0 and sigma=25.0/3.0
+    fn default() -> Rating<F> {
+        Rating::new(F::from_f64(25.0), F::from_f64(25.0 / 3.0))
+    }
+}
+
+impl<F: Float> PartialOrd for Rating<F> {
+    fn partial_cmp(&self, other: &Rating<F>) -> Option<std::cmp::Ordering> {
+        self.conservative_estimate()
+            .partial_cmp(&other.conservative_estimate())
+    }
+}
+
+impl<F: Float> std::fmt::Display for Rating<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let cons_est = self.conservative_estimate();
+        if cons_est < F::ZERO {
+            write!(f, "0.0")
+        } else {
+            write!(f, "{}", cons_est)
+        }
+    }
+}
+
+impl<F: Float> std::fmt::Debug for Rating<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}±{}", self.mu, F::from_f64(3.0) * self.sigma)
+    }
+}
+
+impl<F: Float> Rating<F> {
+    pub fn new(mu: F, sigma: F) -> Rating<F> {
+        Rating {
+            mu,
+            sigma,
+            sigma_sq: sigma.powf(F::from_f64(2.0)),
+        }
+    }
+
+    /// Like `Rating::new`, but validates the parameters first, for ratings
+    /// that come from untrusted sources.
+    ///
+    /// Returns `BBTError::InvalidParameter` if mu is not finite, or if sigma
+    /// is not positive and finite.
+    pub fn try_new(mu: F, sigma: F) -> Result<Rating<F>, BBTError> {
+        if !mu.is_finite() {
+            return Err(BBTError::InvalidParameter { name: "mu" });
+        }
+
+        if !sigma.is_finite() || sigma <= F::ZERO {
+            return Err(BBTError::InvalidParameter { name: "sigma" });
+        }
+
+        Ok(Rating::new(mu, sigma))
+    }
+
+    /// Returns the rating for a new season: mu moves `compression` of the
+    /// way towards `target_mu`, and sigma is raised to `sigma_restore` if it
+    /// is lower, so that the rating can adapt quickly again. Sigma never
+    /// decreases.
+    ///
+    /// Returns `BBTError::InvalidParameter` if `target_mu` is not finite, if
+    /// `compression` is not between zero and one, or if `sigma_restore` is
+    /// negative or not finite. `bbt::season` resets many ratings at once.
+    pub fn season_reset(
+        &self,
+        target_mu: F,
+        compression: F,
+        sigma_restore: F,
+    ) -> Result<Rating<F>, BBTError> {
+        check_season_reset(target_mu, compression, sigma_restore)?;
+
+        let restore_sq = sigma_restore * sigma_restore;
+        let (sigma, sigma_sq) = if self.sigma_sq < restore_sq {
+            (sigma_restore, restore_sq)
+        } else {
+            (self.sigma, self.sigma_sq)
+        };
+
+        Ok(Rating {
+            mu: self.mu + compression * (target_mu - self.mu),
+            sigma,
+            sigma_sq,
+        })
+    }
+
+    /// Returns whether the two ratings are statistically indistinguishable,
+    /// i.e. whether their skills differ by less than `z` standard deviations
+    /// of the difference: `|mu_a - mu_b| < z * sqrt(sigma_a² + sigma_b²)`.
+    ///
+    /// A `z` of two roughly corresponds to a 95% confidence that the players
+    /// differ. `bbt::sort::group_ties` groups the ties of a whole
+    /// leaderboard.
+    pub fn is_statistical_tie(&self, other: &Rating<F>, z: F) -> bool {
+        (self.mu - other.mu).abs() < z * (self.sigma_sq + other.sigma_sq).sqrt()
+    }
+
+    /// Returns whether the rating can take part in an update, i.e. whether
+    /// mu and sigma (and sigma²) are finite and sigma is not negative.
+    pub fn is_valid(&self) -> bool {
+        self.mu.is_finite() && self.sigma_sq.is_finite() && self.sigma >= F::ZERO
+    }
+
+    /// Returns the estimated skill of the player.
+    pub fn mu(&self) -> F {
+        self.mu
+    }
+
+    /// Returns the variance on the estimate of the player's skill.
+    pub fn sigma(&self) -> F {
+        self.sigma
+    }
+
+    /// Returns the conservative skill estimate `mu - 3 * sigma`, which the
+    /// player's true skill exceeds with a probability of about 99.9%.
+    pub fn conservative_estimate(&self) -> F {
+        self.mu - F::from_f64(3.0) * self.sigma
+    }
+}
+
+/// Validates the parameters of `Rating::season_reset`.
+fn check_season_reset<F: Float>(
+    target_mu: F,
+    compression: F,
+    sigma_restore: F,
+) -> Result<(), BBTError> {
+    if !target_mu.is_finite() {
+        return Err(BBTError::InvalidParameter { name: "target_mu" });
+    }
+
+    if !(compression >= F::ZERO && compression <= F::ONE) {
+        return Err(BBTError::InvalidParameter {
+            name: "compression",
+        });
+    }
+
+    if !sigma_restore.is_finite() || sigma_restore < F::ZERO {
+        return Err(BBTError::InvalidParameter {
+            name: "sigma_restore",
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_instantiate_ratings() {
+        let default_rating = Rating::default();
+        let new_rating = Rating::new(25.0, 25.0 / 3.0);
+
+        assert_eq!(default_rating, new_rating)
+    }
+
+    #[test]
+    fn two_player_duel_win_loss() {
+        let p1 = ::Rating::default();
+        let p2 = ::Rating::default();
+
+        let rater = ::Rater::default();
+        let new_rs = rater
+            .update_ratings(vec![vec![p1], vec![p2]], vec![0, 1])
+            .unwrap();
+
+        assert!((new_rs[0][0].mu - 27.63523138).abs() < 1.0 / 100000000.0);
+        assert!((new_rs[0][0].sigma - 8.0655063).abs() < 1.0 / 1000000.0);
+        assert!((new_rs[1][0].mu - 22.36476861).abs() < 1.0 / 100000000.0);
+        assert!((new_rs[1][0].sigma - 8.0655063).abs() < 1.0 / 1000000.0);
+    }
+
+    #[test]
+    fn two_player_duel_tie() {
+        let p1 = Rating::default();
+        let p2 = Rating::default();
+
+        let rater = Rater::default();
+        let (new_p1, new_p2) = rater.duel(p1, p2, Outcome::Draw);
+
+        assert_eq!(new_p1.mu, 25.0);
+        assert_eq!(new_p2.mu, 25.0);
+        assert!((new_p1.sigma - 8.0655063).abs() < 1.0 / 1000000.0);
+        assert!((new_p2.sigma - 8.0655063).abs() < 1.0 / 1000000.0);
+    }
+
+    #[test]
+    fn four_player_race() {
+        let p1 = Rating::default();
+        let p2 = Rating::default();
+        let p3 = Rating::default();
+        let p4 = Rating::default();
+
+        let rater = Rater::default();
+        let teams = vec![vec![p1], vec![p2], vec![p3], vec![p4]];
+        let ranks = vec![1, 2, 3, 4];
+
+        let new_ratings = rater.update_ratings(teams, ranks).unwrap();
+
+        assert!((new_ratings[0][0].mu - 32.9056941).abs() < 1.0 / 10000000.0);
+        assert!((new_ratings[1][0].mu - 27.6352313).abs() < 1.0 / 10000000.0);
+        assert!((new_ratings[2][0].mu - 22.3647686).abs() < 1.0 / 10000000.0);
+        assert!((new_ratings[3][0].mu - 17.0943058).abs() < 1.0 / 10000000.0);
+
+        assert!((new_ratings[0][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
+        assert!((new_ratings[1][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
+        assert!((new_ratings[2][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
+        assert!((new_ratings[3][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
+    }
+
+    #[test]
+    fn large_ratings_do_not_overflow() {
+        let rater = Rater::new(1.0);
+        let teams = vec![
+            vec![Rating::new(3000.0, 1.0)],
+            vec![Rating::new(2990.0, 1.0)],
+            vec![Rating::new(3010.0, 1.0)],
+        ];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2, 3]).unwrap();
+
+        for team in new_ratings.iter() {
+            assert!(team[0].mu.is_finite());
+            assert!(team[0].sigma.is_finite());
+        }
+
+        // The winner gains, the last-placed player loses.
+        assert!(new_ratings[0][0].mu > 3000.0);
+        assert!(new_ratings[2][0].mu < 3010.0);
+        assert!(new_ratings[0][0].mu < 3010.0);
+    }
+
+    #[test]
+    fn stable_logistic_matches_exponential_form() {
+        let c = (2.0 * (25.0f64 / 3.0).powi(2) + 2.0 * (25.0f64 / 6.0).powi(2)).sqrt();
+
+        for mu_i in (0..=50).map(f64::from) {
+            for mu_q in (0..=50).map(f64::from) {
+                let e1 = (mu_i / c).exp();
+                let e2 = (mu_q / c).exp();
+
+                assert!((logistic(mu_i - mu_q, c) - e1 / (e1 + e2)).abs() < 1e-15);
+                assert!((logistic(mu_q - mu_i, c) - e2 / (e1 + e2)).abs() < 1e-15);
+            }
+        }
+    }
+
+    fn assert_all_finite(ratings: &[Vec<Rating>]) {
+        for team in ratings.iter() {
+            for player in team.iter() {
+                assert!(player.mu.is_finite(), "mu is not finite: {:?}", player);
+                assert!(
+                    player.sigma.is_finite(),
+                    "sigma is not finite: {:?}",
+                    player
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zero_variance_solo_team() {
+        let rater = Rater::default();
+        let anchor = Rating::new(25.0, 0.0);
+        let teams = vec![vec![anchor], vec![Rating::default()]];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2]).unwrap();
+
+        assert_all_finite(&new_ratings);
+        assert_eq!(new_ratings[0][0], anchor);
+        assert!(new_ratings[1][0].mu < 25.0);
+        assert!(new_ratings[1][0].sigma < 25.0 / 3.0);
+    }
+
+    #[test]
+    fn zero_variance_multi_player_team() {
+        let rater = Rater::default();
+        let anchors = vec![Rating::new(20.0, 0.0), Rating::new(30.0, 0.0)];
+        let teams = vec![anchors.clone(), vec![Rating::default(), Rating::default()]];
+
+        let new_ratings = rater.update_ratings(teams, vec![2, 1]).unwrap();
+
+        assert_all_finite(&new_ratings);
+        assert_eq!(new_ratings[0], anchors);
+        assert!(new_ratings[1][0].mu > 25.0);
+    }
+
+    #[test]
+    fn mixed_team_with_zero_variance_member() {
+        let rater = Rater::default();
+        let anchor = Rating::new(25.0, 0.0);
+        let teams = vec![
+            vec![anchor, Rating::default()],
+            vec![Rating::default(), Rating::default()],
+        ];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2]).unwrap();
+
+        assert_all_finite(&new_ratings);
+        assert_eq!(new_ratings[0][0], anchor);
+        assert!(new_ratings[0][1].mu > 25.0);
+    }
+
+    #[test]
+    fn unchecked_update_matches_checked_update() {
+        let rater = Rater::default();
+        let teams = vec![
+            vec![Rating::new(30.0, 5.0), Rating::default()],
+            vec![Rating::new(20.0, 7.0)],
+            vec![Rating::default(), Rating::new(27.0, 2.0)],
+        ];
+        let ranks = vec![2, 1, 2];
+
+        let before = VALIDATIONS.with(|count| count.get());
+        let checked = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+        let after_checked = VALIDATIONS.with(|count| count.get());
+        let unchecked = rater.update_ratings_unchecked(teams, ranks);
+        let after_unchecked = VALIDATIONS.with(|count| count.get());
+
+        assert_eq!(checked, unchecked);
+        assert_eq!(after_checked, before + 1);
+        assert_eq!(after_unchecked, after_checked);
+    }
+
+    #[test]
+    fn fewer_than_two_teams_is_an_error() {
+        let rater = Rater::default();
+
+        let err = rater.update_ratings(vec![], vec![]).unwrap_err();
+        assert_eq!(err, BBTError::NotEnoughTeams { found: 0 });
+
+        let err = rater
+            .update_ratings(vec![vec![Rating::default()]], vec![1])
+            .unwrap_err();
+        assert_eq!(err, BBTError::NotEnoughTeams { found: 1 });
+        assert_eq!(
+            err.to_string(),
+            "At least two teams are required, but 1 were given"
+        );
+
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+        assert!(rater.update_ratings(teams, vec![1, 2]).is_ok());
+    }
+
+    #[test]
+    fn fewer_than_two_teams_can_be_allowed() {
+        let rater = Rater::default().allow_fewer_than_two_teams();
+
+        assert_eq!(
+            rater.update_ratings(vec![], vec![]).unwrap(),
+            Vec::<Vec<Rating>>::new()
+        );
+
+        let team = vec![Rating::new(30.0, 4.0), Rating::default()];
+        let new_ratings = rater.update_ratings(vec![team.clone()], vec![1]).unwrap();
+        assert_eq!(new_ratings, vec![team]);
+    }
+
+    #[cfg(feature = "strict-math")]
+    #[test]
+    fn strict_math_rejects_non_finite_results() {
+        let rater = Rater::new(f64::NAN);
+        let teams = vec![
+            vec![Rating::default()],
+            vec![Rating::default(), Rating::default()],
+        ];
+        let original = teams.clone();
+
+        let err = rater.update_ratings(teams.clone(), vec![1, 2]).unwrap_err();
+
+        assert_eq!(err, BBTError::NumericalError { team: 0, player: 0 });
+        assert_eq!(teams, original);
+    }
+
+    /// A math backend whose square root of 9 is NaN, which breaks the update
+    /// of a team with a variance of 9 and no other.
+    #[cfg(feature = "strict-math")]
+    struct PoisonedSqrt;
+
+    #[cfg(feature = "strict-math")]
+    impl MathBackend<f64> for PoisonedSqrt {
+        fn exp(&self, x: f64) -> f64 {
+            x.exp()
+        }
+
+        fn sqrt(&self, x: f64) -> f64 {
+            if x == 9.0 {
+                f64::NAN
+            } else {
+                x.sqrt()
+            }
+        }
+    }
+
+    #[cfg(feature = "strict-math")]
+    #[test]
+    fn strict_math_writes_nothing_if_a_later_team_fails() {
+        static POISONED: PoisonedSqrt = PoisonedSqrt;
+        let rater = Rater::default().with_math_backend(&POISONED);
+
+        let mut first = [Rating::new(25.0, 2.0), Rating::new(30.0, 0.5)];
+        let mut second = [Rating::new(20.0, 3.0)];
+        let original = (first, second);
+
+        // The first team alone would update fine.
+        let mut copy = first;
+        Rater::default()
+            .update_ratings_const(
+                &mut [&mut copy[..], &mut [Rating::new(20.0, 2.0)][..]],
+                [1, 2],
+            )
+            .unwrap();
+        assert_ne!(copy, first);
+
+        let err = rater
+            .update_ratings_const(&mut [&mut first[..], &mut second[..]], [1, 2])
+            .unwrap_err();
+
+        assert_eq!(err, BBTError::NumericalError { team: 1, player: 0 });
+        assert_eq!((first, second), original);
+    }
+
+    #[cfg(feature = "strict-math")]
+    #[test]
+    fn strict_math_rejects_sigma_underflowing_to_zero() {
+        // The smallest positive variance, which the clamped variance
+        // reduction rounds to zero.
+        let tiny = Rating::new(25.0, f64::from_bits(1).sqrt());
+        assert!(tiny.sigma() > 0.0);
+
+        let mut arrays = [[0.0; 1]; 6];
+        let work = Workspace::from_arrays(&mut arrays, 1);
+        work.team_mu[0] = tiny.mu;
+        work.team_sigma_sq[0] = tiny.sigma_sq;
+        work.team_omega[0] = 0.0;
+        work.team_delta[0] = 2.0;
+
+        assert_eq!(
+            check_team_updates(&[[tiny]], &work),
+            Err(BBTError::NumericalError { team: 0, player: 0 })
+        );
+
+        // A fully certain player stays certain.
+        let certain = Rating::new(25.0, 0.0);
+        work.team_sigma_sq[0] = 1.0;
+        assert_eq!(check_team_updates(&[[certain]], &work), Ok(()));
+    }
+
+    #[test]
+    fn compensated_sum_matches_exact_reference() {
+        // Every value is an exact multiple of 2^-21, so the sum can be
+        // computed exactly in 128-bit integer arithmetic and rounded once.
+        let scale = f64::powi(2.0, 21);
+        let exponents = [-20, 0, 20, 30];
+
+        let values: Vec<f64> = (0..10_000)
+            .map(|i| {
+                let sign = if i % 3 == 0 { -1.0 } else { 1.0 };
+                let mantissa = ((i * 7919) % 1000) as f64 + 0.5;
+                sign * mantissa * f64::powi(2.0, exponents[i % exponents.len()])
+            })
+            .collect();
+
+        let exact: i128 = values.iter().map(|v| (v * scale) as i128).sum();
+        let reference = exact as f64 / scale;
+
+        let mut sum = CompensatedSum::default();
+        for v in values.iter() {
+            sum.add(*v);
+        }
+
+        assert_eq!(sum.total(), reference);
+    }
+
+    #[test]
+    fn large_team_update_matches_exact_reference() {
+        // A team of 10,000 players of very different magnitudes. Every mu is
+        // a multiple of 2^-21 and every variance a multiple of 2^-22, so the
+        // team totals can be computed exactly in 128-bit integer arithmetic
+        // and rounded once.
+        let (mu_scale, sigma_sq_scale) = (f64::powi(2.0, 21), f64::powi(2.0, 22));
+        let exponents = [-20, 0, 20, 30];
+
+        let team: Vec<Rating> = (0..10_000)
+            .map(|i| {
+                let sign = if i % 3 == 0 { -1.0 } else { 1.0 };
+                let mantissa = ((i * 7919) % 1000) as f64 + 0.5;
+                let mu = sign * mantissa * f64::powi(2.0, exponents[i % exponents.len()]);
+                let steps = ((i * 104_729) % 4096 + 1) as f64;
+                let sigma = steps * f64::powi(2.0, if i % 5 == 0 { -1 } else { -11 });
+
+                Rating::new(mu, sigma)
+            })
+            .collect();
+
+        let exact_mu: i128 = team.iter().map(|p| (p.mu * mu_scale) as i128).sum();
+        let exact_sigma_sq: i128 = team
+            .iter()
+            .map(|p| (p.sigma_sq * sigma_sq_scale) as i128)
+            .sum();
+        let reference_mu = exact_mu as f64 / mu_scale;
+        let reference_sigma_sq = exact_sigma_sq as f64 / sigma_sq_scale;
+
+        let rater = Rater::default();
+        let teams = vec![team, vec![Rating::new(1000.0, 4.0)]];
+
+        let mut arrays = [[0.0; 2]; 6];
+        let mut work = Workspace::from_arrays(&mut arrays, 2);
+        compute_team_totals(&teams, &mut work);
+        assert_eq!(work.team_mu[0], reference_mu);
+        assert_eq!(work.team_sigma_sq[0], reference_sigma_sq);
+
+        // The update computed from the exact team totals.
+        work.team_mu[0] = reference_mu;
+        work.team_sigma_sq[0] = reference_sigma_sq;
+        rater.compute_all_omega_delta(&[1, 2], &mut work);
+
+        let mut expected = teams.clone();
+        apply_team_updates(&mut expected, &work, rater.write_back(), |_, _| ());
+
+        let actual = rater.update_ratings(teams, vec![1, 2]).unwrap();
+
+        for (actual, expected) in actual.iter().flatten().zip(expected.iter().flatten()) {
+            assert_eq!(actual.mu.to_bits(), expected.mu.to_bits());
+            assert_eq!(actual.sigma.to_bits(), expected.sigma.to_bits());
+        }
+    }
+
+    #[test]
+    fn zero_beta_with_certain_teams() {
+        let rater = Rater::new(0.0);
+        let teams = vec![
+            vec![Rating::new(25.0, 0.0)],
+            vec![Rating::new(25.0, 0.0)],
+            vec![Rating::default()],
+        ];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2, 3]).unwrap();
+
+        assert_all_finite(&new_ratings);
+    }
+
+    #[test]
+    fn huge_beta_leaves_ratings_finite() {
+        let rater = Rater::new(1e300);
+        let teams = vec![vec![Rating::default()], vec![Rating::new(1e300, 1e150)]];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2]).unwrap();
+
+        assert_all_finite(&new_ratings);
+    }
+
+    #[test]
+    fn invalid_ratings_are_rejected() {
+        let rater = Rater::default();
+
+        let teams = vec![vec![Rating::default()], vec![Rating::new(25.0, 1e200)]];
+        let err = rater.update_ratings(teams, vec![1, 2]).unwrap_err();
+        assert_eq!(err, BBTError::InvalidRating { team: 1, player: 0 });
+
+        let teams = vec![
+            vec![Rating::default(), Rating::new(25.0, -1.0)],
+            vec![Rating::default()],
+        ];
+        let err = rater.update_ratings(teams, vec![1, 2]).unwrap_err();
+        assert_eq!(err, BBTError::InvalidRating { team: 0, player: 1 });
+    }
+
+    #[test]
+    fn team_overflow_is_rejected() {
+        let rater = Rater::default();
+        let teams = vec![
+            vec![Rating::default()],
+            vec![Rating::new(1e308, 1.0), Rating::new(1e308, 1.0)],
+        ];
+
+        let err = rater.update_ratings(teams, vec![1, 2]).unwrap_err();
+
+        assert_eq!(err, BBTError::TeamOverflow { index: 1 });
+    }
+
+    #[test]
+    fn duel_with_invalid_rating_is_a_no_op() {
+        let rater = Rater::default();
+        let invalid = Rating::new(25.0, 1e200);
+
+        let (p1, p2) = rater.duel(Rating::default(), invalid, Outcome::Win);
+
+        assert_eq!(p1, Rating::default());
+        assert_eq!(p2, invalid);
+    }
+
+    #[test]
+    fn update_is_permutation_invariant() {
+        let rater = Rater::default();
+        let teams = vec![
+            vec![Rating::new(31.2, 4.1), Rating::new(18.7, 6.3)],
+            vec![Rating::new(27.9, 2.2)],
+            vec![Rating::new(22.4, 7.7), Rating::new(25.0, 1.9)],
+            vec![Rating::new(35.1, 3.3)],
+            vec![Rating::new(12.6, 8.0), Rating::new(29.3, 5.5)],
+            vec![Rating::new(24.8, 0.9)],
+        ];
+        let ranks = vec![3, 1, 2, 6, 3, 5];
+
+        let expected = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+
+        let permutations = [
+            [5, 4, 3, 2, 1, 0],
+            [1, 3, 5, 0, 2, 4],
+            [2, 0, 1, 5, 3, 4],
+            [4, 5, 0, 1, 3, 2],
+        ];
+
+        for permutation in permutations.iter() {
+            let permuted_teams = permutation.iter().map(|&i| teams[i].clone()).collect();
+            let permuted_ranks = permutation.iter().map(|&i| ranks[i]).collect();
+
+            let result = rater
+                .update_ratings(permuted_teams, permuted_ranks)
+                .unwrap();
+
+            for (pos, &i) in permutation.iter().enumerate() {
+                for (actual, expected) in result[pos].iter().zip(expected[i].iter()) {
+                    assert_eq!(actual.mu.to_bits(), expected.mu.to_bits());
+                    assert_eq!(actual.sigma.to_bits(), expected.sigma.to_bits());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn strict_ranks_require_competition_ranking() {
+        let strict = Rater::default().strict_ranks();
+        let lenient = Rater::default();
+        let teams = || vec![vec![Rating::default()]; 4];
+
+        assert!(strict.update_ratings(teams(), vec![1, 2, 2, 4]).is_ok());
+        assert!(strict.update_ratings(teams(), vec![4, 2, 1, 2]).is_ok());
+        assert!(strict.update_ratings(teams(), vec![1, 1, 1, 1]).is_ok());
+
+        for ranks in [vec![1, 2, 2, 3], vec![10, 20, 30, 40], vec![0, 1, 2, 3]] {
+            assert_eq!(
+                strict.update_ratings(teams(), ranks.clone()).unwrap_err(),
+                BBTError::InvalidRanks
+            );
+            assert!(lenient.update_ratings(teams(), ranks).is_ok());
+        }
+    }
+
+    #[test]
+    fn report_counts_sigma_clamps() {
+        // A very uncertain player facing many near-certain opponents would
+        // lose more than all of their variance without the clamp.
+        let rater = Rater::new(0.01);
+        let mut teams = vec![vec![Rating::new(25.0, 100.0)]];
+        teams.extend(vec![vec![Rating::new(25.0, 0.01)]; 5]);
+
+        let (new_ratings, report) = rater
+            .update_ratings_with_report(teams.clone(), vec![1, 2, 3, 4, 5, 6])
+            .unwrap();
+
+        assert_eq!(report.clamps, 1);
+        assert_eq!(report.clamped_players, vec![(0, 0)]);
+        assert_eq!(
+            new_ratings,
+            rater.update_ratings(teams, vec![1, 2, 3, 4, 5, 6]).unwrap()
+        );
+    }
+
+    #[test]
+    fn report_for_normal_match_has_no_clamps() {
+        let rater = Rater::default();
+        let teams = vec![vec![Rating::default()]; 4];
+
+        let (_, report) = rater
+            .update_ratings_with_report(teams, vec![1, 2, 3, 4])
+            .unwrap();
+
+        assert_eq!(report, UpdateReport::default());
+    }
+
+    #[test]
+    fn detailed_update_explains_the_golden_duel() {
+        let rater = Rater::default();
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+
+        let details = rater
+            .update_ratings_detailed(teams.clone(), vec![0, 1])
+            .unwrap();
+
+        // Both players have the default variance and win with probability
+        // 1/2, so omega is ±(sigma²/c)·(1/2) and delta is
+        // (sigma/c)·(sigma²/c²)·(1/4) for both.
+        let sigma_sq = (25.0f64 / 3.0).powi(2);
+        let c = (2.0 * sigma_sq + 2.0 * (25.0f64 / 6.0).powi(2)).sqrt();
+        let omega = sigma_sq / c * 0.5;
+        let delta = (sigma_sq.sqrt() / c) * (sigma_sq / (c * c)) * 0.25;
+
+        assert!((details.team_omega[0] - omega).abs() < 1e-12);
+        assert!((details.team_omega[1] + omega).abs() < 1e-12);
+        for &team_delta in &details.team_delta {
+            assert!((team_delta - delta).abs() < 1e-12);
+        }
+
+        let winner = &details.players[0][0];
+        assert_eq!(winner.old, Rating::default());
+        assert!((winner.new.mu - 27.63523138).abs() < 1e-8);
+        assert!((winner.mu_delta - omega).abs() < 1e-12);
+        assert!((winner.new.sigma_sq - sigma_sq * (1.0 - delta)).abs() < 1e-12);
+        assert_eq!(winner.sigma_delta, winner.new.sigma - winner.old.sigma);
+        assert!(!winner.clamped);
+
+        // The ratings are the same as those of the plain update.
+        assert_eq!(
+            details.into_ratings(),
+            rater.update_ratings(teams, vec![0, 1]).unwrap()
+        );
+    }
+
+    #[test]
+    fn explanations_sum_to_the_detailed_update() {
+        let rater = Rater::default();
+        let teams = vec![
+            vec![Rating::new(30.0, 3.0), Rating::new(22.0, 6.0)],
+            vec![Rating::new(27.0, 8.0)],
+            vec![Rating::new(20.0, 2.0), Rating::new(21.0, 2.5)],
+            vec![Rating::new(26.0, 4.0)],
+        ];
+        let ranks = vec![2, 1, 2, 4];
+
+        let slices: Vec<&[Rating]> = teams.iter().map(|team| &team[..]).collect();
+        let explanations = rater.explain(&slices, &ranks).unwrap();
+        let details = rater.update_ratings_detailed(teams, ranks).unwrap();
+
+        for (team_idx, contributions) in explanations.iter().enumerate() {
+            let opponents: Vec<usize> = contributions.iter().map(|pair| pair.opponent).collect();
+            let expected: Vec<usize> = (0..4).filter(|&idx| idx != team_idx).collect();
+            assert_eq!(opponents, expected);
+
+            let omega: f64 = contributions.iter().map(|pair| pair.omega).sum();
+            let delta: f64 = contributions.iter().map(|pair| pair.delta).sum();
+            assert!((omega - details.team_omega[team_idx]).abs() < 1e-12);
+            assert!((delta - details.team_delta[team_idx]).abs() < 1e-12);
+        }
+
+        // The tied teams score one half against each other.
+        assert_eq!(explanations[0][1].actual, 0.5);
+        assert_eq!(explanations[1][0].actual, 1.0);
+        assert_eq!(
+            explanations[0][0].expected + explanations[1][0].expected,
+            1.0
+        );
+    }
+
+    #[test]
+    fn observed_update_reports_every_pair() {
+        let rater = Rater::default();
+        let race = vec![vec![Rating::default()]; 4];
+        let mixed = vec![
+            vec![Rating::new(30.0, 3.0), Rating::new(22.0, 6.0)],
+            vec![Rating::new(27.0, 8.0)],
+            vec![Rating::new(20.0, 2.0), Rating::new(21.0, 2.5)],
+            vec![Rating::new(26.0, 4.0)],
+        ];
+
+        for (teams, ranks) in [(race, vec![1, 2, 3, 4]), (mixed, vec![2, 1, 2, 4])] {
+            let mut observations = Vec::new();
+            let observed = rater
+                .update_ratings_observed(teams.clone(), ranks.clone(), &mut |pair| {
+                    observations.push(pair)
+                })
+                .unwrap();
+
+            assert_eq!(observations.len(), 4 * 3);
+
+            for pair in &observations {
+                assert!(pair.piq > 0.0 && pair.piq < 1.0, "{:?}", pair);
+                assert_eq!(pair.rank, ranks[pair.team]);
+                assert_eq!(pair.opponent_rank, ranks[pair.opponent]);
+
+                let mirrored = observations
+                    .iter()
+                    .find(|other| other.team == pair.opponent && other.opponent == pair.team)
+                    .unwrap();
+                assert!((pair.piq + mirrored.piq - 1.0).abs() < 1e-12);
+                assert_eq!(pair.s + mirrored.s, 1.0);
+            }
+
+            let plain = rater.update_ratings(teams, ranks).unwrap();
+
+            // The vectorized kernel is not bit-identical to the scalar path.
+            if cfg!(feature = "simd") {
+                for (observed, plain) in observed.iter().flatten().zip(plain.iter().flatten()) {
+                    assert!((observed.mu - plain.mu).abs() < 1e-12);
+                    assert!((observed.sigma - plain.sigma).abs() < 1e-12);
+                }
+            } else {
+                assert_eq!(observed, plain);
+            }
+        }
+    }
+
+    #[test]
+    fn explanations_show_where_a_favourite_lost_rating() {
+        // The favourite finishes third of eight, behind two weaker teams.
+        let rater = Rater::default();
+        let mut teams = vec![vec![Rating::new(35.0, 3.0)]];
+        teams.extend((0..7).map(|i| vec![Rating::new(28.0 - i as f64, 3.0)]));
+        let ranks = vec![3, 1, 2, 4, 5, 6, 7, 8];
+
+        let slices: Vec<&[Rating]> = teams.iter().map(|team| &team[..]).collect();
+        let favourite = &rater.explain(&slices, &ranks).unwrap()[0];
+
+        for pair in favourite {
+            if ranks[pair.opponent] < 3 {
+                assert_eq!(pair.actual, 0.0);
+                assert!(pair.omega < 0.0, "{:?}", pair);
+            } else {
+                assert_eq!(pair.actual, 1.0);
+                assert!(pair.omega > 0.0, "{:?}", pair);
+            }
+        }
+
+        // The two losses outweigh the five expected wins.
+        let omega: f64 = favourite.iter().map(|pair| pair.omega).sum();
+        assert!(omega < 0.0);
+
+        assert_eq!(
+            rater.explain(&[&teams[0]], &[1]),
+            Err(BBTError::NotEnoughTeams { found: 1 })
+        );
+    }
+
+    #[test]
+    fn detailed_update_flags_clamps_and_errors() {
+        let rater = Rater::new(0.01);
+        let mut teams = vec![vec![Rating::new(25.0, 100.0)]];
+        teams.extend(vec![vec![Rating::new(25.0, 0.01)]; 5]);
+
+        let details = rater
+            .update_ratings_detailed(teams, vec![1, 2, 3, 4, 5, 6])
+            .unwrap();
+        let clamped: Vec<bool> = details.players.iter().map(|team| team[0].clamped).collect();
+        assert_eq!(clamped, vec![true, false, false, false, false, false]);
+
+        assert_eq!(
+            rater.update_ratings_detailed(vec![vec![Rating::default()], vec![]], vec![1, 2]),
+            Err(BBTError::EmptyTeam { index: 1 })
+        );
+    }
+
+    #[test]
+    fn weighted_update_interpolates() {
+        let rater = Rater::default();
+        let teams = vec![
+            vec![Rating::new(30.0, 5.0), Rating::default()],
+            vec![Rating::new(20.0, 2.0)],
+        ];
+        let full = rater.update_ratings(teams.clone(), vec![2, 1]).unwrap();
+
+        assert_eq!(
+            rater
+                .update_ratings_weighted(teams.clone(), vec![2, 1], 1.0)
+                .unwrap(),
+            full
+        );
+
+        let none = rater
+            .update_ratings_weighted(teams.clone(), vec![2, 1], 0.0)
+            .unwrap();
+        let half = rater
+            .update_ratings_weighted(teams.clone(), vec![2, 1], 0.5)
+            .unwrap();
+
+        for t in 0..teams.len() {
+            for p in 0..teams[t].len() {
+                let (old, new) = (&teams[t][p], &full[t][p]);
+                assert!((none[t][p].mu - old.mu).abs() < 1e-12);
+                assert!((none[t][p].sigma - old.sigma).abs() < 1e-12);
+                assert!((half[t][p].mu - (old.mu + new.mu) / 2.0).abs() < 1e-12);
+                assert!((half[t][p].sigma_sq - (old.sigma_sq + new.sigma_sq) / 2.0).abs() < 1e-12);
+            }
+        }
+
+        for &weight in &[-0.1, 1.5, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                rater.update_ratings_weighted(teams.clone(), vec![2, 1], weight),
+                Err(BBTError::InvalidParameter { name: "weight" })
+            );
+        }
+    }
+
+    #[test]
+    fn scratch_update_matches_plain_update() {
+        let rater = Rater::default();
+        let mut scratch = UpdateScratch::new();
+
+        let fixtures = vec![
+            (vec![vec![Rating::default()]; 2], vec![1, 2]),
+            (
+                vec![
+                    vec![Rating::new(30.0, 5.0), Rating::default()],
+                    vec![Rating::new(20.0, 7.0)],
+                    vec![Rating::default(), Rating::new(27.0, 2.0)],
+                ],
+                vec![2, 1, 2],
+            ),
+            (vec![vec![Rating::default()]; 6], vec![1, 2, 3, 4, 5, 6]),
+            (vec![vec![Rating::new(25.0, 3.0)]; 3], vec![3, 2, 1]),
+        ];
+
+        let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+        let random_fixtures = (2..40).map(|n_teams| random_match(&mut rng, n_teams));
+
+        for (teams, ranks) in fixtures.into_iter().chain(random_fixtures) {
+            let expected = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+            let actual = rater.update_ratings_in(teams, ranks, &mut scratch).unwrap();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    fn assert_bit_identical(actual: &Rating, expected: &Rating) {
+        assert_eq!(actual.mu.to_bits(), expected.mu.to_bits());
+        assert_eq!(actual.sigma.to_bits(), expected.sigma.to_bits());
+    }
+
+    #[test]
+    fn const_update_matches_dynamic_update() {
+        let rater = Rater::default();
+
+        let mut p1 = [Rating::default()];
+        let mut p2 = [Rating::default()];
+        let expected = rater
+            .update_ratings(vec![p1.to_vec(), p2.to_vec()], vec![0, 1])
+            .unwrap();
+        rater
+            .update_ratings_const(&mut [&mut p1, &mut p2], [0, 1])
+            .unwrap();
+        assert_bit_identical(&p1[0], &expected[0][0]);
+        assert_bit_identical(&p2[0], &expected[1][0]);
+
+        let mut a = [Rating::default()];
+        let mut b = [Rating::default()];
+        let mut c = [Rating::default()];
+        let mut d = [Rating::default()];
+        let expected = rater
+            .update_ratings(vec![vec![Rating::default()]; 4], vec![1, 2, 3, 4])
+            .unwrap();
+        rater
+            .update_ratings_const(&mut [&mut a, &mut b, &mut c, &mut d], [1, 2, 3, 4])
+            .unwrap();
+        for (actual, expected) in [a, b, c, d].iter().zip(expected.iter()) {
+            assert_bit_identical(&actual[0], &expected[0]);
+        }
+
+        let mut t1 = [Rating::new(30.0, 5.0), Rating::default()];
+        let mut t2 = [Rating::new(20.0, 7.0)];
+        let mut t3 = [Rating::default(), Rating::new(27.0, 2.0)];
+        let expected = rater
+            .update_ratings(vec![t1.to_vec(), t2.to_vec(), t3.to_vec()], vec![2, 1, 2])
+            .unwrap();
+        rater
+            .update_ratings_const(&mut [&mut t1, &mut t2, &mut t3], [2, 1, 2])
+            .unwrap();
+        for (actual, expected) in t1
+            .iter()
+            .chain(t2.iter())
+            .chain(t3.iter())
+            .zip(expected.concat().iter())
+        {
+            assert_bit_identical(actual, expected);
+        }
+    }
+
+    #[test]
+    fn const_update_validates_input() {
+        let rater = Rater::default();
+        let mut p1 = [Rating::default()];
+        let mut empty: [Rating; 0] = [];
+
+        let err = rater
+            .update_ratings_const(&mut [&mut p1, &mut empty], [1, 2])
+            .unwrap_err();
+
+        assert_eq!(err, BBTError::EmptyTeam { index: 1 });
+        assert_eq!(p1[0], Rating::default());
+    }
+
+    // With the `simd` feature, `update_ratings` no longer uses the scalar
+    // path; `duel_matches_scalar_update_on_grid` covers that case.
+    #[cfg(not(feature = "simd"))]
+    #[test]
+    fn duel_matches_dynamic_update() {
+        let rater = Rater::default();
+        let p1 = Rating::new(28.0, 4.0);
+        let p2 = Rating::new(23.0, 6.5);
+
+        for &(outcome, ranks) in [
+            (Outcome::Win, [1, 2]),
+            (Outcome::Loss, [2, 1]),
+            (Outcome::Draw, [1, 1]),
+        ]
+        .iter()
+        {
+            let (new_p1, new_p2) = rater.duel(p1, p2, outcome);
+            let expected = rater
+                .update_ratings(vec![vec![p1], vec![p2]], ranks.to_vec())
+                .unwrap();
+
+            assert_bit_identical(&new_p1, &expected[0][0]);
+            assert_bit_identical(&new_p2, &expected[1][0]);
+        }
+    }
+
+    #[test]
+    fn large_match_spills_to_the_heap() {
+        use std::convert::TryInto;
+
+        let rater = Rater::default();
+        let mut ratings: Vec<[Rating; 1]> = (0..20)
+            .map(|i| [Rating::new(15.0 + i as f64, 2.0 + (i % 5) as f64)])
+            .collect();
+        let ranks: Vec<usize> = (0..20).map(|i| (i * 7) % 20 + 1).collect();
+
+        let teams = ratings.iter().map(|team| team.to_vec()).collect();
+        let expected = rater.update_ratings(teams, ranks.clone()).unwrap();
+
+        let mut ranks_array = [0; 20];
+        ranks_array.copy_from_slice(&ranks);
+        let team_refs: Vec<&mut [Rating]> = ratings.iter_mut().map(|t| &mut t[..]).collect();
+        let mut team_array: [&mut [Rating]; 20] = match team_refs.try_into() {
+            Ok(array) => array,
+            Err(_) => unreachable!(),
+        };
+        rater
+            .update_ratings_const(&mut team_array, ranks_array)
+            .unwrap();
+
+        for (actual, expected) in ratings.iter().zip(expected.iter()) {
+            assert_bit_identical(&actual[0], &expected[0]);
+        }
+    }
+
+    #[test]
+    fn buffered_update_matches_plain_update() {
+        let mut rater = Rater::default();
+
+        let teams = vec![
+            vec![Rating::new(30.0, 5.0), Rating::default()],
+            vec![Rating::new(20.0, 7.0)],
+        ];
+        let expected = rater.update_ratings(teams.clone(), vec![2, 1]).unwrap();
+        let actual = rater.update_ratings_buffered(teams, vec![2, 1]).unwrap();
+        assert_eq!(actual, expected);
+        assert!(rater.scratch.team_mu.capacity() >= 2);
+
+        let teams = vec![vec![Rating::default()]; 24];
+        let ranks: Vec<usize> = (1..=24).collect();
+        let expected = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+        let actual = rater.update_ratings_buffered(teams, ranks).unwrap();
+        assert_eq!(actual, expected);
+        assert!(rater.scratch.team_mu.capacity() >= 24);
+    }
+
+    #[test]
+    fn rater_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Rater>();
+    }
+
+    /// A small xorshift generator for reproducible random fixtures.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn next_f64(&mut self) -> f64 {
+            (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next_u64() % n as u64) as usize
+        }
+    }
+
+    fn random_match(rng: &mut XorShift, n_teams: usize) -> (Vec<Vec<Rating>>, Vec<usize>) {
+        let teams = (0..n_teams)
+            .map(|_| {
+                (0..1 + rng.below(3))
+                    .map(|_| Rating::new(50.0 * rng.next_f64(), 0.5 + 8.0 * rng.next_f64()))
+                    .collect()
+            })
+            .collect();
+        let ranks = (0..n_teams).map(|_| 1 + rng.below(n_teams)).collect();
+
+        (teams, ranks)
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_omega_delta_matches_serial() {
+        let rater = Rater::default();
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+        let (teams, ranks) = random_match(&mut rng, 500);
+
+        let mut serial_scratch = UpdateScratch::new();
+        let mut serial = serial_scratch.workspace(teams.len());
+        compute_team_totals(&teams, &mut serial);
+        rater.compute_omega_delta(&ranks, &mut serial);
+
+        let mut parallel_scratch = UpdateScratch::new();
+        let mut parallel = parallel_scratch.workspace(teams.len());
+        compute_team_totals(&teams, &mut parallel);
+        rater.compute_omega_delta_parallel(&ranks, &mut parallel);
+
+        for team_idx in 0..teams.len() {
+            assert_eq!(
+                serial.team_omega[team_idx].to_bits(),
+                parallel.team_omega[team_idx].to_bits()
+            );
+            assert_eq!(
+                serial.team_delta[team_idx].to_bits(),
+                parallel.team_delta[team_idx].to_bits()
+            );
+        }
+
+        // The full update of such a large match goes through the parallel path.
+        let parallel_result = rater.update_ratings_unchecked(teams.clone(), ranks);
+        let mut serial_result = teams;
+        apply_team_updates(&mut serial_result, &serial, WriteBack::default(), |_, _| ());
+        assert_eq!(serial_result, parallel_result);
+    }
+
+    #[test]
+    fn batch_update_matches_individual_updates() {
+        let rater = Rater::default();
+        let mut rng = XorShift(0xD1B5_4A32_D192_ED03);
+
+        let mut games: Vec<(Vec<Vec<Rating>>, Vec<usize>)> = (0..1000)
+            .map(|_| {
+                let n_teams = 2 + rng.below(30);
+                random_match(&mut rng, n_teams)
+            })
+            .collect();
+
+        let expected: Vec<Vec<Vec<Rating>>> = games
+            .iter()
+            .map(|(teams, ranks)| rater.update_ratings(teams.clone(), ranks.clone()).unwrap())
+            .collect();
+
+        let mut batch: Vec<GameMut> = games
+            .iter_mut()
+            .map(|(teams, ranks)| GameMut { teams, ranks })
+            .collect();
+        rater.update_batch(&mut batch).unwrap();
+
+        for ((teams, _), expected) in games.iter().zip(expected.iter()) {
+            assert_eq!(teams, expected);
+        }
+    }
+
+    #[test]
+    fn batch_update_validates_all_games_first() {
+        let rater = Rater::default();
+        let mut games: Vec<(Vec<Vec<Rating>>, Vec<usize>)> =
+            vec![(vec![vec![Rating::default()]; 2], vec![1, 2]); 1000];
+        games[500].1 = vec![1, 2, 3];
+        let original = games.clone();
+
+        let mut batch: Vec<GameMut> = games
+            .iter_mut()
+            .map(|(teams, ranks)| GameMut { teams, ranks })
+            .collect();
+        let err = rater.update_batch(&mut batch).unwrap_err();
+
+        assert_eq!(
+            err,
+            BatchError {
+                game: 500,
+                error: BBTError::MismatchedLengths { teams: 2, ranks: 3 },
+            }
+        );
+        assert_eq!(games, original);
+    }
+
+    #[test]
+    fn windowed_update_with_full_window_is_exact() {
+        let rater = Rater::default();
+        let mut rng = XorShift(0x0123_4567_89AB_CDEF);
+
+        for n_teams in 2..30 {
+            let (teams, ranks) = random_match(&mut rng, n_teams);
+
+            let expected = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+            let actual = rater
+                .update_ratings_windowed(teams, ranks, n_teams)
+                .unwrap();
+
+            for (actual, expected) in actual.concat().iter().zip(expected.concat().iter()) {
+                assert_bit_identical(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn windowed_update_approximates_full_update() {
+        let rater = Rater::default();
+        let teams: Vec<Vec<Rating>> = (0..50)
+            .map(|i| vec![Rating::new(100.0 - 2.0 * i as f64, 2.0)])
+            .collect();
+        let ranks: Vec<usize> = (1..=50).collect();
+
+        let full = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+
+        let before = COMPARISONS.with(|count| count.get());
+        let windowed = rater.update_ratings_windowed(teams, ranks, 10).unwrap();
+        let comparisons = COMPARISONS.with(|count| count.get()) - before;
+
+        for (full, windowed) in full.iter().zip(windowed.iter()) {
+            assert!((full[0].mu - windowed[0].mu).abs() < 0.1);
+        }
+
+        // Every team is compared with at most ten teams on either side.
+        assert!(comparisons <= 50 * 2 * 10);
+        assert!(comparisons < 50 * 49);
+    }
+
+    #[test]
+    fn windowed_update_includes_whole_tied_groups() {
+        let rater = Rater::default();
+        let teams = vec![vec![Rating::default()]; 6];
+        let ranks = vec![1, 2, 2, 2, 5, 6];
+
+        let before = COMPARISONS.with(|count| count.get());
+        rater.update_ratings_windowed(teams, ranks, 1).unwrap();
+        let comparisons = COMPARISONS.with(|count| count.get()) - before;
+
+        // The winner is compared with the whole tied group, each member of
+        // the group with the winner and the other members, and the last two
+        // teams (three and four positions below the group) only with each
+        // other.
+        assert_eq!(comparisons, 3 + 3 * 3 + 1 + 1);
+    }
+
+    #[test]
+    fn mismatched_lengths_error_carries_lengths() {
+        let rater = Rater::default();
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+
+        let err = rater.update_ratings(teams, vec![1, 2, 3]).unwrap_err();
+
+        assert_eq!(err, BBTError::MismatchedLengths { teams: 2, ranks: 3 });
+        assert_eq!(
+            err.to_string(),
+            "`teams` and `ranks` vectors must be of the same length (got 2 teams and 3 ranks)"
+        );
+    }
+
+    #[test]
+    fn empty_team_error_carries_index() {
+        let rater = Rater::default();
+        let teams = vec![vec![Rating::default()], vec![Rating::default()], vec![]];
+
+        let err = rater.update_ratings(teams, vec![1, 2, 3]).unwrap_err();
+
+        assert_eq!(err, BBTError::EmptyTeam { index: 2 });
+        assert_eq!(err.to_string(), "The team at index 2 contains no players");
+    }
+
+    #[test]
+    fn f64_results_are_unchanged() {
+        let rater = Rater::default();
+        let teams = vec![
+            vec![Rating::default(), Rating::new(30.0, 4.0)],
+            vec![Rating::new(20.0, 2.0)],
+            vec![Rating::default()],
+            vec![Rating::new(27.0, 6.0), Rating::new(21.0, 5.0)],
+        ];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2, 2, 4]).unwrap();
+
+        // Recorded before ratings became generic over the scalar type.
+        let expected: [(u64, u64); 6] = [
+            (0x403bb1d9a515ba4f, 0x401f946d3431143b),
+            (0x403e9ef104dba2f0, 0x400f9ebe4c235756),
+            (0x4034687ce11f809e, 0x3ffff6678eeea98e),
+            (0x403c54789b905e90, 0x401e462c234465ad),
+            (0x40343412024e10fc, 0x401749dcd3665ceb),
+            (0x403047b72c446f5a, 0x40139715740f3e7e),
+        ];
+
+        let players = new_ratings.iter().flat_map(|team| team.iter());
+        for (player, &(mu, sigma)) in players.zip(expected.iter()) {
+            assert_eq!(player.mu.to_bits(), mu);
+            assert_eq!(player.sigma.to_bits(), sigma);
+        }
+    }
+
+    #[test]
+    fn f32_duel_fixtures() {
+        let rater = Rater::new(25.0f32 / 6.0);
+
+        let (winner, loser) = rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+        assert!((winner.mu - 27.635231).abs() < 1e-4);
+        assert!((loser.mu - 22.364769).abs() < 1e-4);
+        assert!((winner.sigma - 8.065506).abs() < 1e-4);
+        assert!((loser.sigma - 8.065506).abs() < 1e-4);
+
+        let (p1, p2) = rater.duel(Rating::default(), Rating::default(), Outcome::Draw);
+        assert_eq!(p1.mu, 25.0);
+        assert_eq!(p2.mu, 25.0);
+        assert!((p1.sigma - 8.065506).abs() < 1e-4);
+    }
+
+    #[test]
+    fn f32_four_player_race() {
+        let rater = Rater::new(25.0f32 / 6.0);
+        let teams = vec![vec![Rating::<f32>::default()]; 4];
+
+        let new_ratings = rater.update_ratings(teams, vec![1, 2, 3, 4]).unwrap();
+
+        let expected_mu = [32.905694, 27.635231, 22.364769, 17.094306];
+        for (team, &mu) in new_ratings.iter().zip(expected_mu.iter()) {
+            assert!((team[0].mu - mu).abs() < 1e-4);
+            assert!((team[0].sigma - 7.501219).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn f32_matches_f64_on_random_matches() {
+        let mut rng = XorShift(0xf32f_32f3);
+        let rater64 = Rater::default();
+        let rater32 = Rater::new(25.0f32 / 6.0);
+
+        for n_teams in 2..12 {
+            let (teams, ranks) = random_match(&mut rng, n_teams);
+            let teams32: Vec<Vec<Rating<f32>>> = teams
+                .iter()
+                .map(|team| {
+                    team.iter()
+                        .map(|player| Rating::new(player.mu as f32, player.sigma as f32))
+                        .collect()
+                })
+                .collect();
+
+            let new64 = rater64.update_ratings(teams, ranks.clone()).unwrap();
+            let new32 = rater32.update_ratings(teams32, ranks).unwrap();
+
+            for (team64, team32) in new64.iter().zip(new32.iter()) {
+                for (p64, p32) in team64.iter().zip(team32.iter()) {
+                    assert!((p64.mu - f64::from(p32.mu)).abs() < 1e-3);
+                    assert!((p64.sigma - f64::from(p32.sigma)).abs() < 1e-3);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn f32_rating_display() {
+        let rating = Rating::new(30.0f32, 2.0);
+
+        assert_eq!(format!("{}", rating), "24");
+        assert_eq!(format!("{:?}", rating), "30±6");
+        assert_eq!(format!("{}", Rating::new(1.0f32, 2.0)), "0.0");
+    }
+
+    /// Checks that the vectorized kernel agrees with the scalar path to within
+    /// 1e-12 times the sum of the absolute values of the terms.
+    #[cfg(feature = "simd")]
+    fn assert_simd_matches_scalar(rater: &Rater, teams: &[Vec<Rating>], ranks: &[usize]) {
+        let n_teams = teams.len();
+        let mut scratch = UpdateScratch::new();
+        let mut work = scratch.workspace(n_teams);
+        compute_team_totals(teams, &mut work);
+
+        for team_idx in 0..n_teams {
+            let scalar = rater.team_omega_delta(
+                team_idx,
+                0..n_teams,
+                work.team_mu,
+                work.team_sigma_sq,
+                ranks,
+                (work.omega_terms, work.delta_terms),
+            );
+            let omega_scale: f64 = work.omega_terms[..n_teams - 1]
+                .iter()
+                .map(|t| t.abs())
+                .sum();
+            let delta_scale: f64 = work.delta_terms[..n_teams - 1]
+                .iter()
+                .map(|t| t.abs())
+                .sum();
+
+            let n_terms = simd::pair_terms(
+                team_idx,
+                work.team_mu,
+                work.team_sigma_sq,
+                ranks,
+                rater.beta_sq,
+                (work.omega_terms, work.delta_terms),
+            )
+            .unwrap();
+            assert_eq!(n_terms, n_teams - 1);
+
+            let omega = canonical_sum(&mut work.omega_terms[..n_terms]);
+            let delta = canonical_sum(&mut work.delta_terms[..n_terms]);
+
+            assert!((omega - scalar.0).abs() <= 1e-12 * omega_scale);
+            assert!((delta - scalar.1).abs() <= 1e-12 * delta_scale);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_kernel_matches_scalar_path() {
+        let mut rng = XorShift(0x513d_513d);
+        let rater = Rater::default();
+
+        // Cover full chunks of four as well as every length of the tail.
+        for n_teams in 2..40 {
+            let (teams, ranks) = random_match(&mut rng, n_teams);
+
+            assert_simd_matches_scalar(&rater, &teams, &ranks);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_kernel_handles_extreme_rating_differences() {
+        let rater = Rater::new(1.0);
+        let teams = vec![
+            vec![Rating::new(1e6, 1.0)],
+            vec![Rating::new(0.0, 1.0)],
+            vec![Rating::new(-1e6, 1.0)],
+            vec![Rating::new(3.0, 1.0)],
+            vec![Rating::new(5e5, 0.0)],
+        ];
+
+        assert_simd_matches_scalar(&rater, &teams, &[3, 2, 1, 5, 4]);
+    }
+
+    /// Performs a full update with the general scalar path of Algorithm 1.
+    fn scalar_update(rater: &Rater, teams: &mut [Vec<Rating>], ranks: &[usize]) {
+        let n_teams = teams.len();
+        let mut scratch = UpdateScratch::new();
+        let mut work = scratch.workspace(n_teams);
+        compute_team_totals(teams, &mut work);
+
+        for team_idx in 0..n_teams {
+            let (omega, delta) = rater.team_omega_delta(
+                team_idx,
+                0..n_teams,
+                work.team_mu,
+                work.team_sigma_sq,
+                ranks,
+                (work.omega_terms, work.delta_terms),
+            );
+
+            work.team_omega[team_idx] = omega;
+            work.team_delta[team_idx] = delta;
+        }
+
+        apply_team_updates(teams, &work, WriteBack::default(), |_, _| ());
+    }
+
+    #[test]
+    fn duel_matches_scalar_update_on_grid() {
+        let mus = [-0.0, 0.0, 3.5, 25.0, 41.25, 3000.0];
+        let sigmas = [0.0, 0.5, 25.0 / 3.0, 12.0];
+        let outcomes = [
+            (Outcome::Win, [1, 2]),
+            (Outcome::Loss, [2, 1]),
+            (Outcome::Draw, [1, 1]),
+        ];
+
+        for &beta in [25.0 / 6.0, 0.0].iter() {
+            let rater = Rater::new(beta);
+
+            for &mu1 in mus.iter() {
+                for &mu2 in mus.iter() {
+                    for &sigma1 in sigmas.iter() {
+                        for &sigma2 in sigmas.iter() {
+                            let p1 = Rating::new(mu1, sigma1);
+                            let p2 = Rating::new(mu2, sigma2);
+
+                            for &(outcome, ranks) in outcomes.iter() {
+                                let (new_p1, new_p2) = rater.duel(p1, p2, outcome);
+
+                                let mut expected = vec![vec![p1], vec![p2]];
+                                scalar_update(&rater, &mut expected, &ranks);
+
+                                assert_bit_identical(&new_p1, &expected[0][0]);
+                                assert_bit_identical(&new_p2, &expected[1][0]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "simd"))]
+    /// The update as it was implemented before validation and Step 1 were
+    /// fused and the per-pair and per-player factors were hoisted, kept to
+    /// check that the restructured implementation produces identical results.
+    fn reference_update(
+        rater: &Rater,
+        mut teams: Vec<Vec<Rating>>,
+        ranks: &[usize],
+    ) -> Result<Vec<Vec<Rating>>, BBTError> {
+        rater.validate(&teams, ranks)?;
+
+        let n_teams = teams.len();
+        let mut team_mu = vec![0.0; n_teams];
+        let mut team_sigma_sq = vec![0.0; n_teams];
+
+        for (team_idx, team) in teams.iter().enumerate() {
+            let mut mu = CompensatedSum::default();
+            let mut sigma_sq = CompensatedSum::default();
+
+            for player in team.iter() {
+                mu.add(player.mu);
+                sigma_sq.add(player.sigma_sq);
+            }
+
+            team_mu[team_idx] = mu.total();
+            team_sigma_sq[team_idx] = sigma_sq.total();
+        }
+
+        let mut omegas = Vec::new();
+        let mut deltas = Vec::new();
+
+        for team_idx in 0..n_teams {
+            let mut omega_terms = Vec::new();
+            let mut delta_terms = Vec::new();
+
+            for team2_idx in 0..n_teams {
+                if team_idx == team2_idx {
+                    continue;
+                }
+
+                let c = (team_sigma_sq[team_idx] + team_sigma_sq[team2_idx] + 2.0 * rater.beta_sq)
+                    .sqrt();
+
+                if c == 0.0 {
+                    continue;
+                }
+
+                let piq = logistic(team_mu[team_idx] - team_mu[team2_idx], c);
+                let pqi = logistic(team_mu[team2_idx] - team_mu[team_idx], c);
+
+                let s = match ranks[team2_idx].cmp(&ranks[team_idx]) {
+                    Ordering::Greater => 1.0,
+                    Ordering::Equal => 0.5,
+                    Ordering::Less => 0.0,
+                };
+
+                let delta = (team_sigma_sq[team_idx] / c) * (s - piq);
+                let gamma = team_sigma_sq[team_idx].sqrt() / c;
+                let eta = gamma * (team_sigma_sq[team_idx] / (c * c)) * piq * pqi;
+
+                omega_terms.push(delta);
+                delta_terms.push(eta);
+            }
+
+            omegas.push(canonical_sum(&mut omega_terms));
+            deltas.push(canonical_sum(&mut delta_terms));
+        }
+
+        for (team_idx, team) in teams.iter_mut().enumerate() {
+            if team_sigma_sq[team_idx] == 0.0 {
+                continue;
+            }
+
+            for player in team.iter_mut() {
+                let new_mu =
+                    player.mu + (player.sigma_sq / team_sigma_sq[team_idx]) * omegas[team_idx];
+
+                let mut sigma_adj =
+                    1.0 - (player.sigma_sq / team_sigma_sq[team_idx]) * deltas[team_idx];
+                if sigma_adj < 0.0001 {
+                    sigma_adj = 0.0001;
+                }
+
+                let new_sigma_sq = player.sigma_sq * sigma_adj;
+
+                *player = Rating {
+                    mu: new_mu,
+                    sigma: new_sigma_sq.sqrt(),
+                    sigma_sq: new_sigma_sq,
+                };
+            }
+        }
+
+        Ok(teams)
+    }
+
+    // With the `simd` feature, the pairwise terms are no longer computed by
+    // the scalar path that the reference implements.
+    #[cfg(not(feature = "simd"))]
+    #[test]
+    fn restructured_update_matches_reference() {
+        let mut rng = XorShift(0x0f05_ed00);
+        let rater = Rater::default();
+
+        for _ in 0..20 {
+            for &n_teams in [2, 3, 7, 16, 17, 40, 64].iter() {
+                let (teams, ranks) = random_match(&mut rng, n_teams);
+
+                let expected = reference_update(&rater, teams.clone(), &ranks).unwrap();
+                let actual = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+
+                for (actual, expected) in actual.iter().zip(expected.iter()) {
+                    for (actual, expected) in actual.iter().zip(expected.iter()) {
+                        assert_bit_identical(actual, expected);
+                    }
+                }
+
+                // The const-generic path validates and sums in the same pass.
+                if n_teams == 3 {
+                    let mut owned = teams.clone();
+                    let (a, rest) = owned.split_at_mut(1);
+                    let (b, c) = rest.split_at_mut(1);
+                    let mut refs = [&mut a[0][..], &mut b[0][..], &mut c[0][..]];
+                    rater
+                        .update_ratings_const(&mut refs, [ranks[0], ranks[1], ranks[2]])
+                        .unwrap();
+
+                    assert_eq!(owned, expected);
+                }
+            }
+        }
+
+        // Overflowing teams are still rejected on their plain sums.
+        let huge = vec![Rating::new(f64::MAX, 1.0), Rating::new(f64::MAX, 1.0)];
+        let teams = vec![huge, vec![Rating::default()]];
+        assert_eq!(
+            rater.update_ratings(teams.clone(), vec![1, 2]).unwrap_err(),
+            reference_update(&rater, teams, &[1, 2]).unwrap_err()
+        );
+    }
+
+    fn updated_population() -> Vec<Rating> {
+        let mut rng = XorShift(0x5eed_b0ff);
+        let rater = Rater::default();
+        let (teams, ranks) = random_match(&mut rng, 30);
+
+        // Updated ratings have a variance that is not recomputed from sigma,
+        // so they exercise the exactness of the round trip.
+        let mut ratings: Vec<Rating> = rater
+            .update_ratings(teams, ranks)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        ratings.push(Rating::new(-3.5, 0.0));
+        ratings
+    }
 
-#[cfg(feature = "serde")]
-extern crate serde;
+    #[test]
+    fn ratings_buffer_round_trips_exactly() {
+        let ratings = updated_population();
+        let buffer = RatingsBuffer::from(&ratings[..]);
 
-#[cfg(feature = "serde")]
-mod serialization;
+        assert_eq!(buffer.len(), ratings.len());
+        assert_eq!(buffer.to_vec(), ratings);
+        assert_eq!(buffer.iter().len(), ratings.len());
 
-use std::cmp::Ordering;
-use std::fmt;
+        for (i, (actual, expected)) in buffer.iter().zip(ratings.iter()).enumerate() {
+            assert_bit_identical(&actual, expected);
+            assert_eq!(actual.sigma_sq.to_bits(), expected.sigma_sq.to_bits());
+            assert_eq!(buffer.get(i), Some(*expected));
+        }
+        assert_eq!(buffer.get(ratings.len()), None);
 
-/// Rater is used to calculate rating updates given the β-parameter.
-pub struct Rater {
-    beta_sq: f64,
-}
+        let mut pushed = RatingsBuffer::new();
+        for rating in &ratings {
+            pushed.push(*rating);
+        }
+        assert_eq!(pushed, buffer);
 
-impl Rater {
-    /// This method instantiates a new rater with the given β-parameter.
-    pub fn new(beta: f64) -> Rater {
-        Rater {
-            beta_sq: beta * beta,
+        pushed.set(3, Rating::new(1.0, 2.0));
+        assert_eq!(pushed.get(3), Some(Rating::new(1.0, 2.0)));
+        assert_eq!((&pushed).into_iter().count(), ratings.len());
+    }
+
+    #[test]
+    fn ratings_buffer_bulk_operations_match_per_element() {
+        let ratings = updated_population();
+        let mut buffer = RatingsBuffer::from(&ratings[..]);
+
+        let mut estimates = vec![0.0; buffer.len()];
+        buffer.conservative_estimates(&mut estimates);
+
+        for (estimate, rating) in estimates.iter().zip(ratings.iter()) {
+            assert_eq!(
+                estimate.to_bits(),
+                (rating.mu - 3.0 * rating.sigma).to_bits()
+            );
+        }
+
+        buffer.decay_all(0.25);
+
+        for (decayed, rating) in buffer.iter().zip(ratings.iter()) {
+            let sigma_sq = rating.sigma_sq + 0.25 * 0.25;
+
+            assert_eq!(decayed.mu.to_bits(), rating.mu.to_bits());
+            assert_eq!(decayed.sigma_sq.to_bits(), sigma_sq.to_bits());
+            assert_eq!(decayed.sigma.to_bits(), sigma_sq.sqrt().to_bits());
         }
+        assert_eq!(buffer.mus().len(), buffer.sigmas().len());
     }
-}
 
-impl Default for Rater {
-    /// This method instantiates a new rater the default β-parameter of 25.0/6.0
-    /// used in the paper.
-    fn default() -> Rater {
-        Rater::new(25.0 / 6.0)
+    #[test]
+    #[should_panic(expected = "output length mismatch")]
+    fn ratings_buffer_rejects_short_output() {
+        let buffer = RatingsBuffer::from(&[Rating::default(), Rating::default()][..]);
+        buffer.conservative_estimates(&mut [0.0]);
     }
-}
 
-impl Rater {
-    /// This method takes a vector of teams, with each team being a vector of
-    /// player ratings, and a vector ranks of the same size that specifies the
-    /// order in which the team finished a game. It returns either
-    /// `Err(error_message)` if the input is incorrect or
-    /// `Ok(Vec<Vec<Rating>>)`. The returned vector is an updated version of
-    /// the `teams` vector that was passed into the function.
-    pub fn update_ratings(
-        &self,
-        teams: Vec<Vec<Rating>>,
-        ranks: Vec<usize>,
-    ) -> Result<Vec<Vec<Rating>>, &'static str> {
-        if teams.len() != ranks.len() {
-            return Err("`teams` and `ranks` vectors must be of the same length");
+    #[test]
+    fn fixed_point_math_matches_f64() {
+        let mut rng = XorShift(0xf1_7ed0);
+
+        for _ in 0..10_000 {
+            let x = 40.0 * rng.next_f64() - 20.0;
+            let y = 1000.0 * rng.next_f64();
+            let fx = Fixed::from_f64(x);
+            let fy = Fixed::from_f64(y);
+
+            assert!((fx.exp().to_f64() - x.exp()).abs() <= 1e-9 * x.exp().max(1.0));
+            assert!((fy.sqrt().to_f64() - y.sqrt()).abs() <= 1e-9);
+            assert!(((fx * fy).to_f64() - x * y).abs() <= 1e-6);
+            assert!(((fy / fx).to_f64() - y / x).abs() <= 1e-6 * (y / x).abs().max(1.0));
+            assert!((fy.powf(Fixed::from_f64(0.5)).to_f64() - y.sqrt()).abs() <= 1e-6);
         }
 
-        let mut team_mu = vec![0.0; teams.len()];
-        let mut team_sigma_sq = vec![0.0; teams.len()];
-        let mut team_omega = vec![0.0; teams.len()];
-        let mut team_delta = vec![0.0; teams.len()];
+        assert_eq!(
+            Fixed::from_f64(3.0).powf(Fixed::from_f64(2.0)),
+            Fixed::from_f64(9.0)
+        );
+        assert_eq!(Fixed::from_f64(-1000.0).exp(), Fixed::ZERO);
+        assert_eq!(Fixed::from_f64(1000.0).exp(), Fixed::MAX);
+        assert!(!Fixed::from_f64(-1.0).sqrt().is_finite());
+        assert!(!(Fixed::ONE / Fixed::ZERO).is_finite());
+        assert!(!Fixed::from_f64(f64::NAN).is_finite());
+    }
 
-        ////////////////////////////////////////////////////////////////////////
-        // Step 1 - Collect Team skill and variance ////////////////////////////
-        ////////////////////////////////////////////////////////////////////////
+    /// Replays 1,000 random games between 24 players with both the `f64` and
+    /// the fixed-point rater, returning the final ratings of both.
+    fn replay() -> (Vec<Rating>, Vec<RatingI>) {
+        let mut rng = XorShift(0x0dd_ba11);
+        let rater = Rater::default();
+        let rater_i = RaterI::new(Fixed::from_f64(25.0 / 6.0));
 
-        for (team_idx, team) in teams.iter().enumerate() {
-            if team.is_empty() {
-                return Err("At least one of the teams contains no players");
+        let mut players = vec![Rating::default(); 24];
+        let mut players_i = vec![RatingI::default(); 24];
+
+        for _ in 0..1_000 {
+            let mut order: Vec<usize> = (0..players.len()).collect();
+            for i in (1..order.len()).rev() {
+                order.swap(i, rng.below(i + 1));
             }
 
-            for player in team.iter() {
-                team_mu[team_idx] += player.mu;
-                team_sigma_sq[team_idx] += player.sigma_sq;
+            let n_teams = 2 + rng.below(5);
+            let team_size = 1 + rng.below(2);
+            let ranks: Vec<usize> = (0..n_teams).map(|_| 1 + rng.below(n_teams)).collect();
+            let members: Vec<&[usize]> = order.chunks(team_size).take(n_teams).collect();
+
+            if team_size == 1 && n_teams == 2 {
+                let outcome = match ranks[0].cmp(&ranks[1]) {
+                    Ordering::Less => Outcome::Win,
+                    Ordering::Equal => Outcome::Draw,
+                    Ordering::Greater => Outcome::Loss,
+                };
+                let (a, b) = (members[0][0], members[1][0]);
+
+                let (new_a, new_b) = rater.duel(players[a], players[b], outcome);
+                players[a] = new_a;
+                players[b] = new_b;
+
+                let (new_a, new_b) = rater_i.duel(players_i[a], players_i[b], outcome);
+                players_i[a] = new_a;
+                players_i[b] = new_b;
+                continue;
             }
-        }
 
-        ////////////////////////////////////////////////////////////////////////
-        // Step 2 - Compute Team Omega and Delta ///////////////////////////////
-        ////////////////////////////////////////////////////////////////////////
+            let teams = members
+                .iter()
+                .map(|team| team.iter().map(|&p| players[p]).collect())
+                .collect();
+            let teams_i = members
+                .iter()
+                .map(|team| team.iter().map(|&p| players_i[p]).collect())
+                .collect();
 
-        for team_idx in 0..teams.len() {
-            for team2_idx in 0..teams.len() {
-                if team_idx == team2_idx {
-                    continue;
+            let new = rater.update_ratings(teams, ranks.clone()).unwrap();
+            let new_i = rater_i.update_ratings(teams_i, ranks).unwrap();
+
+            for (team, (new, new_i)) in members.iter().zip(new.into_iter().zip(new_i)) {
+                for (&p, (rating, rating_i)) in team.iter().zip(new.into_iter().zip(new_i)) {
+                    players[p] = rating;
+                    players_i[p] = rating_i;
                 }
+            }
+        }
 
-                let c = (team_sigma_sq[team_idx] + team_sigma_sq[team2_idx] + 2.0 * self.beta_sq)
-                    .sqrt();
-                let e1 = (team_mu[team_idx] / c).exp();
-                let e2 = (team_mu[team2_idx] / c).exp();
-                let piq = e1 / (e1 + e2);
-                let pqi = e2 / (e1 + e2);
-                let ri = ranks[team_idx];
-                let rq = ranks[team2_idx];
-
-                let s = match rq.cmp(&ri) {
-                    Ordering::Greater => 1.0,
-                    Ordering::Equal => 0.5,
-                    Ordering::Less => 0.0,
-                };
+        (players, players_i)
+    }
 
-                let delta = (team_sigma_sq[team_idx] / c) * (s - piq);
-                let gamma = team_sigma_sq[team_idx].sqrt() / c;
-                let eta = gamma * (team_sigma_sq[team_idx] / (c * c)) * piq * pqi;
+    #[test]
+    fn fixed_point_replay_tracks_f64() {
+        let (players, players_i) = replay();
+
+        for (rating, rating_i) in players.iter().zip(players_i.iter()) {
+            assert!((rating.mu - rating_i.mu.to_f64()).abs() < 1e-6);
+            assert!((rating.sigma - rating_i.sigma.to_f64()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn fixed_point_replay_is_bit_exact() {
+        let (_, players_i) = replay();
 
-                team_omega[team_idx] += delta;
-                team_delta[team_idx] += eta;
+        // FNV-1a over the raw bits of every final rating. This only changes
+        // if the fixed-point results change, on any platform.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for rating in &players_i {
+            for value in [rating.mu, rating.sigma, rating.sigma_sq].iter() {
+                for byte in value.to_bits().to_le_bytes().iter() {
+                    hash ^= u64::from(*byte);
+                    hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+                }
             }
         }
 
-        ////////////////////////////////////////////////////////////////////////
-        // Step 3 - Individual skill update ////////////////////////////////////
-        ////////////////////////////////////////////////////////////////////////
+        assert_eq!(hash, 0x36bf_d3b0_8aa1_48b0);
+    }
 
-        let mut result = Vec::with_capacity(teams.len());
+    #[test]
+    fn win_probability_matches_duel_terms() {
+        let rater = Rater::default();
+        let p1 = Rating::new(30.0, 4.0);
+        let p2 = Rating::new(25.0, 6.0);
 
-        for (team_idx, team) in teams.iter().enumerate() {
-            let mut team_result = Vec::with_capacity(team.len());
+        let c = (16.0f64 + 36.0 + 2.0 * rater.beta_sq).sqrt();
+        assert_eq!(rater.win_probability(&p1, &p2), logistic(5.0, c));
+        assert!(
+            (rater.win_probability(&p1, &p2) + rater.win_probability(&p2, &p1) - 1.0).abs() < 1e-15
+        );
 
-            for player in team.iter() {
-                let new_mu =
-                    player.mu + (player.sigma_sq / team_sigma_sq[team_idx]) * team_omega[team_idx];
+        let certain = Rater::new(0.0);
+        let a = Rating::new(30.0, 0.0);
+        let b = Rating::new(25.0, 0.0);
+        assert_eq!(certain.win_probability(&a, &b), 1.0);
+        assert_eq!(certain.win_probability(&b, &a), 0.0);
+        assert_eq!(certain.win_probability(&a, &a), 0.5);
+    }
 
-                let mut sigma_adj =
-                    1.0 - (player.sigma_sq / team_sigma_sq[team_idx]) * team_delta[team_idx];
+    #[test]
+    fn match_quality_of_duels() {
+        let rater = Rater::default();
+        let p1 = Rating::new(30.0, 4.0);
+        let p2 = Rating::new(25.0, 6.0);
 
-                if sigma_adj < 0.0001 {
-                    sigma_adj = 0.0001;
-                }
+        let c_sq = 16.0 + 36.0 + 2.0 * rater.beta_sq;
+        let expected = (2.0 * rater.beta_sq / c_sq).sqrt() * (-25.0 / (2.0 * c_sq)).exp();
+        assert!((rater.match_quality(&p1, &p2) - expected).abs() < 1e-15);
+        assert_eq!(rater.match_quality(&p1, &p2), rater.match_quality(&p2, &p1));
 
-                let new_sigma_sq = player.sigma_sq * sigma_adj;
+        // Certain, equal players are the best possible match.
+        let certain = Rating::new(25.0, 0.0);
+        assert_eq!(rater.match_quality(&certain, &certain), 1.0);
+        assert!(rater.match_quality(&p1, &p1) < 1.0);
 
-                team_result.push(Rating {
-                    mu: new_mu,
-                    sigma: new_sigma_sq.sqrt(),
-                    sigma_sq: new_sigma_sq,
-                });
+        let no_beta = Rater::new(0.0);
+        assert_eq!(no_beta.match_quality(&certain, &certain), 1.0);
+        assert_eq!(
+            no_beta.match_quality(&certain, &Rating::new(26.0, 0.0)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn win_probability_table_is_within_error_bound() {
+        let rater = Rater::default();
+        let sigma = 0.8;
+        let player = |mu| Rating::new(mu, sigma);
+
+        for &step in [0.01, 0.1, 0.5, 2.0].iter() {
+            let table = WinProbabilityTable::new(&rater, sigma, step, 30.0).unwrap();
+
+            let mut diff = -30.0;
+            while diff <= 30.0 {
+                let exact = rater.win_probability(&player(25.0 + diff), &player(25.0));
+                let error = (table.lookup(diff) - exact).abs();
+
+                assert!(error <= table.error_bound() + 1e-15, "{} {}", step, diff);
+                diff += 0.0137;
             }
 
-            result.push(team_result);
+            // Differences outside of the range are clamped to the ends.
+            assert_eq!(table.lookup(1000.0), table.lookup(30.0));
+            assert_eq!(table.lookup(-1000.0), table.lookup(-30.0));
+            assert_eq!(table.lookup(f64::NAN), table.lookup(-30.0));
         }
 
-        Ok(result)
+        let table = WinProbabilityTable::new(&rater, sigma, 0.1, 30.0).unwrap();
+        assert!(table.error_bound() < 4e-6);
+        assert!((table.lookup(0.0) - 0.5).abs() < 1e-12);
     }
 
-    /// This method calculates the new ratings for two players after a
-    /// head-to-head duel. The outcome is from the first player `p1`'s
-    /// perspective, i.e. `Win` if the first player won, `Loss` if the second
-    /// player won and `Draw` if neither player won.
-    pub fn duel(&self, p1: Rating, p2: Rating, outcome: Outcome) -> (Rating, Rating) {
-        let teams = vec![vec![p1], vec![p2]];
-        let ranks = match outcome {
-            Outcome::Win => vec![1, 2],
-            Outcome::Loss => vec![2, 1],
-            Outcome::Draw => vec![1, 1],
-        };
+    #[test]
+    fn win_probability_table_validates_parameters() {
+        let rater = Rater::default();
+        let invalid = |name| Err(BBTError::InvalidParameter { name });
 
-        let result = self.update_ratings(teams, ranks).unwrap();
+        assert_eq!(
+            WinProbabilityTable::new(&rater, -1.0, 0.1, 30.0).map(|_| ()),
+            invalid("sigma")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&rater, f64::NAN, 0.1, 30.0).map(|_| ()),
+            invalid("sigma")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&rater, 1.0, 0.0, 30.0).map(|_| ()),
+            invalid("mu_step")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&rater, 1.0, -0.1, 30.0).map(|_| ()),
+            invalid("mu_step")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&rater, 1.0, f64::INFINITY, 30.0).map(|_| ()),
+            invalid("mu_step")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&rater, 1.0, 0.1, f64::INFINITY).map(|_| ()),
+            invalid("mu_range")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&rater, 1.0, 0.1, 0.0).map(|_| ()),
+            invalid("mu_range")
+        );
+        assert_eq!(
+            WinProbabilityTable::new(&Rater::new(0.0), 0.0, 0.1, 1.0).map(|_| ()),
+            invalid("sigma")
+        );
+    }
 
-        (result[0][0].clone(), result[1][0].clone())
+    fn elo_expected_score(elo_diff: f64) -> f64 {
+        1.0 / (1.0 + 10f64.powf(-elo_diff / 400.0))
     }
-}
 
-/// Outcome represents the outcome of a head-to-head duel between two players.
-#[derive(Clone, Copy)]
-pub enum Outcome {
-    /// The first player won the game
-    Win,
+    #[test]
+    fn elo_scale_maps_anchor_exactly() {
+        let scale = EloScale::default();
+        assert_eq!(Rating::default().to_elo(&scale), 1500.0);
+        assert_eq!(
+            Rating::from_elo(1500.0, &scale, 2.0),
+            Rating::new(25.0, 2.0)
+        );
 
-    /// The first player lost the game
-    Loss,
+        let scale = scale.with_anchor(1000.0, 2000.0);
+        assert_eq!(Rating::new(1000.0, 1.0).to_elo(&scale), 2000.0);
+        assert_eq!(Rating::from_elo(2000.0, &scale, 1.0).mu, 1000.0);
 
-    /// Neither player won
-    Draw,
-}
+        let rating = Rating::new(31.25, 1.5);
+        let elo = rating.to_elo(&scale);
+        assert!((Rating::from_elo(elo, &scale, 1.5).mu - 31.25).abs() < 1e-12);
+    }
 
-/// Rating represents the skill of a player.
-#[derive(PartialEq, Clone)]
-pub struct Rating {
-    mu: f64,
-    sigma: f64,
-    sigma_sq: f64,
-}
+    #[test]
+    fn elo_scale_maps_symmetric_differences_symmetrically() {
+        let scale = EloScale::default();
 
-impl Default for Rating {
-    /// Instantiates a Rating with the default values of mu=25.0 and sigma=25.0/3.0
-    fn default() -> Rating {
-        Rating {
-            mu: 25.0,
-            sigma: 25.0 / 3.0,
-            sigma_sq: f64::powf(25.0 / 3.0, 2.0),
+        for &diff in [0.5, 3.0, 10.0, 40.0].iter() {
+            let above = Rating::new(25.0 + diff, 1.0).to_elo(&scale) - 1500.0;
+            let below = 1500.0 - Rating::new(25.0 - diff, 1.0).to_elo(&scale);
+            assert!((above - below).abs() < 1e-9);
+            assert!((above - diff * scale.elo_per_mu()).abs() < 1e-9);
         }
-    }
-}
 
-impl PartialOrd for Rating {
-    fn partial_cmp(&self, other: &Rating) -> Option<std::cmp::Ordering> {
-        (self.mu - 3.0 * self.sigma).partial_cmp(&(other.mu - 3.0 * other.sigma))
+        assert!((scale.elo_per_mu() - 29.48).abs() < 0.01);
     }
-}
 
-impl fmt::Display for Rating {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let cons_est = self.mu - 3.0 * self.sigma;
-        if cons_est < 0.0 {
-            write!(f, "0.0")
-        } else {
-            write!(f, "{}", cons_est)
+    #[test]
+    fn elo_scale_matches_win_probabilities() {
+        let rater = Rater::default();
+
+        // With the converged sigma, both curves are the same logistic.
+        for &sigma in [0.0, 1.0, 2.5].iter() {
+            let scale = EloScale::new(&rater, sigma);
+
+            for &gap in [-400.0, -100.0, 0.0, 100.0, 800.0].iter() {
+                let p1 = Rating::from_elo(1600.0 + gap, &scale, sigma);
+                let p2 = Rating::from_elo(1600.0, &scale, sigma);
+
+                let expected = elo_expected_score(gap);
+                assert!((rater.win_probability(&p1, &p2) - expected).abs() < 1e-12);
+            }
         }
+
+        // Less converged players stay within the documented tolerance.
+        let scale = EloScale::default();
+        let p1 = Rating::from_elo(1600.0, &scale, 1.0);
+        let p2 = Rating::from_elo(1500.0, &scale, 1.0);
+        let difference = rater.win_probability(&p1, &p2) - elo_expected_score(100.0);
+        assert!(difference.abs() < 0.005);
     }
-}
 
-impl fmt::Debug for Rating {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}±{}", self.mu, 3.0 * self.sigma)
+    #[test]
+    fn glicko_default_scale_maps_anchors() {
+        let scale = GlickoScale::default();
+
+        let rating = Rating::from_glicko2(1500.0, 350.0, &scale);
+        assert_eq!(rating, Rating::default());
+        assert_eq!(rating.to_glicko2(&scale), (1500.0, 350.0));
+
+        let rating = Rating::from_glicko2(1920.0, 84.0, &scale);
+        assert_eq!(rating, Rating::new(35.0, 2.0));
     }
-}
 
-impl Rating {
-    pub fn new(mu: f64, sigma: f64) -> Rating {
-        Rating {
-            mu,
-            sigma,
-            sigma_sq: sigma.powf(2.0),
+    #[test]
+    fn glicko_round_trips_are_exact() {
+        let mut rng = XorShift(0x611c_0c02);
+        let scale = GlickoScale::default();
+
+        for _ in 0..100_000 {
+            let decimals = [1.0, 10.0, 100.0, 1000.0][rng.below(4)];
+            let rating =
+                ((4000.0 * rng.next_f64() * decimals).round() - 500.0 * decimals) / decimals;
+            let rd = ((350.0 * rng.next_f64() * decimals).round() + decimals) / decimals;
+
+            let converted = Rating::from_glicko2(rating, rd, &scale);
+            assert_eq!(converted.to_glicko2(&scale), (rating, rd));
         }
     }
 
-    /// Returns the estimated skill of the player.
-    pub fn mu(&self) -> f64 {
-        self.mu
+    #[test]
+    fn glicko_custom_scale() {
+        // The traditional 0 to 3000 scale described in the crate docs.
+        let scale = GlickoScale::new(1500.0, 350.0, 1500.0, 500.0);
+
+        assert_eq!(
+            Rating::from_glicko2(1500.0, 350.0, &scale),
+            Rating::new(1500.0, 500.0)
+        );
+        assert_eq!(
+            Rating::from_glicko2(1850.0, 70.0, &scale),
+            Rating::new(2000.0, 100.0)
+        );
+        assert_eq!(
+            Rating::new(2000.0, 100.0).to_glicko2(&scale),
+            (1850.0, 70.0)
+        );
     }
 
-    /// Returns the variance on the estimate of the player's skill.
-    pub fn sigma(&self) -> f64 {
-        self.sigma
+    #[test]
+    fn glicko_zero_rd_maps_to_minimum_sigma() {
+        let scale = GlickoScale::default();
+        assert_eq!(Rating::from_glicko2(1700.0, 0.0, &scale).sigma, 0.01);
+        assert_eq!(Rating::from_glicko2(1700.0, 0.1, &scale).sigma, 0.01);
+
+        let scale = scale.with_min_sigma(0.5);
+        assert_eq!(scale.min_sigma(), 0.5);
+        assert_eq!(Rating::from_glicko2(1700.0, 0.0, &scale).sigma, 0.5);
+        assert_eq!(Rating::from_glicko2(1700.0, 42.0, &scale).sigma, 1.0);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    #[test]
+    fn bytes_round_trip() {
+        let mut rng = XorShift(0x663b_17e5);
+        let special = [
+            0.0,
+            -0.0,
+            5e-324,
+            -5e-324,
+            2.2e-310,
+            f64::MIN_POSITIVE,
+            f64::EPSILON,
+            1.0,
+            25.0,
+            f64::MAX,
+            f64::MIN,
+        ];
+
+        let mut checked = 0;
+        for i in 0..100_000 {
+            let (mu, sigma) = if i < special.len() * special.len() {
+                (special[i / special.len()], special[i % special.len()])
+            } else {
+                (
+                    f64::from_bits(rng.next_u64()),
+                    f64::from_bits(rng.next_u64()),
+                )
+            };
+
+            if !mu.is_finite() || !sigma.is_finite() || sigma <= 0.0 {
+                continue;
+            }
+
+            let rating = Rating::new(mu, sigma);
+            let bytes = rating.to_bytes();
+            let decoded = Rating::from_bytes(&bytes).unwrap();
+
+            assert_eq!(decoded.to_bytes(), bytes);
+            assert_eq!(decoded.mu.to_bits(), mu.to_bits());
+            assert_eq!(decoded.sigma.to_bits(), sigma.to_bits());
+            checked += 1;
+        }
+        assert!(checked > 40_000);
+
+        for &beta in &[0.0, 5e-154, 1e-10, 25.0 / 6.0, 1e150] {
+            let rater = Rater::new(beta);
+            let decoded = Rater::from_bytes(&rater.to_bytes()).unwrap();
+            assert_eq!(decoded.beta(), beta);
+        }
+    }
 
     #[test]
-    fn can_instantiate_ratings() {
-        let default_rating = Rating::default();
-        let new_rating = Rating::new(25.0, 25.0 / 3.0);
+    fn bytes_reject_invalid_payloads() {
+        let nan = f64::NAN.to_le_bytes();
+        let one = 1.0f64.to_le_bytes();
+        let mut bytes = [0; 16];
 
-        assert_eq!(default_rating, new_rating)
+        bytes[..8].copy_from_slice(&nan);
+        bytes[8..].copy_from_slice(&one);
+        assert_eq!(
+            Rating::from_bytes(&bytes),
+            Err(BBTError::InvalidParameter { name: "mu" })
+        );
+
+        bytes[..8].copy_from_slice(&one);
+        for &sigma in &[f64::NAN, f64::INFINITY, 0.0, -0.0, -1.0] {
+            bytes[8..].copy_from_slice(&sigma.to_le_bytes());
+            assert_eq!(
+                Rating::from_bytes(&bytes),
+                Err(BBTError::InvalidParameter { name: "sigma" })
+            );
+        }
+
+        for &beta in &[f64::NAN, f64::NEG_INFINITY, -1.0] {
+            assert_eq!(
+                Rater::from_bytes(&beta.to_le_bytes()).err(),
+                Some(BBTError::InvalidParameter { name: "beta" })
+            );
+        }
     }
 
     #[test]
-    fn two_player_duel_win_loss() {
-        let p1 = ::Rating::default();
-        let p2 = ::Rating::default();
+    fn try_new_validates_parameters() {
+        assert_eq!(Rating::try_new(25.0, 8.0), Ok(Rating::new(25.0, 8.0)));
+        assert_eq!(Rating::try_new(1.5f32, 0.5), Ok(Rating::new(1.5f32, 0.5)));
 
-        let rater = ::Rater::default();
-        let new_rs = rater
-            .update_ratings(vec![vec![p1], vec![p2]], vec![0, 1])
-            .unwrap();
+        for &mu in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                Rating::try_new(mu, 1.0),
+                Err(BBTError::InvalidParameter { name: "mu" })
+            );
+        }
 
-        assert!((new_rs[0][0].mu - 27.63523138).abs() < 1.0 / 100000000.0);
-        assert!((new_rs[0][0].sigma - 8.0655063).abs() < 1.0 / 1000000.0);
-        assert!((new_rs[1][0].mu - 22.36476861).abs() < 1.0 / 100000000.0);
-        assert!((new_rs[1][0].sigma - 8.0655063).abs() < 1.0 / 1000000.0);
+        for &sigma in &[f64::NAN, f64::INFINITY, 0.0, -0.0, -1.0] {
+            assert_eq!(
+                Rating::try_new(25.0, sigma),
+                Err(BBTError::InvalidParameter { name: "sigma" })
+            );
+        }
     }
 
     #[test]
-    fn two_player_duel_tie() {
-        let p1 = Rating::default();
-        let p2 = Rating::default();
+    fn bytes_golden() {
+        // The wire format must never change.
+        assert_eq!(
+            Rating::default().to_bytes(),
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x39, 0x40, //
+                0xab, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0x20, 0x40,
+            ]
+        );
+        assert_eq!(
+            Rater::default().to_bytes(),
+            [0xab, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0x10, 0x40]
+        );
 
-        let rater = Rater::default();
-        let (new_p1, new_p2) = rater.duel(p1, p2, Outcome::Draw);
+        let decoded = Rating::from_bytes(&[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x41, 0xc0, //
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe0, 0x3f,
+        ])
+        .unwrap();
+        assert_eq!(decoded, Rating::new(-35.0, 0.5));
+    }
 
-        assert_eq!(new_p1.mu, 25.0);
-        assert_eq!(new_p2.mu, 25.0);
-        assert!((new_p1.sigma - 8.0655063).abs() < 1.0 / 1000000.0);
-        assert!((new_p2.sigma - 8.0655063).abs() < 1.0 / 1000000.0);
+    #[test]
+    fn quantized_round_trips_within_the_bound() {
+        let specs = [
+            QuantizationSpec::new(0.0..=50.0, 16, 0.01..=25.0 / 3.0, 16).unwrap(),
+            QuantizationSpec::new(-10.0..=60.0, 12, 0.5..=8.5, 8).unwrap(),
+            QuantizationSpec::new(0.0..=3000.0, 31, 1.0..=2.0, 1).unwrap(),
+        ];
+
+        for spec in &specs {
+            let (mu_min, mu_max) = spec.mu_range().into_inner();
+            let (sigma_min, sigma_max) = spec.sigma_range().into_inner();
+
+            for i in 0..=200 {
+                for j in 0..=20 {
+                    let mu = mu_min + (mu_max - mu_min) * i as f64 / 200.0;
+                    let sigma = sigma_min + (sigma_max - sigma_min) * j as f64 / 20.0;
+
+                    let decoded =
+                        Rating::from_quantized(Rating::new(mu, sigma).to_quantized(spec), spec);
+
+                    // Allows for the rounding of the arithmetic itself.
+                    let slack = 1e-9;
+                    assert!((decoded.mu - mu).abs() <= spec.max_mu_error() + slack);
+                    assert!((decoded.sigma - sigma).abs() <= spec.max_sigma_error() + slack);
+                }
+            }
+        }
+
+        assert!((specs[0].max_mu_error() - 50.0 / 131_070.0).abs() < 1e-15);
     }
 
     #[test]
-    fn four_player_race() {
-        let p1 = Rating::default();
-        let p2 = Rating::default();
-        let p3 = Rating::default();
-        let p4 = Rating::default();
+    fn quantized_values_saturate() {
+        let spec = QuantizationSpec::new(-10.0..=60.0, 16, 0.5..=8.5, 12).unwrap();
+        let decode = |mu: f64, sigma: f64| {
+            Rating::from_quantized(Rating::new(mu, sigma).to_quantized(&spec), &spec)
+        };
 
-        let rater = Rater::default();
-        let teams = vec![vec![p1], vec![p2], vec![p3], vec![p4]];
-        let ranks = vec![1, 2, 3, 4];
+        assert_eq!(decode(1e9, 1e9), Rating::new(60.0, 8.5));
+        assert_eq!(decode(f64::INFINITY, 100.0), Rating::new(60.0, 8.5));
+        assert_eq!(decode(-1e9, 1e-9), Rating::new(-10.0, 0.5));
+        assert_eq!(decode(f64::NEG_INFINITY, 0.0), Rating::new(-10.0, 0.5));
+        assert_eq!(decode(f64::NAN, f64::NAN), Rating::new(-10.0, 0.5));
 
-        let new_ratings = rater.update_ratings(teams, ranks).unwrap();
+        // All ones is the top of both ranges.
+        assert_eq!(
+            Rating::from_quantized(u32::MAX, &spec),
+            Rating::new(60.0, 8.5)
+        );
+        assert_eq!(Rating::new(1e9, 1e9).to_quantized(&spec), 0xffff_fff0);
+    }
 
-        assert!((new_ratings[0][0].mu - 32.9056941).abs() < 1.0 / 10000000.0);
-        assert!((new_ratings[1][0].mu - 27.6352313).abs() < 1.0 / 10000000.0);
-        assert!((new_ratings[2][0].mu - 22.3647686).abs() < 1.0 / 10000000.0);
-        assert!((new_ratings[3][0].mu - 17.0943058).abs() < 1.0 / 10000000.0);
+    #[test]
+    fn quantization_specs_are_validated() {
+        let spec = QuantizationSpec::new;
+        let invalid = |name| Err(BBTError::InvalidParameter { name });
 
-        assert!((new_ratings[0][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
-        assert!((new_ratings[1][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
-        assert!((new_ratings[2][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
-        assert!((new_ratings[3][0].sigma - 7.50121906).abs() < 1.0 / 1000000.0);
+        assert_eq!(spec(5.0..=5.0, 16, 1.0..=2.0, 16), invalid("mu"));
+        assert_eq!(spec(0.0..=f64::INFINITY, 16, 1.0..=2.0, 16), invalid("mu"));
+        assert_eq!(spec(f64::NAN..=1.0, 16, 1.0..=2.0, 16), invalid("mu"));
+        assert_eq!(spec(0.0..=50.0, 16, 0.0..=2.0, 16), invalid("sigma"));
+        assert_eq!(spec(0.0..=50.0, 16, 2.0..=1.0, 16), invalid("sigma"));
+        assert_eq!(spec(0.0..=50.0, 0, 1.0..=2.0, 16), invalid("mu_bits"));
+        assert_eq!(spec(0.0..=50.0, 32, 1.0..=2.0, 1), invalid("mu_bits"));
+        assert_eq!(spec(0.0..=50.0, 16, 1.0..=2.0, 0), invalid("sigma_bits"));
+        assert_eq!(spec(0.0..=50.0, 16, 1.0..=2.0, 17), invalid("sigma_bits"));
+    }
+
+    #[test]
+    fn quantized_golden() {
+        // The packing layout must never change: mu in the high bits, sigma
+        // below it, and the unused bits at the bottom.
+        let spec = QuantizationSpec::new(-10.0..=60.0, 16, 0.5..=8.5, 12).unwrap();
+
+        assert_eq!(Rating::new(30.0, 2.5).to_quantized(&spec), 0x9249_4000);
+        assert_eq!(
+            Rating::from_quantized(0x9249_4000, &spec),
+            Rating::new(
+                -10.0 + 70.0 * (37449.0 / 65535.0),
+                0.5 + 8.0 * (1024.0 / 4095.0)
+            )
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    mod properties {
+        use super::*;
+        use proptest::teams_strategy;
+        use proptest_crate::prelude::*;
+
+        proptest! {
+            #[test]
+            fn duel_winner_mu_never_decreases(
+                rater in any::<Rater>(),
+                p1 in any::<Rating>(),
+                p2 in any::<Rating>(),
+            ) {
+                let (new_p1, new_p2) = rater.duel(p1, p2, Outcome::Win);
+
+                prop_assert!(new_p1.mu >= p1.mu);
+                prop_assert!(new_p2.mu <= p2.mu);
+            }
+
+            #[test]
+            fn sole_winners_mu_never_decreases(
+                rater in any::<Rater>(),
+                (teams, ranks) in teams_strategy(6, 4),
+            ) {
+                let updated = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+
+                let winners: Vec<_> = (0..ranks.len()).filter(|&i| ranks[i] == 1).collect();
+                if let [winner] = winners[..] {
+                    for (old, new) in teams[winner].iter().zip(&updated[winner]) {
+                        prop_assert!(new.mu >= old.mu, "{:?} -> {:?}", old, new);
+                    }
+                }
+            }
+
+            #[test]
+            fn sigma_never_increases(
+                rater in any::<Rater>().prop_map(Rater::strict_ranks),
+                (teams, ranks) in teams_strategy(6, 4),
+            ) {
+                let updated = rater.update_ratings(teams.clone(), ranks).unwrap();
+
+                for (old, new) in teams.iter().flatten().zip(updated.iter().flatten()) {
+                    prop_assert!(new.sigma <= old.sigma, "{:?} -> {:?}", old, new);
+                    prop_assert!(new.sigma > 0.0);
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,93 @@
+use {BBTError, Rater, Rating};
+
+/// MarginModel maps the skill gap between two players to the margin by
+/// which the stronger one is expected to win, such as a number of goals or
+/// points. The margin is linear in the gap: each point of mu is worth a
+/// fixed number of points of score.
+///
+/// The scale depends on the game and is usually fitted to past results with
+/// `MarginModel::fit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarginModel {
+    points_per_mu: f64,
+}
+
+impl MarginModel {
+    /// Creates a model in which each point of mu is worth `points_per_mu`
+    /// points of score.
+    pub fn new(points_per_mu: f64) -> MarginModel {
+        MarginModel { points_per_mu }
+    }
+
+    /// Returns the number of points of score per point of mu.
+    pub fn points_per_mu(&self) -> f64 {
+        self.points_per_mu
+    }
+
+    /// Fits a model to games given as the ratings of the two players before
+    /// the game and the margin by which the first one won, negative if they
+    /// lost. The scale is the least-squares fit of the margins to the
+    /// differences in mu, through the origin, so that equal players are
+    /// expected to draw.
+    ///
+    /// Returns `BBTError::InvalidParameter` if a margin is not finite, or if
+    /// no game is between players of different mu, since the scale is then
+    /// undetermined.
+    ///
+    /// ```rust
+    /// use bbt::{MarginModel, Rating};
+    ///
+    /// let games = [
+    ///     (Rating::new(30.0, 1.0), Rating::new(25.0, 1.0), 2.0),
+    ///     (Rating::new(20.0, 1.0), Rating::new(30.0, 1.0), -5.0),
+    /// ];
+    ///
+    /// let model = MarginModel::fit(games.iter().cloned()).unwrap();
+    /// assert!((model.points_per_mu() - 0.48).abs() < 1e-12);
+    /// ```
+    pub fn fit<I: IntoIterator<Item = (Rating, Rating, f64)>>(
+        games: I,
+    ) -> Result<MarginModel, BBTError> {
+        let mut gap_margin = 0.0;
+        let mut gap_sq = 0.0;
+
+        for (a, b, margin) in games {
+            if !margin.is_finite() {
+                return Err(BBTError::InvalidParameter { name: "margin" });
+            }
+
+            let gap = a.mu - b.mu;
+            gap_margin += gap * margin;
+            gap_sq += gap * gap;
+        }
+
+        if !(gap_sq > 0.0 && gap_sq.is_finite()) {
+            return Err(BBTError::InvalidParameter { name: "games" });
+        }
+
+        Ok(MarginModel::new(gap_margin / gap_sq))
+    }
+}
+
+impl Rater {
+    /// Returns the margin by which `a` is expected to win against `b` under
+    /// the given model, negative if `a` is expected to lose. Equal ratings
+    /// give zero, and swapping the players negates the margin.
+    ///
+    /// The expected difference of the performances of the players is the
+    /// difference of their mus, whatever their sigmas and β, so the margin
+    /// only depends on the mus.
+    ///
+    /// ```rust
+    /// use bbt::{MarginModel, Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let model = MarginModel::new(0.4);
+    ///
+    /// let margin = rater.expected_margin(&Rating::new(30.0, 2.0), &Rating::new(25.0, 4.0), &model);
+    /// assert!((margin - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn expected_margin(&self, a: &Rating, b: &Rating, margin_model: &MarginModel) -> f64 {
+        margin_model.points_per_mu * (a.mu - b.mu)
+    }
+}
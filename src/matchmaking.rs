@@ -0,0 +1,167 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use {Rater, Rating};
+
+/// MatchQueue holds the players waiting for a duel and decides who plays
+/// whom. Players are paired when the `Rater::match_quality` of their duel
+/// exceeds a threshold, which relaxes the longer they wait, so that players
+/// with unusual ratings are eventually matched, too.
+///
+/// Times are in seconds, for example since the Unix epoch, and pairing is
+/// deterministic: the same queue and inputs always give the same pairs.
+///
+/// ```rust
+/// let mut queue = bbt::MatchQueue::new(bbt::Rater::default());
+/// queue.enqueue("alice", bbt::Rating::new(25.0, 1.0), 0);
+/// queue.enqueue("bob", bbt::Rating::new(40.0, 1.0), 0);
+/// queue.enqueue("carol", bbt::Rating::new(25.5, 1.0), 5);
+///
+/// assert_eq!(queue.pair(0.5, 0.01, 10), vec![("alice", "carol")]);
+/// assert_eq!(queue.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct MatchQueue<K> {
+    rater: Rater,
+    /// The waiting players, in the order in which they were enqueued.
+    waiting: Vec<(K, Rating, i64)>,
+}
+
+impl<K: PartialEq> MatchQueue<K> {
+    /// Creates an empty queue that rates matches with `rater`.
+    pub fn new(rater: Rater) -> MatchQueue<K> {
+        MatchQueue {
+            rater,
+            waiting: Vec::new(),
+        }
+    }
+
+    /// Returns the rater used to rate matches.
+    pub fn rater(&self) -> &Rater {
+        &self.rater
+    }
+
+    /// Returns the number of waiting players.
+    pub fn len(&self) -> usize {
+        self.waiting.len()
+    }
+
+    /// Returns whether no players are waiting.
+    pub fn is_empty(&self) -> bool {
+        self.waiting.is_empty()
+    }
+
+    /// Returns whether the player is waiting.
+    pub fn contains(&self, id: &K) -> bool {
+        self.waiting.iter().any(|(waiting, _, _)| waiting == id)
+    }
+
+    /// Adds a player who started waiting at `joined_at`. If the player is
+    /// already waiting, only their rating is updated, and they keep their
+    /// place and waiting time.
+    pub fn enqueue(&mut self, id: K, rating: Rating, joined_at: i64) {
+        match self
+            .waiting
+            .iter_mut()
+            .find(|&&mut (ref waiting, _, _)| *waiting == id)
+        {
+            Some(&mut (_, ref mut current, _)) => *current = rating,
+            None => self.waiting.push((id, rating, joined_at)),
+        }
+    }
+
+    /// Removes a player from the queue and returns their rating, or `None`
+    /// if they were not waiting.
+    pub fn remove(&mut self, id: &K) -> Option<Rating> {
+        let index = self
+            .waiting
+            .iter()
+            .position(|(waiting, _, _)| waiting == id)?;
+
+        Some(self.waiting.remove(index).1)
+    }
+
+    /// Pairs up waiting players, removes them from the queue and returns
+    /// the pairs.
+    ///
+    /// A player who has waited `t` seconds at `now` accepts a match whose
+    /// quality exceeds `min_quality - quality_relaxation_per_sec * t`. A pair
+    /// is formed if the player who has waited longer accepts it.
+    ///
+    /// Players are considered from the longest waiting to the most recent,
+    /// with ties broken by the order in which they were enqueued. Each is
+    /// paired with the acceptable opponent that gives the best match among
+    /// those not yet paired, and is listed first in the pair.
+    pub fn pair(
+        &mut self,
+        min_quality: f64,
+        quality_relaxation_per_sec: f64,
+        now: i64,
+    ) -> Vec<(K, K)> {
+        let mut order: Vec<usize> = (0..self.waiting.len()).collect();
+        order.sort_by_key(|&index| self.waiting[index].2);
+
+        let mut paired = vec![false; self.waiting.len()];
+        let mut pairs = Vec::new();
+
+        for (position, &index) in order.iter().enumerate() {
+            if paired[index] {
+                continue;
+            }
+
+            let (_, ref rating, joined_at) = self.waiting[index];
+            // Saturates, as waiting more than 2^53 seconds is not a concern.
+            let waited = now.saturating_sub(joined_at).max(0) as f64;
+            let threshold = min_quality - quality_relaxation_per_sec * waited;
+
+            // Everyone later in the order waited at most as long, so this
+            // player's threshold decides.
+            let mut best: Option<(usize, f64)> = None;
+            for &candidate in &order[position + 1..] {
+                if paired[candidate] {
+                    continue;
+                }
+
+                let quality = self.rater.match_quality(rating, &self.waiting[candidate].1);
+                let is_better = match best {
+                    Some((_, best_quality)) => quality > best_quality,
+                    None => true,
+                };
+
+                if quality > threshold && is_better {
+                    best = Some((candidate, quality));
+                }
+            }
+
+            if let Some((opponent, _)) = best {
+                paired[index] = true;
+                paired[opponent] = true;
+                pairs.push((index, opponent));
+            }
+        }
+
+        // Take the paired players out of the queue, keeping the order of the
+        // others.
+        let mut ids = Vec::with_capacity(self.waiting.len());
+        let mut remaining = Vec::with_capacity(self.waiting.len() - 2 * pairs.len());
+
+        for (index, (id, rating, joined_at)) in self.waiting.drain(..).enumerate() {
+            if paired[index] {
+                ids.push(Some(id));
+            } else {
+                ids.push(None);
+                remaining.push((id, rating, joined_at));
+            }
+        }
+        self.waiting = remaining;
+
+        pairs
+            .into_iter()
+            .map(|(first, second)| {
+                let first = ids[first].take().expect("player paired twice");
+                let second = ids[second].take().expect("player paired twice");
+                (first, second)
+            })
+            .collect()
+    }
+}
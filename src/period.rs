@@ -0,0 +1,211 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "tracing")]
+use trace_update;
+#[cfg(feature = "validate")]
+use validate;
+use {canonical_sum, updated_rating, with_workspace};
+use {BBTError, Float, Rater, Rating};
+
+/// PeriodGame is a single game of a rating period, for
+/// `Rater::update_period`: the indices of the players of each team, and the
+/// rank of each team, as passed to `Rater::update_ratings`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeriodGame {
+    /// The indices of the players of each team.
+    pub teams: Vec<Vec<usize>>,
+
+    /// The rank of each team, lower is better.
+    pub ranks: Vec<usize>,
+}
+
+impl PeriodGame {
+    /// Creates a game with the given teams and ranks.
+    pub fn new(teams: Vec<Vec<usize>>, ranks: Vec<usize>) -> PeriodGame {
+        PeriodGame { teams, ranks }
+    }
+}
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings of players from all games of a rating
+    /// period at once, as Glicko does, so the result does not depend on the
+    /// order of the games within the period.
+    ///
+    /// Every game is rated like `update_ratings`, but with the ratings from
+    /// the start of the period, and the players' changes are only applied at
+    /// the end of it: the changes of mu from all games of a player are
+    /// added up, and so are the fractions by which the games reduce the
+    /// player's variance. Sigma is then reduced once by the summed fraction,
+    /// with the same floor as in a single game. Unlike games that are rated
+    /// one after another, no game sees the variances reduced by the other
+    /// games, so every game counts as much as the first game of the period
+    /// would. A period with a single game is the same as `update_ratings`,
+    /// and players that play no game are left untouched.
+    ///
+    /// Returns `BBTError::InvalidParameter` if a game refers to a player
+    /// outside `ratings`, has an empty team, or has a player more than once,
+    /// and `BBTError::InvalidRating` if a player's rating is invalid, with
+    /// the index of the player as the team and zero as the player. Other
+    /// errors of `update_ratings` for a game are returned as they are. The
+    /// ratings are only changed if no error is returned.
+    ///
+    /// ```rust
+    /// use bbt::{PeriodGame, Rater, Rating};
+    ///
+    /// let mut ratings = [Rating::default(); 3];
+    /// let games = [
+    ///     PeriodGame::new(vec![vec![0], vec![1]], vec![1, 2]),
+    ///     PeriodGame::new(vec![vec![2], vec![0]], vec![1, 2]),
+    /// ];
+    ///
+    /// Rater::default().update_period(&mut ratings, &games).unwrap();
+    ///
+    /// // Both games were rated as if player 0 had not played the other one.
+    /// assert_eq!(ratings[0].mu(), 25.0);
+    /// assert!(ratings[0].sigma() < ratings[1].sigma());
+    /// ```
+    pub fn update_period(
+        &self,
+        ratings: &mut [Rating<F>],
+        games: &[PeriodGame],
+    ) -> Result<(), BBTError> {
+        let mut seen = vec![false; ratings.len()];
+
+        for game in games {
+            for team in &game.teams {
+                for &player in team {
+                    if player >= ratings.len() || seen[player] {
+                        return Err(BBTError::InvalidParameter { name: "games" });
+                    }
+                    seen[player] = true;
+                }
+
+                if team.is_empty() {
+                    return Err(BBTError::InvalidParameter { name: "games" });
+                }
+            }
+
+            for &player in game.teams.iter().flatten() {
+                seen[player] = false;
+
+                if !ratings[player].is_valid() {
+                    return Err(BBTError::InvalidRating {
+                        team: player,
+                        player: 0,
+                    });
+                }
+            }
+        }
+
+        // The shares of the team omega and delta of every game, by player,
+        // computed with the same operations as in `update_ratings`.
+        let mut terms: Vec<(usize, F, F)> = Vec::new();
+
+        for game in games {
+            let teams: Vec<Vec<Rating<F>>> = game
+                .teams
+                .iter()
+                .map(|team| team.iter().map(|&player| ratings[player]).collect())
+                .collect();
+
+            with_workspace(teams.len(), |work| {
+                self.validate_into(&teams, &game.ranks, work)?;
+                self.compute_all_omega_delta(&game.ranks, work);
+
+                for (team_idx, team) in game.teams.iter().enumerate() {
+                    if work.team_sigma_sq[team_idx] == F::ZERO {
+                        continue;
+                    }
+
+                    for &player in team {
+                        let share = ratings[player].sigma_sq / work.team_sigma_sq[team_idx];
+                        terms.push((
+                            player,
+                            share * work.team_omega[team_idx],
+                            share * work.team_delta[team_idx],
+                        ));
+                    }
+                }
+
+                Ok(())
+            })?;
+        }
+
+        terms.sort_by_key(|&(player, _, _)| player);
+
+        let mut omega = vec![F::ZERO; ratings.len()];
+        let mut delta = vec![F::ZERO; ratings.len()];
+        let mut played = vec![false; ratings.len()];
+        let mut omega_terms = Vec::new();
+        let mut delta_terms = Vec::new();
+
+        for chunk in terms.chunk_by(|a, b| a.0 == b.0) {
+            let player = chunk[0].0;
+
+            omega_terms.clear();
+            omega_terms.extend(chunk.iter().map(|&(_, omega, _)| omega));
+            delta_terms.clear();
+            delta_terms.extend(chunk.iter().map(|&(_, _, delta)| delta));
+
+            omega[player] = canonical_sum(&mut omega_terms);
+            delta[player] = canonical_sum(&mut delta_terms);
+            played[player] = true;
+        }
+
+        // The summed terms are already the player's shares, so every player
+        // is updated as a team of their own.
+        #[cfg(feature = "strict-math")]
+        for player in 0..ratings.len() {
+            if !played[player] || ratings[player].sigma_sq == F::ZERO {
+                continue;
+            }
+
+            let rating = &ratings[player];
+            let (rating, _) = updated_rating(rating, rating.sigma_sq, omega[player], delta[player]);
+            if !rating.mu.is_finite() || !rating.sigma.is_finite() || rating.sigma < F::ZERO {
+                return Err(BBTError::NumericalError {
+                    team: player,
+                    player: 0,
+                });
+            }
+        }
+
+        #[cfg(feature = "validate")]
+        let mu: Vec<F> = ratings.iter().map(|rating| rating.mu).collect();
+        #[cfg(feature = "validate")]
+        let sigma_sq: Vec<F> = ratings.iter().map(|rating| rating.sigma_sq).collect();
+        #[cfg(feature = "validate")]
+        let values = validate::TeamValues {
+            mu: &mu,
+            sigma_sq: &sigma_sq,
+            omega: &omega,
+            delta: &delta,
+        };
+        #[cfg(feature = "validate")]
+        validate::assert_teams(&values);
+
+        let write_back = self.write_back();
+
+        for player in 0..ratings.len() {
+            // A fully certain player is not updated by any game.
+            if !played[player] || ratings[player].sigma_sq == F::ZERO {
+                continue;
+            }
+
+            let old = &ratings[player];
+            let (rating, _clamped) =
+                updated_rating(old, old.sigma_sq, omega[player], delta[player]);
+
+            #[cfg(feature = "tracing")]
+            trace_update(player, 0, &rating, _clamped);
+
+            #[cfg(feature = "validate")]
+            validate::assert_update(player, 0, old, &rating, &values);
+
+            ratings[player] = write_back.apply(old, rating);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use replay::{apply_record, check_players, GameRecord, PlayerId};
+use {BBTError, Outcome, Rater, Rating};
+
+/// PgnGame is the result of a single game of a PGN file, as read by
+/// `pgn_results`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PgnGame {
+    /// The name of the player with the white pieces, from the `White` tag.
+    pub white: String,
+
+    /// The name of the player with the black pieces, from the `Black` tag.
+    pub black: String,
+
+    /// The outcome from the perspective of white, from the `Result` tag.
+    pub outcome: Outcome,
+
+    /// The value of the `Date` tag, such as `2018.08.25`, if there is one.
+    /// Unknown parts of a date are question marks in PGN, as in
+    /// `2018.??.??`.
+    pub date: Option<String>,
+
+    /// The line of the first tag of the game, starting at one.
+    pub line: u64,
+}
+
+impl PgnGame {
+    /// Returns the game as a record for `replay`, with white as the first
+    /// team and black as the second.
+    pub fn to_record(&self) -> GameRecord {
+        let ranks = match self.outcome {
+            Outcome::Win => vec![1, 2],
+            Outcome::Loss => vec![2, 1],
+            Outcome::Draw => vec![1, 1],
+        };
+
+        GameRecord::new(
+            vec![vec![self.white.clone()], vec![self.black.clone()]],
+            ranks,
+        )
+    }
+}
+
+/// PgnError describes why a game of a PGN file could not be read or rated.
+/// Line numbers start at one and point at the first tag of the game.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PgnError {
+    /// Reading the underlying stream failed. No further games are read.
+    Io(io::Error),
+
+    /// A line of the tag section is not a tag pair of the form
+    /// `[Name "value"]`.
+    MalformedTag { line: u64 },
+
+    /// The game has no tag with the given name.
+    MissingTag { line: u64, name: &'static str },
+
+    /// The `Result` tag is not one of `1-0`, `0-1`, `1/2-1/2` or `*`.
+    InvalidResult { line: u64, value: String },
+
+    /// The game has no result, because it was abandoned or is still in
+    /// progress. This is only a warning: `replay_pgn` skips such games.
+    Unfinished { line: u64 },
+
+    /// The game is not a valid game for `replay_pgn`, because a player
+    /// plays against themselves.
+    InvalidGame { line: u64, message: String },
+
+    /// The rater rejected the game.
+    Rejected { line: u64, error: BBTError },
+}
+
+impl PgnError {
+    /// Returns the line of the game the error belongs to, if it belongs to
+    /// one.
+    pub fn line(&self) -> Option<u64> {
+        match *self {
+            PgnError::MalformedTag { line }
+            | PgnError::MissingTag { line, .. }
+            | PgnError::InvalidResult { line, .. }
+            | PgnError::Unfinished { line }
+            | PgnError::InvalidGame { line, .. }
+            | PgnError::Rejected { line, .. } => Some(line),
+            PgnError::Io(_) => None,
+        }
+    }
+
+    /// Returns true if the error is only a warning, after which the game is
+    /// skipped, such as `PgnError::Unfinished`.
+    pub fn is_warning(&self) -> bool {
+        matches!(*self, PgnError::Unfinished { .. })
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PgnError::Io(ref error) => write!(f, "I/O error: {}", error),
+            PgnError::MalformedTag { line } => {
+                write!(f, "Malformed tag pair in the game on line {}", line)
+            }
+            PgnError::MissingTag { line, name } => {
+                write!(f, "The game on line {} has no `{}` tag", line, name)
+            }
+            PgnError::InvalidResult { line, ref value } => write!(
+                f,
+                "The game on line {} has the invalid result `{}`",
+                line, value
+            ),
+            PgnError::Unfinished { line } => {
+                write!(f, "The game on line {} has no result", line)
+            }
+            PgnError::InvalidGame { line, ref message } => {
+                write!(f, "Invalid game on line {}: {}", line, message)
+            }
+            PgnError::Rejected { line, ref error } => {
+                write!(f, "The game on line {} was rejected: {}", line, error)
+            }
+        }
+    }
+}
+
+impl Error for PgnError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            PgnError::Io(ref error) => Some(error),
+            PgnError::Rejected { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PgnError {
+    fn from(error: io::Error) -> PgnError {
+        PgnError::Io(error)
+    }
+}
+
+/// Reads the results of the games of a PGN file, such as a database dump of
+/// a chess server, one game at a time.
+///
+/// Only the tag sections are parsed, for the `White`, `Black`, `Result` and
+/// `Date` tags. The movetext is skipped without being parsed, including
+/// comments in braces that span several lines or contain brackets,
+/// rest-of-line comments, escaped lines, variations and NAGs. The input need
+/// not be valid UTF-8, as is common with older files; invalid bytes are
+/// replaced in names.
+///
+/// Every game yields either its result or an error, and reading continues
+/// with the next game after an error, except after `PgnError::Io`. Games
+/// with the result `*` yield `PgnError::Unfinished`, which is only a
+/// warning.
+///
+/// ```rust
+/// use bbt::io;
+/// use bbt::Outcome;
+///
+/// let pgn = "[White \"alice\"]\n[Black \"bob\"]\n[Result \"0-1\"]\n\n1. e4 e5 {Solid.} 0-1\n";
+///
+/// let games: Vec<_> = io::pgn_results(pgn.as_bytes()).collect();
+/// let game = games[0].as_ref().unwrap();
+///
+/// assert_eq!(games.len(), 1);
+/// assert_eq!((game.white.as_str(), game.black.as_str()), ("alice", "bob"));
+/// assert_eq!(game.outcome, Outcome::Loss);
+/// ```
+pub fn pgn_results<R: BufRead>(reader: R) -> PgnResults<R> {
+    PgnResults {
+        reader,
+        buffer: Vec::new(),
+        line: 0,
+        in_comment: false,
+        done: false,
+    }
+}
+
+/// PgnResults is the iterator over the games of a PGN file returned by
+/// `pgn_results`.
+#[derive(Debug)]
+pub struct PgnResults<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    line: u64,
+    in_comment: bool,
+    done: bool,
+}
+
+/// The tags of a game, as they are read.
+#[derive(Default)]
+struct Tags {
+    line: u64,
+    white: Option<String>,
+    black: Option<String>,
+    result: Option<String>,
+    date: Option<String>,
+    malformed: bool,
+}
+
+impl Tags {
+    fn into_game(self) -> Result<PgnGame, PgnError> {
+        let line = self.line;
+
+        if self.malformed {
+            return Err(PgnError::MalformedTag { line });
+        }
+
+        let white = self.white.ok_or(PgnError::MissingTag {
+            line,
+            name: "White",
+        })?;
+        let black = self.black.ok_or(PgnError::MissingTag {
+            line,
+            name: "Black",
+        })?;
+        let result = self.result.ok_or(PgnError::MissingTag {
+            line,
+            name: "Result",
+        })?;
+
+        let outcome = match result.as_str() {
+            "1-0" => Outcome::Win,
+            "0-1" => Outcome::Loss,
+            "1/2-1/2" => Outcome::Draw,
+            "*" => return Err(PgnError::Unfinished { line }),
+            _ => {
+                return Err(PgnError::InvalidResult {
+                    line,
+                    value: result,
+                })
+            }
+        };
+
+        Ok(PgnGame {
+            white,
+            black,
+            outcome,
+            date: self.date,
+            line,
+        })
+    }
+}
+
+impl<R: BufRead> PgnResults<R> {
+    /// Reads the next line into the buffer, without the line ending. Returns
+    /// false at the end of the input.
+    fn read_line(&mut self) -> io::Result<bool> {
+        self.buffer.clear();
+        if self.reader.read_until(b'\n', &mut self.buffer)? == 0 {
+            return Ok(false);
+        }
+
+        self.line += 1;
+        while self.buffer.last() == Some(&b'\n') || self.buffer.last() == Some(&b'\r') {
+            self.buffer.pop();
+        }
+
+        Ok(true)
+    }
+
+    /// Skips a line of movetext, keeping track of comments in braces, which
+    /// may span lines.
+    fn skip_movetext(&mut self) {
+        // Lines starting with `%` are escaped and ignored entirely.
+        if !self.in_comment && self.buffer.first() == Some(&b'%') {
+            return;
+        }
+
+        for &byte in &self.buffer {
+            match byte {
+                b'}' if self.in_comment => self.in_comment = false,
+                b'{' if !self.in_comment => self.in_comment = true,
+                // The rest of the line is a comment.
+                b';' if !self.in_comment => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns true if the current line starts a tag pair outside of a
+    /// comment.
+    fn at_tag(&self) -> bool {
+        !self.in_comment && trim(&self.buffer).first() == Some(&b'[')
+    }
+}
+
+impl<R: BufRead> Iterator for PgnResults<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut tags: Option<Tags> = None;
+        let mut in_movetext = false;
+
+        loop {
+            // The current line is kept in the buffer when it starts the next
+            // game, so it is only read if it has been consumed.
+            let more = if self.buffer.is_empty() || tags.is_some() || in_movetext {
+                match self.read_line() {
+                    Ok(more) => more,
+                    Err(error) => {
+                        self.done = true;
+                        return Some(Err(PgnError::Io(error)));
+                    }
+                }
+            } else {
+                true
+            };
+
+            if !more {
+                self.done = true;
+                return tags.map(Tags::into_game);
+            }
+
+            if self.at_tag() {
+                if in_movetext {
+                    // The line is the first tag of the next game.
+                    return tags.map(Tags::into_game);
+                }
+
+                let tags = tags.get_or_insert_with(|| Tags {
+                    line: self.line,
+                    ..Tags::default()
+                });
+                match parse_tag(trim(&self.buffer)) {
+                    Some((name, value)) => match name.as_str() {
+                        "White" => tags.white = Some(value),
+                        "Black" => tags.black = Some(value),
+                        "Result" => tags.result = Some(value),
+                        "Date" => tags.date = Some(value),
+                        _ => {}
+                    },
+                    None => tags.malformed = true,
+                }
+            } else if tags.is_some() {
+                // The first line that is neither a tag nor blank starts the
+                // movetext.
+                in_movetext = in_movetext || !trim(&self.buffer).is_empty();
+                self.skip_movetext();
+            } else {
+                // Movetext without tags, or blank lines between games.
+                self.skip_movetext();
+                self.buffer.clear();
+            }
+        }
+    }
+}
+
+/// Removes leading and trailing ASCII whitespace.
+fn trim(line: &[u8]) -> &[u8] {
+    let start = line
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let end = line
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+
+    &line[start..end]
+}
+
+/// Parses a tag pair of the form `[Name "value"]`, in which the value may
+/// contain the escapes `\"` and `\\`.
+fn parse_tag(line: &[u8]) -> Option<(String, String)> {
+    let inner = line.strip_prefix(b"[")?.strip_suffix(b"]")?;
+
+    let name_end = inner.iter().position(|b| b.is_ascii_whitespace())?;
+    let (name, rest) = inner.split_at(name_end);
+    let rest = trim(rest).strip_prefix(b"\"")?;
+
+    let mut value = Vec::new();
+    let mut bytes = rest.iter();
+    loop {
+        match *bytes.next()? {
+            b'\\' => value.push(*bytes.next()?),
+            b'"' => break,
+            byte => value.push(byte),
+        }
+    }
+
+    // Nothing may follow the closing quote but whitespace.
+    if !trim(bytes.as_slice()).is_empty() {
+        return None;
+    }
+
+    Some((
+        String::from_utf8_lossy(name).into_owned(),
+        String::from_utf8_lossy(&value).into_owned(),
+    ))
+}
+
+/// Replays the games of a PGN file like `replay`, with white as the first
+/// team and black as the second, and returns the final rating of every
+/// player. Players start with the `initial` rating when they first appear.
+///
+/// Games that only yield a warning, such as unfinished games, are skipped.
+/// Returns the first other error of `pgn_results`,
+/// `PgnError::InvalidGame` if a player plays against themselves, and
+/// `PgnError::Rejected` if the rater rejects a game.
+///
+/// ```rust
+/// use bbt::{io, Rater, Rating};
+///
+/// let pgn = "\
+/// [White \"alice\"]
+/// [Black \"bob\"]
+/// [Result \"1-0\"]
+///
+/// 1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0
+///
+/// [White \"bob\"]
+/// [Black \"carol\"]
+/// [Result \"*\"]
+///
+/// 1. d4 *
+/// ";
+///
+/// let ratings = io::replay_pgn(&Rater::default(), pgn.as_bytes(), Rating::default()).unwrap();
+///
+/// assert_eq!(ratings.len(), 2);
+/// assert!(ratings["alice"].mu() > ratings["bob"].mu());
+/// ```
+pub fn replay_pgn<R: BufRead>(
+    rater: &Rater,
+    reader: R,
+    initial: Rating,
+) -> Result<HashMap<PlayerId, Rating>, PgnError> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("replay_pgn").entered();
+
+    let mut ratings = HashMap::new();
+
+    for game in pgn_results(reader) {
+        let game = match game {
+            Ok(game) => game,
+            Err(ref error) if error.is_warning() => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%error, "skipped game");
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+
+        let line = game.line;
+        let record = game.to_record();
+        check_players(&record).map_err(|message| PgnError::InvalidGame { line, message })?;
+        apply_record(rater, &mut ratings, &record, &initial, 1.0)
+            .map_err(|error| PgnError::Rejected { line, error })?;
+    }
+
+    Ok(ratings)
+}
@@ -0,0 +1,130 @@
+use {BBTError, Float, Outcome, Rater, Rating};
+
+/// PlacementSeries chooses the opponents of a new player's placement matches
+/// from a pool of rated players, and tracks the player's rating over the
+/// series.
+///
+/// Each opponent is the member of the pool against whom a duel is expected
+/// to reduce the variance of the player's rating the most, given the current
+/// estimate. That is usually the member whose mu is closest to the estimate,
+/// preferring certain ratings over uncertain ones, so the series homes in on
+/// the player's skill much like a binary search: a win moves the estimate,
+/// and with it the next opponent, up, and a loss moves it down. A pool
+/// without an opponent near the estimate yields the most informative of the
+/// ones it has.
+///
+/// The choice is deterministic: the same series always chooses the same
+/// opponent, and ties go to the lowest index. The ratings of the pool are
+/// not changed; rate the opponents' side of the games separately.
+///
+/// ```rust
+/// use bbt::{Outcome, PlacementSeries, Rater, Rating};
+///
+/// let rater = Rater::default();
+/// let pool: Vec<Rating> = (0..11).map(|i| Rating::new(5.0 * i as f64, 1.0)).collect();
+///
+/// let mut series = PlacementSeries::new(&rater, Rating::default(), &pool, 3).unwrap();
+/// assert_eq!(series.next_opponent(), 5);
+///
+/// series.record(Outcome::Win);
+/// assert!(series.next_opponent() > 5);
+///
+/// series.record(Outcome::Win);
+/// series.record(Outcome::Loss);
+/// assert!(series.is_done());
+/// assert!(series.current_estimate().mu() > 25.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlacementSeries<'a, F: Float = f64> {
+    rater: &'a Rater<F>,
+    pool: &'a [Rating<F>],
+    estimate: Rating<F>,
+    games: usize,
+    played: usize,
+}
+
+impl<'a, F: Float> PlacementSeries<'a, F> {
+    /// Starts a series of `games` placement matches for a player with the
+    /// rating `prior`, against opponents from `pool`.
+    ///
+    /// Returns `BBTError::InvalidParameter` if the prior is not valid, or if
+    /// the pool is empty or contains a rating that is not valid (see
+    /// `Rating::is_valid`).
+    pub fn new(
+        rater: &'a Rater<F>,
+        prior: Rating<F>,
+        pool: &'a [Rating<F>],
+        games: usize,
+    ) -> Result<PlacementSeries<'a, F>, BBTError> {
+        if !prior.is_valid() {
+            return Err(BBTError::InvalidParameter { name: "prior" });
+        }
+
+        if pool.is_empty() || pool.iter().any(|rating| !rating.is_valid()) {
+            return Err(BBTError::InvalidParameter { name: "pool" });
+        }
+
+        Ok(PlacementSeries {
+            rater,
+            pool,
+            estimate: prior,
+            games,
+            played: 0,
+        })
+    }
+
+    /// Returns the index into the pool of the opponent of the next game: the
+    /// one whose duel with the current estimate has the lowest expected
+    /// variance afterwards, averaging the outcomes by their probability.
+    pub fn next_opponent(&self) -> usize {
+        let mut best = 0;
+        let mut best_variance = self.expected_variance(&self.pool[0]);
+
+        for (index, opponent) in self.pool.iter().enumerate().skip(1) {
+            let variance = self.expected_variance(opponent);
+            if variance < best_variance {
+                best = index;
+                best_variance = variance;
+            }
+        }
+
+        best
+    }
+
+    /// Records the outcome of a game against `next_opponent()`, from the
+    /// perspective of the placed player, and updates the estimate with
+    /// `Rater::duel`.
+    ///
+    /// Games beyond the length of the series are recorded like the others;
+    /// they refine the estimate, but the series stays done.
+    pub fn record(&mut self, outcome: Outcome) {
+        let opponent = self.pool[self.next_opponent()];
+        let (estimate, _) = self.rater.duel(self.estimate, opponent, outcome);
+
+        self.estimate = estimate;
+        self.played += 1;
+    }
+
+    /// Returns the rating of the player after the games recorded so far.
+    pub fn current_estimate(&self) -> Rating<F> {
+        self.estimate
+    }
+
+    /// Returns the number of games recorded so far.
+    pub fn games_played(&self) -> usize {
+        self.played
+    }
+
+    /// Returns whether all games of the series have been recorded.
+    pub fn is_done(&self) -> bool {
+        self.played >= self.games
+    }
+
+    fn expected_variance(&self, opponent: &Rating<F>) -> F {
+        let p = self.rater.win_probability(&self.estimate, opponent);
+        let (win, _) = self.rater.duel(self.estimate, *opponent, Outcome::Win);
+        let (loss, _) = self.rater.duel(self.estimate, *opponent, Outcome::Loss);
+
+        p * win.sigma_sq + (F::ONE - p) * loss.sigma_sq
+    }
+}
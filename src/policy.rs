@@ -0,0 +1,91 @@
+use {Float, Rating};
+
+/// Policy adjusts ratings after every update, for rules that the model does
+/// not know about, such as a league that never lets a player's rating drop
+/// below a floor. A rater created with `Rater::with_policy` calls
+/// `Policy::apply` with the old and the updated rating of every player whose
+/// rating it writes back, and writes back the result instead.
+///
+/// Closures taking the old and the updated rating implement `Policy`:
+///
+/// ```rust
+/// use bbt::{Outcome, Rater, Rating};
+///
+/// // Ratings may fall by at most 1 per game.
+/// let rater = Rater::default().with_policy(|old: &Rating, new: &Rating| {
+///     let floor = old.mu() - 1.0;
+///     if new.mu() < floor {
+///         Rating::new(floor, new.sigma())
+///     } else {
+///         *new
+///     }
+/// });
+///
+/// let (_, loser) = rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+/// assert_eq!(loser.mu(), 24.0);
+/// ```
+pub trait Policy<F: Float = f64>: Send + Sync {
+    /// Returns the rating to write back, given the rating before the update
+    /// and the updated rating.
+    fn apply(&self, old: &Rating<F>, new: &Rating<F>) -> Rating<F>;
+}
+
+impl<F: Float, P: Fn(&Rating<F>, &Rating<F>) -> Rating<F> + Send + Sync> Policy<F> for P {
+    fn apply(&self, old: &Rating<F>, new: &Rating<F>) -> Rating<F> {
+        self(old, new)
+    }
+}
+
+/// MuBounds is a `Policy` that keeps mu between a floor and a ceiling,
+/// while sigma is updated as usual. Ratings that start outside the bounds
+/// are not moved into them, but they can only move towards them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MuBounds<F: Float = f64> {
+    floor: Option<F>,
+    ceiling: Option<F>,
+}
+
+impl<F: Float> MuBounds<F> {
+    /// Keeps mu between `floor` and `ceiling`. `None` leaves that side
+    /// unbounded.
+    pub fn new(floor: Option<F>, ceiling: Option<F>) -> MuBounds<F> {
+        MuBounds { floor, ceiling }
+    }
+
+    /// Keeps mu from dropping below `floor`.
+    pub fn floor(floor: F) -> MuBounds<F> {
+        MuBounds::new(Some(floor), None)
+    }
+
+    /// Keeps mu from rising above `ceiling`.
+    pub fn ceiling(ceiling: F) -> MuBounds<F> {
+        MuBounds::new(None, Some(ceiling))
+    }
+}
+
+impl<F: Float> Policy<F> for MuBounds<F> {
+    fn apply(&self, old: &Rating<F>, new: &Rating<F>) -> Rating<F> {
+        let mut mu = new.mu;
+
+        if let Some(floor) = self.floor {
+            // A rating that already is below the floor may not fall further.
+            let floor = if old.mu < floor { old.mu } else { floor };
+            if mu < floor {
+                mu = floor;
+            }
+        }
+
+        if let Some(ceiling) = self.ceiling {
+            let ceiling = if old.mu > ceiling { old.mu } else { ceiling };
+            if mu > ceiling {
+                mu = ceiling;
+            }
+        }
+
+        Rating {
+            mu,
+            sigma: new.sigma,
+            sigma_sq: new.sigma_sq,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! PostgreSQL support through [sqlx](https://docs.rs/sqlx), enabled by the
+//! `sqlx-postgres` feature.
+//!
+//! `Rating` implements `sqlx::Type`, `Encode` and `Decode` for the composite
+//! type
+//!
+//! ```sql
+//! CREATE TYPE rating AS (mu double precision, sigma double precision);
+//! ```
+//!
+//! so it can be bound and fetched like any other value, including as
+//! `rating[]` (`Vec<Rating>`) and as a nullable column (`Option<Rating>`):
+//!
+//! ```rust,no_run,edition2018
+//! # extern crate bbt;
+//! # extern crate sqlx;
+//! # async fn example(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+//! use bbt::Rating;
+//!
+//! sqlx::query("UPDATE players SET rating = $1 WHERE name = $2")
+//!     .bind(Rating::new(31.5, 2.25))
+//!     .bind("alice")
+//!     .execute(&pool)
+//!     .await?;
+//!
+//! let rating: Rating = sqlx::query_scalar("SELECT rating FROM players WHERE name = $1")
+//!     .bind("alice")
+//!     .fetch_one(&pool)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Ratings stored in two `double precision` columns are read with
+//! `rating_from_columns`, which is the building block for a `FromRow`
+//! implementation:
+//!
+//! ```rust,no_run,edition2018
+//! # extern crate bbt;
+//! # extern crate sqlx;
+//! use bbt::Rating;
+//! use sqlx::postgres::PgRow;
+//! use sqlx::{FromRow, Row};
+//!
+//! struct Player {
+//!     name: String,
+//!     rating: Rating,
+//! }
+//!
+//! impl<'r> FromRow<'r, PgRow> for Player {
+//!     fn from_row(row: &'r PgRow) -> Result<Player, sqlx::Error> {
+//!         Ok(Player {
+//!             name: row.try_get("name")?,
+//!             rating: bbt::postgres::rating_from_columns(row, "mu", "sigma")?,
+//!         })
+//!     }
+//! }
+//!
+//! # async fn example(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+//! let players: Vec<Player> = sqlx::query_as("SELECT name, mu, sigma FROM players")
+//!     .fetch_all(&pool)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A rating is written to two columns by binding `rating.mu()` and
+//! `rating.sigma()`.
+//!
+//! Either way, decoding validates the ratings like `Rating::try_new`, so a
+//! row with a NaN mu, or a sigma that is not positive, is reported as a
+//! `sqlx::Error::ColumnDecode` instead of turning into a rating that poisons
+//! later updates.
+
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::postgres::types::{PgRecordDecoder, PgRecordEncoder};
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgRow, PgTypeInfo, PgValueRef, Postgres};
+use sqlx::{Row, Type};
+
+use Rating;
+
+impl Type<Postgres> for Rating {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("rating")
+    }
+}
+
+impl PgHasArrayType for Rating {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_rating")
+    }
+}
+
+impl Encode<'_, Postgres> for Rating {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let mut encoder = PgRecordEncoder::new(buf);
+        encoder.encode(self.mu)?;
+        encoder.encode(self.sigma)?;
+        encoder.finish();
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Rating {
+    fn decode(value: PgValueRef<'r>) -> Result<Rating, BoxDynError> {
+        let mut decoder = PgRecordDecoder::new(value)?;
+        let mu = decoder.try_decode::<f64>()?;
+        let sigma = decoder.try_decode::<f64>()?;
+
+        validate(mu, sigma)
+    }
+}
+
+/// Reads a rating from the two `double precision` columns `mu` and `sigma` of
+/// a row, validating it like `Rating::try_new`.
+///
+/// Returns `sqlx::Error::ColumnDecode` for the offending column if the rating
+/// is invalid, and the usual errors of `Row::try_get` if the columns are
+/// missing, `NULL`, or of the wrong type.
+pub fn rating_from_columns(row: &PgRow, mu: &str, sigma: &str) -> Result<Rating, sqlx::Error> {
+    let mu_value: f64 = row.try_get(mu)?;
+    let sigma_value: f64 = row.try_get(sigma)?;
+
+    validate(mu_value, sigma_value).map_err(|source| {
+        let column = if mu_value.is_finite() { sigma } else { mu };
+
+        sqlx::Error::ColumnDecode {
+            index: format!("{:?}", column),
+            source,
+        }
+    })
+}
+
+fn validate(mu: f64, sigma: f64) -> Result<Rating, BoxDynError> {
+    Rating::try_new(mu, sigma).map_err(|error| {
+        format!("invalid rating (mu = {}, sigma = {}): {}", mu, sigma, error).into()
+    })
+}
@@ -0,0 +1,71 @@
+//! Common rating scales, each with a matching rater and initial rating.
+//!
+//! A scale is described by its midpoint, the mu of a new player. New
+//! players start with a sigma of a third of the midpoint, so that their
+//! conservative estimate `mu - 3 * sigma` is zero, and the rater's β is a
+//! sixth of it. Scaling all three values by the same factor scales the
+//! ratings without changing any win probability, so every preset predicts
+//! the same outcomes for the same matchups.
+//!
+//! ```rust
+//! use bbt::{presets, Outcome};
+//!
+//! let (rater, rating) = presets::CHESS_LIKE.pair();
+//! let (winner, _) = rater.duel(rating, rating, Outcome::Win);
+//!
+//! assert_eq!(rating.mu(), 1500.0);
+//! assert!(winner.mu() > 1500.0);
+//! ```
+
+use {Rater, Rating};
+
+/// Preset is a rating scale: the initial rating of new players and the β of
+/// the matching rater.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Preset {
+    /// The mu of new players, the midpoint of the scale.
+    pub mu: f64,
+
+    /// The sigma of new players.
+    pub sigma: f64,
+
+    /// The β of the rater.
+    pub beta: f64,
+}
+
+/// TrueSkill's scale from 0 to 50, which is the default of `Rater` and
+/// `Rating`.
+pub const TRUESKILL: Preset = custom(25.0);
+
+/// A scale from 0 to 3000 that resembles chess ratings.
+pub const CHESS_LIKE: Preset = custom(1500.0);
+
+/// A scale from 0 to 100.
+pub const PERCENT: Preset = custom(50.0);
+
+/// Returns the scale with the given midpoint: new players start with a mu
+/// of `midpoint` and a sigma of `midpoint / 3`, and β is `midpoint / 6`.
+pub const fn custom(midpoint: f64) -> Preset {
+    Preset {
+        mu: midpoint,
+        sigma: midpoint / 3.0,
+        beta: midpoint / 6.0,
+    }
+}
+
+impl Preset {
+    /// Returns a rater with the β of the scale.
+    pub fn rater(&self) -> Rater {
+        Rater::new(self.beta)
+    }
+
+    /// Returns the initial rating of new players on the scale.
+    pub fn rating(&self) -> Rating {
+        Rating::new(self.mu, self.sigma)
+    }
+
+    /// Returns the rater and the initial rating of the scale.
+    pub fn pair(&self) -> (Rater, Rating) {
+        (self.rater(), self.rating())
+    }
+}
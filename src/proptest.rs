@@ -0,0 +1,117 @@
+//! Strategies for property testing with [proptest](https://docs.rs/proptest),
+//! enabled by the `proptest` feature.
+//!
+//! `Rating`, `Rater` and `Outcome` implement `Arbitrary`, and
+//! `teams_strategy` generates whole matches:
+//!
+//! ```rust
+//! # extern crate bbt;
+//! # extern crate proptest;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let strategy = (any::<bbt::Rater>(), bbt::proptest::teams_strategy(4, 3));
+//!
+//! TestRunner::default()
+//!     .run(&strategy, |(rater, (teams, ranks))| {
+//!         prop_assert!(rater.update_ratings(teams, ranks).is_ok());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use std::ops::Range;
+
+use proptest_crate::collection::vec;
+use proptest_crate::prelude::*;
+
+use {Outcome, Rater, Rating};
+
+/// The ranges that arbitrary ratings are drawn from, passed to
+/// `any_with::<Rating>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RatingParameters {
+    /// The range of mu. Defaults to 0 to 50.
+    pub mu: Range<f64>,
+
+    /// The range of sigma, which must only contain positive values. Defaults
+    /// to 0.01 to 10.
+    pub sigma: Range<f64>,
+}
+
+impl Default for RatingParameters {
+    fn default() -> RatingParameters {
+        RatingParameters {
+            mu: 0.0..50.0,
+            sigma: 0.01..10.0,
+        }
+    }
+}
+
+impl Arbitrary for Rating {
+    type Parameters = RatingParameters;
+    type Strategy = BoxedStrategy<Rating>;
+
+    fn arbitrary_with(parameters: RatingParameters) -> BoxedStrategy<Rating> {
+        assert!(
+            parameters.sigma.start > 0.0,
+            "sigma must be drawn from positive values"
+        );
+
+        (parameters.mu, parameters.sigma)
+            .prop_map(|(mu, sigma)| Rating::new(mu, sigma))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Rater {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rater>;
+
+    /// Generates raters with a β between 0.1 and 20.
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Rater> {
+        (0.1..20.0).prop_map(Rater::new).boxed()
+    }
+}
+
+impl Arbitrary for Outcome {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Outcome>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Outcome> {
+        prop_oneof![Just(Outcome::Win), Just(Outcome::Loss), Just(Outcome::Draw)].boxed()
+    }
+}
+
+/// Generates matches of two to `max_teams` teams with one to `max_players`
+/// players each, together with their ranks, using the default
+/// `RatingParameters`. The ranks are a standard competition ranking that may
+/// contain ties, so every match passes validation, even by a rater created
+/// with `Rater::strict_ranks`.
+///
+/// # Panics
+///
+/// Panics if `max_teams` is less than two or `max_players` is zero.
+pub fn teams_strategy(
+    max_teams: usize,
+    max_players: usize,
+) -> impl Strategy<Value = (Vec<Vec<Rating>>, Vec<usize>)> {
+    assert!(max_teams >= 2, "a match needs at least two teams");
+    assert!(max_players >= 1, "a team needs at least one player");
+
+    (2..=max_teams).prop_flat_map(move |n_teams| {
+        let teams = vec(vec(any::<Rating>(), 1..=max_players), n_teams);
+        let places = vec(0..n_teams, n_teams);
+
+        (teams, places).prop_map(|(teams, places)| {
+            // Teams with the same place tie, and a team's rank is one more
+            // than the number of teams that placed better.
+            let ranks = places
+                .iter()
+                .map(|place| 1 + places.iter().filter(|other| other < &place).count())
+                .collect();
+
+            (teams, ranks)
+        })
+    })
+}
@@ -0,0 +1,184 @@
+//! Python bindings, enabled by the `python` feature. They are exported as
+//! the module `bbt` with the classes `Rater` and `Rating`:
+//!
+//! ```python
+//! import bbt
+//!
+//! rater = bbt.Rater(25 / 6)
+//! p1, p2 = rater.duel(bbt.Rating(), bbt.Rating(), "win")
+//!
+//! teams = rater.update_ratings([[p1], [bbt.Rating(30, 4)], [p2]], [1, 2, 2])
+//! print(teams[0][0].mu, teams[0][0].ordinal)
+//! ```
+//!
+//! Errors are raised as `ValueError`s carrying the message of the
+//! `BBTError`. Both classes can be pickled.
+//!
+//! To build the extension module, run
+//!
+//! ```text
+//! cargo rustc --release --features python,pyo3/extension-module --crate-type cdylib
+//! ```
+//!
+//! and rename `target/release/libbbt.so` to `bbt.so` (`bbt.pyd` on Windows).
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use {BBTError, Outcome, Rater, Rating};
+
+impl From<BBTError> for PyErr {
+    fn from(error: BBTError) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+/// A `Rating`, exported to Python as `Rating`.
+#[pyclass(name = "Rating", module = "bbt")]
+#[derive(Clone, Debug)]
+pub struct PyRating(pub Rating);
+
+#[pymethods]
+impl PyRating {
+    #[new]
+    #[pyo3(signature = (mu = 25.0, sigma = 25.0 / 3.0))]
+    fn new(mu: f64, sigma: f64) -> PyRating {
+        PyRating(Rating::new(mu, sigma))
+    }
+
+    /// The estimated skill of the player.
+    #[getter]
+    fn mu(&self) -> f64 {
+        self.0.mu
+    }
+
+    /// The uncertainty of the estimate.
+    #[getter]
+    fn sigma(&self) -> f64 {
+        self.0.sigma
+    }
+
+    /// The conservative skill estimate `mu - 3 * sigma`.
+    #[getter]
+    fn ordinal(&self) -> f64 {
+        self.0.conservative_estimate()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Rating(mu={:?}, sigma={:?})", self.0.mu, self.0.sigma)
+    }
+
+    fn __eq__(&self, other: &PyRating) -> bool {
+        self.0.mu == other.0.mu && self.0.sigma == other.0.sigma
+    }
+
+    fn __getstate__(&self) -> (f64, f64) {
+        (self.0.mu, self.0.sigma)
+    }
+
+    fn __setstate__(&mut self, state: (f64, f64)) {
+        self.0 = Rating::new(state.0, state.1);
+    }
+}
+
+/// A `Rater`, exported to Python as `Rater`.
+#[pyclass(name = "Rater", module = "bbt")]
+pub struct PyRater(pub Rater);
+
+#[pymethods]
+impl PyRater {
+    /// Creates a rater with the given β. Raises `ValueError` if β is
+    /// negative or not finite.
+    #[new]
+    #[pyo3(signature = (beta = 25.0 / 6.0))]
+    fn new(beta: f64) -> PyResult<PyRater> {
+        validate_beta(beta)?;
+
+        Ok(PyRater(Rater::new(beta)))
+    }
+
+    /// The β of the rater.
+    #[getter]
+    fn beta(&self) -> f64 {
+        self.0.beta()
+    }
+
+    /// Returns the new ratings of two players after a duel, like
+    /// `Rater::duel`. The outcome is `"win"`, `"loss"` or `"draw"`, from the
+    /// first player's perspective.
+    fn duel(&self, p1: &PyRating, p2: &PyRating, outcome: &str) -> PyResult<(PyRating, PyRating)> {
+        let outcome = match outcome {
+            "win" => Outcome::Win,
+            "loss" => Outcome::Loss,
+            "draw" => Outcome::Draw,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown outcome {:?}",
+                    outcome
+                )))
+            }
+        };
+
+        let (p1, p2) = self.0.duel(p1.0, p2.0, outcome);
+
+        Ok((PyRating(p1), PyRating(p2)))
+    }
+
+    /// Returns the updated ratings of a match, like `Rater::update_ratings`.
+    /// `teams` is a list of teams, each a list of `Rating`s.
+    fn update_ratings(
+        &self,
+        teams: Vec<Vec<PyRating>>,
+        ranks: Vec<usize>,
+    ) -> PyResult<Vec<Vec<PyRating>>> {
+        let teams = teams
+            .into_iter()
+            .map(|team| team.into_iter().map(|rating| rating.0).collect())
+            .collect();
+
+        let teams = self.0.update_ratings(teams, ranks)?;
+
+        Ok(teams
+            .into_iter()
+            .map(|team| team.into_iter().map(PyRating).collect())
+            .collect())
+    }
+
+    /// Returns the probability that the first player beats the second, like
+    /// `Rater::win_probability`.
+    fn win_probability(&self, p1: &PyRating, p2: &PyRating) -> f64 {
+        self.0.win_probability(&p1.0, &p2.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Rater(beta={:?})", self.0.beta())
+    }
+
+    fn __getstate__(&self) -> f64 {
+        self.0.beta()
+    }
+
+    fn __setstate__(&mut self, beta: f64) -> PyResult<()> {
+        validate_beta(beta)?;
+        self.0 = Rater::new(beta);
+
+        Ok(())
+    }
+}
+
+fn validate_beta(beta: f64) -> Result<(), BBTError> {
+    if !beta.is_finite() || beta < 0.0 {
+        return Err(BBTError::InvalidParameter { name: "beta" });
+    }
+
+    Ok(())
+}
+
+/// The `bbt` Python module.
+#[pymodule]
+pub fn bbt(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyRater>()?;
+    module.add_class::<PyRating>()?;
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+//! Aggregation of the rankings of several judges into one.
+//!
+//! Contests that are scored by a panel, such as creative competitions, have
+//! one ranking per judge, but an update needs the ranks of the match.
+//! `aggregate` combines the rankings, and `Rater::update_ratings_judged`
+//! rates the match with the combined ranks.
+//!
+//! Like the ranks of `Rater::update_ratings`, a ranking gives the rank of
+//! every entrant, with the lowest rank first and equal ranks for ties. The
+//! aggregated ranks are a standard competition ranking, e.g. 1, 2, 2, 4.
+//!
+//! ```rust
+//! use bbt::ranks::{self, AggregationMethod};
+//!
+//! let judges: [&[usize]; 3] = [&[1, 2, 3], &[2, 1, 3], &[1, 3, 2]];
+//!
+//! let ranks = ranks::aggregate(&judges, AggregationMethod::Borda).unwrap();
+//! assert_eq!(ranks, vec![1, 2, 3]);
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use {ranks_from_scores, BBTError, Float, Rater, Rating, ScoreOrder};
+
+/// AggregationMethod is the way `aggregate` combines the rankings of the
+/// judges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AggregationMethod {
+    /// Every judge gives an entrant one point for every entrant they ranked
+    /// below it, and half a point for every other entrant they tied it with.
+    /// Entrants are ranked by their total points, and entrants with the same
+    /// total are tied.
+    Borda,
+
+    /// Entrants are ranked by the median of the ranks the judges gave them,
+    /// the mean of the two middle ranks for an even number of judges.
+    /// Entrants with the same median are tied. Each ranking is first turned
+    /// into a standard competition ranking, so that judges who number their
+    /// ties differently are treated alike.
+    MedianRank,
+}
+
+/// Combines the rankings of several judges, each of which gives the rank of
+/// every entrant, into the ranks of the match.
+///
+/// Returns `BBTError::InvalidParameter` if there are no judges, or if the
+/// rankings are not all of the same length.
+pub fn aggregate(
+    judge_rankings: &[&[usize]],
+    method: AggregationMethod,
+) -> Result<Vec<usize>, BBTError> {
+    let entrants = match judge_rankings.first() {
+        Some(ranking) => ranking.len(),
+        None => {
+            return Err(BBTError::InvalidParameter {
+                name: "judge_rankings",
+            })
+        }
+    };
+
+    if judge_rankings
+        .iter()
+        .any(|ranking| ranking.len() != entrants)
+    {
+        return Err(BBTError::InvalidParameter {
+            name: "judge_rankings",
+        });
+    }
+
+    Ok(match method {
+        AggregationMethod::Borda => borda(judge_rankings, entrants),
+        AggregationMethod::MedianRank => median_rank(judge_rankings, entrants),
+    })
+}
+
+/// Ranks the entrants by their Borda count. The points are doubled, so that
+/// they are whole numbers that are summed exactly.
+fn borda(judge_rankings: &[&[usize]], entrants: usize) -> Vec<usize> {
+    let mut points = vec![0.0; entrants];
+
+    for ranking in judge_rankings {
+        for (entrant, &rank) in ranking.iter().enumerate() {
+            let below = ranking.iter().filter(|&&other| other > rank).count();
+            let tied = ranking.iter().filter(|&&other| other == rank).count() - 1;
+
+            points[entrant] += (2 * below + tied) as f64;
+        }
+    }
+
+    ranks_from_scores(&points, ScoreOrder::HigherIsBetter, 0.0)
+}
+
+/// Ranks the entrants by their median rank. The medians are doubled, so that
+/// those of an even number of judges are whole numbers.
+fn median_rank(judge_rankings: &[&[usize]], entrants: usize) -> Vec<usize> {
+    let normalized: Vec<Vec<usize>> = judge_rankings
+        .iter()
+        .map(|ranking| {
+            let ranks: Vec<f64> = ranking.iter().map(|&rank| rank as f64).collect();
+            ranks_from_scores(&ranks, ScoreOrder::LowerIsBetter, 0.0)
+        })
+        .collect();
+
+    let medians: Vec<f64> = (0..entrants)
+        .map(|entrant| {
+            let mut ranks: Vec<usize> = normalized.iter().map(|ranks| ranks[entrant]).collect();
+            ranks.sort_unstable();
+
+            // The two middle ranks, which are the same for an odd number of
+            // judges.
+            (ranks[(ranks.len() - 1) / 2] + ranks[ranks.len() / 2]) as f64
+        })
+        .collect();
+
+    ranks_from_scores(&medians, ScoreOrder::LowerIsBetter, 0.0)
+}
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings of a match like `update_ratings`,
+    /// with the ranks aggregated from the rankings of several judges by
+    /// `ranks::aggregate`.
+    ///
+    /// Returns `BBTError::MismatchedLengths` if a ranking is not as long as
+    /// `teams`, the errors of `ranks::aggregate`, and the errors of
+    /// `update_ratings` otherwise. The rankings are checked before anything
+    /// is updated.
+    ///
+    /// ```rust
+    /// use bbt::ranks::AggregationMethod;
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let teams = vec![vec![Rating::default()]; 3];
+    /// let judges: [&[usize]; 2] = [&[1, 2, 3], &[1, 3, 2]];
+    ///
+    /// let judged = rater
+    ///     .update_ratings_judged(teams.clone(), &judges, AggregationMethod::Borda)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(judged, rater.update_ratings(teams, vec![1, 2, 2]).unwrap());
+    /// ```
+    pub fn update_ratings_judged(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        judge_rankings: &[&[usize]],
+        method: AggregationMethod,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        if let Some(ranking) = judge_rankings
+            .iter()
+            .find(|ranking| ranking.len() != teams.len())
+        {
+            return Err(BBTError::MismatchedLengths {
+                teams: teams.len(),
+                ranks: ranking.len(),
+            });
+        }
+
+        let ranks = aggregate(judge_rankings, method)?;
+
+        self.update_ratings(teams, ranks)
+    }
+}
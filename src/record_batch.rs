@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, TimestampSecondType, UInt16Type,
+    UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::{DataType, TimeUnit};
+
+use replay::{apply_record, check_players, GameRecord, PlayerId, ReplayError};
+use {Rater, Rating};
+
+/// GameSchema names the columns of the record batches replayed by
+/// `from_record_batches`, with one game per row.
+///
+/// The `teams` column is a list of teams, each a list of player ids
+/// (`List<List<Utf8>>`), and the `ranks` column a list of integer ranks, as
+/// in a game log. The optional `timestamp` column holds seconds since the
+/// Unix epoch as an `Int64` or a `Timestamp` in seconds, and the optional
+/// `weight` column a `Float64`. Null timestamps and weights are missing, as
+/// in a `GameRecord`.
+///
+/// The default schema reads the columns `teams` and `ranks`, like the
+/// fields of a game log, and no timestamps or weights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameSchema {
+    /// The name of the column holding the player ids of each team.
+    pub teams: String,
+
+    /// The name of the column holding the rank of each team.
+    pub ranks: String,
+
+    /// The name of the column holding the timestamps, if there is one.
+    pub timestamp: Option<String>,
+
+    /// The name of the column holding the weights, if there is one.
+    pub weight: Option<String>,
+}
+
+impl Default for GameSchema {
+    fn default() -> GameSchema {
+        GameSchema {
+            teams: "teams".to_string(),
+            ranks: "ranks".to_string(),
+            timestamp: None,
+            weight: None,
+        }
+    }
+}
+
+/// Replays the games of a sequence of record batches, such as those read
+/// from a Parquet file, like `replay`: the rows are applied in order, and
+/// players start with the `initial` rating when they first appear.
+///
+/// Returns `ReplayError::MissingColumn` or `ReplayError::InvalidColumn` if a
+/// batch does not match the schema, `ReplayError::InvalidRow` if a row is
+/// not a valid game, for example because of a null rank, and
+/// `ReplayError::RejectedRow` if the rater rejects it. Replaying stops at
+/// the first batch or row that fails.
+///
+/// ```rust
+/// extern crate arrow_array;
+/// extern crate bbt;
+///
+/// use std::sync::Arc;
+///
+/// use arrow_array::builder::{Int64Builder, ListBuilder, StringBuilder};
+/// use arrow_array::{ArrayRef, RecordBatch};
+/// use bbt::replay::{self, GameSchema};
+///
+/// let mut teams = ListBuilder::new(ListBuilder::new(StringBuilder::new()));
+/// let mut ranks = ListBuilder::new(Int64Builder::new());
+///
+/// // "alice" beats "bob".
+/// for player in &["alice", "bob"] {
+///     teams.values().append_value([Some(player)]);
+/// }
+/// teams.append(true);
+/// ranks.append_value([Some(1), Some(2)]);
+///
+/// let batch = RecordBatch::try_from_iter(vec![
+///     ("teams", Arc::new(teams.finish()) as ArrayRef),
+///     ("ranks", Arc::new(ranks.finish()) as ArrayRef),
+/// ])
+/// .unwrap();
+///
+/// let rater = bbt::Rater::default();
+/// let ratings =
+///     replay::from_record_batches(&rater, vec![batch], &GameSchema::default(), bbt::Rating::default())
+///         .unwrap();
+///
+/// assert!(ratings["alice"].mu() > ratings["bob"].mu());
+/// ```
+pub fn from_record_batches<I: IntoIterator<Item = RecordBatch>>(
+    rater: &Rater,
+    batches: I,
+    schema: &GameSchema,
+    initial: Rating,
+) -> Result<HashMap<PlayerId, Rating>, ReplayError> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("replay_record_batches").entered();
+
+    let mut ratings = HashMap::new();
+
+    for (batch_index, batch) in batches.into_iter().enumerate() {
+        let records = batch_records(&batch, batch_index, schema)?;
+
+        for (row, record) in records.iter().enumerate() {
+            apply_record(rater, &mut ratings, record, &initial, 1.0).map_err(|error| {
+                ReplayError::RejectedRow {
+                    batch: batch_index,
+                    row,
+                    error,
+                }
+            })?;
+        }
+    }
+
+    Ok(ratings)
+}
+
+/// Reads the games of a sequence of record batches as `GameRecord`s, for
+/// replaying them with `replay_with_options` or other functions that take
+/// records.
+///
+/// Returns the errors of `from_record_batches`, except for
+/// `ReplayError::RejectedRow`, since the games are not rated.
+pub fn read_record_batches<I: IntoIterator<Item = RecordBatch>>(
+    batches: I,
+    schema: &GameSchema,
+) -> Result<Vec<GameRecord>, ReplayError> {
+    let mut records = Vec::new();
+
+    for (batch_index, batch) in batches.into_iter().enumerate() {
+        records.extend(batch_records(&batch, batch_index, schema)?);
+    }
+
+    Ok(records)
+}
+
+/// Reads the games of a single record batch.
+fn batch_records(
+    batch: &RecordBatch,
+    batch_index: usize,
+    schema: &GameSchema,
+) -> Result<Vec<GameRecord>, ReplayError> {
+    let column = |name: &str, expected: &'static str, matches: fn(&DataType) -> bool| {
+        let column = batch
+            .column_by_name(name)
+            .ok_or_else(|| ReplayError::MissingColumn {
+                batch: batch_index,
+                name: name.to_string(),
+            })?;
+
+        if matches(column.data_type()) {
+            Ok(column)
+        } else {
+            Err(ReplayError::InvalidColumn {
+                batch: batch_index,
+                name: name.to_string(),
+                message: format!("expected {}, found {}", expected, column.data_type()),
+            })
+        }
+    };
+
+    let teams = column(&schema.teams, "a list of lists of strings", is_teams)?;
+    let ranks = column(&schema.ranks, "a list of integers", is_ranks)?;
+    let timestamps = match schema.timestamp {
+        Some(ref name) => Some(column(
+            name,
+            "an Int64 or a Timestamp in seconds",
+            is_timestamp,
+        )?),
+        None => None,
+    };
+    let weights = match schema.weight {
+        Some(ref name) => Some(column(name, "a Float64", |t| *t == DataType::Float64)?),
+        None => None,
+    };
+
+    (0..batch.num_rows())
+        .map(|row| {
+            let invalid = |message: String| ReplayError::InvalidRow {
+                batch: batch_index,
+                row,
+                message,
+            };
+
+            let mut record = GameRecord::new(
+                row_teams(teams, row).map_err(invalid)?,
+                row_ranks(ranks, row).map_err(invalid)?,
+            );
+            record.timestamp = timestamps.and_then(|column| row_timestamp(column, row));
+            record.weight = weights
+                .filter(|column| column.is_valid(row))
+                .map(|column| column.as_primitive::<Float64Type>().value(row));
+
+            check_players(&record).map_err(invalid)?;
+
+            Ok(record)
+        })
+        .collect()
+}
+
+fn is_teams(data_type: &DataType) -> bool {
+    match *data_type {
+        DataType::List(ref team) => match *team.data_type() {
+            DataType::List(ref player) => *player.data_type() == DataType::Utf8,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_ranks(data_type: &DataType) -> bool {
+    match *data_type {
+        DataType::List(ref rank) => rank.data_type().is_integer(),
+        _ => false,
+    }
+}
+
+fn is_timestamp(data_type: &DataType) -> bool {
+    matches!(
+        *data_type,
+        DataType::Int64 | DataType::Timestamp(TimeUnit::Second, _)
+    )
+}
+
+/// Reads the player ids of each team of a row of the teams column.
+fn row_teams(teams: &ArrayRef, row: usize) -> Result<Vec<Vec<PlayerId>>, String> {
+    let teams = teams.as_list::<i32>();
+    if teams.is_null(row) {
+        return Err("the teams are null".to_string());
+    }
+
+    let teams = teams.value(row);
+    let teams = teams.as_list::<i32>();
+
+    (0..teams.len())
+        .map(|team| {
+            if teams.is_null(team) {
+                return Err(format!("team {} is null", team));
+            }
+
+            teams
+                .value(team)
+                .as_string::<i32>()
+                .iter()
+                .map(|player| {
+                    player
+                        .map(str::to_string)
+                        .ok_or_else(|| format!("a player id of team {} is null", team))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reads the ranks of a row of the ranks column.
+fn row_ranks(ranks: &ArrayRef, row: usize) -> Result<Vec<usize>, String> {
+    let ranks = ranks.as_list::<i32>();
+    if ranks.is_null(row) {
+        return Err("the ranks are null".to_string());
+    }
+
+    let ranks = ranks.value(row);
+
+    macro_rules! collect_ranks {
+        ($type:ty) => {
+            ranks
+                .as_primitive::<$type>()
+                .iter()
+                .map(|rank| {
+                    let rank = rank.ok_or_else(|| "a rank is null".to_string())?;
+                    usize::try_from(rank).map_err(|_| format!("expected a rank, found {}", rank))
+                })
+                .collect()
+        };
+    }
+
+    match *ranks.data_type() {
+        DataType::Int8 => collect_ranks!(Int8Type),
+        DataType::Int16 => collect_ranks!(Int16Type),
+        DataType::Int32 => collect_ranks!(Int32Type),
+        DataType::Int64 => collect_ranks!(Int64Type),
+        DataType::UInt8 => collect_ranks!(UInt8Type),
+        DataType::UInt16 => collect_ranks!(UInt16Type),
+        DataType::UInt32 => collect_ranks!(UInt32Type),
+        DataType::UInt64 => collect_ranks!(UInt64Type),
+        // The column was checked to hold a list of integers.
+        _ => unreachable!(),
+    }
+}
+
+/// Reads the timestamp of a row of the timestamp column, if it is not null.
+fn row_timestamp(timestamps: &ArrayRef, row: usize) -> Option<i64> {
+    if timestamps.is_null(row) {
+        return None;
+    }
+
+    match *timestamps.data_type() {
+        DataType::Int64 => Some(timestamps.as_primitive::<Int64Type>().value(row)),
+        _ => Some(timestamps.as_primitive::<TimestampSecondType>().value(row)),
+    }
+}
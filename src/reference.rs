@@ -0,0 +1,138 @@
+//! A plain transcription of Algorithm 1 of the paper, enabled by the
+//! `reference` feature.
+//!
+//! `Rater::update_ratings` is optimized: it reuses working memory, sums with
+//! compensation in a canonical order, shares factors between opponents and
+//! evaluates the logistic on skill differences. `reference_update` does none
+//! of that. It follows the steps of the paper line by line, with the paper's
+//! notation, so that it can be checked against the paper by reading it, and
+//! the optimized update can be checked against it by testing.
+//!
+//! The two agree within 1e-12, with one known exception, the sigma floor:
+//! the paper bounds the factor by which a player's variance shrinks in one
+//! update below by a small κ, and `update_ratings` uses κ = 0.0001, but the
+//! reference leaves the bound out, so that the updates it changes show up
+//! as differences. For such a player, the reference returns a smaller
+//! variance, or a NaN sigma if the variance became negative; their mu is
+//! the same.
+//!
+//! ```rust
+//! use bbt::reference::reference_update;
+//! use bbt::{Rater, Rating};
+//!
+//! let teams = vec![vec![Rating::new(25.0, 8.0)], vec![Rating::new(27.0, 5.0)]];
+//!
+//! let reference = reference_update(25.0 / 6.0, &teams, &[1, 2]);
+//! let optimized = Rater::default().update_ratings(teams, vec![1, 2]).unwrap();
+//!
+//! assert!((reference[0][0].mu() - optimized[0][0].mu()).abs() < 1e-12);
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use {Float, Rating};
+
+/// Rates a match like `Rater::new(beta).update_ratings(teams, ranks)`, but
+/// with a direct transcription of Algorithm 1. Lower ranks are better.
+///
+/// There is no validation and none of the special cases of
+/// `update_ratings`: every sigma and β have to be positive, `teams` and
+/// `ranks` have to be of the same length, and `mu / c` has to be small
+/// enough for `exp` not to overflow. Otherwise the ratings are NaN or the
+/// function panics.
+pub fn reference_update(beta: f64, teams: &[Vec<Rating>], ranks: &[usize]) -> Vec<Vec<Rating>> {
+    let k = teams.len();
+
+    // Step 1: the skill of team i is the sum of its players' skills,
+    //
+    //     mu_i = sum_j mu_ij,    sigma_i² = sum_j sigma_ij².
+    let mu: Vec<f64> = teams
+        .iter()
+        .map(|team| team.iter().map(|player| player.mu).sum())
+        .collect();
+    let sigma_sq: Vec<f64> = teams
+        .iter()
+        .map(|team| team.iter().map(|player| player.sigma * player.sigma).sum())
+        .collect();
+
+    // Step 2: every team i is compared with every other team q.
+    let mut omega = Vec::new();
+    let mut delta = Vec::new();
+
+    for i in 0..k {
+        let mut omega_i = 0.0;
+        let mut delta_i = 0.0;
+
+        for q in 0..k {
+            if q == i {
+                continue;
+            }
+
+            // c_iq = (sigma_i² + sigma_q² + 2 beta²)^(1/2)
+            let c_iq = Float::sqrt(sigma_sq[i] + sigma_sq[q] + 2.0 * beta * beta);
+
+            // p_iq = e^(mu_i / c_iq) / (e^(mu_i / c_iq) + e^(mu_q / c_iq))
+            let e_i = Float::exp(mu[i] / c_iq);
+            let e_q = Float::exp(mu[q] / c_iq);
+            let p_iq = e_i / (e_i + e_q);
+            let p_qi = e_q / (e_i + e_q);
+
+            // s = 1 if r(q) > r(i), 1/2 if r(q) = r(i), 0 if r(q) < r(i)
+            let s = if ranks[q] > ranks[i] {
+                1.0
+            } else if ranks[q] == ranks[i] {
+                0.5
+            } else {
+                0.0
+            };
+
+            // delta_q = (sigma_i² / c_iq) (s - p_iq)
+            let delta_q = (sigma_sq[i] / c_iq) * (s - p_iq);
+
+            // gamma_q = sigma_i / c_iq
+            let gamma_q = Float::sqrt(sigma_sq[i]) / c_iq;
+
+            // eta_q = gamma_q (sigma_i / c_iq)² p_iq p_qi
+            let eta_q = gamma_q * (sigma_sq[i] / (c_iq * c_iq)) * p_iq * p_qi;
+
+            // Omega_i = sum_q delta_q, Delta_i = sum_q eta_q
+            omega_i += delta_q;
+            delta_i += eta_q;
+        }
+
+        omega.push(omega_i);
+        delta.push(delta_i);
+    }
+
+    // Step 3: every player gets their share of the team's update,
+    //
+    //     mu_ij     = mu_ij + (sigma_ij² / sigma_i²) Omega_i,
+    //     sigma_ij² = sigma_ij² (1 - (sigma_ij² / sigma_i²) Delta_i).
+    //
+    // The paper takes the maximum of the factor and κ; this transcription
+    // does not, see the module documentation.
+    let mut updated = Vec::new();
+
+    for i in 0..k {
+        let mut team = Vec::new();
+
+        for player in &teams[i] {
+            let sigma_ij_sq = player.sigma * player.sigma;
+            let share = sigma_ij_sq / sigma_sq[i];
+
+            let new_mu = player.mu + share * omega[i];
+            let new_sigma_sq = sigma_ij_sq * (1.0 - share * delta[i]);
+
+            team.push(Rating {
+                mu: new_mu,
+                sigma: Float::sqrt(new_sigma_sq),
+                sigma_sq: new_sigma_sq,
+            });
+        }
+
+        updated.push(team);
+    }
+
+    updated
+}
@@ -0,0 +1,409 @@
+//! `bevy_reflect` implementations, enabled by the `bevy_reflect` feature.
+//! Ratings are reflected as structs with the read-only fields `mu` and
+//! `sigma`, and raters as opaque values. Both are implemented for `f32` and
+//! `f64`; `Outcome` derives `Reflect`.
+
+use std::fmt;
+
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::utility::NonGenericTypeInfoCell;
+use bevy_reflect::{
+    struct_partial_eq, ApplyError, FieldIter, FromReflect, FromType, GetTypeRegistration,
+    NamedField, OpaqueInfo, PartialReflect, Reflect, ReflectCloneError, ReflectDeserialize,
+    ReflectFromPtr, ReflectFromReflect, ReflectMut, ReflectOwned, ReflectRef, ReflectSerialize,
+    Struct, StructInfo, TypeInfo, TypePath, TypeRegistration, TypeRegistry, Typed,
+};
+
+use {Float, Rater, Rating, UpdateScratch};
+
+/// Returns the rating with the given mu and sigma if it is valid, like
+/// `Rating::try_new`, and an error for applying it otherwise.
+fn validated<F: Float>(mu: F, sigma: F, to_type: &str) -> Result<Rating<F>, ApplyError> {
+    Rating::try_new(mu, sigma).map_err(|_| ApplyError::MismatchedTypes {
+        from_type: format!("{{mu: {}, sigma: {}}}", mu, sigma).into(),
+        to_type: to_type.into(),
+    })
+}
+
+/// Returns a rater with the β-parameter and settings of `rater`. The policy
+/// cannot be cloned and is left out.
+fn reflected_copy<F: Float>(rater: &Rater<F>) -> Rater<F> {
+    Rater {
+        beta_sq: rater.beta_sq,
+        allow_fewer_than_two_teams: rater.allow_fewer_than_two_teams,
+        strict_ranks: rater.strict_ranks,
+        scratch: UpdateScratch::new(),
+        policy: None,
+        math: rater.math,
+        volatility: rater.volatility,
+        loss_protection: rater.loss_protection,
+        truncation: rater.truncation,
+    }
+}
+
+macro_rules! impl_reflect {
+    ($f:ty) => {
+        impl TypePath for Rating<$f> {
+            fn type_path() -> &'static str {
+                concat!("bbt::Rating<", stringify!($f), ">")
+            }
+
+            fn short_type_path() -> &'static str {
+                concat!("Rating<", stringify!($f), ">")
+            }
+
+            fn type_ident() -> Option<&'static str> {
+                Some("Rating")
+            }
+
+            fn crate_name() -> Option<&'static str> {
+                Some("bbt")
+            }
+
+            fn module_path() -> Option<&'static str> {
+                Some("bbt")
+            }
+        }
+
+        impl Typed for Rating<$f> {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+                CELL.get_or_set(|| {
+                    TypeInfo::Struct(StructInfo::new::<Self>(&[
+                        NamedField::new::<$f>("mu"),
+                        NamedField::new::<$f>("sigma"),
+                    ]))
+                })
+            }
+        }
+
+        impl GetTypeRegistration for Rating<$f> {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<Self>();
+                registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+                registration.insert::<ReflectFromReflect>(FromType::<Self>::from_type());
+                registration.insert::<ReflectDefault>(FromType::<Self>::from_type());
+                registration
+            }
+
+            fn register_type_dependencies(registry: &mut TypeRegistry) {
+                registry.register::<$f>();
+            }
+        }
+
+        impl Struct for Rating<$f> {
+            fn field(&self, name: &str) -> Option<&dyn PartialReflect> {
+                match name {
+                    "mu" => Some(&self.mu),
+                    "sigma" => Some(&self.sigma),
+                    _ => None,
+                }
+            }
+
+            /// The fields are read-only, since a sigma written in place would
+            /// not update the cached variance. Use `apply` instead.
+            fn field_mut(&mut self, _name: &str) -> Option<&mut dyn PartialReflect> {
+                None
+            }
+
+            fn field_at(&self, index: usize) -> Option<&dyn PartialReflect> {
+                self.name_at(index).and_then(|name| self.field(name))
+            }
+
+            fn field_at_mut(&mut self, _index: usize) -> Option<&mut dyn PartialReflect> {
+                None
+            }
+
+            fn name_at(&self, index: usize) -> Option<&str> {
+                match index {
+                    0 => Some("mu"),
+                    1 => Some("sigma"),
+                    _ => None,
+                }
+            }
+
+            fn field_len(&self) -> usize {
+                2
+            }
+
+            fn iter_fields(&self) -> FieldIter<'_> {
+                FieldIter::new(self)
+            }
+        }
+
+        impl PartialReflect for Rating<$f> {
+            fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+                Some(<Self as Typed>::type_info())
+            }
+
+            fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+                self
+            }
+
+            fn as_partial_reflect(&self) -> &dyn PartialReflect {
+                self
+            }
+
+            fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+                self
+            }
+
+            fn try_into_reflect(
+                self: Box<Self>,
+            ) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+                Ok(self)
+            }
+
+            fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+                Some(self)
+            }
+
+            fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+                Some(self)
+            }
+
+            /// Applies the fields `mu` and `sigma` of a struct, keeping the
+            /// current value of missing fields. If the result would not be a
+            /// valid rating (see `Rating::try_new`), the rating is left
+            /// unchanged and `ApplyError::MismatchedTypes` is returned.
+            fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+                let patch = value.reflect_ref().as_struct()?;
+
+                let field = |name, current| match patch.field(name) {
+                    Some(field) => {
+                        <$f>::from_reflect(field).ok_or_else(|| ApplyError::MismatchedTypes {
+                            from_type: field.reflect_type_path().into(),
+                            to_type: <$f>::type_path().into(),
+                        })
+                    }
+                    None => Ok(current),
+                };
+
+                let mu = field("mu", self.mu)?;
+                let sigma = field("sigma", self.sigma)?;
+
+                *self = validated(mu, sigma, Self::type_path())?;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Struct(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Struct(self)
+            }
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Struct(self)
+            }
+
+            fn reflect_clone(&self) -> Result<Box<dyn Reflect>, ReflectCloneError> {
+                Ok(Box::new(*self))
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn PartialReflect) -> Option<bool> {
+                struct_partial_eq(self, value)
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(self, f)
+            }
+        }
+
+        impl Reflect for Rating<$f> {
+            fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+                self
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+
+            fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+        }
+
+        /// Converts any struct with the fields `mu` and `sigma`, such as a
+        /// `DynamicStruct`, if they make a valid rating.
+        impl FromReflect for Rating<$f> {
+            fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
+                let fields = reflect.reflect_ref().as_struct().ok()?;
+                let mu = <$f>::from_reflect(fields.field("mu")?)?;
+                let sigma = <$f>::from_reflect(fields.field("sigma")?)?;
+
+                Rating::try_new(mu, sigma).ok()
+            }
+        }
+
+        impl TypePath for Rater<$f> {
+            fn type_path() -> &'static str {
+                concat!("bbt::Rater<", stringify!($f), ">")
+            }
+
+            fn short_type_path() -> &'static str {
+                concat!("Rater<", stringify!($f), ">")
+            }
+
+            fn type_ident() -> Option<&'static str> {
+                Some("Rater")
+            }
+
+            fn crate_name() -> Option<&'static str> {
+                Some("bbt")
+            }
+
+            fn module_path() -> Option<&'static str> {
+                Some("bbt")
+            }
+        }
+
+        impl Typed for Rater<$f> {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+                CELL.get_or_set(|| TypeInfo::Opaque(OpaqueInfo::new::<Self>()))
+            }
+        }
+
+        impl GetTypeRegistration for Rater<$f> {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<Self>();
+                registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+                registration.insert::<ReflectFromReflect>(FromType::<Self>::from_type());
+                registration.insert::<ReflectSerialize>(FromType::<Self>::from_type());
+                registration.insert::<ReflectDeserialize>(FromType::<Self>::from_type());
+                registration
+            }
+        }
+
+        impl PartialReflect for Rater<$f> {
+            fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+                Some(<Self as Typed>::type_info())
+            }
+
+            fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+                self
+            }
+
+            fn as_partial_reflect(&self) -> &dyn PartialReflect {
+                self
+            }
+
+            fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+                self
+            }
+
+            fn try_into_reflect(
+                self: Box<Self>,
+            ) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+                Ok(self)
+            }
+
+            fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+                Some(self)
+            }
+
+            fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+                Some(self)
+            }
+
+            /// Takes the β-parameter and the settings of another rater, but
+            /// keeps the policy.
+            fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+                let other = value.try_downcast_ref::<Self>().ok_or_else(|| {
+                    ApplyError::MismatchedTypes {
+                        from_type: value.reflect_type_path().into(),
+                        to_type: Self::type_path().into(),
+                    }
+                })?;
+
+                self.beta_sq = other.beta_sq;
+                self.allow_fewer_than_two_teams = other.allow_fewer_than_two_teams;
+                self.strict_ranks = other.strict_ranks;
+                self.math = other.math;
+                self.volatility = other.volatility;
+                self.loss_protection = other.loss_protection;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Opaque(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Opaque(self)
+            }
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Opaque(self)
+            }
+
+            /// Clones the rater without its policy.
+            fn reflect_clone(&self) -> Result<Box<dyn Reflect>, ReflectCloneError> {
+                Ok(Box::new(reflected_copy(self)))
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(self, f)
+            }
+        }
+
+        impl Reflect for Rater<$f> {
+            fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+                self
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+
+            fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+        }
+
+        impl FromReflect for Rater<$f> {
+            fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
+                reflect.try_downcast_ref::<Self>().map(reflected_copy)
+            }
+        }
+    };
+}
+
+impl_reflect!(f32);
+impl_reflect!(f64);
@@ -0,0 +1,764 @@
+//! Replaying game logs into tables of ratings, enabled by the `serde` and
+//! `std` features.
+//!
+//! A game log is in the JSON Lines format, with one game per line:
+//!
+//! ```text
+//! {"teams": [["alice", "bob"], ["carol"]], "ranks": [1, 2], "ts": 1534000000}
+//! ```
+//!
+//! `teams` lists the player ids of each team and `ranks` the rank of each
+//! team, as passed to `Rater::update_ratings`. Other fields, such as the
+//! timestamp `ts`, are ignored.
+//!
+//! ```rust
+//! let log = r#"
+//! {"teams": [["alice", "bob"], ["carol"]], "ranks": [1, 2]}
+//! {"teams": [["carol"], ["alice"]], "ranks": [1, 2]}
+//! "#;
+//!
+//! let rater = bbt::Rater::default();
+//! let ratings = bbt::replay::from_jsonl(&rater, log.as_bytes(), bbt::Rating::default()).unwrap();
+//!
+//! assert_eq!(ratings.len(), 3);
+//! assert!(ratings["bob"].mu() > ratings["alice"].mu());
+//! ```
+//!
+//! Histories that are already in memory, for example because they were
+//! loaded from a database, are replayed from `GameRecord`s with `replay`.
+//! `replay_with_options` can also make older games count less than recent
+//! ones.
+//!
+//! With the `arrow` feature, histories stored in a columnar format such as
+//! Parquet are replayed from Arrow record batches with
+//! `from_record_batches`, whose columns are named by a `GameSchema`.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::time::Duration;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{self, Value};
+
+#[cfg(feature = "arrow")]
+pub use record_batch::{from_record_batches, read_record_batches, GameSchema};
+use {BBTError, Outcome, Rater, Rating};
+
+/// ReplayError describes the ways in which replaying a game log or a list of
+/// `GameRecord`s can fail. Line numbers start at one, record indices at zero.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReplayError {
+    /// Reading the log failed.
+    Io(io::Error),
+
+    /// The line is not valid JSON.
+    Malformed { line: u64, message: String },
+
+    /// The line is valid JSON, but not a game, for example because it has no
+    /// `ranks` field or a player id is not a string.
+    InvalidRecord { line: u64, message: String },
+
+    /// The game was rejected by `Rater::update_ratings`.
+    Rating { line: u64, error: BBTError },
+
+    /// The record is not a valid game, because a player appears in it more
+    /// than once.
+    InvalidGame { index: usize, message: String },
+
+    /// The record was rejected by `Rater::update_ratings`, or its weight is
+    /// not between zero and one.
+    RejectedGame { index: usize, error: BBTError },
+
+    /// The record has no timestamp, but `ReplayOptions` requires one.
+    MissingTimestamp { index: usize },
+
+    /// The record batch has no column with the name given in the
+    /// `GameSchema`.
+    MissingColumn { batch: usize, name: String },
+
+    /// The column of the record batch does not have the type the
+    /// `GameSchema` expects.
+    InvalidColumn {
+        batch: usize,
+        name: String,
+        message: String,
+    },
+
+    /// The row of the record batch is not a valid game, for example because
+    /// a rank is null or a player appears in it more than once.
+    InvalidRow {
+        batch: usize,
+        row: usize,
+        message: String,
+    },
+
+    /// The row of the record batch was rejected by `Rater::update_ratings`,
+    /// or its weight is not between zero and one.
+    RejectedRow {
+        batch: usize,
+        row: usize,
+        error: BBTError,
+    },
+}
+
+impl ReplayError {
+    /// Returns the line on which the error occurred, if it belongs to one.
+    pub fn line(&self) -> Option<u64> {
+        match *self {
+            ReplayError::Malformed { line, .. }
+            | ReplayError::InvalidRecord { line, .. }
+            | ReplayError::Rating { line, .. } => Some(line),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the record that failed, if the error belongs to
+    /// one.
+    pub fn index(&self) -> Option<usize> {
+        match *self {
+            ReplayError::InvalidGame { index, .. }
+            | ReplayError::RejectedGame { index, .. }
+            | ReplayError::MissingTimestamp { index } => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the record batch that failed, if the error
+    /// belongs to one. Batches are counted from zero.
+    pub fn batch(&self) -> Option<usize> {
+        match *self {
+            ReplayError::MissingColumn { batch, .. }
+            | ReplayError::InvalidColumn { batch, .. }
+            | ReplayError::InvalidRow { batch, .. }
+            | ReplayError::RejectedRow { batch, .. } => Some(batch),
+            _ => None,
+        }
+    }
+
+    /// Returns the row of the record batch that failed, if the error belongs
+    /// to one. Rows are counted from zero in every batch.
+    pub fn row(&self) -> Option<usize> {
+        match *self {
+            ReplayError::InvalidRow { row, .. } | ReplayError::RejectedRow { row, .. } => Some(row),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::Io(ref error) => write!(f, "I/O error: {}", error),
+            ReplayError::Malformed { line, ref message } => {
+                write!(f, "Malformed JSON on line {}: {}", line, message)
+            }
+            ReplayError::InvalidRecord { line, ref message } => {
+                write!(f, "Invalid game on line {}: {}", line, message)
+            }
+            ReplayError::Rating { line, ref error } => {
+                write!(f, "The game on line {} was rejected: {}", line, error)
+            }
+            ReplayError::InvalidGame { index, ref message } => {
+                write!(f, "Invalid game in record {}: {}", index, message)
+            }
+            ReplayError::RejectedGame { index, ref error } => {
+                write!(f, "The game in record {} was rejected: {}", index, error)
+            }
+            ReplayError::MissingTimestamp { index } => {
+                write!(f, "Record {} has no timestamp", index)
+            }
+            ReplayError::MissingColumn { batch, ref name } => {
+                write!(f, "Record batch {} has no column `{}`", batch, name)
+            }
+            ReplayError::InvalidColumn {
+                batch,
+                ref name,
+                ref message,
+            } => write!(
+                f,
+                "Invalid column `{}` in record batch {}: {}",
+                name, batch, message
+            ),
+            ReplayError::InvalidRow {
+                batch,
+                row,
+                ref message,
+            } => write!(
+                f,
+                "Invalid game in row {} of record batch {}: {}",
+                row, batch, message
+            ),
+            ReplayError::RejectedRow {
+                batch,
+                row,
+                ref error,
+            } => write!(
+                f,
+                "The game in row {} of record batch {} was rejected: {}",
+                row, batch, error
+            ),
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ReplayError::Io(ref error) => Some(error),
+            ReplayError::Rating { ref error, .. }
+            | ReplayError::RejectedGame { ref error, .. }
+            | ReplayError::RejectedRow { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(error: io::Error) -> ReplayError {
+        ReplayError::Io(error)
+    }
+}
+
+/// Replays a game log, applying the games in order, and returns the final
+/// rating of every player in it. Players start with the `initial` rating
+/// when they first appear.
+///
+/// Blank lines are skipped, and both LF and CRLF line endings are accepted.
+/// Replaying stops at the first line that fails.
+pub fn from_jsonl<R: BufRead>(
+    rater: &Rater,
+    reader: R,
+    initial: Rating,
+) -> Result<HashMap<String, Rating>, ReplayError> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("replay_jsonl").entered();
+
+    let mut ratings = HashMap::new();
+
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = index as u64 + 1;
+
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let value: Value = serde_json::from_str(&text).map_err(|error| {
+            // Every line is parsed on its own, so the position that
+            // serde_json appends would always claim line 1.
+            let mut message = error.to_string();
+            if let Some(position) = message.rfind(" at line ") {
+                message.truncate(position);
+            }
+
+            ReplayError::Malformed {
+                line,
+                message: format!("{} at column {}", message, error.column()),
+            }
+        })?;
+
+        let record = parse_game(&value)
+            .and_then(|record| check_players(&record).map(|_| record))
+            .map_err(|message| ReplayError::InvalidRecord { line, message })?;
+
+        apply_record(rater, &mut ratings, &record, &initial, 1.0)
+            .map_err(|error| ReplayError::Rating { line, error })?;
+    }
+
+    Ok(ratings)
+}
+
+/// A player id, as used in game logs.
+pub type PlayerId = String;
+
+/// GameRecord is a single game of a history: the player ids of each team,
+/// and the rank of each team, as passed to `Rater::update_ratings`.
+///
+/// The optional `weight`, between zero and one, makes the game count less,
+/// as described for `Rater::update_ratings_weighted`. The optional
+/// `timestamp` is not used by `replay`, but kept for auditing and used by
+/// `replay_with_options` to weigh games by age. It is in seconds since the
+/// Unix epoch.
+///
+/// Records are serialized as a struct with the fields `teams`, `ranks`,
+/// `weight` and `timestamp`. The last two may be missing or `null`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    /// The player ids of each team.
+    pub teams: Vec<Vec<PlayerId>>,
+
+    /// The rank of each team, lower is better.
+    pub ranks: Vec<usize>,
+
+    /// The weight of the game. `None` is a full update.
+    pub weight: Option<f64>,
+
+    /// When the game was played.
+    pub timestamp: Option<i64>,
+}
+
+impl GameRecord {
+    /// Creates a record with the given teams and ranks, without a weight or
+    /// timestamp.
+    pub fn new(teams: Vec<Vec<PlayerId>>, ranks: Vec<usize>) -> GameRecord {
+        GameRecord {
+            teams,
+            ranks,
+            weight: None,
+            timestamp: None,
+        }
+    }
+}
+
+/// Replays a history of games, applying the records in order, and returns
+/// the final rating of every player in it. Players start with the `initial`
+/// rating when they first appear.
+///
+/// Every record is validated like `Rater::update_ratings` validates a match,
+/// and is additionally rejected if a player appears in it more than once.
+/// The first record that fails stops the replay, and the error names its
+/// index. The ratings computed up to that point are discarded; use
+/// `replay_into` to keep them.
+pub fn replay(
+    rater: &Rater,
+    records: &[GameRecord],
+    initial: Rating,
+) -> Result<HashMap<PlayerId, Rating>, ReplayError> {
+    let mut ratings = HashMap::new();
+    replay_into(rater, records, initial, &mut ratings)?;
+
+    Ok(ratings)
+}
+
+/// Replays a history of games like `replay`, updating the ratings in
+/// `ratings`, which may already contain players.
+///
+/// If a record fails, `ratings` holds the ratings after every earlier record
+/// and is not changed by the failing record or any later one, so a history
+/// can be repaired and replayed from the failing record on.
+pub fn replay_into(
+    rater: &Rater,
+    records: &[GameRecord],
+    initial: Rating,
+    ratings: &mut HashMap<PlayerId, Rating>,
+) -> Result<(), ReplayError> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("replay", records = records.len()).entered();
+
+    for (index, record) in records.iter().enumerate() {
+        check_players(record).map_err(|message| ReplayError::InvalidGame { index, message })?;
+
+        apply_record(rater, ratings, record, &initial, 1.0)
+            .map_err(|error| ReplayError::RejectedGame { index, error })?;
+    }
+
+    Ok(())
+}
+
+/// ReplayOptions changes how `replay_with_options` weighs the records of a
+/// history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayOptions {
+    /// The age after which a game counts half as much. A game's weight is
+    /// `0.5^(age / half_life)`, where the age is the time between the game
+    /// and the newest record of the history, so the newest games always
+    /// count fully.
+    ///
+    /// A half-life of zero only counts the newest games, and
+    /// `Duration::MAX`, the default, counts all games fully.
+    pub half_life: Duration,
+
+    /// Whether records without a timestamp are allowed. They are weighed
+    /// like the newest games. By default, they are an error, because the
+    /// age of the game is unknown.
+    pub allow_untimed: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> ReplayOptions {
+        ReplayOptions {
+            half_life: Duration::MAX,
+            allow_untimed: false,
+        }
+    }
+}
+
+/// Replays a history of games like `replay`, but weighs every record by its
+/// age as described by `options`. The weight is applied as a weighted
+/// update, like the `weight` of a record, and multiplies it if the record
+/// has one.
+///
+/// The whole history is checked for missing timestamps before any record
+/// is applied.
+pub fn replay_with_options(
+    rater: &Rater,
+    records: &[GameRecord],
+    initial: Rating,
+    options: &ReplayOptions,
+) -> Result<HashMap<PlayerId, Rating>, ReplayError> {
+    let mut ratings = HashMap::new();
+    replay_into_with_options(rater, records, initial, options, &mut ratings)?;
+
+    Ok(ratings)
+}
+
+/// Replays a history of games like `replay_with_options`, updating the
+/// ratings in `ratings` like `replay_into`.
+pub fn replay_into_with_options(
+    rater: &Rater,
+    records: &[GameRecord],
+    initial: Rating,
+    options: &ReplayOptions,
+    ratings: &mut HashMap<PlayerId, Rating>,
+) -> Result<(), ReplayError> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("replay", records = records.len()).entered();
+
+    if !options.allow_untimed {
+        if let Some(index) = records.iter().position(|record| record.timestamp.is_none()) {
+            return Err(ReplayError::MissingTimestamp { index });
+        }
+    }
+
+    let newest = records.iter().filter_map(|record| record.timestamp).max();
+    let half_life = options.half_life.as_secs_f64();
+
+    for (index, record) in records.iter().enumerate() {
+        check_players(record).map_err(|message| ReplayError::InvalidGame { index, message })?;
+
+        let age = match (newest, record.timestamp) {
+            (Some(newest), Some(timestamp)) => newest.saturating_sub(timestamp) as f64,
+            _ => 0.0,
+        };
+
+        let decay = if options.half_life == Duration::MAX || age == 0.0 {
+            1.0
+        } else if half_life == 0.0 {
+            0.0
+        } else {
+            0.5f64.powf(age / half_life)
+        };
+
+        apply_record(rater, ratings, record, &initial, decay)
+            .map_err(|error| ReplayError::RejectedGame { index, error })?;
+    }
+
+    Ok(())
+}
+
+/// Replays a history of games like `replay` and returns the games of one
+/// player, as the rating of each opponent before the game and the outcome
+/// from the player's perspective, for `stats::strength_of_schedule`.
+///
+/// In a game with more than two teams or more than one player per team,
+/// every player of every other team is an opponent, and the outcome against
+/// them is decided by the ranks of the teams.
+///
+/// Returns the errors of `replay`.
+///
+/// ```rust
+/// use bbt::replay::{self, GameRecord};
+/// use bbt::{stats, Outcome, Rater, Rating};
+///
+/// let games = vec![
+///     GameRecord::new(vec![vec!["alice".to_string()], vec!["bob".to_string()]], vec![1, 2]),
+///     GameRecord::new(vec![vec!["bob".to_string()], vec!["carol".to_string()]], vec![1, 1]),
+/// ];
+///
+/// let rater = Rater::default();
+/// let schedule = replay::schedule(&rater, &games, "bob", Rating::default()).unwrap();
+/// assert_eq!(schedule[0], (Rating::default(), Outcome::Loss));
+/// assert_eq!(schedule[1], (Rating::default(), Outcome::Draw));
+///
+/// let report = stats::strength_of_schedule(&rater, &schedule).unwrap();
+/// assert_eq!(report.mean_mu, 25.0);
+/// ```
+pub fn schedule(
+    rater: &Rater,
+    records: &[GameRecord],
+    player: &str,
+    initial: Rating,
+) -> Result<Vec<(Rating, Outcome)>, ReplayError> {
+    let mut ratings = HashMap::new();
+    let mut games = Vec::new();
+
+    for (index, record) in records.iter().enumerate() {
+        check_players(record).map_err(|message| ReplayError::InvalidGame { index, message })?;
+
+        let team = record
+            .teams
+            .iter()
+            .position(|team| team.iter().any(|id| id == player));
+
+        if let Some(team) = team {
+            for (other, opponents) in record.teams.iter().enumerate() {
+                if other == team {
+                    continue;
+                }
+
+                // Mismatched ranks are rejected by the rater below.
+                let outcome = match (record.ranks.get(team), record.ranks.get(other)) {
+                    (Some(rank), Some(other_rank)) if rank < other_rank => Outcome::Win,
+                    (Some(rank), Some(other_rank)) if rank > other_rank => Outcome::Loss,
+                    _ => Outcome::Draw,
+                };
+
+                for opponent in opponents {
+                    let rating = *ratings.get(opponent).unwrap_or(&initial);
+                    games.push((rating, outcome));
+                }
+            }
+        }
+
+        apply_record(rater, &mut ratings, record, &initial, 1.0)
+            .map_err(|error| ReplayError::RejectedGame { index, error })?;
+    }
+
+    Ok(games)
+}
+
+/// Extracts a game from a line of a game log, or describes why the value is
+/// not a game.
+fn parse_game(value: &Value) -> Result<GameRecord, String> {
+    let game = value
+        .as_object()
+        .ok_or_else(|| "expected an object".to_string())?;
+
+    let field = |name: &str| {
+        game.get(name)
+            .and_then(Value::as_array)
+            .ok_or_else(|| format!("expected `{}` to be an array", name))
+    };
+
+    let teams = field("teams")?
+        .iter()
+        .map(|team| {
+            team.as_array()
+                .ok_or_else(|| "expected every team to be an array of player ids".to_string())?
+                .iter()
+                .map(|player| {
+                    player
+                        .as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| format!("expected a player id, found {}", player))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ranks = field("ranks")?
+        .iter()
+        .map(|rank| {
+            rank.as_u64()
+                .map(|rank| rank as usize)
+                .ok_or_else(|| format!("expected a rank, found {}", rank))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(GameRecord::new(teams, ranks))
+}
+
+/// Checks that no player appears in a game more than once.
+pub(crate) fn check_players(record: &GameRecord) -> Result<(), String> {
+    let mut seen = Vec::new();
+    for player in record.teams.iter().flatten() {
+        if seen.contains(&player) {
+            return Err(format!("player `{}` appears more than once", player));
+        }
+        seen.push(player);
+    }
+
+    Ok(())
+}
+
+/// Updates the ratings of the players of a game, with the weight of the
+/// record multiplied by `decay`. The table is only changed if the update
+/// succeeds.
+pub(crate) fn apply_record(
+    rater: &Rater,
+    ratings: &mut HashMap<PlayerId, Rating>,
+    record: &GameRecord,
+    initial: &Rating,
+    decay: f64,
+) -> Result<(), BBTError> {
+    let current = record
+        .teams
+        .iter()
+        .map(|team| {
+            team.iter()
+                .map(|player| *ratings.get(player).unwrap_or(initial))
+                .collect()
+        })
+        .collect();
+
+    let ranks = record.ranks.clone();
+    let weight = match record.weight {
+        // The weight is checked before it is decayed, so that a weight that
+        // is too large is not hidden by a small decay.
+        Some(weight) if !(0.0..=1.0).contains(&weight) => {
+            return Err(BBTError::InvalidParameter { name: "weight" })
+        }
+        Some(weight) => Some(weight * decay),
+        None if decay == 1.0 => None,
+        None => Some(decay),
+    };
+
+    let updated = match weight {
+        Some(weight) => rater.update_ratings_weighted(current, ranks, weight)?,
+        None => rater.update_ratings(current, ranks)?,
+    };
+
+    for (team, new_ratings) in record.teams.iter().zip(updated) {
+        for (player, rating) in team.iter().zip(new_ratings) {
+            ratings.insert(player.clone(), rating);
+        }
+    }
+
+    Ok(())
+}
+
+impl Serialize for GameRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GameRecord", 4)?;
+        state.serialize_field("teams", &self.teams)?;
+        state.serialize_field("ranks", &self.ranks)?;
+        state.serialize_field("weight", &self.weight)?;
+        state.serialize_field("timestamp", &self.timestamp)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GameRecord {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Teams,
+            Ranks,
+            Weight,
+            Timestamp,
+            Ignored,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("a field of a game record")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        Ok(match value {
+                            "teams" => Field::Teams,
+                            "ranks" => Field::Ranks,
+                            "weight" => Field::Weight,
+                            "timestamp" => Field::Timestamp,
+                            _ => Field::Ignored,
+                        })
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct RecordVisitor;
+
+        impl<'de> Visitor<'de> for RecordVisitor {
+            type Value = GameRecord;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct GameRecord")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<GameRecord, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let teams = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let ranks = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                Ok(GameRecord {
+                    teams,
+                    ranks,
+                    weight: seq.next_element()?.unwrap_or(None),
+                    timestamp: seq.next_element()?.unwrap_or(None),
+                })
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<GameRecord, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut teams = None;
+                let mut ranks = None;
+                let mut weight = None;
+                let mut timestamp = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Teams => {
+                            if teams.is_some() {
+                                return Err(de::Error::duplicate_field("teams"));
+                            }
+                            teams = Some(map.next_value()?);
+                        }
+                        Field::Ranks => {
+                            if ranks.is_some() {
+                                return Err(de::Error::duplicate_field("ranks"));
+                            }
+                            ranks = Some(map.next_value()?);
+                        }
+                        Field::Weight => {
+                            if weight.is_some() {
+                                return Err(de::Error::duplicate_field("weight"));
+                            }
+                            weight = Some(map.next_value()?);
+                        }
+                        Field::Timestamp => {
+                            if timestamp.is_some() {
+                                return Err(de::Error::duplicate_field("timestamp"));
+                            }
+                            timestamp = Some(map.next_value()?);
+                        }
+                        Field::Ignored => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                Ok(GameRecord {
+                    teams: teams.ok_or_else(|| de::Error::missing_field("teams"))?,
+                    ranks: ranks.ok_or_else(|| de::Error::missing_field("ranks"))?,
+                    weight: weight.unwrap_or(None),
+                    timestamp: timestamp.unwrap_or(None),
+                })
+            }
+        }
+
+        const FIELDS: &[&str] = &["teams", "ranks", "weight", "timestamp"];
+        deserializer.deserialize_struct("GameRecord", FIELDS, RecordVisitor)
+    }
+}
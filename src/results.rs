@@ -0,0 +1,183 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use {BBTError, Float, Rater, Rating};
+
+/// TeamResult is how a team finished a race, for
+/// `Rater::update_ratings_results`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TeamResult {
+    /// The team finished with the given rank. Lower ranks are better.
+    Ranked(usize),
+
+    /// The team did not finish. All teams that did not finish share a rank
+    /// behind the last team that did.
+    Dnf,
+
+    /// The team was disqualified. Its rating is not updated, and the other
+    /// teams are rated as if it had not entered.
+    Dsq,
+}
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings of a race like `update_ratings`,
+    /// where teams may not have finished or may have been disqualified:
+    ///
+    /// * Teams that did not finish are tied behind the worst finisher.
+    /// * Disqualified teams are returned unchanged and left out of all
+    ///   comparisons, so the other teams get the same ratings as in a race
+    ///   without them.
+    ///
+    /// Only the order of the `Ranked` ranks matters. They are turned into a
+    /// standard competition ranking (1, 2, 2, 4) after the disqualified
+    /// teams are removed, so the gaps left by those teams are not rejected
+    /// by `Rater::strict_ranks`.
+    ///
+    /// Returns the errors of `update_ratings`, with the indices of teams
+    /// referring to `teams`. Disqualified teams are not validated, and
+    /// `BBTError::NotEnoughTeams` counts only the other teams.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating, TeamResult};
+    ///
+    /// let teams = vec![vec![Rating::default()]; 4];
+    /// let results = [TeamResult::Dnf, TeamResult::Ranked(1), TeamResult::Dsq, TeamResult::Ranked(2)];
+    ///
+    /// let new = Rater::default().update_ratings_results(teams, &results).unwrap();
+    ///
+    /// assert!(new[1][0].mu() > new[3][0].mu() && new[3][0].mu() > new[0][0].mu());
+    /// assert_eq!(new[2][0], Rating::default());
+    /// ```
+    pub fn update_ratings_results(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        results: &[TeamResult],
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        if teams.len() != results.len() {
+            return Err(BBTError::MismatchedLengths {
+                teams: teams.len(),
+                ranks: results.len(),
+            });
+        }
+
+        let mut finishers: Vec<usize> = results
+            .iter()
+            .filter_map(|result| match *result {
+                TeamResult::Ranked(rank) => Some(rank),
+                _ => None,
+            })
+            .collect();
+        finishers.sort_unstable();
+
+        let ranks = results
+            .iter()
+            .map(|result| match *result {
+                TeamResult::Ranked(rank) => {
+                    Some(1 + finishers.partition_point(|&other| other < rank))
+                }
+                TeamResult::Dnf => Some(finishers.len() + 1),
+                TeamResult::Dsq => None,
+            })
+            .collect();
+
+        self.update_ranked_teams(teams, ranks)
+    }
+
+    /// This method updates the ratings of the teams of a fixed roster that
+    /// took part in a match, like `update_ratings` for just those teams.
+    /// Teams with a rank of `None` did not take part: they are returned
+    /// unchanged and left out of all comparisons. If no team took part,
+    /// nothing happens.
+    ///
+    /// Returns the errors of `update_ratings` for the teams that took part,
+    /// with the indices of teams referring to `teams`.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let roster = vec![vec![Rating::default()]; 20];
+    /// let mut ranks = vec![None; 20];
+    /// ranks[4] = Some(1);
+    /// ranks[17] = Some(2);
+    ///
+    /// let new = Rater::default().update_ratings_partial_roster(roster, &ranks).unwrap();
+    ///
+    /// assert!(new[4][0].mu() > 25.0 && new[17][0].mu() < 25.0);
+    /// assert_eq!(new[0][0], Rating::default());
+    /// ```
+    pub fn update_ratings_partial_roster(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: &[Option<usize>],
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        if teams.len() != ranks.len() {
+            return Err(BBTError::MismatchedLengths {
+                teams: teams.len(),
+                ranks: ranks.len(),
+            });
+        }
+
+        if ranks.iter().all(Option::is_none) {
+            return Ok(teams);
+        }
+
+        self.update_ranked_teams(teams, ranks.to_vec())
+    }
+
+    /// Updates the teams that have a rank like `update_ratings`, and returns
+    /// the others unchanged. `teams` and `ranks` are of the same length.
+    fn update_ranked_teams(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: Vec<Option<usize>>,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let mut updated: Vec<Option<Vec<Rating<F>>>> = Vec::with_capacity(teams.len());
+        let mut entered = Vec::new();
+        let mut entered_teams = Vec::new();
+        let mut entered_ranks = Vec::new();
+
+        for (team_idx, (team, rank)) in teams.into_iter().zip(ranks).enumerate() {
+            match rank {
+                Some(rank) => {
+                    updated.push(None);
+                    entered.push(team_idx);
+                    entered_teams.push(team);
+                    entered_ranks.push(rank);
+                }
+                None => updated.push(Some(team)),
+            }
+        }
+
+        let entered_teams = self
+            .update_ratings(entered_teams, entered_ranks)
+            .map_err(|error| reindex(error, &entered))?;
+
+        for (team_idx, team) in entered.into_iter().zip(entered_teams) {
+            updated[team_idx] = Some(team);
+        }
+
+        Ok(updated.into_iter().flatten().collect())
+    }
+}
+
+/// Maps the team indices of an error of the update of the teams that took
+/// part back to the indices of all teams.
+fn reindex(error: BBTError, entered: &[usize]) -> BBTError {
+    match error {
+        BBTError::EmptyTeam { index } => BBTError::EmptyTeam {
+            index: entered[index],
+        },
+        BBTError::InvalidRating { team, player } => BBTError::InvalidRating {
+            team: entered[team],
+            player,
+        },
+        BBTError::TeamOverflow { index } => BBTError::TeamOverflow {
+            index: entered[index],
+        },
+        BBTError::NumericalError { team, player } => BBTError::NumericalError {
+            team: entered[team],
+            player,
+        },
+        error => error,
+    }
+}
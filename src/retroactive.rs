@@ -0,0 +1,146 @@
+#[cfg(feature = "tracing")]
+use trace_update;
+#[cfg(feature = "validate")]
+use validate;
+use {inflated, pair_terms, sum_of_one, updated_rating, Float, Outcome, Rater, Rating};
+
+impl<F: Float> Rater<F> {
+    /// This method rates a duel that finished long after it started, such as
+    /// a correspondence game, from the ratings the players had when it
+    /// started. Rating the result against the current ratings would count
+    /// the games played in the meantime twice, as they already moved the
+    /// current ratings towards what the result will show.
+    ///
+    /// The update is computed like `duel` from `p1_at_start` and
+    /// `p2_at_start`, and applied to `p1_now` and `p2_now`: mu changes by
+    /// the same amount as it would have at the start, and the variance is
+    /// multiplied by the same factor, which is clamped like in `duel` so
+    /// that sigma stays positive. The outcome is from `p1`'s perspective.
+    ///
+    /// This is an approximation. It treats the games played in the
+    /// meantime as independent of this one, and the variance factor was
+    /// derived for the variance at the start, so the current ratings are
+    /// moved as far as if they were as uncertain as they were then. The
+    /// longer the game and the more the ratings moved, the coarser it is;
+    /// when the ratings did not change, the result is exactly that of
+    /// `duel`. With `Rater::with_volatility`, whether the result is
+    /// surprising is decided from the ratings at the start, and the current
+    /// ratings are inflated if it is. Truncation and policies are applied to
+    /// the current ratings, as in `duel`.
+    ///
+    /// If any of the ratings is invalid (see `Rating::is_valid`), the current
+    /// ratings are left unchanged. Fully certain players are not updated.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let (p1_at_start, p2_at_start) = (Rating::new(25.0, 4.0), Rating::new(27.0, 4.0));
+    ///
+    /// // Both players won other games before this one finished.
+    /// let (mut p1, mut p2) = (Rating::new(28.0, 3.5), Rating::new(31.0, 3.5));
+    /// rater.duel_retroactive(&mut p1, &p1_at_start, &mut p2, &p2_at_start, Outcome::Win);
+    ///
+    /// let (p1_then, p2_then) = rater.duel(p1_at_start, p2_at_start, Outcome::Win);
+    /// assert!((p1.mu() - 28.0 - (p1_then.mu() - 25.0)).abs() < 1e-12);
+    /// assert!((p2.mu() - 31.0 - (p2_then.mu() - 27.0)).abs() < 1e-12);
+    /// ```
+    pub fn duel_retroactive(
+        &self,
+        p1_now: &mut Rating<F>,
+        p1_at_start: &Rating<F>,
+        p2_now: &mut Rating<F>,
+        p2_at_start: &Rating<F>,
+        outcome: Outcome,
+    ) {
+        let all_valid = p1_now.is_valid()
+            && p1_at_start.is_valid()
+            && p2_now.is_valid()
+            && p2_at_start.is_valid();
+
+        if !all_valid {
+            return;
+        }
+
+        let (mut start1, mut start2) = (*p1_at_start, *p2_at_start);
+        let (mut now1, mut now2) = (*p1_now, *p2_now);
+
+        if let Some(ref volatility) = self.volatility {
+            let (mu1, mu2) = (sum_of_one(start1.mu), sum_of_one(start2.mu));
+            let sigma_sq_sum = sum_of_one(start1.sigma_sq) + sum_of_one(start2.sigma_sq);
+
+            let surprised = match outcome {
+                Outcome::Win => self.is_surprising(volatility, mu1 - mu2, sigma_sq_sum),
+                Outcome::Loss => self.is_surprising(volatility, mu2 - mu1, sigma_sq_sum),
+                Outcome::Draw => false,
+            };
+
+            if surprised {
+                start1 = inflated(&start1, volatility.inflation_sq);
+                start2 = inflated(&start2, volatility.inflation_sq);
+                now1 = inflated(&now1, volatility.inflation_sq);
+                now2 = inflated(&now2, volatility.inflation_sq);
+            }
+        }
+
+        let s1 = match outcome {
+            Outcome::Win => F::ONE,
+            Outcome::Loss => F::ZERO,
+            Outcome::Draw => F::from_f64(0.5),
+        };
+
+        // The same operations as in `duel`, on the ratings at the start.
+        let mu1 = sum_of_one(start1.mu);
+        let mu2 = sum_of_one(start2.mu);
+        let sigma_sq1 = sum_of_one(start1.sigma_sq);
+        let sigma_sq2 = sum_of_one(start2.sigma_sq);
+
+        let c = self.sqrt(sigma_sq1 + sigma_sq2 + F::from_f64(2.0) * self.beta_sq);
+
+        if c == F::ZERO {
+            return;
+        }
+
+        let p12 = self.logistic(mu1 - mu2, c);
+        let p21 = self.logistic(mu2 - mu1, c);
+
+        let (omega1, delta1) = pair_terms(sigma_sq1, self.sqrt(sigma_sq1), c, p12, p21, s1);
+        let (omega2, delta2) =
+            pair_terms(sigma_sq2, self.sqrt(sigma_sq2), c, p21, p12, F::ONE - s1);
+        let (omega1, delta1) = (sum_of_one(omega1), sum_of_one(delta1));
+        let (omega2, delta2) = (sum_of_one(omega2), sum_of_one(delta2));
+
+        #[cfg(feature = "validate")]
+        let values = validate::TeamValues {
+            mu: &[mu1, mu2],
+            sigma_sq: &[sigma_sq1, sigma_sq2],
+            omega: &[omega1, omega2],
+            delta: &[delta1, delta2],
+        };
+        #[cfg(feature = "validate")]
+        validate::assert_teams(&values);
+
+        let write_back = self.write_back();
+
+        // A player who is alone on their team takes all of the team's
+        // update, so passing the current variance as the team's applies the
+        // change of mu and the variance factor of the start unscaled.
+        if sigma_sq1 != F::ZERO && now1.sigma_sq != F::ZERO {
+            let (rating, _clamped) = updated_rating(&now1, now1.sigma_sq, omega1, delta1);
+            #[cfg(feature = "tracing")]
+            trace_update(0, 0, &rating, _clamped);
+            #[cfg(feature = "validate")]
+            validate::assert_update(0, 0, &now1, &rating, &values);
+            *p1_now = write_back.apply(&now1, rating);
+        }
+
+        if sigma_sq2 != F::ZERO && now2.sigma_sq != F::ZERO {
+            let (rating, _clamped) = updated_rating(&now2, now2.sigma_sq, omega2, delta2);
+            #[cfg(feature = "tracing")]
+            trace_update(1, 0, &rating, _clamped);
+            #[cfg(feature = "validate")]
+            validate::assert_update(1, 0, &now2, &rating, &values);
+            *p2_now = write_back.apply(&now2, rating);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "strict-math")]
+use check_team_updates;
+use {
+    apply_team_updates, compute_team_totals, inflated, BBTError, Float, Rater, Rating,
+    UpdateScratch, Workspace,
+};
+
+/// RoyaleSession rates a free-for-all match whose placements become known
+/// one elimination at a time, such as a battle royale, so that a player's
+/// rating change can be shown as soon as they are out.
+///
+/// Every player is their own team. A player eliminated while `n` players are
+/// still in the match finishes `n`th, so the first player out of a match of
+/// 100 finishes 100th. `finalize` rates the match once a single player is
+/// left, who finishes first.
+///
+/// `eliminate` returns the update of the eliminated player. It is computed
+/// with the rater on the whole match, with the eliminated players at their
+/// placements and the players still in the match tied for first. Since the
+/// eliminated player finishes behind all of the latter and ahead of everyone
+/// eliminated before, all of their pairwise results are already known, and
+/// the order of the players still in the match does not enter their update.
+/// Their provisional update is therefore exactly the one `finalize` writes
+/// back, bit for bit. Only the eliminated player's comparisons with the
+/// others are computed, so an elimination takes time linear in the number
+/// of players. The exception is a rater with `Rater::with_volatility`:
+/// whether an opponent still in the match is surprised, and their sigma
+/// inflated, depends on results that are not known yet, so the provisional
+/// update of a player can differ from the final one after surprising
+/// results.
+///
+/// ```rust
+/// use bbt::{Rater, Rating, RoyaleSession};
+///
+/// let rater = Rater::default();
+/// let mut ratings = vec![Rating::default(); 4];
+///
+/// let mut session = RoyaleSession::new(&rater, &ratings);
+/// let first_out = session.eliminate(2).unwrap();
+/// assert_eq!(first_out.placement, 4);
+/// assert!(first_out.mu_delta < 0.0);
+///
+/// session.eliminate(0).unwrap();
+/// session.eliminate(3).unwrap();
+/// session.finalize(&mut ratings).unwrap();
+///
+/// assert_eq!(ratings[2], first_out.after);
+/// assert!(ratings[1].mu() > 25.0);
+/// ```
+#[derive(Debug)]
+pub struct RoyaleSession<'a, F: Float = f64> {
+    rater: &'a Rater<F>,
+    players: Vec<[Rating<F>; 1]>,
+    placements: Vec<Option<usize>>,
+    surprised: Vec<bool>,
+    remaining: usize,
+    scratch: UpdateScratch<F>,
+}
+
+/// PartialDelta is the update of a player eliminated from a
+/// `RoyaleSession`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartialDelta<F: Float = f64> {
+    /// The index of the player.
+    pub player: usize,
+
+    /// The placement of the player, where the winner is first.
+    pub placement: usize,
+
+    /// The rating of the player before the match.
+    pub before: Rating<F>,
+
+    /// The rating of the player after the match.
+    pub after: Rating<F>,
+
+    /// The change of mu, `after.mu() - before.mu()`.
+    pub mu_delta: F,
+}
+
+/// RoyaleError describes why a player could not be eliminated from a
+/// `RoyaleSession`, or why it could not be finalized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RoyaleError {
+    /// There is no player with the index `player` in a session of `players`
+    /// players.
+    UnknownPlayer { player: usize, players: usize },
+
+    /// The player has already been eliminated.
+    AlreadyEliminated { player: usize },
+
+    /// The player is the only one left, and has won the match.
+    Winner { player: usize },
+
+    /// `finalize` was called while `remaining` players are still in the
+    /// match.
+    Undecided { remaining: usize },
+
+    /// The ratings given to `finalize` are not as many as the players.
+    MismatchedLength { players: usize, ratings: usize },
+
+    /// The update was rejected by the rater.
+    Rating(BBTError),
+}
+
+impl fmt::Display for RoyaleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RoyaleError::UnknownPlayer { player, players } => write!(
+                f,
+                "There is no player {} in a match of {} players",
+                player, players
+            ),
+            RoyaleError::AlreadyEliminated { player } => {
+                write!(f, "Player {} has already been eliminated", player)
+            }
+            RoyaleError::Winner { player } => {
+                write!(
+                    f,
+                    "Player {} is the last one left and cannot be eliminated",
+                    player
+                )
+            }
+            RoyaleError::Undecided { remaining } => write!(
+                f,
+                "The match is not decided yet ({} players left)",
+                remaining
+            ),
+            RoyaleError::MismatchedLength { players, ratings } => write!(
+                f,
+                "Got {} ratings for a match of {} players",
+                ratings, players
+            ),
+            RoyaleError::Rating(ref error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for RoyaleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            RoyaleError::Rating(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<BBTError> for RoyaleError {
+    fn from(error: BBTError) -> RoyaleError {
+        RoyaleError::Rating(error)
+    }
+}
+
+impl<'a, F: Float> RoyaleSession<'a, F> {
+    /// Starts a match between players with the given ratings, none of whom
+    /// has been eliminated yet. The ratings are checked by the rater when
+    /// the first update is computed.
+    pub fn new(rater: &'a Rater<F>, players: &[Rating<F>]) -> RoyaleSession<'a, F> {
+        RoyaleSession {
+            rater,
+            players: players.iter().map(|&player| [player]).collect(),
+            placements: vec![None; players.len()],
+            surprised: vec![false; players.len()],
+            remaining: players.len(),
+            scratch: UpdateScratch::with_capacity(players.len()),
+        }
+    }
+
+    /// Returns the number of players still in the match.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns the placement of the player, or `None` if they are still in
+    /// the match or there is no such player.
+    pub fn placement(&self, player: usize) -> Option<usize> {
+        self.placements.get(player).cloned().flatten()
+    }
+
+    /// Eliminates the player, fixing their placement to the number of
+    /// players still in the match before the elimination, and returns their
+    /// update. See `RoyaleSession` for how it relates to the final update.
+    ///
+    /// Returns `RoyaleError::UnknownPlayer`, `RoyaleError::AlreadyEliminated`
+    /// or `RoyaleError::Winner` if the player cannot be eliminated, and
+    /// `RoyaleError::Rating` with the errors of `Rater::update_ratings` if
+    /// the ratings cannot be updated. The session is unchanged on errors.
+    pub fn eliminate(&mut self, player: usize) -> Result<PartialDelta<F>, RoyaleError> {
+        match self.placements.get(player) {
+            None => {
+                return Err(RoyaleError::UnknownPlayer {
+                    player,
+                    players: self.players.len(),
+                })
+            }
+            Some(Some(_)) => return Err(RoyaleError::AlreadyEliminated { player }),
+            Some(None) if self.remaining == 1 => return Err(RoyaleError::Winner { player }),
+            Some(None) => {}
+        }
+
+        let placement = self.remaining;
+        let mut ranks: Vec<usize> = self
+            .placements
+            .iter()
+            .map(|placement| placement.unwrap_or(1))
+            .collect();
+        ranks[player] = placement;
+
+        let n_players = self.players.len();
+        let rater = self.rater;
+        let mut work = self.scratch.workspace(n_players);
+        rater.validate_into(&self.players, &ranks, &mut work)?;
+
+        // The players still in the match are tied, and the results between
+        // eliminated players were checked when they were eliminated, so only
+        // the results against the eliminated player can surprise anyone new.
+        let mut surprised = None;
+        if let Some(ref volatility) = rater.volatility {
+            let mut flags = self.surprised.clone();
+
+            for other in 0..n_players {
+                if self.placements[other].is_some() || other == player {
+                    continue;
+                }
+
+                if rater.is_surprising(
+                    volatility,
+                    work.team_mu[other] - work.team_mu[player],
+                    work.team_sigma_sq[other] + work.team_sigma_sq[player],
+                ) {
+                    flags[other] = true;
+                    flags[player] = true;
+                }
+            }
+
+            surprised = Some(flags);
+        }
+
+        let current = match surprised {
+            Some(ref flags) if flags.iter().any(|&surprised| surprised) => {
+                let volatility = rater.volatility.as_ref().unwrap();
+                let current: Vec<[Rating<F>; 1]> = self
+                    .players
+                    .iter()
+                    .zip(flags)
+                    .map(|(&[rating], &surprised)| {
+                        if surprised {
+                            [inflated(&rating, volatility.inflation_sq)]
+                        } else {
+                            [rating]
+                        }
+                    })
+                    .collect();
+
+                compute_team_totals(&current, &mut work);
+                current[player]
+            }
+            _ => self.players[player],
+        };
+
+        let (omega, delta) = rater.team_omega_delta_against_all(
+            player,
+            work.team_mu,
+            work.team_sigma_sq,
+            &ranks,
+            (work.omega_terms, work.delta_terms),
+        );
+
+        // The update of the eliminated player alone, as a match of one team;
+        // errors are reported with their index in the whole match.
+        let mut arrays = [[F::ZERO; 1]; 6];
+        let single = Workspace::from_arrays(&mut arrays, 1);
+        single.team_mu[0] = work.team_mu[player];
+        single.team_sigma_sq[0] = work.team_sigma_sq[player];
+        single.team_omega[0] = omega;
+        single.team_delta[0] = delta;
+
+        let mut updated = [current];
+
+        #[cfg(feature = "strict-math")]
+        check_team_updates(&updated, &single).map_err(|error| match error {
+            BBTError::NumericalError { .. } => BBTError::NumericalError {
+                team: player,
+                player: 0,
+            },
+            error => error,
+        })?;
+
+        apply_team_updates(&mut updated, &single, rater.write_back(), |_, _| ());
+
+        self.placements[player] = Some(placement);
+        self.remaining -= 1;
+        if let Some(flags) = surprised {
+            self.surprised = flags;
+        }
+
+        let before = self.players[player][0];
+        let after = updated[0][0];
+
+        Ok(PartialDelta {
+            player,
+            placement,
+            before,
+            after,
+            mu_delta: after.mu - before.mu,
+        })
+    }
+
+    /// Rates the match once a single player is left, and writes the updated
+    /// rating of every player into `ratings`, which is indexed like the
+    /// players given to `RoyaleSession::new`. The update is that of
+    /// `Rater::update_ratings` for the ratings from before the match, with
+    /// every player as a team of one and their placement as rank; the
+    /// current contents of `ratings` are not used.
+    ///
+    /// Returns `RoyaleError::Undecided` if more than one player is left,
+    /// `RoyaleError::MismatchedLength` if `ratings` is not as long as the
+    /// players, and `RoyaleError::Rating` with the errors of
+    /// `Rater::update_ratings`. `ratings` is unchanged on errors.
+    pub fn finalize(self, ratings: &mut [Rating<F>]) -> Result<(), RoyaleError> {
+        if self.remaining > 1 {
+            return Err(RoyaleError::Undecided {
+                remaining: self.remaining,
+            });
+        }
+
+        if ratings.len() != self.players.len() {
+            return Err(RoyaleError::MismatchedLength {
+                players: self.players.len(),
+                ratings: ratings.len(),
+            });
+        }
+
+        let ranks = self
+            .placements
+            .iter()
+            .map(|placement| placement.unwrap_or(1))
+            .collect();
+        let updated = self.rater.update_ratings(self.teams(), ranks)?;
+
+        for (rating, team) in ratings.iter_mut().zip(updated) {
+            *rating = team[0];
+        }
+
+        Ok(())
+    }
+
+    fn teams(&self) -> Vec<Vec<Rating<F>>> {
+        self.players.iter().map(|&[player]| vec![player]).collect()
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::vec::Vec;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use BBTError;
+use {Outcome, Rater, Rating};
+
+/// Scale is a marker for the scale of a ladder: the mu and sigma of a new
+/// player, and the β-parameter of its rater. Ratings and raters tagged with
+/// different scales cannot be mixed; see `ScaledRating`.
+///
+/// Scales are usually zero-sized types:
+///
+/// ```rust
+/// use bbt::Scale;
+///
+/// /// A ladder displayed on the familiar scale around 1500.
+/// struct Legacy;
+///
+/// impl Scale for Legacy {
+///     const MU: f64 = 1500.0;
+///     const SIGMA: f64 = 500.0;
+///     const BETA: f64 = 250.0;
+/// }
+/// ```
+pub trait Scale {
+    /// The mu of a new player.
+    const MU: f64;
+
+    /// The sigma of a new player.
+    const SIGMA: f64;
+
+    /// The β-parameter of the rater, the standard deviation of a player's
+    /// performance around their skill.
+    const BETA: f64;
+}
+
+/// DefaultScale is the scale of `Rating::default` and `Rater::default`: new
+/// players start at a mu of 25 with a sigma of 25/3, and β is 25/6.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DefaultScale;
+
+impl Scale for DefaultScale {
+    const MU: f64 = 25.0;
+    const SIGMA: f64 = 25.0 / 3.0;
+    const BETA: f64 = 25.0 / 6.0;
+}
+
+/// ScaledRating is a rating on the scale `S`. It can only be rated by a
+/// `ScaledRater` of the same scale, so that a rating of one ladder cannot be
+/// passed to the rater of another by mistake:
+///
+/// ```rust,compile_fail
+/// use bbt::{DefaultScale, Outcome, Scale, ScaledRater, ScaledRating};
+///
+/// struct Legacy;
+///
+/// impl Scale for Legacy {
+///     const MU: f64 = 1500.0;
+///     const SIGMA: f64 = 500.0;
+///     const BETA: f64 = 250.0;
+/// }
+///
+/// let rater = ScaledRater::<DefaultScale>::new();
+/// let legacy = ScaledRating::<Legacy>::default();
+///
+/// rater.duel(legacy, legacy, Outcome::Win);
+/// ```
+///
+/// `convert` is the only way to move a rating to another scale. The wrapped
+/// `Rating` is available with `rating`, for the parts of the library that do
+/// not know about scales.
+pub struct ScaledRating<S: Scale = DefaultScale> {
+    rating: Rating,
+    scale: PhantomData<S>,
+}
+
+impl<S: Scale> ScaledRating<S> {
+    /// Creates a rating on the scale `S`.
+    pub fn new(mu: f64, sigma: f64) -> ScaledRating<S> {
+        ScaledRating::from_rating(Rating::new(mu, sigma))
+    }
+
+    /// Tags a rating with the scale `S`. The caller is responsible for the
+    /// rating actually being on that scale.
+    pub fn from_rating(rating: Rating) -> ScaledRating<S> {
+        ScaledRating {
+            rating,
+            scale: PhantomData,
+        }
+    }
+
+    /// Returns the rating without its scale.
+    pub fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    /// Returns the mu of the rating.
+    pub fn mu(&self) -> f64 {
+        self.rating.mu
+    }
+
+    /// Returns the sigma of the rating.
+    pub fn sigma(&self) -> f64 {
+        self.rating.sigma
+    }
+
+    /// Converts the rating to the scale `T`.
+    ///
+    /// The conversion is affine: the mu of a new player on `S` maps to that
+    /// on `T`, and distances from it, as well as sigma, are multiplied by
+    /// the ratio of the β-parameters. Win probabilities under the raters of
+    /// the two scales are therefore the same before and after the
+    /// conversion, and so are the updates, up to rounding. The sigma of a
+    /// new player is not used.
+    ///
+    /// ```rust
+    /// use bbt::{DefaultScale, Scale, ScaledRating};
+    ///
+    /// struct Doubled;
+    ///
+    /// impl Scale for Doubled {
+    ///     const MU: f64 = 50.0;
+    ///     const SIGMA: f64 = 50.0 / 3.0;
+    ///     const BETA: f64 = 50.0 / 6.0;
+    /// }
+    ///
+    /// let rating = ScaledRating::<DefaultScale>::new(30.0, 2.0).convert::<Doubled>();
+    /// assert!((rating.mu() - 60.0).abs() < 1e-12);
+    /// assert!((rating.sigma() - 4.0).abs() < 1e-12);
+    /// ```
+    pub fn convert<T: Scale>(&self) -> ScaledRating<T> {
+        let factor = T::BETA / S::BETA;
+
+        ScaledRating::new(
+            T::MU + (self.rating.mu - S::MU) * factor,
+            self.rating.sigma * factor,
+        )
+    }
+}
+
+impl<S: Scale> Default for ScaledRating<S> {
+    /// Returns the rating of a new player on the scale `S`.
+    fn default() -> ScaledRating<S> {
+        ScaledRating::new(S::MU, S::SIGMA)
+    }
+}
+
+// The traits are implemented by hand, since deriving them would require the
+// scale markers to implement them as well.
+
+impl<S: Scale> Clone for ScaledRating<S> {
+    fn clone(&self) -> ScaledRating<S> {
+        *self
+    }
+}
+
+impl<S: Scale> Copy for ScaledRating<S> {}
+
+impl<S: Scale> PartialEq for ScaledRating<S> {
+    fn eq(&self, other: &ScaledRating<S>) -> bool {
+        self.rating == other.rating
+    }
+}
+
+impl<S: Scale> fmt::Debug for ScaledRating<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.rating, f)
+    }
+}
+
+/// ScaledRater is a rater for the ratings of the scale `S`, with the
+/// β-parameter of that scale.
+pub struct ScaledRater<S: Scale = DefaultScale> {
+    rater: Rater,
+    scale: PhantomData<S>,
+}
+
+impl<S: Scale> ScaledRater<S> {
+    /// Creates a rater with the β-parameter of the scale `S`.
+    pub fn new() -> ScaledRater<S> {
+        ScaledRater {
+            rater: Rater::new(S::BETA),
+            scale: PhantomData,
+        }
+    }
+
+    /// Returns the underlying rater.
+    pub fn rater(&self) -> &Rater {
+        &self.rater
+    }
+
+    /// Like `Rater::duel`, for ratings of the scale `S`.
+    pub fn duel(
+        &self,
+        p1: ScaledRating<S>,
+        p2: ScaledRating<S>,
+        outcome: Outcome,
+    ) -> (ScaledRating<S>, ScaledRating<S>) {
+        let (p1, p2) = self.rater.duel(p1.rating, p2.rating, outcome);
+        (ScaledRating::from_rating(p1), ScaledRating::from_rating(p2))
+    }
+
+    /// Like `Rater::update_ratings`, for ratings of the scale `S`.
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings(
+        &self,
+        teams: Vec<Vec<ScaledRating<S>>>,
+        ranks: Vec<usize>,
+    ) -> Result<Vec<Vec<ScaledRating<S>>>, BBTError> {
+        let teams = teams
+            .into_iter()
+            .map(|team| team.into_iter().map(|rating| rating.rating).collect())
+            .collect();
+
+        Ok(self
+            .rater
+            .update_ratings(teams, ranks)?
+            .into_iter()
+            .map(|team| team.into_iter().map(ScaledRating::from_rating).collect())
+            .collect())
+    }
+
+    /// Like `Rater::win_probability`, for ratings of the scale `S`.
+    pub fn win_probability(&self, p1: &ScaledRating<S>, p2: &ScaledRating<S>) -> f64 {
+        self.rater.win_probability(&p1.rating, &p2.rating)
+    }
+
+    /// Like `Rater::match_quality`, for ratings of the scale `S`.
+    pub fn match_quality(&self, p1: &ScaledRating<S>, p2: &ScaledRating<S>) -> f64 {
+        self.rater.match_quality(&p1.rating, &p2.rating)
+    }
+}
+
+impl<S: Scale> Default for ScaledRater<S> {
+    fn default() -> ScaledRater<S> {
+        ScaledRater::new()
+    }
+}
+
+impl<S: Scale> fmt::Debug for ScaledRater<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.rater, f)
+    }
+}
@@ -0,0 +1,85 @@
+//! `JsonSchema` implementations, enabled by the `schemars` feature. The
+//! schemas describe the human-readable serde representations, i.e. what
+//! `serde_json` produces and accepts.
+
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use {Float, Outcome, Rater, Rating};
+
+impl<F: Float> JsonSchema for Rating<F> {
+    fn schema_name() -> Cow<'static, str> {
+        "Rating".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "bbt::Rating".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "description": "The skill rating of a player, a normal distribution over their skill.",
+            "properties": {
+                "mu": {
+                    "type": "number",
+                    "description": "The estimated skill of the player."
+                },
+                "sigma": {
+                    "type": "number",
+                    "exclusiveMinimum": 0,
+                    "description": "The uncertainty of the estimate, which must be positive."
+                }
+            },
+            "required": ["mu", "sigma"],
+            "examples": [{ "mu": 25.0, "sigma": 25.0 / 3.0 }]
+        })
+    }
+}
+
+impl<F: Float> JsonSchema for Rater<F> {
+    fn schema_name() -> Cow<'static, str> {
+        "Rater".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "bbt::Rater".into()
+    }
+
+    /// Only β is serialized. The legacy field `beta_sq` is still accepted by
+    /// the deserializer, but not part of the schema.
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "description": "A rater. Only its β-parameter, which describes how much randomness the game has, is stored.",
+            "properties": {
+                "beta": {
+                    "type": "number",
+                    "minimum": 0,
+                    "description": "The β-parameter of the rater."
+                }
+            },
+            "required": ["beta"],
+            "examples": [{ "beta": 25.0 / 6.0 }]
+        })
+    }
+}
+
+impl JsonSchema for Outcome {
+    fn schema_name() -> Cow<'static, str> {
+        "Outcome".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "bbt::Outcome".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "The outcome of a duel, from the perspective of the first player.",
+            "enum": ["Win", "Loss", "Draw"]
+        })
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+use std::cmp::Ordering;
+
+use {BBTError, Float, Rater, Rating};
+
+/// ScoreOrder is the direction in which scores are ranked: points, where
+/// the highest score wins, or finish times, where the lowest score wins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoreOrder {
+    /// The team with the highest score finishes first.
+    HigherIsBetter,
+    /// The team with the lowest score finishes first.
+    LowerIsBetter,
+}
+
+/// Derives the ranks of a match from the scores of the teams, for use with
+/// `Rater::update_ratings`. The best score gets rank one.
+///
+/// Scores within `tie_epsilon` of the best score of a group of ties are tied
+/// with it, and tied teams share the best rank of their group, the next
+/// team after them skipping the ranks they took up (a standard competition
+/// ranking, e.g. 1, 2, 2, 4). Equal scores are always tied, so a
+/// `tie_epsilon` of zero only ties equal scores. NaN scores rank after all
+/// others and are never tied.
+///
+/// ```rust
+/// use bbt::{ranks_from_scores, ScoreOrder};
+///
+/// let points = [10.0, 30.0, 20.0, 30.0];
+/// assert_eq!(ranks_from_scores(&points, ScoreOrder::HigherIsBetter, 0.0), vec![4, 1, 3, 1]);
+///
+/// let finish_times = [61.3, 59.95, 60.0];
+/// assert_eq!(ranks_from_scores(&finish_times, ScoreOrder::LowerIsBetter, 0.1), vec![3, 1, 1]);
+/// ```
+pub fn ranks_from_scores<F: Float>(scores: &[F], order: ScoreOrder, tie_epsilon: F) -> Vec<usize> {
+    let mut sorted: Vec<usize> = (0..scores.len()).collect();
+    sorted.sort_by(|&a, &b| compare_scores(scores[a], scores[b], order));
+
+    let mut ranks = vec![0; scores.len()];
+    let mut group: Option<(F, usize)> = None;
+
+    for (position, &index) in sorted.iter().enumerate() {
+        let score = scores[index];
+
+        ranks[index] = match group {
+            Some((best, rank)) if score == best || (score - best).abs() <= tie_epsilon => rank,
+            _ => {
+                group = Some((score, position + 1));
+                position + 1
+            }
+        };
+    }
+
+    ranks
+}
+
+/// Orders two scores from best to worst, with NaN after everything else.
+fn compare_scores<F: Float>(a: F, b: F, order: ScoreOrder) -> Ordering {
+    let ordering = match order {
+        ScoreOrder::HigherIsBetter => b.partial_cmp(&a),
+        ScoreOrder::LowerIsBetter => a.partial_cmp(&b),
+    };
+
+    ordering.unwrap_or_else(|| is_nan(a).cmp(&is_nan(b)))
+}
+
+fn is_nan<F: Float>(value: F) -> bool {
+    value.partial_cmp(&value).is_none()
+}
+
+impl<F: Float> Rater<F> {
+    /// This method updates the ratings of a match like `update_ratings`,
+    /// with ranks derived from the scores of the teams by
+    /// `ranks_from_scores`.
+    ///
+    /// Returns `BBTError::InvalidParameter` if a score is NaN or if
+    /// `tie_epsilon` is negative or not finite, and the errors of
+    /// `update_ratings` otherwise.
+    pub fn update_ratings_from_scores(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        scores: &[F],
+        order: ScoreOrder,
+        tie_epsilon: F,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        if !tie_epsilon.is_finite() || tie_epsilon < F::ZERO {
+            return Err(BBTError::InvalidParameter {
+                name: "tie_epsilon",
+            });
+        }
+
+        if scores.iter().any(|&score| is_nan(score)) {
+            return Err(BBTError::InvalidParameter { name: "scores" });
+        }
+
+        self.update_ratings(teams, ranks_from_scores(scores, order, tie_epsilon))
+    }
+
+    /// This method updates the ratings of a match like `update_ratings`,
+    /// with ranks that carry a tiebreaker, such as a league table that ranks
+    /// on points and breaks ties on goal difference. Each rank is a pair of
+    /// the primary rank and the tiebreak value: teams with different primary
+    /// ranks are ordered by the primary rank alone, teams with the same
+    /// primary rank by the tiebreak, and only teams for which both are equal
+    /// are tied. Like ranks, lower tiebreak values are better, so a goal
+    /// difference has to be negated.
+    ///
+    /// The update is that of `update_ratings` with the standard competition
+    /// ranking of the pairs, e.g. 1, 2, 2, 4, and it returns the errors of
+    /// `update_ratings`.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let teams = vec![vec![Rating::default()]; 3];
+    ///
+    /// // The second and third teams are level on points, but the third one
+    /// // has the better goal difference.
+    /// let with_tiebreaks = rater
+    ///     .update_ratings_with_tiebreaks(teams.clone(), &[(1, 0), (2, -3), (2, -5)])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(with_tiebreaks, rater.update_ratings(teams, vec![1, 3, 2]).unwrap());
+    /// ```
+    pub fn update_ratings_with_tiebreaks(
+        &self,
+        teams: Vec<Vec<Rating<F>>>,
+        ranks: &[(usize, i64)],
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let mut sorted: Vec<usize> = (0..ranks.len()).collect();
+        sorted.sort_by_key(|&index| ranks[index]);
+
+        let mut plain = vec![0; ranks.len()];
+        for (position, &index) in sorted.iter().enumerate() {
+            plain[index] = match position {
+                0 => 1,
+                _ if ranks[sorted[position - 1]] == ranks[index] => plain[sorted[position - 1]],
+                _ => position + 1,
+            };
+        }
+
+        self.update_ratings(teams, plain)
+    }
+}
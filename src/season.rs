@@ -0,0 +1,57 @@
+//! Soft resets of many ratings at once, at the start of a new season.
+//!
+//! Rather than wiping ratings, a soft reset compresses every mu towards a
+//! target and restores some uncertainty, as described for
+//! `Rating::season_reset`.
+//!
+//! ```rust
+//! let mut ratings = [bbt::Rating::new(40.0, 1.0), bbt::Rating::new(10.0, 1.0)];
+//! bbt::season::reset(&mut ratings, 25.0, 0.5, 4.0).unwrap();
+//!
+//! assert_eq!(ratings[0].mu(), 32.5);
+//! assert_eq!(ratings[1].mu(), 17.5);
+//! assert_eq!(ratings[1].sigma(), 4.0);
+//! ```
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use check_season_reset;
+use {BBTError, Float, Rating};
+
+/// Resets every rating with `Rating::season_reset`. The parameters are
+/// checked before any rating is changed, so either all ratings are reset or
+/// none are.
+pub fn reset<F: Float>(
+    ratings: &mut [Rating<F>],
+    target_mu: F,
+    compression: F,
+    sigma_restore: F,
+) -> Result<(), BBTError> {
+    check_season_reset(target_mu, compression, sigma_restore)?;
+
+    for rating in ratings.iter_mut() {
+        *rating = rating.season_reset(target_mu, compression, sigma_restore)?;
+    }
+
+    Ok(())
+}
+
+/// Resets every rating of a table like `reset`.
+#[cfg(feature = "std")]
+pub fn reset_map<K: Eq + Hash, F: Float>(
+    ratings: &mut HashMap<K, Rating<F>>,
+    target_mu: F,
+    compression: F,
+    sigma_restore: F,
+) -> Result<(), BBTError> {
+    check_season_reset(target_mu, compression, sigma_restore)?;
+
+    for rating in ratings.values_mut() {
+        *rating = rating.season_reset(target_mu, compression, sigma_restore)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+//! Serializes a `Rating` as the 2-tuple `[mu, sigma]` instead of a struct with
+//! named fields, for use with `#[serde(with = "bbt::serde_compact")]`. This
+//! saves space when storing many ratings in a self-describing format such as
+//! JSON.
+//!
+//! ```rust
+//! # extern crate bbt;
+//! # #[macro_use] extern crate serde_derive;
+//! #[derive(Serialize, Deserialize)]
+//! struct Player {
+//!     #[serde(with = "bbt::serde_compact")]
+//!     rating: bbt::Rating,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! The `Deserialize` impl of `Rating` accepts both forms, so fields can be
+//! switched to the compact form without converting stored ratings, and
+//! compact ratings can be read back without this module.
+
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use {Float, Rating};
+
+pub fn serialize<F, S>(rating: &Rating<F>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    F: Float + Serialize,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_tuple(2)?;
+    state.serialize_element(&rating.mu)?;
+    state.serialize_element(&rating.sigma)?;
+    state.end()
+}
+
+pub fn deserialize<'de, F, D>(deserializer: D) -> Result<Rating<F>, D::Error>
+where
+    F: Float + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Rating::deserialize(deserializer)
+}
@@ -0,0 +1,59 @@
+//! Serializes a `Rating` like its `Serialize` impl, but deserializes it with a
+//! default for a missing sigma, for use with
+//! `#[serde(with = "bbt::serde_default_sigma")]`.
+//!
+//! This loads documents from before sigma was stored. `deserialize`
+//! substitutes the sigma of `Rating::default()`, 25/3. For another default,
+//! wrap `deserialize_with_sigma` in a function and use it with
+//! `#[serde(deserialize_with = "...")]`:
+//!
+//! ```rust
+//! # extern crate bbt;
+//! # extern crate serde;
+//! # #[macro_use] extern crate serde_derive;
+//! use serde::Deserializer;
+//!
+//! fn legacy_rating<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bbt::Rating, D::Error> {
+//!     bbt::serde_default_sigma::deserialize_with_sigma(deserializer, 500.0)
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Player {
+//!     #[serde(deserialize_with = "legacy_rating")]
+//!     rating: bbt::Rating,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! A missing mu is still an error, and ratings are validated as by the
+//! `Deserialize` impl.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use serialization::deserialize_rating;
+use {Float, Rating};
+
+pub fn serialize<F, S>(rating: &Rating<F>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    F: Float + Serialize,
+    S: Serializer,
+{
+    rating.serialize(serializer)
+}
+
+pub fn deserialize<'de, F, D>(deserializer: D) -> Result<Rating<F>, D::Error>
+where
+    F: Float + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserialize_with_sigma(deserializer, Rating::<F>::default().sigma())
+}
+
+/// Deserializes a rating, substituting `sigma` if it is missing.
+pub fn deserialize_with_sigma<'de, F, D>(deserializer: D, sigma: F) -> Result<Rating<F>, D::Error>
+where
+    F: Float + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserialize_rating(deserializer, true, Some(sigma))
+}
@@ -0,0 +1,210 @@
+//! Alternative serde representations of `Outcome`, for use with
+//! `#[serde(with = "...")]` on a field of type `Outcome`:
+//!
+//! * `lowercase`: `"win"`, `"loss"` and `"draw"`.
+//! * `chess`: `"1-0"`, `"0-1"` and `"1/2-1/2"`.
+//! * `int`: `1`, `-1` and `0`.
+//!
+//! The string forms are deserialized regardless of case, and `chess` also
+//! accepts `"½-½"` for draws. The integer form only accepts the integers 1,
+//! -1 and 0.
+//!
+//! ```rust
+//! # extern crate bbt;
+//! # #[macro_use] extern crate serde_derive;
+//! #[derive(Serialize, Deserialize)]
+//! struct Game {
+//!     #[serde(with = "bbt::serde_outcome::chess")]
+//!     result: bbt::Outcome,
+//! }
+//! # fn main() {}
+//! ```
+
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+
+use Outcome;
+
+/// Deserializes one of the string forms, ignoring case.
+fn deserialize_str<'de, D>(
+    deserializer: D,
+    expecting: &'static str,
+    variants: &'static [&'static str],
+    names: &'static [(&'static str, Outcome)],
+) -> Result<Outcome, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StrVisitor {
+        expecting: &'static str,
+        variants: &'static [&'static str],
+        names: &'static [(&'static str, Outcome)],
+    }
+
+    impl<'de> Visitor<'de> for StrVisitor {
+        type Value = Outcome;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(self.expecting)
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Outcome, E>
+        where
+            E: de::Error,
+        {
+            self.names
+                .iter()
+                .find(|&&(name, _)| name.eq_ignore_ascii_case(value))
+                .map(|&(_, outcome)| outcome)
+                .ok_or_else(|| de::Error::unknown_variant(value, self.variants))
+        }
+    }
+
+    deserializer.deserialize_str(StrVisitor {
+        expecting,
+        variants,
+        names,
+    })
+}
+
+/// Serializes outcomes as `"win"`, `"loss"` and `"draw"`.
+pub mod lowercase {
+    use serde::{Deserializer, Serializer};
+
+    use Outcome;
+
+    const NAMES: &[(&str, Outcome)] = &[
+        ("win", Outcome::Win),
+        ("loss", Outcome::Loss),
+        ("draw", Outcome::Draw),
+    ];
+
+    pub fn serialize<S>(outcome: &Outcome, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match *outcome {
+            Outcome::Win => "win",
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw",
+        })
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Outcome, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_str(
+            deserializer,
+            "`win`, `loss` or `draw`",
+            &["win", "loss", "draw"],
+            NAMES,
+        )
+    }
+}
+
+/// Serializes outcomes as chess results: `"1-0"`, `"0-1"` and `"1/2-1/2"`.
+pub mod chess {
+    use serde::{Deserializer, Serializer};
+
+    use Outcome;
+
+    const NAMES: &[(&str, Outcome)] = &[
+        ("1-0", Outcome::Win),
+        ("0-1", Outcome::Loss),
+        ("1/2-1/2", Outcome::Draw),
+        ("½-½", Outcome::Draw),
+    ];
+
+    pub fn serialize<S>(outcome: &Outcome, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match *outcome {
+            Outcome::Win => "1-0",
+            Outcome::Loss => "0-1",
+            Outcome::Draw => "1/2-1/2",
+        })
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Outcome, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_str(
+            deserializer,
+            "`1-0`, `0-1` or `1/2-1/2`",
+            &["1-0", "0-1", "1/2-1/2"],
+            NAMES,
+        )
+    }
+}
+
+/// Serializes outcomes as the integers `1` (win), `-1` (loss) and `0`
+/// (draw).
+pub mod int {
+    use std::fmt;
+
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::Serializer;
+
+    use Outcome;
+
+    pub fn serialize<S>(outcome: &Outcome, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i8(match *outcome {
+            Outcome::Win => 1,
+            Outcome::Loss => -1,
+            Outcome::Draw => 0,
+        })
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Outcome, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct IntVisitor;
+
+        impl<'de> Visitor<'de> for IntVisitor {
+            type Value = Outcome;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("1, -1 or 0")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Outcome, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    1 => Ok(Outcome::Win),
+                    -1 => Ok(Outcome::Loss),
+                    0 => Ok(Outcome::Draw),
+                    _ => Err(de::Error::invalid_value(
+                        de::Unexpected::Signed(value),
+                        &self,
+                    )),
+                }
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Outcome, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    1 => Ok(Outcome::Win),
+                    0 => Ok(Outcome::Draw),
+                    _ => Err(de::Error::invalid_value(
+                        de::Unexpected::Unsigned(value),
+                        &self,
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_i8(IntVisitor)
+    }
+}
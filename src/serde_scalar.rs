@@ -0,0 +1,150 @@
+//! Serializes a `Rating` as a single number, for public-facing documents
+//! that should not expose both mu and sigma, such as the responses of a web
+//! API. There are two modules for use with `#[serde(with = "...")]`:
+//!
+//! * `conservative` serializes the conservative estimate `mu - 3 * sigma`.
+//!   The rating cannot be recovered from it, so deserialization always
+//!   fails.
+//! * `mu_only` serializes mu, and deserializes it with a default sigma.
+//!
+//! Other fields of the same struct are not affected, so a document can show
+//! one rating as a number and another in full:
+//!
+//! ```rust
+//! # extern crate bbt;
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate serde_json;
+//! #[derive(Serialize)]
+//! struct PublicPlayer {
+//!     #[serde(with = "bbt::serde_scalar::conservative")]
+//!     rating: bbt::Rating,
+//!     #[serde(with = "bbt::serde_scalar::mu_only")]
+//!     skill: bbt::Rating,
+//!     full: bbt::Rating,
+//! }
+//!
+//! # fn main() {
+//! let rating = bbt::Rating::new(30.0, 2.0);
+//! let player = PublicPlayer { rating, skill: rating, full: rating };
+//!
+//! assert_eq!(
+//!     serde_json::to_string(&player).unwrap(),
+//!     r#"{"rating":24.0,"skill":30.0,"full":{"mu":30.0,"sigma":2.0}}"#
+//! );
+//! # }
+//! ```
+
+/// Serializes a `Rating` as its conservative estimate `mu - 3 * sigma`, for
+/// use with `#[serde(with = "bbt::serde_scalar::conservative")]` or
+/// `#[serde(serialize_with = "bbt::serde_scalar::conservative::serialize")]`.
+///
+/// Sigma cannot be recovered from the estimate, so `deserialize` always
+/// returns an error. It only exists so that the module can be used with
+/// `with` on types that also derive `Deserialize`, whose field then fails to
+/// deserialize with an error that says why.
+pub mod conservative {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use {Float, Rating};
+
+    pub fn serialize<F, S>(rating: &Rating<F>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: Float + Serialize,
+        S: Serializer,
+    {
+        rating.conservative_estimate().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, F, D>(_deserializer: D) -> Result<Rating<F>, D::Error>
+    where
+        F: Float + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Err(D::Error::custom(
+            "a rating serialized as its conservative estimate cannot be deserialized",
+        ))
+    }
+}
+
+/// Serializes a `Rating` as its mu, for use with
+/// `#[serde(with = "bbt::serde_scalar::mu_only")]`.
+///
+/// `deserialize` pairs the mu with the sigma of `Rating::default()`, 25/3.
+/// Since a sigma cannot be passed through the `with` attribute, another
+/// default is chosen like with `bbt::serde_default_sigma`: wrap
+/// `deserialize_with_sigma` in a function with the sigma as a constant, and
+/// use it with `#[serde(deserialize_with = "...")]`:
+///
+/// ```rust
+/// # extern crate bbt;
+/// # extern crate serde;
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate serde_json;
+/// use serde::Deserializer;
+///
+/// const PUBLIC_SIGMA: f64 = 2.5;
+///
+/// fn public_rating<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bbt::Rating, D::Error> {
+///     bbt::serde_scalar::mu_only::deserialize_with_sigma(deserializer, PUBLIC_SIGMA)
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Player {
+///     #[serde(
+///         serialize_with = "bbt::serde_scalar::mu_only::serialize",
+///         deserialize_with = "public_rating"
+///     )]
+///     rating: bbt::Rating,
+/// }
+///
+/// # fn main() {
+/// let player: Player = serde_json::from_str(r#"{"rating":31.5}"#).unwrap();
+/// assert_eq!(player.rating, bbt::Rating::new(31.5, PUBLIC_SIGMA));
+/// # }
+/// ```
+///
+/// A mu that is not finite is an error.
+pub mod mu_only {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use {Float, Rating};
+
+    pub fn serialize<F, S>(rating: &Rating<F>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: Float + Serialize,
+        S: Serializer,
+    {
+        rating.mu.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<Rating<F>, D::Error>
+    where
+        F: Float + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserialize_with_sigma(deserializer, Rating::<F>::default().sigma())
+    }
+
+    /// Deserializes a mu, and returns it as a rating with the given sigma.
+    pub fn deserialize_with_sigma<'de, F, D>(
+        deserializer: D,
+        sigma: F,
+    ) -> Result<Rating<F>, D::Error>
+    where
+        F: Float + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let mu = F::deserialize(deserializer)?;
+
+        if !mu.is_finite() {
+            return Err(D::Error::custom(format_args!(
+                "invalid mu {}: must be finite",
+                mu
+            )));
+        }
+
+        Ok(Rating::new(mu, sigma))
+    }
+}
@@ -0,0 +1,27 @@
+//! Serializes a `Rating` like its `Serialize` impl, but deserializes it
+//! without validation, for use with `#[serde(with = "bbt::serde_unchecked")]`.
+//!
+//! The `Deserialize` impl of `Rating` rejects a non-finite mu or sigma and a
+//! sigma that is not positive. Use this module for fields that store such
+//! values on purpose, for example as sentinels.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use serialization::deserialize_rating;
+use {Float, Rating};
+
+pub fn serialize<F, S>(rating: &Rating<F>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    F: Float + Serialize,
+    S: Serializer,
+{
+    rating.serialize(serializer)
+}
+
+pub fn deserialize<'de, F, D>(deserializer: D) -> Result<Rating<F>, D::Error>
+where
+    F: Float + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserialize_rating(deserializer, false, None)
+}
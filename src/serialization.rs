@@ -1,16 +1,29 @@
 use std::fmt;
 
 use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
-use serde::ser::SerializeStruct;
+use serde::ser::{SerializeStruct, SerializeTuple};
 use serde::{Serialize, Serializer};
 
-use Rating;
+use std::marker::PhantomData;
 
-impl Serialize for Rating {
+use form::{self, FormRating};
+use {Float, Outcome, Rater, Rating, RatingUpdate, TrackedRating};
+
+/// Ratings are serialized as a struct with the fields `mu` and `sigma` in
+/// human-readable formats, and as the tuple `(mu, sigma)` in binary formats,
+/// where field names are pure overhead.
+impl<F: Float + Serialize> Serialize for Rating<F> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
+        if !serializer.is_human_readable() {
+            let mut state = serializer.serialize_tuple(2)?;
+            state.serialize_element(&self.mu)?;
+            state.serialize_element(&self.sigma)?;
+            return state.end();
+        }
+
         let mut state = serializer.serialize_struct("Rating", 2)?;
         state.serialize_field("mu", &self.mu)?;
         state.serialize_field("sigma", &self.sigma)?;
@@ -18,7 +31,182 @@ impl Serialize for Rating {
     }
 }
 
-impl<'de> Deserialize<'de> for Rating {
+impl<'de, F: Float + Deserialize<'de>> Deserialize<'de> for Rating<F> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_rating(deserializer, true, None)
+    }
+}
+
+/// Returns the rating with the given mu and sigma. If `checked` is set,
+/// ratings with a non-finite mu or sigma, or a sigma that is not positive,
+/// are rejected.
+fn checked_rating<F: Float, E: de::Error>(mu: F, sigma: F, checked: bool) -> Result<Rating<F>, E> {
+    if checked {
+        if !mu.is_finite() {
+            return Err(E::custom(format_args!("invalid mu {}: must be finite", mu)));
+        }
+
+        if !sigma.is_finite() || sigma <= F::ZERO {
+            return Err(E::custom(format_args!(
+                "invalid sigma {}: must be positive and finite",
+                sigma
+            )));
+        }
+    }
+
+    Ok(Rating::new(mu, sigma))
+}
+
+/// Deserializes a rating, validating it if `checked` is set. A missing sigma
+/// is replaced by `default_sigma` if given. This is shared between the
+/// `Deserialize` impl, `bbt::serde_unchecked` and `bbt::serde_default_sigma`.
+pub fn deserialize_rating<'de, F, D>(
+    deserializer: D,
+    checked: bool,
+    default_sigma: Option<F>,
+) -> Result<Rating<F>, D::Error>
+where
+    F: Float + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    enum Field {
+        Mu,
+        Sigma,
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct FieldVisitor;
+
+            impl<'de> Visitor<'de> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("`mu` or `sigma`")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        "mu" => Ok(Field::Mu),
+                        "sigma" => Ok(Field::Sigma),
+                        _ => Err(de::Error::unknown_field(value, FIELDS)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    struct RatingVisitor<F> {
+        checked: bool,
+        default_sigma: Option<F>,
+        marker: PhantomData<F>,
+    }
+
+    impl<'de, F: Float + Deserialize<'de>> Visitor<'de> for RatingVisitor<F> {
+        type Value = Rating<F>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("struct Rating")
+        }
+
+        fn visit_seq<V>(self, mut seq: V) -> Result<Rating<F>, V::Error>
+        where
+            V: SeqAccess<'de>,
+        {
+            let mu = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let sigma = seq
+                .next_element()?
+                .or(self.default_sigma)
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            checked_rating(mu, sigma, self.checked)
+        }
+
+        fn visit_map<V>(self, mut map: V) -> Result<Rating<F>, V::Error>
+        where
+            V: MapAccess<'de>,
+        {
+            let mut mu = None;
+            let mut sigma = None;
+            while let Some(key) = map.next_key()? {
+                match key {
+                    Field::Mu => {
+                        if mu.is_some() {
+                            return Err(de::Error::duplicate_field("mu"));
+                        } else {
+                            mu = Some(map.next_value()?);
+                        }
+                    }
+                    Field::Sigma => {
+                        if sigma.is_some() {
+                            return Err(de::Error::duplicate_field("sigma"));
+                        } else {
+                            sigma = Some(map.next_value()?);
+                        }
+                    }
+                }
+            }
+            let mu = mu.ok_or_else(|| de::Error::missing_field("mu"))?;
+            let sigma = sigma
+                .or(self.default_sigma)
+                .ok_or_else(|| de::Error::missing_field("sigma"))?;
+            checked_rating(mu, sigma, self.checked)
+        }
+    }
+
+    const FIELDS: &[&str] = &["mu", "sigma"];
+    let visitor = RatingVisitor {
+        checked,
+        default_sigma,
+        marker: PhantomData,
+    };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_struct("Rating", FIELDS, visitor)
+    } else {
+        deserializer.deserialize_tuple(2, visitor)
+    }
+}
+
+/// Tracked ratings are serialized like ratings, with the number of games
+/// played as an additional field `games` in human-readable formats and as
+/// the tuple `(mu, sigma, games)` in binary formats.
+impl<F: Float + Serialize> Serialize for TrackedRating<F> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            let mut state = serializer.serialize_tuple(3)?;
+            state.serialize_element(&self.mu())?;
+            state.serialize_element(&self.sigma())?;
+            state.serialize_element(&self.games_played())?;
+            return state.end();
+        }
+
+        let mut state = serializer.serialize_struct("TrackedRating", 3)?;
+        state.serialize_field("mu", &self.mu())?;
+        state.serialize_field("sigma", &self.sigma())?;
+        state.serialize_field("games", &self.games_played())?;
+        state.end()
+    }
+}
+
+/// In human-readable formats, a missing `games` field is read as zero, so
+/// that serialized ratings can be read as tracked ratings. The rating is
+/// validated like a `Rating`.
+impl<'de, F: Float + Deserialize<'de>> Deserialize<'de> for TrackedRating<F> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -26,6 +214,7 @@ impl<'de> Deserialize<'de> for Rating {
         enum Field {
             Mu,
             Sigma,
+            Games,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -39,7 +228,7 @@ impl<'de> Deserialize<'de> for Rating {
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("`mu` or `sigma`")
+                        formatter.write_str("`mu`, `sigma` or `games`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -49,6 +238,7 @@ impl<'de> Deserialize<'de> for Rating {
                         match value {
                             "mu" => Ok(Field::Mu),
                             "sigma" => Ok(Field::Sigma),
+                            "games" => Ok(Field::Games),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -58,16 +248,16 @@ impl<'de> Deserialize<'de> for Rating {
             }
         }
 
-        struct RatingVisitor;
+        struct TrackedVisitor<F>(PhantomData<F>);
 
-        impl<'de> Visitor<'de> for RatingVisitor {
-            type Value = Rating;
+        impl<'de, F: Float + Deserialize<'de>> Visitor<'de> for TrackedVisitor<F> {
+            type Value = TrackedRating<F>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("struct Rating")
+                formatter.write_str("struct TrackedRating")
             }
 
-            fn visit_seq<V>(self, mut seq: V) -> Result<Rating, V::Error>
+            fn visit_seq<V>(self, mut seq: V) -> Result<TrackedRating<F>, V::Error>
             where
                 V: SeqAccess<'de>,
             {
@@ -77,40 +267,589 @@ impl<'de> Deserialize<'de> for Rating {
                 let sigma = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &self))?;
-                Ok(Rating::new(mu, sigma))
+                let games = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+                Ok(TrackedRating::with_games(
+                    checked_rating(mu, sigma, true)?,
+                    games,
+                ))
             }
 
-            fn visit_map<V>(self, mut map: V) -> Result<Rating, V::Error>
+            fn visit_map<V>(self, mut map: V) -> Result<TrackedRating<F>, V::Error>
             where
                 V: MapAccess<'de>,
             {
                 let mut mu = None;
                 let mut sigma = None;
+                let mut games = None;
+
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Mu => {
                             if mu.is_some() {
                                 return Err(de::Error::duplicate_field("mu"));
-                            } else {
-                                mu = Some(map.next_value()?);
                             }
+                            mu = Some(map.next_value()?);
                         }
                         Field::Sigma => {
                             if sigma.is_some() {
                                 return Err(de::Error::duplicate_field("sigma"));
-                            } else {
-                                sigma = Some(map.next_value()?);
                             }
+                            sigma = Some(map.next_value()?);
+                        }
+                        Field::Games => {
+                            if games.is_some() {
+                                return Err(de::Error::duplicate_field("games"));
+                            }
+                            games = Some(map.next_value()?);
                         }
                     }
                 }
+
                 let mu = mu.ok_or_else(|| de::Error::missing_field("mu"))?;
                 let sigma = sigma.ok_or_else(|| de::Error::missing_field("sigma"))?;
-                Ok(Rating::new(mu, sigma))
+
+                Ok(TrackedRating::with_games(
+                    checked_rating(mu, sigma, true)?,
+                    games.unwrap_or(0),
+                ))
+            }
+        }
+
+        const FIELDS: &[&str] = &["mu", "sigma", "games"];
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct("TrackedRating", FIELDS, TrackedVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_tuple(3, TrackedVisitor(PhantomData))
+        }
+    }
+}
+
+/// Forms are serialized like ratings, with the decay as an additional field
+/// `decay` in human-readable formats and as the tuple `(mu, sigma, decay)` in
+/// binary formats.
+impl Serialize for FormRating {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            let mut state = serializer.serialize_tuple(3)?;
+            state.serialize_element(&self.mu())?;
+            state.serialize_element(&self.sigma())?;
+            state.serialize_element(&self.decay_rate())?;
+            return state.end();
+        }
+
+        let mut state = serializer.serialize_struct("FormRating", 3)?;
+        state.serialize_field("mu", &self.mu())?;
+        state.serialize_field("sigma", &self.sigma())?;
+        state.serialize_field("decay", &self.decay_rate())?;
+        state.end()
+    }
+}
+
+/// In human-readable formats, a missing `decay` field is read as
+/// `form::DEFAULT_DECAY`, so that serialized ratings can be read as forms.
+/// The rating is validated like a `Rating`, and the decay is clamped like in
+/// `FormRating::with_decay`.
+impl<'de> Deserialize<'de> for FormRating {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Mu,
+            Sigma,
+            Decay,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`mu`, `sigma` or `decay`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "mu" => Ok(Field::Mu),
+                            "sigma" => Ok(Field::Sigma),
+                            "decay" => Ok(Field::Decay),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct FormVisitor;
+
+        impl<'de> Visitor<'de> for FormVisitor {
+            type Value = FormRating;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct FormRating")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<FormRating, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let mu = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let sigma = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let decay = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+                Ok(FormRating::with_decay(
+                    checked_rating(mu, sigma, true)?,
+                    decay,
+                ))
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<FormRating, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut mu = None;
+                let mut sigma = None;
+                let mut decay = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Mu => {
+                            if mu.is_some() {
+                                return Err(de::Error::duplicate_field("mu"));
+                            }
+                            mu = Some(map.next_value()?);
+                        }
+                        Field::Sigma => {
+                            if sigma.is_some() {
+                                return Err(de::Error::duplicate_field("sigma"));
+                            }
+                            sigma = Some(map.next_value()?);
+                        }
+                        Field::Decay => {
+                            if decay.is_some() {
+                                return Err(de::Error::duplicate_field("decay"));
+                            }
+                            decay = Some(map.next_value()?);
+                        }
+                    }
+                }
+
+                let mu = mu.ok_or_else(|| de::Error::missing_field("mu"))?;
+                let sigma = sigma.ok_or_else(|| de::Error::missing_field("sigma"))?;
+
+                Ok(FormRating::with_decay(
+                    checked_rating(mu, sigma, true)?,
+                    decay.unwrap_or(form::DEFAULT_DECAY),
+                ))
+            }
+        }
+
+        const FIELDS: &[&str] = &["mu", "sigma", "decay"];
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct("FormRating", FIELDS, FormVisitor)
+        } else {
+            deserializer.deserialize_tuple(3, FormVisitor)
+        }
+    }
+}
+
+/// Raters are serialized as their β-parameter, as a struct with the field
+/// `beta` in human-readable formats and as the tuple `(beta,)` in binary
+/// formats. The other options of a rater are not serialized.
+impl<F: Float + Serialize> Serialize for Rater<F> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            let mut state = serializer.serialize_tuple(1)?;
+            state.serialize_element(&self.beta())?;
+            return state.end();
+        }
+
+        let mut state = serializer.serialize_struct("Rater", 1)?;
+        state.serialize_field("beta", &self.beta())?;
+        state.end()
+    }
+}
+
+/// In human-readable formats, raters can be deserialized from either `beta` or
+/// the legacy `beta_sq` field. If both are given, β² has to equal `beta_sq`
+/// exactly.
+impl<'de, F: Float + Deserialize<'de>> Deserialize<'de> for Rater<F> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Beta,
+            BetaSq,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`beta` or `beta_sq`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "beta" => Ok(Field::Beta),
+                            "beta_sq" => Ok(Field::BetaSq),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct RaterVisitor<F>(PhantomData<F>);
+
+        impl<'de, F: Float + Deserialize<'de>> Visitor<'de> for RaterVisitor<F> {
+            type Value = Rater<F>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Rater")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Rater<F>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let beta = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                Ok(Rater::new(beta))
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Rater<F>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut beta: Option<F> = None;
+                let mut beta_sq: Option<F> = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Beta => {
+                            if beta.is_some() {
+                                return Err(de::Error::duplicate_field("beta"));
+                            } else {
+                                beta = Some(map.next_value()?);
+                            }
+                        }
+                        Field::BetaSq => {
+                            if beta_sq.is_some() {
+                                return Err(de::Error::duplicate_field("beta_sq"));
+                            } else {
+                                beta_sq = Some(map.next_value()?);
+                            }
+                        }
+                    }
+                }
+
+                match (beta, beta_sq) {
+                    (Some(beta), None) => Ok(Rater::new(beta)),
+                    (beta, Some(beta_sq)) => {
+                        if beta_sq < F::ZERO {
+                            return Err(de::Error::custom("`beta_sq` must not be negative"));
+                        }
+
+                        if let Some(beta) = beta {
+                            if beta * beta != beta_sq {
+                                return Err(de::Error::custom(
+                                    "`beta` and `beta_sq` are both given, but do not agree",
+                                ));
+                            }
+                        }
+
+                        // The legacy field is used as is, so that the rater
+                        // computes exactly as it did before it was stored.
+                        let mut rater = Rater::new(F::ZERO);
+                        rater.beta_sq = beta_sq;
+                        Ok(rater)
+                    }
+                    (None, None) => Err(de::Error::missing_field("beta")),
+                }
+            }
+        }
+
+        const FIELDS: &[&str] = &["beta", "beta_sq"];
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct("Rater", FIELDS, RaterVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_tuple(1, RaterVisitor(PhantomData))
+        }
+    }
+}
+
+impl<F: Float + Serialize> Serialize for RatingUpdate<F> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RatingUpdate", 4)?;
+        state.serialize_field("before", &self.before)?;
+        state.serialize_field("after", &self.after)?;
+        state.serialize_field("mu_delta", &self.mu_delta)?;
+        state.serialize_field("win_probability", &self.win_probability)?;
+        state.end()
+    }
+}
+
+impl<'de, F: Float + Deserialize<'de>> Deserialize<'de> for RatingUpdate<F> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Before,
+            After,
+            MuDelta,
+            WinProbability,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`before`, `after`, `mu_delta` or `win_probability`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "before" => Ok(Field::Before),
+                            "after" => Ok(Field::After),
+                            "mu_delta" => Ok(Field::MuDelta),
+                            "win_probability" => Ok(Field::WinProbability),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct UpdateVisitor<F>(PhantomData<F>);
+
+        impl<'de, F: Float + Deserialize<'de>> Visitor<'de> for UpdateVisitor<F> {
+            type Value = RatingUpdate<F>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct RatingUpdate")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<RatingUpdate<F>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let before = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let after = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let mu_delta = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let win_probability = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+
+                Ok(RatingUpdate {
+                    before,
+                    after,
+                    mu_delta,
+                    win_probability,
+                })
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<RatingUpdate<F>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut before = None;
+                let mut after = None;
+                let mut mu_delta = None;
+                let mut win_probability = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Before => {
+                            if before.is_some() {
+                                return Err(de::Error::duplicate_field("before"));
+                            }
+                            before = Some(map.next_value()?);
+                        }
+                        Field::After => {
+                            if after.is_some() {
+                                return Err(de::Error::duplicate_field("after"));
+                            }
+                            after = Some(map.next_value()?);
+                        }
+                        Field::MuDelta => {
+                            if mu_delta.is_some() {
+                                return Err(de::Error::duplicate_field("mu_delta"));
+                            }
+                            mu_delta = Some(map.next_value()?);
+                        }
+                        Field::WinProbability => {
+                            if win_probability.is_some() {
+                                return Err(de::Error::duplicate_field("win_probability"));
+                            }
+                            win_probability = Some(map.next_value()?);
+                        }
+                    }
+                }
+
+                Ok(RatingUpdate {
+                    before: before.ok_or_else(|| de::Error::missing_field("before"))?,
+                    after: after.ok_or_else(|| de::Error::missing_field("after"))?,
+                    mu_delta: mu_delta.ok_or_else(|| de::Error::missing_field("mu_delta"))?,
+                    win_probability: win_probability
+                        .ok_or_else(|| de::Error::missing_field("win_probability"))?,
+                })
+            }
+        }
+
+        const FIELDS: &[&str] = &["before", "after", "mu_delta", "win_probability"];
+        deserializer.deserialize_struct("RatingUpdate", FIELDS, UpdateVisitor(PhantomData))
+    }
+}
+impl Serialize for Outcome {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Outcome::Win => serializer.serialize_unit_variant("Outcome", 0, "Win"),
+            Outcome::Loss => serializer.serialize_unit_variant("Outcome", 1, "Loss"),
+            Outcome::Draw => serializer.serialize_unit_variant("Outcome", 2, "Draw"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Outcome {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VariantVisitor;
+
+        impl<'de> Visitor<'de> for VariantVisitor {
+            type Value = Outcome;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`Win`, `Loss` or `Draw`")
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Outcome, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    0 => Ok(Outcome::Win),
+                    1 => Ok(Outcome::Loss),
+                    2 => Ok(Outcome::Draw),
+                    _ => Err(de::Error::invalid_value(
+                        de::Unexpected::Unsigned(value),
+                        &"variant index 0 <= i < 3",
+                    )),
+                }
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Outcome, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "Win" => Ok(Outcome::Win),
+                    "Loss" => Ok(Outcome::Loss),
+                    "Draw" => Ok(Outcome::Draw),
+                    _ => Err(de::Error::unknown_variant(value, VARIANTS)),
+                }
+            }
+        }
+
+        struct Variant(Outcome);
+
+        impl<'de> Deserialize<'de> for Variant {
+            fn deserialize<D>(deserializer: D) -> Result<Variant, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer
+                    .deserialize_identifier(VariantVisitor)
+                    .map(Variant)
+            }
+        }
+
+        struct OutcomeVisitor;
+
+        impl<'de> Visitor<'de> for OutcomeVisitor {
+            type Value = Outcome;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("enum Outcome")
+            }
+
+            fn visit_enum<A>(self, data: A) -> Result<Outcome, A::Error>
+            where
+                A: de::EnumAccess<'de>,
+            {
+                let (Variant(outcome), variant) = data.variant()?;
+                de::VariantAccess::unit_variant(variant)?;
+                Ok(outcome)
             }
         }
 
-        const FIELDS: &[&str] = &["mu", "sigma"];
-        deserializer.deserialize_struct("Rating", FIELDS, RatingVisitor)
+        const VARIANTS: &[&str] = &["Win", "Loss", "Draw"];
+        deserializer.deserialize_enum("Outcome", VARIANTS, OutcomeVisitor)
     }
 }
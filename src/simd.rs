@@ -0,0 +1,92 @@
+use std::cmp::Ordering;
+
+use wide::{f64x4, CmpGt};
+
+/// The number of opponents that are processed at once.
+const LANES: usize = 4;
+
+/// The vectorized exponential returns zero instead of infinity for arguments
+/// above about 708.39. Larger arguments are handled separately; the scalar
+/// path computes win probabilities below 1e-307 for them.
+const MAX_EXP: f64 = 708.0;
+
+/// Computes the pairwise omega and delta terms of team `team_idx` against all
+/// other teams, four opponents at a time, and returns the number of terms.
+/// The terms are written in the same order as by the scalar path.
+///
+/// Returns `None` if β is zero, since pairs of fully certain teams then have
+/// a `c` of zero and have to be skipped, which the scalar path takes care of.
+///
+/// The terms are computed in a different order of operations than in the
+/// scalar path: the reciprocal of `c` is computed once per pair, and both
+/// win probabilities are derived from a single exponential. After summation,
+/// omega and delta differ from the scalar path by at most 1e-12 times the sum
+/// of the absolute values of their terms.
+pub fn pair_terms(
+    team_idx: usize,
+    team_mu: &[f64],
+    team_sigma_sq: &[f64],
+    ranks: &[usize],
+    beta_sq: f64,
+    terms: (&mut [f64], &mut [f64]),
+) -> Option<usize> {
+    if beta_sq == 0.0 {
+        return None;
+    }
+
+    let (omega_terms, delta_terms) = terms;
+    let n_teams = team_mu.len();
+
+    let mu_i = f64x4::splat(team_mu[team_idx]);
+    let sigma_sq_i = f64x4::splat(team_sigma_sq[team_idx]);
+    let sigma_i = sigma_sq_i.sqrt();
+    let two_beta_sq = f64x4::splat(2.0 * beta_sq);
+    let max_exp = f64x4::splat(MAX_EXP);
+
+    // The terms are first computed for every team including `team_idx`
+    // itself, whose entry is removed at the end.
+    for start in (0..n_teams).step_by(LANES) {
+        let lanes = (n_teams - start).min(LANES);
+
+        let mut mu_q = [0.0; LANES];
+        let mut sigma_sq_q = [0.0; LANES];
+        let mut s = [0.0; LANES];
+
+        mu_q[..lanes].copy_from_slice(&team_mu[start..start + lanes]);
+        sigma_sq_q[..lanes].copy_from_slice(&team_sigma_sq[start..start + lanes]);
+
+        for (lane, s) in s[..lanes].iter_mut().enumerate() {
+            *s = match ranks[start + lane].cmp(&ranks[team_idx]) {
+                Ordering::Greater => 1.0,
+                Ordering::Equal => 0.5,
+                Ordering::Less => 0.0,
+            };
+        }
+
+        let c = (sigma_sq_i + f64x4::from(sigma_sq_q) + two_beta_sq).sqrt();
+        let inv_c = f64x4::ONE / c;
+
+        // piq = 1 / (1 + e) and pqi = e / (1 + e), with e = exp((mu_q - mu_i) / c).
+        // The exponential returns zero instead of infinity for large arguments,
+        // so those lanes are set to the limits directly.
+        let x = (f64x4::from(mu_q) - mu_i) * inv_c;
+        let overflows = x.cmp_gt(max_exp);
+        let e = x.min(max_exp).exp();
+        let piq = f64x4::ONE / (f64x4::ONE + e);
+        let pqi = e * piq;
+        let piq = overflows.blend(f64x4::ZERO, piq);
+        let pqi = overflows.blend(f64x4::ONE, pqi);
+
+        let sigma_sq_c = sigma_sq_i * inv_c;
+        let delta = sigma_sq_c * (f64x4::from(s) - piq);
+        let eta = (sigma_i * inv_c) * (sigma_sq_c * inv_c) * piq * pqi;
+
+        omega_terms[start..start + lanes].copy_from_slice(&delta.to_array()[..lanes]);
+        delta_terms[start..start + lanes].copy_from_slice(&eta.to_array()[..lanes]);
+    }
+
+    omega_terms.copy_within(team_idx + 1..n_teams, team_idx);
+    delta_terms.copy_within(team_idx + 1..n_teams, team_idx);
+
+    Some(n_teams - 1)
+}
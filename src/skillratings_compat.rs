@@ -0,0 +1,37 @@
+//! Conversions between bbt and the Weng-Lin types of the `skillratings`
+//! crate, which implements the same paper. The fields correspond as follows:
+//!
+//! | `skillratings`                    | bbt             |
+//! | --------------------------------- | --------------- |
+//! | `WengLinRating::rating`           | `Rating::mu`    |
+//! | `WengLinRating::uncertainty`      | `Rating::sigma` |
+//! | `WengLinConfig::beta`             | `Rater::beta`   |
+//!
+//! Both crates default to a mu of 25, a sigma of 25/3 and a β of 25/6.
+//! `WengLinConfig::uncertainty_tolerance` has no counterpart: bbt always
+//! keeps at least 0.0001 of a player's variance.
+
+use skillratings::weng_lin::{WengLinConfig, WengLinRating};
+
+use {Rater, Rating};
+
+impl From<WengLinRating> for Rating {
+    fn from(rating: WengLinRating) -> Rating {
+        Rating::new(rating.rating, rating.uncertainty)
+    }
+}
+
+impl From<Rating> for WengLinRating {
+    fn from(rating: Rating) -> WengLinRating {
+        WengLinRating {
+            rating: rating.mu,
+            uncertainty: rating.sigma,
+        }
+    }
+}
+
+impl From<WengLinConfig> for Rater {
+    fn from(config: WengLinConfig) -> Rater {
+        Rater::new(config.beta)
+    }
+}
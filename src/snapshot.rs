@@ -0,0 +1,390 @@
+//! A binary file format for the ratings of a whole population, enabled by
+//! the `std` feature.
+//!
+//! A snapshot is written with `write` and read back with `read`. Every
+//! rating is stored as its mu and sigma, without rounding, and a checksum
+//! over the whole file detects damaged snapshots.
+//!
+//! ```rust
+//! use bbt::{snapshot, Rating};
+//!
+//! let ratings = vec![("alice", Rating::new(27.5, 4.0)), ("bob", Rating::default())];
+//!
+//! let mut file = Vec::new();
+//! snapshot::write(&mut file, ratings.iter().map(|(id, r)| (id, r))).unwrap();
+//!
+//! let read = snapshot::read(&file[..]).unwrap();
+//! assert_eq!(read[0], ("alice".to_string(), Rating::new(27.5, 4.0)));
+//! assert_eq!(read.len(), 2);
+//! ```
+//!
+//! ## Format
+//!
+//! All numbers are little-endian. A snapshot starts with a header:
+//!
+//! | Bytes | Field                                                       |
+//! |-------|-------------------------------------------------------------|
+//! | 8     | The magic bytes `BBTSNAP\0`.                                |
+//! | 2     | The major version, 1.                                       |
+//! | 2     | The minor version, 0.                                       |
+//! | 4     | The length of the header in bytes, 32 in version 1.0.       |
+//! | 8     | The number of records.                                      |
+//! | 4     | The width of the id field of a record in bytes.             |
+//! | 4     | The length of a record in bytes, `id width + 18` in 1.0.    |
+//!
+//! It is followed by the records, which all have the same length: the
+//! length of the id as two bytes, the id in UTF-8, padded with zeros to the
+//! width of the id field, and the rating as encoded by `Rating::to_bytes`.
+//! The snapshot ends with the CRC-32 (as used by zlib and PNG) of all bytes
+//! before it.
+//!
+//! Minor versions only add fields at the end of the header or of a record,
+//! which readers of an older minor version skip, so they can read every
+//! snapshot of the same major version. They add at most 1024 bytes to a
+//! record; longer records are rejected as damaged. Snapshots of another major version
+//! are rejected.
+
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use Rating;
+
+/// The magic bytes that every snapshot starts with.
+const MAGIC: [u8; 8] = *b"BBTSNAP\0";
+
+/// The major version of the format, which is changed for changes that older
+/// readers cannot read.
+pub const MAJOR_VERSION: u16 = 1;
+
+/// The minor version of the format, which is changed for additions that
+/// older readers can skip.
+pub const MINOR_VERSION: u16 = 0;
+
+/// The length of the header of version 1.0.
+const HEADER_LEN: u32 = 32;
+
+/// The length of a record of version 1.0 without its id field.
+const RECORD_FIELDS_LEN: u32 = 2 + 16;
+
+/// The number of bytes that newer minor versions may add to a record. Longer
+/// records are rejected, so that a damaged header cannot make the reader
+/// allocate a huge record.
+const MAX_RECORD_EXTENSION_LEN: usize = 1024;
+
+/// SnapshotError describes the ways in which reading a snapshot can fail.
+/// Record indices start at zero.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// Reading the snapshot failed, or it ended early.
+    Io(io::Error),
+
+    /// The snapshot does not start with the magic bytes, so it is not a
+    /// snapshot at all.
+    NotASnapshot,
+
+    /// The snapshot has a major version that this version of the crate
+    /// cannot read.
+    UnsupportedVersion { major: u16, minor: u16 },
+
+    /// The header describes a layout that is impossible, such as records
+    /// that are too short for their fields.
+    InvalidHeader,
+
+    /// The id of the record is longer than its field, or not valid UTF-8.
+    InvalidId { index: u64 },
+
+    /// The rating of the record is not finite or has a negative sigma.
+    InvalidRating { index: u64 },
+
+    /// The checksum at the end of the snapshot does not match its contents,
+    /// so the snapshot was damaged.
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SnapshotError::Io(ref error) => write!(f, "I/O error: {}", error),
+            SnapshotError::NotASnapshot => write!(f, "The data is not a snapshot"),
+            SnapshotError::UnsupportedVersion { major, minor } => write!(
+                f,
+                "The snapshot has version {}.{}, but only version {}.x can be read",
+                major, minor, MAJOR_VERSION
+            ),
+            SnapshotError::InvalidHeader => write!(f, "The header of the snapshot is invalid"),
+            SnapshotError::InvalidId { index } => {
+                write!(f, "The id of record {} is invalid", index)
+            }
+            SnapshotError::InvalidRating { index } => write!(
+                f,
+                "The rating of record {} is not finite or has a negative sigma",
+                index
+            ),
+            SnapshotError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "The snapshot is damaged: its checksum is {:08x}, but its contents have {:08x}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            SnapshotError::Io(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> SnapshotError {
+        SnapshotError::Io(error)
+    }
+}
+
+/// Writes a snapshot of the given ratings, in the order of `entries`. The
+/// snapshot is written a record at a time, so a file should be wrapped in a
+/// `BufWriter`.
+///
+/// Returns an error of kind `io::ErrorKind::InvalidInput` without writing
+/// anything if an id is longer than 65535 bytes or a rating is not valid
+/// (see `Rating::is_valid`).
+pub fn write<'a, W, I, D>(mut w: W, entries: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (D, &'a Rating)>,
+    D: AsRef<str>,
+{
+    let entries: Vec<(D, &Rating)> = entries.into_iter().collect();
+
+    let mut id_width = 0;
+    for (id, rating) in &entries {
+        if id.as_ref().len() > usize::from(u16::MAX) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the id is longer than 65535 bytes",
+            ));
+        }
+        if !rating.is_valid() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the rating is not finite or has a negative sigma",
+            ));
+        }
+        id_width = id_width.max(id.as_ref().len());
+    }
+
+    let mut w = ChecksumWriter {
+        inner: &mut w,
+        crc: Crc32::new(),
+    };
+
+    w.write_all(&MAGIC)?;
+    w.write_all(&MAJOR_VERSION.to_le_bytes())?;
+    w.write_all(&MINOR_VERSION.to_le_bytes())?;
+    w.write_all(&HEADER_LEN.to_le_bytes())?;
+    w.write_all(&(entries.len() as u64).to_le_bytes())?;
+    w.write_all(&(id_width as u32).to_le_bytes())?;
+    w.write_all(&(id_width as u32 + RECORD_FIELDS_LEN).to_le_bytes())?;
+
+    let mut record = vec![0; id_width + RECORD_FIELDS_LEN as usize];
+    for (id, rating) in &entries {
+        let id = id.as_ref().as_bytes();
+
+        record.fill(0);
+        record[..2].copy_from_slice(&(id.len() as u16).to_le_bytes());
+        record[2..2 + id.len()].copy_from_slice(id);
+        record[2 + id_width..].copy_from_slice(&rating.to_bytes());
+
+        w.write_all(&record)?;
+    }
+
+    let crc = w.crc.finish();
+    w.inner.write_all(&crc.to_le_bytes())?;
+    w.inner.flush()
+}
+
+/// Reads a snapshot written by `write`, or by a version of the crate that
+/// writes the same major version of the format, and returns its ratings in
+/// the order they were written.
+///
+/// The checksum is only known at the end of the snapshot, so the whole
+/// snapshot is read before any rating is returned. Like `write`, this reads
+/// a record at a time, so a file should be wrapped in a `BufReader`.
+pub fn read<R: Read>(r: R) -> Result<Vec<(String, Rating)>, SnapshotError> {
+    let mut r = ChecksumReader {
+        inner: r,
+        crc: Crc32::new(),
+    };
+
+    let mut start = [0; 16];
+    r.read_exact(&mut start).map_err(|error| {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            SnapshotError::NotASnapshot
+        } else {
+            SnapshotError::Io(error)
+        }
+    })?;
+
+    if start[..8] != MAGIC {
+        return Err(SnapshotError::NotASnapshot);
+    }
+
+    let major = u16::from_le_bytes(start[8..10].try_into().unwrap());
+    let minor = u16::from_le_bytes(start[10..12].try_into().unwrap());
+    if major != MAJOR_VERSION {
+        return Err(SnapshotError::UnsupportedVersion { major, minor });
+    }
+
+    let header_len = u32::from_le_bytes(start[12..].try_into().unwrap());
+    if header_len < HEADER_LEN {
+        return Err(SnapshotError::InvalidHeader);
+    }
+
+    let mut header = [0; 16];
+    r.read_exact(&mut header)?;
+    // Fields that were added to the header by newer minor versions.
+    io::copy(
+        &mut (&mut r).take(u64::from(header_len - HEADER_LEN)),
+        &mut io::sink(),
+    )?;
+
+    let count = u64::from_le_bytes(header[..8].try_into().unwrap());
+    let id_width = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+    let record_len = u32::from_le_bytes(header[12..].try_into().unwrap()) as usize;
+
+    // Ids are at most 65535 bytes long, so a wider id field is damaged.
+    if id_width > usize::from(u16::MAX) {
+        return Err(SnapshotError::InvalidHeader);
+    }
+
+    let known_len = id_width + RECORD_FIELDS_LEN as usize;
+    if record_len < known_len || record_len > known_len + MAX_RECORD_EXTENSION_LEN {
+        return Err(SnapshotError::InvalidHeader);
+    }
+
+    // The count is not trusted until the records have been read, so a
+    // damaged header cannot make the reader allocate a huge table up front.
+    let mut ratings = Vec::with_capacity(count.min(1 << 16) as usize);
+    let mut record = vec![0; record_len];
+
+    for index in 0..count {
+        r.read_exact(&mut record)?;
+
+        let id_len = usize::from(u16::from_le_bytes(record[..2].try_into().unwrap()));
+        if id_len > id_width {
+            return Err(SnapshotError::InvalidId { index });
+        }
+        let id = String::from_utf8(record[2..2 + id_len].to_vec())
+            .map_err(|_| SnapshotError::InvalidId { index })?;
+
+        // Not `Rating::from_bytes`, which rejects fully certain ratings.
+        let rating = &record[2 + id_width..];
+        let mu = f64::from_le_bytes(rating[..8].try_into().unwrap());
+        let sigma = f64::from_le_bytes(rating[8..16].try_into().unwrap());
+
+        let rating = Rating::new(mu, sigma);
+        if !rating.is_valid() {
+            return Err(SnapshotError::InvalidRating { index });
+        }
+
+        ratings.push((id, rating));
+    }
+
+    let actual = r.crc.finish();
+    let mut expected = [0; 4];
+    r.inner.read_exact(&mut expected)?;
+    let expected = u32::from_le_bytes(expected);
+
+    if expected != actual {
+        return Err(SnapshotError::ChecksumMismatch { expected, actual });
+    }
+
+    Ok(ratings)
+}
+
+/// The CRC-32 of zlib and PNG, with the reflected polynomial `0xEDB88320`.
+struct Crc32 {
+    state: u32,
+}
+
+/// The CRC of every byte, for computing the CRC a byte at a time.
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut byte = 0;
+
+    while byte < 256 {
+        let mut crc = byte as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[byte] = crc;
+        byte += 1;
+    }
+
+    table
+}
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        Crc32 { state: !0 }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state =
+                CRC_TABLE[((self.state ^ u32::from(byte)) & 0xFF) as usize] ^ (self.state >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+/// Computes the CRC of everything written through it.
+struct ChecksumWriter<W> {
+    inner: W,
+    crc: Crc32,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(bytes)?;
+        self.crc.update(&bytes[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Computes the CRC of everything read through it.
+struct ChecksumReader<R> {
+    inner: R,
+    crc: Crc32,
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, bytes: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(bytes)?;
+        self.crc.update(&bytes[..read]);
+        Ok(read)
+    }
+}
@@ -0,0 +1,222 @@
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::vec::Vec;
+
+#[cfg(all(feature = "alloc", feature = "strict-math"))]
+use check_team_updates;
+#[cfg(feature = "alloc")]
+use {apply_team_updates, canonical_sum, pair_terms, with_workspace};
+use {BBTError, Float, Rater, Rating};
+
+/// SoftScores are the scores of the teams of a match rated with
+/// `Rater::update_ratings_soft`. A score of one against an opponent counts as
+/// a win, one half as a draw and zero as a loss, and anything in between as
+/// a partial result, such as the share of rounds won or a judge's decision.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SoftScores<F: Float = f64> {
+    /// The score of every team against every other team, where
+    /// `scores[i][q]` is the score of team `i` against team `q`. Both scores
+    /// of a pair are used as given, so they should add up to one. The
+    /// diagonal is ignored.
+    Pairwise(Vec<Vec<F>>),
+
+    /// The score of every team, which it achieved against every one of its
+    /// opponents.
+    PerTeam(Vec<F>),
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Float> SoftScores<F> {
+    /// Returns the number of teams the scores are given for.
+    fn len(&self) -> usize {
+        match *self {
+            SoftScores::Pairwise(ref scores) => scores.len(),
+            SoftScores::PerTeam(ref scores) => scores.len(),
+        }
+    }
+
+    /// Returns the score of team `team` against team `opponent`.
+    fn score(&self, team: usize, opponent: usize) -> F {
+        match *self {
+            SoftScores::Pairwise(ref scores) => scores[team][opponent],
+            SoftScores::PerTeam(ref scores) => scores[team],
+        }
+    }
+
+    /// Checks that every row of a score matrix has a score for every team,
+    /// and that every score that is used is between zero and one.
+    fn check(&self) -> Result<(), BBTError> {
+        let n_teams = self.len();
+
+        if let SoftScores::Pairwise(ref scores) = *self {
+            if scores.iter().any(|row| row.len() != n_teams) {
+                return Err(BBTError::InvalidParameter { name: "scores" });
+            }
+        }
+
+        for team in 0..n_teams {
+            for opponent in (0..n_teams).filter(|&opponent| opponent != team) {
+                if !is_score(self.score(team, opponent)) {
+                    return Err(BBTError::InvalidParameter { name: "scores" });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Float> From<Vec<Vec<F>>> for SoftScores<F> {
+    fn from(scores: Vec<Vec<F>>) -> SoftScores<F> {
+        SoftScores::Pairwise(scores)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Float> From<Vec<F>> for SoftScores<F> {
+    fn from(scores: Vec<F>) -> SoftScores<F> {
+        SoftScores::PerTeam(scores)
+    }
+}
+
+/// Returns whether `score` is between zero and one, which NaN is not.
+fn is_score<F: Float>(score: F) -> bool {
+    score >= F::ZERO && score <= F::ONE
+}
+
+impl<F: Float> Rater<F> {
+    /// This method rates a duel like `duel`, but with the score `p1` achieved
+    /// instead of an outcome: one is a win, one half a draw and zero a loss,
+    /// and `p2` achieved one minus the score. Scores of one, one half and
+    /// zero give exactly the ratings of `Win`, `Draw` and `Loss`.
+    ///
+    /// Returns `BBTError::InvalidParameter` if the score is not between zero
+    /// and one, and `BBTError::InvalidRating` if either rating is invalid
+    /// (see `Rating::is_valid`). The ratings are unchanged in either case.
+    ///
+    /// Soft results are never surprising, so the ratings are not inflated by
+    /// `with_volatility`.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let mut alice = Rating::default();
+    /// let mut bob = Rating::default();
+    ///
+    /// // Alice won 7 of 10 rounds.
+    /// rater.duel_soft(&mut alice, &mut bob, 0.7).unwrap();
+    ///
+    /// let (winner, _) = rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+    /// assert!(alice.mu() > Rating::<f64>::default().mu());
+    /// assert!(alice.mu() < winner.mu());
+    /// ```
+    pub fn duel_soft(
+        &self,
+        p1: &mut Rating<F>,
+        p2: &mut Rating<F>,
+        score: F,
+    ) -> Result<(), BBTError> {
+        if !is_score(score) {
+            return Err(BBTError::InvalidParameter { name: "score" });
+        }
+
+        // The two players are reported as teams of one, like in a match.
+        for (team, rating) in [*p1, *p2].iter().enumerate() {
+            if !rating.is_valid() {
+                return Err(BBTError::InvalidRating { team, player: 0 });
+            }
+        }
+
+        let ((new_p1, new_p2), _clamps) = self.duel_scored(*p1, *p2, score);
+        *p1 = new_p1;
+        *p2 = new_p2;
+
+        Ok(())
+    }
+
+    /// This method updates the ratings of a match like `update_ratings`, but
+    /// with the scores the teams achieved against each other instead of
+    /// their ranks. Scores of one, one half and zero give exactly the
+    /// ratings of the corresponding ranks.
+    ///
+    /// Returns the errors of `update_ratings`, with `BBTError::MismatchedLengths`
+    /// reporting the number of scores as `ranks`, and
+    /// `BBTError::InvalidParameter` if a row of a score matrix does not have
+    /// a score for every team or a score is not between zero and one.
+    ///
+    /// As with `duel_soft`, the ratings are not inflated by
+    /// `with_volatility`.
+    ///
+    /// ```rust
+    /// use bbt::{Rater, Rating};
+    ///
+    /// let rater = Rater::default();
+    /// let teams = vec![vec![Rating::default(); 2], vec![Rating::default(); 2]];
+    ///
+    /// // The first team won 60% of the points.
+    /// let teams = rater.update_ratings_soft(teams, vec![0.6, 0.4]).unwrap();
+    /// assert!(teams[0][0].mu() > teams[1][0].mu());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn update_ratings_soft<S: Into<SoftScores<F>>>(
+        &self,
+        mut teams: Vec<Vec<Rating<F>>>,
+        scores: S,
+    ) -> Result<Vec<Vec<Rating<F>>>, BBTError> {
+        let scores = scores.into();
+        let n_teams = teams.len();
+
+        if scores.len() != n_teams {
+            return Err(BBTError::MismatchedLengths {
+                teams: n_teams,
+                ranks: scores.len(),
+            });
+        }
+
+        scores.check()?;
+
+        with_workspace(n_teams, |work| {
+            self.validate_into(&teams, &vec![1; n_teams], work)?;
+
+            let two_beta_sq = F::from_f64(2.0) * self.beta_sq;
+
+            for team in 0..n_teams {
+                let sigma_sq = work.team_sigma_sq[team];
+                let sigma = self.sqrt(sigma_sq);
+                let mut n_terms = 0;
+
+                for opponent in 0..n_teams {
+                    if opponent == team {
+                        continue;
+                    }
+
+                    let c = self.sqrt(sigma_sq + work.team_sigma_sq[opponent] + two_beta_sq);
+                    if c == F::ZERO {
+                        continue;
+                    }
+
+                    let piq = self.logistic(work.team_mu[team] - work.team_mu[opponent], c);
+                    let pqi = self.logistic(work.team_mu[opponent] - work.team_mu[team], c);
+                    let s = scores.score(team, opponent);
+
+                    let (delta, eta) = pair_terms(sigma_sq, sigma, c, piq, pqi, s);
+                    work.omega_terms[n_terms] = delta;
+                    work.delta_terms[n_terms] = eta;
+                    n_terms += 1;
+                }
+
+                work.team_omega[team] = canonical_sum(&mut work.omega_terms[..n_terms]);
+                work.team_delta[team] = canonical_sum(&mut work.delta_terms[..n_terms]);
+            }
+
+            #[cfg(feature = "strict-math")]
+            check_team_updates(&teams, work)?;
+
+            apply_team_updates(&mut teams, work, self.write_back(), |_, _| ());
+
+            Ok(teams)
+        })
+    }
+}
@@ -0,0 +1,66 @@
+//! Grouping of leaderboard positions that cannot be told apart.
+//!
+//! A leaderboard sorted by conservative estimate shows a strict order even
+//! where the ratings are too uncertain to support it. `group_ties` finds the
+//! runs of a sorted leaderboard whose players are statistical ties, as
+//! decided by `Rating::is_statistical_tie`, so they can be displayed with a
+//! shared rank.
+//!
+//! ```rust
+//! use bbt::Rating;
+//!
+//! let standings = [
+//!     Rating::new(30.0, 1.0),
+//!     Rating::new(29.0, 1.0),
+//!     Rating::new(20.0, 1.0),
+//! ];
+//!
+//! assert_eq!(bbt::sort::group_ties(&standings, 2.0), vec![0..2, 2..3]);
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+use std::ops::Range;
+
+use {Float, Rating};
+
+/// Returns the groups of tied players of a leaderboard, as contiguous ranges
+/// of indices into `standings` that cover all of it, in order.
+///
+/// `standings` should be sorted by conservative estimate. Two players are
+/// tied if `Rating::is_statistical_tie` holds for them with the given `z`,
+/// and ties are transitive: a group is the transitive closure of the ties of
+/// its players, so if A ties with B and B ties with C, all three are grouped
+/// even if A and C are not tied. Since groups are contiguous, a group also
+/// contains every player sorted between two of its players, even if none of
+/// them tie with their neighbours.
+///
+/// Every pair of players may be compared, so this takes quadratic time in
+/// the length of `standings`.
+pub fn group_ties<F: Float>(standings: &[Rating<F>], z: F) -> Vec<Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+
+    while start < standings.len() {
+        let mut end = start + 1;
+        let mut member = start;
+
+        // Extends the group to the last player that ties with any of its
+        // members, including the ones added along the way.
+        while member < end {
+            if let Some(last) = (end..standings.len())
+                .rev()
+                .find(|&other| standings[member].is_statistical_tie(&standings[other], z))
+            {
+                end = last + 1;
+            }
+
+            member += 1;
+        }
+
+        groups.push(start..end);
+        start = end;
+    }
+
+    groups
+}
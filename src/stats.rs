@@ -0,0 +1,497 @@
+//! Where a rating stands within a population of ratings, and how the
+//! population as a whole is distributed.
+//!
+//! Ratings are compared by a `Metric`, either the conservative estimate
+//! `mu - 3 * sigma` that leaderboards usually show, or mu itself.
+//!
+//! ```rust
+//! use bbt::stats::{self, Metric};
+//! use bbt::Rating;
+//!
+//! let population: Vec<Rating> = (0..10).map(|i| Rating::new(i as f64, 0.0)).collect();
+//!
+//! let rank = stats::percentile_rank(&population, &Rating::new(7.5, 0.0), Metric::Mu).unwrap();
+//! assert_eq!(rank, 0.8);
+//! ```
+
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use {BBTError, CompensatedSum, Float, Outcome, Rater, Rating};
+
+/// Metric is the value that ratings are compared by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// The conservative estimate `mu - 3 * sigma`.
+    ConservativeEstimate,
+    /// The estimated skill, ignoring its uncertainty.
+    Mu,
+}
+
+impl Metric {
+    /// Returns the value of the rating under this metric.
+    pub fn value(self, rating: &Rating) -> f64 {
+        match self {
+            Metric::ConservativeEstimate => rating.conservative_estimate(),
+            Metric::Mu => rating.mu,
+        }
+    }
+}
+
+/// Returns the fraction of the population that `r` is ahead of, between 0
+/// and 1.
+///
+/// Ties are counted as half below and half above (the midrank convention),
+/// so a rating that equals every member of the population is at 0.5, and a
+/// population member is never at 0 or 1 unless it is alone in its
+/// population. Ratings whose value is NaN are neither below nor tied with
+/// anything.
+///
+/// Returns `BBTError::InvalidParameter` if the population is empty.
+pub fn percentile_rank(population: &[Rating], r: &Rating, metric: Metric) -> Result<f64, BBTError> {
+    if population.is_empty() {
+        return Err(BBTError::InvalidParameter { name: "population" });
+    }
+
+    let value = metric.value(r);
+    let mut below = 0;
+    let mut tied = 0;
+
+    for member in population {
+        let member = metric.value(member);
+
+        if member < value {
+            below += 1;
+        } else if member == value {
+            tied += 1;
+        }
+    }
+
+    Ok((below as f64 + 0.5 * tied as f64) / population.len() as f64)
+}
+
+/// Returns the value that a rating needs to be at percentile `p` of the
+/// population, where `p` is between 0 and 1.
+///
+/// The values of the population are sorted and `p` is mapped linearly onto
+/// them, interpolating between neighbours: 0 is the lowest value, 1 the
+/// highest and 0.5 the median. Note that this is not quite the inverse of
+/// `percentile_rank`, which never places the lowest member at 0.
+///
+/// Returns `BBTError::InvalidParameter` if the population is empty or if
+/// `p` is not between 0 and 1.
+#[cfg(feature = "alloc")]
+pub fn percentile_threshold(
+    population: &[Rating],
+    p: f64,
+    metric: Metric,
+) -> Result<f64, BBTError> {
+    if population.is_empty() {
+        return Err(BBTError::InvalidParameter { name: "population" });
+    }
+
+    if !(0.0..=1.0).contains(&p) {
+        return Err(BBTError::InvalidParameter { name: "p" });
+    }
+
+    Ok(quantile(&sorted_values(population, metric), p))
+}
+
+/// PopulationSummary describes the distribution of a population of ratings.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PopulationSummary {
+    /// The number of ratings.
+    pub count: usize,
+    /// The mean of the mus.
+    pub mean_mu: f64,
+    /// The standard deviation of the mus, treating the ratings as the whole
+    /// population rather than a sample of it.
+    pub std_mu: f64,
+    /// The mean of the sigmas.
+    pub mean_sigma: f64,
+    /// The lowest conservative estimate.
+    pub min_estimate: f64,
+    /// The conservative estimate at the 25th percentile.
+    pub lower_quartile_estimate: f64,
+    /// The median conservative estimate.
+    pub median_estimate: f64,
+    /// The conservative estimate at the 75th percentile.
+    pub upper_quartile_estimate: f64,
+    /// The highest conservative estimate.
+    pub max_estimate: f64,
+}
+
+/// Summarizes a population of ratings. The quantiles are interpolated like
+/// those of `percentile_threshold`.
+///
+/// Returns `BBTError::InvalidParameter` if the population is empty.
+#[cfg(feature = "alloc")]
+pub fn population_summary(population: &[Rating]) -> Result<PopulationSummary, BBTError> {
+    if population.is_empty() {
+        return Err(BBTError::InvalidParameter { name: "population" });
+    }
+
+    let (mean_mu, std_mu) = mu_moments(population);
+
+    let mut sigma = CompensatedSum::default();
+    for rating in population {
+        sigma.add(rating.sigma);
+    }
+
+    let estimates = sorted_values(population, Metric::ConservativeEstimate);
+
+    Ok(PopulationSummary {
+        count: population.len(),
+        mean_mu,
+        std_mu,
+        mean_sigma: sigma.total() / population.len() as f64,
+        min_estimate: estimates[0],
+        lower_quartile_estimate: quantile(&estimates, 0.25),
+        median_estimate: quantile(&estimates, 0.5),
+        upper_quartile_estimate: quantile(&estimates, 0.75),
+        max_estimate: estimates[estimates.len() - 1],
+    })
+}
+
+/// SosReport is the strength of the schedule of a player, as computed by
+/// `strength_of_schedule`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SosReport {
+    /// The number of games.
+    pub games: usize,
+    /// The mean mu of the opponents.
+    pub mean_mu: f64,
+    /// The mean conservative estimate `mu - 3 * sigma` of the opponents.
+    pub mean_conservative: f64,
+    /// The mean probability that an opponent beats a new player, rated
+    /// `Rating::default()`. Unlike the means, it is not dominated by a
+    /// single very strong or very weak opponent, since every game counts
+    /// between zero and one.
+    pub opponent_win_probability: f64,
+    /// The mean score of the player: one for a win, one half for a draw and
+    /// zero for a loss.
+    pub score: f64,
+}
+
+/// Returns the strength of a player's schedule, from the ratings of their
+/// opponents before each game and the outcomes from the player's
+/// perspective. Higher values are a tougher schedule. With the `serde`
+/// feature, `replay::schedule` extracts the games of a player from a
+/// history.
+///
+/// Returns `BBTError::InvalidParameter` if there are no games, since an
+/// empty schedule has no strength.
+///
+/// ```rust
+/// use bbt::stats;
+/// use bbt::{Outcome, Rater, Rating};
+///
+/// let games = [(Rating::new(30.0, 2.0), Outcome::Win), (Rating::new(20.0, 2.0), Outcome::Draw)];
+/// let report = stats::strength_of_schedule(&Rater::default(), &games).unwrap();
+///
+/// assert_eq!(report.mean_mu, 25.0);
+/// assert_eq!(report.mean_conservative, 19.0);
+/// assert_eq!(report.score, 0.75);
+/// ```
+pub fn strength_of_schedule(
+    rater: &Rater,
+    player_games: &[(Rating, Outcome)],
+) -> Result<SosReport, BBTError> {
+    if player_games.is_empty() {
+        return Err(BBTError::InvalidParameter {
+            name: "player_games",
+        });
+    }
+
+    let new_player = Rating::default();
+    let mut mu = CompensatedSum::default();
+    let mut conservative = CompensatedSum::default();
+    let mut win_probability = CompensatedSum::default();
+    let mut score = CompensatedSum::default();
+
+    for (opponent, outcome) in player_games {
+        mu.add(opponent.mu);
+        conservative.add(Metric::ConservativeEstimate.value(opponent));
+        win_probability.add(rater.win_probability(opponent, &new_player));
+        score.add(match outcome {
+            Outcome::Win => 1.0,
+            Outcome::Draw => 0.5,
+            Outcome::Loss => 0.0,
+        });
+    }
+
+    let games = player_games.len() as f64;
+
+    Ok(SosReport {
+        games: player_games.len(),
+        mean_mu: mu.total() / games,
+        mean_conservative: conservative.total() / games,
+        opponent_win_probability: win_probability.total() / games,
+        score: score.total() / games,
+    })
+}
+
+/// Returns the volatility of a player at the end of their rating history:
+/// the standard deviation of the changes of mu between consecutive ratings,
+/// over the last `window` changes. The history is in chronological order,
+/// as kept by a `RatingHistory`. Two players with the same current rating
+/// can differ widely in volatility, if one of them keeps swinging up and
+/// down.
+///
+/// If the history has fewer than `window` changes, all of them are used. A
+/// history without changes, because it has fewer than two ratings or the
+/// window is zero, has a volatility of zero. Like that of
+/// `PopulationSummary`, the standard deviation treats the changes as the
+/// whole population rather than a sample of it.
+///
+/// ```rust
+/// use bbt::stats;
+/// use bbt::Rating;
+///
+/// let history: Vec<Rating> = [25.0, 27.0, 25.0, 27.0].iter().map(|&mu| Rating::new(mu, 2.0)).collect();
+///
+/// // The changes are +2, -2 and +2.
+/// let volatility = stats::volatility(&history, 2);
+/// assert_eq!(volatility, 2.0);
+/// ```
+pub fn volatility(history: &[Rating], window: usize) -> f64 {
+    let changes = window.min(history.len().saturating_sub(1));
+    if changes == 0 {
+        return 0.0;
+    }
+
+    let recent = &history[history.len() - changes - 1..];
+    let change = |i: usize| recent[i + 1].mu - recent[i].mu;
+    let n = changes as f64;
+
+    let mut sum = CompensatedSum::default();
+    for i in 0..changes {
+        sum.add(change(i));
+    }
+    let mean = sum.total() / n;
+
+    let mut squares = CompensatedSum::default();
+    for i in 0..changes {
+        squares.add((change(i) - mean) * (change(i) - mean));
+    }
+
+    Float::sqrt(squares.total() / n)
+}
+
+/// Returns the volatility of the player after every rating of their
+/// history, for plotting: element `i` is the `volatility` of the history up
+/// to and including rating `i`. The series has one element per rating, the
+/// first of which is zero, and its early elements use fewer changes than
+/// the window while the history is shorter than it.
+#[cfg(feature = "alloc")]
+pub fn volatility_series(history: &[Rating], window: usize) -> Vec<f64> {
+    (1..=history.len())
+        .map(|end| volatility(&history[..end], window))
+        .collect()
+}
+
+/// Shifts every mu so that their mean becomes `target_mean`, to undo the
+/// drift of a population whose ratings inflated or deflated over time.
+///
+/// With a `target_std`, the mus are also spread around the new mean so that
+/// their standard deviation becomes `target_std`, and every sigma is scaled
+/// by the same factor, since it is measured in the same units as mu. The
+/// win probabilities of the model only stay the same if the `beta` of the
+/// `Rater` is scaled by that factor as well. If all mus are equal, there is
+/// no spread to scale, and they are only shifted.
+///
+/// Both corrections are increasing affine maps, so the order of the ratings
+/// by mu, and by conservative estimate, is preserved. Rounding may at most
+/// turn two mus that differ by a few ulps into equal ones.
+///
+/// Returns `BBTError::InvalidParameter` if `target_mean` is not finite or if
+/// `target_std` is not positive and finite.
+pub fn renormalize(
+    ratings: &mut [Rating],
+    target_mean: f64,
+    target_std: Option<f64>,
+) -> Result<(), BBTError> {
+    if !target_mean.is_finite() {
+        return Err(BBTError::InvalidParameter {
+            name: "target_mean",
+        });
+    }
+
+    if let Some(target_std) = target_std {
+        if !(target_std.is_finite() && target_std > 0.0) {
+            return Err(BBTError::InvalidParameter { name: "target_std" });
+        }
+    }
+
+    if ratings.is_empty() {
+        return Ok(());
+    }
+
+    let (mean, std) = mu_moments(ratings);
+    let scale = match target_std {
+        Some(target_std) if std > 0.0 => target_std / std,
+        _ => 1.0,
+    };
+
+    for rating in ratings.iter_mut() {
+        rating.mu = target_mean + (rating.mu - mean) * scale;
+
+        if scale != 1.0 {
+            rating.sigma *= scale;
+            rating.sigma_sq = rating.sigma * rating.sigma;
+        }
+    }
+
+    Ok(())
+}
+
+/// Movement is how a player's place in the standings changed between two
+/// snapshots, as computed by `standings_diff`. Ranks start at one.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Movement<K> {
+    /// The player.
+    pub id: K,
+    /// The rank in the earlier snapshot, if the player was in it.
+    pub old_rank: Option<usize>,
+    /// The rank in the later snapshot, if the player is in it.
+    pub new_rank: Option<usize>,
+    /// The number of places the player climbed, negative if they fell. Only
+    /// set for players in both snapshots.
+    pub rank_delta: Option<isize>,
+    /// The change of the player's mu. Only set for players in both
+    /// snapshots.
+    pub mu_delta: Option<f64>,
+    /// Whether the player is only in the later snapshot.
+    pub is_new: bool,
+    /// Whether the player is only in the earlier snapshot.
+    pub is_removed: bool,
+}
+
+/// Compares two snapshots of the standings and returns the movement of every
+/// player in either of them: first the players of `after` in their new
+/// order, then the players that were removed, in their old order.
+///
+/// Both snapshots are ranked by conservative estimate, highest first. Ties
+/// are broken by the order of the snapshot, so that equal ratings do not
+/// shuffle between runs; players should appear once per snapshot.
+#[cfg(feature = "std")]
+pub fn standings_diff<K: Eq + Hash + Clone>(
+    before: &[(K, Rating)],
+    after: &[(K, Rating)],
+) -> Vec<Movement<K>> {
+    let old_order = standings_order(before);
+    let old_ranks: HashMap<&K, usize> = old_order
+        .iter()
+        .enumerate()
+        .map(|(rank, &index)| (&before[index].0, rank + 1))
+        .collect();
+
+    let mut movements: Vec<Movement<K>> = standings_order(after)
+        .into_iter()
+        .enumerate()
+        .map(|(rank, index)| {
+            let (ref id, ref rating) = after[index];
+            let new_rank = rank + 1;
+            let old = old_ranks.get(id).map(|&old_rank| {
+                let old_rating = &before[old_order[old_rank - 1]].1;
+                (old_rank, rating.mu - old_rating.mu)
+            });
+
+            Movement {
+                id: id.clone(),
+                old_rank: old.map(|(old_rank, _)| old_rank),
+                new_rank: Some(new_rank),
+                rank_delta: old.map(|(old_rank, _)| old_rank as isize - new_rank as isize),
+                mu_delta: old.map(|(_, mu_delta)| mu_delta),
+                is_new: old.is_none(),
+                is_removed: false,
+            }
+        })
+        .collect();
+
+    let remaining: HashSet<&K> = after.iter().map(|(id, _)| id).collect();
+    for (rank, &index) in old_order.iter().enumerate() {
+        let id = &before[index].0;
+        if remaining.contains(id) {
+            continue;
+        }
+
+        movements.push(Movement {
+            id: id.clone(),
+            old_rank: Some(rank + 1),
+            new_rank: None,
+            rank_delta: None,
+            mu_delta: None,
+            is_new: false,
+            is_removed: true,
+        });
+    }
+
+    movements
+}
+
+/// Returns the indices of a snapshot, ordered by conservative estimate from
+/// highest to lowest. Ties keep their order.
+#[cfg(feature = "std")]
+fn standings_order<K>(snapshot: &[(K, Rating)]) -> Vec<usize> {
+    let estimate = |index: usize| Metric::ConservativeEstimate.value(&snapshot[index].1);
+
+    let mut order: Vec<usize> = (0..snapshot.len()).collect();
+    order.sort_by(|&a, &b| {
+        estimate(b)
+            .partial_cmp(&estimate(a))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    order
+}
+
+/// Returns the mean and the population standard deviation of the mus.
+fn mu_moments(ratings: &[Rating]) -> (f64, f64) {
+    let n = ratings.len() as f64;
+
+    let mut sum = CompensatedSum::default();
+    for rating in ratings {
+        sum.add(rating.mu);
+    }
+    let mean = sum.total() / n;
+
+    let mut squares = CompensatedSum::default();
+    for rating in ratings {
+        squares.add((rating.mu - mean) * (rating.mu - mean));
+    }
+
+    (mean, Float::sqrt(squares.total() / n))
+}
+
+#[cfg(feature = "alloc")]
+fn sorted_values(population: &[Rating], metric: Metric) -> Vec<f64> {
+    let mut values: Vec<f64> = population.iter().map(|r| metric.value(r)).collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    values
+}
+
+/// Returns the value at `p` of non-empty sorted values, interpolating
+/// linearly between neighbours.
+#[cfg(feature = "alloc")]
+fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let position = p * (sorted.len() - 1) as f64;
+    let lower = position as usize;
+    let fraction = position - lower as f64;
+
+    if fraction == 0.0 {
+        return sorted[lower];
+    }
+
+    sorted[lower] + fraction * (sorted[lower + 1] - sorted[lower])
+}
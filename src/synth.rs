@@ -0,0 +1,192 @@
+//! Synthetic game histories with a known ground truth.
+//!
+//! `generate` plays games between players of known true skill, sampling the
+//! outcomes from the model's own win probabilities, and `recovery_error`
+//! measures how well the ratings obtained by replaying the games recover
+//! those skills. Together they show whether a configuration of the rater
+//! fits data that follows the model, before it is used on real data.
+//!
+//! ```rust
+//! extern crate bbt;
+//! extern crate rand;
+//!
+//! use bbt::replay;
+//! use bbt::synth::{self, Schedule};
+//! use bbt::{Rater, Rating};
+//! use rand::rngs::SmallRng;
+//! use rand::SeedableRng;
+//!
+//! let skills = [30.0, 25.0, 20.0];
+//! let mut rng = SmallRng::seed_from_u64(1);
+//! let games = synth::generate(&skills, 25.0 / 6.0, 300, Schedule::Random, &mut rng).unwrap();
+//!
+//! let ratings = replay::replay(&Rater::default(), &games, Rating::default()).unwrap();
+//! assert!(ratings["0"].mu() > ratings["1"].mu());
+//! assert!(ratings["1"].mu() > ratings["2"].mu());
+//! ```
+
+use rand::Rng;
+
+use replay::GameRecord;
+use {BBTError, Rater, Rating};
+
+/// Schedule decides which players meet in the games of `generate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Schedule {
+    /// Every game is between two players chosen uniformly at random.
+    Random,
+
+    /// The games go through every pair of players in turn, starting over
+    /// when all pairs have played.
+    RoundRobin,
+
+    /// Every game is between a player chosen at random and one of the
+    /// players closest to them in true skill, as a matchmaker that knows the
+    /// skills would pair them.
+    Matchmade,
+}
+
+/// Generates `games` duels between players with the given true skills, with
+/// pairings chosen by `schedule`. The player ids of the records are the
+/// indices of the players in `true_skills`, so the player with skill
+/// `true_skills[0]` is `"0"`.
+///
+/// The winner of every game is sampled from `Rater::win_probability`
+/// between ratings with the true skills and a sigma of zero, under a rater
+/// with the given `beta`. The games have no draws, weights or timestamps.
+///
+/// Returns `BBTError::InvalidParameter` if `beta` is negative or not
+/// finite, if a skill is not finite, or if there are games to play and
+/// fewer than two players.
+pub fn generate<R: Rng + ?Sized>(
+    true_skills: &[f64],
+    beta: f64,
+    games: usize,
+    schedule: Schedule,
+    rng: &mut R,
+) -> Result<Vec<GameRecord>, BBTError> {
+    if !beta.is_finite() || beta < 0.0 {
+        return Err(BBTError::InvalidParameter { name: "beta" });
+    }
+
+    let n = true_skills.len();
+    if true_skills.iter().any(|skill| !skill.is_finite()) || (games > 0 && n < 2) {
+        return Err(BBTError::InvalidParameter {
+            name: "true_skills",
+        });
+    }
+
+    let rater = Rater::new(beta);
+    // The players sorted by true skill, for matchmaking.
+    let mut by_skill: Vec<usize> = (0..n).collect();
+    by_skill.sort_by(|&a, &b| true_skills[a].total_cmp(&true_skills[b]));
+
+    let mut records = Vec::with_capacity(games);
+    let (mut first, mut second) = (0, 0);
+
+    for _ in 0..games {
+        let (p1, p2) = match schedule {
+            Schedule::Random => {
+                let p1 = rng.gen_range(0..n);
+                // Skips over `p1` to choose uniformly among the others.
+                let p2 = rng.gen_range(0..n - 1);
+                (p1, if p2 >= p1 { p2 + 1 } else { p2 })
+            }
+
+            Schedule::RoundRobin => {
+                second += 1;
+                if second >= n {
+                    first += 1;
+                    second = first + 1;
+                }
+                if second >= n {
+                    first = 0;
+                    second = 1;
+                }
+                (first, second)
+            }
+
+            Schedule::Matchmade => {
+                let position = rng.gen_range(0..n);
+                let neighbour = if position == 0 {
+                    1
+                } else if position == n - 1 || rng.gen::<bool>() {
+                    position - 1
+                } else {
+                    position + 1
+                };
+                (by_skill[position], by_skill[neighbour])
+            }
+        };
+
+        let win = rater.win_probability(
+            &Rating::new(true_skills[p1], 0.0),
+            &Rating::new(true_skills[p2], 0.0),
+        );
+        let ranks = if rng.gen::<f64>() < win {
+            vec![1, 2]
+        } else {
+            vec![2, 1]
+        };
+
+        records.push(GameRecord::new(
+            vec![vec![p1.to_string()], vec![p2.to_string()]],
+            ranks,
+        ));
+    }
+
+    Ok(records)
+}
+
+/// Returns the root-mean-square error between the true skills and the mus
+/// of the final ratings, after aligning the mus to the skills with the
+/// least-squares affine map `a * mu + b`. The alignment removes the offset
+/// and scale of the ratings, which are arbitrary, so only the relative
+/// positions of the players count.
+///
+/// If all mus are equal, as they are before any game, the aligned mus are
+/// the mean skill and the error is the standard deviation of the skills.
+///
+/// # Panics
+///
+/// Panics if `true_skills` and `final_ratings` differ in length.
+pub fn recovery_error(true_skills: &[f64], final_ratings: &[Rating]) -> f64 {
+    assert_eq!(
+        true_skills.len(),
+        final_ratings.len(),
+        "every true skill needs a final rating"
+    );
+
+    if true_skills.is_empty() {
+        return 0.0;
+    }
+
+    let n = true_skills.len() as f64;
+    let mean_skill = true_skills.iter().sum::<f64>() / n;
+    let mean_mu = final_ratings.iter().map(|rating| rating.mu).sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance = 0.0;
+    for (skill, rating) in true_skills.iter().zip(final_ratings) {
+        covariance += (rating.mu - mean_mu) * (skill - mean_skill);
+        variance += (rating.mu - mean_mu) * (rating.mu - mean_mu);
+    }
+
+    let scale = if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    };
+
+    let squared_error: f64 = true_skills
+        .iter()
+        .zip(final_ratings)
+        .map(|(skill, rating)| {
+            let aligned = mean_skill + scale * (rating.mu - mean_mu);
+            (aligned - skill) * (aligned - skill)
+        })
+        .sum();
+
+    (squared_error / n).sqrt()
+}
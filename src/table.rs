@@ -0,0 +1,103 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use logistic;
+use BBTError;
+use Float;
+use Rater;
+
+/// WinProbabilityTable precomputes the probability of winning a duel as a
+/// function of the mu difference of the two players, for players that all
+/// have the same sigma. Looking up a probability interpolates linearly
+/// between the two closest entries, which is much cheaper than computing it.
+///
+/// Between the entries, the interpolation error is at most
+/// `step² / (48 * √3 * c²)`, where `c² = 2 * sigma² + 2 * β²`, since the
+/// second derivative of the logistic function is bounded by `1 / (6 * √3)`.
+/// On the default scale, a step of 0.1 gives an error below 4e-6.
+/// `WinProbabilityTable::error_bound` returns this value. Differences outside
+/// of the table's range are clamped to its ends.
+#[derive(Clone, Debug)]
+pub struct WinProbabilityTable {
+    min_diff: f64,
+    step: f64,
+    error_bound: f64,
+    probabilities: Vec<f64>,
+}
+
+impl WinProbabilityTable {
+    /// Creates a table for players with the given sigma, with entries every
+    /// `mu_step` for mu differences between `-mu_range` and `mu_range`.
+    ///
+    /// Returns `BBTError::InvalidParameter` if sigma is negative or not
+    /// finite, or if `mu_step` or `mu_range` are not positive and finite.
+    pub fn new(
+        rater: &Rater,
+        sigma: f64,
+        mu_step: f64,
+        mu_range: f64,
+    ) -> Result<WinProbabilityTable, BBTError> {
+        if !sigma.is_finite() || sigma < 0.0 {
+            return Err(BBTError::InvalidParameter { name: "sigma" });
+        }
+
+        if !mu_step.is_finite() || mu_step <= 0.0 {
+            return Err(BBTError::InvalidParameter { name: "mu_step" });
+        }
+
+        if !mu_range.is_finite() || mu_range <= 0.0 {
+            return Err(BBTError::InvalidParameter { name: "mu_range" });
+        }
+
+        let c = Float::sqrt(2.0 * sigma * sigma + 2.0 * rater.beta_sq);
+        if c == 0.0 {
+            return Err(BBTError::InvalidParameter { name: "sigma" });
+        }
+
+        // The number of intervals, rounded up so the table covers the range.
+        let span = 2.0 * mu_range / mu_step;
+        let mut n_steps = span as usize;
+        if (n_steps as f64) < span {
+            n_steps += 1;
+        }
+
+        let probabilities = (0..=n_steps)
+            .map(|i| logistic(-mu_range + i as f64 * mu_step, c))
+            .collect();
+
+        Ok(WinProbabilityTable {
+            min_diff: -mu_range,
+            step: mu_step,
+            error_bound: mu_step * mu_step / (48.0 * Float::sqrt(3.0) * c * c),
+            probabilities,
+        })
+    }
+
+    /// Returns the probability that a player beats another player whose mu
+    /// is `mu_diff` lower.
+    pub fn lookup(&self, mu_diff: f64) -> f64 {
+        let last = self.probabilities.len() - 1;
+        let position = (mu_diff - self.min_diff) / self.step;
+
+        if position.is_nan() || position <= 0.0 {
+            return self.probabilities[0];
+        }
+
+        if position >= last as f64 {
+            return self.probabilities[last];
+        }
+
+        let index = position as usize;
+        let fraction = position - index as f64;
+        let lower = self.probabilities[index];
+        let upper = self.probabilities[index + 1];
+
+        lower + fraction * (upper - lower)
+    }
+
+    /// Returns the largest difference between `lookup` and the exact
+    /// probability for mu differences within the table's range.
+    pub fn error_bound(&self) -> f64 {
+        self.error_bound
+    }
+}
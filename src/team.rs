@@ -0,0 +1,94 @@
+use CompensatedSum;
+use {Float, Outcome, Rater, Rating};
+
+/// TeamRating is the rating of a fixed roster as a whole, for displaying a
+/// single rating for a clan or a doubles pair. Its mu is the sum of the
+/// members' mus and its variance is the sum of their variances, which is
+/// how `Rater::update_ratings` sees a team (Step 1 of Algorithm 1).
+///
+/// A team rating can also be rated on its own with `Rater::team_duel_rating`,
+/// for ladders that do not track the individual members.
+///
+/// ```rust
+/// use bbt::{Rater, Rating, TeamRating};
+///
+/// let pair = TeamRating::from_members(&[Rating::new(25.0, 3.0), Rating::new(20.0, 4.0)]);
+///
+/// assert_eq!(pair.mu(), 45.0);
+/// assert_eq!(pair.sigma(), 5.0);
+/// assert_eq!(pair.conservative_estimate(), 30.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TeamRating<F: Float = f64> {
+    rating: Rating<F>,
+}
+
+impl<F: Float> TeamRating<F> {
+    /// Aggregates the ratings of the members of a team. A team without
+    /// members has a mu and sigma of zero.
+    pub fn from_members(players: &[Rating<F>]) -> TeamRating<F> {
+        let mut mu = CompensatedSum::default();
+        let mut sigma_sq = CompensatedSum::default();
+
+        for player in players {
+            mu.add(player.mu);
+            sigma_sq.add(player.sigma_sq);
+        }
+
+        let sigma_sq = sigma_sq.total();
+
+        TeamRating {
+            rating: Rating {
+                mu: mu.total(),
+                sigma: sigma_sq.sqrt(),
+                sigma_sq,
+            },
+        }
+    }
+
+    /// Returns the estimated skill of the team, the sum of the members' mus.
+    pub fn mu(&self) -> F {
+        self.rating.mu
+    }
+
+    /// Returns the uncertainty of the team's skill, the square root of the
+    /// sum of the members' variances.
+    pub fn sigma(&self) -> F {
+        self.rating.sigma
+    }
+
+    /// Returns the conservative skill estimate `mu - 3 * sigma` of the team.
+    pub fn conservative_estimate(&self) -> F {
+        self.rating.conservative_estimate()
+    }
+
+    /// Returns the team as a single synthetic player, for use with
+    /// `Rater::win_probability` and the other methods that take ratings.
+    pub fn as_rating(&self) -> &Rating<F> {
+        &self.rating
+    }
+}
+
+impl<F: Float> Rater<F> {
+    /// This method rates a duel between two teams as if each team were a
+    /// single player with the team's aggregate rating, and returns the
+    /// updated team ratings. The outcome is from team `a`'s perspective.
+    ///
+    /// This is meant for clan ladders that only track the teams. It is not
+    /// the same as updating the members with `update_ratings` and
+    /// aggregating again: there, every member moves by their share of the
+    /// team's variance and the team's variance shrinks through its members,
+    /// whereas here the team's mu and variance change as those of a single
+    /// player would. Members cannot be recovered from an updated
+    /// `TeamRating`, so a roster change needs a new aggregate.
+    pub fn team_duel_rating(
+        &self,
+        a: &TeamRating<F>,
+        b: &TeamRating<F>,
+        outcome: Outcome,
+    ) -> (TeamRating<F>, TeamRating<F>) {
+        let (a, b) = self.duel(a.rating, b.rating, outcome);
+
+        (TeamRating { rating: a }, TeamRating { rating: b })
+    }
+}
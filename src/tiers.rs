@@ -0,0 +1,276 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::string::String;
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+
+use {BBTError, Rating};
+
+/// Tier is a named rank of a `TierLadder`, such as "Gold".
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tier {
+    index: usize,
+    name: String,
+    floor: Option<f64>,
+}
+
+impl Tier {
+    /// Returns the position of the tier in its ladder, starting with zero
+    /// for the lowest tier.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the name of the tier.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the lowest conservative estimate in the tier, or `None` for
+    /// the lowest tier, which has no lower boundary.
+    pub fn floor(&self) -> Option<f64> {
+        self.floor
+    }
+}
+
+/// TierLadder maps ratings onto named tiers by their conservative skill
+/// estimate `mu - 3 * sigma`, which is what players see as their rank.
+///
+/// A ladder with n boundaries has n + 1 tiers. A rating whose estimate is
+/// exactly on a boundary belongs to the tier above it.
+///
+/// ```rust
+/// use bbt::{Rating, TierLadder};
+///
+/// let ladder = TierLadder::new(&["Bronze", "Silver", "Gold"], &[10.0, 20.0]).unwrap();
+///
+/// let gold = ladder.tier_for(&Rating::new(26.0, 2.0));
+/// assert_eq!(gold.name(), "Gold");
+///
+/// // Slipping just below the boundary does not demote the player yet.
+/// let slipped = Rating::new(25.0, 2.0);
+/// assert_eq!(ladder.tier_for(&slipped).name(), "Silver");
+/// assert_eq!(ladder.promote_demote(gold, &slipped, 2.0).name(), "Gold");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TierLadder {
+    tiers: Vec<Tier>,
+}
+
+impl TierLadder {
+    /// Creates a ladder from the names of its tiers, from lowest to highest,
+    /// and the boundaries between them.
+    ///
+    /// Returns `BBTError::InvalidParameter` if there is not exactly one more
+    /// name than there are boundaries, or if the boundaries are not finite
+    /// and strictly increasing.
+    pub fn new<S: AsRef<str>>(names: &[S], boundaries: &[f64]) -> Result<TierLadder, BBTError> {
+        if names.len() != boundaries.len() + 1 {
+            return Err(BBTError::InvalidParameter { name: "names" });
+        }
+
+        let increasing = boundaries.windows(2).all(|pair| pair[0] < pair[1]);
+        if !increasing || !boundaries.iter().all(|boundary| boundary.is_finite()) {
+            return Err(BBTError::InvalidParameter { name: "boundaries" });
+        }
+
+        let tiers = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| Tier {
+                index,
+                name: name.as_ref().into(),
+                floor: index.checked_sub(1).map(|below| boundaries[below]),
+            })
+            .collect();
+
+        Ok(TierLadder { tiers })
+    }
+
+    /// Returns the tiers, from lowest to highest.
+    pub fn tiers(&self) -> &[Tier] {
+        &self.tiers
+    }
+
+    /// Returns the tier that the rating belongs to.
+    pub fn tier_for(&self, r: &Rating) -> &Tier {
+        let estimate = r.conservative_estimate();
+
+        // The first tier has no floor, so this is never empty. A NaN
+        // estimate is in the lowest tier.
+        let above = self.tiers[1..]
+            .iter()
+            .take_while(|tier| match tier.floor {
+                Some(floor) => estimate >= floor,
+                None => false,
+            })
+            .count();
+
+        &self.tiers[above]
+    }
+
+    /// Returns the tier that a player who was in `previous` is in after
+    /// their rating changed to `r`. Promotions happen as soon as the rating
+    /// reaches the next tier, but a player is only demoted once their
+    /// estimate falls more than `hysteresis` below the floor of `previous`,
+    /// so that a rating that hovers around a boundary does not flip between
+    /// two tiers.
+    ///
+    /// `previous` should be a tier of this ladder; tiers of other ladders
+    /// are treated as the tier with the same index.
+    pub fn promote_demote(&self, previous: &Tier, r: &Rating, hysteresis: f64) -> &Tier {
+        let tier = self.tier_for(r);
+
+        let previous = match self.tiers.get(previous.index) {
+            Some(previous) => previous,
+            None => return tier,
+        };
+
+        if tier.index >= previous.index {
+            return tier;
+        }
+
+        // Within the band below the floor, the player keeps their tier.
+        let floor = previous.floor.expect("only the lowest tier has no floor");
+        if r.conservative_estimate() >= floor - hysteresis {
+            previous
+        } else {
+            tier
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    #[cfg(not(any(feature = "std", test)))]
+    use alloc::string::String;
+    #[cfg(not(any(feature = "std", test)))]
+    use alloc::vec::Vec;
+    use std::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+    use super::TierLadder;
+
+    /// Ladders are serialized as a struct with the fields `names`, the names
+    /// of the tiers from lowest to highest, and `boundaries`, the boundaries
+    /// between them, the same as the arguments of `TierLadder::new`. The
+    /// ladder is validated when it is deserialized.
+    impl Serialize for TierLadder {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let names: Vec<&str> = self.tiers.iter().map(|tier| tier.name()).collect();
+            let boundaries: Vec<f64> = self.tiers.iter().filter_map(|tier| tier.floor).collect();
+
+            let mut state = serializer.serialize_struct("TierLadder", 2)?;
+            state.serialize_field("names", &names)?;
+            state.serialize_field("boundaries", &boundaries)?;
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for TierLadder {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            enum Field {
+                Names,
+                Boundaries,
+            }
+
+            impl<'de> Deserialize<'de> for Field {
+                fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    struct FieldVisitor;
+
+                    impl<'de> Visitor<'de> for FieldVisitor {
+                        type Value = Field;
+
+                        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                            formatter.write_str("`names` or `boundaries`")
+                        }
+
+                        fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                        where
+                            E: de::Error,
+                        {
+                            match value {
+                                "names" => Ok(Field::Names),
+                                "boundaries" => Ok(Field::Boundaries),
+                                _ => Err(de::Error::unknown_field(value, FIELDS)),
+                            }
+                        }
+                    }
+
+                    deserializer.deserialize_identifier(FieldVisitor)
+                }
+            }
+
+            struct LadderVisitor;
+
+            impl<'de> Visitor<'de> for LadderVisitor {
+                type Value = TierLadder;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("struct TierLadder")
+                }
+
+                fn visit_seq<V>(self, mut seq: V) -> Result<TierLadder, V::Error>
+                where
+                    V: SeqAccess<'de>,
+                {
+                    let names = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    let boundaries = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                    build(names, boundaries)
+                }
+
+                fn visit_map<V>(self, mut map: V) -> Result<TierLadder, V::Error>
+                where
+                    V: MapAccess<'de>,
+                {
+                    let mut names = None;
+                    let mut boundaries = None;
+
+                    while let Some(key) = map.next_key()? {
+                        match key {
+                            Field::Names => {
+                                if names.is_some() {
+                                    return Err(de::Error::duplicate_field("names"));
+                                }
+                                names = Some(map.next_value()?);
+                            }
+                            Field::Boundaries => {
+                                if boundaries.is_some() {
+                                    return Err(de::Error::duplicate_field("boundaries"));
+                                }
+                                boundaries = Some(map.next_value()?);
+                            }
+                        }
+                    }
+
+                    build(
+                        names.ok_or_else(|| de::Error::missing_field("names"))?,
+                        boundaries.ok_or_else(|| de::Error::missing_field("boundaries"))?,
+                    )
+                }
+            }
+
+            const FIELDS: &[&str] = &["names", "boundaries"];
+            deserializer.deserialize_struct("TierLadder", FIELDS, LadderVisitor)
+        }
+    }
+
+    fn build<E: de::Error>(names: Vec<String>, boundaries: Vec<f64>) -> Result<TierLadder, E> {
+        TierLadder::new(&names, &boundaries).map_err(E::custom)
+    }
+}
@@ -0,0 +1,1047 @@
+//! Projected tables for round-robin tournaments, in which every entrant
+//! plays every other entrant once, or twice in a double round robin.
+//!
+//! `round_robin` computes the expected number of wins, draws and losses of
+//! every entrant from the pairwise outcome probabilities of the model.
+//! `simulate_round_robin`, enabled by the `rand` feature, plays the
+//! tournament many times to also estimate the probability of every final
+//! placement.
+//!
+//! `swiss_pairings`, which needs the `std` feature, pairs the players of the
+//! next round of a Swiss-system tournament instead. `project_season` and
+//! `simulate_season` project the final table of a league from its current
+//! table and remaining fixtures. `Bracket`, which also needs the `std`
+//! feature, rates the results of a single-elimination tournament at once.
+//!
+//! ```rust
+//! use bbt::tournament::{self, PointsScheme};
+//! use bbt::{Rater, Rating};
+//!
+//! let entrants = [Rating::new(30.0, 2.0), Rating::new(25.0, 2.0), Rating::new(20.0, 2.0)];
+//! let records = tournament::round_robin(&Rater::default(), &entrants, false);
+//!
+//! assert_eq!(tournament::standings(&records, &PointsScheme::default()), vec![0, 1, 2]);
+//! assert!((records[1].wins + records[1].draws + records[1].losses - 2.0).abs() < 1e-12);
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::vec::Vec;
+use std::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::fmt;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+#[cfg(feature = "std")]
+use Outcome;
+use {logistic, BBTError, Float, Rater, Rating};
+
+/// ExpectedRecord is the expected number of wins, draws and losses of an
+/// entrant over a tournament.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExpectedRecord {
+    pub wins: f64,
+    pub draws: f64,
+    pub losses: f64,
+}
+
+impl ExpectedRecord {
+    /// Returns the expected number of points under the given scheme.
+    pub fn points(&self, scheme: &PointsScheme) -> f64 {
+        self.wins * scheme.win + self.draws * scheme.draw + self.losses * scheme.loss
+    }
+}
+
+/// PointsScheme is the number of points awarded for a win, a draw and a
+/// loss. The default is the common 3/1/0 scheme.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointsScheme {
+    pub win: f64,
+    pub draw: f64,
+    pub loss: f64,
+}
+
+impl PointsScheme {
+    /// Creates a scheme with the given points for a win, a draw and a loss.
+    pub fn new(win: f64, draw: f64, loss: f64) -> PointsScheme {
+        PointsScheme { win, draw, loss }
+    }
+}
+
+impl Default for PointsScheme {
+    fn default() -> PointsScheme {
+        PointsScheme::new(3.0, 1.0, 0.0)
+    }
+}
+
+/// Returns the expected record of every entrant of a round robin, in the
+/// order of `entrants`. With `double`, every pair of entrants plays twice.
+///
+/// Games cannot be drawn; see `round_robin_with_draw_margin` for games that
+/// can.
+pub fn round_robin(rater: &Rater, entrants: &[Rating], double: bool) -> Vec<ExpectedRecord> {
+    expected_records(rater, entrants, double, 0.0)
+}
+
+/// Returns the expected record of every entrant of a round robin like
+/// `round_robin`, for games that are drawn when the performances of the two
+/// entrants differ by less than `draw_margin`.
+///
+/// Returns `BBTError::InvalidParameter` if `draw_margin` is negative or not
+/// finite.
+pub fn round_robin_with_draw_margin(
+    rater: &Rater,
+    entrants: &[Rating],
+    double: bool,
+    draw_margin: f64,
+) -> Result<Vec<ExpectedRecord>, BBTError> {
+    check_draw_margin(draw_margin)?;
+
+    Ok(expected_records(rater, entrants, double, draw_margin))
+}
+
+/// Returns the indices of the entrants, ordered by expected points from most
+/// to fewest. Entrants with equal points keep their order.
+pub fn standings(records: &[ExpectedRecord], scheme: &PointsScheme) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..records.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (records[a].points(scheme), records[b].points(scheme));
+        b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+    });
+
+    order
+}
+
+fn expected_records(
+    rater: &Rater,
+    entrants: &[Rating],
+    double: bool,
+    draw_margin: f64,
+) -> Vec<ExpectedRecord> {
+    let games = if double { 2.0 } else { 1.0 };
+    let mut records = vec![ExpectedRecord::default(); entrants.len()];
+
+    for i in 0..entrants.len() {
+        for j in i + 1..entrants.len() {
+            let c = Float::sqrt(entrants[i].sigma_sq + entrants[j].sigma_sq + 2.0 * rater.beta_sq);
+            let (win, draw, loss) =
+                outcome_probabilities(entrants[i].mu - entrants[j].mu, c, draw_margin);
+
+            records[i].wins += games * win;
+            records[i].draws += games * draw;
+            records[i].losses += games * loss;
+            records[j].wins += games * loss;
+            records[j].draws += games * draw;
+            records[j].losses += games * win;
+        }
+    }
+
+    records
+}
+
+/// Returns the probabilities that the first of two players wins, draws and
+/// loses, given the difference of their skills and the scale `c` of the
+/// logistic performance distribution.
+fn outcome_probabilities(diff: f64, c: f64, draw_margin: f64) -> (f64, f64, f64) {
+    if c == 0.0 {
+        return match diff {
+            _ if diff > draw_margin => (1.0, 0.0, 0.0),
+            _ if diff < -draw_margin => (0.0, 0.0, 1.0),
+            _ => (0.0, 1.0, 0.0),
+        };
+    }
+
+    let win = logistic(diff - draw_margin, c);
+    let loss = logistic(-diff - draw_margin, c);
+
+    (win, (1.0 - win - loss).max(0.0), loss)
+}
+
+fn check_draw_margin(draw_margin: f64) -> Result<(), BBTError> {
+    if !draw_margin.is_finite() || draw_margin < 0.0 {
+        return Err(BBTError::InvalidParameter {
+            name: "draw_margin",
+        });
+    }
+
+    Ok(())
+}
+
+/// SimulatedStanding is the outcome of `simulate_round_robin` for a single
+/// entrant.
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulatedStanding {
+    /// The average record over all simulations.
+    pub record: ExpectedRecord,
+
+    /// The probability of every final placement: the first element is the
+    /// probability of finishing first, the second that of finishing second,
+    /// and so on.
+    pub placements: Vec<f64>,
+}
+
+/// Simulates a round robin `simulations` times and returns the average
+/// record and the placement probabilities of every entrant, in the order of
+/// `entrants`. Placements are decided by points under `scheme`, with ties
+/// broken at random.
+///
+/// Every simulation first draws the true skill of each entrant from their
+/// rating, and then plays all games with those skills. The results of an
+/// entrant are therefore correlated, as they are in a real tournament: an
+/// entrant who is better than their rating suggests tends to win all of
+/// their games, not just some. The average records are thus close to, but
+/// not the same as, those of `round_robin_with_draw_margin`.
+///
+/// Returns `BBTError::InvalidParameter` if `simulations` is zero or if
+/// `draw_margin` is negative or not finite.
+#[cfg(feature = "rand")]
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_round_robin<R: Rng + ?Sized>(
+    rater: &Rater,
+    entrants: &[Rating],
+    double: bool,
+    draw_margin: f64,
+    scheme: &PointsScheme,
+    simulations: usize,
+    rng: &mut R,
+) -> Result<Vec<SimulatedStanding>, BBTError> {
+    check_draw_margin(draw_margin)?;
+
+    if simulations == 0 {
+        return Err(BBTError::InvalidParameter {
+            name: "simulations",
+        });
+    }
+
+    let n = entrants.len();
+    let games = if double { 2 } else { 1 };
+    // The skills are certain within a simulation, so only the variance of
+    // the performances remains.
+    let c = Float::sqrt(2.0 * rater.beta_sq);
+
+    let mut standings = vec![
+        SimulatedStanding {
+            record: ExpectedRecord::default(),
+            placements: vec![0.0; n],
+        };
+        n
+    ];
+    let mut skills = vec![0.0; n];
+    let mut points = vec![0.0; n];
+    let mut order: Vec<(usize, u64)> = Vec::with_capacity(n);
+
+    for _ in 0..simulations {
+        for (skill, entrant) in skills.iter_mut().zip(entrants) {
+            *skill = entrant.mu + entrant.sigma * standard_normal(rng);
+        }
+
+        for points in points.iter_mut() {
+            *points = 0.0;
+        }
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let (win, draw, _) = outcome_probabilities(skills[i] - skills[j], c, draw_margin);
+
+                for _ in 0..games {
+                    let roll: f64 = rng.gen();
+                    let (first, second) = if roll < win {
+                        (i, j)
+                    } else if roll < win + draw {
+                        standings[i].record.draws += 1.0;
+                        standings[j].record.draws += 1.0;
+                        points[i] += scheme.draw;
+                        points[j] += scheme.draw;
+                        continue;
+                    } else {
+                        (j, i)
+                    };
+
+                    standings[first].record.wins += 1.0;
+                    standings[second].record.losses += 1.0;
+                    points[first] += scheme.win;
+                    points[second] += scheme.loss;
+                }
+            }
+        }
+
+        order.clear();
+        order.extend((0..n).map(|index| (index, rng.gen::<u64>())));
+        order.sort_by(|&(a, a_tiebreak), &(b, b_tiebreak)| {
+            points[b]
+                .partial_cmp(&points[a])
+                .unwrap_or(Ordering::Equal)
+                .then(a_tiebreak.cmp(&b_tiebreak))
+        });
+
+        for (placement, &(index, _)) in order.iter().enumerate() {
+            standings[index].placements[placement] += 1.0;
+        }
+    }
+
+    let simulations = simulations as f64;
+    for standing in &mut standings {
+        standing.record.wins /= simulations;
+        standing.record.draws /= simulations;
+        standing.record.losses /= simulations;
+
+        for probability in &mut standing.placements {
+            *probability /= simulations;
+        }
+    }
+
+    Ok(standings)
+}
+
+/// Draws a sample from the standard normal distribution with the Box-Muller
+/// transform.
+#[cfg(feature = "rand")]
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // `gen` returns values in [0, 1), so `1 - u` is never zero.
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+/// The number of pairings `swiss_pairings` tries before giving up on
+/// avoiding repeated pairings.
+#[cfg(feature = "std")]
+const SWISS_SEARCH_LIMIT: usize = 1_000_000;
+
+/// Returns the pairings of the next round of a Swiss-system tournament.
+///
+/// Players are paired in order of their conservative estimate
+/// `mu - 3 * sigma`, from highest to lowest: every player is paired with the
+/// remaining opponent of the highest `Rater::match_quality` they have not
+/// played yet. If that would leave some of the remaining players without
+/// such an opponent, the next-best opponents are tried, so previous
+/// pairings are only repeated when they cannot be avoided. Previous
+/// pairings count in either order.
+///
+/// With an odd number of players, the lowest-rated player who has not had a
+/// bye yet gets one, or the lowest-rated player if all have. A bye is
+/// returned as the pairing of a player with themselves, `(id, id)`, after
+/// all other pairings, and previous byes are recognized in the same way in
+/// `previous_pairings`.
+///
+/// Players with equal estimates and opponents of equal quality are taken in
+/// the order of `players`, so the same input always gives the same
+/// pairings.
+///
+/// ```rust
+/// use std::collections::HashSet;
+///
+/// use bbt::tournament::swiss_pairings;
+/// use bbt::{Rater, Rating};
+///
+/// let players = [
+///     ("ann", Rating::new(30.0, 2.0)),
+///     ("bob", Rating::new(29.0, 2.0)),
+///     ("cid", Rating::new(20.0, 2.0)),
+/// ];
+///
+/// let mut previous = HashSet::new();
+/// previous.insert(("bob", "ann"));
+/// // cid already had a bye.
+/// previous.insert(("cid", "cid"));
+///
+/// let pairings = swiss_pairings(&Rater::default(), &players, &previous);
+/// assert_eq!(pairings, vec![("ann", "cid"), ("bob", "bob")]);
+/// ```
+#[cfg(feature = "std")]
+pub fn swiss_pairings<Id: Eq + Hash + Clone>(
+    rater: &Rater,
+    players: &[(Id, Rating)],
+    previous_pairings: &HashSet<(Id, Id)>,
+) -> Vec<(Id, Id)> {
+    let n = players.len();
+    let met: Vec<Vec<bool>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    let (a, b) = (players[i].0.clone(), players[j].0.clone());
+                    previous_pairings.contains(&(b.clone(), a.clone()))
+                        || previous_pairings.contains(&(a, b))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (
+            players[a].1.conservative_estimate(),
+            players[b].1.conservative_estimate(),
+        );
+        b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+    });
+
+    let bye = if n % 2 == 1 {
+        let bye = order
+            .iter()
+            .rev()
+            .find(|&&player| !met[player][player])
+            .or_else(|| order.last())
+            .copied();
+        order.retain(|&player| Some(player) != bye);
+        bye
+    } else {
+        None
+    };
+
+    // The opponents of every player, from the best match to the worst.
+    let mut candidates = vec![Vec::new(); n];
+    for &player in &order {
+        let mut opponents: Vec<(usize, f64)> = order
+            .iter()
+            .filter(|&&opponent| opponent != player)
+            .map(|&opponent| {
+                let quality = rater.match_quality(&players[player].1, &players[opponent].1);
+                (opponent, quality)
+            })
+            .collect();
+        opponents.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+
+        candidates[player] = opponents
+            .into_iter()
+            .map(|(opponent, _)| opponent)
+            .collect();
+    }
+
+    let mut paired = vec![false; n];
+    let mut pairs = Vec::with_capacity(n / 2 + 1);
+    let mut steps = 0;
+
+    if !pair_without_repeats(
+        &order,
+        &candidates,
+        &met,
+        &mut paired,
+        &mut pairs,
+        &mut steps,
+    ) {
+        // Some previous pairing has to be repeated, so prefer new opponents
+        // where possible.
+        pairs.clear();
+        paired = vec![false; n];
+
+        for &player in &order {
+            if paired[player] {
+                continue;
+            }
+
+            let mut opponents = candidates[player].iter().filter(|&&o| !paired[o]);
+            let opponent = match opponents.clone().find(|&&o| !met[player][o]) {
+                Some(&opponent) => opponent,
+                None => *opponents.next().expect("an even number of players"),
+            };
+
+            paired[player] = true;
+            paired[opponent] = true;
+            pairs.push((player, opponent));
+        }
+    }
+
+    pairs.extend(bye.map(|bye| (bye, bye)));
+    pairs
+        .into_iter()
+        .map(|(a, b)| (players[a].0.clone(), players[b].0.clone()))
+        .collect()
+}
+
+/// Pairs the first unpaired player of `order` with each of their unpaired
+/// candidates they have not met, in turn, until the remaining players can
+/// be paired in the same way. Returns false if there is no such pairing, or
+/// if it was not found within `SWISS_SEARCH_LIMIT` steps.
+#[cfg(feature = "std")]
+fn pair_without_repeats(
+    order: &[usize],
+    candidates: &[Vec<usize>],
+    met: &[Vec<bool>],
+    paired: &mut [bool],
+    pairs: &mut Vec<(usize, usize)>,
+    steps: &mut usize,
+) -> bool {
+    let player = match order.iter().find(|&&player| !paired[player]) {
+        Some(&player) => player,
+        None => return true,
+    };
+
+    paired[player] = true;
+
+    for &opponent in &candidates[player] {
+        if paired[opponent] || met[player][opponent] {
+            continue;
+        }
+
+        *steps += 1;
+        if *steps > SWISS_SEARCH_LIMIT {
+            break;
+        }
+
+        paired[opponent] = true;
+        pairs.push((player, opponent));
+
+        if pair_without_repeats(order, candidates, met, paired, pairs, steps) {
+            return true;
+        }
+
+        pairs.pop();
+        paired[opponent] = false;
+    }
+
+    paired[player] = false;
+    false
+}
+
+#[cfg(feature = "std")]
+/// ProjectedRow is a row of the final table of a season, as projected by
+/// `project_season`.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectedRow<Id> {
+    /// The team.
+    pub id: Id,
+
+    /// The team's current points.
+    pub points: f64,
+
+    /// The expected record of the team's remaining fixtures.
+    pub remaining: ExpectedRecord,
+
+    /// The expected points at the end of the season: the current points
+    /// plus the expected points of the remaining fixtures.
+    pub expected_points: f64,
+}
+
+/// Returns the projected final table of a season, given the current table
+/// and the remaining fixtures.
+///
+/// Every row of `table` is a team, with its current rating and points.
+/// Every fixture adds the expected points of both teams under `scheme` to
+/// their current points, computed like `round_robin_with_draw_margin` from
+/// the probabilities of a win, a draw and a loss. A pair of teams may meet
+/// more than once. The rows are sorted by expected final points, from most
+/// to fewest, and rows with equal points keep the order of `table`.
+///
+/// Returns `BBTError::InvalidParameter` if a team appears in `table` more
+/// than once, if a fixture refers to a team that is not in `table` or pits a
+/// team against itself, or if `draw_margin` is negative or not finite.
+///
+/// ```rust
+/// use bbt::tournament::{project_season, PointsScheme};
+/// use bbt::{Rater, Rating};
+///
+/// let table = [
+///     ("Rovers", Rating::new(30.0, 2.0), 40.0),
+///     ("United", Rating::new(20.0, 2.0), 41.0),
+///     ("City", Rating::new(25.0, 2.0), 35.0),
+/// ];
+/// let remaining = [("Rovers", "City"), ("United", "Rovers"), ("City", "United")];
+///
+/// let rows = project_season(&Rater::default(), &table, &remaining, &PointsScheme::default(), 1.0)
+///     .unwrap();
+///
+/// assert_eq!(rows[0].id, "Rovers");
+/// assert!(rows[0].expected_points > 40.0 && rows[0].expected_points < 46.0);
+/// ```
+#[cfg(feature = "std")]
+pub fn project_season<Id: Eq + Hash + Clone>(
+    rater: &Rater,
+    table: &[(Id, Rating, f64)],
+    remaining: &[(Id, Id)],
+    scheme: &PointsScheme,
+    draw_margin: f64,
+) -> Result<Vec<ProjectedRow<Id>>, BBTError> {
+    check_draw_margin(draw_margin)?;
+    let fixtures = fixture_indices(table, remaining)?;
+
+    let mut records = vec![ExpectedRecord::default(); table.len()];
+
+    for &(i, j) in &fixtures {
+        let (a, b) = (&table[i].1, &table[j].1);
+        let c = Float::sqrt(a.sigma_sq + b.sigma_sq + 2.0 * rater.beta_sq);
+        let (win, draw, loss) = outcome_probabilities(a.mu - b.mu, c, draw_margin);
+
+        records[i].wins += win;
+        records[i].draws += draw;
+        records[i].losses += loss;
+        records[j].wins += loss;
+        records[j].draws += draw;
+        records[j].losses += win;
+    }
+
+    let mut rows: Vec<ProjectedRow<Id>> = table
+        .iter()
+        .zip(records)
+        .map(|(&(ref id, _, points), remaining)| ProjectedRow {
+            id: id.clone(),
+            points,
+            remaining,
+            expected_points: points + remaining.points(scheme),
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.expected_points
+            .partial_cmp(&a.expected_points)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    Ok(rows)
+}
+
+/// SimulatedSeasonRow is a row of the final table of a season, as estimated
+/// by `simulate_season`.
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulatedSeasonRow<Id> {
+    /// The team.
+    pub id: Id,
+
+    /// The team's current points.
+    pub points: f64,
+
+    /// The average points at the end of the season over all simulations.
+    pub expected_points: f64,
+
+    /// The probability of finishing first.
+    pub title_probability: f64,
+
+    /// The probability of finishing in one of the relegation places.
+    pub relegation_probability: f64,
+}
+
+/// Simulates the remaining fixtures of a season `simulations` times, like
+/// `simulate_round_robin`, and returns the average final points of every
+/// team and its probabilities of winning the title and of finishing in one
+/// of the last `relegated` places. Places are decided by points under
+/// `scheme`, with ties broken at random.
+///
+/// The rows are sorted by average final points like in `project_season`,
+/// whose expected points they are close to.
+///
+/// Returns the errors of `project_season`, and `BBTError::InvalidParameter`
+/// if `simulations` is zero or if `relegated` is greater than the number of
+/// teams.
+#[cfg(feature = "rand")]
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_season<Id: Eq + Hash + Clone, R: Rng + ?Sized>(
+    rater: &Rater,
+    table: &[(Id, Rating, f64)],
+    remaining: &[(Id, Id)],
+    scheme: &PointsScheme,
+    draw_margin: f64,
+    relegated: usize,
+    simulations: usize,
+    rng: &mut R,
+) -> Result<Vec<SimulatedSeasonRow<Id>>, BBTError> {
+    check_draw_margin(draw_margin)?;
+    let fixtures = fixture_indices(table, remaining)?;
+
+    if simulations == 0 {
+        return Err(BBTError::InvalidParameter {
+            name: "simulations",
+        });
+    }
+
+    if relegated > table.len() {
+        return Err(BBTError::InvalidParameter { name: "relegated" });
+    }
+
+    let n = table.len();
+    // The skills are certain within a simulation, so only the variance of
+    // the performances remains.
+    let c = Float::sqrt(2.0 * rater.beta_sq);
+
+    let mut rows: Vec<SimulatedSeasonRow<Id>> = table
+        .iter()
+        .map(|&(ref id, _, points)| SimulatedSeasonRow {
+            id: id.clone(),
+            points,
+            expected_points: 0.0,
+            title_probability: 0.0,
+            relegation_probability: 0.0,
+        })
+        .collect();
+    let mut skills = vec![0.0; n];
+    let mut points = vec![0.0; n];
+    let mut order: Vec<(usize, u64)> = Vec::with_capacity(n);
+
+    for _ in 0..simulations {
+        for ((skill, points), &(_, ref rating, current)) in
+            skills.iter_mut().zip(points.iter_mut()).zip(table)
+        {
+            *skill = rating.mu + rating.sigma * standard_normal(rng);
+            *points = current;
+        }
+
+        for &(i, j) in &fixtures {
+            let (win, draw, _) = outcome_probabilities(skills[i] - skills[j], c, draw_margin);
+
+            let roll: f64 = rng.gen();
+            if roll < win {
+                points[i] += scheme.win;
+                points[j] += scheme.loss;
+            } else if roll < win + draw {
+                points[i] += scheme.draw;
+                points[j] += scheme.draw;
+            } else {
+                points[i] += scheme.loss;
+                points[j] += scheme.win;
+            }
+        }
+
+        order.clear();
+        order.extend((0..n).map(|index| (index, rng.gen::<u64>())));
+        order.sort_by(|&(a, a_tiebreak), &(b, b_tiebreak)| {
+            points[b]
+                .partial_cmp(&points[a])
+                .unwrap_or(Ordering::Equal)
+                .then(a_tiebreak.cmp(&b_tiebreak))
+        });
+
+        if let Some(&(champion, _)) = order.first() {
+            rows[champion].title_probability += 1.0;
+        }
+        for &(index, _) in &order[n - relegated..] {
+            rows[index].relegation_probability += 1.0;
+        }
+        for (row, &points) in rows.iter_mut().zip(&points) {
+            row.expected_points += points;
+        }
+    }
+
+    let simulations = simulations as f64;
+    for row in &mut rows {
+        row.expected_points /= simulations;
+        row.title_probability /= simulations;
+        row.relegation_probability /= simulations;
+    }
+
+    rows.sort_by(|a, b| {
+        b.expected_points
+            .partial_cmp(&a.expected_points)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    Ok(rows)
+}
+
+/// Returns the indices into `table` of the teams of every fixture.
+#[cfg(feature = "std")]
+fn fixture_indices<Id: Eq + Hash>(
+    table: &[(Id, Rating, f64)],
+    remaining: &[(Id, Id)],
+) -> Result<Vec<(usize, usize)>, BBTError> {
+    let mut indices = HashMap::with_capacity(table.len());
+    for (index, (id, _, _)) in table.iter().enumerate() {
+        if indices.insert(id, index).is_some() {
+            return Err(BBTError::InvalidParameter { name: "table" });
+        }
+    }
+
+    remaining
+        .iter()
+        .map(|(a, b)| match (indices.get(a), indices.get(b)) {
+            (Some(&i), Some(&j)) if i != j => Ok((i, j)),
+            _ => Err(BBTError::InvalidParameter { name: "remaining" }),
+        })
+        .collect()
+}
+
+/// BracketError describes why a bracket could not be built or applied.
+/// Rounds and matches are counted from zero.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BracketError {
+    /// A bracket needs at least two entrants.
+    TooFewEntrants,
+
+    /// The entrant with the given seed, counted from zero, was already
+    /// entered with a better seed.
+    DuplicateEntrant { seed: usize },
+
+    /// The bracket has no such match.
+    NoSuchMatch { round: usize, match_idx: usize },
+
+    /// The reported winner is not an entrant of the bracket.
+    UnknownEntrant { round: usize, match_idx: usize },
+
+    /// The reported winner did not play in the match, for example because
+    /// they lost in an earlier round.
+    InconsistentResult { round: usize, match_idx: usize },
+
+    /// A later match was reported, but the result of this one, which
+    /// decides one of its players, is missing.
+    MissingResult { round: usize, match_idx: usize },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for BracketError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BracketError::TooFewEntrants => write!(f, "A bracket needs at least two entrants"),
+            BracketError::DuplicateEntrant { seed } => {
+                write!(f, "The entrant with seed {} was entered twice", seed)
+            }
+            BracketError::NoSuchMatch { round, match_idx } => {
+                write!(f, "Round {} has no match {}", round, match_idx)
+            }
+            BracketError::UnknownEntrant { round, match_idx } => write!(
+                f,
+                "The winner of match {} of round {} is not an entrant",
+                match_idx, round
+            ),
+            BracketError::InconsistentResult { round, match_idx } => write!(
+                f,
+                "The winner of match {} of round {} did not play in it",
+                match_idx, round
+            ),
+            BracketError::MissingResult { round, match_idx } => write!(
+                f,
+                "The result of match {} of round {} is missing",
+                match_idx, round
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BracketError {}
+
+/// Bracket is a single-elimination tournament, whose results are rated
+/// together once they are reported.
+///
+/// The entrants are placed in the usual seeding order, in which the first
+/// seed meets the last in the first round and can only meet the second seed
+/// in the final. If the number of entrants is not a power of two, the best
+/// seeds get byes in the first round and advance without playing.
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use bbt::tournament::Bracket;
+/// use bbt::Rater;
+///
+/// let mut bracket = Bracket::new(vec!["ann", "bob", "cid"]).unwrap();
+/// // ann has a bye in match 0, bob plays cid in match 1.
+/// bracket.set_result(0, 1, "cid").unwrap();
+/// bracket.set_result(1, 0, "cid").unwrap();
+///
+/// let mut ratings = HashMap::new();
+/// bracket.apply(&Rater::default(), &mut ratings).unwrap();
+///
+/// assert!(ratings["cid"].mu() > ratings["ann"].mu());
+/// assert!(ratings["ann"].mu() > ratings["bob"].mu());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Bracket<Id> {
+    entrants: Vec<Id>,
+    indices: HashMap<Id, usize>,
+    /// The first-round slot of every entrant, by seed. Byes are `None`.
+    slots: Vec<Option<usize>>,
+    /// The index of the reported winner of every match, by round.
+    results: Vec<Vec<Option<usize>>>,
+}
+
+/// The player of one side of a match, as far as it is known.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, PartialEq)]
+enum Side {
+    /// The side is a bye.
+    Empty,
+    /// The match that decides the side has not been reported.
+    Undecided,
+    Entrant(usize),
+}
+
+#[cfg(feature = "std")]
+impl<Id: Eq + Hash + Clone> Bracket<Id> {
+    /// Creates a bracket of the given entrants, from the first seed to the
+    /// last.
+    ///
+    /// Returns `BracketError::TooFewEntrants` if there are fewer than two
+    /// entrants, and `BracketError::DuplicateEntrant` if an entrant appears
+    /// twice.
+    pub fn new(seeds: Vec<Id>) -> Result<Bracket<Id>, BracketError> {
+        if seeds.len() < 2 {
+            return Err(BracketError::TooFewEntrants);
+        }
+
+        let mut indices = HashMap::with_capacity(seeds.len());
+        for (seed, id) in seeds.iter().enumerate() {
+            if indices.insert(id.clone(), seed).is_some() {
+                return Err(BracketError::DuplicateEntrant { seed });
+            }
+        }
+
+        // Every round doubles the number of slots, and the seed `s` of the
+        // previous round meets the seed `size + 1 - s`, counting from one.
+        let size = seeds.len().next_power_of_two();
+        let mut order = vec![1];
+        while order.len() < size {
+            let doubled = 2 * order.len();
+            order = order
+                .iter()
+                .flat_map(|&seed| vec![seed, doubled + 1 - seed])
+                .collect();
+        }
+
+        let slots = order
+            .iter()
+            .map(|&seed| Some(seed - 1).filter(|&seed| seed < seeds.len()))
+            .collect();
+
+        let rounds = size.trailing_zeros() as usize;
+        let results = (0..rounds)
+            .map(|round| vec![None; size >> (round + 1)])
+            .collect();
+
+        Ok(Bracket {
+            entrants: seeds,
+            indices,
+            slots,
+            results,
+        })
+    }
+
+    /// Returns the number of rounds, the last of which is the final.
+    pub fn rounds(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Reports the winner of a match. Round zero is the first round, and its
+    /// matches are numbered from the top of the bracket, where the first
+    /// seed plays, down. A match of a later round is between the winners of
+    /// the matches `2 * match_idx` and `2 * match_idx + 1` of the round
+    /// before. Reporting a match again replaces its winner.
+    ///
+    /// Returns `BracketError::NoSuchMatch` if the bracket has no such match,
+    /// and `BracketError::UnknownEntrant` if the winner is not an entrant.
+    /// Whether the winner played in the match is checked by `apply`.
+    pub fn set_result(
+        &mut self,
+        round: usize,
+        match_idx: usize,
+        winner: Id,
+    ) -> Result<(), BracketError> {
+        let result = self
+            .results
+            .get_mut(round)
+            .and_then(|matches| matches.get_mut(match_idx))
+            .ok_or(BracketError::NoSuchMatch { round, match_idx })?;
+
+        let winner = *self
+            .indices
+            .get(&winner)
+            .ok_or(BracketError::UnknownEntrant { round, match_idx })?;
+
+        *result = Some(winner);
+
+        Ok(())
+    }
+
+    /// Rates every reported match as a duel between its players, round by
+    /// round and from the top of the bracket down within a round, with the
+    /// player from the upper half of the match as the first player. Entrants
+    /// without a rating start with the default rating. Byes are not rated,
+    /// and neither are matches without a result, so a bracket can be
+    /// applied before the tournament is over, as long as the reported
+    /// matches have all been played.
+    ///
+    /// All results are checked before any rating is changed: returns
+    /// `BracketError::InconsistentResult` if a reported winner did not play
+    /// in their match, and `BracketError::MissingResult` if a match is
+    /// reported but one of the matches deciding its players is not. The
+    /// ratings are unchanged on error.
+    pub fn apply(
+        &self,
+        rater: &Rater,
+        ratings: &mut HashMap<Id, Rating>,
+    ) -> Result<(), BracketError> {
+        let mut sides: Vec<Side> = self
+            .slots
+            .iter()
+            .map(|slot| slot.map_or(Side::Empty, Side::Entrant))
+            .collect();
+        let mut duels = Vec::new();
+
+        for (round, results) in self.results.iter().enumerate() {
+            let mut winners = Vec::with_capacity(results.len());
+
+            for (match_idx, &result) in results.iter().enumerate() {
+                let (upper, lower) = (sides[2 * match_idx], sides[2 * match_idx + 1]);
+                let inconsistent = BracketError::InconsistentResult { round, match_idx };
+
+                let winner = match (upper, lower, result) {
+                    (Side::Undecided, _, Some(_)) | (_, Side::Undecided, Some(_)) => {
+                        let feeder = if upper == Side::Undecided {
+                            2 * match_idx
+                        } else {
+                            2 * match_idx + 1
+                        };
+
+                        return Err(BracketError::MissingResult {
+                            round: round - 1,
+                            match_idx: feeder,
+                        });
+                    }
+                    (Side::Undecided, _, None) | (_, Side::Undecided, None) => Side::Undecided,
+                    (Side::Entrant(a), Side::Entrant(b), Some(winner)) => {
+                        if winner != a && winner != b {
+                            return Err(inconsistent);
+                        }
+                        duels.push((a, b, winner == a));
+                        Side::Entrant(winner)
+                    }
+                    (Side::Entrant(_), Side::Entrant(_), None) => Side::Undecided,
+                    // Byes advance the other side without a match.
+                    (Side::Entrant(player), Side::Empty, result)
+                    | (Side::Empty, Side::Entrant(player), result) => {
+                        if result.is_some() && result != Some(player) {
+                            return Err(inconsistent);
+                        }
+                        Side::Entrant(player)
+                    }
+                    (Side::Empty, Side::Empty, None) => Side::Empty,
+                    (Side::Empty, Side::Empty, Some(_)) => return Err(inconsistent),
+                };
+
+                winners.push(winner);
+            }
+
+            sides = winners;
+        }
+
+        for (a, b, upper_won) in duels {
+            let rating = |index: usize| {
+                ratings
+                    .get(&self.entrants[index])
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            let outcome = if upper_won {
+                Outcome::Win
+            } else {
+                Outcome::Loss
+            };
+
+            let (new_a, new_b) = rater.duel(rating(a), rating(b), outcome);
+            ratings.insert(self.entrants[a].clone(), new_a);
+            ratings.insert(self.entrants[b].clone(), new_b);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::Deref;
+
+#[cfg(all(feature = "alloc", not(any(feature = "std", test))))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use BBTError;
+use {Float, Outcome, Rater, Rating};
+
+/// TrackedRating is a rating that also counts the games it has taken part
+/// in, for rules that depend on a player's experience, such as provisional
+/// ratings. Sigma is a poor proxy for that, since it can grow again when
+/// ratings decay.
+///
+/// The counter is incremented by `Rater::duel_tracked` and
+/// `Rater::update_tracked_ratings`. A tracked rating dereferences to its
+/// rating for everything else.
+///
+/// With the `serde` feature, tracked ratings are serialized like ratings
+/// with an additional `games` field, which may be missing, so that stored
+/// ratings can be read as tracked ratings that have played no games. Binary
+/// formats use the tuple `(mu, sigma, games)`.
+///
+/// ```rust
+/// use bbt::{Outcome, Rater, TrackedRating};
+///
+/// let mut alice = TrackedRating::default();
+/// let mut bob = TrackedRating::default();
+///
+/// Rater::default().duel_tracked(&mut alice, &mut bob, Outcome::Win);
+///
+/// assert_eq!(alice.games_played(), 1);
+/// assert!(alice.mu() > bob.mu());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackedRating<F: Float = f64> {
+    rating: Rating<F>,
+    games: u32,
+}
+
+impl<F: Float> TrackedRating<F> {
+    /// Starts tracking a rating that has played no games.
+    pub fn new(rating: Rating<F>) -> TrackedRating<F> {
+        TrackedRating::with_games(rating, 0)
+    }
+
+    /// Starts tracking a rating that has already played `games` games.
+    pub fn with_games(rating: Rating<F>, games: u32) -> TrackedRating<F> {
+        TrackedRating { rating, games }
+    }
+
+    /// Returns the number of games the rating has taken part in. The counter
+    /// stops at `u32::MAX`.
+    pub fn games_played(&self) -> u32 {
+        self.games
+    }
+
+    /// Returns the rating.
+    pub fn rating(&self) -> Rating<F> {
+        self.rating
+    }
+
+    /// Replaces the rating after a game, keeping the old mu if it was higher
+    /// and the player is still within the first `loss_protection` games.
+    fn record(&mut self, rating: Rating<F>, loss_protection: u32) {
+        self.rating = if self.games < loss_protection && rating.mu < self.rating.mu {
+            Rating {
+                mu: self.rating.mu,
+                ..rating
+            }
+        } else {
+            rating
+        };
+        self.games = self.games.saturating_add(1);
+    }
+}
+
+impl<F: Float> Default for TrackedRating<F> {
+    fn default() -> TrackedRating<F> {
+        TrackedRating::new(Rating::default())
+    }
+}
+
+impl<F: Float> Deref for TrackedRating<F> {
+    type Target = Rating<F>;
+
+    fn deref(&self) -> &Rating<F> {
+        &self.rating
+    }
+}
+
+impl<F: Float> From<Rating<F>> for TrackedRating<F> {
+    fn from(rating: Rating<F>) -> TrackedRating<F> {
+        TrackedRating::new(rating)
+    }
+}
+
+impl<F: Float> From<TrackedRating<F>> for Rating<F> {
+    fn from(tracked: TrackedRating<F>) -> Rating<F> {
+        tracked.rating
+    }
+}
+
+impl<F: Float> Rater<F> {
+    /// This method rates a duel like `duel`, updating the tracked ratings in
+    /// place and counting the game for both players. New players are
+    /// protected from losing mu as set by `with_loss_protection`.
+    ///
+    /// If either rating is invalid (see `Rating::is_valid`), both ratings
+    /// are left unchanged and the game is not counted.
+    pub fn duel_tracked(
+        &self,
+        p1: &mut TrackedRating<F>,
+        p2: &mut TrackedRating<F>,
+        outcome: Outcome,
+    ) {
+        if !p1.is_valid() || !p2.is_valid() {
+            return;
+        }
+
+        let (new_p1, new_p2) = self.duel(p1.rating, p2.rating, outcome);
+        p1.record(new_p1, self.loss_protection);
+        p2.record(new_p2, self.loss_protection);
+    }
+
+    /// This method updates the tracked ratings of a match like
+    /// `update_ratings`, in place, and counts the match once for every
+    /// player in it. New players are protected from losing mu as set by
+    /// `with_loss_protection`.
+    ///
+    /// Returns the errors of `update_ratings`, in which case neither the
+    /// ratings nor the counters are changed.
+    #[cfg(feature = "alloc")]
+    pub fn update_tracked_ratings(
+        &self,
+        teams: &mut [Vec<TrackedRating<F>>],
+        ranks: &[usize],
+    ) -> Result<(), BBTError> {
+        let ratings = teams
+            .iter()
+            .map(|team| team.iter().map(|tracked| tracked.rating).collect())
+            .collect();
+
+        let updated = self.update_ratings(ratings, ranks.to_vec())?;
+
+        for (team, ratings) in teams.iter_mut().zip(updated) {
+            for (tracked, rating) in team.iter_mut().zip(ratings) {
+                tracked.record(rating, self.loss_protection);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use {Float, Rater, Rating};
+
+impl<F: Float> Rating<F> {
+    /// Returns the rating that describes the same belief about the skill of
+    /// the player if skill cannot fall below `lower`: the mean and standard
+    /// deviation of the normal distribution of the rating, truncated at
+    /// `lower`.
+    ///
+    /// The truncated distribution is not normal, so the returned rating only
+    /// matches its first two moments. Its mu is always above `lower`, and its
+    /// sigma is never larger than that of the rating. Ratings many sigmas
+    /// above the bound are practically unchanged. Fully certain ratings have
+    /// their mu raised to `lower` if it is below.
+    ///
+    /// ```rust
+    /// use bbt::Rating;
+    ///
+    /// let rating = Rating::new(2.0, 4.0).truncated(0.0);
+    ///
+    /// assert!(rating.mu() > 2.0);
+    /// assert!(rating.sigma() < 4.0);
+    /// assert_eq!(Rating::new(40.0, 2.0).truncated(0.0), Rating::new(40.0, 2.0));
+    /// ```
+    pub fn truncated(&self, lower: F) -> Rating<F> {
+        if self.sigma == F::ZERO {
+            return if self.mu < lower {
+                Rating::new(lower, F::ZERO)
+            } else {
+                *self
+            };
+        }
+
+        let alpha = (lower - self.mu) / self.sigma;
+        let hazard = normal_hazard(alpha);
+
+        // Far above the bound the hazard underflows, and the rating is
+        // returned exactly as it is.
+        if hazard == F::ZERO {
+            return *self;
+        }
+
+        let variance_factor = F::ONE + alpha * hazard - hazard * hazard;
+        let variance_factor = if variance_factor > F::ZERO {
+            variance_factor
+        } else {
+            // Only reachable through rounding, far below the bound, where the
+            // variance is about `1/α²` of the original.
+            F::ONE / (alpha * alpha)
+        };
+
+        Rating::new(
+            self.mu + self.sigma * hazard,
+            self.sigma * variance_factor.sqrt(),
+        )
+    }
+
+    /// Returns the mean skill of the player if skill cannot fall below
+    /// `lower`, which is always above `lower`. This is the mu of
+    /// `truncated`.
+    pub fn truncated_mean(&self, lower: F) -> F {
+        self.truncated(lower).mu
+    }
+
+    /// Returns the interval of `z` standard deviations on either side of the
+    /// mean skill of the player if skill cannot fall below `lower`, like
+    /// `mu ± z * sigma` for `truncated`. The lower end is never below
+    /// `lower`, so the interval of a rating near the bound is lopsided.
+    ///
+    /// ```rust
+    /// use bbt::Rating;
+    ///
+    /// let (low, high) = Rating::new(3.0, 5.0).truncated_interval(0.0, 3.0);
+    ///
+    /// assert_eq!(low, 0.0);
+    /// assert!(high > 3.0);
+    /// ```
+    pub fn truncated_interval(&self, lower: F, z: F) -> (F, F) {
+        let truncated = self.truncated(lower);
+        let low = truncated.mu - z * truncated.sigma;
+        let low = if low < lower { lower } else { low };
+
+        (low, truncated.mu + z * truncated.sigma)
+    }
+}
+
+impl<F: Float> Rater<F> {
+    /// This method returns a rater for skills that cannot fall below
+    /// `lower`, such as on a scale where zero means no skill at all. Every
+    /// updated rating is replaced by `Rating::truncated` before it is
+    /// written back, so that mu and sigma describe the truncated
+    /// distribution. Without it, the ratings of weak players put much of
+    /// their mass below the bound, and keep falling below it with every
+    /// loss.
+    ///
+    /// Truncation applies wherever a policy would, before the policy. Ratings
+    /// that are not updated, such as those of fully certain players, are
+    /// left as they are. Like the policy, it is neither serialized nor
+    /// archived.
+    ///
+    /// ```rust
+    /// use bbt::{Outcome, Rater, Rating};
+    ///
+    /// let rater = Rater::default().with_truncation(0.0);
+    /// let mut weak = Rating::new(2.0, 3.0);
+    ///
+    /// for _ in 0..50 {
+    ///     weak = rater.duel(weak, Rating::new(40.0, 1.0), Outcome::Loss).0;
+    /// }
+    ///
+    /// assert!(weak.mu() > 0.0);
+    /// ```
+    pub fn with_truncation(mut self, lower: F) -> Rater<F> {
+        self.truncation = Some(lower);
+        self
+    }
+}
+
+/// Returns the hazard function `φ(α) / (1 - Φ(α))` of the standard normal
+/// distribution, which is also the mean of the standard normal distribution
+/// truncated at `α`.
+fn normal_hazard<F: Float>(alpha: F) -> F {
+    if alpha > F::from_f64(3.0) {
+        // The continued fraction of the Mills ratio, `(1 - Φ(α)) / φ(α)`,
+        // converges quickly in the tail, where the ratio of the two
+        // functions cannot be taken since both underflow.
+        let mut denominator = alpha;
+        for k in (1..=50).rev() {
+            denominator = alpha + F::from_f64(f64::from(k)) / denominator;
+        }
+
+        return denominator;
+    }
+
+    // `1 - Φ(α) = erfc(α / √2) / 2`, with the approximation of erfc from
+    // Numerical Recipes, whose relative error is below 1.2e-7:
+    // `erfc(x) = t * exp(-x² + p(t))` for `x >= 0`, where `t = 1 / (1 + x/2)`.
+    let x = alpha / F::from_f64(std::f64::consts::SQRT_2);
+    let z = x.abs();
+    let t = F::ONE / (F::ONE + z / F::from_f64(2.0));
+
+    let mut poly = F::from_f64(0.17087277);
+    for &coefficient in &[
+        -0.82215223,
+        1.48851587,
+        -1.13520398,
+        0.27886807,
+        -0.18628806,
+        0.09678418,
+        0.37409196,
+        1.00002368,
+        -1.26551223,
+    ] {
+        poly = F::from_f64(coefficient) + t * poly;
+    }
+
+    // 1/√(2π)
+    let inv_sqrt_2pi = F::from_f64(0.398_942_280_401_432_7);
+
+    if x >= F::ZERO {
+        // The factors `exp(-x²)` of φ(α) and erfc cancel.
+        F::from_f64(2.0) * inv_sqrt_2pi / (t * poly.exp())
+    } else {
+        let tail = t * (poly - z * z).exp() / F::from_f64(2.0);
+        inv_sqrt_2pi * (-x * x).exp() / (F::ONE - tail)
+    }
+}
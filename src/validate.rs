@@ -0,0 +1,146 @@
+//! Checks of the invariants that every rating update keeps.
+//!
+//! After an update, every mu is finite, every sigma is positive and finite,
+//! and no sigma is larger than before: Algorithm 1 only ever reduces the
+//! variance of a player, and the sigma floor keeps it from reaching zero.
+//! `check_ratings` checks the first two for any slice of ratings.
+//!
+//! With the `validate` feature, `Rater` also checks all of them after every
+//! update, including that the omega value of every team is finite, and
+//! panics with the indices of the offending team and player and the team
+//! values that the update was computed from. The checks run before the
+//! policy of the rater, so a `Policy` may still raise sigma, as may
+//! `bbt::decay` between updates. Without the feature, none of this is
+//! compiled.
+//!
+//! ```rust
+//! use bbt::validate::{self, InvariantViolation};
+//! use bbt::Rating;
+//!
+//! let ratings = [Rating::new(25.0, 8.0), Rating::new(f64::NAN, 8.0)];
+//!
+//! assert_eq!(
+//!     validate::check_ratings(&ratings),
+//!     Err(InvariantViolation::NonFiniteMu { index: 1 })
+//! );
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use {Float, Rating};
+
+/// InvariantViolation describes the first rating that `check_ratings` found
+/// to be broken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// The mu of the rating at position `index` is not finite.
+    NonFiniteMu { index: usize },
+
+    /// The sigma of the rating at position `index` is not finite, or not
+    /// positive.
+    InvalidSigma { index: usize },
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvariantViolation::NonFiniteMu { index } => {
+                write!(f, "The rating at index {} has a non-finite mu", index)
+            }
+            InvariantViolation::InvalidSigma { index } => write!(
+                f,
+                "The rating at index {} has a sigma that is not positive and finite",
+                index
+            ),
+        }
+    }
+}
+
+impl Error for InvariantViolation {}
+
+/// Checks that every rating has a finite mu and a positive, finite sigma,
+/// and returns the first one that does not.
+///
+/// Note that a sigma of zero is reported, too: `Rater` accepts such fully
+/// certain ratings, but never produces one from a rating that was not.
+pub fn check_ratings<F: Float>(ratings: &[Rating<F>]) -> Result<(), InvariantViolation> {
+    for (index, rating) in ratings.iter().enumerate() {
+        if !rating.mu.is_finite() {
+            return Err(InvariantViolation::NonFiniteMu { index });
+        }
+
+        if !(rating.sigma.is_finite() && rating.sigma > F::ZERO) {
+            return Err(InvariantViolation::InvalidSigma { index });
+        }
+    }
+
+    Ok(())
+}
+
+/// TeamValues are the values of every team that an update computed the new
+/// ratings from, for the panic messages of the `validate` feature.
+#[cfg(feature = "validate")]
+pub(crate) struct TeamValues<'a, F> {
+    pub mu: &'a [F],
+    pub sigma_sq: &'a [F],
+    pub omega: &'a [F],
+    pub delta: &'a [F],
+}
+
+#[cfg(feature = "validate")]
+impl<'a, F: Float> fmt::Display for TeamValues<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for team in 0..self.mu.len() {
+            write!(
+                f,
+                "\n  team {}: mu = {:?}, sigma² = {:?}, omega = {:?}, delta = {:?}",
+                team, self.mu[team], self.sigma_sq[team], self.omega[team], self.delta[team]
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Panics if the omega value of any team is not finite.
+#[cfg(feature = "validate")]
+pub(crate) fn assert_teams<F: Float>(teams: &TeamValues<F>) {
+    for (team, omega) in teams.omega.iter().enumerate() {
+        if !omega.is_finite() {
+            panic!(
+                "bbt invariant violated: the omega of team {} is not finite{}",
+                team, teams
+            );
+        }
+    }
+}
+
+/// Panics if the update of a player from `old` to `new`, before any policy,
+/// broke an invariant. A player with a sigma of zero keeps it.
+#[cfg(feature = "validate")]
+pub(crate) fn assert_update<F: Float>(
+    team: usize,
+    player: usize,
+    old: &Rating<F>,
+    new: &Rating<F>,
+    teams: &TeamValues<F>,
+) {
+    let violation = if !new.mu.is_finite() {
+        "its mu is not finite"
+    } else if !new.sigma.is_finite() || new.sigma < F::ZERO {
+        "its sigma is not finite and non-negative"
+    } else if new.sigma == F::ZERO && old.sigma != F::ZERO {
+        "its sigma dropped to zero"
+    } else if new.sigma_sq > old.sigma_sq {
+        "its sigma increased"
+    } else {
+        return;
+    };
+
+    panic!(
+        "bbt invariant violated by the update of player {} of team {}: {}\n  \
+         before: mu = {:?}, sigma = {:?}\n  after: mu = {:?}, sigma = {:?}{}",
+        player, team, violation, old.mu, old.sigma, new.mu, new.sigma, teams
+    );
+}
@@ -0,0 +1,143 @@
+//! JavaScript bindings, enabled by the `wasm` feature when compiling for
+//! WebAssembly. They are exported to JavaScript as the classes `Rater` and
+//! `Rating`:
+//!
+//! ```js
+//! const rater = new Rater(25 / 6);
+//! const [p1, p2] = rater.duel(new Rating(25, 25 / 3), new Rating(25, 25 / 3), "win");
+//!
+//! const teams = rater.updateRatings([[p1], [{ mu: 30, sigma: 4 }], [p2]], [1, 2, 2]);
+//! console.log(teams[0][0].mu, teams[0][0].conservativeEstimate());
+//! ```
+//!
+//! Errors are thrown as JavaScript `Error`s carrying the message of the
+//! `BBTError`.
+
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+
+use {BBTError, Outcome, Rater, Rating};
+
+/// A `Rating`, exported to JavaScript as `Rating`.
+#[wasm_bindgen(js_name = Rating)]
+#[derive(Clone, Debug)]
+pub struct JsRating(Rating);
+
+#[wasm_bindgen(js_class = Rating)]
+impl JsRating {
+    /// Creates a rating with the given mu and sigma.
+    #[wasm_bindgen(constructor)]
+    pub fn new(mu: f64, sigma: f64) -> JsRating {
+        JsRating(Rating::new(mu, sigma))
+    }
+
+    /// The estimated skill of the player.
+    #[wasm_bindgen(getter)]
+    pub fn mu(&self) -> f64 {
+        self.0.mu
+    }
+
+    /// The uncertainty of the estimate.
+    #[wasm_bindgen(getter)]
+    pub fn sigma(&self) -> f64 {
+        self.0.sigma
+    }
+
+    /// Returns the conservative skill estimate `mu - 3 * sigma`.
+    #[wasm_bindgen(js_name = conservativeEstimate)]
+    pub fn conservative_estimate(&self) -> f64 {
+        self.0.conservative_estimate()
+    }
+}
+
+/// A `Rater`, exported to JavaScript as `Rater`.
+#[wasm_bindgen(js_name = Rater)]
+pub struct JsRater(Rater);
+
+#[wasm_bindgen(js_class = Rater)]
+impl JsRater {
+    /// Creates a rater with the given β. Throws if β is negative or not
+    /// finite.
+    #[wasm_bindgen(constructor)]
+    pub fn new(beta: f64) -> Result<JsRater, JsError> {
+        if !beta.is_finite() || beta < 0.0 {
+            return Err(error(BBTError::InvalidParameter { name: "beta" }));
+        }
+
+        Ok(JsRater(Rater::new(beta)))
+    }
+
+    /// Returns the new ratings of two players after a duel, like
+    /// `Rater::duel`. The outcome is `"win"`, `"loss"` or `"draw"`, from the
+    /// first player's perspective.
+    pub fn duel(
+        &self,
+        p1: &JsRating,
+        p2: &JsRating,
+        outcome: &str,
+    ) -> Result<Vec<JsRating>, JsError> {
+        let outcome = match outcome {
+            "win" => Outcome::Win,
+            "loss" => Outcome::Loss,
+            "draw" => Outcome::Draw,
+            _ => return Err(JsError::new(&format!("unknown outcome {:?}", outcome))),
+        };
+
+        let (p1, p2) = self.0.duel(p1.0, p2.0, outcome);
+
+        Ok(vec![JsRating(p1), JsRating(p2)])
+    }
+
+    /// Returns the updated ratings of a match, like `Rater::update_ratings`.
+    /// `teams` is an array of teams, each an array of objects with `mu` and
+    /// `sigma` properties, such as `Rating`s. The result has the same shape
+    /// and contains `Rating`s.
+    #[wasm_bindgen(js_name = updateRatings)]
+    pub fn update_ratings(&self, teams: Array, ranks: Vec<usize>) -> Result<Array, JsError> {
+        let teams = teams
+            .iter()
+            .map(|team| {
+                Array::from(&team)
+                    .iter()
+                    .map(|player| {
+                        Ok(Rating::new(
+                            number(&player, "mu")?,
+                            number(&player, "sigma")?,
+                        ))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, JsError>>()?;
+
+        let teams = self.0.update_ratings(teams, ranks).map_err(error)?;
+
+        Ok(teams
+            .into_iter()
+            .map(|team| {
+                team.into_iter()
+                    .map(|rating| JsValue::from(JsRating(rating)))
+                    .collect::<Array>()
+            })
+            .collect())
+    }
+
+    /// Returns the probability that the first player beats the second, like
+    /// `Rater::win_probability`.
+    #[wasm_bindgen(js_name = winProbability)]
+    pub fn win_probability(&self, p1: &JsRating, p2: &JsRating) -> f64 {
+        self.0.win_probability(&p1.0, &p2.0)
+    }
+}
+
+/// Reads the numeric property `name` of a JavaScript object.
+fn number(object: &JsValue, name: &str) -> Result<f64, JsError> {
+    Reflect::get(object, &JsValue::from_str(name))
+        .ok()
+        .and_then(|value| value.as_f64())
+        .ok_or_else(|| JsError::new(&format!("expected a number as `{}`", name)))
+}
+
+/// Converts a `BBTError` into a JavaScript error with the same message.
+fn error(error: BBTError) -> JsError {
+    JsError::new(&error.to_string())
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use bbt::{GameMut, Rater, Rating, UpdateScratch};
+
+struct CountingAllocator;
+
+// Tests run on several threads, so allocations are counted per thread.
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn update_with_scratch_does_not_allocate_after_warm_up() {
+    let rater = Rater::default();
+    let mut scratch = UpdateScratch::new();
+
+    let teams = vec![
+        vec![Rating::default(), Rating::new(30.0, 4.0)],
+        vec![Rating::new(20.0, 6.0)],
+        vec![Rating::default()],
+        vec![Rating::new(27.0, 2.0), Rating::default()],
+    ];
+    let ranks = vec![1, 2, 2, 4];
+
+    rater
+        .update_ratings_in(teams.clone(), ranks.clone(), &mut scratch)
+        .unwrap();
+
+    let (teams, ranks) = (teams.clone(), ranks.clone());
+    let before = allocations();
+    let result = rater.update_ratings_in(teams, ranks, &mut scratch);
+    let after = allocations();
+
+    assert!(result.is_ok());
+    assert_eq!(after, before);
+}
+
+#[test]
+fn small_update_does_not_allocate() {
+    let rater = Rater::default();
+    let teams = vec![vec![Rating::default()]; 6];
+    let ranks = vec![1, 2, 3, 4, 5, 6];
+
+    let before = allocations();
+    let result = rater.update_ratings(teams, ranks);
+    let after = allocations();
+
+    assert!(result.is_ok());
+    assert_eq!(after, before);
+}
+
+#[test]
+fn batch_update_allocates_working_memory_once() {
+    let rater = Rater::default();
+    let ranks: Vec<usize> = (1..=20).collect();
+    let mut games: Vec<Vec<Vec<Rating>>> = vec![vec![vec![Rating::default()]; 20]; 100];
+
+    let mut batch: Vec<GameMut> = games
+        .iter_mut()
+        .map(|teams| GameMut {
+            teams,
+            ranks: &ranks,
+        })
+        .collect();
+
+    let before = allocations();
+    rater.update_batch(&mut batch).unwrap();
+    let after = allocations();
+
+    // One allocation for each of the six working arrays, instead of six per
+    // game when updating the games one by one.
+    assert!(after - before <= 6);
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "arrow")]
+
+extern crate arrow_array;
+extern crate bbt;
+
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    Float64Builder, Int32Builder, Int64Builder, ListBuilder, StringBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use bbt::replay::{self, GameRecord, GameSchema, ReplayError};
+use bbt::{Rater, Rating};
+
+/// The teams and ranks of a game.
+type Game<'a> = (&'a [&'a [&'a str]], &'a [Option<i64>]);
+
+/// Builds the `teams` and `ranks` columns of a batch with one row per game.
+fn games(games: &[Game]) -> Vec<(&'static str, ArrayRef)> {
+    let mut teams = ListBuilder::new(ListBuilder::new(StringBuilder::new()));
+    let mut ranks = ListBuilder::new(Int64Builder::new());
+
+    for &(game_teams, game_ranks) in games {
+        for team in game_teams {
+            teams.values().append_value(team.iter().map(Some));
+        }
+        teams.append(true);
+        ranks.append_value(game_ranks.iter().cloned());
+    }
+
+    vec![
+        ("teams", Arc::new(teams.finish()) as ArrayRef),
+        ("ranks", Arc::new(ranks.finish()) as ArrayRef),
+    ]
+}
+
+fn batch(columns: Vec<(&'static str, ArrayRef)>) -> RecordBatch {
+    RecordBatch::try_from_iter(columns).unwrap()
+}
+
+#[test]
+fn replays_match_the_direct_api() {
+    let rater = Rater::default();
+
+    let batches = vec![
+        batch(games(&[(&[&["alice"], &["bob"]], &[Some(1), Some(2)])])),
+        batch(games(&[(
+            &[&["alice", "carol"], &["bob"], &["dave", "erin"]],
+            &[Some(2), Some(1), Some(2)],
+        )])),
+    ];
+
+    let ratings =
+        replay::from_record_batches(&rater, batches, &GameSchema::default(), Rating::default())
+            .unwrap();
+
+    let duel = rater
+        .update_ratings(vec![vec![Rating::default()]; 2], vec![1, 2])
+        .unwrap();
+    let (alice, bob) = (duel[0][0], duel[1][0]);
+    let match_ = rater
+        .update_ratings(
+            vec![
+                vec![alice, Rating::default()],
+                vec![bob],
+                vec![Rating::default(); 2],
+            ],
+            vec![2, 1, 2],
+        )
+        .unwrap();
+
+    assert_eq!(ratings.len(), 5);
+    assert_eq!(ratings["alice"], match_[0][0]);
+    assert_eq!(ratings["carol"], match_[0][1]);
+    assert_eq!(ratings["bob"], match_[1][0]);
+    assert_eq!(ratings["dave"], match_[2][0]);
+    assert_eq!(ratings["erin"], match_[2][1]);
+}
+
+#[test]
+fn timestamps_and_weights_are_read_from_the_named_columns() {
+    let mut columns = games(&[
+        (&[&["alice"], &["bob"]], &[Some(1), Some(2)]),
+        (&[&["bob"], &["alice"]], &[Some(1), Some(2)]),
+    ]);
+
+    let mut played = Int64Builder::new();
+    played.append_value(1_534_000_000);
+    played.append_null();
+    let mut weight = Float64Builder::new();
+    weight.append_null();
+    weight.append_value(0.5);
+    columns.push(("played", Arc::new(played.finish()) as ArrayRef));
+    columns.push(("weight", Arc::new(weight.finish()) as ArrayRef));
+
+    let schema = GameSchema {
+        timestamp: Some("played".to_string()),
+        weight: Some("weight".to_string()),
+        ..GameSchema::default()
+    };
+    let records = replay::read_record_batches(vec![batch(columns)], &schema).unwrap();
+
+    let mut first = GameRecord::new(
+        vec![vec!["alice".to_string()], vec!["bob".to_string()]],
+        vec![1, 2],
+    );
+    first.timestamp = Some(1_534_000_000);
+    let mut second = GameRecord::new(
+        vec![vec!["bob".to_string()], vec!["alice".to_string()]],
+        vec![1, 2],
+    );
+    second.weight = Some(0.5);
+    assert_eq!(records, vec![first, second]);
+}
+
+#[test]
+fn schema_mismatches_name_the_batch() {
+    let rater = Rater::default();
+    let good = || batch(games(&[(&[&["alice"], &["bob"]], &[Some(1), Some(2)])]));
+
+    let renamed = GameSchema {
+        ranks: "placement".to_string(),
+        ..GameSchema::default()
+    };
+    let error =
+        replay::from_record_batches(&rater, vec![good()], &renamed, Rating::default()).unwrap_err();
+    match error {
+        ReplayError::MissingColumn { batch: 0, ref name } if name == "placement" => {}
+        ref other => panic!("unexpected error {:?}", other),
+    }
+
+    // The ranks are a flat column instead of a list.
+    let mut flat = games(&[(&[&["alice"], &["bob"]], &[Some(1), Some(2)])]);
+    let mut ranks = Int32Builder::new();
+    ranks.append_value(1);
+    flat[1].1 = Arc::new(ranks.finish());
+
+    let error = replay::from_record_batches(
+        &rater,
+        vec![good(), batch(flat)],
+        &GameSchema::default(),
+        Rating::default(),
+    )
+    .unwrap_err();
+    assert_eq!(error.batch(), Some(1));
+    assert_eq!(error.row(), None);
+    match error {
+        ReplayError::InvalidColumn { ref name, .. } if name == "ranks" => {}
+        ref other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn invalid_rows_name_the_batch_and_row() {
+    let rater = Rater::default();
+    let batches = vec![batch(games(&[
+        (&[&["alice"], &["bob"]], &[Some(1), Some(2)]),
+        (&[&["alice"], &["bob"]], &[Some(1), None]),
+    ]))];
+
+    let error =
+        replay::from_record_batches(&rater, batches, &GameSchema::default(), Rating::default())
+            .unwrap_err();
+    assert_eq!((error.batch(), error.row()), (Some(0), Some(1)));
+    match error {
+        ReplayError::InvalidRow { .. } => {}
+        ref other => panic!("unexpected error {:?}", other),
+    }
+
+    // Three ranks for two teams.
+    let batches = vec![batch(games(&[(
+        &[&["alice"], &["bob"]],
+        &[Some(1), Some(2), Some(3)],
+    )]))];
+    let error =
+        replay::from_record_batches(&rater, batches, &GameSchema::default(), Rating::default())
+            .unwrap_err();
+    match error {
+        ReplayError::RejectedRow {
+            batch: 0, row: 0, ..
+        } => {}
+        ref other => panic!("unexpected error {:?}", other),
+    }
+}
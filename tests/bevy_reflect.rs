@@ -0,0 +1,134 @@
+#![cfg(feature = "bevy_reflect")]
+extern crate bbt;
+extern crate bevy_reflect;
+extern crate ron;
+extern crate serde;
+
+use bevy_reflect::serde::{ReflectDeserializer, ReflectSerializer};
+use bevy_reflect::{
+    DynamicStruct, FromReflect, GetField, GetPath, PartialReflect, Struct, TypeRegistry,
+};
+use serde::de::DeserializeSeed;
+
+use bbt::{Outcome, Rater, Rating};
+
+fn registry() -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    registry.register::<Rating>();
+    registry.register::<Rating<f32>>();
+    registry.register::<Rater>();
+    registry.register::<Outcome>();
+    registry
+}
+
+/// Serializes a value like a component in a Bevy scene, and reads it back.
+fn scene_round_trip(value: &dyn PartialReflect) -> (String, Box<dyn PartialReflect>) {
+    let registry = registry();
+
+    let serializer = ReflectSerializer::new(value, &registry);
+    let text = ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::default()).unwrap();
+
+    let mut deserializer = ron::de::Deserializer::from_str(&text).unwrap();
+    let read = ReflectDeserializer::new(&registry)
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+    (text, read)
+}
+
+#[test]
+fn ratings_are_patched_through_dynamic_structs() {
+    let rating = Rating::new(25.0, 4.0);
+
+    let mut dynamic: DynamicStruct = rating.to_dynamic_struct();
+    assert_eq!(dynamic.field_len(), 2);
+    *dynamic.get_field_mut::<f64>("mu").unwrap() = 31.5;
+
+    assert_eq!(
+        Rating::<f64>::from_reflect(&dynamic),
+        Some(Rating::new(31.5, 4.0))
+    );
+
+    let mut patched = rating;
+    patched.apply(&dynamic);
+    assert_eq!(patched, Rating::new(31.5, 4.0));
+
+    // Missing fields are left alone.
+    let mut sigma_only = DynamicStruct::default();
+    sigma_only.insert("sigma", 2.0f64);
+    patched.apply(&sigma_only);
+    assert_eq!(patched, Rating::new(31.5, 2.0));
+}
+
+#[test]
+fn fields_are_read_only() {
+    let mut rating = Rating::new(25.0, 4.0);
+
+    assert_eq!(rating.path::<f64>("mu"), Ok(&25.0));
+    assert_eq!(rating.path::<f64>("sigma"), Ok(&4.0));
+    assert_eq!(rating.get_field::<f64>("sigma"), Some(&4.0));
+    assert!(rating.field_mut("sigma").is_none());
+    assert!(rating.path_mut::<f64>("mu").is_err());
+    assert_eq!(rating.field_len(), 2);
+}
+
+#[test]
+fn invalid_patches_are_rejected() {
+    let mut rating = Rating::new(25.0, 4.0);
+
+    for &(name, value) in &[("sigma", -1.0), ("sigma", 0.0), ("mu", f64::NAN)] {
+        let mut patch = DynamicStruct::default();
+        patch.insert(name, value);
+
+        assert!(rating.try_apply(&patch).is_err(), "{} = {}", name, value);
+        assert_eq!(rating, Rating::new(25.0, 4.0));
+        assert!(Rating::<f64>::from_reflect(&patch).is_none());
+    }
+
+    let mut wrong_type = DynamicStruct::default();
+    wrong_type.insert("mu", "thirty");
+    assert!(rating.try_apply(&wrong_type).is_err());
+
+    let mut incomplete = DynamicStruct::default();
+    incomplete.insert("mu", 30.0f64);
+    assert!(Rating::<f64>::from_reflect(&incomplete).is_none());
+}
+
+#[test]
+fn ratings_round_trip_through_scenes() {
+    let rating = Rating::new(27.5, 3.25);
+    let (text, read) = scene_round_trip(&rating);
+
+    assert!(text.contains("\"bbt::Rating<f64>\""), "{}", text);
+    assert!(text.contains("mu: 27.5"), "{}", text);
+    assert_eq!(Rating::<f64>::from_reflect(&*read), Some(rating));
+
+    let small = Rating::new(12.0f32, 1.5);
+    let (_, read) = scene_round_trip(&small);
+    assert_eq!(Rating::<f32>::from_reflect(&*read), Some(small));
+
+    let mut damaged = DynamicStruct::default();
+    damaged.insert("mu", 27.5f64);
+    damaged.insert("sigma", -3.0f64);
+    assert!(Rating::<f64>::from_reflect(&damaged).is_none());
+}
+
+#[test]
+fn outcomes_and_raters_round_trip_through_scenes() {
+    let (_, read) = scene_round_trip(&Outcome::Draw);
+    assert_eq!(Outcome::from_reflect(&*read), Some(Outcome::Draw));
+
+    let rater = Rater::new(3.0).strict_ranks();
+    let (text, read) = scene_round_trip(&rater);
+    assert!(text.contains("beta: 3.0"), "{}", text);
+
+    let read: Rater = Rater::from_reflect(&*read).unwrap();
+    assert_eq!(read.beta(), 3.0);
+
+    // Raters are opaque, but can be applied and cloned.
+    let mut target = Rater::default();
+    target.apply(&rater);
+    assert_eq!(target.beta(), 3.0);
+    let cloned = rater.reflect_clone().unwrap();
+    assert_eq!(cloned.downcast_ref::<Rater>().unwrap().beta(), 3.0);
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "ndarray")]
+
+extern crate bbt;
+extern crate ndarray;
+
+use bbt::bulk::{self, BulkError};
+use bbt::{Outcome, Rater, Rating};
+use ndarray::{Array1, ArrayView1};
+
+/// A xorshift generator, so that the columns are the same on every run.
+struct Columns(u64);
+
+impl Columns {
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn column(&mut self, n: usize, low: f64, high: f64) -> Array1<f64> {
+        (0..n).map(|_| low + (high - low) * self.next()).collect()
+    }
+}
+
+#[test]
+fn win_probabilities_match_the_scalar_api() {
+    let rater = Rater::default();
+    let mut columns = Columns(0x9e37_79b9_7f4a_7c15);
+    let mus = columns.column(40, 0.0, 50.0);
+    let sigmas = columns.column(40, 0.5, 9.0);
+
+    let matrix = bulk::win_probability_matrix(&rater, mus.view(), sigmas.view()).unwrap();
+
+    assert_eq!(matrix.dim(), (40, 40));
+    for i in 0..40 {
+        for j in 0..40 {
+            let expected = rater.win_probability(
+                &Rating::new(mus[i], sigmas[i]),
+                &Rating::new(mus[j], sigmas[j]),
+            );
+            assert_eq!(matrix[[i, j]], expected);
+        }
+    }
+}
+
+#[test]
+fn conservative_estimates_match_the_scalar_api() {
+    let mut columns = Columns(42);
+    let mus = columns.column(100, -10.0, 60.0);
+    let sigmas = columns.column(100, 0.0, 9.0);
+
+    let estimates = bulk::conservative(mus.view(), sigmas.view()).unwrap();
+
+    let mut buffer = bbt::RatingsBuffer::new();
+    for (&mu, &sigma) in mus.iter().zip(&sigmas) {
+        buffer.push(Rating::new(mu, sigma));
+    }
+    let mut expected = vec![0.0; 100];
+    buffer.conservative_estimates(&mut expected);
+
+    assert_eq!(estimates.to_vec(), expected);
+}
+
+#[test]
+fn duel_updates_match_the_scalar_api() {
+    let rater = Rater::default();
+    let mut columns = Columns(7);
+    let n = 200;
+    let mus = columns.column(n, 0.0, 50.0);
+    let sigmas = columns.column(n, 0.5, 9.0);
+    let opponents_mu = columns.column(n, 0.0, 50.0);
+    let opponents_sigma = columns.column(n, 0.5, 9.0);
+    let outcomes: Array1<Outcome> = (0..n)
+        .map(|_| match (columns.next() * 3.0) as u32 {
+            0 => Outcome::Win,
+            1 => Outcome::Draw,
+            _ => Outcome::Loss,
+        })
+        .collect();
+
+    let (new_mus, new_sigmas) = bulk::duel_updates(
+        &rater,
+        mus.view(),
+        sigmas.view(),
+        opponents_mu.view(),
+        opponents_sigma.view(),
+        outcomes.view(),
+    )
+    .unwrap();
+
+    for i in 0..n {
+        let (expected, _) = rater.duel(
+            Rating::new(mus[i], sigmas[i]),
+            Rating::new(opponents_mu[i], opponents_sigma[i]),
+            outcomes[i],
+        );
+        assert_eq!(new_mus[i], expected.mu());
+        assert_eq!(new_sigmas[i], expected.sigma());
+    }
+}
+
+#[test]
+fn mismatched_columns_are_rejected() {
+    let rater = Rater::default();
+    let three = Array1::from(vec![25.0; 3]);
+    let two = Array1::from(vec![25.0 / 3.0; 2]);
+    let outcomes = Array1::from(vec![Outcome::Win; 3]);
+
+    assert_eq!(
+        bulk::win_probability_matrix(&rater, three.view(), two.view()),
+        Err(BulkError::MismatchedLength {
+            argument: "sigmas",
+            expected: 3,
+            found: 2
+        })
+    );
+    assert!(bulk::conservative(two.view(), three.view()).is_err());
+    assert_eq!(
+        bulk::duel_updates(
+            &rater,
+            three.view(),
+            three.view(),
+            three.view(),
+            two.view(),
+            outcomes.view(),
+        ),
+        Err(BulkError::MismatchedLength {
+            argument: "opponents_sigma",
+            expected: 3,
+            found: 2
+        })
+    );
+    assert!(bulk::duel_updates(
+        &rater,
+        three.view(),
+        three.view(),
+        three.view(),
+        three.view(),
+        ArrayView1::from(&[Outcome::Win][..]),
+    )
+    .is_err());
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "alloc")]
+
+extern crate bbt;
+
+use bbt::{BBTError, RankObservation, Rater, Rating};
+use RankObservation::{Below, Exact};
+
+#[cfg(not(feature = "simd"))]
+fn qualifier() -> Vec<Vec<Rating>> {
+    (0..14)
+        .map(|i| vec![Rating::new(15.0 + f64::from(i), 3.0 + 0.25 * f64::from(i))])
+        .collect()
+}
+
+// With the `simd` feature, `update_ratings` computes the pairwise terms with
+// a vectorized kernel, so its results are close to, but not identical with,
+// those of the censored update.
+#[cfg(not(feature = "simd"))]
+#[test]
+fn exact_teams_are_updated_as_if_censored_teams_finished_below_them() {
+    let rater = Rater::default();
+
+    let mut observations: Vec<RankObservation> = (1..=10).rev().map(Exact).collect();
+    observations.extend(vec![Below(10); 4]);
+
+    let censored = rater
+        .update_ratings_censored(qualifier(), &observations)
+        .unwrap();
+
+    let mut ranks: Vec<usize> = (1..=10).rev().collect();
+    ranks.extend(11..=14);
+    let full = rater.update_ratings(qualifier(), ranks).unwrap();
+
+    assert_eq!(censored[..10], full[..10]);
+    assert_ne!(censored[10..], full[10..]);
+}
+
+#[test]
+fn censored_teams_do_not_affect_each_other() {
+    let rater = Rater::default();
+    let leader = Rating::new(30.0, 2.0);
+    let strong = Rating::new(35.0, 5.0);
+    let weak = Rating::new(10.0, 4.0);
+
+    let alone = rater
+        .update_ratings_censored(vec![vec![leader], vec![strong]], &[Exact(1), Below(1)])
+        .unwrap();
+    let together = rater
+        .update_ratings_censored(
+            vec![vec![leader], vec![strong], vec![weak]],
+            &[Exact(1), Below(1), Below(1)],
+        )
+        .unwrap();
+
+    assert_eq!(together[1], alone[1]);
+
+    let weak_alone = rater
+        .update_ratings_censored(vec![vec![leader], vec![weak]], &[Exact(1), Below(1)])
+        .unwrap();
+    assert_eq!(together[2], weak_alone[1]);
+}
+
+#[test]
+fn observations_are_validated() {
+    let rater = Rater::default();
+    let teams = || vec![vec![Rating::default()]; 4];
+
+    assert!(rater
+        .update_ratings_censored(teams(), &[Exact(1), Exact(2), Exact(2), Below(3)])
+        .is_ok());
+    assert!(rater
+        .update_ratings_censored(teams(), &[Below(0), Below(0), Below(0), Below(0)])
+        .is_ok());
+
+    assert_eq!(
+        rater.update_ratings_censored(teams(), &[Exact(1), Exact(3), Below(2), Below(2)]),
+        Err(BBTError::InvalidRanks)
+    );
+    assert_eq!(
+        rater.update_ratings_censored(teams(), &[Exact(1), Below(1)]),
+        Err(BBTError::MismatchedLengths { teams: 4, ranks: 2 })
+    );
+
+    let strict = Rater::default().strict_ranks();
+    assert!(strict
+        .update_ratings_censored(teams(), &[Exact(1), Exact(2), Below(2), Below(2)])
+        .is_ok());
+    assert_eq!(
+        strict.update_ratings_censored(teams(), &[Exact(1), Exact(2), Below(5), Below(5)]),
+        Err(BBTError::InvalidRanks)
+    );
+}
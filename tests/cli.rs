@@ -0,0 +1,152 @@
+#![cfg(feature = "cli")]
+
+extern crate bbt;
+
+use std::fs;
+use std::process::{Command, Output};
+
+use bbt::replay::{self, GameRecord};
+use bbt::{Rater, Rating};
+
+fn bbt(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bbt"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// The rating table of the games in the fixtures, as written by the library.
+fn expected_table(beta: f64) -> String {
+    let game = |teams: &[&[&str]], ranks: &[usize]| {
+        GameRecord::new(
+            teams
+                .iter()
+                .map(|team| team.iter().map(|player| player.to_string()).collect())
+                .collect(),
+            ranks.to_vec(),
+        )
+    };
+    let games = vec![
+        game(&[&["alice"], &["bob"]], &[1, 2]),
+        game(&[&["alice", "carol"], &["bob"], &["dave"]], &[2, 1, 3]),
+        game(&[&["carol"], &["dave"]], &[1, 2]),
+    ];
+
+    let ratings = replay::replay(&Rater::new(beta), &games, Rating::default()).unwrap();
+    let mut table: Vec<_> = ratings.iter().collect();
+    table.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut csv = Vec::new();
+    bbt::io::write_csv(&mut csv, table).unwrap();
+    String::from_utf8(csv).unwrap()
+}
+
+#[test]
+fn replay_writes_the_rating_table() {
+    let csv = bbt(&["replay", "tests/fixtures/cli/games.csv"]);
+    assert_eq!(csv.status.code(), Some(0));
+    assert_eq!(stdout(&csv), expected_table(25.0 / 6.0));
+
+    let jsonl = bbt(&["replay", "tests/fixtures/cli/games.jsonl", "--beta", "3.5"]);
+    assert_eq!(jsonl.status.code(), Some(0));
+    assert_eq!(stdout(&jsonl), expected_table(3.5));
+}
+
+#[test]
+fn replay_writes_to_the_out_file() {
+    let path = std::env::temp_dir().join(format!("bbt-cli-{}.csv", std::process::id()));
+
+    let output = bbt(&[
+        "replay",
+        "tests/fixtures/cli/games.csv",
+        "--out",
+        path.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        expected_table(25.0 / 6.0)
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn predict_prints_the_win_probability() {
+    let output = bbt(&["predict", "30", "2", "25", "3", "--beta", "4"]);
+
+    let expected =
+        Rater::new(4.0).win_probability(&Rating::new(30.0, 2.0), &Rating::new(25.0, 3.0));
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), format!("{}\n", expected));
+}
+
+#[test]
+fn fit_beta_prints_the_grid_and_the_best_beta() {
+    let output = bbt(&[
+        "fit-beta",
+        "tests/fixtures/cli/games.jsonl",
+        "--min",
+        "1",
+        "--max",
+        "3",
+        "--steps",
+        "3",
+    ]);
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[1].trim_start().starts_with("1.0000"));
+    assert!(lines[3].trim_start().starts_with("3.0000"));
+    assert!(lines[4].starts_with("best beta: "));
+}
+
+#[test]
+fn data_errors_name_the_line() {
+    let output = bbt(&["replay", "tests/fixtures/cli/bad_rank.csv"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).starts_with("bbt: tests/fixtures/cli/bad_rank.csv:3: "));
+
+    let output = bbt(&["replay", "tests/fixtures/cli/rejected.jsonl"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("bbt: tests/fixtures/cli/rejected.jsonl:2: "));
+
+    let output = bbt(&["replay", "tests/fixtures/cli/missing.csv"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn usage_errors_exit_with_two() {
+    for args in &[
+        &[][..],
+        &["frobnicate"][..],
+        &["replay"][..],
+        &["replay", "tests/fixtures/cli/games.csv", "--beta", "-1"][..],
+        &["replay", "tests/fixtures/cli/games.csv", "--beta"][..],
+        &["replay", "tests/fixtures/cli/games.csv", "--format", "xml"][..],
+        &["predict", "30", "2", "25"][..],
+        &["predict", "30", "two", "25", "3"][..],
+        &["fit-beta", "tests/fixtures/cli/games.csv", "--steps", "1"][..],
+    ] {
+        let output = bbt(args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(stderr(&output).contains("Usage:"), "{:?}", args);
+    }
+
+    let help = bbt(&["help"]);
+    assert_eq!(help.status.code(), Some(0));
+    assert!(stdout(&help).starts_with("Usage:"));
+}
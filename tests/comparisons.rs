@@ -0,0 +1,133 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+fn players() -> Vec<Rating> {
+    vec![
+        Rating::new(30.0, 3.0),
+        Rating::new(27.0, 8.0),
+        Rating::new(20.0, 2.0),
+        Rating::new(26.0, 4.0),
+    ]
+}
+
+#[test]
+fn a_single_comparison_is_a_duel() {
+    let rater = Rater::default();
+
+    for &outcome in &[Outcome::Win, Outcome::Draw, Outcome::Loss] {
+        let mut ratings = players();
+        rater
+            .update_from_comparisons(&mut ratings, &[(3, 1, outcome)])
+            .unwrap();
+
+        let (p3, p1) = rater.duel(players()[3], players()[1], outcome);
+        assert_eq!(ratings, vec![players()[0], p1, players()[2], p3]);
+    }
+}
+
+#[test]
+fn a_round_robin_is_a_ranked_update() {
+    let rater = Rater::default();
+    let ranks = [2, 1, 2, 4];
+
+    let mut comparisons = Vec::new();
+    for i in 0..4 {
+        for j in i + 1..4 {
+            let outcome = match ranks[i] {
+                rank if rank < ranks[j] => Outcome::Win,
+                rank if rank > ranks[j] => Outcome::Loss,
+                _ => Outcome::Draw,
+            };
+            comparisons.push((i, j, outcome));
+        }
+    }
+
+    let mut ratings = players();
+    rater
+        .update_from_comparisons(&mut ratings, &comparisons)
+        .unwrap();
+
+    let teams: Vec<Vec<Rating>> = players().into_iter().map(|r| vec![r]).collect();
+    let expected = rater.update_ratings(teams, ranks.to_vec()).unwrap();
+
+    for (rating, expected) in ratings.iter().zip(&expected) {
+        // The vectorized kernel of `update_ratings` is not bit-identical.
+        if cfg!(feature = "simd") {
+            assert!((rating.mu() - expected[0].mu()).abs() < 1e-12);
+            assert!((rating.sigma() - expected[0].sigma()).abs() < 1e-12);
+        } else {
+            assert_eq!(rating, &expected[0]);
+        }
+    }
+}
+
+#[test]
+fn the_order_of_the_comparisons_does_not_matter() {
+    let rater = Rater::default();
+    // Incomplete, repeated and contradictory decisions.
+    let mut comparisons = vec![
+        (0, 1, Outcome::Win),
+        (1, 0, Outcome::Win),
+        (2, 0, Outcome::Draw),
+        (3, 2, Outcome::Loss),
+        (0, 3, Outcome::Win),
+        (0, 1, Outcome::Win),
+        (2, 1, Outcome::Win),
+    ];
+
+    let mut expected = players();
+    rater
+        .update_from_comparisons(&mut expected, &comparisons)
+        .unwrap();
+
+    for shift in 1..comparisons.len() {
+        comparisons.rotate_left(1);
+        if shift % 2 == 0 {
+            comparisons.reverse();
+        }
+
+        let mut ratings = players();
+        rater
+            .update_from_comparisons(&mut ratings, &comparisons)
+            .unwrap();
+
+        for (rating, expected) in ratings.iter().zip(&expected) {
+            assert_eq!(rating.mu().to_bits(), expected.mu().to_bits());
+            assert_eq!(rating.sigma().to_bits(), expected.sigma().to_bits());
+        }
+    }
+}
+
+#[test]
+fn invalid_comparisons_change_nothing() {
+    let rater = Rater::default();
+
+    for comparisons in &[
+        vec![(0, 1, Outcome::Win), (1, 4, Outcome::Loss)],
+        vec![(0, 1, Outcome::Win), (2, 2, Outcome::Draw)],
+    ] {
+        let mut ratings = players();
+        assert_eq!(
+            rater.update_from_comparisons(&mut ratings, comparisons),
+            Err(BBTError::InvalidParameter {
+                name: "comparisons"
+            })
+        );
+        assert_eq!(ratings, players());
+    }
+
+    let mut ratings = players();
+    ratings[2] = Rating::new(f64::NAN, 1.0);
+    assert_eq!(
+        rater.update_from_comparisons(&mut ratings, &[(0, 1, Outcome::Win), (2, 3, Outcome::Win)]),
+        Err(BBTError::InvalidRating { team: 2, player: 0 })
+    );
+    assert_eq!(ratings[0], players()[0]);
+
+    // An empty list of comparisons is fine, and changes nothing either.
+    let mut ratings = players();
+    rater.update_from_comparisons(&mut ratings, &[]).unwrap();
+    assert_eq!(ratings, players());
+}
@@ -0,0 +1,11 @@
+// The compiler's messages are compared with the `.stderr` files next to the
+// cases. After a change of the messages, regenerate them with
+// `TRYBUILD=overwrite cargo test --test compile_fail`.
+#![cfg(not(target_arch = "wasm32"))]
+
+extern crate trybuild;
+
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/compile_fail/*.rs");
+}
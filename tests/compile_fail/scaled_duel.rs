@@ -0,0 +1,19 @@
+extern crate bbt;
+
+use bbt::{DefaultScale, Outcome, Scale, ScaledRater, ScaledRating};
+
+struct Legacy;
+
+impl Scale for Legacy {
+    const MU: f64 = 1500.0;
+    const SIGMA: f64 = 500.0;
+    const BETA: f64 = 250.0;
+}
+
+fn main() {
+    let rater = ScaledRater::<DefaultScale>::new();
+    let alice = ScaledRating::<DefaultScale>::default();
+    let bob = ScaledRating::<Legacy>::default();
+
+    rater.duel(alice, bob, Outcome::Win);
+}
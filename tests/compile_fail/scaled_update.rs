@@ -0,0 +1,21 @@
+extern crate bbt;
+
+use bbt::{DefaultScale, Scale, ScaledRater, ScaledRating};
+
+struct Legacy;
+
+impl Scale for Legacy {
+    const MU: f64 = 1500.0;
+    const SIGMA: f64 = 500.0;
+    const BETA: f64 = 250.0;
+}
+
+fn main() {
+    let rater = ScaledRater::<Legacy>::new();
+    let teams = vec![
+        vec![ScaledRating::<DefaultScale>::default()],
+        vec![ScaledRating::<DefaultScale>::default()],
+    ];
+
+    rater.update_ratings(teams, vec![1, 2]).unwrap();
+}
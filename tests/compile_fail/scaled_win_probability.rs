@@ -0,0 +1,19 @@
+extern crate bbt;
+
+use bbt::{Scale, ScaledRater, ScaledRating};
+
+struct Legacy;
+
+impl Scale for Legacy {
+    const MU: f64 = 1500.0;
+    const SIGMA: f64 = 500.0;
+    const BETA: f64 = 250.0;
+}
+
+fn main() {
+    // The default scale is used when none is given.
+    let rater: ScaledRater = ScaledRater::new();
+    let legacy = ScaledRating::<Legacy>::default();
+
+    rater.win_probability(&legacy, &legacy);
+}
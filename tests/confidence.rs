@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+extern crate bbt;
+
+use bbt::{BBTError, Rating};
+
+#[test]
+fn three_sigma_intervals_give_the_default_rating() {
+    let default: Rating = Rating::default();
+
+    // The probability of a normal variable being within three sigmas of its
+    // mean, erf(3 / sqrt(2)).
+    let rating = Rating::new_with_confidence(25.0, 25.0, 0.997_300_203_936_739_8).unwrap();
+    assert_eq!(rating.mu(), 25.0);
+    assert!((rating.sigma() - default.sigma()).abs() < 1e-9);
+
+    let rounded = Rating::new_with_confidence(25.0, 25.0, 0.997).unwrap();
+    assert!((rounded.sigma() - default.sigma()).abs() < 0.1);
+}
+
+#[test]
+fn sigma_is_the_half_width_over_the_quantile() {
+    for &(confidence, z) in &[
+        (0.5, 0.674_489_750_196_081_7),
+        (0.9, 1.644_853_626_951_472_2),
+        (0.95, 1.959_963_984_540_054),
+        (0.99, 2.575_829_303_548_901),
+        (0.999_999, 4.891_638_475_671_084),
+    ] {
+        let rating = Rating::new_with_confidence(10.0, 2.0 * z, confidence).unwrap();
+        assert!((rating.sigma() - 2.0).abs() < 1e-9, "{}", confidence);
+    }
+}
+
+#[test]
+fn less_confidence_means_more_uncertainty() {
+    let sigma = |confidence| {
+        Rating::new_with_confidence(25.0, 5.0, confidence)
+            .unwrap()
+            .sigma()
+    };
+
+    assert!(sigma(0.5) > 2.5 * sigma(0.95));
+
+    let mut previous = f64::INFINITY;
+    for i in 1..100 {
+        let current = sigma(i as f64 / 100.0);
+        assert!(current < previous);
+        previous = current;
+    }
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let error = |name: &'static str| Err(BBTError::InvalidParameter { name });
+
+    for &confidence in &[0.0, 1.0, -0.5, 1.5, f64::NAN] {
+        assert_eq!(
+            Rating::new_with_confidence(25.0, 5.0, confidence),
+            error("confidence")
+        );
+    }
+
+    for &half_width in &[0.0, -1.0, f64::INFINITY, f64::NAN] {
+        assert_eq!(
+            Rating::new_with_confidence(25.0, half_width, 0.9),
+            error("half_width")
+        );
+    }
+
+    assert_eq!(Rating::new_with_confidence(f64::NAN, 5.0, 0.9), error("mu"));
+}
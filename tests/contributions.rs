@@ -0,0 +1,94 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{BBTError, Rater, Rating};
+
+fn teams() -> Vec<Vec<Rating>> {
+    vec![
+        vec![Rating::new(25.0, 4.0), Rating::new(25.0, 4.0)],
+        vec![
+            Rating::new(28.0, 6.0),
+            Rating::new(22.0, 3.0),
+            Rating::new(24.0, 5.0),
+        ],
+    ]
+}
+
+#[test]
+fn equal_contributions_are_the_flat_update() {
+    let rater = Rater::default();
+    let expected = rater.update_ratings(teams(), vec![1, 2]).unwrap();
+
+    for contributions in &[
+        [&[1.0, 1.0][..], &[0.5, 0.5, 0.5][..]],
+        [&[7.0, 7.0][..], &[3.0, 3.0, 3.0][..]],
+    ] {
+        let new = rater
+            .update_ratings_with_contributions(teams(), vec![1, 2], contributions)
+            .unwrap();
+
+        assert_eq!(new, expected);
+    }
+}
+
+#[test]
+fn the_carry_moves_further_than_the_passenger() {
+    let rater = Rater::default();
+    let flat = rater.update_ratings(teams(), vec![1, 2]).unwrap();
+
+    let contributions: [&[f64]; 2] = [&[2.0, 1.0], &[1.0, 0.0, 3.0]];
+    let new = rater
+        .update_ratings_with_contributions(teams(), vec![1, 2], &contributions)
+        .unwrap();
+
+    let carry = new[0][0].mu() - 25.0;
+    let passenger = new[0][1].mu() - 25.0;
+    assert!(passenger > 0.0);
+    assert!((carry - 2.0 * passenger).abs() < 1e-12);
+
+    // The team as a whole moves as far as in the flat update.
+    for (team, (new, flat)) in teams().iter().zip(new.iter().zip(&flat)) {
+        let change = |ratings: &Vec<Rating>| {
+            ratings
+                .iter()
+                .zip(team)
+                .map(|(new, old)| new.mu() - old.mu())
+                .sum::<f64>()
+        };
+        assert!((change(new) - change(flat)).abs() < 1e-12);
+    }
+
+    // Sigma does not depend on the contributions, and a player who did not
+    // contribute keeps their mu.
+    for (new, flat) in new.iter().flatten().zip(flat.iter().flatten()) {
+        assert_eq!(new.sigma(), flat.sigma());
+    }
+    assert_eq!(new[1][1].mu(), 22.0);
+}
+
+#[test]
+fn invalid_contributions_are_rejected() {
+    let rater = Rater::default();
+    let invalid = Err(BBTError::InvalidParameter {
+        name: "contributions",
+    });
+
+    for contributions in &[
+        vec![&[1.0, 1.0][..]],
+        vec![&[1.0, 1.0][..], &[1.0, 1.0][..]],
+        vec![&[1.0, -1.0][..], &[1.0, 1.0, 1.0][..]],
+        vec![&[1.0, f64::NAN][..], &[1.0, 1.0, 1.0][..]],
+        vec![&[1.0, 1.0][..], &[0.0, 0.0, 0.0][..]],
+    ] {
+        assert_eq!(
+            rater.update_ratings_with_contributions(teams(), vec![1, 2], contributions),
+            invalid
+        );
+    }
+
+    // The match itself is checked first.
+    assert_eq!(
+        rater.update_ratings_with_contributions(teams(), vec![1], &[]),
+        Err(BBTError::MismatchedLengths { teams: 2, ranks: 1 })
+    );
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "csv")]
+extern crate bbt;
+
+use bbt::io::{read_csv, write_csv, CsvError};
+use bbt::Rating;
+
+#[test]
+fn round_trip() {
+    let table = vec![
+        ("alice".to_string(), Rating::new(31.25, 2.5)),
+        ("bob, the builder".to_string(), Rating::new(-4.0, 0.1)),
+        ("\"quoted\"".to_string(), Rating::new(25.0, 25.0 / 3.0)),
+        ("line\nbreak".to_string(), Rating::new(1e-3, 1e10)),
+    ];
+
+    let mut out = Vec::new();
+    write_csv(&mut out, table.iter().map(|(id, rating)| (id, rating))).unwrap();
+
+    let csv = String::from_utf8(out).unwrap();
+    assert!(csv.starts_with("id,mu,sigma,conservative\nalice,31.25,2.5,23.75\n"));
+    assert!(csv.contains("\"bob, the builder\""));
+
+    assert_eq!(read_csv(csv.as_bytes()).unwrap(), table);
+}
+
+#[test]
+fn columns_are_found_by_name() {
+    let csv = "sigma,note,mu,id\n2,\"a, b\",30,alice\n";
+
+    assert_eq!(
+        read_csv(csv.as_bytes()).unwrap(),
+        vec![("alice".to_string(), Rating::new(30.0, 2.0))]
+    );
+}
+
+#[test]
+fn crlf_line_endings() {
+    let csv = "id,mu,sigma,conservative\r\nalice,30,2,24\r\nbob,20,1.5,15.5\r\n";
+
+    assert_eq!(
+        read_csv(csv.as_bytes()).unwrap(),
+        vec![
+            ("alice".to_string(), Rating::new(30.0, 2.0)),
+            ("bob".to_string(), Rating::new(20.0, 1.5)),
+        ]
+    );
+}
+
+#[test]
+fn no_trailing_newline() {
+    let csv = "id,mu,sigma\nalice,30,2";
+
+    assert_eq!(
+        read_csv(csv.as_bytes()).unwrap(),
+        vec![("alice".to_string(), Rating::new(30.0, 2.0))]
+    );
+}
+
+#[test]
+fn empty_table() {
+    assert_eq!(
+        read_csv(&b"id,mu,sigma,conservative\n"[..]).unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn missing_column() {
+    match read_csv(&b"id,mu\nalice,30\n"[..]) {
+        Err(CsvError::MissingColumn { name: "sigma" }) => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn malformed_rows_report_their_line() {
+    let cases: &[(&str, u64)] = &[
+        ("id,mu,sigma\nalice,30,2\nbob,abc,2\n", 3),
+        ("id,mu,sigma\nalice,30,2\r\nbob,20,2\r\ncarol,20,\r\n", 4),
+        ("id,mu,sigma\nalice,30,2\nbob,20\n", 3),
+        ("id,mu,sigma\r\na,1,1\r\nb,1,1\r\nc,1,1\r\nd,x,1\r\n", 5),
+        ("id,mu,sigma\r\na,1,1\r\nb,1,1\r\nc,1\r\n", 4),
+        ("id,mu,sigma\n\"multi\nline\",30,2\nbob,20,0\n", 4),
+        ("id,mu,sigma\nalice,30,-1\n", 2),
+        ("id,mu,sigma\nalice,NaN,2\n", 2),
+        ("id,mu,sigma\nalice,30,inf\n", 2),
+    ];
+
+    for &(csv, line) in cases {
+        let error = read_csv(csv.as_bytes()).unwrap_err();
+        assert_eq!(error.line(), Some(line), "{:?}: {}", csv, error);
+    }
+}
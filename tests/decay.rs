@@ -0,0 +1,128 @@
+extern crate bbt;
+
+use bbt::{decay, BBTError, Rating};
+
+#[test]
+fn mixed_periods() {
+    let mut ratings = vec![
+        Rating::new(30.0, 2.0),
+        Rating::new(25.0, 2.0),
+        Rating::new(20.0, 2.0),
+        Rating::new(15.0, 3.0),
+    ];
+    let before = ratings.clone();
+
+    let modified = decay::apply(&mut ratings, &[0, 1, 4, 16], 0.5, 8.0).unwrap();
+    assert_eq!(modified, 3);
+
+    // Zero periods change nothing, not even the last bit.
+    assert_eq!(ratings[0], before[0]);
+    assert_eq!(ratings[1].sigma(), 4.25f64.sqrt());
+    assert_eq!(ratings[2].sigma(), 5.0f64.sqrt());
+    assert_eq!(ratings[3].sigma(), 13.0f64.sqrt());
+
+    for (decayed, rating) in ratings.iter().zip(&before) {
+        assert_eq!(decayed.mu(), rating.mu());
+    }
+}
+
+#[test]
+fn sigma_is_capped() {
+    let mut ratings = vec![
+        Rating::new(25.0, 7.9),
+        Rating::new(25.0, 8.0),
+        Rating::new(25.0, 9.0),
+        Rating::new(25.0, 1.0),
+    ];
+
+    let modified = decay::apply(&mut ratings, &[100, 100, 100, 1], 1.0, 8.0).unwrap();
+    assert_eq!(modified, 2);
+
+    assert_eq!(ratings[0].sigma(), 8.0);
+    assert_eq!(ratings[1].sigma(), 8.0);
+    // Decay never reduces sigma, even above the cap.
+    assert_eq!(ratings[2].sigma(), 9.0);
+    assert_eq!(ratings[3].sigma(), 2.0f64.sqrt());
+
+    // The capped rating behaves like a fresh one with that sigma.
+    let rater = bbt::Rater::default();
+    let capped = rater.duel(ratings[0], Rating::default(), bbt::Outcome::Win);
+    let fresh = rater.duel(Rating::new(25.0, 8.0), Rating::default(), bbt::Outcome::Win);
+    assert_eq!(capped, fresh);
+}
+
+#[test]
+fn empty_input() {
+    let mut ratings: Vec<Rating> = Vec::new();
+    assert_eq!(decay::apply(&mut ratings, &[], 0.5, 8.0), Ok(0));
+}
+
+#[test]
+fn invalid_parameters() {
+    let mut ratings = vec![Rating::new(25.0, 2.0)];
+
+    assert_eq!(
+        decay::apply(&mut ratings, &[1, 2], 0.5, 8.0),
+        Err(BBTError::InvalidParameter { name: "periods" })
+    );
+    for &tau in &[-0.5, f64::NAN, f64::INFINITY] {
+        assert_eq!(
+            decay::apply(&mut ratings, &[1], tau, 8.0),
+            Err(BBTError::InvalidParameter { name: "tau" })
+        );
+    }
+    for &max_sigma in &[0.0, -1.0, f64::NAN] {
+        assert_eq!(
+            decay::apply(&mut ratings, &[1], 0.5, max_sigma),
+            Err(BBTError::InvalidParameter { name: "max_sigma" })
+        );
+    }
+
+    assert_eq!(ratings[0], Rating::new(25.0, 2.0));
+
+    // An infinite cap is no cap at all.
+    assert_eq!(
+        decay::apply(&mut ratings, &[1_000], 1.0, f64::INFINITY),
+        Ok(1)
+    );
+    assert_eq!(ratings[0].sigma(), 1_004.0f64.sqrt());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn elapsed_periods() {
+    use std::collections::HashMap;
+
+    let day = 86_400;
+    let now = 100 * day;
+
+    let mut ratings = HashMap::new();
+    let mut last_played = HashMap::new();
+    for &(id, days_ago) in &[
+        ("alice", Some(0)),
+        ("bob", Some(1)),
+        ("carol", Some(3)),
+        ("dave", None),
+        ("erin", Some(-2)),
+    ] {
+        ratings.insert(id, Rating::new(25.0, 2.0));
+        if let Some(days_ago) = days_ago {
+            // Half a day more, which does not make a whole period.
+            last_played.insert(id, now - days_ago * day - day / 2);
+        }
+    }
+
+    let modified = decay::apply_elapsed(&mut ratings, &last_played, now, day, 0.5, 8.0).unwrap();
+    assert_eq!(modified, 2);
+
+    assert_eq!(ratings["alice"], Rating::new(25.0, 2.0));
+    assert_eq!(ratings["bob"].sigma(), 4.25f64.sqrt());
+    assert_eq!(ratings["carol"].sigma(), 4.75f64.sqrt());
+    assert_eq!(ratings["dave"], Rating::new(25.0, 2.0));
+    assert_eq!(ratings["erin"], Rating::new(25.0, 2.0));
+
+    assert_eq!(
+        decay::apply_elapsed(&mut ratings, &last_played, now, 0, 0.5, 8.0),
+        Err(BBTError::InvalidParameter { name: "period" })
+    );
+}
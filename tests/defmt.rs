@@ -0,0 +1,56 @@
+#![cfg(feature = "defmt")]
+extern crate bbt;
+extern crate defmt;
+
+use bbt::{BBTError, Fixed, Float, Outcome, Rater, Rating};
+
+// defmt only sends the index of an interned format string, followed by the
+// encoded arguments, so these tests check that the arguments are there.
+
+/// Logs `value` and returns the bytes defmt wrote.
+fn log<T: defmt::Format>(value: &T) -> Vec<u8> {
+    defmt::export::fetch_bytes();
+    defmt::println!("{}", value);
+    defmt::export::fetch_bytes()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn ratings_contain_mu_and_sigma() {
+    let bytes = log(&Rating::new(31.5, 2.25));
+    assert!(contains(&bytes, &31.5f64.to_le_bytes()));
+    assert!(contains(&bytes, &2.25f64.to_le_bytes()));
+
+    let bytes = log(&Rating::new(Fixed::from_f64(31.5), Fixed::from_f64(2.25)));
+    assert!(contains(&bytes, &31.5f64.to_le_bytes()));
+    assert!(contains(&bytes, &2.25f64.to_le_bytes()));
+
+    let bytes = log(&Rating::new(31.5f32, 2.25f32));
+    assert!(contains(&bytes, &31.5f32.to_le_bytes()));
+    assert!(contains(&bytes, &2.25f32.to_le_bytes()));
+}
+
+#[test]
+fn raters_contain_beta() {
+    let bytes = log(&Rater::new(3.5));
+    assert!(contains(&bytes, &3.5f64.to_le_bytes()));
+}
+
+#[test]
+fn outcomes_and_errors_are_distinguishable() {
+    let outcomes = [Outcome::Win, Outcome::Loss, Outcome::Draw];
+    let logged: Vec<_> = outcomes.iter().map(log).collect();
+    assert_ne!(logged[0], logged[1]);
+    assert_ne!(logged[1], logged[2]);
+
+    let bytes = log(&BBTError::EmptyTeam { index: 0x1234_5678 });
+    assert!(contains(&bytes, &0x1234_5678u32.to_le_bytes()));
+
+    let bytes = log(&BBTError::InvalidParameter { name: "beta" });
+    assert!(contains(&bytes, b"beta"));
+}
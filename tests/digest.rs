@@ -0,0 +1,80 @@
+extern crate bbt;
+
+use std::collections::HashMap;
+
+use bbt::digest::{state_digest, DigestBuilder};
+use bbt::Rating;
+
+fn table() -> Vec<(String, Rating)> {
+    vec![
+        ("alice".to_string(), Rating::new(27.5, 4.0)),
+        ("bob".to_string(), Rating::new(22.0, 6.0)),
+        ("carol".to_string(), Rating::default()),
+        ("".to_string(), Rating::new(-3.25, 0.5)),
+    ]
+}
+
+fn digest(table: &[(String, Rating)]) -> u64 {
+    state_digest(table.iter().map(|(id, rating)| (id, rating)))
+}
+
+#[test]
+fn digests_ignore_the_order_of_the_entries() {
+    let table = table();
+    let expected = digest(&table);
+
+    let mut reversed = table.clone();
+    reversed.reverse();
+    assert_eq!(digest(&reversed), expected);
+
+    let mut rotated = table.clone();
+    rotated.rotate_left(1);
+    assert_eq!(digest(&rotated), expected);
+
+    let map: HashMap<String, Rating> = table.iter().cloned().collect();
+    assert_eq!(state_digest(&map), expected);
+
+    let mut builder = DigestBuilder::new();
+    for (id, rating) in &rotated {
+        builder.add(id, rating);
+    }
+    assert_eq!(builder.finish(), expected);
+}
+
+#[test]
+fn every_bit_of_every_mu_counts() {
+    let table = table();
+    let expected = digest(&table);
+
+    for entry in 0..table.len() {
+        for bit in 0..64 {
+            let mut changed = table.clone();
+            let (mu, sigma) = (changed[entry].1.mu(), changed[entry].1.sigma());
+            changed[entry].1 = Rating::new(f64::from_bits(mu.to_bits() ^ (1 << bit)), sigma);
+
+            assert_ne!(digest(&changed), expected, "entry {}, bit {}", entry, bit);
+        }
+    }
+
+    // The ids and sigmas count too, and ids are not confused with each other
+    // when they are moved between entries.
+    let mut renamed = table.clone();
+    renamed[0].0 = "alicf".to_string();
+    assert_ne!(digest(&renamed), expected);
+
+    let mut sigma = table.clone();
+    sigma[1].1 = Rating::new(22.0, 6.000000000000001);
+    assert_ne!(digest(&sigma), expected);
+
+    let mut swapped = table.clone();
+    swapped[0].1 = table[1].1;
+    swapped[1].1 = table[0].1;
+    assert_ne!(digest(&swapped), expected);
+}
+
+#[test]
+fn digest_golden() {
+    // The algorithm must never change.
+    assert_eq!(state_digest(Vec::<(&str, &Rating)>::new()), 0);
+    assert_eq!(digest(&table()), 0x857b_5f55_20bc_390c);
+}
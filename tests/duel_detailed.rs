@@ -0,0 +1,70 @@
+extern crate bbt;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use bbt::{Outcome, Rater, Rating, RatingUpdate};
+
+#[test]
+fn updates_match_duel_and_win_probability() {
+    let rater = Rater::default();
+
+    for &outcome in &[Outcome::Win, Outcome::Loss, Outcome::Draw] {
+        let (before1, before2) = (Rating::new(27.0, 4.0), Rating::new(23.5, 6.5));
+        let (mut p1, mut p2) = (before1, before2);
+
+        let update = rater.duel_detailed(&mut p1, &mut p2, outcome);
+        let (after1, after2) = rater.duel(before1, before2, outcome);
+
+        assert_eq!(update.before, [before1, before2]);
+        assert_eq!(update.after, [after1, after2]);
+        assert_eq!((p1, p2), (after1, after2));
+        assert_eq!(
+            update.mu_delta,
+            [after1.mu() - before1.mu(), after2.mu() - before2.mu()]
+        );
+        assert_eq!(
+            update.win_probability,
+            rater.win_probability(&before1, &before2)
+        );
+    }
+}
+
+#[test]
+fn draws_between_equals_change_nothing_but_sigma() {
+    let rater = Rater::default();
+    let (mut p1, mut p2): (Rating, Rating) = (Rating::default(), Rating::default());
+
+    let update = rater.duel_detailed(&mut p1, &mut p2, Outcome::Draw);
+
+    assert_eq!(update.win_probability, 0.5);
+    assert_eq!(update.mu_delta, [0.0, 0.0]);
+    assert!(p1.sigma() < Rating::<f64>::default().sigma());
+
+    // The update is a plain value that can be kept around.
+    let copy: RatingUpdate = update;
+    assert_eq!(copy, update);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn updates_round_trip_through_json() {
+    let rater = Rater::default();
+    let (mut p1, mut p2) = (Rating::new(30.0, 2.0), Rating::new(25.0, 3.0));
+    let update = rater.duel_detailed(&mut p1, &mut p2, Outcome::Loss);
+
+    let json = serde_json::to_string(&update).unwrap();
+    assert!(json.starts_with(r#"{"before":[{"mu":30.0,"sigma":2.0},{"mu":25.0,"sigma":3.0}]"#));
+
+    // serde_json may round the last bit of a float.
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+    let deserialized: RatingUpdate = serde_json::from_str(&json).unwrap();
+    assert!(close(deserialized.win_probability, update.win_probability));
+    for i in 0..2 {
+        assert!(close(deserialized.mu_delta[i], update.mu_delta[i]));
+        assert!(close(deserialized.after[i].mu(), update.after[i].mu()));
+        assert!(close(
+            deserialized.after[i].sigma(),
+            update.after[i].sigma()
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+#![cfg(all(feature = "serde", feature = "std"))]
+extern crate bbt;
+extern crate rand;
+
+use bbt::eval::{self, Metrics, PrequentialOptions};
+use bbt::replay::{GameRecord, ReplayError};
+use bbt::{Rater, Rating};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+fn duel(a: &str, b: &str, ranks: [usize; 2]) -> GameRecord {
+    GameRecord::new(vec![vec![a.into()], vec![b.into()]], ranks.to_vec())
+}
+
+/// Duels between players with fixed, known skills, whose results are drawn
+/// from the model's own win probabilities.
+fn synthetic_history(games: usize) -> Vec<GameRecord> {
+    let mut rng = SmallRng::seed_from_u64(709);
+    let truth = Rater::new(25.0 / 6.0);
+    let skills: Vec<Rating> = (0..40).map(|i| Rating::new(5.0 + i as f64, 0.0)).collect();
+
+    (0..games)
+        .map(|_| {
+            let a = rng.gen_range(0..skills.len());
+            let b = (a + rng.gen_range(1..skills.len())) % skills.len();
+            let won = rng.gen::<f64>() < truth.win_probability(&skills[a], &skills[b]);
+
+            duel(
+                &a.to_string(),
+                &b.to_string(),
+                if won { [1, 2] } else { [2, 1] },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn predictions_improve_as_the_ratings_converge() {
+    let games = synthetic_history(4000);
+    let report = eval::prequential(&Rater::default(), &games, Rating::default()).unwrap();
+
+    assert_eq!(report.predictions.len(), games.len());
+    assert_eq!(report.metrics.count, games.len());
+    assert!(report.metrics.accuracy > 0.5);
+    assert!(report.metrics.log_loss < 2f64.ln());
+    assert!(report.metrics.brier_score < 0.25);
+
+    let quartile = games.len() / 4;
+    let first = Metrics::from_predictions(&report.predictions[..quartile], 10);
+    let last = Metrics::from_predictions(&report.predictions[3 * quartile..], 10);
+    assert!(last.accuracy > first.accuracy);
+    assert!(last.log_loss < first.log_loss);
+    assert!(last.brier_score < first.brier_score);
+
+    // The ratings are the same as those of a plain replay.
+    let replayed = bbt::replay::replay(&Rater::default(), &games, Rating::default()).unwrap();
+    assert_eq!(report.ratings, replayed);
+}
+
+#[test]
+fn calibration_bins_cover_every_prediction() {
+    let games = synthetic_history(2000);
+    let report = eval::prequential(&Rater::default(), &games, Rating::default()).unwrap();
+    let calibration = &report.metrics.calibration;
+
+    assert_eq!(calibration.len(), 10);
+    assert_eq!(calibration[0].lower, 0.0);
+    assert_eq!(calibration[9].upper, 1.0);
+    assert_eq!(
+        calibration.iter().map(|bin| bin.count).sum::<usize>(),
+        report.predictions.len()
+    );
+
+    for bin in calibration.iter().filter(|bin| bin.count > 0) {
+        assert!(bin.lower <= bin.mean_probability && bin.mean_probability <= bin.upper);
+    }
+
+    // The model the games were drawn from is well calibrated, so the well
+    // populated bins win about as often as they predict.
+    for bin in calibration.iter().filter(|bin| bin.count > 200) {
+        assert!(
+            (bin.win_rate - bin.mean_probability).abs() < 0.1,
+            "{:?}",
+            bin
+        );
+    }
+
+    let options = PrequentialOptions {
+        calibration_bins: 0,
+        ..PrequentialOptions::default()
+    };
+    let report =
+        eval::prequential_with_options(&Rater::default(), &games, Rating::default(), &options)
+            .unwrap();
+    assert!(report.metrics.calibration.is_empty());
+}
+
+#[test]
+fn first_games_can_be_left_out_of_the_metrics() {
+    let games = vec![
+        duel("alice", "bob", [1, 2]),
+        duel("alice", "bob", [1, 2]),
+        duel("alice", "carol", [2, 1]),
+    ];
+
+    let report = eval::prequential(&Rater::default(), &games, Rating::default()).unwrap();
+    let new_players: Vec<bool> = report.predictions.iter().map(|p| p.new_players).collect();
+    assert_eq!(new_players, vec![true, false, true]);
+    assert_eq!(report.metrics.count, 3);
+
+    let options = PrequentialOptions {
+        exclude_new_players: true,
+        ..PrequentialOptions::default()
+    };
+    let excluded =
+        eval::prequential_with_options(&Rater::default(), &games, Rating::default(), &options)
+            .unwrap();
+
+    assert_eq!(excluded.predictions, report.predictions);
+    assert_eq!(
+        excluded.metrics,
+        Metrics::from_predictions(&report.predictions[1..2], 10)
+    );
+    assert_eq!(excluded.metrics.accuracy, 1.0);
+}
+
+#[test]
+fn teams_are_compared_in_pairs() {
+    let game = GameRecord::new(
+        vec![
+            vec!["a".into(), "b".into()],
+            vec!["c".into()],
+            vec!["d".into()],
+        ],
+        vec![2, 1, 2],
+    );
+
+    let report = eval::prequential(&Rater::default(), &[game], Rating::default()).unwrap();
+    let pairs: Vec<_> = report
+        .predictions
+        .iter()
+        .map(|p| (p.teams, p.won))
+        .collect();
+
+    // The first and last team tied, so they are not compared.
+    assert_eq!(pairs, vec![((0, 1), false), ((1, 2), true)]);
+
+    // A pair of players is favoured over a single player.
+    assert!(report.predictions[0].probability > 0.5);
+}
+
+#[test]
+fn certain_mistakes_have_a_finite_loss() {
+    let predictions = [eval::Prediction {
+        game: 0,
+        teams: (0, 1),
+        probability: 1.0,
+        won: false,
+        new_players: false,
+    }];
+
+    let metrics = Metrics::from_predictions(&predictions, 4);
+    assert!(metrics.log_loss.is_finite() && metrics.log_loss > 30.0);
+    assert_eq!(metrics.brier_score, 1.0);
+    assert_eq!(metrics.accuracy, 0.0);
+    assert_eq!(metrics.calibration[3].count, 1);
+    assert!(Metrics::from_predictions(&[], 4).accuracy.is_nan());
+}
+
+#[test]
+fn invalid_games_stop_the_evaluation() {
+    let games = vec![duel("alice", "bob", [1, 2]), duel("alice", "alice", [1, 2])];
+
+    match eval::prequential(&Rater::default(), &games, Rating::default()) {
+        Err(ReplayError::InvalidGame { index: 1, .. }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn the_generating_model_wins_the_comparison() {
+    let games = synthetic_history(4000);
+    let configs = [
+        ("overconfident".to_string(), Rater::new(0.5)),
+        ("bradley_terry".to_string(), Rater::new(25.0 / 6.0)),
+        ("underconfident".to_string(), Rater::new(25.0)),
+    ];
+
+    let report = eval::compare_models(&configs, &games, Rating::default()).unwrap();
+    assert_eq!(report.models.len(), 3);
+    assert_eq!(report.best_by_log_loss().unwrap().name, "bradley_terry");
+
+    // Every configuration is scored like a prequential evaluation.
+    for (model, (name, rater)) in report.models.iter().zip(&configs) {
+        assert_eq!(model.name, *name);
+        let single = eval::prequential(rater, &games, Rating::default()).unwrap();
+        assert_eq!(model.metrics, single.metrics);
+    }
+
+    assert!(eval::compare_models(&[], &games, Rating::default())
+        .unwrap()
+        .best_by_log_loss()
+        .is_none());
+}
+
+#[test]
+fn comparisons_render_as_a_table() {
+    let games = synthetic_history(200);
+    let configs = [
+        ("default".to_string(), Rater::default()),
+        ("a_longer_name".to_string(), Rater::new(2.0)),
+    ];
+
+    let report = eval::compare_models(&configs, &games, Rating::default()).unwrap();
+    let table = report.to_string();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+
+    let header: Vec<&str> = lines[0].split_whitespace().collect();
+    assert_eq!(
+        header,
+        ["model", "log_loss", "brier", "accuracy", "runtime_ms"]
+    );
+
+    for (line, model) in lines[1..].iter().zip(&report.models) {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(columns.len(), 5);
+        assert_eq!(columns[0], model.name);
+
+        let log_loss: f64 = columns[1].parse().unwrap();
+        let brier: f64 = columns[2].parse().unwrap();
+        let accuracy: f64 = columns[3].parse().unwrap();
+        let runtime: f64 = columns[4].parse().unwrap();
+        assert!((log_loss - model.metrics.log_loss).abs() < 1e-6);
+        assert!((brier - model.metrics.brier_score).abs() < 1e-6);
+        assert!((accuracy - model.metrics.accuracy).abs() < 1e-6);
+        assert!(runtime >= 0.0);
+    }
+
+    // The columns are aligned.
+    let offset = lines[0].find("log_loss").unwrap() + "log_loss".len();
+    for line in &lines[1..] {
+        assert!(line.as_bytes()[offset - 1].is_ascii_digit());
+        assert_eq!(line.as_bytes()[offset], b' ');
+    }
+}
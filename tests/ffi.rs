@@ -0,0 +1,231 @@
+#![cfg(feature = "ffi")]
+extern crate bbt;
+
+use std::ptr;
+
+use bbt::ffi::*;
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+#[test]
+fn duel_matches_safe_api() {
+    let rater = Rater::new(3.5);
+    let ffi_rater = bbt_rater_new(3.5);
+    assert!(!ffi_rater.is_null());
+
+    for &(code, outcome) in &[(1, Outcome::Win), (-1, Outcome::Loss), (0, Outcome::Draw)] {
+        let (p1, p2) = (Rating::new(27.0, 4.0), Rating::new(22.5, 6.0));
+        let expected = rater.duel(p1, p2, outcome);
+
+        let (mut mu1, mut sigma1, mut mu2, mut sigma2) = (27.0, 4.0, 22.5, 6.0);
+        let result = unsafe {
+            bbt_duel(
+                ffi_rater,
+                &mut mu1,
+                &mut sigma1,
+                &mut mu2,
+                &mut sigma2,
+                code,
+            )
+        };
+
+        assert_eq!(result, BBT_OK);
+        assert_eq!((mu1, sigma1), (expected.0.mu(), expected.0.sigma()));
+        assert_eq!((mu2, sigma2), (expected.1.mu(), expected.1.sigma()));
+    }
+
+    unsafe { bbt_rater_free(ffi_rater) };
+}
+
+#[test]
+fn update_ratings_matches_safe_api() {
+    let rater = Rater::default();
+    let ffi_rater = bbt_rater_new(25.0 / 6.0);
+
+    let teams = vec![
+        vec![Rating::new(25.0, 8.0), Rating::new(30.0, 3.0)],
+        vec![Rating::new(20.0, 2.0)],
+        vec![
+            Rating::new(28.0, 5.0),
+            Rating::new(22.0, 7.0),
+            Rating::new(24.0, 1.0),
+        ],
+    ];
+    let ranks = vec![2, 1, 2];
+    let expected = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+
+    let mut mus: Vec<f64> = teams.iter().flatten().map(Rating::mu).collect();
+    let mut sigmas: Vec<f64> = teams.iter().flatten().map(Rating::sigma).collect();
+    let offsets = [0, 2, 3];
+
+    let result = unsafe {
+        bbt_update_ratings(
+            ffi_rater,
+            mus.as_mut_ptr(),
+            sigmas.as_mut_ptr(),
+            mus.len(),
+            offsets.as_ptr(),
+            offsets.len(),
+            ranks.as_ptr(),
+        )
+    };
+    assert_eq!(result, BBT_OK);
+
+    let expected: Vec<(f64, f64)> = expected
+        .iter()
+        .flatten()
+        .map(|rating| (rating.mu(), rating.sigma()))
+        .collect();
+    let actual: Vec<(f64, f64)> = mus.into_iter().zip(sigmas).collect();
+    assert_eq!(actual, expected);
+
+    unsafe { bbt_rater_free(ffi_rater) };
+}
+
+/// Calls `bbt_update_ratings` with the default rater and returns its result,
+/// checking that the ratings are left alone on failure.
+fn update(mus: &[f64], sigmas: &[f64], offsets: &[usize], ranks: &[usize]) -> i32 {
+    let rater = bbt_rater_new(25.0 / 6.0);
+    let mut new_mus = mus.to_vec();
+    let mut new_sigmas = sigmas.to_vec();
+
+    let result = unsafe {
+        bbt_update_ratings(
+            rater,
+            new_mus.as_mut_ptr(),
+            new_sigmas.as_mut_ptr(),
+            mus.len(),
+            offsets.as_ptr(),
+            offsets.len(),
+            ranks.as_ptr(),
+        )
+    };
+
+    if result != BBT_OK {
+        assert_eq!(new_mus, mus);
+        assert_eq!(new_sigmas, sigmas);
+    }
+
+    unsafe { bbt_rater_free(rater) };
+    result
+}
+
+#[test]
+fn update_ratings_error_codes() {
+    let mus = [25.0, 25.0, 25.0];
+    let sigmas = [8.0, 8.0, 8.0];
+
+    assert_eq!(
+        update(&mus, &sigmas, &[0, 1, 1], &[1, 2, 3]),
+        BBT_EMPTY_TEAM
+    );
+    assert_eq!(update(&mus, &sigmas, &[0], &[1]), BBT_NOT_ENOUGH_TEAMS);
+    assert_eq!(
+        update(&mus, &[8.0, -1.0, 8.0], &[0, 1, 2], &[1, 2, 3]),
+        BBT_INVALID_RATING
+    );
+    assert_eq!(
+        update(&mus, &sigmas, &[1, 2], &[1, 2]),
+        BBT_INVALID_ARGUMENT
+    );
+    assert_eq!(
+        update(&mus, &sigmas, &[0, 2, 1], &[1, 2, 3]),
+        BBT_INVALID_ARGUMENT
+    );
+    assert_eq!(
+        update(&mus, &sigmas, &[0, 4], &[1, 2]),
+        BBT_INVALID_ARGUMENT
+    );
+}
+
+#[test]
+fn null_pointers_and_bad_arguments() {
+    let rater = bbt_rater_new(25.0 / 6.0);
+    let (mut mu1, mut sigma1, mut mu2, mut sigma2) = (25.0, 8.0, 25.0, 8.0);
+
+    unsafe {
+        assert_eq!(
+            bbt_duel(ptr::null(), &mut mu1, &mut sigma1, &mut mu2, &mut sigma2, 1),
+            BBT_NULL_POINTER
+        );
+        assert_eq!(
+            bbt_duel(rater, &mut mu1, ptr::null_mut(), &mut mu2, &mut sigma2, 1),
+            BBT_NULL_POINTER
+        );
+        assert_eq!(
+            bbt_duel(rater, &mut mu1, &mut sigma1, &mut mu2, &mut sigma2, 2),
+            BBT_INVALID_ARGUMENT
+        );
+
+        sigma1 = f64::NAN;
+        assert_eq!(
+            bbt_duel(rater, &mut mu1, &mut sigma1, &mut mu2, &mut sigma2, 1),
+            BBT_INVALID_RATING
+        );
+        assert_eq!((mu1, mu2, sigma2), (25.0, 25.0, 8.0));
+
+        let mut mus = [25.0, 25.0];
+        let mut sigmas = [8.0, 8.0];
+        let offsets = [0, 1];
+        let ranks = [1, 2];
+        assert_eq!(
+            bbt_update_ratings(
+                rater,
+                mus.as_mut_ptr(),
+                sigmas.as_mut_ptr(),
+                2,
+                ptr::null(),
+                2,
+                ranks.as_ptr()
+            ),
+            BBT_NULL_POINTER
+        );
+        assert_eq!(
+            bbt_update_ratings(
+                ptr::null(),
+                mus.as_mut_ptr(),
+                sigmas.as_mut_ptr(),
+                2,
+                offsets.as_ptr(),
+                2,
+                ranks.as_ptr()
+            ),
+            BBT_NULL_POINTER
+        );
+
+        bbt_rater_free(rater);
+        bbt_rater_free(ptr::null_mut());
+    }
+
+    assert!(bbt_rater_new(f64::NAN).is_null());
+    assert!(bbt_rater_new(-1.0).is_null());
+}
+
+#[test]
+fn error_codes_match_variants() {
+    let errors = [
+        (
+            BBTError::MismatchedLengths { teams: 1, ranks: 2 },
+            BBT_MISMATCHED_LENGTHS,
+        ),
+        (BBTError::EmptyTeam { index: 0 }, BBT_EMPTY_TEAM),
+        (BBTError::NotEnoughTeams { found: 1 }, BBT_NOT_ENOUGH_TEAMS),
+        (
+            BBTError::InvalidRating { team: 0, player: 0 },
+            BBT_INVALID_RATING,
+        ),
+        (BBTError::TeamOverflow { index: 0 }, BBT_TEAM_OVERFLOW),
+        (BBTError::InvalidRanks, BBT_INVALID_RANKS),
+        (
+            BBTError::NumericalError { team: 0, player: 0 },
+            BBT_NUMERICAL_ERROR,
+        ),
+        (
+            BBTError::InvalidParameter { name: "beta" },
+            BBT_INVALID_PARAMETER,
+        ),
+    ];
+
+    for (error, code) in &errors {
+        assert_eq!(error_code(error), *code);
+    }
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{BBTError, Rater, Rating};
+
+#[test]
+fn identical_fields_give_the_pairwise_probability() {
+    let rater = Rater::default();
+    let player = Rating::new(30.0, 2.0);
+    let opponent = Rating::new(26.0, 3.5);
+    let field = vec![opponent; 7];
+
+    let win_rate = rater.expected_winrate_vs_field(&player, &field).unwrap();
+    assert!((win_rate - rater.win_probability(&player, &opponent)).abs() < 1e-12);
+
+    let weighted = rater
+        .expected_winrate_vs_field_weighted(&player, &field, &[1.0, 2.0, 0.0, 5.0, 1.0, 1.0, 3.0])
+        .unwrap();
+    assert!((weighted - win_rate).abs() < 1e-12);
+}
+
+#[test]
+fn symmetric_fields_give_even_odds() {
+    let rater = Rater::default();
+    let player = Rating::new(30.0, 2.0);
+    let field: Vec<Rating> = (1..=10)
+        .flat_map(|offset| {
+            let offset = f64::from(offset);
+            vec![
+                Rating::new(30.0 - offset, 1.0 + offset / 4.0),
+                Rating::new(30.0 + offset, 1.0 + offset / 4.0),
+            ]
+        })
+        .collect();
+
+    let win_rate = rater.expected_winrate_vs_field(&player, &field).unwrap();
+    assert!((win_rate - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn weights_shift_the_win_rate() {
+    let rater = Rater::default();
+    let player = Rating::new(30.0, 2.0);
+    let field = [Rating::new(20.0, 2.0), Rating::new(40.0, 2.0)];
+
+    let even = rater.expected_winrate_vs_field(&player, &field).unwrap();
+    let weak = rater
+        .expected_winrate_vs_field_weighted(&player, &field, &[3.0, 1.0])
+        .unwrap();
+    let strong = rater
+        .expected_winrate_vs_field_weighted(&player, &field, &[1.0, 3.0])
+        .unwrap();
+    assert!(weak > even);
+    assert!(strong < even);
+
+    // Weights are relative.
+    let scaled = rater
+        .expected_winrate_vs_field_weighted(&player, &field, &[30.0, 10.0])
+        .unwrap();
+    assert!((scaled - weak).abs() < 1e-12);
+
+    let only_weak = rater
+        .expected_winrate_vs_field_weighted(&player, &field, &[1.0, 0.0])
+        .unwrap();
+    assert!((only_weak - rater.win_probability(&player, &field[0])).abs() < 1e-12);
+}
+
+#[test]
+fn curves_rise_with_mu() {
+    let rater = Rater::default();
+    let field: Vec<Rating> = (0..20).map(|i| Rating::new(15.0 + i as f64, 2.0)).collect();
+    let mu_points: Vec<f64> = (0..=50).map(f64::from).collect();
+
+    let curve = rater.winrate_curve(&field, &mu_points, 2.0).unwrap();
+    assert_eq!(curve.len(), mu_points.len());
+    assert!(curve.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(curve[0] < 0.1 && curve[50] > 0.9);
+
+    for (&mu, &win_rate) in mu_points.iter().zip(&curve) {
+        let expected = rater
+            .expected_winrate_vs_field(&Rating::new(mu, 2.0), &field)
+            .unwrap();
+        assert_eq!(win_rate, expected);
+    }
+
+    assert_eq!(rater.winrate_curve(&field, &[], 2.0), Ok(vec![]));
+}
+
+fn invalid<T>(name: &'static str) -> Result<T, BBTError> {
+    Err(BBTError::InvalidParameter { name })
+}
+
+#[test]
+fn invalid_fields_are_rejected() {
+    let rater = Rater::default();
+    let player = Rating::default();
+    let field = [Rating::default(), Rating::new(30.0, 2.0)];
+
+    assert_eq!(
+        rater.expected_winrate_vs_field(&player, &[]),
+        invalid("field")
+    );
+    assert_eq!(
+        rater.expected_winrate_vs_field_weighted(&player, &[], &[]),
+        invalid("field")
+    );
+    assert_eq!(rater.winrate_curve(&[], &[25.0], 1.0), invalid("field"));
+
+    for weights in &[&[1.0][..], &[1.0, -1.0], &[0.0, 0.0], &[1.0, f64::INFINITY]] {
+        assert_eq!(
+            rater.expected_winrate_vs_field_weighted(&player, &field, weights),
+            invalid("weights")
+        );
+    }
+
+    assert_eq!(rater.winrate_curve(&field, &[25.0], -1.0), invalid("sigma"));
+    assert_eq!(
+        rater.winrate_curve(&field, &[f64::NAN], 1.0),
+        invalid("mu_points")
+    );
+}
@@ -0,0 +1,69 @@
+// Generates openskill.json, the fixture for tests/openskill.rs:
+//
+//     node tests/fixtures/openskill/generate.js
+//
+// The games are rated with openskill.js's Bradley-Terry full-pair model, so
+// the `openskill` package must be installed (`npm install openskill`). The
+// fixture records the version that was used. openskill.js is called with
+// tau = 0, since bbt does not add dynamics.
+
+const fs = require('fs')
+const path = require('path')
+
+let openskill
+try {
+  openskill = require('openskill')
+} catch (e) {
+  console.error('openskill.js is not installed; run `npm install openskill` first')
+  process.exit(1)
+}
+
+const version = require('openskill/package.json').version
+const rate = (teams, { rank }) =>
+  openskill.rate(teams, { rank, model: openskill.bradleyTerryFull, tau: 0 })
+
+// A small deterministic generator, so that the fixture is reproducible.
+let state = 0x2545f491
+const random = () => {
+  state ^= state << 13
+  state ^= state >>> 17
+  state ^= state << 5
+  return (state >>> 0) / 4294967296
+}
+
+const player = () => ({ mu: 10 + 30 * random(), sigma: 0.5 + 8 * random() })
+
+const games = [
+  { teams: [[{ mu: 25, sigma: 25 / 3 }], [{ mu: 25, sigma: 25 / 3 }]], rank: [1, 2] },
+  { teams: [[{ mu: 25, sigma: 25 / 3 }], [{ mu: 25, sigma: 25 / 3 }]], rank: [1, 1] },
+  {
+    teams: [
+      [{ mu: 25, sigma: 25 / 3 }, { mu: 25, sigma: 25 / 3 }],
+      [{ mu: 25, sigma: 25 / 3 }, { mu: 25, sigma: 25 / 3 }],
+      [{ mu: 25, sigma: 25 / 3 }, { mu: 25, sigma: 25 / 3 }],
+      [{ mu: 25, sigma: 25 / 3 }, { mu: 25, sigma: 25 / 3 }],
+    ],
+    rank: [1, 2, 2, 4],
+  },
+]
+
+for (let i = 0; i < 40; i++) {
+  const nTeams = 2 + Math.floor(random() * 5)
+  const teams = []
+  const rank = []
+  for (let t = 0; t < nTeams; t++) {
+    const size = 1 + Math.floor(random() * 3)
+    teams.push(Array.from({ length: size }, player))
+    rank.push(1 + Math.floor(random() * nTeams))
+  }
+  games.push({ teams, rank })
+}
+
+const fixture = {
+  source: 'openskill.js',
+  version,
+  games: games.map(({ teams, rank }) => ({ teams, rank, expected: rate(teams, { rank }) })),
+}
+
+fs.writeFileSync(path.join(__dirname, 'openskill.json'), JSON.stringify(fixture, null, 1) + '\n')
+console.log(`Wrote ${games.length} games rated with openskill.js ${version}`)
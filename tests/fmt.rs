@@ -0,0 +1,66 @@
+extern crate bbt;
+
+use bbt::fmt::format_delta;
+use bbt::Rating;
+
+#[test]
+fn gains_have_explicit_plus_signs() {
+    let before: Rating = Rating::new(25.0, 8.0);
+    let delta = format_delta(&before, &Rating::new(26.3, 7.2));
+
+    assert_eq!(
+        delta.to_string(),
+        "mu +1.3 (now 26.3)\nsigma -0.8 (now 7.2)\nestimate +3.7 (now 4.7)"
+    );
+    assert_eq!(format!("{:#}", delta), "mu +1.3 (now 26.3), sigma -0.8");
+
+    assert!((delta.mu_delta() - 1.3).abs() < 1e-12);
+    assert!((delta.sigma_delta() + 0.8).abs() < 1e-12);
+    assert!((delta.estimate_delta() - 3.7).abs() < 1e-12);
+    assert_eq!(delta.before(), &Rating::new(25.0, 8.0));
+    assert_eq!(delta.after(), &Rating::new(26.3, 7.2));
+}
+
+#[test]
+fn losses_have_minus_signs() {
+    let delta = format_delta(&Rating::new(25.0, 3.0), &Rating::new(22.125, 2.875));
+
+    assert_eq!(
+        delta.to_string(),
+        "mu -2.9 (now 22.1)\nsigma -0.1 (now 2.9)\nestimate -2.5 (now 13.5)"
+    );
+    assert_eq!(
+        format!("{:#}", delta.with_precision(3)),
+        "mu -2.875 (now 22.125), sigma -0.125"
+    );
+    assert_eq!(
+        format!("{:#}", delta.with_precision(0)),
+        "mu -3 (now 22), sigma 0"
+    );
+
+    // A negative rating keeps its sign.
+    let delta = format_delta(&Rating::new(-1.0, 1.0), &Rating::new(-2.0, 1.0));
+    assert_eq!(format!("{:#}", delta), "mu -1.0 (now -2.0), sigma 0.0");
+}
+
+#[test]
+fn no_change_is_an_unsigned_zero() {
+    let rating = Rating::new(25.0, 8.0);
+    assert_eq!(
+        format_delta(&rating, &rating).to_string(),
+        "mu 0.0 (now 25.0)\nsigma 0.0 (now 8.0)\nestimate 0.0 (now 1.0)"
+    );
+
+    // Negative zeros, and changes that round to zero, are not signed either.
+    let zero: Rating = Rating::new(0.0, 1.0);
+    let delta = format_delta(&zero, &Rating::new(-0.0, 1.0));
+    assert_eq!(delta.mu_delta().to_bits(), (-0.0f64).to_bits());
+    assert_eq!(format!("{:#}", delta), "mu 0.0 (now 0.0), sigma 0.0");
+
+    let delta = format_delta(&Rating::new(0.0, 1.0), &Rating::new(-0.04, 0.96));
+    assert_eq!(format!("{:#}", delta), "mu 0.0 (now 0.0), sigma 0.0");
+    assert_eq!(
+        format!("{:#}", delta.with_precision(2)),
+        "mu -0.04 (now -0.04), sigma -0.04"
+    );
+}
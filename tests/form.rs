@@ -0,0 +1,115 @@
+extern crate bbt;
+#[cfg(feature = "serde")]
+extern crate bincode;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use bbt::form::{self, FormRating};
+use bbt::{Outcome, Rater, Rating};
+
+#[test]
+fn form_runs_ahead_of_the_main_rating_on_a_streak() {
+    let rater = Rater::default();
+    let mut main = Rating::new(25.0, 1.5);
+    let mut form = FormRating::new(&main);
+    let opponent = Rating::new(25.0, 1.5);
+
+    assert_eq!(form.delta_vs_main(&main), 0.0);
+
+    let mut previous = 0.0;
+    for game in 0..10 {
+        form::update_pair(&rater, &mut main, &mut form, &opponent, Outcome::Win);
+
+        let delta = form.delta_vs_main(&main);
+        assert!(delta > 0.0, "game {}: delta {}", game, delta);
+        if game < 3 {
+            assert!(delta > previous);
+        }
+        previous = delta;
+    }
+
+    assert!(previous > 1.0, "delta {}", previous);
+    assert!(form.sigma() >= rater.beta());
+
+    // A losing streak swings the form below the main rating.
+    for _ in 0..10 {
+        form::update_pair(&rater, &mut main, &mut form, &opponent, Outcome::Loss);
+    }
+    assert!(form.delta_vs_main(&main) < -1.0);
+}
+
+#[test]
+fn form_returns_to_the_main_rating_when_decayed() {
+    let main = Rating::new(25.0, 1.0);
+    let mut form = FormRating::with_decay(Rating::new(31.0, 4.0), 0.5);
+
+    form.decay(&main);
+    assert_eq!(form.delta_vs_main(&main), 3.0);
+    assert_eq!(form.sigma(), 4.0);
+
+    for _ in 0..60 {
+        form.decay(&main);
+    }
+    assert!(form.delta_vs_main(&main).abs() < 1e-12);
+
+    // Without decay the form stays where it is.
+    let mut frozen = FormRating::with_decay(Rating::new(31.0, 4.0), 0.0);
+    frozen.decay(&main);
+    assert_eq!(frozen.mu(), 31.0);
+}
+
+#[test]
+fn decay_is_clamped() {
+    let rating = Rating::new(30.0, 2.0);
+
+    assert_eq!(FormRating::with_decay(rating, 1.5).decay_rate(), 1.0);
+    assert_eq!(FormRating::with_decay(rating, -0.5).decay_rate(), 0.0);
+    assert_eq!(
+        FormRating::with_decay(rating, f64::NAN).decay_rate(),
+        form::DEFAULT_DECAY
+    );
+    assert_eq!(FormRating::new(&rating).rating(), rating);
+}
+
+#[test]
+fn main_trajectory_is_unchanged_by_form_tracking() {
+    let rater = Rater::default();
+    let opponents = [
+        (Rating::new(28.0, 3.0), Outcome::Win),
+        (Rating::new(22.0, 5.0), Outcome::Loss),
+        (Rating::new(25.0, 1.0), Outcome::Draw),
+        (Rating::new(35.0, 2.0), Outcome::Win),
+        (Rating::new(18.0, 6.0), Outcome::Loss),
+    ];
+
+    let mut main = Rating::default();
+    let mut form = FormRating::new(&main);
+    let mut plain = Rating::default();
+
+    for &(opponent, outcome) in &opponents {
+        form::update_pair(&rater, &mut main, &mut form, &opponent, outcome);
+        plain = rater.duel(plain, opponent, outcome).0;
+
+        assert_eq!(main, plain);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn forms_are_serialized_with_their_decay() {
+    let form = FormRating::with_decay(Rating::new(27.5, 4.0), 0.25);
+
+    let json = serde_json::to_string(&form).unwrap();
+    assert_eq!(json, r#"{"mu":27.5,"sigma":4.0,"decay":0.25}"#);
+    assert_eq!(serde_json::from_str::<FormRating>(&json).unwrap(), form);
+
+    let binary = bincode::serialize(&form).unwrap();
+    assert_eq!(binary.len(), 24);
+    assert_eq!(bincode::deserialize::<FormRating>(&binary).unwrap(), form);
+
+    let legacy = serde_json::to_string(&Rating::new(27.5, 4.0)).unwrap();
+    let read: FormRating = serde_json::from_str(&legacy).unwrap();
+    assert_eq!(read.decay_rate(), form::DEFAULT_DECAY);
+
+    assert!(serde_json::from_str::<FormRating>(r#"{"mu":27.5,"sigma":-1.0}"#).is_err());
+}
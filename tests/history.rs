@@ -0,0 +1,143 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use bbt::{Rating, RatingHistory};
+
+fn rise_and_fall() -> Vec<(i64, Rating)> {
+    vec![
+        (10, Rating::new(25.0, 8.0)),
+        (20, Rating::new(29.0, 6.0)),
+        (30, Rating::new(33.0, 7.0)),
+        (40, Rating::new(31.0, 3.0)),
+        (50, Rating::new(27.0, 2.5)),
+        (60, Rating::new(26.0, 2.0)),
+    ]
+}
+
+#[test]
+fn peaks_survive_a_fall() {
+    let mut history = RatingHistory::new();
+    assert!(history.is_empty());
+    assert_eq!(history.current(), None);
+    assert_eq!(history.peak_mu(), None);
+
+    for (time, rating) in rise_and_fall() {
+        history.push(time, rating);
+    }
+
+    assert_eq!(history.len(), 6);
+    assert_eq!(history.capacity(), None);
+    assert_eq!(history.current(), Some(&(60, Rating::new(26.0, 2.0))));
+    assert_eq!(history.peak_mu(), Some(&(30, Rating::new(33.0, 7.0))));
+    // 31 - 3 * 3 = 22 beats 27 - 3 * 2.5 = 19.5 and 33 - 3 * 7 = 12.
+    assert_eq!(
+        history.peak_conservative(),
+        Some(&(40, Rating::new(31.0, 3.0)))
+    );
+}
+
+#[test]
+fn ties_keep_the_earlier_peak() {
+    let mut history = RatingHistory::new();
+    history.push(1, Rating::new(30.0, 5.0));
+    history.push(2, Rating::new(30.0, 5.0));
+
+    assert_eq!(history.peak_mu().map(|&(time, _)| time), Some(1));
+    assert_eq!(history.peak_conservative().map(|&(time, _)| time), Some(1));
+}
+
+#[test]
+fn bounded_histories_evict_the_oldest_snapshots() {
+    let mut history = RatingHistory::bounded(3);
+    for (time, rating) in rise_and_fall() {
+        history.push(time, rating);
+    }
+
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.capacity(), Some(3));
+    let times: Vec<_> = history.iter().map(|&(time, _)| time).collect();
+    assert_eq!(times, [40, 50, 60]);
+    assert_eq!(history.at(0).map(|&(time, _)| time), Some(40));
+    assert_eq!(history.at(3), None);
+
+    // The peak in mu was evicted, but is still remembered.
+    assert_eq!(history.peak_mu(), Some(&(30, Rating::new(33.0, 7.0))));
+    assert!(history.iter().all(|&(time, _)| time != 30));
+}
+
+#[test]
+#[should_panic]
+fn bounded_histories_keep_at_least_one_snapshot() {
+    RatingHistory::bounded(0);
+}
+
+#[test]
+fn deltas() {
+    let mut history = RatingHistory::new();
+    assert_eq!(history.delta_over_last(0), None);
+
+    for (time, rating) in rise_and_fall() {
+        history.push(time, rating);
+    }
+
+    assert_eq!(history.delta_over_last(0), Some(0.0));
+    assert_eq!(history.delta_over_last(1), Some(-1.0));
+    assert_eq!(history.delta_over_last(3), Some(-7.0));
+    assert_eq!(history.delta_over_last(5), Some(1.0));
+    assert_eq!(history.delta_over_last(6), None);
+
+    let mut count = 0;
+    for &(time, _) in &history {
+        assert_eq!(time, 10 * (count + 1));
+        count += 1;
+    }
+    assert_eq!(count, 6);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialization_round_trip() {
+    let mut history = RatingHistory::bounded(2);
+    for (time, rating) in rise_and_fall() {
+        history.push(time, rating);
+    }
+
+    let json = serde_json::to_string(&history).unwrap();
+    let deserialized: RatingHistory = serde_json::from_str(&json).unwrap();
+
+    // Ratings are serialized as mu and sigma, so compare those.
+    let snapshots = |history: &RatingHistory| {
+        history
+            .iter()
+            .chain(history.peak_mu())
+            .chain(history.peak_conservative())
+            .map(|&(time, ref rating)| (time, rating.mu(), rating.sigma()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(snapshots(&deserialized), snapshots(&history));
+    assert_eq!(deserialized.capacity(), Some(2));
+
+    let empty = serde_json::to_string(&RatingHistory::new()).unwrap();
+    assert_eq!(
+        empty,
+        r#"{"capacity":null,"snapshots":[],"peak_mu":null,"peak_conservative":null}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<RatingHistory>(&empty).unwrap(),
+        RatingHistory::new()
+    );
+
+    for invalid in [
+        r#"{"capacity":0,"snapshots":[]}"#,
+        r#"{"capacity":1,"snapshots":[[1,{"mu":25.0,"sigma":8.0}],[2,{"mu":25.0,"sigma":8.0}]],"peak_mu":[1,{"mu":25.0,"sigma":8.0}],"peak_conservative":[1,{"mu":25.0,"sigma":8.0}]}"#,
+        r#"{"capacity":null,"snapshots":[[1,{"mu":25.0,"sigma":8.0}]]}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<RatingHistory>(invalid).is_err(),
+            "{}",
+            invalid
+        );
+    }
+}
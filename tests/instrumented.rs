@@ -0,0 +1,87 @@
+#![cfg(feature = "stats")]
+extern crate bbt;
+
+use std::thread;
+
+use bbt::{BBTError, InstrumentedRater, Outcome, Rater, RaterStats, Rating};
+
+#[test]
+fn counters_follow_a_mixed_workload() {
+    let rater = InstrumentedRater::new(Rater::new(0.01));
+    assert_eq!(rater.stats(), RaterStats::default());
+
+    // A normal match of four teams with six players.
+    let teams = vec![
+        vec![Rating::default(), Rating::default()],
+        vec![Rating::default()],
+        vec![Rating::default(), Rating::default()],
+        vec![Rating::default()],
+    ];
+    let updated = rater.update_ratings(teams.clone(), vec![1, 2, 3, 4]);
+    assert_eq!(
+        updated,
+        rater.rater().update_ratings(teams, vec![1, 2, 3, 4])
+    );
+
+    // A very uncertain player facing many near-certain opponents, whose
+    // variance reduction is clamped.
+    let mut teams = vec![vec![Rating::new(25.0, 100.0)]];
+    teams.extend(vec![vec![Rating::new(25.0, 0.01)]; 5]);
+    rater.update_ratings(teams, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+    // Two duels, one of them with an invalid rating.
+    let (winner, loser) = rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+    assert!(winner.mu() > loser.mu());
+    let invalid = Rating::new(f64::NAN, 1.0);
+    assert_eq!(
+        rater.duel(invalid, Rating::default(), Outcome::Draw).1,
+        Rating::default()
+    );
+
+    // A match with a single team.
+    assert_eq!(
+        rater.update_ratings(vec![vec![Rating::default()]], vec![1]),
+        Err(BBTError::NotEnoughTeams { found: 1 })
+    );
+
+    assert_eq!(
+        rater.stats(),
+        RaterStats {
+            updates: 3,
+            players: 6 + 6 + 2,
+            clamps: 1,
+            errors: 2,
+        }
+    );
+
+    rater.reset_stats();
+    assert_eq!(rater.stats(), RaterStats::default());
+}
+
+#[test]
+fn counters_can_be_shared_between_threads() {
+    let rater = InstrumentedRater::default();
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..250 {
+                    rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+                    rater.update_ratings(vec![], vec![]).unwrap_err();
+                    // Reading the counters during updates is fine, too.
+                    assert!(rater.stats().updates <= 1000);
+                }
+            });
+        }
+    });
+
+    assert_eq!(
+        rater.stats(),
+        RaterStats {
+            updates: 1000,
+            players: 2000,
+            clamps: 0,
+            errors: 1000,
+        }
+    );
+}
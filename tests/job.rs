@@ -0,0 +1,174 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{BBTError, JobError, JobStatus, Rater, Rating, UpdateJob};
+
+/// A match of `n_teams` teams of one to three players with varied ratings
+/// and some tied ranks.
+fn large_match(n_teams: usize) -> (Vec<Vec<Rating>>, Vec<usize>) {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+
+    let teams = (0..n_teams)
+        .map(|_| {
+            let size = 1 + (next() * 3.0) as usize;
+            (0..size)
+                .map(|_| Rating::new(10.0 + 30.0 * next(), 0.5 + 8.0 * next()))
+                .collect()
+        })
+        .collect();
+    let ranks = (0..n_teams).map(|i| 1 + i / 3).collect();
+
+    (teams, ranks)
+}
+
+#[cfg(not(feature = "simd"))]
+fn run(rater: &Rater, teams: &[Vec<Rating>], ranks: &[usize], chunk: usize) -> Vec<Vec<Rating>> {
+    let mut job = UpdateJob::new(rater, teams, ranks).unwrap();
+
+    let mut steps = 0;
+    while let JobStatus::Running { processed, total } = job.step(chunk) {
+        assert!(processed <= total);
+        steps += 1;
+    }
+    assert_eq!(job.status(), JobStatus::Complete);
+
+    // Every call but the last processes a full chunk.
+    let n = teams.len();
+    assert_eq!(steps + 1, (n * (n - 1)).div_ceil(chunk), "{}", chunk);
+
+    let mut updated = teams.to_vec();
+    job.finish(&mut updated).unwrap();
+    updated
+}
+
+// With the `simd` feature, `update_ratings` computes the pairwise terms with
+// a vectorized kernel that jobs do not use.
+#[cfg(not(feature = "simd"))]
+#[test]
+fn any_chunk_size_gives_the_one_shot_results() {
+    let rater = Rater::default();
+    let (teams, ranks) = large_match(40);
+    let expected = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+
+    for &chunk in &[1, 2, 3, 38, 39, 40, 41, 777, usize::MAX] {
+        let updated = run(&rater, &teams, &ranks, chunk);
+
+        for (team, expected) in updated.iter().zip(&expected) {
+            for (player, expected) in team.iter().zip(expected) {
+                assert_eq!(player.mu().to_bits(), expected.mu().to_bits(), "{}", chunk);
+                assert_eq!(player.sigma().to_bits(), expected.sigma().to_bits());
+            }
+        }
+    }
+
+    // Equal variances take another path in `update_ratings`.
+    let fresh = vec![vec![Rating::default()]; 25];
+    let ranks: Vec<usize> = (0..25).rev().collect();
+    let expected = rater.update_ratings(fresh.clone(), ranks.clone()).unwrap();
+    for &chunk in &[1, 5, 1000] {
+        assert_eq!(run(&rater, &fresh, &ranks, chunk), expected);
+    }
+
+    // As do the surprised teams of a rater with volatility.
+    let volatile = Rater::default().with_volatility(1.0, 2.0);
+    let (teams, ranks) = large_match(12);
+    let reversed: Vec<usize> = ranks.iter().rev().cloned().collect();
+    let expected = volatile
+        .update_ratings(teams.clone(), reversed.clone())
+        .unwrap();
+    assert_eq!(run(&volatile, &teams, &reversed, 7), expected);
+}
+
+#[test]
+fn progress_is_reported() {
+    let rater = Rater::default();
+    let (teams, ranks) = large_match(5);
+    let mut job = UpdateJob::new(&rater, &teams, &ranks).unwrap();
+
+    assert_eq!(
+        job.status(),
+        JobStatus::Running {
+            processed: 0,
+            total: 20
+        }
+    );
+    assert_eq!(
+        job.step(6),
+        JobStatus::Running {
+            processed: 6,
+            total: 20
+        }
+    );
+    assert_eq!(
+        job.step(0),
+        JobStatus::Running {
+            processed: 6,
+            total: 20
+        }
+    );
+    assert_eq!(job.step(14), JobStatus::Complete);
+    assert_eq!(job.step(14), JobStatus::Complete);
+}
+
+#[test]
+fn cancelled_jobs_change_nothing() {
+    let rater = Rater::default();
+    let (teams, ranks) = large_match(30);
+    let before = teams.clone();
+
+    let mut job = UpdateJob::new(&rater, &teams, &ranks).unwrap();
+    job.step(100);
+    job.cancel();
+
+    assert_eq!(teams, before);
+}
+
+#[test]
+fn unfinished_jobs_cannot_be_finished() {
+    let rater = Rater::default();
+    let (mut teams, ranks) = large_match(10);
+    let before = teams.clone();
+
+    let mut job = UpdateJob::new(&rater, &teams, &ranks).unwrap();
+    job.step(89);
+
+    let error = job.finish(&mut teams).unwrap_err();
+    assert_eq!(
+        error,
+        JobError::Incomplete {
+            processed: 89,
+            total: 90
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "The job is not complete (89 of 90 pairs processed)"
+    );
+    assert_eq!(teams, before);
+
+    let mut job = UpdateJob::new(&rater, &teams, &ranks).unwrap();
+    job.step(90);
+    teams[3].push(Rating::default());
+    assert_eq!(job.finish(&mut teams), Err(JobError::TeamsChanged));
+}
+
+#[test]
+fn malformed_matches_are_rejected_up_front() {
+    let rater = Rater::default();
+    let teams = vec![vec![Rating::default()], vec![]];
+
+    assert_eq!(
+        UpdateJob::new(&rater, &teams, &[1, 2]).err(),
+        Some(BBTError::EmptyTeam { index: 1 })
+    );
+    assert_eq!(
+        UpdateJob::new(&rater, &teams[..1], &[1, 2]).err(),
+        Some(BBTError::MismatchedLengths { teams: 1, ranks: 2 })
+    );
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "std")]
+extern crate bbt;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use bbt::{BBTError, Leaderboard, LeaderboardError, Outcome, Rater, Rating};
+
+#[test]
+fn unknown_players_are_created() {
+    let initial = Rating::new(1500.0, 350.0);
+    let mut leaderboard = Leaderboard::new(Rater::new(200.0), initial);
+    assert!(leaderboard.is_empty());
+
+    leaderboard
+        .record_duel(&"alice", &"bob", Outcome::Win)
+        .unwrap();
+
+    let (alice, bob) = Rater::new(200.0).duel(initial, initial, Outcome::Win);
+    assert_eq!(leaderboard.len(), 2);
+    assert_eq!(leaderboard.rating(&"alice"), Some(&alice));
+    assert_eq!(leaderboard.rating(&"bob"), Some(&bob));
+    assert_eq!(leaderboard.rating(&"carol"), None);
+}
+
+#[test]
+fn strict_leaderboards_reject_unknown_players() {
+    let mut leaderboard = Leaderboard::strict(Rater::default());
+    assert_eq!(leaderboard.initial_rating(), None);
+    leaderboard.insert("alice", Rating::default());
+    leaderboard.insert("bob", Rating::new(30.0, 4.0));
+
+    assert_eq!(
+        leaderboard.record_match(&[&["alice", "bob"], &["carol"]], &[1, 2]),
+        Err(LeaderboardError::UnknownPlayer { team: 1, player: 0 })
+    );
+    assert_eq!(
+        leaderboard.record_duel(&"dave", &"alice", Outcome::Draw),
+        Err(LeaderboardError::UnknownPlayer { team: 0, player: 0 })
+    );
+
+    // Rejected games change nothing.
+    assert_eq!(leaderboard.len(), 2);
+    assert_eq!(leaderboard.rating(&"alice"), Some(&Rating::default()));
+
+    leaderboard
+        .record_duel(&"alice", &"bob", Outcome::Loss)
+        .unwrap();
+    assert!(leaderboard.rating(&"bob").unwrap().mu() > 30.0);
+}
+
+#[test]
+fn invalid_games_are_rejected_atomically() {
+    let mut leaderboard = Leaderboard::new(Rater::default(), Rating::default());
+    leaderboard
+        .record_duel(&"alice", &"bob", Outcome::Win)
+        .unwrap();
+    let alice = leaderboard.rating(&"alice").cloned();
+
+    assert_eq!(
+        leaderboard.record_match(&[&["alice", "carol"], &["bob", "alice"]], &[1, 2]),
+        Err(LeaderboardError::DuplicatePlayer { team: 1, player: 1 })
+    );
+    assert_eq!(
+        leaderboard.record_duel(&"alice", &"alice", Outcome::Win),
+        Err(LeaderboardError::DuplicatePlayer { team: 1, player: 0 })
+    );
+    assert_eq!(
+        leaderboard.record_match(&[&["alice", "carol"], &[]], &[1, 2]),
+        Err(LeaderboardError::Rating(BBTError::EmptyTeam { index: 1 }))
+    );
+
+    assert_eq!(leaderboard.len(), 2);
+    assert_eq!(leaderboard.rating(&"alice").cloned(), alice);
+}
+
+fn season() -> Leaderboard<String> {
+    let mut leaderboard = Leaderboard::new(Rater::default(), Rating::default());
+    let ids = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let (alice, bob, carol, dave, erin) = (
+        "alice".to_string(),
+        "bob".to_string(),
+        "carol".to_string(),
+        "dave".to_string(),
+        "erin".to_string(),
+    );
+
+    leaderboard.record_duel(&alice, &bob, Outcome::Win).unwrap();
+    leaderboard
+        .record_duel(&carol, &dave, Outcome::Win)
+        .unwrap();
+    leaderboard
+        .record_duel(&alice, &carol, Outcome::Win)
+        .unwrap();
+    leaderboard.record_duel(&bob, &dave, Outcome::Draw).unwrap();
+    leaderboard
+        .record_match(
+            &[&ids(&["alice", "dave"]), &ids(&["bob", "carol"])],
+            &[1, 2],
+        )
+        .unwrap();
+    leaderboard
+        .record_match(
+            &[&ids(&["carol"]), &ids(&["bob"]), &ids(&["dave"])],
+            &[1, 2, 3],
+        )
+        .unwrap();
+    // Erin joins late and has not played yet.
+    leaderboard.insert(erin, Rating::default());
+
+    leaderboard
+}
+
+#[test]
+fn standings_after_a_season() {
+    let leaderboard = season();
+    let standings: Vec<_> = leaderboard
+        .standings()
+        .into_iter()
+        .map(|(id, _)| id.as_str())
+        .collect();
+
+    // With losses on record, bob's and dave's conservative estimates fell
+    // below that of a new player.
+    assert_eq!(standings, ["alice", "carol", "erin", "bob", "dave"]);
+
+    let estimates: Vec<_> = leaderboard
+        .standings()
+        .into_iter()
+        .map(|(_, rating)| rating.mu() - 3.0 * rating.sigma())
+        .collect();
+    assert!(estimates.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+#[test]
+fn ties_are_broken_by_insertion_order() {
+    let mut leaderboard = Leaderboard::strict(Rater::default());
+    let names = ["mallory", "alice", "trent", "bob", "eve", "zed", "carol"];
+    for name in &names {
+        leaderboard.insert(*name, Rating::default());
+    }
+    leaderboard.insert("victor", Rating::new(40.0, 1.0));
+    // Updating a rating does not move the player to the back.
+    leaderboard.insert("mallory", Rating::default());
+
+    let standings: Vec<_> = leaderboard
+        .standings()
+        .into_iter()
+        .map(|(id, _)| *id)
+        .collect();
+    assert_eq!(standings[0], "victor");
+    assert_eq!(&standings[1..], &names);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialization_round_trip() {
+    let leaderboard = season();
+
+    let json = serde_json::to_string(&leaderboard).unwrap();
+    let deserialized: Leaderboard<String> = serde_json::from_str(&json).unwrap();
+
+    // Ratings are serialized as mu and sigma, so compare those.
+    let standings = |leaderboard: &Leaderboard<String>| {
+        leaderboard
+            .standings()
+            .into_iter()
+            .map(|(id, rating)| (id.clone(), rating.mu(), rating.sigma()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(standings(&deserialized), standings(&leaderboard));
+    assert_eq!(deserialized.rater().beta(), leaderboard.rater().beta());
+    assert_eq!(deserialized.initial_rating(), leaderboard.initial_rating());
+
+    let strict: Leaderboard<String> = Leaderboard::strict(Rater::new(3.0));
+    let json = serde_json::to_string(&strict).unwrap();
+    assert_eq!(
+        json,
+        r#"{"rater":{"beta":3.0},"initial":null,"players":[]}"#
+    );
+    let deserialized: Leaderboard<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.initial_rating(), None);
+
+    let duplicate = r#"{"rater":{"beta":3.0},"initial":null,"players":[["a",{"mu":1.0,"sigma":1.0}],["a",{"mu":2.0,"sigma":1.0}]]}"#;
+    assert!(serde_json::from_str::<Leaderboard<String>>(duplicate).is_err());
+}
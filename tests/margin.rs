@@ -0,0 +1,73 @@
+extern crate bbt;
+
+use bbt::{BBTError, MarginModel, Rater, Rating};
+
+/// A xorshift generator, so that the games are the same on every run.
+struct Noise(u64);
+
+impl Noise {
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[test]
+fn fitting_recovers_a_linear_relationship() {
+    let mut noise = Noise(726);
+
+    // Games in which every point of mu is worth 0.3 goals, give or take one.
+    let games: Vec<(Rating, Rating, f64)> = (0..2000)
+        .map(|_| {
+            let a = Rating::new(10.0 + 30.0 * noise.next(), 2.0);
+            let b = Rating::new(10.0 + 30.0 * noise.next(), 3.0);
+            let margin = 0.3 * (a.mu() - b.mu()) + 2.0 * noise.next() - 1.0;
+            (a, b, margin)
+        })
+        .collect();
+
+    let model = MarginModel::fit(games).unwrap();
+    assert!((model.points_per_mu() - 0.3).abs() < 0.01, "{:?}", model);
+
+    let rater = Rater::default();
+    let margin = rater.expected_margin(&Rating::new(32.0, 1.0), &Rating::new(25.0, 5.0), &model);
+    assert!((margin - 2.1).abs() < 0.07, "{}", margin);
+}
+
+#[test]
+fn margins_are_antisymmetric() {
+    let rater = Rater::default();
+    let model = MarginModel::new(0.45);
+    let (alice, bob) = (Rating::new(31.25, 2.0), Rating::new(23.5, 6.0));
+
+    assert_eq!(
+        rater.expected_margin(&alice, &bob, &model),
+        -rater.expected_margin(&bob, &alice, &model)
+    );
+    assert!(rater.expected_margin(&alice, &bob, &model) > 0.0);
+    assert_eq!(rater.expected_margin(&alice, &alice, &model), 0.0);
+    assert_eq!(
+        rater.expected_margin(&Rating::new(25.0, 1.0), &Rating::new(25.0, 8.0), &model),
+        0.0
+    );
+}
+
+#[test]
+fn fitting_needs_informative_games() {
+    let even = (Rating::default(), Rating::default(), 3.0);
+
+    assert_eq!(
+        MarginModel::fit(vec![]),
+        Err(BBTError::InvalidParameter { name: "games" })
+    );
+    assert_eq!(
+        MarginModel::fit(vec![even, even]),
+        Err(BBTError::InvalidParameter { name: "games" })
+    );
+    assert_eq!(
+        MarginModel::fit(vec![(Rating::new(30.0, 1.0), Rating::default(), f64::NAN)]),
+        Err(BBTError::InvalidParameter { name: "margin" })
+    );
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{MatchQueue, Rater, Rating};
+
+#[test]
+fn equal_players_pair_immediately() {
+    let mut queue = MatchQueue::new(Rater::default());
+    queue.enqueue("alice", Rating::new(25.0, 1.0), 100);
+    queue.enqueue("bob", Rating::new(25.0, 1.0), 100);
+
+    assert_eq!(queue.pair(0.9, 0.0, 100), vec![("alice", "bob")]);
+    assert!(queue.is_empty());
+    assert_eq!(queue.pair(0.9, 0.0, 100), vec![]);
+}
+
+#[test]
+fn outliers_pair_once_the_threshold_has_relaxed() {
+    let rater = Rater::default();
+    let (alice, outlier) = (Rating::new(25.0, 1.0), Rating::new(45.0, 1.0));
+    let quality = rater.match_quality(&alice, &outlier);
+    assert!(quality < 0.01);
+
+    let mut queue = MatchQueue::new(rater);
+    queue.enqueue("outlier", outlier, 0);
+    queue.enqueue("alice", alice, 50);
+
+    // After t seconds, the outlier accepts matches above 0.9 - 0.01 * t.
+    let waited = ((0.9 - quality) / 0.01).ceil() as i64;
+    for &now in &[0, 50, waited - 1] {
+        assert_eq!(queue.pair(0.9, 0.01, now), vec![], "{}", now);
+        assert_eq!(queue.len(), 2);
+    }
+
+    // Alice has waited less, but the outlier's threshold decides.
+    assert_eq!(queue.pair(0.9, 0.01, waited), vec![("outlier", "alice")]);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn the_best_match_is_taken() {
+    let mut queue = MatchQueue::new(Rater::default());
+    queue.enqueue("alice", Rating::new(25.0, 1.0), 0);
+    queue.enqueue("bob", Rating::new(28.0, 1.0), 0);
+    queue.enqueue("carol", Rating::new(25.5, 1.0), 0);
+    queue.enqueue("dave", Rating::new(28.5, 1.0), 0);
+
+    assert_eq!(
+        queue.pair(0.5, 0.0, 0),
+        vec![("alice", "carol"), ("bob", "dave")]
+    );
+}
+
+fn crowd() -> MatchQueue<u32> {
+    let mut queue = MatchQueue::new(Rater::default());
+    for id in 0..50u32 {
+        let mu = 10.0 + f64::from((id * 37) % 41);
+        let sigma = 1.0 + f64::from(id % 5);
+        queue.enqueue(id, Rating::new(mu, sigma), i64::from((id * 13) % 17));
+    }
+
+    queue
+}
+
+#[test]
+fn no_player_is_paired_twice() {
+    let mut queue = crowd();
+    let pairs = queue.pair(0.6, 0.01, 20);
+    assert!(!pairs.is_empty());
+
+    let mut seen = Vec::new();
+    for &(first, second) in &pairs {
+        assert_ne!(first, second);
+        for id in [first, second] {
+            assert!(!seen.contains(&id), "{} was paired twice", id);
+            assert!(!queue.contains(&id));
+            seen.push(id);
+        }
+    }
+    assert_eq!(queue.len() + seen.len(), 50);
+
+    // Pairing is deterministic.
+    assert_eq!(crowd().pair(0.6, 0.01, 20), pairs);
+}
+
+#[test]
+fn re_enqueueing_keeps_the_place_in_the_queue() {
+    let mut queue = MatchQueue::new(Rater::default());
+    queue.enqueue("outlier", Rating::new(45.0, 1.0), 0);
+    queue.enqueue("alice", Rating::new(25.0, 1.0), 10);
+    queue.enqueue("outlier", Rating::new(25.0, 1.0), 50);
+
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.pair(0.9, 0.0, 50), vec![("outlier", "alice")]);
+
+    queue.enqueue("bob", Rating::new(25.0, 1.0), 0);
+    assert_eq!(queue.remove(&"bob"), Some(Rating::new(25.0, 1.0)));
+    assert_eq!(queue.remove(&"bob"), None);
+}
@@ -0,0 +1,123 @@
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating, StdMath};
+
+fn teams() -> Vec<Vec<Rating>> {
+    vec![
+        vec![Rating::new(30.0, 3.0), Rating::new(22.0, 6.0)],
+        vec![Rating::new(27.0, 8.0)],
+        vec![Rating::new(20.0, 2.0), Rating::new(26.0, 4.0)],
+        vec![Rating::new(45.0, 1.0)],
+    ]
+}
+
+#[test]
+fn std_math_is_the_default() {
+    let default = Rater::default();
+    let rater = Rater::default().with_math_backend(&StdMath);
+    let (p1, p2) = (Rating::new(30.0, 3.0), Rating::new(27.0, 8.0));
+
+    for &outcome in &[Outcome::Win, Outcome::Draw, Outcome::Loss] {
+        assert_eq!(rater.duel(p1, p2, outcome), default.duel(p1, p2, outcome));
+    }
+    assert_eq!(
+        rater.win_probability(&p1, &p2),
+        default.win_probability(&p1, &p2)
+    );
+    assert_eq!(
+        rater.match_quality(&p1, &p2),
+        default.match_quality(&p1, &p2)
+    );
+
+    let new = rater.update_ratings(teams(), vec![2, 1, 3, 2]).unwrap();
+    let expected = default.update_ratings(teams(), vec![2, 1, 3, 2]).unwrap();
+
+    for (rating, expected) in new.iter().flatten().zip(expected.iter().flatten()) {
+        // A rater with a backend does not use the vectorized kernel, which
+        // is not bit-identical.
+        if cfg!(feature = "simd") {
+            assert!((rating.mu() - expected.mu()).abs() < 1e-12);
+            assert!((rating.sigma() - expected.sigma()).abs() < 1e-12);
+        } else {
+            assert_eq!(rating, expected);
+        }
+    }
+}
+
+#[cfg(feature = "fast-math")]
+mod fast_math {
+    use super::teams;
+    use bbt::{FastMath, MathBackend, Outcome, Rater, Rating};
+
+    /// The largest difference in rating points between updates with
+    /// `FastMath` and the default backend, as documented.
+    const TOLERANCE: f64 = 1e-4;
+
+    fn assert_close(a: &Rating, b: &Rating) {
+        assert!((a.mu() - b.mu()).abs() < TOLERANCE, "{:?} {:?}", a, b);
+        assert!((a.sigma() - b.sigma()).abs() < TOLERANCE, "{:?} {:?}", a, b);
+    }
+
+    #[test]
+    fn updates_are_close_to_the_default() {
+        let default = Rater::default();
+        let rater = Rater::default().with_math_backend(&FastMath);
+
+        for &(p1, p2) in &[
+            (Rating::default(), Rating::default()),
+            (Rating::new(30.0, 3.0), Rating::new(27.0, 8.0)),
+            (Rating::new(1500.0, 50.0), Rating::new(-200.0, 0.5)),
+        ] {
+            for &outcome in &[Outcome::Win, Outcome::Draw, Outcome::Loss] {
+                let (a1, a2) = rater.duel(p1, p2, outcome);
+                let (b1, b2) = default.duel(p1, p2, outcome);
+                assert_close(&a1, &b1);
+                assert_close(&a2, &b2);
+            }
+
+            let p = rater.win_probability(&p1, &p2);
+            assert!((p - default.win_probability(&p1, &p2)).abs() < 1e-10);
+        }
+
+        let new = rater.update_ratings(teams(), vec![2, 1, 3, 2]).unwrap();
+        let expected = default.update_ratings(teams(), vec![2, 1, 3, 2]).unwrap();
+        for (rating, expected) in new.iter().flatten().zip(expected.iter().flatten()) {
+            assert_close(rating, expected);
+        }
+    }
+
+    #[test]
+    fn exp_is_accurate_and_never_nan() {
+        let mut x = -1000.0;
+        while x <= 1000.0 {
+            let fast = MathBackend::<f64>::exp(&FastMath, x);
+            assert!(!fast.is_nan(), "{}", x);
+            assert!(fast >= 0.0);
+
+            let exact = x.exp();
+            if exact.is_finite() && exact != 0.0 {
+                assert!(((fast - exact) / exact).abs() < 1e-11, "{}", x);
+            } else {
+                assert_eq!(fast, exact);
+            }
+
+            let fast = MathBackend::<f32>::exp(&FastMath, x as f32);
+            assert!(!fast.is_nan(), "{}", x);
+
+            x += 0.0371;
+        }
+
+        for &x in &[
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.0,
+            -0.0,
+            1e-300,
+            f64::MAX,
+        ] {
+            let fast = MathBackend::<f64>::exp(&FastMath, x);
+            assert_eq!(fast, x.exp());
+        }
+        assert!(MathBackend::<f64>::exp(&FastMath, f64::NAN).is_nan());
+    }
+}
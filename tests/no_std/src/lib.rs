@@ -0,0 +1,66 @@
+//! This crate checks that bbt builds and works without the standard library
+//! and without an allocator. Build it for a bare-metal target to make sure
+//! nothing pulls in `std`:
+//!
+//! ```text
+//! cargo build --manifest-path tests/no_std/Cargo.toml --target thumbv7em-none-eabihf
+//! ```
+//!
+//! `cargo test --manifest-path tests/no_std/Cargo.toml` runs the checks below
+//! on the host.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate bbt;
+
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+/// Rates a head-to-head game.
+pub fn duel(p1: Rating<f32>, p2: Rating<f32>, outcome: Outcome) -> (Rating<f32>, Rating<f32>) {
+    Rater::new(25.0 / 6.0).duel(p1, p2, outcome)
+}
+
+/// Rates a four-player race, updating the ratings in place.
+pub fn race(players: &mut [Rating; 4], ranks: [usize; 4]) -> Result<(), BBTError> {
+    let [p1, p2, p3, p4] = players;
+    let mut teams = [
+        core::slice::from_mut(p1),
+        core::slice::from_mut(p2),
+        core::slice::from_mut(p3),
+        core::slice::from_mut(p4),
+    ];
+
+    Rater::default().update_ratings_const(&mut teams, ranks)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn duel_matches_std_fixture() {
+        let (winner, loser) = duel(Rating::default(), Rating::default(), Outcome::Win);
+
+        assert!((winner.mu() - 27.635231).abs() < 1e-4);
+        assert!((loser.mu() - 22.364769).abs() < 1e-4);
+        assert!((winner.sigma() - 8.065506).abs() < 1e-4);
+    }
+
+    #[test]
+    fn race_matches_std_fixture() {
+        let mut players = [
+            Rating::default(),
+            Rating::default(),
+            Rating::default(),
+            Rating::default(),
+        ];
+
+        race(&mut players, [1, 2, 3, 4]).unwrap();
+
+        let expected_mu = [32.9056941, 27.6352313, 22.3647686, 17.0943058];
+        for (player, &mu) in players.iter().zip(expected_mu.iter()) {
+            assert!((player.mu() - mu).abs() < 1e-7);
+            assert!((player.sigma() - 7.50121906).abs() < 1e-6);
+        }
+    }
+}
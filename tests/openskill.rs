@@ -0,0 +1,97 @@
+extern crate bbt;
+extern crate serde_json;
+
+use bbt::{Rater, Rating};
+use serde_json::Value;
+
+fn parse_teams(teams: &Value) -> Vec<Vec<Rating>> {
+    teams
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|team| {
+            team.as_array()
+                .unwrap()
+                .iter()
+                .map(|player| {
+                    Rating::new(
+                        player["mu"].as_f64().unwrap(),
+                        player["sigma"].as_f64().unwrap(),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Compares bbt with games rated by OpenSkill's Bradley-Terry full-pair
+/// model. The fixture is not checked in; generate it with openskill.js
+/// installed, which fails if the package is missing, and run the test:
+///
+/// ```text
+/// npm install openskill
+/// node tests/fixtures/openskill/generate.js
+/// cargo test --test openskill -- --ignored
+/// ```
+#[test]
+#[ignore]
+fn matches_openskill_bradley_terry_full() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/openskill/openskill.json"
+    );
+    let fixture = std::fs::read_to_string(path).expect("generate the fixture first");
+    let fixture: Value = serde_json::from_str(&fixture).unwrap();
+    let rater = Rater::default();
+
+    assert_eq!(fixture["source"], "openskill.js");
+    let version = fixture["version"].as_str().unwrap();
+
+    let games = fixture["games"].as_array().unwrap();
+    assert!(!games.is_empty());
+
+    for game in games {
+        let teams = parse_teams(&game["teams"]);
+        let expected = parse_teams(&game["expected"]);
+        let ranks = game["rank"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rank| rank.as_u64().unwrap() as usize)
+            .collect();
+
+        let actual = rater.update_ratings(teams, ranks).unwrap();
+
+        for (actual, expected) in actual.iter().flatten().zip(expected.iter().flatten()) {
+            assert!(
+                (actual.mu() - expected.mu()).abs() < 1e-9,
+                "openskill.js {}: {:?}",
+                version,
+                game
+            );
+            assert!(
+                (actual.sigma() - expected.sigma()).abs() < 1e-9,
+                "openskill.js {}: {:?}",
+                version,
+                game
+            );
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserializes_openskill_ratings() {
+    // The shape of the ratings returned by openskill.js's `rate`.
+    let teams = r#"[
+        [{"mu": 27.63523138347365, "sigma": 8.065506316323548}],
+        [{"mu": 22.36476861652635, "sigma": 8.065506316323548}, {"mu": 25, "sigma": 0.5}]
+    ]"#;
+    let teams: Value = serde_json::from_str(teams).unwrap();
+
+    let ratings: Vec<Vec<Rating>> = serde_json::from_value(teams.clone()).unwrap();
+    assert_eq!(ratings, parse_teams(&teams));
+
+    let serialized = serde_json::to_value(&ratings).unwrap();
+    assert_eq!(parse_teams(&serialized), ratings);
+}
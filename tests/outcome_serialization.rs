@@ -0,0 +1,84 @@
+#![cfg(feature = "serde")]
+extern crate bbt;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+
+use bbt::Outcome;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Game {
+    plain: Outcome,
+    #[serde(with = "bbt::serde_outcome::lowercase")]
+    lowercase: Outcome,
+    #[serde(with = "bbt::serde_outcome::chess")]
+    chess: Outcome,
+    #[serde(with = "bbt::serde_outcome::int")]
+    int: Outcome,
+}
+
+fn game(outcome: Outcome) -> Game {
+    Game {
+        plain: outcome,
+        lowercase: outcome,
+        chess: outcome,
+        int: outcome,
+    }
+}
+
+#[test]
+fn every_representation_round_trips() {
+    let expected = [
+        (
+            Outcome::Win,
+            r#"{"plain":"Win","lowercase":"win","chess":"1-0","int":1}"#,
+        ),
+        (
+            Outcome::Loss,
+            r#"{"plain":"Loss","lowercase":"loss","chess":"0-1","int":-1}"#,
+        ),
+        (
+            Outcome::Draw,
+            r#"{"plain":"Draw","lowercase":"draw","chess":"1/2-1/2","int":0}"#,
+        ),
+    ];
+
+    for &(outcome, json) in expected.iter() {
+        let serialized = serde_json::to_string(&game(outcome)).unwrap();
+        assert_eq!(serialized, json);
+
+        let deserialized: Game = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, game(outcome));
+    }
+}
+
+#[test]
+fn string_forms_ignore_case() {
+    let json = r#"{"plain":"Draw","lowercase":"WIN","chess":"½-½","int":0}"#;
+    let deserialized: Game = serde_json::from_str(json).unwrap();
+    assert_eq!(deserialized.lowercase, Outcome::Win);
+    assert_eq!(deserialized.chess, Outcome::Draw);
+
+    let json = r#"{"plain":"Win","lowercase":"Loss","chess":"1/2-1/2","int":1}"#;
+    let deserialized: Game = serde_json::from_str(json).unwrap();
+    assert_eq!(deserialized.lowercase, Outcome::Loss);
+}
+
+#[test]
+fn unknown_values_are_rejected() {
+    let rejected = [
+        r#"{"plain":"win","lowercase":"win","chess":"1-0","int":1}"#,
+        r#"{"plain":"Win","lowercase":"victory","chess":"1-0","int":1}"#,
+        r#"{"plain":"Win","lowercase":"win","chess":"2-0","int":1}"#,
+        r#"{"plain":"Win","lowercase":"win","chess":"1-0","int":2}"#,
+        r#"{"plain":"Win","lowercase":"win","chess":"1-0","int":-2}"#,
+        r#"{"plain":"Win","lowercase":"win","chess":"1-0","int":1.0}"#,
+        r#"{"plain":"Win","lowercase":"win","chess":"1-0","int":"1"}"#,
+        r#"{"plain":"Win","lowercase":1,"chess":"1-0","int":1}"#,
+    ];
+
+    for json in rejected.iter() {
+        assert!(serde_json::from_str::<Game>(json).is_err(), "{}", json);
+    }
+}
@@ -0,0 +1,60 @@
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating};
+
+fn estimate(rating: &Rating) -> f64 {
+    rating.mu() - 3.0 * rating.sigma()
+}
+
+#[test]
+fn a_chaser_who_is_ahead_needs_no_games() {
+    let rater = Rater::default();
+    let chaser = Rating::new(30.0, 1.0);
+    let target = Rating::new(20.0, 1.0);
+
+    for &outcome in &[None, Some(Outcome::Win)] {
+        assert_eq!(
+            rater.games_to_overtake(&chaser, &target, &Rating::default(), 0, outcome),
+            Some(0)
+        );
+    }
+
+    // An equal estimate is not enough.
+    assert_eq!(
+        rater.games_to_overtake(&target, &target, &target, 0, None),
+        None
+    );
+}
+
+#[test]
+fn an_enormous_gap_cannot_be_closed_quickly() {
+    let rater = Rater::default();
+    let chaser = Rating::default();
+    let target = Rating::new(200.0, 1.0);
+
+    assert_eq!(
+        rater.games_to_overtake(&chaser, &target, &Rating::default(), 10, None),
+        None
+    );
+}
+
+#[test]
+fn a_single_win_matches_a_duel() {
+    let rater = Rater::default();
+    let chaser = Rating::new(25.0, 3.0);
+    let opponent = Rating::new(25.0, 3.0);
+
+    let (after, _) = rater.duel(chaser, opponent, Outcome::Win);
+    // A target between the chaser's estimates before and after one win.
+    let target = Rating::new((estimate(&chaser) + estimate(&after)) / 2.0 + 3.0, 1.0);
+    assert!(estimate(&after) > estimate(&target));
+
+    assert_eq!(
+        rater.games_to_overtake(&chaser, &target, &opponent, 10, None),
+        Some(1)
+    );
+    assert_eq!(
+        rater.games_to_overtake(&chaser, &target, &opponent, 0, None),
+        None
+    );
+}
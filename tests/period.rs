@@ -0,0 +1,150 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{BBTError, PeriodGame, Rater, Rating};
+
+fn players() -> Vec<Rating> {
+    vec![
+        Rating::new(30.0, 3.0),
+        Rating::new(27.0, 8.0),
+        Rating::new(20.0, 2.0),
+        Rating::new(26.0, 4.0),
+        Rating::new(22.0, 6.0),
+    ]
+}
+
+fn games() -> Vec<PeriodGame> {
+    vec![
+        PeriodGame::new(vec![vec![0], vec![1]], vec![1, 2]),
+        PeriodGame::new(vec![vec![2, 3], vec![1, 0]], vec![1, 2]),
+        PeriodGame::new(vec![vec![4], vec![0], vec![2]], vec![2, 1, 2]),
+        PeriodGame::new(vec![vec![1], vec![4]], vec![1, 1]),
+    ]
+}
+
+/// Rates the games one after another, with `update_ratings`.
+fn sequential(rater: &Rater, games: &[PeriodGame]) -> Vec<Rating> {
+    let mut ratings = players();
+    for game in games {
+        let teams = game
+            .teams
+            .iter()
+            .map(|team| team.iter().map(|&player| ratings[player]).collect())
+            .collect();
+        let updated = rater.update_ratings(teams, game.ranks.clone()).unwrap();
+
+        for (team, new) in game.teams.iter().zip(updated) {
+            for (&player, rating) in team.iter().zip(new) {
+                ratings[player] = rating;
+            }
+        }
+    }
+
+    ratings
+}
+
+#[test]
+fn a_single_game_is_a_ranked_update() {
+    let rater = Rater::default();
+
+    for game in games().chunks(1) {
+        let mut ratings = players();
+        rater.update_period(&mut ratings, game).unwrap();
+
+        let expected = sequential(&rater, game);
+        for (rating, expected) in ratings.iter().zip(&expected) {
+            // The vectorized kernel of `update_ratings` is not bit-identical.
+            if cfg!(feature = "simd") {
+                assert!((rating.mu() - expected.mu()).abs() < 1e-12);
+                assert!((rating.sigma() - expected.sigma()).abs() < 1e-12);
+            } else {
+                assert_eq!(rating, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn the_order_of_the_games_does_not_matter() {
+    let rater = Rater::default();
+    let mut games = games();
+
+    let mut expected = players();
+    rater.update_period(&mut expected, &games).unwrap();
+
+    for shift in 1..games.len() {
+        games.rotate_left(1);
+        if shift % 2 == 0 {
+            games.reverse();
+        }
+
+        let mut ratings = players();
+        rater.update_period(&mut ratings, &games).unwrap();
+
+        for (rating, expected) in ratings.iter().zip(&expected) {
+            assert_eq!(rating.mu().to_bits(), expected.mu().to_bits());
+            assert_eq!(rating.sigma().to_bits(), expected.sigma().to_bits());
+        }
+    }
+}
+
+#[test]
+fn sequential_updates_depend_on_the_order() {
+    let rater = Rater::default();
+    let mut games = games();
+
+    let forward = sequential(&rater, &games);
+    games.reverse();
+    let backward = sequential(&rater, &games);
+
+    assert!((forward[0].mu() - backward[0].mu()).abs() > 1e-3);
+
+    // A period is neither of them.
+    let mut ratings = players();
+    rater.update_period(&mut ratings, &games).unwrap();
+    assert!((ratings[0].mu() - forward[0].mu()).abs() > 1e-3);
+    assert!((ratings[0].mu() - backward[0].mu()).abs() > 1e-3);
+}
+
+#[test]
+fn invalid_games_change_nothing() {
+    let rater = Rater::default();
+
+    for games in &[
+        vec![PeriodGame::new(vec![vec![0], vec![5]], vec![1, 2])],
+        vec![PeriodGame::new(vec![vec![0], vec![]], vec![1, 2])],
+        vec![
+            PeriodGame::new(vec![vec![0], vec![1]], vec![1, 2]),
+            PeriodGame::new(vec![vec![2, 3], vec![3]], vec![1, 2]),
+        ],
+    ] {
+        let mut ratings = players();
+        assert_eq!(
+            rater.update_period(&mut ratings, games),
+            Err(BBTError::InvalidParameter { name: "games" })
+        );
+        assert_eq!(ratings, players());
+    }
+
+    let mut ratings = players();
+    ratings[3] = Rating::new(f64::NAN, 1.0);
+    assert_eq!(
+        rater.update_period(&mut ratings, &games()),
+        Err(BBTError::InvalidRating { team: 3, player: 0 })
+    );
+
+    let mut ratings = players();
+    let games = vec![
+        PeriodGame::new(vec![vec![0], vec![1]], vec![1, 2]),
+        PeriodGame::new(vec![vec![2], vec![3]], vec![1]),
+    ];
+    assert_eq!(
+        rater.update_period(&mut ratings, &games),
+        Err(BBTError::MismatchedLengths { teams: 2, ranks: 1 })
+    );
+    assert_eq!(ratings, players());
+
+    // A period without games is fine, and changes nothing either.
+    rater.update_period(&mut ratings, &[]).unwrap();
+    assert_eq!(ratings, players());
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "pgn")]
+
+extern crate bbt;
+
+use bbt::io::{self, PgnError, PgnGame};
+use bbt::replay::{self, GameRecord};
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+const GAMES: &str = r#"[Event "Club championship"]
+[Site "?"]
+[Date "2018.08.25"]
+[Round "1"]
+[White "Alice, A."]
+[Black "Bob"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 $1 Nc6 {The most common reply.
+[%clk 0:05:00] A bracket at the start of a line inside a comment
+[Result "0-1"] must not start a new game.} 3. Bb5 a6 ; a rest-of-line { comment
+4. Ba4 (4. Bxc6 dxc6 5. O-O) 4... Nf6 $14 1-0
+
+[White "Bob"]
+[Black "Carol \"The Rook\""]
+[Result "1/2-1/2"]
+[Date "2018.??.??"]
+
+%This escaped line is ignored: [White "Mallory"]
+1. d4 d5 2. c4 {Queen's gambit} e6 1/2-1/2
+
+[White "Carol \"The Rook\""]
+[Black "Alice, A."]
+[Result "*"]
+
+1. c4 *
+
+[White "Carol \"The Rook\""]
+[Black "Dave"]
+[Result "0-1"]
+1. f3 e5 2. g4 Qh4# 0-1
+"#;
+
+fn game(white: &str, black: &str, outcome: Outcome, date: Option<&str>, line: u64) -> PgnGame {
+    PgnGame {
+        white: white.to_string(),
+        black: black.to_string(),
+        outcome,
+        date: date.map(str::to_string),
+        line,
+    }
+}
+
+#[test]
+fn results_are_read_from_the_tags() {
+    let results: Vec<_> = io::pgn_results(GAMES.as_bytes()).collect();
+    assert_eq!(results.len(), 4);
+
+    assert_eq!(
+        *results[0].as_ref().unwrap(),
+        game("Alice, A.", "Bob", Outcome::Win, Some("2018.08.25"), 1)
+    );
+    assert_eq!(
+        *results[1].as_ref().unwrap(),
+        game(
+            "Bob",
+            "Carol \"The Rook\"",
+            Outcome::Draw,
+            Some("2018.??.??"),
+            14
+        )
+    );
+
+    match results[2] {
+        Err(ref error @ PgnError::Unfinished { line: 22 }) => assert!(error.is_warning()),
+        ref other => panic!("unexpected result {:?}", other),
+    }
+
+    assert_eq!(
+        *results[3].as_ref().unwrap(),
+        game("Carol \"The Rook\"", "Dave", Outcome::Loss, None, 28)
+    );
+}
+
+#[test]
+fn windows_line_endings_are_accepted() {
+    let crlf = GAMES.replace('\n', "\r\n");
+
+    let lf: Vec<_> = io::pgn_results(GAMES.as_bytes())
+        .filter_map(Result::ok)
+        .collect();
+    let crlf: Vec<_> = io::pgn_results(crlf.as_bytes())
+        .filter_map(Result::ok)
+        .collect();
+    assert_eq!(lf, crlf);
+}
+
+#[test]
+fn replays_match_the_direct_api() {
+    let rater = Rater::default();
+
+    let ratings = io::replay_pgn(&rater, GAMES.as_bytes(), Rating::default()).unwrap();
+
+    let records: Vec<GameRecord> = io::pgn_results(GAMES.as_bytes())
+        .filter_map(Result::ok)
+        .map(|game| game.to_record())
+        .collect();
+    assert_eq!(records[1].ranks, vec![1, 1]);
+    assert_eq!(records[2].ranks, vec![2, 1]);
+
+    let expected = replay::replay(&rater, &records, Rating::default()).unwrap();
+    assert_eq!(ratings, expected);
+    assert_eq!(ratings.len(), 4);
+}
+
+#[test]
+fn invalid_games_are_errors_but_reading_continues() {
+    let pgn = r#"[White "Alice"]
+[Black "Bob"]
+[Result "2-0"]
+
+1. e4 2-0
+
+[White "Alice"]
+[Result "1-0"]
+
+1. e4 1-0
+
+[White "Alice"]
+[Black Bob]
+[Result "1-0"]
+
+1. e4 1-0
+
+[White "Alice"]
+[Black "Bob"]
+[Result "0-1"]
+
+1. e4 0-1
+"#;
+
+    let results: Vec<_> = io::pgn_results(pgn.as_bytes()).collect();
+    assert_eq!(results.len(), 4);
+
+    match results[0] {
+        Err(PgnError::InvalidResult { line: 1, ref value }) if value == "2-0" => {}
+        ref other => panic!("unexpected result {:?}", other),
+    }
+    match results[1] {
+        Err(PgnError::MissingTag {
+            line: 7,
+            name: "Black",
+        }) => {}
+        ref other => panic!("unexpected result {:?}", other),
+    }
+    match results[2] {
+        Err(PgnError::MalformedTag { line: 12 }) => {}
+        ref other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(results[3].as_ref().unwrap().outcome, Outcome::Loss);
+
+    let error = io::replay_pgn(&Rater::default(), pgn.as_bytes(), Rating::default()).unwrap_err();
+    assert_eq!(error.line(), Some(1));
+    assert!(!error.is_warning());
+}
+
+#[test]
+fn rejected_games_name_the_line() {
+    let pgn = "[White \"Alice\"]\n[Black \"Alice\"]\n[Result \"1-0\"]\n\n1-0\n";
+    match io::replay_pgn(&Rater::default(), pgn.as_bytes(), Rating::default()) {
+        Err(PgnError::InvalidGame { line: 1, .. }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    let pgn = "[White \"Alice\"]\n[Black \"Bob\"]\n[Result \"1-0\"]\n\n1-0\n";
+    let invalid = Rating::new(25.0, -1.0);
+    match io::replay_pgn(&Rater::default(), pgn.as_bytes(), invalid) {
+        Err(PgnError::Rejected {
+            line: 1,
+            error: BBTError::InvalidRating { .. },
+        }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
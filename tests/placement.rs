@@ -0,0 +1,151 @@
+#![cfg(not(target_arch = "wasm32"))]
+extern crate bbt;
+extern crate rand;
+
+use bbt::{BBTError, Outcome, PlacementSeries, Rater, Rating};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// A pool of settled players whose ratings are their true skills.
+fn pool() -> Vec<Rating> {
+    (0..=40).map(|i| Rating::new(5.0 + i as f64, 1.0)).collect()
+}
+
+/// Draws the outcome of a game between a player of the given true skill
+/// and an opponent of the pool from the model's own win probability.
+fn play(rater: &Rater, rng: &mut SmallRng, skill: f64, opponent: &Rating) -> Outcome {
+    let truth = Rater::new(rater.beta());
+    let p = truth.win_probability(&Rating::new(skill, 0.0), &Rating::new(opponent.mu(), 0.0));
+
+    if rng.gen::<f64>() < p {
+        Outcome::Win
+    } else {
+        Outcome::Loss
+    }
+}
+
+#[test]
+fn adaptive_opponents_place_better_than_random_ones() {
+    let rater = Rater::default();
+    let pool = pool();
+    let mut rng = SmallRng::seed_from_u64(740);
+
+    let trials = 400;
+    let (mut adaptive_error, mut random_error) = (0.0, 0.0);
+
+    for _ in 0..trials {
+        let skill = rng.gen_range(10.0..40.0);
+
+        let mut series = PlacementSeries::new(&rater, Rating::default(), &pool, 5).unwrap();
+        while !series.is_done() {
+            let opponent = pool[series.next_opponent()];
+            let outcome = play(&rater, &mut rng, skill, &opponent);
+            series.record(outcome);
+        }
+        assert_eq!(series.games_played(), 5);
+        adaptive_error += (series.current_estimate().mu() - skill).abs();
+
+        let mut rating = Rating::default();
+        for _ in 0..5 {
+            let opponent = pool[rng.gen_range(0..pool.len())];
+            let outcome = play(&rater, &mut rng, skill, &opponent);
+            rating = rater.duel(rating, opponent, outcome).0;
+        }
+        random_error += (rating.mu() - skill).abs();
+    }
+
+    let (adaptive_error, random_error) =
+        (adaptive_error / trials as f64, random_error / trials as f64);
+    assert!(
+        adaptive_error < random_error,
+        "adaptive error {} is not below random error {}",
+        adaptive_error,
+        random_error
+    );
+}
+
+#[test]
+fn opponent_selection_is_deterministic() {
+    let rater = Rater::default();
+    let pool = pool();
+    let outcomes = [Outcome::Win, Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    let mut first = PlacementSeries::new(&rater, Rating::default(), &pool, 4).unwrap();
+    let mut second = PlacementSeries::new(&rater, Rating::default(), &pool, 4).unwrap();
+
+    for &outcome in &outcomes {
+        assert_eq!(first.next_opponent(), second.next_opponent());
+        assert_eq!(first.next_opponent(), first.clone().next_opponent());
+        first.record(outcome);
+        second.record(outcome);
+        assert_eq!(first.current_estimate(), second.current_estimate());
+    }
+
+    assert!(first.is_done());
+}
+
+#[test]
+fn estimates_follow_the_results() {
+    let rater = Rater::default();
+    let pool = pool();
+
+    let mut winner = PlacementSeries::new(&rater, Rating::default(), &pool, 3).unwrap();
+    let mut loser = PlacementSeries::new(&rater, Rating::default(), &pool, 3).unwrap();
+    let start = winner.next_opponent();
+
+    for _ in 0..3 {
+        winner.record(Outcome::Win);
+        loser.record(Outcome::Loss);
+    }
+
+    assert!(winner.next_opponent() > start);
+    assert!(loser.next_opponent() < start);
+    assert!(winner.current_estimate().mu() > 25.0);
+    assert!(loser.current_estimate().mu() < 25.0);
+    assert!(winner.current_estimate().sigma() < Rating::default().sigma());
+}
+
+#[test]
+fn pools_without_a_close_opponent_use_the_nearest() {
+    let rater = Rater::default();
+    let pool = [
+        Rating::new(60.0, 1.0),
+        Rating::new(45.0, 1.0),
+        Rating::new(50.0, 1.0),
+        Rating::new(45.0, 1.0),
+    ];
+
+    let mut series = PlacementSeries::new(&rater, Rating::default(), &pool, 2).unwrap();
+    assert_eq!(series.next_opponent(), 1);
+
+    series.record(Outcome::Loss);
+    assert_eq!(series.next_opponent(), 1);
+}
+
+#[test]
+fn certain_opponents_are_preferred() {
+    let rater = Rater::default();
+    let pool = [Rating::new(25.0, 8.0), Rating::new(25.0, 1.0)];
+
+    let series = PlacementSeries::new(&rater, Rating::default(), &pool, 1).unwrap();
+    assert_eq!(series.next_opponent(), 1);
+}
+
+#[test]
+fn invalid_series_are_rejected() {
+    let rater = Rater::default();
+    let pool = pool();
+
+    let error = PlacementSeries::new(&rater, Rating::default(), &[], 5).unwrap_err();
+    assert_eq!(error, BBTError::InvalidParameter { name: "pool" });
+
+    let invalid = [Rating::default(), Rating::new(f64::NAN, 1.0)];
+    let error = PlacementSeries::new(&rater, Rating::default(), &invalid, 5).unwrap_err();
+    assert_eq!(error, BBTError::InvalidParameter { name: "pool" });
+
+    let error = PlacementSeries::new(&rater, Rating::new(25.0, -1.0), &pool, 5).unwrap_err();
+    assert_eq!(error, BBTError::InvalidParameter { name: "prior" });
+
+    let series = PlacementSeries::new(&rater, Rating::default(), &pool, 0).unwrap();
+    assert!(series.is_done());
+}
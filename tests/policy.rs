@@ -0,0 +1,95 @@
+extern crate bbt;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use bbt::{MuBounds, Outcome, Rater, Rating};
+
+#[test]
+fn no_policy_reproduces_golden_values() {
+    let (winner, loser) = Rater::default().duel(Rating::default(), Rating::default(), Outcome::Win);
+
+    assert!((winner.mu() - 27.635_231_38).abs() < 1e-8);
+    assert!((winner.sigma() - 8.065_506_3).abs() < 1e-6);
+    assert!((loser.mu() - 22.364_768_61).abs() < 1e-8);
+    assert!((loser.sigma() - 8.065_506_3).abs() < 1e-6);
+}
+
+#[test]
+fn floors_stop_mu_but_not_sigma() {
+    let plain = Rater::default();
+    let floored = Rater::default().with_policy(MuBounds::floor(24.0));
+
+    let (winner, loser) = plain.duel(Rating::default(), Rating::default(), Outcome::Win);
+    let (floored_winner, floored_loser) =
+        floored.duel(Rating::default(), Rating::default(), Outcome::Win);
+
+    assert_eq!(floored_loser.mu(), 24.0);
+    assert_eq!(floored_loser.sigma(), loser.sigma());
+    assert!(floored_loser.sigma() < Rating::default().sigma());
+    // The opponent is unaffected.
+    assert_eq!(floored_winner, winner);
+
+    let teams = vec![
+        vec![Rating::new(10.0, 3.0), Rating::new(12.0, 2.0)],
+        vec![Rating::new(24.5, 3.0)],
+    ];
+    let expected = plain.update_ratings(teams.clone(), vec![1, 2]).unwrap();
+    let updated = floored.update_ratings(teams, vec![1, 2]).unwrap();
+
+    assert_eq!(updated[0], expected[0]);
+    assert!(expected[1][0].mu() < 24.0);
+    assert_eq!(updated[1][0].mu(), 24.0);
+    assert_eq!(updated[1][0].sigma(), expected[1][0].sigma());
+
+    // A player who already is below the floor may not fall further, but is
+    // not lifted to it either.
+    let below = Rating::new(20.0, 4.0);
+    let (_, expected) = plain.duel(Rating::default(), below, Outcome::Win);
+    let (_, updated) = floored.duel(Rating::default(), below, Outcome::Win);
+
+    assert!(expected.mu() < 20.0);
+    assert_eq!(updated.mu(), 20.0);
+    assert_eq!(updated.sigma(), expected.sigma());
+}
+
+#[test]
+fn ceilings() {
+    let capped = Rater::default().with_policy(MuBounds::new(Some(10.0), Some(26.0)));
+    let (winner, loser) = capped.duel(Rating::default(), Rating::default(), Outcome::Win);
+
+    assert_eq!(winner.mu(), 26.0);
+    assert!(loser.mu() < 23.0);
+}
+
+#[test]
+fn closures_see_every_written_back_rating() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let rater = Rater::default().with_policy(move |_: &Rating, new: &Rating| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        *new
+    });
+
+    rater.duel(Rating::default(), Rating::default(), Outcome::Draw);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Certain players are not updated, so the policy does not see them.
+    rater.duel(Rating::new(25.0, 0.0), Rating::default(), Outcome::Draw);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    rater
+        .update_ratings(
+            vec![vec![Rating::default(); 3], vec![Rating::default(); 2]],
+            vec![1, 2],
+        )
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 8);
+
+    // A policy that changes nothing changes nothing.
+    let teams = vec![vec![Rating::new(28.0, 3.0)], vec![Rating::default()]];
+    assert_eq!(
+        rater.update_ratings(teams.clone(), vec![2, 1]),
+        Rater::default().update_ratings(teams, vec![2, 1])
+    );
+}
@@ -0,0 +1,65 @@
+extern crate bbt;
+
+use bbt::presets::{self, Preset};
+use bbt::{Outcome, Rater, Rating};
+
+const PRESETS: [Preset; 3] = [presets::TRUESKILL, presets::CHESS_LIKE, presets::PERCENT];
+
+#[test]
+fn presets_use_the_standard_ratios() {
+    for &(preset, midpoint) in &[
+        (presets::TRUESKILL, 25.0),
+        (presets::CHESS_LIKE, 1500.0),
+        (presets::PERCENT, 50.0),
+        (presets::custom(1000.0), 1000.0),
+    ] {
+        assert_eq!(preset.mu, midpoint);
+        assert_eq!(preset.sigma, midpoint / 3.0);
+        assert_eq!(preset.beta, midpoint / 6.0);
+
+        let (rater, rating) = preset.pair();
+        assert_eq!(rater.beta(), preset.beta);
+        assert_eq!(rating, preset.rating());
+        assert_eq!(rating.mu(), preset.mu);
+        assert_eq!(rating.sigma(), preset.sigma);
+        assert!((rating.mu() - 3.0 * rating.sigma()).abs() < 1e-12 * midpoint);
+    }
+}
+
+#[test]
+fn trueskill_is_the_default() {
+    let (rater, rating) = presets::TRUESKILL.pair();
+
+    assert_eq!(rating, Rating::default());
+    assert_eq!(rater.beta(), Rater::default().beta());
+    assert_eq!(
+        rater.duel(rating, Rating::new(30.0, 2.0), Outcome::Win),
+        Rater::default().duel(Rating::default(), Rating::new(30.0, 2.0), Outcome::Win)
+    );
+}
+
+#[test]
+fn equivalent_matchups_agree_across_presets() {
+    let reference = presets::TRUESKILL;
+
+    for preset in &PRESETS {
+        let scale = preset.mu / reference.mu;
+        let (rater, rating) = preset.pair();
+        let stronger = Rating::new(preset.mu + 5.0 * scale, 2.0 * scale);
+
+        let expected = reference
+            .rater()
+            .win_probability(&Rating::new(reference.mu + 5.0, 2.0), &reference.rating());
+        let probability = rater.win_probability(&stronger, &rating);
+        assert!((probability - expected).abs() < 1e-12, "{:?}", preset);
+
+        let (winner, loser) = rater.duel(stronger, rating, Outcome::Loss);
+        let (expected_winner, expected_loser) = reference.rater().duel(
+            Rating::new(reference.mu + 5.0, 2.0),
+            reference.rating(),
+            Outcome::Loss,
+        );
+        assert!((winner.mu() / scale - expected_winner.mu()).abs() < 1e-9);
+        assert!((loser.sigma() / scale - expected_loser.sigma()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "python")]
+extern crate bbt;
+extern crate pyo3;
+
+use std::ffi::CString;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+/// Runs `code` with the `bbt` module imported, then passes its local
+/// variables to `check`.
+fn run<T, F: FnOnce(Python, &Bound<PyDict>) -> T>(code: &str, check: F) -> T {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "bbt").unwrap();
+        bbt::python::bbt(&module).unwrap();
+        py.import("sys")
+            .unwrap()
+            .getattr("modules")
+            .unwrap()
+            .set_item("bbt", &module)
+            .unwrap();
+
+        let locals = PyDict::new(py);
+        let code = CString::new(format!("import bbt\n{}", code)).unwrap();
+        py.run(&code, None, Some(&locals)).unwrap();
+
+        check(py, &locals)
+    })
+}
+
+fn get(locals: &Bound<PyDict>, name: &str) -> f64 {
+    locals.get_item(name).unwrap().unwrap().extract().unwrap()
+}
+
+#[test]
+fn duel_matches_rust() {
+    let p1 = Rating::new(25.0, 25.0 / 3.0);
+    let p2 = Rating::new(20.0, 4.0);
+    let (new_p1, new_p2) = Rater::default().duel(p1, p2, Outcome::Loss);
+
+    run(
+        "rater = bbt.Rater()\n\
+         p1, p2 = rater.duel(bbt.Rating(), bbt.Rating(20, 4), 'loss')\n\
+         mu1, sigma1, ordinal1 = p1.mu, p1.sigma, p1.ordinal\n\
+         mu2, sigma2 = p2.mu, p2.sigma\n\
+         probability = rater.win_probability(bbt.Rating(), bbt.Rating(20, 4))",
+        |_, locals| {
+            assert_eq!(get(locals, "mu1"), new_p1.mu());
+            assert_eq!(get(locals, "sigma1"), new_p1.sigma());
+            assert_eq!(get(locals, "ordinal1"), new_p1.mu() - 3.0 * new_p1.sigma());
+            assert_eq!(get(locals, "mu2"), new_p2.mu());
+            assert_eq!(get(locals, "sigma2"), new_p2.sigma());
+            assert_eq!(
+                get(locals, "probability"),
+                Rater::default().win_probability(&p1, &p2)
+            );
+        },
+    );
+}
+
+#[test]
+fn update_ratings_matches_rust() {
+    let expected = Rater::new(3.0)
+        .update_ratings(
+            vec![
+                vec![Rating::new(25.0, 8.0), Rating::new(30.0, 3.0)],
+                vec![Rating::new(20.0, 2.0)],
+                vec![Rating::new(28.0, 5.0)],
+            ],
+            vec![2, 1, 2],
+        )
+        .unwrap();
+
+    run(
+        "teams = bbt.Rater(3.0).update_ratings(\n\
+             [[bbt.Rating(25, 8), bbt.Rating(30, 3)], [bbt.Rating(20, 2)], [bbt.Rating(28, 5)]],\n\
+             [2, 1, 2])\n\
+         mus = [[p.mu for p in team] for team in teams]\n\
+         sigmas = [[p.sigma for p in team] for team in teams]",
+        |_, locals| {
+            let mus: Vec<Vec<f64>> = locals.get_item("mus").unwrap().unwrap().extract().unwrap();
+            let sigmas: Vec<Vec<f64>> = locals
+                .get_item("sigmas")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+
+            for (t, team) in expected.iter().enumerate() {
+                for (p, rating) in team.iter().enumerate() {
+                    assert_eq!(mus[t][p], rating.mu());
+                    assert_eq!(sigmas[t][p], rating.sigma());
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn errors_are_value_errors() {
+    run(
+        "rater = bbt.Rater()\n\
+         try:\n    \
+             rater.update_ratings([[bbt.Rating()], []], [1, 2])\n    \
+             message = None\n\
+         except ValueError as error:\n    \
+             message = str(error)",
+        |_, locals| {
+            let message: String = locals
+                .get_item("message")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(message, BBTError::EmptyTeam { index: 1 }.to_string());
+        },
+    );
+
+    run("", |py, _| {
+        let error = py
+            .run(
+                &CString::new("import bbt\nbbt.Rater(-1.0)").unwrap(),
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert!(error.is_instance_of::<PyValueError>(py));
+
+        let error = py
+            .run(
+                &CString::new("import bbt\nbbt.Rater().duel(bbt.Rating(), bbt.Rating(), 'tie')")
+                    .unwrap(),
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert!(error.is_instance_of::<PyValueError>(py));
+    });
+}
+
+#[test]
+fn pickling_round_trips() {
+    run(
+        "import pickle\n\
+         rating = pickle.loads(pickle.dumps(bbt.Rating(31.5, 2.25)))\n\
+         rater = pickle.loads(pickle.dumps(bbt.Rater(3.5)))\n\
+         mu, sigma, beta = rating.mu, rating.sigma, rater.beta\n\
+         same = rating == bbt.Rating(31.5, 2.25)\n\
+         updated, _ = bbt.Rater().duel(bbt.Rating(), bbt.Rating(), 'win')\n\
+         same = same and pickle.loads(pickle.dumps(updated)) == updated",
+        |_, locals| {
+            assert_eq!(get(locals, "mu"), 31.5);
+            assert_eq!(get(locals, "sigma"), 2.25);
+            assert_eq!(get(locals, "beta"), 3.5);
+
+            let same: bool = locals.get_item("same").unwrap().unwrap().extract().unwrap();
+            assert!(same);
+        },
+    );
+}
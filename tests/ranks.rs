@@ -0,0 +1,109 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::ranks::{self, AggregationMethod};
+use bbt::{BBTError, Rater, Rating};
+
+const METHODS: [AggregationMethod; 2] = [AggregationMethod::Borda, AggregationMethod::MedianRank];
+
+fn teams() -> Vec<Vec<Rating>> {
+    vec![
+        vec![Rating::new(27.0, 3.0)],
+        vec![Rating::new(25.0, 4.0), Rating::new(23.0, 6.0)],
+        vec![Rating::new(24.0, 5.0)],
+        vec![Rating::new(22.0, 2.0)],
+    ]
+}
+
+#[test]
+fn unanimous_judges_agree_with_a_single_judge() {
+    let rater = Rater::default();
+    let ranking: &[usize] = &[2, 1, 3, 3];
+    let single = rater.update_ratings(teams(), ranking.to_vec()).unwrap();
+
+    for &method in &METHODS {
+        assert_eq!(ranks::aggregate(&[ranking], method).unwrap(), ranking);
+        assert_eq!(ranks::aggregate(&[ranking; 5], method).unwrap(), ranking);
+
+        let judged = rater
+            .update_ratings_judged(teams(), &[ranking; 3], method)
+            .unwrap();
+        assert_eq!(judged, single);
+    }
+
+    // Ties numbered differently by different judges are still the same ties.
+    assert_eq!(
+        ranks::aggregate(
+            &[&[2, 1, 3, 3], &[2, 1, 4, 4]],
+            AggregationMethod::MedianRank
+        )
+        .unwrap(),
+        vec![2, 1, 3, 3]
+    );
+}
+
+#[test]
+fn disagreements_match_the_hand_computed_aggregates() {
+    let judges: [&[usize]; 3] = [&[1, 2, 3, 4], &[2, 1, 1, 4], &[4, 3, 2, 1]];
+
+    // Borda counts of 4, 5.5, 5.5 and 3 points.
+    assert_eq!(
+        ranks::aggregate(&judges, AggregationMethod::Borda).unwrap(),
+        vec![3, 1, 1, 4]
+    );
+
+    // The second judge's ranking counts as 3, 1, 1, 4, which gives median
+    // ranks of 3, 2, 2 and 4.
+    assert_eq!(
+        ranks::aggregate(&judges, AggregationMethod::MedianRank).unwrap(),
+        vec![3, 1, 1, 4]
+    );
+
+    // With an even number of judges, the median is the mean of the middle
+    // ranks: 1.5, 2.5, 2 and 4.
+    let judges: [&[usize]; 2] = [&[1, 2, 3, 4], &[2, 3, 1, 4]];
+    assert_eq!(
+        ranks::aggregate(&judges, AggregationMethod::MedianRank).unwrap(),
+        vec![1, 3, 2, 4]
+    );
+
+    let rater = Rater::default();
+    assert_eq!(
+        rater
+            .update_ratings_judged(teams(), &judges, AggregationMethod::MedianRank)
+            .unwrap(),
+        rater.update_ratings(teams(), vec![1, 3, 2, 4]).unwrap()
+    );
+}
+
+#[test]
+fn mismatched_rankings_are_rejected() {
+    let rater = Rater::default();
+    let judges: [&[usize]; 2] = [&[1, 2, 3, 4], &[1, 2, 3]];
+
+    for &method in &METHODS {
+        assert_eq!(
+            ranks::aggregate(&judges, method),
+            Err(BBTError::InvalidParameter {
+                name: "judge_rankings"
+            })
+        );
+        assert_eq!(
+            ranks::aggregate(&[], method),
+            Err(BBTError::InvalidParameter {
+                name: "judge_rankings"
+            })
+        );
+
+        assert_eq!(
+            rater.update_ratings_judged(teams(), &judges, method),
+            Err(BBTError::MismatchedLengths { teams: 4, ranks: 3 })
+        );
+        assert_eq!(
+            rater.update_ratings_judged(teams(), &[], method),
+            Err(BBTError::InvalidParameter {
+                name: "judge_rankings"
+            })
+        );
+    }
+}
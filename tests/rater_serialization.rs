@@ -0,0 +1,79 @@
+#![cfg(feature = "serde")]
+extern crate bbt;
+extern crate bincode;
+extern crate serde;
+extern crate serde_json;
+
+use bbt::{Outcome, Rater, Rating};
+
+/// Two raters compute identically if and only if their β² is the same, so the
+/// reconstructed β² is compared through the results of a duel.
+fn assert_same_beta_sq(actual: &Rater, expected: &Rater) {
+    let p1 = Rating::new(27.0, 5.0);
+    let p2 = Rating::new(23.0, 7.0);
+
+    let (a1, a2) = actual.duel(p1, p2, Outcome::Win);
+    let (e1, e2) = expected.duel(p1, p2, Outcome::Win);
+    assert_eq!(a1.mu().to_bits(), e1.mu().to_bits());
+    assert_eq!(a2.sigma().to_bits(), e2.sigma().to_bits());
+
+    let actual = actual.win_probability(&p1, &p2);
+    let expected = expected.win_probability(&p1, &p2);
+    assert_eq!(actual.to_bits(), expected.to_bits());
+}
+
+#[test]
+fn end_to_end() {
+    for &beta in [25.0f64 / 6.0, 4.0, 0.0, 1500.0 / 6.0, 0.1].iter() {
+        let original = Rater::new(beta);
+
+        let serialized = serde_json::to_string(&original).unwrap();
+        let expected = format!("{{\"beta\":{}}}", serde_json::to_string(&beta).unwrap());
+        assert_eq!(serialized, expected);
+
+        let deserialized: Rater = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.beta().to_bits(), beta.to_bits());
+        assert_same_beta_sq(&deserialized, &original);
+    }
+}
+
+#[test]
+fn legacy_beta_sq() {
+    let beta: f64 = 25.0 / 6.0;
+    let legacy = format!("{{\"beta_sq\":{}}}", beta * beta);
+
+    let deserialized: Rater = serde_json::from_str(&legacy).unwrap();
+    assert_same_beta_sq(&deserialized, &Rater::new(beta));
+
+    // β² is kept as stored, even if it is not the square of a float.
+    let deserialized: Rater = serde_json::from_str("{\"beta_sq\":17.36}").unwrap();
+    assert_eq!(deserialized.beta(), 17.36f64.sqrt());
+
+    let both = format!("{{\"beta\":{},\"beta_sq\":{}}}", beta, beta * beta);
+    let deserialized: Rater = serde_json::from_str(&both).unwrap();
+    assert_same_beta_sq(&deserialized, &Rater::new(beta));
+}
+
+#[test]
+fn conflicting_fields_are_rejected() {
+    let result: Result<Rater, _> = serde_json::from_str("{\"beta\":4.0,\"beta_sq\":17.36}");
+    let error = result.err().unwrap();
+    assert!(error.to_string().contains("do not agree"), "{}", error);
+
+    let result: Result<Rater, _> = serde_json::from_str("{\"beta_sq\":-1.0}");
+    assert!(result.is_err());
+
+    let result: Result<Rater, _> = serde_json::from_str("{}");
+    assert!(result.is_err());
+}
+
+#[test]
+fn binary_round_trip() {
+    let original = Rater::new(25.0 / 6.0);
+
+    let bytes = bincode::serialize(&original).unwrap();
+    assert_eq!(bytes, (25.0f64 / 6.0).to_le_bytes().to_vec());
+
+    let deserialized: Rater = bincode::deserialize(&bytes).unwrap();
+    assert_same_beta_sq(&deserialized, &original);
+}
@@ -1,10 +1,15 @@
 #![cfg(feature = "serde")]
 extern crate bbt;
+extern crate bincode;
 /// Using a system test for serialization feature, as `serde_json` should not be included as a crate for builds, only for testing.
 extern crate serde;
+#[macro_use]
+extern crate serde_derive;
 extern crate serde_json;
 
 use bbt::Rating;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::Deserialize;
 
 #[test]
 fn end_to_end() {
@@ -17,3 +22,316 @@ fn end_to_end() {
 
     assert_eq!(original, deserialized);
 }
+
+#[test]
+fn end_to_end_f32() {
+    let original = Rating::new(30.0f32, 2.5);
+
+    let serialized = serde_json::to_string(&original)
+        .unwrap_or_else(|_| panic!("Failed to serialize {:?}", original));
+    let deserialized: Rating<f32> = serde_json::from_str(&serialized)
+        .unwrap_or_else(|_| panic!("Failed to deserialize {}", &serialized));
+
+    assert_eq!(original, deserialized);
+}
+
+fn from_map(mu: f64, sigma: f64) -> Result<Rating, serde::de::value::Error> {
+    let map = vec![("mu", mu), ("sigma", sigma)];
+    Rating::deserialize(MapDeserializer::new(map.into_iter()))
+}
+
+fn from_seq(mu: f64, sigma: f64) -> Result<Rating, serde::de::value::Error> {
+    Rating::deserialize(SeqDeserializer::new(vec![mu, sigma].into_iter()))
+}
+
+#[test]
+fn invalid_ratings_are_rejected() {
+    // JSON cannot express NaN or infinity, so these go through serde's own
+    // value deserializers, once as a map and once as a sequence.
+    let invalid = [
+        (f64::NAN, 3.0, "invalid mu NaN"),
+        (f64::INFINITY, 3.0, "invalid mu inf"),
+        (25.0, f64::NAN, "invalid sigma NaN"),
+        (25.0, f64::NEG_INFINITY, "invalid sigma -inf"),
+        (25.0, -3.0, "invalid sigma -3"),
+        (25.0, 0.0, "invalid sigma 0"),
+    ];
+
+    for &(mu, sigma, message) in invalid.iter() {
+        let error = from_map(mu, sigma).unwrap_err().to_string();
+        assert!(error.contains(message), "{}", error);
+
+        let error = from_seq(mu, sigma).unwrap_err().to_string();
+        assert!(error.contains(message), "{}", error);
+    }
+
+    let error = serde_json::from_str::<Rating>(r#"{"mu":25.0,"sigma":-3.0}"#)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("invalid sigma -3"), "{}", error);
+
+    assert_eq!(from_map(25.0, 3.0).unwrap(), Rating::new(25.0, 3.0));
+    assert_eq!(from_seq(25.0, 3.0).unwrap(), Rating::new(25.0, 3.0));
+    assert_eq!(
+        serde_json::from_str::<Rating>(r#"{"mu":-5.0,"sigma":0.5}"#).unwrap(),
+        Rating::new(-5.0, 0.5)
+    );
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    #[serde(with = "bbt::serde_unchecked")]
+    rating: Rating,
+}
+
+#[test]
+fn unchecked_ratings_are_accepted() {
+    let map = vec![("mu", f64::NAN), ("sigma", -1.0)];
+    let rating: Rating = bbt::serde_unchecked::deserialize(MapDeserializer::<
+        _,
+        serde::de::value::Error,
+    >::new(map.into_iter()))
+    .unwrap();
+    assert!(rating.mu().is_nan());
+    assert_eq!(rating.sigma(), -1.0);
+
+    let entry: Entry = serde_json::from_str(r#"{"rating":{"mu":25.0,"sigma":0.0}}"#).unwrap();
+    assert_eq!(entry.rating, Rating::new(25.0, 0.0));
+    assert_eq!(
+        serde_json::to_string(&entry).unwrap(),
+        r#"{"rating":{"mu":25.0,"sigma":0.0}}"#
+    );
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyEntry {
+    #[serde(with = "bbt::serde_default_sigma")]
+    rating: Rating,
+}
+
+fn custom_sigma<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Rating, D::Error> {
+    bbt::serde_default_sigma::deserialize_with_sigma(deserializer, 2.5)
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomEntry {
+    #[serde(deserialize_with = "custom_sigma")]
+    rating: Rating,
+}
+
+#[test]
+fn missing_sigma_uses_default() {
+    let legacy: LegacyEntry = serde_json::from_str(r#"{"rating":{"mu":30.0}}"#).unwrap();
+    assert_eq!(legacy.rating, Rating::new(30.0, 25.0 / 3.0));
+
+    let custom: CustomEntry = serde_json::from_str(r#"{"rating":{"mu":30.0}}"#).unwrap();
+    assert_eq!(custom.rating, Rating::new(30.0, 2.5));
+
+    let custom: CustomEntry = serde_json::from_str(r#"{"rating":[30.0]}"#).unwrap();
+    assert_eq!(custom.rating, Rating::new(30.0, 2.5));
+
+    // Modern documents keep their sigma.
+    let modern = r#"{"rating":{"mu":30.0,"sigma":4.0}}"#;
+    let legacy: LegacyEntry = serde_json::from_str(modern).unwrap();
+    assert_eq!(legacy.rating, Rating::new(30.0, 4.0));
+    let custom: CustomEntry = serde_json::from_str(modern).unwrap();
+    assert_eq!(custom.rating, Rating::new(30.0, 4.0));
+
+    // A missing mu is still an error, and so is a missing sigma without a
+    // default.
+    let error = serde_json::from_str::<LegacyEntry>(r#"{"rating":{"sigma":4.0}}"#)
+        .err()
+        .unwrap();
+    assert!(
+        error.to_string().contains("missing field `mu`"),
+        "{}",
+        error
+    );
+
+    let error = serde_json::from_str::<Rating>(r#"{"mu":30.0}"#)
+        .err()
+        .unwrap();
+    assert!(
+        error.to_string().contains("missing field `sigma`"),
+        "{}",
+        error
+    );
+
+    // Substituted ratings are validated like any other.
+    let zero_sigma =
+        |deserializer| bbt::serde_default_sigma::deserialize_with_sigma(deserializer, 0.0);
+    let map = vec![("mu", 30.0)];
+    let result: Result<Rating, serde::de::value::Error> =
+        zero_sigma(MapDeserializer::new(map.into_iter()));
+    assert!(result.is_err());
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CompactEntry {
+    #[serde(with = "bbt::serde_compact")]
+    rating: Rating,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PlainEntry {
+    rating: Rating,
+}
+
+#[test]
+fn compact_form_round_trips() {
+    let rating = Rating::new(25.0, 8.5);
+    let compact = CompactEntry { rating };
+    let plain = PlainEntry { rating };
+
+    // The default impl keeps emitting the struct form.
+    let compact_json = serde_json::to_string(&compact).unwrap();
+    let plain_json = serde_json::to_string(&plain).unwrap();
+    assert_eq!(compact_json, r#"{"rating":[25.0,8.5]}"#);
+    assert_eq!(plain_json, r#"{"rating":{"mu":25.0,"sigma":8.5}}"#);
+    assert!(compact_json.len() < plain_json.len());
+
+    // Both forms deserialize through either field type.
+    for json in [&compact_json, &plain_json].iter() {
+        assert_eq!(serde_json::from_str::<CompactEntry>(json).unwrap(), compact);
+        assert_eq!(serde_json::from_str::<PlainEntry>(json).unwrap(), plain);
+    }
+    assert_eq!(
+        serde_json::from_str::<Rating>("[25.0,8.5]").unwrap(),
+        rating
+    );
+
+    let compact_bytes = bincode::serialize(&compact).unwrap();
+    let plain_bytes = bincode::serialize(&plain).unwrap();
+    assert_eq!(compact_bytes.len(), 16);
+    assert_eq!(
+        bincode::deserialize::<CompactEntry>(&compact_bytes).unwrap(),
+        compact
+    );
+    assert_eq!(
+        bincode::deserialize::<PlainEntry>(&compact_bytes).unwrap(),
+        plain
+    );
+    assert_eq!(
+        bincode::deserialize::<CompactEntry>(&plain_bytes).unwrap(),
+        compact
+    );
+}
+
+#[test]
+fn binary_formats_use_tuples() {
+    let rating = Rating::new(27.5, 3.25);
+
+    let bytes = bincode::serialize(&rating).unwrap();
+    let mut expected = 27.5f64.to_le_bytes().to_vec();
+    expected.extend_from_slice(&3.25f64.to_le_bytes());
+    assert_eq!(bytes, expected);
+    assert_eq!(bincode::deserialize::<Rating>(&bytes).unwrap(), rating);
+
+    let ratings = vec![rating, Rating::default()];
+    let bytes = bincode::serialize(&ratings).unwrap();
+    assert_eq!(
+        bincode::deserialize::<Vec<Rating>>(&bytes).unwrap(),
+        ratings
+    );
+
+    let bytes = bincode::serialize(&Rating::new(25.0, -1.0)).unwrap();
+    assert!(bincode::deserialize::<Rating>(&bytes).is_err());
+}
+
+#[test]
+fn json_from_earlier_versions_still_loads() {
+    // These documents were produced by the struct-only `Serialize` impl.
+    let documents = [
+        (
+            r#"{"mu":25.0,"sigma":8.333333333333334}"#,
+            Rating::default(),
+        ),
+        (r#"{"sigma":2.0,"mu":-4.5}"#, Rating::new(-4.5, 2.0)),
+        (r#"[30.0,1.5]"#, Rating::new(30.0, 1.5)),
+    ];
+
+    for &(json, ref expected) in documents.iter() {
+        assert_eq!(&serde_json::from_str::<Rating>(json).unwrap(), expected);
+    }
+
+    let json = serde_json::to_string(&Rating::<f64>::default()).unwrap();
+    assert_eq!(json, documents[0].0);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PublicEntry {
+    #[serde(with = "bbt::serde_scalar::conservative")]
+    public: Rating,
+    #[serde(with = "bbt::serde_scalar::mu_only")]
+    skill: Rating,
+    internal: Rating,
+}
+
+fn public_sigma<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Rating, D::Error> {
+    bbt::serde_scalar::mu_only::deserialize_with_sigma(deserializer, 1.5)
+}
+
+#[derive(Debug, Deserialize)]
+struct SkillEntry {
+    #[serde(deserialize_with = "public_sigma")]
+    skill: Rating,
+    internal: Option<Rating>,
+}
+
+#[test]
+fn scalar_forms_are_flat_numbers() {
+    let rating = Rating::new(30.0, 2.5);
+    let entry = PublicEntry {
+        public: rating,
+        skill: rating,
+        internal: rating,
+    };
+
+    assert_eq!(
+        serde_json::to_string(&entry).unwrap(),
+        r#"{"public":22.5,"skill":30.0,"internal":{"mu":30.0,"sigma":2.5}}"#
+    );
+
+    let f32_rating = Rating::new(30.0f32, 2.5);
+    let value =
+        bbt::serde_scalar::conservative::serialize(&f32_rating, serde_json::value::Serializer)
+            .unwrap();
+    assert_eq!(value, serde_json::json!(22.5));
+}
+
+#[test]
+fn scalar_forms_deserialize_as_documented() {
+    // Conservative estimates cannot be turned back into ratings.
+    let error = serde_json::from_str::<PublicEntry>(
+        r#"{"public":22.5,"skill":30.0,"internal":{"mu":30.0,"sigma":2.5}}"#,
+    )
+    .err()
+    .unwrap();
+    assert!(
+        error.to_string().contains("conservative estimate"),
+        "{}",
+        error
+    );
+
+    // Mus are paired with the default sigma, or the one of the caller.
+    let value = serde_json::json!(31.0);
+    let skill: Rating = bbt::serde_scalar::mu_only::deserialize(value).unwrap();
+    assert_eq!(skill, Rating::new(31.0, 25.0 / 3.0));
+
+    let entry: SkillEntry = serde_json::from_str(r#"{"skill":31.0}"#).unwrap();
+    assert_eq!(entry.skill, Rating::new(31.0, 1.5));
+
+    // Sibling fields are read in full.
+    let entry: SkillEntry =
+        serde_json::from_str(r#"{"skill":31.0,"internal":{"mu":31.0,"sigma":4.0}}"#).unwrap();
+    assert_eq!(entry.skill, Rating::new(31.0, 1.5));
+    assert_eq!(entry.internal, Some(Rating::new(31.0, 4.0)));
+
+    // Mus must be finite numbers.
+    assert!(serde_json::from_str::<SkillEntry>(r#"{"skill":"31.0"}"#).is_err());
+    let infinite =
+        bbt::serde_scalar::mu_only::deserialize::<f64, _>(serde::de::value::F64Deserializer::<
+            serde::de::value::Error,
+        >::new(f64::INFINITY));
+    assert!(infinite.is_err());
+}
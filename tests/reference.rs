@@ -0,0 +1,100 @@
+//! Checks `Rater::update_ratings` against the transcription of Algorithm 1
+//! in `bbt::reference` on random matches.
+
+#![cfg(all(feature = "reference", not(target_arch = "wasm32")))]
+extern crate bbt;
+extern crate rand;
+
+use bbt::reference::reference_update;
+use bbt::{Rater, Rating};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+const FIXTURES: usize = 5_000;
+
+/// Returns a random rating. Sigma is drawn on a log scale, so that the
+/// fixtures mix certain and uncertain players.
+fn rating(rng: &mut SmallRng) -> Rating {
+    let sigma = rng.gen_range((0.05f64).ln()..(20.0f64).ln()).exp();
+
+    Rating::new(rng.gen_range(0.0..50.0), sigma)
+}
+
+/// Returns a random match of two to eight teams of one to four players, with
+/// ties.
+fn fixture(rng: &mut SmallRng) -> (f64, Vec<Vec<Rating>>, Vec<usize>) {
+    let beta = rng.gen_range(0.5..10.0);
+    let n_teams = rng.gen_range(2..=8);
+
+    let teams = (0..n_teams)
+        .map(|_| (0..rng.gen_range(1..=4)).map(|_| rating(rng)).collect())
+        .collect();
+    let ranks = (0..n_teams).map(|_| rng.gen_range(1..=n_teams)).collect();
+
+    (beta, teams, ranks)
+}
+
+fn assert_close(optimized: f64, reference: f64, what: &str) {
+    let tolerance = 1e-12 * reference.abs().max(1.0);
+
+    assert!(
+        (optimized - reference).abs() <= tolerance,
+        "{}: {} != {}",
+        what,
+        optimized,
+        reference
+    );
+}
+
+#[test]
+fn optimized_update_matches_the_reference() {
+    let mut rng = SmallRng::seed_from_u64(687);
+    let mut floored = 0;
+
+    for _ in 0..FIXTURES {
+        let (beta, teams, ranks) = fixture(&mut rng);
+
+        let reference = reference_update(beta, &teams, &ranks);
+        let (optimized, report) = Rater::new(beta)
+            .update_ratings_with_report(teams, ranks)
+            .unwrap();
+
+        for (team_idx, (optimized, reference)) in optimized.iter().zip(&reference).enumerate() {
+            for (player_idx, (optimized, reference)) in optimized.iter().zip(reference).enumerate()
+            {
+                assert_close(optimized.mu(), reference.mu(), "mu");
+
+                // The single known divergence: the sigma floor.
+                if report.clamped_players.contains(&(team_idx, player_idx)) {
+                    floored += 1;
+                    // The reference's variance is smaller, or negative.
+                    let sigma = reference.sigma();
+                    assert!(sigma.is_nan() || sigma < optimized.sigma());
+                } else {
+                    assert_close(optimized.sigma(), reference.sigma(), "sigma");
+                }
+            }
+        }
+    }
+
+    // The fixtures should exercise the floor, but rarely.
+    assert!(floored > 0 && floored < FIXTURES / 10, "{}", floored);
+}
+
+#[test]
+fn duels_match_the_reference() {
+    let mut rng = SmallRng::seed_from_u64(6870);
+
+    for _ in 0..FIXTURES {
+        let beta = rng.gen_range(0.5..10.0);
+        let (p1, p2) = (rating(&mut rng), rating(&mut rng));
+
+        let reference = reference_update(beta, &[vec![p1], vec![p2]], &[1, 2]);
+        let (new_p1, new_p2) = Rater::new(beta).duel(p1, p2, bbt::Outcome::Win);
+
+        for (optimized, reference) in [(&new_p1, &reference[0][0]), (&new_p2, &reference[1][0])] {
+            assert_close(optimized.mu(), reference.mu(), "mu");
+            assert_close(optimized.sigma(), reference.sigma(), "sigma");
+        }
+    }
+}
@@ -0,0 +1,407 @@
+#![cfg(all(feature = "serde", feature = "std"))]
+extern crate bbt;
+extern crate serde_json;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::Duration;
+
+use bbt::replay::{
+    from_jsonl, replay, replay_into, replay_with_options, GameRecord, ReplayError, ReplayOptions,
+};
+use bbt::{BBTError, Rater, Rating};
+
+const LOG: &str = r#"{"teams": [["alice", "bob"], ["carol"]], "ranks": [1, 2], "ts": 1534000000}
+
+{"teams": [["carol"], ["alice"], ["dave"]], "ranks": [1, 2, 2], "ts": 1534000060}
+{"teams": [["bob"], ["dave"]], "ranks": [2, 1]}
+"#;
+
+fn assert_close(actual: &Rating, mu: f64, sigma: f64) {
+    assert!((actual.mu() - mu).abs() < 1e-9, "{:?}", actual);
+    assert!((actual.sigma() - sigma).abs() < 1e-9, "{:?}", actual);
+}
+
+#[test]
+fn replays_a_log() {
+    let rater = Rater::default();
+    let ratings = from_jsonl(&rater, LOG.as_bytes(), Rating::default()).unwrap();
+
+    // The same games, applied by hand.
+    let new = Rating::default;
+    let game1 = rater
+        .update_ratings(vec![vec![new(), new()], vec![new()]], vec![1, 2])
+        .unwrap();
+    let (alice, bob, carol) = (&game1[0][0], &game1[0][1], &game1[1][0]);
+    let game2 = rater
+        .update_ratings(vec![vec![*carol], vec![*alice], vec![new()]], vec![1, 2, 2])
+        .unwrap();
+    let (carol, alice, dave) = (&game2[0][0], &game2[1][0], &game2[2][0]);
+    let game3 = rater
+        .update_ratings(vec![vec![*bob], vec![*dave]], vec![2, 1])
+        .unwrap();
+    let (bob, dave) = (&game3[0][0], &game3[1][0]);
+
+    assert_eq!(ratings.len(), 4);
+    assert_eq!(&ratings["alice"], alice);
+    assert_eq!(&ratings["bob"], bob);
+    assert_eq!(&ratings["carol"], carol);
+    assert_eq!(&ratings["dave"], dave);
+
+    assert_close(
+        &ratings["alice"],
+        22.942_658_524_047_864,
+        7.683_041_455_983_094,
+    );
+    assert_close(
+        &ratings["dave"],
+        25.041_010_944_225_363,
+        7.559_990_621_953_356,
+    );
+}
+
+#[test]
+fn crlf_and_missing_trailing_newline() {
+    let rater = Rater::default();
+    let expected = from_jsonl(&rater, LOG.as_bytes(), Rating::default()).unwrap();
+
+    let crlf = LOG.trim_end().replace('\n', "\r\n");
+    let ratings = from_jsonl(&rater, crlf.as_bytes(), Rating::default()).unwrap();
+
+    assert_eq!(ratings, expected);
+}
+
+#[test]
+fn new_players_start_with_the_initial_rating() {
+    let log = r#"{"teams": [["alice"], ["bob"]], "ranks": [1, 1]}"#;
+    let initial = Rating::new(1500.0, 350.0);
+    let ratings = from_jsonl(&Rater::new(200.0), log.as_bytes(), initial).unwrap();
+
+    let expected = Rater::new(200.0)
+        .update_ratings(vec![vec![initial], vec![initial]], vec![1, 1])
+        .unwrap();
+    assert_eq!(ratings["alice"], expected[0][0]);
+    assert_eq!(ratings["bob"], expected[1][0]);
+
+    let ratings = from_jsonl(&Rater::default(), "\n  \n".as_bytes(), Rating::default()).unwrap();
+    assert!(ratings.is_empty());
+}
+
+#[test]
+fn errors_name_the_line() {
+    let replay = |log: &str| from_jsonl(&Rater::default(), log.as_bytes(), Rating::default());
+    let valid = r#"{"teams": [["alice"], ["bob"]], "ranks": [1, 2]}"#;
+
+    let error = replay(&format!("{}\n\n{{\"teams\": [", valid)).unwrap_err();
+    assert!(
+        matches!(error, ReplayError::Malformed { line: 3, .. }),
+        "{}",
+        error
+    );
+    assert!(
+        error.to_string().starts_with("Malformed JSON on line 3: "),
+        "{}",
+        error
+    );
+    assert!(!error.to_string().contains("line 1"), "{}", error);
+
+    for (game, message) in [
+        (r#"[1, 2]"#, "expected an object"),
+        (
+            r#"{"teams": [["alice"], ["bob"]]}"#,
+            "expected `ranks` to be an array",
+        ),
+        (r#"{"ranks": [1, 2]}"#, "expected `teams` to be an array"),
+        (
+            r#"{"teams": [["alice"], [7]], "ranks": [1, 2]}"#,
+            "expected a player id, found 7",
+        ),
+        (
+            r#"{"teams": [["alice"], ["bob"]], "ranks": [1, -2]}"#,
+            "expected a rank, found -2",
+        ),
+        (
+            r#"{"teams": [["alice"], ["alice"]], "ranks": [1, 2]}"#,
+            "player `alice` appears more than once",
+        ),
+    ] {
+        match replay(&format!("{}\n{}\n{}", valid, valid, game)) {
+            Err(ReplayError::InvalidRecord {
+                line: 3,
+                message: actual,
+            }) => {
+                assert_eq!(actual, message)
+            }
+            other => panic!("expected an invalid record on line 3, got {:?}", other),
+        }
+    }
+
+    let error = replay(&format!(
+        "{}\n{}",
+        valid, r#"{"teams": [["alice"], []], "ranks": [1, 2]}"#
+    ))
+    .unwrap_err();
+    assert_eq!(error.line(), Some(2));
+    match error {
+        ReplayError::Rating { error, .. } => assert_eq!(error, BBTError::EmptyTeam { index: 1 }),
+        other => panic!("expected a rejected game, got {:?}", other),
+    }
+    let error = replay(r#"{"teams": [["alice"], ["bob"]], "ranks": [1]}"#).unwrap_err();
+    assert!(error.source().is_some());
+}
+
+fn history() -> Vec<GameRecord> {
+    let team = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+    vec![
+        GameRecord::new(vec![team(&["alice", "bob"]), team(&["carol"])], vec![1, 2]),
+        GameRecord {
+            timestamp: Some(1_534_000_060),
+            ..GameRecord::new(
+                vec![team(&["carol"]), team(&["alice"]), team(&["dave"])],
+                vec![1, 2, 2],
+            )
+        },
+        GameRecord {
+            weight: Some(0.5),
+            ..GameRecord::new(vec![team(&["bob"]), team(&["dave"])], vec![2, 1])
+        },
+    ]
+}
+
+#[test]
+fn replays_records() {
+    let rater = Rater::default();
+    let ratings = replay(&rater, &history(), Rating::default()).unwrap();
+
+    // Without the weight of the last record, this is the log above.
+    let mut unweighted = history();
+    unweighted[2].weight = None;
+    let expected = from_jsonl(&rater, LOG.as_bytes(), Rating::default()).unwrap();
+    assert_eq!(
+        replay(&rater, &unweighted, Rating::default()).unwrap(),
+        expected
+    );
+
+    assert_eq!(ratings.len(), 4);
+    assert_eq!(ratings["alice"], expected["alice"]);
+    assert_eq!(ratings["carol"], expected["carol"]);
+    assert_close(
+        &ratings["bob"],
+        24.250_944_355_022_497,
+        8.071_430_367_617_534,
+    );
+    assert_close(
+        &ratings["dave"],
+        23.697_376_421_677_845,
+        7.670_845_084_913_057,
+    );
+}
+
+#[test]
+fn beta_changes_the_replay() {
+    let history = history();
+    let default = replay(&Rater::default(), &history, Rating::default()).unwrap();
+    let noisy = replay(&Rater::new(10.0), &history, Rating::default()).unwrap();
+
+    for player in ["alice", "bob", "carol", "dave"] {
+        assert_ne!(default[player].mu(), noisy[player].mu(), "{}", player);
+    }
+    assert_eq!(
+        replay(&Rater::new(10.0), &history, Rating::default()).unwrap(),
+        noisy
+    );
+}
+
+#[test]
+fn failing_records_are_named_and_not_applied() {
+    let rater = Rater::default();
+    let mut history = history();
+    history.insert(
+        2,
+        GameRecord::new(
+            vec![vec!["erin".to_string()], vec!["erin".to_string()]],
+            vec![1, 2],
+        ),
+    );
+
+    let error = replay(&rater, &history, Rating::default()).unwrap_err();
+    assert_eq!(error.index(), Some(2));
+    assert_eq!(error.line(), None);
+    assert!(
+        matches!(error, ReplayError::InvalidGame { index: 2, .. }),
+        "{}",
+        error
+    );
+
+    // The first two records were applied, and nothing of the third.
+    let mut ratings = HashMap::new();
+    assert!(replay_into(&rater, &history, Rating::default(), &mut ratings).is_err());
+    assert_eq!(
+        ratings,
+        replay(&rater, &history[..2], Rating::default()).unwrap()
+    );
+    assert!(!ratings.contains_key("erin"));
+
+    history[2] = GameRecord {
+        weight: Some(2.0),
+        ..GameRecord::new(
+            vec![vec!["erin".to_string()], vec!["bob".to_string()]],
+            vec![1, 2],
+        )
+    };
+    match replay(&rater, &history, Rating::default()) {
+        Err(ReplayError::RejectedGame { index: 2, error }) => {
+            assert_eq!(error, BBTError::InvalidParameter { name: "weight" })
+        }
+        other => panic!("expected a rejected record, got {:?}", other),
+    }
+
+    history[2] = GameRecord::new(vec![vec!["erin".to_string()], vec![]], vec![1, 2]);
+    let mut ratings = HashMap::new();
+    match replay_into(&rater, &history, Rating::default(), &mut ratings) {
+        Err(ReplayError::RejectedGame { index: 2, error }) => {
+            assert_eq!(error, BBTError::EmptyTeam { index: 1 })
+        }
+        other => panic!("expected a rejected record, got {:?}", other),
+    }
+    assert!(!ratings.contains_key("erin"));
+}
+
+const DAY: i64 = 86_400;
+
+/// Three games, a day apart.
+fn timed_history() -> Vec<GameRecord> {
+    let duel = |a: &str, b: &str, day: i64| GameRecord {
+        timestamp: Some(1_534_000_000 + day * DAY),
+        ..GameRecord::new(vec![vec![a.to_string()], vec![b.to_string()]], vec![1, 2])
+    };
+
+    vec![
+        duel("alice", "bob", 0),
+        duel("bob", "carol", 1),
+        duel("carol", "alice", 2),
+    ]
+}
+
+#[test]
+fn an_infinite_half_life_is_the_unweighted_replay() {
+    let rater = Rater::default();
+    let options = ReplayOptions::default();
+
+    assert_eq!(options.half_life, Duration::MAX);
+    assert_eq!(
+        replay_with_options(&rater, &timed_history(), Rating::default(), &options).unwrap(),
+        replay(&rater, &timed_history(), Rating::default()).unwrap()
+    );
+}
+
+#[test]
+fn older_games_count_less() {
+    let rater = Rater::default();
+    let new = Rating::default;
+    let duel = |a: Rating, b: Rating, weight: f64| {
+        let mut teams = rater
+            .update_ratings_weighted(vec![vec![a], vec![b]], vec![1, 2], weight)
+            .unwrap()
+            .into_iter();
+        (
+            teams.next().unwrap().remove(0),
+            teams.next().unwrap().remove(0),
+        )
+    };
+
+    // With a half-life of a day, the games weigh 1/4, 1/2 and 1.
+    let options = ReplayOptions {
+        half_life: Duration::from_secs(DAY as u64),
+        ..ReplayOptions::default()
+    };
+    let ratings = replay_with_options(&rater, &timed_history(), new(), &options).unwrap();
+
+    let (alice, bob) = duel(new(), new(), 0.25);
+    let (bob, carol) = duel(bob, new(), 0.5);
+    let (carol, alice) = duel(carol, alice, 1.0);
+    assert_close(&ratings["alice"], alice.mu(), alice.sigma());
+    assert_close(&ratings["bob"], bob.mu(), bob.sigma());
+    assert_close(&ratings["carol"], carol.mu(), carol.sigma());
+
+    // With a tiny half-life, only the newest game moves the ratings.
+    let options = ReplayOptions {
+        half_life: Duration::from_secs(1),
+        ..ReplayOptions::default()
+    };
+    let ratings = replay_with_options(&rater, &timed_history(), new(), &options).unwrap();
+
+    let (carol, alice) = duel(new(), new(), 1.0);
+    assert_close(&ratings["alice"], alice.mu(), alice.sigma());
+    assert_close(&ratings["bob"], new().mu(), new().sigma());
+    assert_close(&ratings["carol"], carol.mu(), carol.sigma());
+
+    // The weight of a record is multiplied by its decay.
+    let mut history = timed_history();
+    history[1].weight = Some(0.5);
+    let options = ReplayOptions {
+        half_life: Duration::from_secs(DAY as u64),
+        ..ReplayOptions::default()
+    };
+    let ratings = replay_with_options(&rater, &history, new(), &options).unwrap();
+
+    let (_, bob) = duel(new(), new(), 0.25);
+    let (bob, _) = duel(bob, new(), 0.25);
+    assert_close(&ratings["bob"], bob.mu(), bob.sigma());
+}
+
+#[test]
+fn untimed_records_are_an_error_unless_allowed() {
+    let rater = Rater::default();
+    let mut history = timed_history();
+    history[1].timestamp = None;
+
+    let error = replay_with_options(
+        &rater,
+        &history,
+        Rating::default(),
+        &ReplayOptions::default(),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(error, ReplayError::MissingTimestamp { index: 1 }),
+        "{}",
+        error
+    );
+    assert_eq!(error.index(), Some(1));
+    assert_eq!(error.to_string(), "Record 1 has no timestamp");
+
+    // Allowed, the untimed record counts fully, like the newest one.
+    let options = ReplayOptions {
+        half_life: Duration::from_secs(1),
+        allow_untimed: true,
+    };
+    let ratings = replay_with_options(&rater, &history, Rating::default(), &options).unwrap();
+    let expected = replay(&rater, &history[1..], Rating::default()).unwrap();
+    for player in ["bob", "carol"] {
+        assert_close(
+            &ratings[player],
+            expected[player].mu(),
+            expected[player].sigma(),
+        );
+    }
+}
+
+#[test]
+fn records_serialize() {
+    let history = history();
+    let json = serde_json::to_string(&history).unwrap();
+    let deserialized: Vec<GameRecord> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, history);
+
+    let record: GameRecord =
+        serde_json::from_str(r#"{"teams": [["alice"], ["bob"]], "ranks": [2, 1], "ts": 7}"#)
+            .unwrap();
+    assert_eq!(
+        record,
+        GameRecord::new(vec![vec!["alice".into()], vec!["bob".into()]], vec![2, 1])
+    );
+
+    assert!(serde_json::from_str::<GameRecord>(r#"{"teams": [["alice"], ["bob"]]}"#).is_err());
+}
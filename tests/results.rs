@@ -0,0 +1,146 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::TeamResult::{Dnf, Dsq, Ranked};
+use bbt::{BBTError, Rater, Rating};
+
+fn field() -> Vec<Vec<Rating>> {
+    vec![
+        vec![Rating::new(30.0, 3.0), Rating::new(22.0, 6.0)],
+        vec![Rating::new(27.0, 8.0)],
+        vec![Rating::new(20.0, 2.0), Rating::new(21.0, 2.5)],
+        vec![Rating::new(26.0, 4.0)],
+        vec![Rating::new(24.0, 5.0)],
+    ]
+}
+
+#[test]
+fn disqualified_teams_are_left_out() {
+    let rater = Rater::default();
+    let results = [Ranked(1), Ranked(3), Dsq, Ranked(4), Ranked(2)];
+
+    let updated = rater.update_ratings_results(field(), &results).unwrap();
+
+    // The disqualified team is untouched, down to the bit.
+    for (new, old) in updated[2].iter().zip(&field()[2]) {
+        assert_eq!(new.mu().to_bits(), old.mu().to_bits());
+        assert_eq!(new.sigma().to_bits(), old.sigma().to_bits());
+    }
+
+    // Everyone else is rated as if the team had not entered.
+    let mut without = field();
+    without.remove(2);
+    let expected = rater.update_ratings(without, vec![1, 3, 4, 2]).unwrap();
+
+    let mut rest = updated;
+    rest.remove(2);
+    assert_eq!(rest, expected);
+}
+
+#[test]
+fn teams_that_did_not_finish_tie_behind_the_finishers() {
+    let rater = Rater::default();
+    let results = [Dnf, Ranked(2), Ranked(1), Dnf, Ranked(2)];
+
+    assert_eq!(
+        rater.update_ratings_results(field(), &results),
+        rater.update_ratings(field(), vec![4, 2, 1, 4, 2])
+    );
+
+    // The gaps left by disqualified teams are closed, so strict ranks
+    // accept them.
+    let strict = Rater::default().strict_ranks();
+    let results = [Dnf, Ranked(3), Dsq, Dnf, Ranked(1)];
+    let updated = strict.update_ratings_results(field(), &results).unwrap();
+
+    let mut without = field();
+    without.remove(2);
+    let mut rest = updated;
+    rest.remove(2);
+    assert_eq!(
+        rest,
+        strict.update_ratings(without, vec![3, 2, 3, 1]).unwrap()
+    );
+}
+
+#[test]
+fn errors_refer_to_all_teams() {
+    let rater = Rater::default();
+    let mut teams = field();
+    teams[3] = vec![];
+
+    assert_eq!(
+        rater.update_ratings_results(teams, &[Dsq, Ranked(1), Dsq, Ranked(2), Dnf]),
+        Err(BBTError::EmptyTeam { index: 3 })
+    );
+
+    let mut teams = field();
+    teams[4][0] = Rating::new(f64::NAN, 1.0);
+    assert_eq!(
+        rater.update_ratings_results(teams, &[Dsq, Ranked(1), Dsq, Ranked(2), Dnf]),
+        Err(BBTError::InvalidRating { team: 4, player: 0 })
+    );
+
+    // A disqualified team is not validated.
+    let mut teams = field();
+    teams[0] = vec![];
+    assert!(rater
+        .update_ratings_results(teams, &[Dsq, Ranked(1), Dnf, Dnf, Dnf])
+        .is_ok());
+
+    assert_eq!(
+        rater.update_ratings_results(field(), &[Dsq, Dsq, Dsq, Dsq, Ranked(1)]),
+        Err(BBTError::NotEnoughTeams { found: 1 })
+    );
+    assert_eq!(
+        rater.update_ratings_results(field(), &[Ranked(1), Dnf]),
+        Err(BBTError::MismatchedLengths { teams: 5, ranks: 2 })
+    );
+}
+
+#[test]
+fn only_the_teams_that_played_are_rated() {
+    let rater = Rater::default();
+    let ranks = [None, Some(2), None, Some(1), Some(2)];
+
+    let updated = rater
+        .update_ratings_partial_roster(field(), &ranks)
+        .unwrap();
+
+    for team_idx in [0, 2] {
+        for (new, old) in updated[team_idx].iter().zip(&field()[team_idx]) {
+            assert_eq!(new.mu().to_bits(), old.mu().to_bits());
+            assert_eq!(new.sigma().to_bits(), old.sigma().to_bits());
+        }
+    }
+
+    let subset = vec![field()[1].clone(), field()[3].clone(), field()[4].clone()];
+    let expected = rater.update_ratings(subset, vec![2, 1, 2]).unwrap();
+    assert_eq!(
+        vec![updated[1].clone(), updated[3].clone(), updated[4].clone()],
+        expected
+    );
+}
+
+#[test]
+fn a_roster_without_a_match_is_unchanged() {
+    let rater = Rater::default().strict_ranks();
+
+    assert_eq!(
+        rater.update_ratings_partial_roster(field(), &[None; 5]),
+        Ok(field())
+    );
+
+    assert_eq!(
+        rater.update_ratings_partial_roster(field(), &[None, None, Some(1), None, None]),
+        Err(BBTError::NotEnoughTeams { found: 1 })
+    );
+    assert_eq!(
+        rater.update_ratings_partial_roster(field(), &[None, Some(1), None, Some(3), None]),
+        Err(BBTError::InvalidRanks)
+    );
+    assert_eq!(
+        rater.update_ratings_partial_roster(field(), &[None; 4]),
+        Err(BBTError::MismatchedLengths { teams: 5, ranks: 4 })
+    );
+}
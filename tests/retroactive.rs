@@ -0,0 +1,83 @@
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating};
+
+#[test]
+fn unchanged_ratings_match_duel() {
+    let raters = [
+        Rater::default(),
+        Rater::default().with_volatility(4.0, 1.0),
+        Rater::default().with_truncation(0.0),
+    ];
+    let pairs = [
+        (Rating::default(), Rating::default()),
+        (Rating::new(30.0, 2.0), Rating::new(20.0, 6.0)),
+        (Rating::new(2.0, 3.0), Rating::new(40.0, 1.0)),
+    ];
+
+    for rater in &raters {
+        for &(p1, p2) in &pairs {
+            for &outcome in &[Outcome::Win, Outcome::Loss, Outcome::Draw] {
+                let (mut now1, mut now2) = (p1, p2);
+                rater.duel_retroactive(&mut now1, &p1, &mut now2, &p2, outcome);
+
+                assert_eq!((now1, now2), rater.duel(p1, p2, outcome));
+            }
+        }
+    }
+}
+
+#[test]
+fn deltas_come_from_the_start() {
+    let rater = Rater::default();
+    let (start1, start2) = (Rating::new(25.0, 5.0), Rating::new(25.0, 5.0));
+    let (now1, now2) = (Rating::new(32.0, 5.0), Rating::new(29.0, 5.0));
+
+    let (mut p1, mut p2) = (now1, now2);
+    rater.duel_retroactive(&mut p1, &start1, &mut p2, &start2, Outcome::Loss);
+
+    let (then1, then2) = rater.duel(start1, start2, Outcome::Loss);
+    let (current1, _) = rater.duel(now1, now2, Outcome::Loss);
+
+    let delta = p1.mu() - now1.mu();
+    assert!((delta - (then1.mu() - start1.mu())).abs() < 1e-12);
+    assert!((p2.mu() - now2.mu() - (then2.mu() - start2.mu())).abs() < 1e-12);
+
+    // Rated against the current ratings, the upset would cost p1 more.
+    assert!(current1.mu() - now1.mu() < delta - 0.1);
+
+    // The variance shrinks by the same factor as at the start.
+    let factor = then1.sigma().powi(2) / start1.sigma().powi(2);
+    assert!((p1.sigma().powi(2) / now1.sigma().powi(2) - factor).abs() < 1e-12);
+}
+
+#[test]
+fn sigma_stays_positive() {
+    let rater = Rater::new(0.1);
+    let start = Rating::new(25.0, 20.0);
+    let now = Rating::new(25.0, 0.01);
+
+    for &outcome in &[Outcome::Win, Outcome::Loss, Outcome::Draw] {
+        let (mut p1, mut p2) = (now, now);
+        rater.duel_retroactive(&mut p1, &start, &mut p2, &start, outcome);
+
+        assert!(p1.sigma() > 0.0 && p1.sigma() < now.sigma());
+        assert!(p2.sigma() > 0.0 && p2.sigma() < now.sigma());
+    }
+}
+
+#[test]
+fn invalid_and_certain_ratings_are_left_alone() {
+    let rater = Rater::default();
+    let start = Rating::default();
+
+    let (mut p1, mut p2) = (Rating::new(f64::NAN, 1.0), Rating::default());
+    rater.duel_retroactive(&mut p1, &start, &mut p2, &start, Outcome::Win);
+    assert!(p1.mu().is_nan());
+    assert_eq!(p2, Rating::default());
+
+    let (mut p1, mut p2) = (Rating::new(30.0, 0.0), Rating::default());
+    rater.duel_retroactive(&mut p1, &start, &mut p2, &start, Outcome::Win);
+    assert_eq!(p1, Rating::new(30.0, 0.0));
+    assert!(p2.mu() < 25.0);
+}
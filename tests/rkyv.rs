@@ -0,0 +1,74 @@
+#![cfg(feature = "rkyv")]
+extern crate bbt;
+extern crate rkyv;
+
+use bbt::{ArchivedOutcome, Outcome, Rater, Rating};
+use rkyv::{check_archived_root, Deserialize, Infallible};
+
+#[test]
+fn ratings_round_trip() {
+    let rater = Rater::default();
+    let (winner, _) = rater.duel(Rating::default(), Rating::new(31.0, 2.5), Outcome::Win);
+
+    for original in [Rating::new(31.5, 2.25), Rating::new(-4.0, 1e-9), winner] {
+        let bytes = rkyv::to_bytes::<_, 64>(&original).unwrap();
+        let archived = check_archived_root::<Rating>(&bytes).unwrap();
+
+        assert_eq!(archived.mu(), original.mu());
+        assert_eq!(archived.sigma(), original.sigma());
+        assert_eq!(
+            archived.conservative_estimate(),
+            original.mu() - 3.0 * original.sigma()
+        );
+
+        let deserialized: Rating = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, original);
+    }
+}
+
+#[test]
+fn raters_round_trip() {
+    let original = Rater::new(3.5).strict_ranks();
+
+    let bytes = rkyv::to_bytes::<_, 64>(&original).unwrap();
+    let archived = check_archived_root::<Rater>(&bytes).unwrap();
+    assert_eq!(archived.beta(), 3.5);
+
+    let deserialized: Rater = archived.deserialize(&mut Infallible).unwrap();
+    assert_eq!(format!("{:?}", deserialized), format!("{:?}", original));
+}
+
+#[test]
+fn outcomes_round_trip() {
+    for (outcome, archived_outcome) in &[
+        (Outcome::Win, ArchivedOutcome::Win),
+        (Outcome::Loss, ArchivedOutcome::Loss),
+        (Outcome::Draw, ArchivedOutcome::Draw),
+    ] {
+        let bytes = rkyv::to_bytes::<_, 16>(outcome).unwrap();
+        let archived = check_archived_root::<Outcome>(&bytes).unwrap();
+        assert_eq!(archived, archived_outcome);
+
+        let deserialized: Outcome = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, *outcome);
+    }
+}
+
+#[test]
+fn corrupted_buffers_are_rejected() {
+    let bytes = rkyv::to_bytes::<_, 16>(&Outcome::Draw).unwrap();
+    assert!(check_archived_root::<Outcome>(&bytes).is_ok());
+    let mut corrupted = bytes.clone();
+    corrupted[0] = 7;
+    assert!(check_archived_root::<Outcome>(&corrupted).is_err());
+
+    // The flags of a rater are stored as bools, which must be 0 or 1.
+    let bytes = rkyv::to_bytes::<_, 64>(&Rater::default()).unwrap();
+    assert!(check_archived_root::<Rater>(&bytes).is_ok());
+    let mut corrupted = bytes.clone();
+    corrupted[8] = 2;
+    assert!(check_archived_root::<Rater>(&corrupted).is_err());
+
+    let bytes = rkyv::to_bytes::<_, 64>(&Rating::<f64>::default()).unwrap();
+    assert!(check_archived_root::<Rating>(&bytes[..bytes.len() - 8]).is_err());
+}
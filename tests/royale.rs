@@ -0,0 +1,189 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::{BBTError, Rater, Rating, RoyaleError, RoyaleSession};
+
+fn players() -> Vec<Rating> {
+    (0..8)
+        .map(|i| Rating::new(18.0 + 2.5 * i as f64, 2.0 + 0.75 * i as f64))
+        .collect()
+}
+
+/// Eliminates the players in `order`, checks every provisional update
+/// against the final ratings, and returns the final ratings.
+fn play(rater: &Rater, players: &[Rating], order: &[usize]) -> Vec<Rating> {
+    let mut session = RoyaleSession::new(rater, players);
+    let mut deltas = Vec::new();
+
+    for (i, &player) in order.iter().enumerate() {
+        let delta = session.eliminate(player).unwrap();
+        assert_eq!(delta.placement, players.len() - i);
+        assert_eq!(session.placement(player), Some(delta.placement));
+        deltas.push(delta);
+    }
+
+    let mut ratings = vec![Rating::default(); players.len()];
+    session.finalize(&mut ratings).unwrap();
+
+    for delta in deltas {
+        assert_eq!(delta.before, players[delta.player]);
+        assert_eq!(delta.after, ratings[delta.player]);
+        assert_eq!(delta.mu_delta, delta.after.mu() - delta.before.mu());
+    }
+
+    ratings
+}
+
+#[test]
+fn finalize_matches_update_ratings() {
+    let rater = Rater::default();
+    let players = players();
+    let orders: [[usize; 7]; 3] = [
+        [0, 1, 2, 3, 4, 5, 6],
+        [7, 6, 5, 4, 3, 2, 1],
+        [3, 0, 6, 2, 7, 5, 4],
+    ];
+
+    for order in &orders {
+        let ratings = play(&rater, &players, order);
+
+        let mut ranks = vec![1; players.len()];
+        for (i, &player) in order.iter().enumerate() {
+            ranks[player] = players.len() - i;
+        }
+        let teams = players.iter().map(|&player| vec![player]).collect();
+        let expected = rater.update_ratings(teams, ranks).unwrap();
+
+        for (rating, team) in ratings.iter().zip(&expected) {
+            assert_eq!(rating.mu().to_bits(), team[0].mu().to_bits());
+            assert_eq!(rating.sigma().to_bits(), team[0].sigma().to_bits());
+        }
+    }
+}
+
+#[test]
+fn provisional_updates_match_update_ratings_with_volatility() {
+    // Strong players go out first, so that the results are surprising.
+    let rater = Rater::default().with_volatility(2.0, 1.5);
+    let players = players();
+    let mut session = RoyaleSession::new(&rater, &players);
+    let mut ranks = vec![1; players.len()];
+
+    for (i, &player) in [7, 6, 0, 5, 1, 4, 2].iter().enumerate() {
+        let delta = session.eliminate(player).unwrap();
+
+        ranks[player] = players.len() - i;
+        let teams = players.iter().map(|&player| vec![player]).collect();
+        let expected = rater.update_ratings(teams, ranks.clone()).unwrap();
+
+        assert_eq!(
+            delta.after.mu().to_bits(),
+            expected[player][0].mu().to_bits()
+        );
+        assert_eq!(
+            delta.after.sigma().to_bits(),
+            expected[player][0].sigma().to_bits()
+        );
+    }
+}
+
+#[test]
+fn early_eliminations_lose_rating() {
+    let rater = Rater::default();
+    let players = vec![Rating::default(); 10];
+    let mut session = RoyaleSession::new(&rater, &players);
+
+    let first = session.eliminate(4).unwrap();
+    let second = session.eliminate(7).unwrap();
+    assert_eq!((first.placement, second.placement), (10, 9));
+    assert!(first.mu_delta < 0.0);
+    assert!(second.mu_delta < 0.0);
+    assert!(first.mu_delta < second.mu_delta);
+    assert!(first.after.sigma() < first.before.sigma());
+    assert_eq!(session.remaining(), 8);
+
+    for player in &[0, 1, 2, 3, 5, 6, 8] {
+        session.eliminate(*player).unwrap();
+    }
+    assert_eq!(session.remaining(), 1);
+    assert_eq!(session.placement(9), None);
+
+    let mut ratings = players.clone();
+    session.finalize(&mut ratings).unwrap();
+    assert!(ratings[9].mu() > players[9].mu());
+}
+
+#[test]
+fn invalid_eliminations_are_rejected() {
+    let rater = Rater::default();
+    let players = vec![Rating::default(); 3];
+    let mut session = RoyaleSession::new(&rater, &players);
+
+    assert_eq!(
+        session.eliminate(3),
+        Err(RoyaleError::UnknownPlayer {
+            player: 3,
+            players: 3
+        })
+    );
+
+    session.eliminate(1).unwrap();
+    assert_eq!(
+        session.eliminate(1),
+        Err(RoyaleError::AlreadyEliminated { player: 1 })
+    );
+    assert_eq!(session.remaining(), 2);
+
+    session.eliminate(0).unwrap();
+    assert_eq!(session.eliminate(2), Err(RoyaleError::Winner { player: 2 }));
+    assert_eq!(
+        session.eliminate(0),
+        Err(RoyaleError::AlreadyEliminated { player: 0 })
+    );
+}
+
+#[test]
+fn invalid_finalizations_are_rejected() {
+    let rater = Rater::default();
+    let players = vec![Rating::default(); 3];
+
+    let mut session = RoyaleSession::new(&rater, &players);
+    session.eliminate(0).unwrap();
+    let mut ratings = players.clone();
+    assert_eq!(
+        session.finalize(&mut ratings),
+        Err(RoyaleError::Undecided { remaining: 2 })
+    );
+    assert_eq!(ratings, players);
+
+    let mut session = RoyaleSession::new(&rater, &players);
+    session.eliminate(0).unwrap();
+    session.eliminate(1).unwrap();
+    let mut short = vec![Rating::default(); 2];
+    assert_eq!(
+        session.finalize(&mut short),
+        Err(RoyaleError::MismatchedLength {
+            players: 3,
+            ratings: 2
+        })
+    );
+}
+
+#[test]
+fn rater_errors_are_passed_on() {
+    let rater = Rater::default();
+    let players = vec![Rating::default(), Rating::new(f64::NAN, 1.0)];
+    let mut session = RoyaleSession::new(&rater, &players);
+
+    match session.eliminate(0) {
+        Err(RoyaleError::Rating(_)) => {}
+        other => panic!("expected a rating error, got {:?}", other),
+    }
+    assert_eq!(session.remaining(), 2);
+
+    let error: RoyaleError = BBTError::InvalidParameter { name: "beta" }.into();
+    assert_eq!(
+        error,
+        RoyaleError::Rating(BBTError::InvalidParameter { name: "beta" })
+    );
+}
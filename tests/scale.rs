@@ -0,0 +1,99 @@
+extern crate bbt;
+
+use bbt::{DefaultScale, Outcome, Rater, Rating, Scale, ScaledRater, ScaledRating};
+
+/// The display-facing ladder around 1500.
+struct Legacy;
+
+impl Scale for Legacy {
+    const MU: f64 = 1500.0;
+    const SIGMA: f64 = 500.0;
+    const BETA: f64 = 250.0;
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9 * b.abs().max(1.0), "{} != {}", a, b);
+}
+
+#[test]
+fn the_default_scale_matches_the_plain_types() {
+    let rater = ScaledRater::<DefaultScale>::default();
+    let (alice, bob) = (ScaledRating::new(30.0, 2.0), ScaledRating::default());
+
+    assert_eq!(bob.rating(), Rating::default());
+    assert_eq!(
+        rater.win_probability(&alice, &bob),
+        Rater::default().win_probability(&alice.rating(), &bob.rating())
+    );
+
+    let (new_alice, new_bob) = rater.duel(alice, bob, Outcome::Loss);
+    let expected = Rater::default().duel(alice.rating(), bob.rating(), Outcome::Loss);
+    assert_eq!((new_alice.rating(), new_bob.rating()), expected);
+}
+
+#[test]
+fn conversion_preserves_win_probabilities() {
+    let default = ScaledRater::<DefaultScale>::new();
+    let legacy = ScaledRater::<Legacy>::new();
+
+    let pairs = [
+        ((25.0, 25.0 / 3.0), (25.0, 25.0 / 3.0)),
+        ((30.0, 2.0), (22.0, 5.0)),
+        ((10.0, 1.0), (40.0, 0.5)),
+    ];
+
+    for &((mu1, sigma1), (mu2, sigma2)) in &pairs {
+        let p1 = ScaledRating::<DefaultScale>::new(mu1, sigma1);
+        let p2 = ScaledRating::<DefaultScale>::new(mu2, sigma2);
+        let (q1, q2) = (p1.convert::<Legacy>(), p2.convert::<Legacy>());
+
+        assert_close(
+            legacy.win_probability(&q1, &q2),
+            default.win_probability(&p1, &p2),
+        );
+        assert_close(
+            legacy.match_quality(&q1, &q2),
+            default.match_quality(&p1, &p2),
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn conversion_commutes_with_updates() {
+    let default = ScaledRater::<DefaultScale>::new();
+    let legacy = ScaledRater::<Legacy>::new();
+
+    let teams = vec![
+        vec![ScaledRating::new(30.0, 2.0), ScaledRating::default()],
+        vec![ScaledRating::new(20.0, 4.0)],
+    ];
+    let converted = teams
+        .iter()
+        .map(|team| team.iter().map(|r| r.convert::<Legacy>()).collect())
+        .collect();
+
+    let updated = default.update_ratings(teams, vec![2, 1]).unwrap();
+    let updated_legacy = legacy.update_ratings(converted, vec![2, 1]).unwrap();
+
+    for (team, legacy_team) in updated.iter().zip(&updated_legacy) {
+        for (rating, legacy_rating) in team.iter().zip(legacy_team) {
+            let back = legacy_rating.convert::<DefaultScale>();
+            assert_close(back.mu(), rating.mu());
+            assert_close(back.sigma(), rating.sigma());
+        }
+    }
+}
+
+#[test]
+fn new_players_map_to_new_players_when_the_ratios_agree() {
+    let rating = ScaledRating::<DefaultScale>::default().convert::<Legacy>();
+    assert_close(rating.mu(), Legacy::MU);
+    assert_close(rating.sigma(), Legacy::SIGMA);
+
+    let round_trip = ScaledRating::<DefaultScale>::new(31.5, 3.25)
+        .convert::<Legacy>()
+        .convert::<DefaultScale>();
+    assert_close(round_trip.mu(), 31.5);
+    assert_close(round_trip.sigma(), 3.25);
+}
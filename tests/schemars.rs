@@ -0,0 +1,81 @@
+#![cfg(all(feature = "schemars", feature = "serde"))]
+extern crate bbt;
+extern crate jsonschema;
+extern crate schemars;
+extern crate serde_json;
+
+use bbt::{Outcome, Rater, Rating};
+use schemars::{schema_for, JsonSchema};
+use serde_json::{json, Value};
+
+fn validator<T: JsonSchema>() -> jsonschema::Validator {
+    jsonschema::validator_for(&schema_for!(T).to_value()).unwrap()
+}
+
+#[test]
+fn serialized_ratings_match_the_schema() {
+    let validator = validator::<Rating>();
+    let (winner, loser) =
+        Rater::default().duel(Rating::default(), Rating::new(31.0, 2.5), Outcome::Win);
+
+    for rating in [Rating::default(), Rating::new(-4.0, 1e-9), winner, loser] {
+        let value = serde_json::to_value(rating).unwrap();
+        assert!(validator.is_valid(&value), "{}", value);
+    }
+
+    // The schema rejects what the deserializer rejects.
+    for invalid in [
+        json!({ "mu": 25.0 }),
+        json!({ "mu": "25", "sigma": 8.0 }),
+        json!({ "mu": 25.0, "sigma": 0.0 }),
+    ] {
+        assert!(!validator.is_valid(&invalid), "{}", invalid);
+        assert!(serde_json::from_value::<Rating>(invalid).is_err());
+    }
+}
+
+#[test]
+fn serialized_raters_match_the_schema() {
+    let validator = validator::<Rater>();
+
+    for rater in [
+        Rater::default(),
+        Rater::new(0.0),
+        Rater::new(3.5).strict_ranks(),
+    ] {
+        let value = serde_json::to_value(&rater).unwrap();
+        assert!(validator.is_valid(&value), "{}", value);
+    }
+
+    assert!(!validator.is_valid(&json!({})));
+    assert!(!validator.is_valid(&json!({ "beta": -1.0 })));
+}
+
+#[test]
+fn serialized_outcomes_match_the_schema() {
+    let validator = validator::<Outcome>();
+
+    for outcome in [Outcome::Win, Outcome::Loss, Outcome::Draw] {
+        let value = serde_json::to_value(outcome).unwrap();
+        assert!(validator.is_valid(&value), "{}", value);
+    }
+
+    for invalid in [json!("win"), json!(0), json!("Tie")] {
+        assert!(!validator.is_valid(&invalid), "{}", invalid);
+    }
+}
+
+#[test]
+fn schema_examples_deserialize() {
+    let schema: Value = schema_for!(Rating).to_value();
+    for example in schema["examples"].as_array().unwrap() {
+        let rating: Rating = serde_json::from_value(example.clone()).unwrap();
+        assert_eq!(rating, Rating::default());
+    }
+
+    let schema: Value = schema_for!(Rater).to_value();
+    for example in schema["examples"].as_array().unwrap() {
+        let rater: Rater = serde_json::from_value(example.clone()).unwrap();
+        assert_eq!(rater.beta(), Rater::default().beta());
+    }
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::ScoreOrder::{HigherIsBetter, LowerIsBetter};
+use bbt::{ranks_from_scores, BBTError, Rater, Rating};
+
+#[test]
+fn scores_are_ranked_in_both_directions() {
+    let scores = [12.0, 40.0, 7.5, 21.0];
+
+    assert_eq!(
+        ranks_from_scores(&scores, HigherIsBetter, 0.0),
+        vec![3, 1, 4, 2]
+    );
+    assert_eq!(
+        ranks_from_scores(&scores, LowerIsBetter, 0.0),
+        vec![2, 4, 1, 3]
+    );
+
+    assert!(ranks_from_scores::<f64>(&[], HigherIsBetter, 0.0).is_empty());
+    assert_eq!(ranks_from_scores(&[3.0f32], LowerIsBetter, 0.0), vec![1]);
+}
+
+#[test]
+fn ties_share_the_best_rank_in_both_directions() {
+    let scores = [5.0, 9.0, 5.0, 1.0, 9.0];
+
+    assert_eq!(
+        ranks_from_scores(&scores, HigherIsBetter, 0.0),
+        vec![3, 1, 3, 5, 1]
+    );
+    assert_eq!(
+        ranks_from_scores(&scores, LowerIsBetter, 0.0),
+        vec![2, 4, 2, 1, 4]
+    );
+
+    // Within the epsilon of the best score of a group, scores are tied, but
+    // ties do not chain beyond it.
+    let times = [60.0, 60.25, 60.5, 60.75, 59.0];
+    assert_eq!(
+        ranks_from_scores(&times, LowerIsBetter, 0.5),
+        vec![2, 2, 2, 5, 1]
+    );
+    assert_eq!(
+        ranks_from_scores(&times, HigherIsBetter, 0.5),
+        vec![4, 1, 1, 1, 5]
+    );
+
+    // Infinite scores, such as a finish time for a team that did not finish,
+    // tie with each other.
+    let times = [f64::INFINITY, 30.0, f64::INFINITY];
+    assert_eq!(ranks_from_scores(&times, LowerIsBetter, 0.0), vec![2, 1, 2]);
+
+    let scores = [f64::NAN, 2.0, f64::NAN, 1.0];
+    assert_eq!(
+        ranks_from_scores(&scores, HigherIsBetter, 0.0),
+        vec![3, 1, 4, 2]
+    );
+    assert_eq!(
+        ranks_from_scores(&scores, LowerIsBetter, 0.0),
+        vec![3, 2, 4, 1]
+    );
+}
+
+#[test]
+fn score_updates_match_updates_with_derived_ranks() {
+    let rater = Rater::default();
+    let teams = vec![
+        vec![Rating::new(30.0, 3.0), Rating::new(22.0, 6.0)],
+        vec![Rating::new(27.0, 8.0)],
+        vec![Rating::new(20.0, 2.0), Rating::new(21.0, 2.5)],
+        vec![Rating::new(26.0, 4.0)],
+    ];
+    let scores = [310.0, 305.5, 420.0, 305.0];
+
+    for &order in &[HigherIsBetter, LowerIsBetter] {
+        for &tie_epsilon in &[0.0, 1.0] {
+            let ranks = ranks_from_scores(&scores, order, tie_epsilon);
+
+            assert_eq!(
+                rater.update_ratings_from_scores(teams.clone(), &scores, order, tie_epsilon),
+                rater.update_ratings(teams.clone(), ranks)
+            );
+        }
+    }
+
+    // The two directions are mirror images of each other.
+    let higher = rater
+        .update_ratings_from_scores(teams.clone(), &scores, HigherIsBetter, 0.0)
+        .unwrap();
+    let negated: Vec<f64> = scores.iter().map(|score| -score).collect();
+    let lower = rater
+        .update_ratings_from_scores(teams.clone(), &negated, LowerIsBetter, 0.0)
+        .unwrap();
+    assert_eq!(higher, lower);
+    assert!(higher[2][0].mu() > 20.0);
+}
+
+#[test]
+fn score_updates_reject_invalid_input() {
+    let rater = Rater::default();
+    let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+
+    for &tie_epsilon in &[-0.5, f64::NAN, f64::INFINITY] {
+        assert_eq!(
+            rater.update_ratings_from_scores(
+                teams.clone(),
+                &[1.0, 2.0],
+                HigherIsBetter,
+                tie_epsilon
+            ),
+            Err(BBTError::InvalidParameter {
+                name: "tie_epsilon"
+            })
+        );
+    }
+
+    assert_eq!(
+        rater.update_ratings_from_scores(teams.clone(), &[1.0, f64::NAN], LowerIsBetter, 0.0),
+        Err(BBTError::InvalidParameter { name: "scores" })
+    );
+    assert_eq!(
+        rater.update_ratings_from_scores(teams, &[1.0, 2.0, 3.0], LowerIsBetter, 0.0),
+        Err(BBTError::MismatchedLengths { teams: 2, ranks: 3 })
+    );
+}
+
+fn league() -> Vec<Vec<Rating>> {
+    vec![
+        vec![Rating::new(27.0, 3.0)],
+        vec![Rating::new(25.0, 4.0)],
+        vec![Rating::new(24.0, 5.0)],
+        vec![Rating::new(22.0, 2.0)],
+    ]
+}
+
+#[test]
+fn tiebreaks_order_teams_with_the_same_rank() {
+    let rater = Rater::default();
+
+    // The second and third teams share rank 2, and the third one wins the
+    // tiebreak.
+    let updated = rater
+        .update_ratings_with_tiebreaks(league(), &[(1, 0), (2, -4), (2, -9), (4, 0)])
+        .unwrap();
+    let plain = rater.update_ratings(league(), vec![1, 3, 2, 4]).unwrap();
+    assert_eq!(updated, plain);
+
+    let tied = rater.update_ratings(league(), vec![1, 2, 2, 4]).unwrap();
+    assert!(updated[2][0].mu() > tied[2][0].mu());
+    assert!(updated[1][0].mu() < tied[1][0].mu());
+}
+
+#[test]
+fn full_ties_are_draws() {
+    let rater = Rater::default();
+
+    let updated = rater
+        .update_ratings_with_tiebreaks(league(), &[(1, 0), (2, 7), (2, 7), (4, 0)])
+        .unwrap();
+    let tied = rater.update_ratings(league(), vec![1, 2, 2, 4]).unwrap();
+    assert_eq!(updated, tied);
+
+    let all_tied = rater
+        .update_ratings_with_tiebreaks(league(), &[(1, 3); 4])
+        .unwrap();
+    assert_eq!(
+        all_tied,
+        rater.update_ratings(league(), vec![1; 4]).unwrap()
+    );
+}
+
+#[test]
+fn tiebreaks_do_not_matter_across_ranks() {
+    let rater = Rater::default();
+    let plain = rater.update_ratings(league(), vec![1, 2, 3, 4]).unwrap();
+
+    for tiebreaks in &[[0, 0, 0, 0], [9, -9, 4, -100], [-100, 100, 0, i64::MIN]] {
+        let ranks: Vec<(usize, i64)> = (1..=4).zip(tiebreaks.iter().cloned()).collect();
+        let updated = rater
+            .update_ratings_with_tiebreaks(league(), &ranks)
+            .unwrap();
+        assert_eq!(updated, plain);
+    }
+
+    assert_eq!(
+        rater.update_ratings_with_tiebreaks(league(), &[(1, 0), (2, 0)]),
+        Err(BBTError::MismatchedLengths { teams: 4, ranks: 2 })
+    );
+}
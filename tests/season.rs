@@ -0,0 +1,118 @@
+extern crate bbt;
+
+use bbt::{season, BBTError, Rating};
+
+#[test]
+fn mu_is_compressed_towards_the_target() {
+    let rating = Rating::new(40.0, 2.0);
+
+    for &(compression, mu) in &[(0.0, 40.0), (0.25, 36.25), (0.5, 32.5), (1.0, 25.0)] {
+        let reset = rating.season_reset(25.0, compression, 1.0).unwrap();
+        assert_eq!(reset.mu(), mu, "{}", compression);
+        assert_eq!(reset.sigma(), 2.0);
+    }
+
+    let below = Rating::new(10.0, 2.0).season_reset(25.0, 0.2, 1.0).unwrap();
+    assert_eq!(below.mu(), 13.0);
+}
+
+#[test]
+fn sigma_never_shrinks() {
+    for &sigma in &[0.5, 2.0, 3.0, 5.0, 25.0 / 3.0] {
+        let rating = Rating::new(30.0, sigma);
+        let reset = rating.season_reset(25.0, 0.3, 3.0).unwrap();
+
+        assert!(reset.sigma() >= rating.sigma(), "{}", sigma);
+        assert_eq!(reset.sigma(), if sigma < 3.0 { 3.0 } else { sigma });
+    }
+}
+
+#[test]
+fn newcomers_are_unaffected() {
+    let newcomer = Rating::default();
+    assert_eq!(newcomer.season_reset(25.0, 0.5, 4.0), Ok(newcomer));
+}
+
+#[test]
+fn compression_zero_is_the_identity() {
+    let rating = Rating::new(31.5, 2.5);
+
+    let once = rating.season_reset(25.0, 0.0, 2.0).unwrap();
+    let twice = once.season_reset(25.0, 0.0, 2.0).unwrap();
+    assert_eq!(once, rating);
+    assert_eq!(twice, rating);
+
+    // A restored sigma only applies once.
+    let once = rating.season_reset(25.0, 0.0, 4.0).unwrap();
+    assert_eq!(once.season_reset(25.0, 0.0, 4.0), Ok(once));
+    assert_eq!(once.mu(), 31.5);
+}
+
+#[test]
+fn invalid_parameters() {
+    let rating = Rating::default();
+    let invalid = |name| Err(BBTError::InvalidParameter { name });
+
+    assert_eq!(
+        rating.season_reset(f64::NAN, 0.5, 4.0),
+        invalid("target_mu")
+    );
+    assert_eq!(
+        rating.season_reset(f64::INFINITY, 0.5, 4.0),
+        invalid("target_mu")
+    );
+    for &compression in &[-0.1, 1.1, f64::NAN] {
+        assert_eq!(
+            rating.season_reset(25.0, compression, 4.0),
+            invalid("compression")
+        );
+    }
+    for &sigma_restore in &[-1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(
+            rating.season_reset(25.0, 0.5, sigma_restore),
+            invalid("sigma_restore")
+        );
+    }
+
+    let mut ratings = vec![Rating::new(40.0, 1.0)];
+    assert_eq!(
+        season::reset(&mut ratings, 25.0, 2.0, 4.0),
+        Err(BBTError::InvalidParameter {
+            name: "compression"
+        })
+    );
+    assert_eq!(ratings[0], Rating::new(40.0, 1.0));
+}
+
+#[test]
+fn bulk_resets() {
+    let mut ratings = vec![
+        Rating::new(40.0, 1.0),
+        Rating::new(10.0, 6.0),
+        Rating::default(),
+    ];
+    let expected: Vec<_> = ratings
+        .iter()
+        .map(|rating| rating.season_reset(25.0, 0.5, 4.0).unwrap())
+        .collect();
+
+    season::reset(&mut ratings, 25.0, 0.5, 4.0).unwrap();
+    assert_eq!(ratings, expected);
+
+    let mut empty: Vec<Rating> = Vec::new();
+    assert_eq!(season::reset(&mut empty, 25.0, 0.5, 4.0), Ok(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn map_resets() {
+    use std::collections::HashMap;
+
+    let mut ratings = HashMap::new();
+    ratings.insert("alice", Rating::new(40.0, 1.0));
+    ratings.insert("bob", Rating::new(10.0, 6.0));
+
+    season::reset_map(&mut ratings, 25.0, 0.5, 4.0).unwrap();
+    assert_eq!(ratings["alice"], Rating::new(32.5, 4.0));
+    assert_eq!(ratings["bob"], Rating::new(17.5, 6.0));
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "skillratings")]
+extern crate bbt;
+extern crate skillratings;
+
+use skillratings::weng_lin::{weng_lin, weng_lin_multi_team, WengLinConfig, WengLinRating};
+use skillratings::{MultiTeamOutcome, Outcomes};
+
+use bbt::{Outcome, Rater, Rating};
+
+fn assert_close(actual: &Rating, expected: WengLinRating) {
+    assert!((actual.mu() - expected.rating).abs() < 1e-6, "{:?}", actual);
+    assert!(
+        (actual.sigma() - expected.uncertainty).abs() < 1e-6,
+        "{:?}",
+        actual
+    );
+}
+
+#[test]
+fn conversions_round_trip() {
+    let rating = WengLinRating {
+        rating: 31.5,
+        uncertainty: 4.25,
+    };
+    let converted = Rating::from(rating);
+    assert_eq!(converted, Rating::new(31.5, 4.25));
+
+    let back = WengLinRating::from(converted);
+    assert_eq!(back.rating, 31.5);
+    assert_eq!(back.uncertainty, 4.25);
+
+    assert_eq!(Rating::from(WengLinRating::new()), Rating::default());
+    assert_eq!(Rater::from(WengLinConfig::new()).beta(), 25.0 / 6.0);
+}
+
+#[test]
+fn duels_agree() {
+    let config = WengLinConfig::new();
+    let rater = Rater::from(config);
+
+    let p1 = WengLinRating {
+        rating: 27.0,
+        uncertainty: 6.0,
+    };
+    let p2 = WengLinRating {
+        rating: 22.0,
+        uncertainty: 3.5,
+    };
+
+    let outcomes = [
+        (Outcome::Win, Outcomes::WIN),
+        (Outcome::Loss, Outcomes::LOSS),
+        (Outcome::Draw, Outcomes::DRAW),
+    ];
+
+    for &(outcome, their_outcome) in outcomes.iter() {
+        let (ours1, ours2) = rater.duel(p1.into(), p2.into(), outcome);
+        let (theirs1, theirs2) = weng_lin(&p1, &p2, &their_outcome, &config);
+
+        assert_close(&ours1, theirs1);
+        assert_close(&ours2, theirs2);
+    }
+}
+
+#[test]
+fn four_teams_agree() {
+    let config = WengLinConfig::new();
+    let rater = Rater::from(config);
+
+    let rating = |mu, sigma| WengLinRating {
+        rating: mu,
+        uncertainty: sigma,
+    };
+    let teams = [
+        vec![rating(25.0, 8.3), rating(30.0, 4.0)],
+        vec![rating(20.0, 6.0), rating(28.0, 2.5)],
+        vec![rating(35.0, 7.0)],
+        vec![rating(18.0, 5.0), rating(22.0, 3.0), rating(26.0, 8.0)],
+    ];
+    let ranks = vec![2, 1, 2, 4];
+
+    let ours = rater
+        .update_ratings(
+            teams
+                .iter()
+                .map(|team| team.iter().map(|&r| Rating::from(r)).collect())
+                .collect(),
+            ranks.clone(),
+        )
+        .unwrap();
+
+    let teams_and_ranks: Vec<(&[WengLinRating], MultiTeamOutcome)> = teams
+        .iter()
+        .zip(ranks.iter())
+        .map(|(team, &rank)| (&team[..], MultiTeamOutcome::new(rank)))
+        .collect();
+    let theirs = weng_lin_multi_team(&teams_and_ranks, &config);
+
+    for (ours, theirs) in ours.iter().zip(theirs.iter()) {
+        assert_eq!(ours.len(), theirs.len());
+        for (ours, &theirs) in ours.iter().zip(theirs.iter()) {
+            assert_close(ours, theirs);
+        }
+    }
+}
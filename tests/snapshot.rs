@@ -0,0 +1,224 @@
+#![cfg(feature = "std")]
+extern crate bbt;
+
+use std::io;
+
+use bbt::snapshot::{self, SnapshotError};
+use bbt::{Rater, Rating};
+
+fn table(players: usize) -> Vec<(String, Rating)> {
+    let rater = Rater::default();
+
+    (0..players)
+        .map(|i| {
+            let rating = Rating::new(i as f64 * 0.01, 1.0 + (i % 97) as f64 * 0.1);
+            let (rating, _) = rater.duel(rating, Rating::default(), bbt::Outcome::Win);
+            (format!("player-{}", i), rating)
+        })
+        .collect()
+}
+
+fn write(entries: &[(String, Rating)]) -> Vec<u8> {
+    let mut file = Vec::new();
+    snapshot::write(&mut file, entries.iter().map(|(id, r)| (id, r))).unwrap();
+    file
+}
+
+#[test]
+fn large_tables_round_trip() {
+    let mut entries = table(100_000);
+    entries.push(("".to_string(), Rating::new(-3.5, 0.0)));
+    entries.push(("ünïcødé".to_string(), Rating::new(1e300, 1e-300)));
+
+    let read = snapshot::read(&write(&entries)[..]).unwrap();
+
+    assert_eq!(read.len(), entries.len());
+    for ((id, rating), (read_id, read_rating)) in entries.iter().zip(&read) {
+        assert_eq!(id, read_id);
+        assert_eq!(rating.mu().to_bits(), read_rating.mu().to_bits());
+        assert_eq!(rating.sigma().to_bits(), read_rating.sigma().to_bits());
+    }
+
+    assert_eq!(snapshot::read(&write(&[])[..]).unwrap(), vec![]);
+}
+
+#[test]
+fn flipped_bits_are_detected() {
+    let file = write(&table(100));
+
+    for &byte in &[40, file.len() / 2, file.len() - 5] {
+        let mut damaged = file.clone();
+        damaged[byte] ^= 0x10;
+
+        match snapshot::read(&damaged[..]) {
+            Err(SnapshotError::ChecksumMismatch { expected, actual }) => {
+                assert_ne!(expected, actual)
+            }
+            other => panic!("byte {}: unexpected result {:?}", byte, other),
+        }
+    }
+
+    let mut damaged = file.clone();
+    *damaged.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        snapshot::read(&damaged[..]),
+        Err(SnapshotError::ChecksumMismatch { .. })
+    ));
+
+    match snapshot::read(&file[..file.len() - 1]) {
+        Err(SnapshotError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn future_major_versions_are_rejected() {
+    let mut file = write(&table(3));
+    file[8..10].copy_from_slice(&2u16.to_le_bytes());
+    file[10..12].copy_from_slice(&5u16.to_le_bytes());
+
+    let error = snapshot::read(&file[..]).unwrap_err();
+    assert!(matches!(
+        error,
+        SnapshotError::UnsupportedVersion { major: 2, minor: 5 }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "The snapshot has version 2.5, but only version 1.x can be read"
+    );
+
+    assert!(matches!(
+        snapshot::read(&b"{\"alice\": [25.0, 8.3]}"[..]),
+        Err(SnapshotError::NotASnapshot)
+    ));
+}
+
+/// Appends the CRC-32 of the buffer, computed bit by bit.
+fn seal(mut buffer: Vec<u8>) -> Vec<u8> {
+    let mut crc = !0u32;
+    for &byte in &buffer {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    buffer.extend_from_slice(&(!crc).to_le_bytes());
+    buffer
+}
+
+fn record(buffer: &mut Vec<u8>, id: &str, id_width: usize, mu: f64, sigma: f64) {
+    buffer.extend_from_slice(&(id.len() as u16).to_le_bytes());
+    buffer.extend_from_slice(id.as_bytes());
+    buffer.resize(buffer.len() + id_width - id.len(), 0);
+    buffer.extend_from_slice(&mu.to_le_bytes());
+    buffer.extend_from_slice(&sigma.to_le_bytes());
+}
+
+#[test]
+fn version_1_0_snapshots_are_read() {
+    let mut buffer = b"BBTSNAP\0".to_vec();
+    buffer.extend_from_slice(&1u16.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&32u32.to_le_bytes());
+    buffer.extend_from_slice(&2u64.to_le_bytes());
+    buffer.extend_from_slice(&5u32.to_le_bytes());
+    buffer.extend_from_slice(&23u32.to_le_bytes());
+    record(&mut buffer, "alice", 5, 27.5, 4.0);
+    record(&mut buffer, "bob", 5, 25.0, 25.0 / 3.0);
+    let buffer = seal(buffer);
+
+    assert_eq!(
+        snapshot::read(&buffer[..]).unwrap(),
+        vec![
+            ("alice".to_string(), Rating::new(27.5, 4.0)),
+            ("bob".to_string(), Rating::default()),
+        ]
+    );
+
+    // The writer produces exactly this layout.
+    let alice = Rating::new(27.5, 4.0);
+    let bob = Rating::default();
+    let mut written = Vec::new();
+    snapshot::write(&mut written, vec![("alice", &alice), ("bob", &bob)]).unwrap();
+    assert_eq!(written, buffer);
+}
+
+#[test]
+fn newer_minor_versions_are_read() {
+    // Version 1.3 with four more bytes of header and two more bytes per
+    // record, which this version does not know about.
+    let mut buffer = b"BBTSNAP\0".to_vec();
+    buffer.extend_from_slice(&1u16.to_le_bytes());
+    buffer.extend_from_slice(&3u16.to_le_bytes());
+    buffer.extend_from_slice(&36u32.to_le_bytes());
+    buffer.extend_from_slice(&1u64.to_le_bytes());
+    buffer.extend_from_slice(&4u32.to_le_bytes());
+    buffer.extend_from_slice(&24u32.to_le_bytes());
+    buffer.extend_from_slice(b"new!");
+    record(&mut buffer, "carl", 4, 20.0, 2.0);
+    buffer.extend_from_slice(&[0xAB, 0xCD]);
+    let buffer = seal(buffer);
+
+    assert_eq!(
+        snapshot::read(&buffer[..]).unwrap(),
+        vec![("carl".to_string(), Rating::new(20.0, 2.0))]
+    );
+}
+
+#[test]
+fn invalid_contents_are_rejected() {
+    let mut buffer = b"BBTSNAP\0".to_vec();
+    buffer.extend_from_slice(&1u16.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&32u32.to_le_bytes());
+    buffer.extend_from_slice(&1u64.to_le_bytes());
+    buffer.extend_from_slice(&3u32.to_le_bytes());
+    buffer.extend_from_slice(&21u32.to_le_bytes());
+
+    let mut nan = buffer.clone();
+    record(&mut nan, "bob", 3, f64::NAN, 1.0);
+    assert!(matches!(
+        snapshot::read(&seal(nan)[..]),
+        Err(SnapshotError::InvalidRating { index: 0 })
+    ));
+
+    let mut long_id = buffer.clone();
+    record(&mut long_id, "bob", 3, 25.0, 1.0);
+    long_id[32] = 4;
+    assert!(matches!(
+        snapshot::read(&seal(long_id)[..]),
+        Err(SnapshotError::InvalidId { index: 0 })
+    ));
+
+    let mut short_records = buffer.clone();
+    short_records[28..32].copy_from_slice(&20u32.to_le_bytes());
+    assert!(matches!(
+        snapshot::read(&seal(short_records)[..]),
+        Err(SnapshotError::InvalidHeader)
+    ));
+
+    // A damaged header must not make the reader allocate a huge record
+    // before the checksum is checked.
+    let mut huge_records = buffer.clone();
+    huge_records[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        snapshot::read(&huge_records[..]),
+        Err(SnapshotError::InvalidHeader)
+    ));
+
+    let mut wide_ids = buffer.clone();
+    wide_ids[24..28].copy_from_slice(&(u32::MAX - 18).to_le_bytes());
+    wide_ids[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        snapshot::read(&wide_ids[..]),
+        Err(SnapshotError::InvalidHeader)
+    ));
+
+    let invalid = Rating::new(25.0, -1.0);
+    let error = snapshot::write(Vec::new(), vec![("alice", &invalid)]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
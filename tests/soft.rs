@@ -0,0 +1,166 @@
+extern crate bbt;
+
+#[cfg(feature = "alloc")]
+use bbt::SoftScores;
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+fn soft(rater: &Rater, p1: Rating, p2: Rating, score: f64) -> (Rating, Rating) {
+    let (mut p1, mut p2) = (p1, p2);
+    rater.duel_soft(&mut p1, &mut p2, score).unwrap();
+    (p1, p2)
+}
+
+#[test]
+fn whole_scores_match_outcomes_exactly() {
+    let rater = Rater::default();
+    let alice = Rating::new(27.0, 4.0);
+    let bob = Rating::new(22.0, 6.5);
+
+    for &(score, outcome) in &[
+        (1.0, Outcome::Win),
+        (0.5, Outcome::Draw),
+        (0.0, Outcome::Loss),
+    ] {
+        assert_eq!(
+            soft(&rater, alice, bob, score),
+            rater.duel(alice, bob, outcome)
+        );
+    }
+}
+
+#[test]
+fn partial_scores_interpolate_monotonically() {
+    let rater = Rater::default();
+    let alice = Rating::new(25.0, 5.0);
+    let bob = Rating::new(26.0, 5.0);
+
+    let (win, _) = rater.duel(alice, bob, Outcome::Win);
+    let (draw, _) = rater.duel(alice, bob, Outcome::Draw);
+    let (partial, _) = soft(&rater, alice, bob, 0.7);
+    assert!(draw.mu() < partial.mu() && partial.mu() < win.mu());
+
+    let mut previous = draw;
+    for step in 1..=10 {
+        let (current, opponent) = soft(&rater, alice, bob, 0.5 + 0.05 * f64::from(step));
+        assert!(current.mu() > previous.mu());
+        assert!(opponent.mu() < bob.mu());
+        previous = current;
+    }
+}
+
+#[test]
+fn invalid_duels_are_rejected() {
+    let rater = Rater::default();
+    let mut alice = Rating::default();
+    let mut bob = Rating::default();
+
+    for &score in &[-0.1, 1.5, f64::NAN] {
+        assert_eq!(
+            rater.duel_soft(&mut alice, &mut bob, score),
+            Err(BBTError::InvalidParameter { name: "score" })
+        );
+    }
+
+    let mut broken = Rating::new(f64::INFINITY, 1.0);
+    assert_eq!(
+        rater.duel_soft(&mut alice, &mut broken, 0.5),
+        Err(BBTError::InvalidRating { team: 1, player: 0 })
+    );
+
+    assert_eq!(alice, Rating::default());
+    assert_eq!(bob, Rating::default());
+}
+
+#[cfg(feature = "alloc")]
+fn match_teams() -> Vec<Vec<Rating>> {
+    vec![
+        vec![Rating::new(28.0, 3.0), Rating::default()],
+        vec![Rating::new(20.0, 7.0)],
+        vec![Rating::default(), Rating::new(24.0, 2.0)],
+    ]
+}
+
+// With the `simd` feature, `update_ratings` computes the pairwise terms with
+// a vectorized kernel, so its results are close to, but not identical with,
+// the soft update.
+#[cfg(all(feature = "alloc", not(feature = "simd")))]
+#[test]
+fn whole_scores_match_ranks_exactly() {
+    let rater = Rater::default();
+    let ranks = vec![2, 1, 2];
+
+    let scores: Vec<Vec<f64>> = ranks
+        .iter()
+        .map(|&rank| {
+            ranks
+                .iter()
+                .map(|&other: &usize| match other.cmp(&rank) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                })
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(
+        rater.update_ratings_soft(match_teams(), scores).unwrap(),
+        rater.update_ratings(match_teams(), ranks).unwrap()
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn per_team_scores_apply_to_every_opponent() {
+    let rater = Rater::default();
+    let alice = Rating::new(25.0, 5.0);
+    let bob = Rating::new(26.0, 5.0);
+
+    let per_team = rater
+        .update_ratings_soft(vec![vec![alice], vec![bob]], vec![0.7, 0.3])
+        .unwrap();
+    let pairwise = rater
+        .update_ratings_soft(
+            vec![vec![alice], vec![bob]],
+            vec![vec![0.0, 0.7], vec![0.3, 0.0]],
+        )
+        .unwrap();
+    assert_eq!(per_team, pairwise);
+
+    let (new_alice, new_bob) = soft(&rater, alice, bob, 0.7);
+    assert_eq!(per_team, vec![vec![new_alice], vec![new_bob]]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn malformed_scores_are_rejected() {
+    let rater = Rater::default();
+
+    assert_eq!(
+        rater.update_ratings_soft(match_teams(), vec![0.5, 0.5]),
+        Err(BBTError::MismatchedLengths { teams: 3, ranks: 2 })
+    );
+    assert_eq!(
+        rater.update_ratings_soft(match_teams(), vec![0.5, 0.5, 1.2]),
+        Err(BBTError::InvalidParameter { name: "scores" })
+    );
+    assert_eq!(
+        rater.update_ratings_soft(
+            match_teams(),
+            SoftScores::Pairwise(vec![vec![0.0, 1.0, 1.0], vec![0.0, 0.0], vec![0.0; 3]])
+        ),
+        Err(BBTError::InvalidParameter { name: "scores" })
+    );
+
+    // The diagonal is not a score.
+    assert!(rater
+        .update_ratings_soft(
+            match_teams(),
+            vec![
+                vec![f64::NAN, 1.0, 1.0],
+                vec![0.0, f64::NAN, 0.5],
+                vec![0.0, 0.5, f64::NAN]
+            ]
+        )
+        .is_ok());
+}
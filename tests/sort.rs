@@ -0,0 +1,56 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+
+use bbt::sort::group_ties;
+use bbt::Rating;
+
+#[test]
+fn close_converged_players_tie_at_a_wide_interval() {
+    let a: Rating = Rating::new(26.0, 1.0);
+    let b = Rating::new(25.0, 1.0);
+
+    assert!(a.is_statistical_tie(&b, 2.0));
+    assert!(b.is_statistical_tie(&a, 2.0));
+    assert!(!a.is_statistical_tie(&b, 0.5));
+    assert!(!a.is_statistical_tie(&a, 0.0));
+
+    assert_eq!(group_ties(&[a, b], 2.0), vec![0..2]);
+    assert_eq!(group_ties(&[a, b], 0.5), vec![0..1, 1..2]);
+}
+
+#[test]
+fn chained_ties_are_grouped() {
+    let standings: Vec<Rating> = vec![
+        Rating::new(30.0, 1.0),
+        Rating::new(28.5, 1.0),
+        Rating::new(27.0, 1.0),
+        Rating::new(10.0, 1.0),
+    ];
+
+    assert!(!standings[0].is_statistical_tie(&standings[2], 2.0));
+    assert_eq!(group_ties(&standings, 2.0), vec![0..3, 3..4]);
+
+    // The first and the third player tie, although neither ties with the
+    // second, which is sorted between them.
+    let standings: Vec<Rating> = vec![
+        Rating::new(30.0, 1.0),
+        Rating::new(20.0, 1.0),
+        Rating::new(29.0, 4.0),
+        Rating::new(5.0, 1.0),
+    ];
+
+    assert_eq!(group_ties(&standings, 2.0), vec![0..3, 3..4]);
+}
+
+#[test]
+fn separated_players_never_group() {
+    let standings: Vec<Rating> = (0..5)
+        .map(|i| Rating::new(50.0 - 10.0 * i as f64, 1.0))
+        .collect();
+
+    assert_eq!(
+        group_ties(&standings, 3.0),
+        vec![0..1, 1..2, 2..3, 3..4, 4..5]
+    );
+    assert!(group_ties::<f64>(&[], 2.0).is_empty());
+}
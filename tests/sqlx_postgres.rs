@@ -0,0 +1,161 @@
+#![cfg(feature = "sqlx-postgres")]
+extern crate bbt;
+extern crate sqlx;
+extern crate tokio;
+
+use bbt::Rating;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, Postgres};
+use sqlx::{Type, TypeInfo};
+
+/// The OID of `double precision`.
+const FLOAT8: u32 = 701;
+
+/// Parses a record in PostgreSQL's binary format, as it is sent by the
+/// server: the number of fields, then the type OID, length and value of each
+/// field.
+fn parse_record(mut bytes: &[u8]) -> Vec<(u32, f64)> {
+    fn take<'a>(bytes: &mut &'a [u8], n: usize) -> &'a [u8] {
+        let (head, tail) = bytes.split_at(n);
+        *bytes = tail;
+        head
+    }
+
+    fn take_u32(bytes: &mut &[u8]) -> u32 {
+        let mut buffer = [0; 4];
+        buffer.copy_from_slice(take(bytes, 4));
+        u32::from_be_bytes(buffer)
+    }
+
+    let count = take_u32(&mut bytes);
+    let fields = (0..count)
+        .map(|_| {
+            let oid = take_u32(&mut bytes);
+            assert_eq!(take_u32(&mut bytes), 8);
+
+            let mut value = [0; 8];
+            value.copy_from_slice(take(&mut bytes, 8));
+            (oid, f64::from_be_bytes(value))
+        })
+        .collect();
+
+    assert!(bytes.is_empty());
+    fields
+}
+
+#[test]
+fn ratings_are_the_rating_composite_type() {
+    assert_eq!(<Rating as Type<Postgres>>::type_info().name(), "rating");
+    assert_eq!(
+        <Rating as PgHasArrayType>::array_type_info().name(),
+        "_rating"
+    );
+}
+
+#[test]
+fn encoding_round_trips() {
+    let rater = bbt::Rater::default();
+    let (winner, _) = rater.duel(Rating::default(), Rating::new(31.0, 2.5), bbt::Outcome::Win);
+
+    for rating in [Rating::new(31.5, 2.25), Rating::new(-4.0, 1e-9), winner] {
+        let mut buffer = PgArgumentBuffer::default();
+        assert!(matches!(rating.encode_by_ref(&mut buffer), Ok(IsNull::No)));
+
+        let fields = parse_record(&buffer);
+        assert_eq!(fields, [(FLOAT8, rating.mu()), (FLOAT8, rating.sigma())]);
+
+        // Decoding validates the fields with `Rating::try_new`.
+        let decoded = Rating::try_new(fields[0].1, fields[1].1).unwrap();
+        assert_eq!(
+            (decoded.mu(), decoded.sigma()),
+            (rating.mu(), rating.sigma())
+        );
+    }
+}
+
+/// Runs against the database in `DATABASE_URL`, which needs permission to
+/// create the `rating` type:
+///
+/// ```text
+/// DATABASE_URL=postgres://localhost/bbt cargo test --features sqlx-postgres -- --ignored
+/// ```
+#[test]
+#[ignore]
+fn live_database_round_trip() {
+    use sqlx::{Connection, Executor, PgConnection};
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let mut connection = runtime.block_on(PgConnection::connect(&url)).unwrap();
+    runtime
+        .block_on(connection.execute(
+            "DO $$ BEGIN \
+                 CREATE TYPE rating AS (mu double precision, sigma double precision); \
+             EXCEPTION WHEN duplicate_object THEN NULL; \
+             END $$",
+        ))
+        .unwrap();
+
+    let original = Rating::new(31.5, 2.25);
+    let rating: Rating = runtime
+        .block_on(
+            sqlx::query_scalar("SELECT $1::rating")
+                .bind(original)
+                .fetch_one(&mut connection),
+        )
+        .unwrap();
+    assert_eq!(rating, original);
+
+    let ratings: Vec<Rating> = runtime
+        .block_on(
+            sqlx::query_scalar("SELECT $1::rating[]")
+                .bind(vec![original, Rating::default()])
+                .fetch_one(&mut connection),
+        )
+        .unwrap();
+    assert_eq!(ratings, [original, Rating::default()]);
+
+    let missing: Option<Rating> = runtime
+        .block_on(sqlx::query_scalar("SELECT NULL::rating").fetch_one(&mut connection))
+        .unwrap();
+    assert_eq!(missing, None);
+
+    for invalid in [
+        "SELECT ROW('NaN', 1.0)::rating",
+        "SELECT ROW(25.0, 0.0)::rating",
+        "SELECT ROW(25.0, -1.0)::rating",
+    ] {
+        let error = runtime
+            .block_on(sqlx::query_scalar::<_, Rating>(invalid).fetch_one(&mut connection))
+            .unwrap_err();
+        assert!(
+            matches!(error, sqlx::Error::ColumnDecode { .. }),
+            "{}",
+            error
+        );
+        assert!(error.to_string().contains("invalid rating"), "{}", error);
+    }
+
+    let row = runtime
+        .block_on(
+            sqlx::query("SELECT 31.5::float8 AS mu, 2.25::float8 AS sigma")
+                .fetch_one(&mut connection),
+        )
+        .unwrap();
+    let rating = bbt::postgres::rating_from_columns(&row, "mu", "sigma").unwrap();
+    assert_eq!(rating, Rating::new(31.5, 2.25));
+
+    let row = runtime
+        .block_on(
+            sqlx::query("SELECT 31.5::float8 AS mu, -1.0::float8 AS sigma")
+                .fetch_one(&mut connection),
+        )
+        .unwrap();
+    match bbt::postgres::rating_from_columns(&row, "mu", "sigma") {
+        Err(sqlx::Error::ColumnDecode { index, .. }) => assert_eq!(index, "\"sigma\""),
+        other => panic!("expected a decoding error, got {:?}", other),
+    }
+}
@@ -0,0 +1,421 @@
+extern crate bbt;
+
+#[cfg(feature = "std")]
+use bbt::stats::Movement;
+use bbt::stats::{self, Metric};
+use bbt::{BBTError, Outcome, Rater, Rating};
+
+fn population() -> Vec<Rating> {
+    vec![
+        Rating::new(20.0, 0.0),
+        Rating::new(40.0, 0.0),
+        Rating::new(10.0, 0.0),
+        Rating::new(30.0, 0.0),
+        Rating::new(20.0, 0.0),
+    ]
+}
+
+#[test]
+fn percentile_ranks_use_midranks() {
+    let population = population();
+    let rank = |mu: f64| stats::percentile_rank(&population, &Rating::new(mu, 0.0), Metric::Mu);
+
+    assert_eq!(rank(5.0), Ok(0.0));
+    assert_eq!(rank(10.0), Ok(0.1));
+    assert_eq!(rank(15.0), Ok(0.2));
+    assert_eq!(rank(20.0), Ok(0.4));
+    assert_eq!(rank(40.0), Ok(0.9));
+    assert_eq!(rank(45.0), Ok(1.0));
+
+    let everyone = vec![Rating::new(25.0, 1.0); 4];
+    assert_eq!(
+        stats::percentile_rank(&everyone, &everyone[0], Metric::Mu),
+        Ok(0.5)
+    );
+
+    assert_eq!(
+        stats::percentile_rank(&[], &Rating::default(), Metric::Mu),
+        Err(BBTError::InvalidParameter { name: "population" })
+    );
+}
+
+#[test]
+fn metrics_compare_different_values() {
+    // Conservative estimates of 15, 14 and 24.
+    let population = vec![
+        Rating::new(30.0, 5.0),
+        Rating::new(20.0, 2.0),
+        Rating::new(27.0, 1.0),
+    ];
+    let r = Rating::new(21.0, 2.0);
+
+    assert_eq!(
+        stats::percentile_rank(&population, &r, Metric::ConservativeEstimate),
+        Ok(1.5 / 3.0)
+    );
+    assert_eq!(
+        stats::percentile_rank(&population, &r, Metric::Mu),
+        Ok(1.0 / 3.0)
+    );
+    assert_eq!(Metric::ConservativeEstimate.value(&r), 15.0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn thresholds_interpolate_between_members() {
+    let population = population();
+    let threshold = |p: f64| stats::percentile_threshold(&population, p, Metric::Mu);
+
+    assert_eq!(threshold(0.0), Ok(10.0));
+    assert_eq!(threshold(0.25), Ok(20.0));
+    assert_eq!(threshold(0.375), Ok(20.0));
+    assert_eq!(threshold(0.5), Ok(20.0));
+    assert_eq!(threshold(0.625), Ok(25.0));
+    assert_eq!(threshold(0.875), Ok(35.0));
+    assert_eq!(threshold(1.0), Ok(40.0));
+
+    let single = [Rating::new(30.0, 3.0)];
+    for &p in &[0.0, 0.3, 1.0] {
+        assert_eq!(
+            stats::percentile_threshold(&single, p, Metric::ConservativeEstimate),
+            Ok(21.0)
+        );
+    }
+
+    for &p in &[-0.1, 1.5, f64::NAN] {
+        assert_eq!(threshold(p), Err(BBTError::InvalidParameter { name: "p" }));
+    }
+    assert_eq!(
+        stats::percentile_threshold(&[], 0.5, Metric::Mu),
+        Err(BBTError::InvalidParameter { name: "population" })
+    );
+}
+
+fn drifted() -> Vec<Rating> {
+    vec![
+        Rating::new(31.7, 2.1),
+        Rating::new(24.2, 6.0),
+        Rating::new(28.0, 1.3),
+        Rating::new(35.9, 3.8),
+        Rating::new(19.4, 8.3),
+        Rating::new(28.3, 0.9),
+        Rating::new(27.1, 4.4),
+    ]
+}
+
+fn order_by(ratings: &[Rating], metric: Metric) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..ratings.len()).collect();
+    order.sort_by(|&a, &b| {
+        metric
+            .value(&ratings[a])
+            .partial_cmp(&metric.value(&ratings[b]))
+            .unwrap()
+    });
+
+    order
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn summaries_describe_the_population() {
+    let population = vec![
+        Rating::new(10.0, 1.0),
+        Rating::new(40.0, 4.0),
+        Rating::new(20.0, 2.0),
+        Rating::new(30.0, 3.0),
+    ];
+    let summary = stats::population_summary(&population).unwrap();
+
+    assert_eq!(summary.count, 4);
+    assert_eq!(summary.mean_mu, 25.0);
+    assert!((summary.std_mu - 125.0f64.sqrt()).abs() < 1e-12);
+    assert_eq!(summary.mean_sigma, 2.5);
+    assert_eq!(summary.min_estimate, 7.0);
+    assert_eq!(summary.lower_quartile_estimate, 12.25);
+    assert_eq!(summary.median_estimate, 17.5);
+    assert_eq!(summary.upper_quartile_estimate, 22.75);
+    assert_eq!(summary.max_estimate, 28.0);
+
+    assert_eq!(
+        stats::population_summary(&[]),
+        Err(BBTError::InvalidParameter { name: "population" })
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn renormalization_hits_the_targets() {
+    let mut ratings = drifted();
+    stats::renormalize(&mut ratings, 25.0, None).unwrap();
+
+    let summary = stats::population_summary(&ratings).unwrap();
+    let before = stats::population_summary(&drifted()).unwrap();
+    assert!((summary.mean_mu - 25.0).abs() < 1e-12);
+    assert!((summary.std_mu - before.std_mu).abs() < 1e-12);
+    assert_eq!(summary.mean_sigma, before.mean_sigma);
+
+    let mut ratings = drifted();
+    stats::renormalize(&mut ratings, 25.0, Some(25.0 / 6.0)).unwrap();
+
+    let summary = stats::population_summary(&ratings).unwrap();
+    let scale = (25.0 / 6.0) / before.std_mu;
+    assert!((summary.mean_mu - 25.0).abs() < 1e-12);
+    assert!((summary.std_mu - 25.0 / 6.0).abs() < 1e-12);
+    for (after, before) in ratings.iter().zip(drifted()) {
+        assert!((after.sigma() - before.sigma() * scale).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn renormalization_preserves_the_order() {
+    for &target_std in &[None, Some(2.0), Some(20.0)] {
+        let mut ratings = drifted();
+        stats::renormalize(&mut ratings, 25.0, target_std).unwrap();
+
+        for &metric in &[Metric::Mu, Metric::ConservativeEstimate] {
+            assert_eq!(order_by(&ratings, metric), order_by(&drifted(), metric));
+        }
+    }
+
+    // Without spread, the ratings are only shifted.
+    let mut equal = vec![Rating::new(28.0, 3.0); 3];
+    stats::renormalize(&mut equal, 25.0, Some(8.0)).unwrap();
+    assert_eq!(equal, vec![Rating::new(25.0, 3.0); 3]);
+
+    let mut ratings = drifted();
+    assert_eq!(
+        stats::renormalize(&mut ratings, f64::NAN, None),
+        Err(BBTError::InvalidParameter {
+            name: "target_mean"
+        })
+    );
+    for &target_std in &[0.0, -1.0, f64::INFINITY] {
+        assert_eq!(
+            stats::renormalize(&mut ratings, 25.0, Some(target_std)),
+            Err(BBTError::InvalidParameter { name: "target_std" })
+        );
+    }
+    assert_eq!(ratings, drifted());
+    assert_eq!(stats::renormalize(&mut [], 25.0, Some(8.0)), Ok(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn standings_diffs_track_every_player() {
+    let before = vec![
+        ("dora", Rating::new(24.0, 1.0)),
+        ("alice", Rating::new(30.0, 1.0)),
+        ("carol", Rating::new(26.0, 1.0)),
+        ("bob", Rating::new(28.0, 1.0)),
+        ("emil", Rating::new(20.0, 1.0)),
+    ];
+    let after = vec![
+        ("alice", Rating::new(29.0, 1.0)),
+        ("bob", Rating::new(27.5, 1.0)),
+        ("carol", Rating::new(32.0, 1.0)),
+        ("emil", Rating::new(20.0, 1.0)),
+        ("finn", Rating::new(25.0, 1.0)),
+    ];
+
+    let movement = |id, old_rank, new_rank, rank_delta, mu_delta| Movement {
+        id,
+        old_rank: Some(old_rank),
+        new_rank: Some(new_rank),
+        rank_delta: Some(rank_delta),
+        mu_delta: Some(mu_delta),
+        is_new: false,
+        is_removed: false,
+    };
+
+    assert_eq!(
+        stats::standings_diff(&before, &after),
+        vec![
+            movement("carol", 3, 1, 2, 6.0),
+            movement("alice", 1, 2, -1, -1.0),
+            movement("bob", 2, 3, -1, -0.5),
+            Movement {
+                id: "finn",
+                old_rank: None,
+                new_rank: Some(4),
+                rank_delta: None,
+                mu_delta: None,
+                is_new: true,
+                is_removed: false,
+            },
+            movement("emil", 5, 5, 0, 0.0),
+            Movement {
+                id: "dora",
+                old_rank: Some(4),
+                new_rank: None,
+                rank_delta: None,
+                mu_delta: None,
+                is_new: false,
+                is_removed: true,
+            },
+        ]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn standings_ties_keep_the_snapshot_order() {
+    let tied = Rating::new(25.0, 2.0);
+    let before = vec![("a", tied), ("b", tied), ("c", tied)];
+    let after = vec![("c", tied), ("a", tied), ("b", tied)];
+
+    let ranks: Vec<_> = stats::standings_diff(&before, &after)
+        .into_iter()
+        .map(|movement| (movement.id, movement.old_rank, movement.new_rank))
+        .collect();
+
+    assert_eq!(
+        ranks,
+        vec![
+            ("c", Some(3), Some(1)),
+            ("a", Some(1), Some(2)),
+            ("b", Some(2), Some(3)),
+        ]
+    );
+    assert!(stats::standings_diff::<&str>(&[], &[]).is_empty());
+}
+
+#[test]
+fn default_opponents_have_the_default_strength() {
+    let games = vec![
+        (Rating::default(), Outcome::Win),
+        (Rating::default(), Outcome::Loss),
+        (Rating::default(), Outcome::Draw),
+    ];
+
+    let report = stats::strength_of_schedule(&Rater::default(), &games).unwrap();
+    assert_eq!(report.games, 3);
+    assert_eq!(report.mean_mu, 25.0);
+    assert!((report.mean_conservative - 0.0).abs() < 1e-12);
+    assert!((report.opponent_win_probability - 0.5).abs() < 1e-12);
+    assert_eq!(report.score, 0.5);
+}
+
+#[test]
+fn stronger_opposition_is_a_stronger_schedule() {
+    let rater = Rater::default();
+    let outcomes = [Outcome::Win, Outcome::Loss, Outcome::Win, Outcome::Draw];
+
+    // Both players have the same results, but against different opponents.
+    let strong: Vec<(Rating, Outcome)> = [32.0, 29.0, 35.0, 30.0]
+        .iter()
+        .zip(&outcomes)
+        .map(|(&mu, &outcome)| (Rating::new(mu, 2.0), outcome))
+        .collect();
+    let weak: Vec<(Rating, Outcome)> = [21.0, 18.0, 24.0, 19.0]
+        .iter()
+        .zip(&outcomes)
+        .map(|(&mu, &outcome)| (Rating::new(mu, 2.0), outcome))
+        .collect();
+
+    let strong = stats::strength_of_schedule(&rater, &strong).unwrap();
+    let weak = stats::strength_of_schedule(&rater, &weak).unwrap();
+
+    assert_eq!(strong.score, weak.score);
+    assert_eq!(strong.mean_mu, 31.5);
+    assert_eq!(weak.mean_mu, 20.5);
+    assert!(strong.mean_conservative > weak.mean_conservative);
+    assert!(strong.opponent_win_probability > weak.opponent_win_probability);
+}
+
+#[test]
+fn empty_schedules_are_rejected() {
+    assert_eq!(
+        stats::strength_of_schedule(&Rater::default(), &[]),
+        Err(BBTError::InvalidParameter {
+            name: "player_games"
+        })
+    );
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+#[test]
+fn schedules_are_read_from_the_history() {
+    use bbt::replay::{self, GameRecord};
+
+    let ids = |team: &[&str]| team.iter().map(|id| id.to_string()).collect();
+    let games = vec![
+        GameRecord::new(vec![ids(&["ann"]), ids(&["bob"])], vec![1, 2]),
+        GameRecord::new(vec![ids(&["cid"]), ids(&["dan"])], vec![2, 1]),
+        GameRecord::new(
+            vec![ids(&["bob", "cid"]), ids(&["ann"]), ids(&["dan"])],
+            vec![2, 1, 3],
+        ),
+    ];
+
+    let rater = Rater::default();
+    let ratings_before_last = replay::replay(&rater, &games[..2], Rating::default()).unwrap();
+
+    let schedule = replay::schedule(&rater, &games, "bob", Rating::default()).unwrap();
+    assert_eq!(
+        schedule,
+        vec![
+            (Rating::default(), Outcome::Loss),
+            (ratings_before_last["ann"], Outcome::Loss),
+            (ratings_before_last["dan"], Outcome::Win),
+        ]
+    );
+
+    assert_eq!(
+        replay::schedule(&rater, &games, "eve", Rating::default()).unwrap(),
+        vec![]
+    );
+}
+
+fn mus(mus: &[f64]) -> Vec<Rating> {
+    mus.iter().map(|&mu| Rating::new(mu, 3.0)).collect()
+}
+
+#[test]
+fn constant_histories_have_no_volatility() {
+    let history = mus(&[25.0; 10]);
+
+    assert_eq!(stats::volatility(&history, 5), 0.0);
+    assert_eq!(stats::volatility(&history, 100), 0.0);
+    assert_eq!(stats::volatility(&history[..1], 5), 0.0);
+    assert_eq!(stats::volatility(&[], 5), 0.0);
+    assert_eq!(stats::volatility(&mus(&[20.0, 30.0]), 0), 0.0);
+}
+
+#[test]
+fn alternating_histories_are_volatile() {
+    // The changes alternate between +1 and -1.
+    let history = mus(&[25.0, 26.0, 25.0, 26.0, 25.0, 26.0, 25.0]);
+
+    // An even number of changes has mean zero and deviation one.
+    assert_eq!(stats::volatility(&history, 4), 1.0);
+    assert_eq!(stats::volatility(&history, 6), 1.0);
+
+    // The last three changes are -1, +1 and -1, with mean -1/3:
+    // ((2/3)^2 + (4/3)^2 + (2/3)^2) / 3 = 8/9.
+    let expected = (8.0f64 / 9.0).sqrt();
+    assert!((stats::volatility(&history, 3) - expected).abs() < 1e-12);
+
+    // A window larger than the history uses all six changes.
+    assert_eq!(stats::volatility(&history, 50), 1.0);
+}
+
+#[test]
+fn volatility_series_follow_the_history() {
+    let history = mus(&[25.0, 26.0, 25.0, 26.0, 30.0]);
+    let series = stats::volatility_series(&history, 2);
+
+    assert_eq!(series.len(), history.len());
+    assert_eq!(series[0], 0.0);
+    // A single change has no deviation.
+    assert_eq!(series[1], 0.0);
+    assert_eq!(series[2], 1.0);
+    assert_eq!(series[3], 1.0);
+    // The changes +1 and +4 have mean 2.5 and deviation 1.5.
+    assert_eq!(series[4], 1.5);
+
+    for (end, &value) in series.iter().enumerate() {
+        assert_eq!(value, stats::volatility(&history[..=end], 2));
+    }
+
+    assert!(stats::volatility_series(&[], 3).is_empty());
+}
@@ -0,0 +1,107 @@
+#![cfg(all(feature = "rand", feature = "serde"))]
+
+extern crate bbt;
+extern crate rand;
+
+use bbt::replay;
+use bbt::synth::{self, Schedule};
+use bbt::{BBTError, Rater, Rating};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+const SKILLS: [f64; 6] = [40.0, 20.0, 35.0, 25.0, 30.0, 15.0];
+
+/// Replays the games and returns the final rating of every player, in the
+/// order of `SKILLS`.
+fn final_ratings(games: &[replay::GameRecord]) -> Vec<Rating> {
+    let ratings = replay::replay(&Rater::default(), games, Rating::default()).unwrap();
+
+    (0..SKILLS.len())
+        .map(|player| {
+            ratings
+                .get(&player.to_string())
+                .cloned()
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[test]
+fn random_pairings_recover_the_skills() {
+    let mut rng = SmallRng::seed_from_u64(723);
+    let games = synth::generate(&SKILLS, 25.0 / 6.0, 3000, Schedule::Random, &mut rng).unwrap();
+    assert_eq!(games.len(), 3000);
+
+    let ratings = final_ratings(&games);
+    let error = synth::recovery_error(&SKILLS, &ratings);
+    assert!(error < 1.5, "recovery error {}", error);
+
+    let mut by_skill: Vec<usize> = (0..SKILLS.len()).collect();
+    by_skill.sort_by(|&a, &b| SKILLS[b].partial_cmp(&SKILLS[a]).unwrap());
+    let mut by_mu: Vec<usize> = (0..SKILLS.len()).collect();
+    by_mu.sort_by(|&a, &b| ratings[b].mu().partial_cmp(&ratings[a].mu()).unwrap());
+    assert_eq!(by_mu, by_skill);
+}
+
+#[test]
+fn every_schedule_recovers_the_skills() {
+    for &schedule in &[Schedule::RoundRobin, Schedule::Matchmade] {
+        let mut rng = SmallRng::seed_from_u64(724);
+        let games = synth::generate(&SKILLS, 25.0 / 6.0, 3000, schedule, &mut rng).unwrap();
+
+        let error = synth::recovery_error(&SKILLS, &final_ratings(&games));
+        assert!(error < 2.0, "{:?}: recovery error {}", schedule, error);
+    }
+}
+
+#[test]
+fn round_robins_go_through_every_pair_in_turn() {
+    let mut rng = SmallRng::seed_from_u64(725);
+    let games = synth::generate(&[1.0, 2.0, 3.0], 1.0, 4, Schedule::RoundRobin, &mut rng).unwrap();
+
+    let pairs: Vec<(String, String)> = games
+        .iter()
+        .map(|game| (game.teams[0][0].clone(), game.teams[1][0].clone()))
+        .collect();
+    let expected = [("0", "1"), ("0", "2"), ("1", "2"), ("0", "1")];
+    assert_eq!(pairs.len(), expected.len());
+    for (pair, &(a, b)) in pairs.iter().zip(&expected) {
+        assert_eq!((pair.0.as_str(), pair.1.as_str()), (a, b));
+    }
+}
+
+#[test]
+fn zero_games_leave_the_ratings_at_the_prior() {
+    let mut rng = SmallRng::seed_from_u64(726);
+    let games = synth::generate(&SKILLS, 25.0 / 6.0, 0, Schedule::Random, &mut rng).unwrap();
+    assert!(games.is_empty());
+
+    let ratings = final_ratings(&games);
+    assert!(ratings.iter().all(|rating| *rating == Rating::default()));
+
+    // Equal mus are aligned to the mean skill.
+    let mean = SKILLS.iter().sum::<f64>() / SKILLS.len() as f64;
+    let deviation =
+        (SKILLS.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / SKILLS.len() as f64).sqrt();
+    assert!((synth::recovery_error(&SKILLS, &ratings) - deviation).abs() < 1e-12);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let mut rng = SmallRng::seed_from_u64(727);
+
+    assert_eq!(
+        synth::generate(&SKILLS, -1.0, 10, Schedule::Random, &mut rng),
+        Err(BBTError::InvalidParameter { name: "beta" })
+    );
+    assert_eq!(
+        synth::generate(&[25.0], 1.0, 10, Schedule::Matchmade, &mut rng),
+        Err(BBTError::InvalidParameter {
+            name: "true_skills"
+        })
+    );
+    assert_eq!(
+        synth::generate(&[25.0], 1.0, 0, Schedule::Matchmade, &mut rng),
+        Ok(vec![])
+    );
+}
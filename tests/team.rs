@@ -0,0 +1,81 @@
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating, TeamRating};
+
+#[test]
+fn aggregates_members() {
+    let pair: TeamRating = TeamRating::from_members(&[Rating::default(), Rating::default()]);
+    let default: Rating = Rating::default();
+
+    assert_eq!(pair.mu(), 50.0);
+    assert!((pair.sigma() - (2.0f64).sqrt() * default.sigma()).abs() < 1e-12);
+    assert_eq!(pair.conservative_estimate(), pair.mu() - 3.0 * pair.sigma());
+
+    let single = TeamRating::from_members(&[Rating::default()]);
+    assert_eq!(single.as_rating(), &default);
+
+    let empty = TeamRating::<f64>::from_members(&[]);
+    assert_eq!((empty.mu(), empty.sigma()), (0.0, 0.0));
+}
+
+#[test]
+fn two_defaults_beat_one() {
+    let rater = Rater::default();
+    let pair: TeamRating = TeamRating::from_members(&[Rating::default(), Rating::default()]);
+    let single = TeamRating::from_members(&[Rating::default()]);
+
+    let probability = rater.win_probability(pair.as_rating(), single.as_rating());
+    assert!(probability > 0.5, "{}", probability);
+    assert!(
+        (probability + rater.win_probability(single.as_rating(), pair.as_rating()) - 1.0).abs()
+            < 1e-15
+    );
+}
+
+#[test]
+fn clan_ladder_duels_rate_synthetic_players() {
+    let rater = Rater::default();
+    let clan_a = TeamRating::from_members(&[
+        Rating::new(27.0, 3.0),
+        Rating::new(24.0, 5.0),
+        Rating::new(30.0, 2.0),
+    ]);
+    let clan_b = TeamRating::from_members(&[Rating::new(40.0, 4.0), Rating::new(35.0, 6.0)]);
+
+    for &outcome in &[Outcome::Win, Outcome::Loss, Outcome::Draw] {
+        let (a, b) = rater.team_duel_rating(&clan_a, &clan_b, outcome);
+        let (synthetic_a, synthetic_b) = rater.duel(
+            Rating::new(clan_a.mu(), clan_a.sigma()),
+            Rating::new(clan_b.mu(), clan_b.sigma()),
+            outcome,
+        );
+
+        for &(team, synthetic) in &[(&a, &synthetic_a), (&b, &synthetic_b)] {
+            assert!((team.mu() - synthetic.mu()).abs() < 1e-12, "{:?}", outcome);
+            assert!(
+                (team.sigma() - synthetic.sigma()).abs() < 1e-12,
+                "{:?}",
+                outcome
+            );
+        }
+    }
+
+    // Unlike updating the members, the whole update lands on the aggregate.
+    let (a, _) = rater.team_duel_rating(&clan_a, &clan_b, Outcome::Win);
+    let members = rater
+        .update_ratings(
+            vec![
+                vec![
+                    Rating::new(27.0, 3.0),
+                    Rating::new(24.0, 5.0),
+                    Rating::new(30.0, 2.0),
+                ],
+                vec![Rating::new(40.0, 4.0), Rating::new(35.0, 6.0)],
+            ],
+            vec![1, 2],
+        )
+        .unwrap();
+    let reaggregated = TeamRating::from_members(&members[0]);
+    assert!((a.mu() - reaggregated.mu()).abs() < 1e-9);
+    assert!(a.sigma() < reaggregated.sigma());
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use bbt::{BBTError, Rating, TierLadder};
+
+fn ladder() -> TierLadder {
+    TierLadder::new(&["Bronze", "Silver", "Gold", "Diamond"], &[0.0, 10.0, 20.0]).unwrap()
+}
+
+/// Returns a rating with the given conservative estimate.
+fn estimate(value: f64) -> Rating {
+    Rating::new(value + 6.0, 2.0)
+}
+
+#[test]
+fn boundaries_belong_to_the_higher_tier() {
+    let ladder = ladder();
+    let name = |value: f64| ladder.tier_for(&estimate(value)).name().to_string();
+
+    assert_eq!(name(-100.0), "Bronze");
+    assert_eq!(name(-0.5), "Bronze");
+    assert_eq!(name(0.0), "Silver");
+    assert_eq!(name(9.5), "Silver");
+    assert_eq!(name(10.0), "Gold");
+    assert_eq!(name(20.0), "Diamond");
+    assert_eq!(name(1000.0), "Diamond");
+
+    let gold = ladder.tier_for(&estimate(15.0));
+    assert_eq!(gold.index(), 2);
+    assert_eq!(gold.floor(), Some(10.0));
+    assert_eq!(ladder.tiers()[0].floor(), None);
+
+    // A single tier without boundaries holds everyone.
+    let flat = TierLadder::new(&["Everyone"], &[]).unwrap();
+    assert_eq!(flat.tier_for(&Rating::default()).name(), "Everyone");
+}
+
+#[test]
+fn hysteresis_prevents_demotion_within_the_band() {
+    let ladder = ladder();
+    let gold = ladder.tier_for(&estimate(10.0)).clone();
+
+    assert_eq!(ladder.promote_demote(&gold, &estimate(9.0), 2.0), &gold);
+    assert_eq!(ladder.promote_demote(&gold, &estimate(8.0), 2.0), &gold);
+    assert_eq!(
+        ladder.promote_demote(&gold, &estimate(7.5), 2.0).name(),
+        "Silver"
+    );
+    // Falling far enough skips tiers.
+    assert_eq!(
+        ladder.promote_demote(&gold, &estimate(-3.0), 2.0).name(),
+        "Bronze"
+    );
+    // Without hysteresis, the boundary is exact.
+    assert_eq!(
+        ladder.promote_demote(&gold, &estimate(9.5), 0.0).name(),
+        "Silver"
+    );
+
+    // Promotions are not delayed.
+    assert_eq!(
+        ladder.promote_demote(&gold, &estimate(20.0), 2.0).name(),
+        "Diamond"
+    );
+    let silver = ladder.tier_for(&estimate(5.0));
+    assert_eq!(ladder.promote_demote(silver, &estimate(10.0), 2.0), &gold);
+}
+
+#[test]
+fn invalid_ladders_are_rejected() {
+    let boundaries = Err(BBTError::InvalidParameter { name: "boundaries" });
+
+    assert_eq!(TierLadder::new(&["A", "B", "C"], &[10.0, 0.0]), boundaries);
+    assert_eq!(TierLadder::new(&["A", "B", "C"], &[5.0, 5.0]), boundaries);
+    assert_eq!(TierLadder::new(&["A", "B"], &[f64::NAN]), boundaries);
+    assert_eq!(TierLadder::new(&["A", "B"], &[f64::INFINITY]), boundaries);
+    assert_eq!(
+        TierLadder::new(&["A", "B"], &[1.0, 2.0]),
+        Err(BBTError::InvalidParameter { name: "names" })
+    );
+    assert_eq!(
+        TierLadder::new::<&str>(&[], &[]),
+        Err(BBTError::InvalidParameter { name: "names" })
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn ladders_round_trip_through_config() {
+    let ladder = ladder();
+    let json = serde_json::to_string(&ladder).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"names":["Bronze","Silver","Gold","Diamond"],"boundaries":[0.0,10.0,20.0]}"#
+    );
+    assert_eq!(serde_json::from_str::<TierLadder>(&json).unwrap(), ladder);
+    assert_eq!(
+        serde_json::from_str::<TierLadder>(r#"[["Low","High"],[3.0]]"#)
+            .unwrap()
+            .tiers()[1]
+            .floor(),
+        Some(3.0)
+    );
+
+    assert!(serde_json::from_str::<TierLadder>(
+        r#"{"names":["A","B","C"],"boundaries":[2.0,1.0]}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<TierLadder>(r#"{"names":["A"]}"#).is_err());
+}
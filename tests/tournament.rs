@@ -0,0 +1,548 @@
+#![cfg(feature = "alloc")]
+extern crate bbt;
+#[cfg(feature = "rand")]
+extern crate rand;
+
+use bbt::tournament::{self, ExpectedRecord, PointsScheme};
+use bbt::{BBTError, Rater, Rating};
+
+fn field() -> Vec<Rating> {
+    vec![
+        Rating::new(24.0, 3.0),
+        Rating::new(31.0, 2.0),
+        Rating::new(18.0, 5.0),
+        Rating::new(26.0, 1.5),
+    ]
+}
+
+#[test]
+fn equal_entrants_have_equal_records() {
+    let rater = Rater::default();
+    let entrants = vec![Rating::default(); 5];
+
+    for &double in &[false, true] {
+        let records =
+            tournament::round_robin_with_draw_margin(&rater, &entrants, double, 1.0).unwrap();
+        let games = if double { 8.0 } else { 4.0 };
+
+        for record in &records {
+            assert_eq!(record, &records[0]);
+            assert!((record.wins - record.losses).abs() < 1e-12);
+            assert!((record.wins + record.draws + record.losses - games).abs() < 1e-12);
+            assert!(record.draws > 0.0);
+        }
+    }
+
+    let records = tournament::round_robin(&rater, &entrants, false);
+    assert_eq!(
+        records[0],
+        ExpectedRecord {
+            wins: 2.0,
+            draws: 0.0,
+            losses: 2.0
+        }
+    );
+}
+
+#[test]
+fn records_follow_the_win_probabilities() {
+    let rater = Rater::default();
+    let entrants = field();
+    let records = tournament::round_robin(&rater, &entrants, true);
+
+    for (i, record) in records.iter().enumerate() {
+        let wins: f64 = (0..entrants.len())
+            .filter(|&j| j != i)
+            .map(|j| 2.0 * rater.win_probability(&entrants[i], &entrants[j]))
+            .sum();
+
+        assert!((record.wins - wins).abs() < 1e-12);
+        assert!((record.losses - (6.0 - wins)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn the_strongest_entrant_leads_the_standings() {
+    let records = tournament::round_robin(&Rater::default(), &field(), false);
+
+    assert_eq!(
+        tournament::standings(&records, &PointsScheme::default()),
+        vec![1, 3, 0, 2]
+    );
+    // With 2/1/0, draws are worth half a win, but the order is the same.
+    let records =
+        tournament::round_robin_with_draw_margin(&Rater::default(), &field(), false, 2.0).unwrap();
+    assert_eq!(
+        tournament::standings(&records, &PointsScheme::new(2.0, 1.0, 0.0)),
+        vec![1, 3, 0, 2]
+    );
+
+    assert_eq!(
+        tournament::round_robin(&Rater::default(), &[], false),
+        vec![]
+    );
+    assert_eq!(
+        tournament::round_robin_with_draw_margin(&Rater::default(), &field(), false, -1.0),
+        Err(BBTError::InvalidParameter {
+            name: "draw_margin"
+        })
+    );
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn simulations_estimate_placements() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    let rater = Rater::default();
+    let entrants = field();
+    let scheme = PointsScheme::default();
+    let mut rng = SmallRng::seed_from_u64(681);
+
+    let standings =
+        tournament::simulate_round_robin(&rater, &entrants, false, 1.0, &scheme, 20_000, &mut rng)
+            .unwrap();
+
+    for standing in &standings {
+        let total: f64 = standing.placements.iter().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let games = standing.record.wins + standing.record.draws + standing.record.losses;
+        assert!((games - 3.0).abs() < 1e-9);
+    }
+
+    // Every placement is taken by exactly one entrant.
+    for placement in 0..entrants.len() {
+        let total: f64 = standings
+            .iter()
+            .map(|standing| standing.placements[placement])
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    let first = |index: usize| standings[index].placements[0];
+    assert!(first(1) > first(3) && first(3) > first(0) && first(0) > first(2));
+    let points: Vec<_> = standings
+        .iter()
+        .map(|standing| standing.record.points(&scheme))
+        .collect();
+    assert!(points[1] > points[3] && points[3] > points[0] && points[0] > points[2]);
+
+    // Equal entrants have equal chances, up to sampling noise.
+    let equal = vec![Rating::default(); 4];
+    let standings =
+        tournament::simulate_round_robin(&rater, &equal, true, 0.0, &scheme, 20_000, &mut rng)
+            .unwrap();
+    for standing in &standings {
+        for &probability in &standing.placements {
+            assert!((probability - 0.25).abs() < 0.02, "{:?}", standing);
+        }
+    }
+
+    assert_eq!(
+        tournament::simulate_round_robin(&rater, &equal, true, 0.0, &scheme, 0, &mut rng),
+        Err(BBTError::InvalidParameter {
+            name: "simulations"
+        })
+    );
+}
+
+#[cfg(feature = "std")]
+mod swiss {
+    use std::collections::HashSet;
+
+    use bbt::tournament::swiss_pairings;
+    use bbt::{Rater, Rating};
+
+    fn assert_round(pairings: &[(usize, usize)], players: usize) {
+        let mut seen: Vec<usize> = pairings.iter().flat_map(|&(a, b)| vec![a, b]).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen, (0..players).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn equal_players_meet_new_opponents() {
+        let rater = Rater::default();
+        let players: Vec<(usize, Rating)> = (0..8).map(|id| (id, Rating::default())).collect();
+        let mut previous = HashSet::new();
+
+        for _ in 0..2 {
+            let pairings = swiss_pairings(&rater, &players, &previous);
+
+            assert_eq!(pairings.len(), 4);
+            assert_round(&pairings, 8);
+            assert_eq!(pairings, swiss_pairings(&rater, &players, &previous));
+
+            for &(a, b) in &pairings {
+                assert!(!previous.contains(&(a, b)) && !previous.contains(&(b, a)));
+                previous.insert((a, b));
+            }
+        }
+
+        assert_eq!(previous.len(), 8);
+    }
+
+    #[test]
+    fn repeats_are_avoided_when_possible() {
+        let rater = Rater::default();
+        let players = [
+            ('a', Rating::new(30.0, 1.0)),
+            ('b', Rating::new(29.0, 1.0)),
+            ('c', Rating::new(20.0, 1.0)),
+            ('d', Rating::new(19.0, 1.0)),
+        ];
+
+        let mut previous = HashSet::new();
+        assert_eq!(
+            swiss_pairings(&rater, &players, &previous),
+            vec![('a', 'b'), ('c', 'd')]
+        );
+
+        // Pairing a with b first would leave c and d to meet again.
+        previous.insert(('d', 'c'));
+        assert_eq!(
+            swiss_pairings(&rater, &players, &previous),
+            vec![('a', 'c'), ('b', 'd')]
+        );
+
+        // Once everyone has met, the best matches are repeated.
+        for &pair in &[('a', 'b'), ('a', 'c'), ('a', 'd'), ('b', 'c'), ('b', 'd')] {
+            previous.insert(pair);
+        }
+        assert_eq!(
+            swiss_pairings(&rater, &players, &previous),
+            vec![('a', 'b'), ('c', 'd')]
+        );
+    }
+
+    #[test]
+    fn the_lowest_rated_player_without_a_bye_gets_one() {
+        let rater = Rater::default();
+        let players: Vec<(usize, Rating)> = (0..5)
+            .map(|id| (id, Rating::new(30.0 - id as f64, 2.0)))
+            .collect();
+
+        let mut previous = HashSet::new();
+        let pairings = swiss_pairings(&rater, &players, &previous);
+        assert_eq!(pairings.len(), 3);
+        assert_round(&pairings, 5);
+        assert_eq!(pairings[2], (4, 4));
+
+        previous.insert((4, 4));
+        previous.insert((3, 3));
+        assert_eq!(swiss_pairings(&rater, &players, &previous)[2], (2, 2));
+
+        // If everyone had a bye, the lowest-rated player gets another.
+        for id in 0..5 {
+            previous.insert((id, id));
+        }
+        assert_eq!(swiss_pairings(&rater, &players, &previous)[2], (4, 4));
+
+        assert!(swiss_pairings(&rater, &players[..0], &previous).is_empty());
+        assert_eq!(
+            swiss_pairings(&rater, &players[..1], &previous),
+            vec![(0, 0)]
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+mod season {
+    use bbt::tournament::{project_season, PointsScheme};
+    use bbt::{BBTError, Rater, Rating};
+
+    fn table() -> Vec<(&'static str, Rating, f64)> {
+        vec![
+            ("ash", Rating::new(25.0, 2.0), 50.0),
+            ("birch", Rating::new(25.0, 2.0), 52.0),
+            ("cedar", Rating::new(35.0, 2.0), 30.0),
+            ("dogwood", Rating::new(15.0, 2.0), 20.0),
+            ("elm", Rating::new(36.0, 2.0), 45.0),
+        ]
+    }
+
+    #[test]
+    fn without_fixtures_the_projection_is_the_table() {
+        let rows = project_season(
+            &Rater::default(),
+            &table(),
+            &[],
+            &PointsScheme::default(),
+            1.0,
+        )
+        .unwrap();
+
+        let ids: Vec<_> = rows.iter().map(|row| row.id).collect();
+        assert_eq!(ids, vec!["birch", "ash", "elm", "cedar", "dogwood"]);
+
+        for row in &rows {
+            assert_eq!(row.expected_points, row.points);
+            assert_eq!(
+                row.remaining.wins + row.remaining.draws + row.remaining.losses,
+                0.0
+            );
+        }
+    }
+
+    #[test]
+    fn easy_fixtures_overtake_hard_ones() {
+        let rater = Rater::default();
+        let scheme = PointsScheme::default();
+        // Ash plays the weakest team twice, birch the two strongest ones.
+        let remaining = [
+            ("ash", "dogwood"),
+            ("dogwood", "ash"),
+            ("birch", "cedar"),
+            ("elm", "birch"),
+        ];
+
+        let rows = project_season(&rater, &table(), &remaining, &scheme, 1.0).unwrap();
+        let row = |id: &str| rows.iter().find(|row| row.id == id).unwrap();
+
+        assert_eq!(rows[0].id, "ash");
+        assert_eq!(rows[1].id, "birch");
+        assert!(row("ash").expected_points > row("birch").expected_points);
+
+        let games = |id: &str| {
+            let record = &row(id).remaining;
+            record.wins + record.draws + record.losses
+        };
+        assert!((games("ash") - 2.0).abs() < 1e-12);
+        assert!((games("dogwood") - 2.0).abs() < 1e-12);
+        assert!((games("elm") - 1.0).abs() < 1e-12);
+
+        // Every fixture hands out between two and three points.
+        let added: f64 = rows
+            .iter()
+            .map(|row| row.expected_points - row.points)
+            .sum();
+        assert!(added > 8.0 && added < 12.0);
+    }
+
+    #[test]
+    fn unknown_teams_are_rejected() {
+        let rater = Rater::default();
+        let scheme = PointsScheme::default();
+
+        for remaining in &[[("ash", "oak")], [("oak", "ash")], [("ash", "ash")]] {
+            assert_eq!(
+                project_season(&rater, &table(), remaining, &scheme, 1.0),
+                Err(BBTError::InvalidParameter { name: "remaining" })
+            );
+        }
+
+        let mut duplicated = table();
+        duplicated.push(("ash", Rating::default(), 0.0));
+        assert_eq!(
+            project_season(&rater, &duplicated, &[], &scheme, 1.0),
+            Err(BBTError::InvalidParameter { name: "table" })
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn simulations_estimate_the_title_and_relegation() {
+        use bbt::tournament::simulate_season;
+        use rand::rngs::SmallRng;
+        use rand::SeedableRng;
+
+        let rater = Rater::default();
+        let scheme = PointsScheme::default();
+        let mut rng = SmallRng::seed_from_u64(705);
+        let remaining = [
+            ("ash", "dogwood"),
+            ("dogwood", "ash"),
+            ("birch", "cedar"),
+            ("elm", "birch"),
+            ("cedar", "elm"),
+        ];
+
+        let rows = simulate_season(
+            &rater,
+            &table(),
+            &remaining,
+            &scheme,
+            1.0,
+            2,
+            20_000,
+            &mut rng,
+        )
+        .unwrap();
+        let projected = project_season(&rater, &table(), &remaining, &scheme, 1.0).unwrap();
+
+        let ids: Vec<_> = rows.iter().map(|row| row.id).collect();
+        let projected_ids: Vec<_> = projected.iter().map(|row| row.id).collect();
+        assert_eq!(ids, projected_ids);
+
+        let titles: f64 = rows.iter().map(|row| row.title_probability).sum();
+        let relegations: f64 = rows.iter().map(|row| row.relegation_probability).sum();
+        assert!((titles - 1.0).abs() < 1e-9);
+        assert!((relegations - 2.0).abs() < 1e-9);
+
+        for (row, projected) in rows.iter().zip(&projected) {
+            assert!((row.expected_points - projected.expected_points).abs() < 0.1);
+        }
+
+        // Dogwood cannot catch up with anyone, and only ash or birch can win.
+        let row = |id: &str| rows.iter().find(|row| row.id == id).unwrap();
+        assert_eq!(row("dogwood").relegation_probability, 1.0);
+        assert!(
+            (row("ash").title_probability + row("birch").title_probability - 1.0).abs() < 1e-12
+        );
+
+        assert_eq!(
+            simulate_season(&rater, &table(), &remaining, &scheme, 1.0, 6, 100, &mut rng),
+            Err(BBTError::InvalidParameter { name: "relegated" })
+        );
+        assert_eq!(
+            simulate_season(&rater, &table(), &remaining, &scheme, 1.0, 2, 0, &mut rng),
+            Err(BBTError::InvalidParameter {
+                name: "simulations"
+            })
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+mod bracket {
+    use std::collections::HashMap;
+
+    use bbt::tournament::{Bracket, BracketError};
+    use bbt::{Outcome, Rater, Rating};
+
+    fn field(players: usize) -> HashMap<usize, Rating> {
+        (0..players)
+            .map(|id| (id, Rating::new(20.0 + id as f64, 3.0 + 0.5 * id as f64)))
+            .collect()
+    }
+
+    #[test]
+    fn a_full_bracket_rates_like_its_duels() {
+        let rater = Rater::default();
+        let mut bracket = Bracket::new((0..8).collect()).unwrap();
+        assert_eq!(bracket.rounds(), 3);
+
+        // The first round is 0-7, 3-4, 1-6 and 2-5, by seed.
+        let results = [
+            (0, 0, 0),
+            (0, 1, 4),
+            (0, 2, 6),
+            (0, 3, 2),
+            (1, 0, 4),
+            (1, 1, 2),
+            (2, 0, 2),
+        ];
+        // Reported out of order, since the order of the rounds is decided by
+        // the bracket.
+        for &(round, match_idx, winner) in results.iter().rev() {
+            bracket.set_result(round, match_idx, winner).unwrap();
+        }
+
+        let mut ratings = field(8);
+        bracket.apply(&rater, &mut ratings).unwrap();
+
+        let mut expected = field(8);
+        for &(a, b, outcome) in &[
+            (0, 7, Outcome::Win),
+            (3, 4, Outcome::Loss),
+            (1, 6, Outcome::Loss),
+            (2, 5, Outcome::Win),
+            (0, 4, Outcome::Loss),
+            (6, 2, Outcome::Loss),
+            (4, 2, Outcome::Loss),
+        ] {
+            let (new_a, new_b) = rater.duel(expected[&a], expected[&b], outcome);
+            expected.insert(a, new_a);
+            expected.insert(b, new_b);
+        }
+
+        assert_eq!(ratings, expected);
+    }
+
+    #[test]
+    fn byes_are_not_rated() {
+        let rater = Rater::default();
+        // With six entrants, the first two seeds have byes.
+        let mut bracket = Bracket::new((0..6).collect()).unwrap();
+        bracket.set_result(0, 1, 4).unwrap();
+        bracket.set_result(0, 3, 2).unwrap();
+
+        let mut ratings = field(6);
+        bracket.apply(&rater, &mut ratings).unwrap();
+
+        let initial = field(6);
+        assert_eq!(ratings[&0], initial[&0]);
+        assert_eq!(ratings[&1], initial[&1]);
+        assert!(ratings[&4].mu() > initial[&4].mu());
+        assert!(ratings[&3].mu() < initial[&3].mu());
+
+        // The bye's winner may be reported, but need not be.
+        bracket.set_result(0, 0, 0).unwrap();
+        bracket.set_result(1, 0, 0).unwrap();
+        let mut with_byes = field(6);
+        bracket.apply(&rater, &mut with_byes).unwrap();
+        assert!(with_byes[&0].mu() > initial[&0].mu());
+        assert_eq!(with_byes[&1], initial[&1]);
+    }
+
+    #[test]
+    fn inconsistent_results_change_nothing() {
+        let rater = Rater::default();
+        let mut bracket = Bracket::new((0..4).collect()).unwrap();
+        bracket.set_result(0, 0, 3).unwrap();
+        bracket.set_result(0, 1, 1).unwrap();
+        // 0 lost to 3 in the first round.
+        bracket.set_result(1, 0, 0).unwrap();
+
+        let mut ratings = field(4);
+        assert_eq!(
+            bracket.apply(&rater, &mut ratings),
+            Err(BracketError::InconsistentResult {
+                round: 1,
+                match_idx: 0
+            })
+        );
+        assert_eq!(ratings, field(4));
+
+        let mut early = Bracket::new((0..4).collect()).unwrap();
+        early.set_result(0, 0, 0).unwrap();
+        early.set_result(1, 0, 0).unwrap();
+        assert_eq!(
+            early.apply(&rater, &mut ratings),
+            Err(BracketError::MissingResult {
+                round: 0,
+                match_idx: 1
+            })
+        );
+        assert_eq!(ratings, field(4));
+    }
+
+    #[test]
+    fn invalid_brackets_and_matches_are_rejected() {
+        assert_eq!(
+            Bracket::new(vec!["ann"]).unwrap_err(),
+            BracketError::TooFewEntrants
+        );
+        assert_eq!(
+            Bracket::new(vec!["ann", "bob", "ann"]).unwrap_err(),
+            BracketError::DuplicateEntrant { seed: 2 }
+        );
+
+        let mut bracket = Bracket::new(vec!["ann", "bob", "cid"]).unwrap();
+        assert_eq!(
+            bracket.set_result(1, 1, "ann"),
+            Err(BracketError::NoSuchMatch {
+                round: 1,
+                match_idx: 1
+            })
+        );
+        assert_eq!(
+            bracket.set_result(0, 1, "dan"),
+            Err(BracketError::UnknownEntrant {
+                round: 0,
+                match_idx: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "tracing")]
+extern crate bbt;
+extern crate tracing;
+extern crate tracing_subscriber;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::Attributes;
+use tracing::{Event, Id, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+use bbt::{BBTError, GameMut, Outcome, Rater, Rating};
+
+/// A span or event, with its fields formatted as strings.
+#[derive(Debug)]
+struct Record {
+    span: bool,
+    name: String,
+    level: Level,
+    fields: BTreeMap<String, String>,
+}
+
+impl Record {
+    fn field(&self, name: &str) -> &str {
+        self.fields
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+impl<'a> Visit for Fields<'a> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// Captures every span and event.
+struct Capture(Arc<Mutex<Vec<Record>>>);
+
+impl<S: Subscriber> Layer<S> for Capture {
+    fn on_new_span(&self, attrs: &Attributes, _: &Id, _: Context<S>) {
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut Fields(&mut fields));
+
+        self.0.lock().unwrap().push(Record {
+            span: true,
+            name: attrs.metadata().name().to_string(),
+            level: *attrs.metadata().level(),
+            fields,
+        });
+    }
+
+    fn on_event(&self, event: &Event, _: Context<S>) {
+        let mut fields = BTreeMap::new();
+        event.record(&mut Fields(&mut fields));
+
+        self.0.lock().unwrap().push(Record {
+            span: false,
+            name: event.metadata().name().to_string(),
+            level: *event.metadata().level(),
+            fields,
+        });
+    }
+}
+
+/// Runs `f` and returns the spans and events it emitted.
+fn capture<R, G: FnOnce() -> R>(f: G) -> Vec<Record> {
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = tracing_subscriber::registry().with(Capture(records.clone()));
+
+    tracing::subscriber::with_default(subscriber, f);
+
+    let mut records = records.lock().unwrap();
+    records.drain(..).collect()
+}
+
+#[test]
+fn updates_open_a_span_without_warnings() {
+    let records = capture(|| {
+        Rater::default()
+            .update_ratings(
+                vec![
+                    vec![Rating::default(), Rating::default()],
+                    vec![Rating::default()],
+                ],
+                vec![1, 2],
+            )
+            .unwrap()
+    });
+
+    assert_eq!(records.len(), 1, "{:?}", records);
+    let span = &records[0];
+    assert!(span.span);
+    assert_eq!(span.name, "update_ratings");
+    assert_eq!(span.level, Level::DEBUG);
+    assert_eq!(span.field("teams"), "2");
+    assert_eq!(span.field("players"), "3");
+}
+
+#[test]
+fn clamps_are_warnings() {
+    // A very uncertain player facing many near-certain opponents would lose
+    // more than all of their variance without the clamp.
+    let mut teams = vec![vec![Rating::new(25.0, 100.0)]];
+    teams.extend(vec![vec![Rating::new(25.0, 0.01)]; 5]);
+
+    let records = capture(|| {
+        Rater::new(0.01)
+            .update_ratings(teams, vec![1, 2, 3, 4, 5, 6])
+            .unwrap()
+    });
+
+    let warnings: Vec<&Record> = records
+        .iter()
+        .filter(|record| !record.span && record.level == Level::WARN)
+        .collect();
+    assert_eq!(warnings.len(), 1, "{:?}", records);
+    assert_eq!(
+        warnings[0].field("message"),
+        "variance reduction clamped to the sigma floor"
+    );
+    assert_eq!(warnings[0].field("team"), "0");
+    assert_eq!(warnings[0].field("player"), "0");
+    assert_eq!(warnings[0].field("sigma"), "1");
+}
+
+#[test]
+fn rejected_matches_are_debug_events() {
+    let records = capture(|| {
+        Rater::default().update_ratings(vec![vec![Rating::default()], vec![]], vec![1, 2])
+    });
+
+    let event = records.iter().find(|record| !record.span).unwrap();
+    assert_eq!(event.level, Level::DEBUG);
+    assert_eq!(event.field("message"), "rejected match");
+    assert_eq!(
+        event.field("error"),
+        BBTError::EmptyTeam { index: 1 }.to_string()
+    );
+    assert_eq!(event.field("teams"), "2");
+}
+
+#[test]
+fn duels_and_batches_open_spans() {
+    let records = capture(|| {
+        let rater = Rater::default();
+        rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+
+        let mut teams = vec![vec![Rating::default()], vec![Rating::default()]];
+        let mut batch = [GameMut {
+            teams: &mut teams,
+            ranks: &[1, 2],
+        }];
+        rater.update_batch(&mut batch).unwrap();
+    });
+
+    let spans: Vec<&str> = records
+        .iter()
+        .filter(|record| record.span)
+        .map(|record| record.name.as_str())
+        .collect();
+    assert_eq!(spans, vec!["duel", "update_batch"]);
+    assert_eq!(records[1].field("games"), "1");
+}
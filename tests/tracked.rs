@@ -0,0 +1,204 @@
+extern crate bbt;
+#[cfg(feature = "serde")]
+extern crate bincode;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use bbt::{Outcome, Rater, Rating, TrackedRating};
+
+#[test]
+fn duels_are_counted_for_both_players() {
+    let rater = Rater::default();
+    let mut alice = TrackedRating::default();
+    let mut bob = TrackedRating::with_games(Rating::new(30.0, 2.0), 41);
+
+    rater.duel_tracked(&mut alice, &mut bob, Outcome::Draw);
+    rater.duel_tracked(&mut alice, &mut bob, Outcome::Loss);
+
+    assert_eq!(alice.games_played(), 2);
+    assert_eq!(bob.games_played(), 43);
+
+    // The ratings are those of plain duels.
+    let (a, b) = rater.duel(Rating::default(), Rating::new(30.0, 2.0), Outcome::Draw);
+    let (a, b) = rater.duel(a, b, Outcome::Loss);
+    assert_eq!(alice.rating(), a);
+    assert_eq!(bob.rating(), b);
+}
+
+#[test]
+fn invalid_duels_are_not_counted() {
+    let mut alice = TrackedRating::default();
+    let mut broken = TrackedRating::new(Rating::new(f64::NAN, 1.0));
+
+    Rater::default().duel_tracked(&mut alice, &mut broken, Outcome::Win);
+
+    assert_eq!(alice, TrackedRating::default());
+    assert_eq!(broken.games_played(), 0);
+}
+
+#[test]
+fn protected_players_keep_their_mu_over_a_losing_streak() {
+    let protected = Rater::default().with_loss_protection(5);
+    let plain = Rater::default();
+
+    let mut newcomer = TrackedRating::default();
+    let mut opponent = TrackedRating::with_games(Rating::new(20.0, 4.0), 100);
+    let mut plain_newcomer = newcomer;
+    let mut plain_opponent = opponent;
+
+    for _ in 0..5 {
+        let before = newcomer;
+        protected.duel_tracked(&mut newcomer, &mut opponent, Outcome::Loss);
+        plain.duel_tracked(&mut plain_newcomer, &mut plain_opponent, Outcome::Loss);
+
+        assert!(newcomer.mu() >= before.mu());
+        assert!(newcomer.sigma() < before.sigma());
+        assert_eq!(newcomer.sigma(), plain_newcomer.sigma());
+
+        // The opponent gains as much as against an unprotected player.
+        assert_eq!(opponent, plain_opponent);
+
+        // Keep the streaks comparable.
+        plain_newcomer = newcomer;
+        plain_opponent = opponent;
+    }
+
+    // Protection has expired after five games.
+    let before = newcomer;
+    protected.duel_tracked(&mut newcomer, &mut opponent, Outcome::Loss);
+    let (expected, _) = plain.duel(before.rating(), plain_opponent.rating(), Outcome::Loss);
+    assert_eq!(newcomer.rating(), expected);
+    assert!(newcomer.mu() < before.mu());
+}
+
+#[test]
+fn protected_players_still_gain_from_wins() {
+    let rater = Rater::default().with_loss_protection(3);
+    let mut newcomer = TrackedRating::default();
+    let mut opponent = TrackedRating::with_games(Rating::default(), 3);
+
+    rater.duel_tracked(&mut newcomer, &mut opponent, Outcome::Win);
+
+    let (winner, loser) = rater.duel(Rating::default(), Rating::default(), Outcome::Win);
+    assert_eq!(newcomer.rating(), winner);
+    assert_eq!(opponent.rating(), loser);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn every_player_of_a_match_is_counted_once() {
+    let rater = Rater::default();
+    let mut teams = vec![
+        vec![TrackedRating::default(); 2],
+        vec![TrackedRating::with_games(Rating::new(20.0, 5.0), 7)],
+        vec![TrackedRating::default(); 3],
+    ];
+
+    rater
+        .update_tracked_ratings(&mut teams, &[2, 1, 3])
+        .unwrap();
+
+    let games: Vec<Vec<u32>> = teams
+        .iter()
+        .map(|team| team.iter().map(|p| p.games_played()).collect())
+        .collect();
+    assert_eq!(games, vec![vec![1, 1], vec![8], vec![1, 1, 1]]);
+
+    let plain = rater
+        .update_ratings(
+            vec![
+                vec![Rating::default(); 2],
+                vec![Rating::new(20.0, 5.0)],
+                vec![Rating::default(); 3],
+            ],
+            vec![2, 1, 3],
+        )
+        .unwrap();
+    for (team, plain) in teams.iter().zip(&plain) {
+        for (tracked, plain) in team.iter().zip(plain) {
+            assert_eq!(tracked.rating(), *plain);
+        }
+    }
+
+    // A rejected match changes nothing.
+    let before = teams.clone();
+    assert!(rater.update_tracked_ratings(&mut teams, &[1, 2]).is_err());
+    assert_eq!(teams, before);
+}
+
+#[test]
+fn tracked_ratings_work_where_ratings_are_expected() {
+    let rater = Rater::default();
+    let tracked = TrackedRating::with_games(Rating::new(30.0, 3.0), 12);
+
+    // Through `Deref`.
+    assert_eq!(tracked.mu(), 30.0);
+    assert_eq!(
+        rater.win_probability(&tracked, &Rating::default()),
+        rater.win_probability(&Rating::new(30.0, 3.0), &Rating::default())
+    );
+
+    let rating: Rating = tracked.into();
+    assert_eq!(rating, Rating::new(30.0, 3.0));
+    assert_eq!(TrackedRating::from(rating).games_played(), 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn the_counter_is_serialized() {
+    let tracked = TrackedRating::with_games(Rating::new(27.5, 4.0), 12);
+
+    let json = serde_json::to_string(&tracked).unwrap();
+    assert_eq!(json, r#"{"mu":27.5,"sigma":4.0,"games":12}"#);
+    assert_eq!(
+        serde_json::from_str::<TrackedRating>(&json).unwrap(),
+        tracked
+    );
+
+    let binary = bincode::serialize(&tracked).unwrap();
+    assert_eq!(binary.len(), 20);
+    assert_eq!(
+        bincode::deserialize::<TrackedRating>(&binary).unwrap(),
+        tracked
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn stored_ratings_read_as_new_tracked_ratings() {
+    let legacy = serde_json::to_string(&Rating::new(27.5, 4.0)).unwrap();
+
+    let tracked: TrackedRating = serde_json::from_str(&legacy).unwrap();
+    assert_eq!(tracked, TrackedRating::new(Rating::new(27.5, 4.0)));
+    assert_eq!(tracked.games_played(), 0);
+
+    assert!(serde_json::from_str::<TrackedRating>(r#"{"mu":27.5,"games":1}"#).is_err());
+    assert!(serde_json::from_str::<TrackedRating>(r#"{"mu":27.5,"sigma":-1,"games":1}"#).is_err());
+    assert!(serde_json::from_str::<TrackedRating>(r#"{"mu":27.5,"sigma":4,"games":-1}"#).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn loss_protection_applies_to_matches() {
+    let rater = Rater::default().with_loss_protection(1);
+    let mut teams = vec![
+        vec![
+            TrackedRating::default(),
+            TrackedRating::with_games(Rating::default(), 1),
+        ],
+        vec![TrackedRating::with_games(Rating::default(), 1)],
+    ];
+
+    rater.update_tracked_ratings(&mut teams, &[2, 1]).unwrap();
+
+    let plain = Rater::default()
+        .update_ratings(
+            vec![vec![Rating::default(); 2], vec![Rating::default()]],
+            vec![2, 1],
+        )
+        .unwrap();
+    assert_eq!(teams[0][0].mu(), 25.0);
+    assert_eq!(teams[0][0].sigma(), plain[0][0].sigma());
+    assert_eq!(teams[0][1].rating(), plain[0][1]);
+    assert_eq!(teams[1][0].rating(), plain[1][0]);
+}
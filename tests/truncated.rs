@@ -0,0 +1,99 @@
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating};
+
+#[test]
+fn ratings_far_from_the_bound_are_virtually_unaffected() {
+    let rating: Rating = Rating::new(25.0, 25.0 / 3.0);
+
+    let truncated = rating.truncated(-50.0);
+    assert!((truncated.mu() - rating.mu()).abs() < 1e-9);
+    assert!((truncated.sigma() - rating.sigma()).abs() < 1e-9);
+    assert!((rating.truncated_mean(-50.0) - 25.0).abs() < 1e-9);
+
+    // Updates far from the bound are those of a plain rater.
+    let truncating = Rater::default().with_truncation(-25.0);
+    let plain = Rater::default();
+    let (a, b) = (Rating::new(30.0, 2.0), Rating::new(25.0, 3.0));
+    let (ta, tb) = truncating.duel(a, b, Outcome::Loss);
+    let (pa, pb) = plain.duel(a, b, Outcome::Loss);
+    for &(t, p) in &[(ta, pa), (tb, pb)] {
+        assert!((t.mu() - p.mu()).abs() < 1e-9);
+        assert!((t.sigma() - p.sigma()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn ratings_straddling_the_bound_move_up_and_tighten() {
+    let rating: Rating = Rating::new(1.0, 4.0);
+    let truncated = rating.truncated(0.0);
+
+    assert!(truncated.mu() > rating.mu());
+    assert!(truncated.sigma() < rating.sigma());
+
+    // The moments of the standard normal distribution truncated at zero.
+    let standard = Rating::new(0.0, 1.0).truncated(0.0);
+    let half_mean = (2.0 / std::f64::consts::PI).sqrt();
+    assert!((standard.mu() - half_mean).abs() < 1e-6);
+    assert!((standard.sigma() - (1.0 - half_mean * half_mean).sqrt()).abs() < 1e-6);
+
+    // Ratings far below the bound end up just above it.
+    let buried = Rating::new(-50.0, 5.0).truncated(0.0);
+    assert!(buried.mu() > 0.0 && buried.mu() < 0.6);
+    assert!(buried.sigma() > 0.0 && buried.sigma() < 0.6);
+
+    let (low, high) = rating.truncated_interval(0.0, 3.0);
+    assert_eq!(low, 0.0);
+    assert!((high - (truncated.mu() + 3.0 * truncated.sigma())).abs() < 1e-12);
+
+    // Updated ratings near the bound are those of the truncated update.
+    let truncating = Rater::default().with_truncation(0.0);
+    let (updated, _) = truncating.duel(rating, Rating::new(10.0, 2.0), Outcome::Loss);
+    let (plain, _) = Rater::default().duel(rating, Rating::new(10.0, 2.0), Outcome::Loss);
+    assert_eq!(updated, plain.truncated(0.0));
+    assert!(updated.mu() > plain.mu());
+    assert!(updated.sigma() < plain.sigma());
+}
+
+#[test]
+fn heavy_losses_asymptote_above_the_bound() {
+    let truncating = Rater::default().with_truncation(0.0);
+    let plain = Rater::default();
+    let strong = Rating::new(40.0, 1.0);
+
+    let mut truncated = Rating::new(5.0, 4.0);
+    let mut untruncated = truncated;
+    let mut history = Vec::new();
+
+    for _ in 0..200 {
+        truncated = truncating.duel(truncated, strong, Outcome::Loss).0;
+        untruncated = plain.duel(untruncated, strong, Outcome::Loss).0;
+
+        assert!(truncated.mu() > 0.0);
+        assert!(truncated.sigma() > 0.0);
+        history.push(truncated.mu());
+    }
+
+    assert!(truncated.mu() > untruncated.mu());
+
+    // The truncated rating has settled.
+    let last = history[history.len() - 1];
+    assert!((history[history.len() - 20] - last).abs() < 0.1);
+
+    let teams = vec![vec![Rating::new(0.5, 2.0)], vec![strong]];
+    let updated = truncating.update_ratings(teams, vec![1, 0]).unwrap();
+    assert!(updated[0][0].mu() > 0.0);
+}
+
+#[test]
+fn truncation_runs_before_the_policy() {
+    // The policy sees the truncated rating, and may move it below the bound.
+    let rater = Rater::default()
+        .with_truncation(0.0)
+        .with_policy(|_: &Rating, new: &Rating| Rating::new(new.mu() - 10.0, new.sigma()));
+
+    let (a, b) = (Rating::new(2.5, 3.0), Rating::new(30.0, 1.0));
+    let (loser, _) = rater.duel(a, b, Outcome::Loss);
+    let (plain, _) = Rater::default().duel(a, b, Outcome::Loss);
+    assert_eq!(loser.mu(), plain.truncated(0.0).mu() - 10.0);
+}
@@ -0,0 +1,119 @@
+extern crate bbt;
+
+use bbt::validate::{self, InvariantViolation};
+use bbt::{Outcome, Rater, Rating};
+
+#[test]
+fn check_ratings_finds_the_first_broken_rating() {
+    assert_eq!(
+        validate::check_ratings(&[Rating::new(25.0, 8.0), Rating::new(-3.0, 0.1)]),
+        Ok(())
+    );
+    assert_eq!(validate::check_ratings::<f64>(&[]), Ok(()));
+
+    let broken = [
+        (
+            Rating::new(f64::NAN, 8.0),
+            InvariantViolation::NonFiniteMu { index: 1 },
+        ),
+        (
+            Rating::new(f64::INFINITY, 8.0),
+            InvariantViolation::NonFiniteMu { index: 1 },
+        ),
+        (
+            Rating::new(25.0, f64::NAN),
+            InvariantViolation::InvalidSigma { index: 1 },
+        ),
+        (
+            Rating::new(25.0, f64::INFINITY),
+            InvariantViolation::InvalidSigma { index: 1 },
+        ),
+        (
+            Rating::new(25.0, -1.0),
+            InvariantViolation::InvalidSigma { index: 1 },
+        ),
+        (
+            Rating::new(25.0, 0.0),
+            InvariantViolation::InvalidSigma { index: 1 },
+        ),
+    ];
+
+    for &(rating, violation) in &broken {
+        let ratings = [Rating::default(), rating, Rating::new(f64::NAN, f64::NAN)];
+        assert_eq!(validate::check_ratings(&ratings), Err(violation));
+    }
+
+    assert_eq!(
+        InvariantViolation::InvalidSigma { index: 4 }.to_string(),
+        "The rating at index 4 has a sigma that is not positive and finite"
+    );
+}
+
+#[test]
+fn updates_keep_the_invariants() {
+    // A small beta makes every game decisive, so the sigmas run into the
+    // floor; the checks of the `validate` feature must not fire on that.
+    let rater = Rater::new(0.01);
+    let mut players = vec![
+        Rating::new(25.0, 8.0),
+        Rating::new(25.0, 0.5),
+        Rating::default(),
+    ];
+
+    for game in 0..50 {
+        let (a, b) = (game % 3, (game + 1) % 3);
+        let (winner, loser) = rater.duel(players[a], players[b], Outcome::Win);
+        assert!(winner.sigma() <= players[a].sigma() && loser.sigma() <= players[b].sigma());
+        players[a] = winner;
+        players[b] = loser;
+
+        let teams = vec![vec![players[0], players[1]], vec![players[2]]];
+        let updated = rater
+            .update_ratings(teams, vec![1 + game % 2, 2 - game % 2])
+            .unwrap();
+        players = vec![updated[0][0], updated[0][1], updated[1][0]];
+
+        assert_eq!(validate::check_ratings(&players), Ok(()));
+    }
+}
+
+/// Skips the validation of `update_ratings` to get a NaN into the update.
+#[cfg(feature = "alloc")]
+fn update_with_nan() -> Vec<Vec<Rating>> {
+    let teams = vec![vec![Rating::default()], vec![Rating::new(f64::NAN, 3.0)]];
+    Rater::default().update_ratings_unchecked(teams, vec![1, 2])
+}
+
+#[cfg(all(feature = "alloc", feature = "validate"))]
+#[test]
+fn broken_invariants_panic_with_the_update() {
+    let panic = std::panic::catch_unwind(update_with_nan).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.contains("bbt invariant violated"), "{}", message);
+    // Depending on the logistic, the NaN either spreads to the omega of the
+    // other team or only breaks the rating of its player.
+    assert!(
+        message.contains("the omega of team 0 is not finite")
+            || message.contains("update of player 0 of team 1: its mu is not finite"),
+        "{}",
+        message
+    );
+    assert!(
+        message.contains("team 1: mu = NaN, sigma² = 9.0"),
+        "{}",
+        message
+    );
+}
+
+#[cfg(all(feature = "alloc", not(feature = "validate")))]
+#[test]
+fn checks_are_compiled_away_without_the_feature() {
+    let updated = update_with_nan();
+
+    assert!(updated[0][0].mu().is_nan());
+    assert_eq!(
+        validate::check_ratings(&updated[0]),
+        Err(InvariantViolation::NonFiniteMu { index: 0 })
+    );
+}
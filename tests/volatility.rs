@@ -0,0 +1,129 @@
+extern crate bbt;
+
+use bbt::{Outcome, Rater, Rating};
+
+#[test]
+fn repeated_upsets_move_converged_ratings_faster() {
+    let plain = Rater::default();
+    let volatile = Rater::default().with_volatility(2.0, 1.0);
+
+    let converged = Rating::new(30.0, 1.0);
+    let weaker = Rating::new(20.0, 1.0);
+
+    let (mut slow, mut slow_weaker) = (converged, weaker);
+    let (mut fast, mut fast_weaker) = (converged, weaker);
+
+    for _ in 0..10 {
+        let (a, b) = plain.duel(slow, slow_weaker, Outcome::Loss);
+        slow = a;
+        slow_weaker = b;
+
+        let (a, b) = volatile.duel(fast, fast_weaker, Outcome::Loss);
+        fast = a;
+        fast_weaker = b;
+    }
+
+    let slow_drop = converged.mu() - slow.mu();
+    let fast_drop = converged.mu() - fast.mu();
+    assert!(
+        fast_drop > 2.0 * slow_drop,
+        "{} vs {}",
+        fast_drop,
+        slow_drop
+    );
+    assert!(fast.sigma() > slow.sigma());
+}
+
+#[test]
+fn expected_results_never_inflate() {
+    let plain = Rater::default();
+    let volatile = Rater::default().with_volatility(1.0, 5.0);
+
+    let favourite = Rating::new(35.0, 2.0);
+    let underdog = Rating::new(15.0, 2.0);
+
+    for &outcome in &[Outcome::Win, Outcome::Draw] {
+        assert_eq!(
+            volatile.duel(favourite, underdog, outcome),
+            plain.duel(favourite, underdog, outcome)
+        );
+    }
+
+    let teams = vec![
+        vec![Rating::new(35.0, 2.0), Rating::new(30.0, 2.0)],
+        vec![Rating::new(20.0, 2.0)],
+        vec![Rating::new(10.0, 2.0), Rating::new(5.0, 2.0)],
+    ];
+    assert_eq!(
+        volatile.update_ratings(teams.clone(), vec![1, 2, 3]),
+        plain.update_ratings(teams.clone(), vec![1, 2, 3])
+    );
+    assert_eq!(
+        volatile.update_ratings(teams.clone(), vec![1, 1, 1]),
+        plain.update_ratings(teams, vec![1, 1, 1])
+    );
+}
+
+// With the `simd` feature, `update_ratings` no longer uses the scalar path
+// that `duel` mirrors exactly.
+#[cfg(not(feature = "simd"))]
+#[test]
+fn duels_agree_with_update_ratings() {
+    let rater = Rater::default().with_volatility(2.0, 3.0);
+    let favourite = Rating::new(32.0, 1.5);
+    let underdog = Rating::new(22.0, 2.5);
+
+    for &(outcome, ranks) in &[
+        (Outcome::Win, [1, 2]),
+        (Outcome::Loss, [2, 1]),
+        (Outcome::Draw, [1, 1]),
+    ] {
+        let (a, b) = rater.duel(favourite, underdog, outcome);
+        let updated = rater
+            .update_ratings(vec![vec![favourite], vec![underdog]], ranks.to_vec())
+            .unwrap();
+        assert_eq!(updated, vec![vec![a], vec![b]]);
+    }
+
+    // The upset was inflated, unlike the other way round.
+    let (upset, _) = rater.duel(favourite, underdog, Outcome::Loss);
+    let (plain, _) = Rater::default().duel(favourite, underdog, Outcome::Loss);
+    assert!(upset.mu() < plain.mu());
+}
+
+#[test]
+fn certain_players_are_not_inflated() {
+    let rater = Rater::default().with_volatility(1.0, 10.0);
+    let certain = Rating::new(40.0, 0.0);
+
+    let (after, _) = rater.duel(certain, Rating::new(10.0, 1.0), Outcome::Loss);
+    assert_eq!(after, certain);
+}
+
+// With the `simd` feature, `update_ratings` no longer uses the scalar path
+// that the windowed update shares.
+#[cfg(not(feature = "simd"))]
+#[test]
+fn windowed_updates_are_inflated_like_full_updates() {
+    let rater = Rater::default().with_volatility(2.0, 3.0);
+    let teams: Vec<Vec<Rating>> = (0..6)
+        .map(|i| vec![Rating::new(20.0 + 3.0 * i as f64, 1.5)])
+        .collect();
+    // The weakest team wins, which surprises it and the teams it beat.
+    let ranks = vec![1, 6, 5, 4, 3, 2];
+
+    let full = rater.update_ratings(teams.clone(), ranks.clone()).unwrap();
+    let largest_window = rater
+        .update_ratings_windowed(teams.clone(), ranks.clone(), teams.len() - 1)
+        .unwrap();
+    assert_eq!(largest_window, full);
+
+    let narrow = rater
+        .update_ratings_windowed(teams.clone(), ranks.clone(), 1)
+        .unwrap();
+    let plain = Rater::default()
+        .update_ratings_windowed(teams.clone(), ranks, 1)
+        .unwrap();
+    assert!(narrow[0][0].mu() > plain[0][0].mu());
+    assert!(narrow[0][0].sigma() > plain[0][0].sigma());
+}
@@ -0,0 +1,109 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+extern crate bbt;
+extern crate js_sys;
+extern crate wasm_bindgen;
+extern crate wasm_bindgen_test;
+
+use bbt::wasm::{JsRater, JsRating};
+use bbt::BBTError;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+// The expected values are those of the native `Rater::duel` and
+// `Rater::update_ratings` for the same inputs. They are compared with a
+// tolerance, because `exp` can differ in the last bits between platforms.
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-12,
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[wasm_bindgen_test]
+fn duel_matches_native() {
+    let rater = JsRater::new(25.0 / 6.0).unwrap();
+    let p1 = JsRating::new(25.0, 25.0 / 3.0);
+    let p2 = JsRating::new(20.0, 4.0);
+
+    let updated = rater.duel(&p1, &p2, "win").unwrap();
+
+    assert_close(updated[0].mu(), 27.45738419476057);
+    assert_close(updated[0].sigma(), 7.886742423664679);
+    assert_close(updated[1].mu(), 19.433818681527164);
+    assert_close(updated[1].sigma(), 3.976861409651893);
+
+    assert_close(rater.win_probability(&p1, &p2), 0.6120930507467504);
+    assert_close(
+        updated[1].conservative_estimate(),
+        19.433818681527164 - 3.0 * 3.976861409651893,
+    );
+}
+
+#[wasm_bindgen_test]
+fn duel_rejects_unknown_outcomes() {
+    let rater = JsRater::new(25.0 / 6.0).unwrap();
+    let p = JsRating::new(25.0, 25.0 / 3.0);
+
+    assert!(rater.duel(&p, &p, "tie").is_err());
+}
+
+fn player(mu: f64, sigma: f64) -> JsValue {
+    let object = Object::new();
+    Reflect::set(&object, &"mu".into(), &mu.into()).unwrap();
+    Reflect::set(&object, &"sigma".into(), &sigma.into()).unwrap();
+    object.into()
+}
+
+#[wasm_bindgen_test]
+fn four_team_update_matches_native() {
+    let rater = JsRater::new(25.0 / 6.0).unwrap();
+
+    let teams = Array::of4(
+        &Array::of1(&JsRating::new(25.0, 25.0 / 3.0).into()),
+        &Array::of2(&player(30.0, 4.0), &player(20.0, 6.0)),
+        &Array::of1(&player(22.0, 3.0)),
+        &Array::of1(&player(28.0, 7.0)),
+    );
+
+    let updated = rater.update_ratings(teams, vec![1, 2, 2, 4]).unwrap();
+
+    let expected = [
+        [(35.84569571071239, 7.3401891539847925)].to_vec(),
+        [
+            (28.323450045852333, 3.958109295842426),
+            (16.227762603167747, 5.857671065708262),
+        ]
+        .to_vec(),
+        [(22.654836342603943, 2.9791634732795362)].to_vec(),
+        [(21.916491253588205, 6.428454040110296)].to_vec(),
+    ];
+
+    assert_eq!(updated.length(), 4);
+    for (team, expected) in updated.iter().zip(&expected) {
+        let team = Array::from(&team);
+        assert_eq!(team.length() as usize, expected.len());
+
+        for (player, &(mu, sigma)) in team.iter().zip(expected) {
+            let get = |name: &str| Reflect::get(&player, &name.into()).unwrap().as_f64();
+            assert_close(get("mu").unwrap(), mu);
+            assert_close(get("sigma").unwrap(), sigma);
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn update_errors_are_exceptions() {
+    let rater = JsRater::new(25.0 / 6.0).unwrap();
+    let teams = Array::of2(&Array::of1(&player(25.0, 8.0)), &Array::new());
+
+    let error = JsValue::from(rater.update_ratings(teams, vec![1, 2]).unwrap_err());
+    assert_eq!(
+        Reflect::get(&error, &"message".into()).unwrap(),
+        BBTError::EmptyTeam { index: 1 }.to_string()
+    );
+    assert!(JsRater::new(f64::NAN).is_err());
+}